@@ -161,6 +161,7 @@ impl pallet_utility::Config for TestRuntime {
 impl pallet_balances::Config for TestRuntime {
 	type ReserveIdentifier = [u8; 8];
 	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
 }
 
 #[derive_impl(pallet_transaction_payment::config_preludes::TestDefaultConfig)]
@@ -70,6 +70,7 @@ impl frame_system::Config for TestRuntime {
 impl pallet_balances::Config for TestRuntime {
 	type ReserveIdentifier = [u8; 8];
 	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
 }
 
 impl pallet_bridge_relayers::Config for TestRuntime {
@@ -75,6 +75,7 @@ impl frame_system::Config for TestRuntime {
 #[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
 impl pallet_balances::Config for TestRuntime {
 	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
 }
 
 parameter_types! {
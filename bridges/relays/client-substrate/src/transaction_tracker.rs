@@ -213,7 +213,8 @@ async fn watch_transaction_status<
 			},
 			Some(TransactionStatusOf::<C>::Future) |
 			Some(TransactionStatusOf::<C>::Ready) |
-			Some(TransactionStatusOf::<C>::Broadcast(_)) => {
+			Some(TransactionStatusOf::<C>::Broadcast(_)) |
+			Some(TransactionStatusOf::<C>::WatchingDegraded) => {
 				// nothing important (for us) has happened
 			},
 			Some(TransactionStatusOf::<C>::InBlock(block_hash)) => {
@@ -228,13 +229,13 @@ async fn watch_transaction_status<
 					block_hash,
 				);
 			},
-			Some(TransactionStatusOf::<C>::Retracted(block_hash)) => {
+			Some(TransactionStatusOf::<C>::Retracted(retracted)) => {
 				log::trace!(
 					target: "bridge",
 					"{} transaction {:?} at block {:?} has been retracted",
 					C::NAME,
 					transaction_hash,
-					block_hash,
+					retracted.hash,
 				);
 			},
 			Some(TransactionStatusOf::<C>::FinalityTimeout(block_hash)) => {
@@ -62,11 +62,22 @@ impl pallet_balances::Config for Test {
 	type DustRemoval = ();
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
 	type WeightInfo = ();
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
 	type RuntimeHoldReason = ();
 	type RuntimeFreezeReason = ();
+	type ResidueQuantum = ();
+	type ResidueThreshold = ();
+	type MaxExpiringPerBlock = ();
+	type MinTransferAmount = ();
+	type LockToFreezeMapping = ();
+	type ManagedFreezes = ();
+	type BurnableHolds = ();
+	type LenientFreezeBound = ();
+	type DoneSlashHandler = ();
+	type OnInconsistency = ();
 }
 
 parameter_types! {
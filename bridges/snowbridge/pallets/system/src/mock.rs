@@ -121,11 +121,22 @@ impl pallet_balances::Config for Test {
 	type DustRemoval = ();
 	type ExistentialDeposit = ConstU128<1>;
 	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
 	type WeightInfo = ();
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
 	type RuntimeHoldReason = ();
 	type RuntimeFreezeReason = ();
+	type ResidueQuantum = ();
+	type ResidueThreshold = ();
+	type MaxExpiringPerBlock = ();
+	type MinTransferAmount = ();
+	type LockToFreezeMapping = ();
+	type ManagedFreezes = ();
+	type BurnableHolds = ();
+	type LenientFreezeBound = ();
+	type DoneSlashHandler = ();
+	type OnInconsistency = ();
 }
 
 impl pallet_xcm_origin::Config for Test {
@@ -62,6 +62,7 @@ impl pallet_balances::Config for Test {
 	type DustRemoval = ();
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
 	type WeightInfo = ();
 	type MaxLocks = ();
 	type MaxReserves = MaxReserves;
@@ -70,6 +71,16 @@ impl pallet_balances::Config for Test {
 	type RuntimeFreezeReason = RuntimeFreezeReason;
 	type FreezeIdentifier = ();
 	type MaxFreezes = ConstU32<0>;
+	type ResidueQuantum = ();
+	type ResidueThreshold = ();
+	type MaxExpiringPerBlock = ();
+	type MinTransferAmount = ();
+	type LockToFreezeMapping = ();
+	type ManagedFreezes = ();
+	type BurnableHolds = ();
+	type LenientFreezeBound = ();
+	type DoneSlashHandler = ();
+	type OnInconsistency = ();
 }
 
 pub struct Author4;
@@ -199,7 +210,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 		invulnerables,
 	};
 	let session = pallet_session::GenesisConfig::<Test> { keys };
-	pallet_balances::GenesisConfig::<Test> { balances }
+	pallet_balances::GenesisConfig::<Test> { balances, ..Default::default() }
 		.assimilate_storage(&mut t)
 		.unwrap();
 	// collator selection must be initialized before session.
@@ -96,6 +96,7 @@ impl pallet_balances::Config for Test {
 	type DustRemoval = ();
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
 	type WeightInfo = ();
 	type MaxLocks = ();
 	type MaxReserves = MaxReserves;
@@ -104,6 +105,16 @@ impl pallet_balances::Config for Test {
 	type RuntimeFreezeReason = RuntimeFreezeReason;
 	type FreezeIdentifier = ();
 	type MaxFreezes = ConstU32<0>;
+	type ResidueQuantum = ();
+	type ResidueThreshold = ();
+	type MaxExpiringPerBlock = ();
+	type MinTransferAmount = ();
+	type LockToFreezeMapping = ();
+	type ManagedFreezes = ();
+	type BurnableHolds = ();
+	type LenientFreezeBound = ();
+	type DoneSlashHandler = ();
+	type OnInconsistency = ();
 }
 
 impl cumulus_pallet_parachain_system::Config for Test {
@@ -259,6 +259,7 @@ mod tests {
 		type DustRemoval = ();
 		type ExistentialDeposit = ConstU64<1>;
 		type AccountStore = System;
+		type AuthoritativeStore = pallet_balances::SystemStore;
 		type MaxLocks = ();
 		type WeightInfo = ();
 		type MaxReserves = MaxReserves;
@@ -267,6 +268,16 @@ mod tests {
 		type RuntimeFreezeReason = RuntimeFreezeReason;
 		type FreezeIdentifier = ();
 		type MaxFreezes = ConstU32<1>;
+		type ResidueQuantum = ();
+		type ResidueThreshold = ();
+		type MaxExpiringPerBlock = ();
+		type MinTransferAmount = ();
+		type LockToFreezeMapping = ();
+		type ManagedFreezes = ();
+		type BurnableHolds = ();
+		type LenientFreezeBound = ();
+		type DoneSlashHandler = ();
+		type OnInconsistency = ();
 	}
 
 	pub struct OneAuthor;
@@ -16,7 +16,7 @@
 pub use paste;
 
 // Substrate
-pub use frame_support::{pallet_prelude::Weight, weights::WeightToFee};
+pub use frame_support::{pallet_prelude::Weight, traits::Get, weights::WeightToFee};
 pub use pallet_assets;
 pub use pallet_balances;
 pub use pallet_message_queue;
@@ -25,18 +25,19 @@ pub use pallet_xcm;
 // Polkadot
 pub use xcm::{
 	prelude::{
-		AccountId32, All, Asset, AssetId, BuyExecution, DepositAsset, ExpectTransactStatus,
-		Fungible, Here, Location, MaybeErrorCode, OriginKind, RefundSurplus, Transact, Unlimited,
-		VersionedXcm, WeightLimit, WithdrawAsset, Xcm,
+		AccountId32, All, Asset, AssetId, Assets, BuyExecution, DepositAsset, ExpectTransactStatus,
+		Fungible, Here, Location, MaybeErrorCode, OriginKind, Outcome, Parent, RefundSurplus,
+		Transact, Unlimited, VersionedXcm, WeightLimit, WithdrawAsset, Xcm,
 	},
 	v3::Location as V3Location,
 };
+pub use xcm_fee_payment_runtime_api::dry_run::runtime_decl_for_dry_run_api::DryRunApiV1;
 
 // Cumulus
 pub use asset_test_utils;
 pub use cumulus_pallet_xcmp_queue;
 pub use parachains_common::AccountId;
-pub use xcm_emulator::Chain;
+pub use xcm_emulator::{Chain, TestExt};
 
 #[macro_export]
 macro_rules! test_parachain_is_trusted_teleporter {
@@ -86,7 +87,7 @@ macro_rules! test_parachain_is_trusted_teleporter {
 									$crate::macros::cumulus_pallet_xcmp_queue::Event::XcmpMessageSent { .. }
 								) => {},
 								RuntimeEvent::Balances(
-									$crate::macros::pallet_balances::Event::Burned { who: sender, amount }
+									$crate::macros::pallet_balances::Event::Burned { who: sender, amount, .. }
 								) => {},
 							]
 						);
@@ -130,3 +131,327 @@ macro_rules! test_parachain_is_trusted_teleporter {
 		}
 	};
 }
+
+/// Teleports `$amount` of the native asset of `$relay` to `$para`, and then half of it back from
+/// the same beneficiary to `$relay`.
+///
+/// Testing both directions with independently funded accounts does not catch a teleported asset
+/// which cannot be returned, so this checks that `$relay` accepts the return against its checking
+/// account without trapping assets, and reconciles the balances of both sides with the delivery
+/// fees. The relay chain is expected to name its XCM pallet `XcmPallet`.
+///
+/// ```ignore
+/// test_teleport_round_trip!(
+/// 	Westend,                  // Relay chain
+/// 	WestendXcmConfig,         // Relay chain XCM configuration
+/// 	AssetHubWestend,          // Parachain
+/// 	AssetHubWestendXcmConfig, // Parachain XCM configuration
+/// 	WESTEND_ED * 1000
+/// );
+/// ```
+#[macro_export]
+macro_rules! test_teleport_round_trip {
+	( $relay:ty, $relay_xcm_config:ty, $para:ty, $para_xcm_config:ty, $amount:expr ) => {
+		$crate::macros::paste::paste! {
+			let fee_asset_item = 0;
+			let weight_limit = $crate::macros::WeightLimit::Unlimited;
+			let amount_to_send = $amount;
+			let amount_to_return = amount_to_send / 2;
+
+			// init Relay Chain variables
+			let relay_sender = [<$relay Sender>]::get();
+			let relay_receiver = [<$relay Receiver>]::get();
+			let check_account = <$relay>::execute_with(|| {
+				<$relay as [<$relay Pallet>]>::XcmPallet::check_account()
+			});
+			let relay_sender_balance_before =
+				<$relay as $crate::macros::Chain>::account_data_of(relay_sender.clone()).free;
+			let relay_receiver_balance_before =
+				<$relay as $crate::macros::Chain>::account_data_of(relay_receiver.clone()).free;
+			let check_account_balance_before =
+				<$relay as $crate::macros::Chain>::account_data_of(check_account.clone()).free;
+
+			// init Parachain variables, the beneficiary of the first teleport sends the second one
+			let para_account = [<$para Receiver>]::get();
+			let para_balance_before =
+				<$para as $crate::macros::Chain>::account_data_of(para_account.clone()).free;
+
+			// Teleport from the Relay Chain to the Parachain
+			let para_destination = <$relay>::child_location_of(<$para>::para_id());
+			let para_beneficiary: $crate::macros::Location =
+				$crate::macros::AccountId32 { network: None, id: para_account.clone().into() }.into();
+			let relay_assets: $crate::macros::Assets =
+				($crate::macros::Here, amount_to_send).into();
+
+			<$relay>::execute_with(|| {
+				assert_ok!(<$relay as [<$relay Pallet>]>::XcmPallet::limited_teleport_assets(
+					<$relay as $crate::macros::Chain>::RuntimeOrigin::signed(relay_sender.clone()),
+					bx!(para_destination.clone().into()),
+					bx!(para_beneficiary.clone().into()),
+					bx!(relay_assets.clone().into()),
+					fee_asset_item,
+					weight_limit.clone(),
+				));
+
+				type RuntimeEvent = <$relay as $crate::macros::Chain>::RuntimeEvent;
+
+				assert_expected_events!(
+					$relay,
+					vec![
+						RuntimeEvent::XcmPallet(
+							$crate::macros::pallet_xcm::Event::Attempted { outcome: Outcome::Complete { .. } }
+						) => {},
+						RuntimeEvent::Balances(
+							$crate::macros::pallet_balances::Event::Burned { who, amount, .. }
+						) => {
+							who: *who == relay_sender,
+							amount: *amount == amount_to_send,
+						},
+						RuntimeEvent::Balances(
+							$crate::macros::pallet_balances::Event::Minted { who, amount, .. }
+						) => {
+							who: *who == check_account,
+							amount: *amount == amount_to_send,
+						},
+					]
+				);
+			});
+
+			<$para>::execute_with(|| {
+				type RuntimeEvent = <$para as $crate::macros::Chain>::RuntimeEvent;
+
+				assert_expected_events!(
+					$para,
+					vec![
+						RuntimeEvent::Balances(
+							$crate::macros::pallet_balances::Event::Minted { who: para_account, .. }
+						) => {},
+						RuntimeEvent::MessageQueue(
+							$crate::macros::pallet_message_queue::Event::Processed { success: true, .. }
+						) => {},
+					]
+				);
+			});
+
+			let relay_delivery_fees = <$relay>::execute_with(|| {
+				$crate::macros::asset_test_utils::xcm_helpers::teleport_assets_delivery_fees::<
+					<$relay_xcm_config as xcm_executor::Config>::XcmSender,
+				>(relay_assets, fee_asset_item, weight_limit.clone(), para_beneficiary, para_destination)
+			});
+			let para_received =
+				<$para as $crate::macros::Chain>::account_data_of(para_account.clone()).free
+					- para_balance_before;
+			assert_eq!(
+				relay_sender_balance_before - amount_to_send - relay_delivery_fees,
+				<$relay as $crate::macros::Chain>::account_data_of(relay_sender.clone()).free,
+			);
+			assert!(para_received > 0 && para_received <= amount_to_send);
+
+			// Teleport half of it back from the same account to the Relay Chain
+			let relay_destination = <$para>::parent_location();
+			let relay_beneficiary: $crate::macros::Location =
+				$crate::macros::AccountId32 { network: None, id: relay_receiver.clone().into() }.into();
+			let para_assets: $crate::macros::Assets =
+				($crate::macros::Parent, amount_to_return).into();
+
+			<$para>::execute_with(|| {
+				assert_ok!(<$para as [<$para Pallet>]>::PolkadotXcm::limited_teleport_assets(
+					<$para as $crate::macros::Chain>::RuntimeOrigin::signed(para_account.clone()),
+					bx!(relay_destination.clone().into()),
+					bx!(relay_beneficiary.clone().into()),
+					bx!(para_assets.clone().into()),
+					fee_asset_item,
+					weight_limit.clone(),
+				));
+
+				type RuntimeEvent = <$para as $crate::macros::Chain>::RuntimeEvent;
+
+				assert_expected_events!(
+					$para,
+					vec![
+						RuntimeEvent::PolkadotXcm(
+							$crate::macros::pallet_xcm::Event::Attempted { outcome: Outcome::Complete { .. } }
+						) => {},
+						RuntimeEvent::Balances(
+							$crate::macros::pallet_balances::Event::Burned { who, amount, .. }
+						) => {
+							who: *who == para_account,
+							amount: *amount == amount_to_return,
+						},
+					]
+				);
+			});
+
+			<$relay>::execute_with(|| {
+				type RuntimeEvent = <$relay as $crate::macros::Chain>::RuntimeEvent;
+
+				let events = <$relay as $crate::macros::Chain>::events();
+				let processed = events.iter().any(|event| matches!(
+					event,
+					RuntimeEvent::MessageQueue(
+						$crate::macros::pallet_message_queue::Event::Processed { success: true, .. }
+					)
+				));
+				let trapped = events.iter().any(|event| matches!(
+					event,
+					RuntimeEvent::XcmPallet($crate::macros::pallet_xcm::Event::AssetsTrapped { .. })
+				));
+				assert!(
+					processed && !trapped,
+					concat!(
+						"{} rejected the teleport back from {}: it does not trust the parachain ",
+						"as teleporter of its native asset, or its checking account cannot cover ",
+						"the return. Events: {:#?}",
+					),
+					stringify!($relay),
+					stringify!($para),
+					events,
+				);
+
+				assert_expected_events!(
+					$relay,
+					vec![
+						RuntimeEvent::Balances(
+							$crate::macros::pallet_balances::Event::Burned { who, amount, .. }
+						) => {
+							who: *who == check_account,
+							amount: *amount == amount_to_return,
+						},
+						RuntimeEvent::Balances(
+							$crate::macros::pallet_balances::Event::Minted { who: relay_receiver, .. }
+						) => {},
+					]
+				);
+			});
+
+			// Check that the balances of both sides reconcile
+			let para_delivery_fees = <$para>::execute_with(|| {
+				$crate::macros::asset_test_utils::xcm_helpers::teleport_assets_delivery_fees::<
+					<$para_xcm_config as xcm_executor::Config>::XcmSender,
+				>(para_assets, fee_asset_item, weight_limit, relay_beneficiary, relay_destination)
+			});
+			assert_eq!(
+				para_balance_before + para_received - amount_to_return - para_delivery_fees,
+				<$para as $crate::macros::Chain>::account_data_of(para_account.clone()).free,
+			);
+			assert_eq!(
+				check_account_balance_before + amount_to_send - amount_to_return,
+				<$relay as $crate::macros::Chain>::account_data_of(check_account.clone()).free,
+			);
+			let relay_receiver_balance_after =
+				<$relay as $crate::macros::Chain>::account_data_of(relay_receiver.clone()).free;
+			assert!(relay_receiver_balance_after > relay_receiver_balance_before);
+			assert!(
+				relay_receiver_balance_after <= relay_receiver_balance_before + amount_to_return
+			);
+		}
+	};
+}
+
+/// Walks a multi-hop XCM journey by dry-running `$initial_call` on the first chain, and then on
+/// every following chain the message forwarded to it by the previous one.
+///
+/// `$initial_call` is a tuple of the `OriginCaller` and the `RuntimeCall` of the first chain. The
+/// assertions of every hop are called with its [`XcmHop`](crate::xcm_helpers::XcmHop) effects
+/// within the externalities of its chain, so that they can check the events of the hop with
+/// `assert_expected_events!` and query the runtime APIs of the chain. A failure names the hop
+/// and its chain.
+///
+/// ```ignore
+/// for_each_xcm_hop!(
+/// 	vec![
+/// 		(PenpalA, |hop: &XcmHop| { /* assertions on PenpalA */ }),
+/// 		(Westend, |hop: &XcmHop| { /* assertions on Westend */ }),
+/// 		(PenpalB, |hop: &XcmHop| { /* assertions on PenpalB */ }),
+/// 	],
+/// 	(origin, call)
+/// );
+/// ```
+#[macro_export]
+macro_rules! for_each_xcm_hop {
+	(
+		vec![($first_chain:ty, $first_assertions:expr) $(, ($chain:ty, $assertions:expr) )* $(,)?],
+		$initial_call:expr
+	) => {
+		{
+			let (origin, call) = $initial_call;
+			let mut hop_number: usize = 1;
+			let mut previous_chain = stringify!($first_chain);
+			let mut previous_location = $crate::for_each_xcm_hop!(@universal_location $first_chain);
+			let mut previous_hop =
+				<$first_chain as $crate::macros::TestExt>::execute_with(|| {
+					type Runtime = <$first_chain as $crate::macros::Chain>::Runtime;
+
+					let effects =
+						<Runtime as $crate::macros::DryRunApiV1<_, _, _, _>>::dry_run_call(origin, call)
+							.unwrap_or_else(|error| panic!(
+								"XCM hop #{} ({}): dry-running the initial call failed: {:?}",
+								hop_number, previous_chain, error,
+							));
+					assert!(
+						effects.execution_result.is_ok(),
+						"XCM hop #{} ({}): the initial call failed: {:?}",
+						hop_number, previous_chain, effects.execution_result,
+					);
+
+					let hop = $crate::xcm_helpers::XcmHop {
+						received_xcms: Vec::new(),
+						forwarded_xcms: effects.forwarded_xcms,
+					};
+					$crate::xcm_helpers::check_xcm_hop(hop_number, previous_chain, &hop, $first_assertions);
+					hop
+				});
+
+			$(
+				hop_number += 1;
+				let location = $crate::for_each_xcm_hop!(@universal_location $chain);
+				// Find the messages the previous hop forwarded to this chain.
+				let destination =
+					$crate::xcm_helpers::relative_location_of(&previous_location, &location);
+				let received_xcms = previous_hop.forwarded_to(&destination).cloned().unwrap_or_else(|| panic!(
+					"XCM hop #{} ({}): no message was forwarded to {:?} by {}, forwarded messages: {:#?}",
+					hop_number, stringify!($chain), destination, previous_chain, previous_hop.forwarded_xcms,
+				));
+				let origin = $crate::xcm_helpers::relative_location_of(&location, &previous_location);
+
+				previous_hop = <$chain as $crate::macros::TestExt>::execute_with(|| {
+					type Runtime = <$chain as $crate::macros::Chain>::Runtime;
+					type RuntimeCall = <$chain as $crate::macros::Chain>::RuntimeCall;
+
+					// We have to do this to turn `VersionedXcm<()>` into `VersionedXcm<RuntimeCall>`.
+					let xcm: $crate::macros::Xcm<()> = received_xcms[0].clone().try_into().unwrap();
+					let xcm = $crate::macros::VersionedXcm::V4(
+						$crate::macros::Xcm::<RuntimeCall>::from(xcm),
+					);
+					let effects =
+						<Runtime as $crate::macros::DryRunApiV1<_, _, _, _>>::dry_run_xcm(origin.into(), xcm)
+							.unwrap_or_else(|error| panic!(
+								"XCM hop #{} ({}): dry-running the received message failed: {:?}",
+								hop_number, stringify!($chain), error,
+							));
+					assert!(
+						matches!(effects.execution_result, $crate::macros::Outcome::Complete { .. }),
+						"XCM hop #{} ({}): the received message failed: {:?}",
+						hop_number, stringify!($chain), effects.execution_result,
+					);
+
+					let hop = $crate::xcm_helpers::XcmHop {
+						received_xcms,
+						forwarded_xcms: effects.forwarded_xcms,
+					};
+					$crate::xcm_helpers::check_xcm_hop(hop_number, stringify!($chain), &hop, $assertions);
+					hop
+				});
+				previous_chain = stringify!($chain);
+				previous_location = location;
+			)*
+
+			let _ = (hop_number, previous_chain, previous_location, previous_hop);
+		}
+	};
+	(@universal_location $chain:ty) => {
+		<$chain as $crate::macros::TestExt>::execute_with(|| {
+			<<<$chain as $crate::macros::Chain>::Runtime as $crate::macros::pallet_xcm::Config>::UniversalLocation as $crate::macros::Get<_>>::get()
+		})
+	};
+}
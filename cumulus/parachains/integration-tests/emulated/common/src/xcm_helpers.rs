@@ -69,3 +69,53 @@ pub fn non_fee_asset(assets: &Assets, fee_idx: usize) -> Option<(Location, u128)
 	};
 	Some((asset.id.0, asset_amount))
 }
+
+/// Helper method to get the location of the chain with universal location `to` as seen by the
+/// chain with universal location `from`
+pub fn relative_location_of(from: &InteriorLocation, to: &InteriorLocation) -> Location {
+	let common = from.iter().zip(to.iter()).take_while(|(a, b)| a == b).count();
+	let mut interior = Junctions::Here;
+	for junction in to.iter().skip(common) {
+		interior.push(junction.clone()).expect("`to` is a valid interior location; qed");
+	}
+	Location::new((from.len() - common) as u8, interior)
+}
+
+/// The effects of dry-running a hop of a multi-hop XCM journey, see
+/// [`for_each_xcm_hop`](crate::for_each_xcm_hop)
+#[derive(Clone, Debug)]
+pub struct XcmHop {
+	/// Messages forwarded to this hop by the previous one, empty for the first hop
+	pub received_xcms: Vec<VersionedXcm<()>>,
+	/// Messages forwarded by this hop, along with their destinations as seen by this hop
+	pub forwarded_xcms: Vec<(VersionedLocation, Vec<VersionedXcm<()>>)>,
+}
+
+impl XcmHop {
+	/// Messages forwarded by this hop to `destination`, as seen by this hop
+	pub fn forwarded_to(&self, destination: &Location) -> Option<&Vec<VersionedXcm<()>>> {
+		self.forwarded_xcms.iter().find_map(|(location, xcms)| {
+			let location: Location = location.clone().try_into().ok()?;
+			(location == *destination).then_some(xcms)
+		})
+	}
+}
+
+/// Helper method to run the `assertions` of the hop `hop_number` on chain `chain`, failing with
+/// a message naming the hop if any of them does not hold
+pub fn check_xcm_hop(
+	hop_number: usize,
+	chain: &str,
+	hop: &XcmHop,
+	assertions: impl FnOnce(&XcmHop),
+) {
+	let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| assertions(hop)));
+	if let Err(error) = result {
+		let message = error
+			.downcast_ref::<String>()
+			.cloned()
+			.or_else(|| error.downcast_ref::<&str>().map(|message| message.to_string()))
+			.unwrap_or_default();
+		panic!("XCM hop #{hop_number} ({chain}) failed: {message}\n{hop:#?}");
+	}
+}
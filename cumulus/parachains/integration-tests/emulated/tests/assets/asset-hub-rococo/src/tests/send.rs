@@ -96,7 +96,7 @@ fn send_xcm_from_para_to_system_para_paying_fee_with_system_assets_works() {
 			AssetHubRococo,
 			vec![
 				// Burned the fee
-				RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount }) => {
+				RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount, .. }) => {
 					who: *who == para_sovereign_account,
 					amount: *amount == fee_amount,
 				},
@@ -24,12 +24,12 @@ fn relay_origin_assertions(t: RelayToSystemParaTest) {
 		Rococo,
 		vec![
 			// Amount to teleport is withdrawn from Sender
-			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount }) => {
+			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount, .. }) => {
 				who: *who == t.sender.account_id,
 				amount: *amount == t.args.amount,
 			},
 			// Amount to teleport is deposited in Relay's `CheckAccount`
-			RuntimeEvent::Balances(pallet_balances::Event::Minted { who, amount }) => {
+			RuntimeEvent::Balances(pallet_balances::Event::Minted { who, amount, .. }) => {
 				who: *who == <Rococo as RococoPallet>::XcmPallet::check_account(),
 				amount:  *amount == t.args.amount,
 			},
@@ -50,7 +50,7 @@ fn relay_dest_assertions(t: SystemParaToRelayTest) {
 		Rococo,
 		vec![
 			// Amount is withdrawn from Relay Chain's `CheckAccount`
-			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount }) => {
+			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount, .. }) => {
 				who: *who == <Rococo as RococoPallet>::XcmPallet::check_account(),
 				amount: *amount == t.args.amount,
 			},
@@ -84,7 +84,7 @@ fn para_origin_assertions(t: SystemParaToRelayTest) {
 		AssetHubRococo,
 		vec![
 			// Amount is withdrawn from Sender's account
-			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount }) => {
+			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount, .. }) => {
 				who: *who == t.sender.account_id,
 				amount: *amount == t.args.amount,
 			},
@@ -150,7 +150,7 @@ fn penpal_to_ah_foreign_assets_receiver_assertions(t: ParaToSystemParaTest) {
 		vec![
 			// native asset reserve transfer for paying fees, withdrawn from Penpal's sov account
 			RuntimeEvent::Balances(
-				pallet_balances::Event::Burned { who, amount }
+				pallet_balances::Event::Burned { who, amount, .. }
 			) => {
 				who: *who == sov_penpal_on_ahr.clone().into(),
 				amount: *amount == t.args.amount,
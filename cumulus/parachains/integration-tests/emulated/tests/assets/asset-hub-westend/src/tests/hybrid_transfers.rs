@@ -33,7 +33,7 @@ fn para_to_para_assethub_hop_assertions(t: ParaToParaThroughAHTest) {
 		vec![
 			// Withdrawn from sender parachain SA
 			RuntimeEvent::Balances(
-				pallet_balances::Event::Burned { who, amount }
+				pallet_balances::Event::Burned { who, amount, .. }
 			) => {
 				who: *who == sov_penpal_a_on_ah,
 				amount: *amount == t.args.amount,
@@ -693,12 +693,12 @@ fn transfer_native_asset_from_relay_to_para_through_asset_hub() {
 			Westend,
 			vec![
 				// Amount to teleport is withdrawn from Sender
-				RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount }) => {
+				RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount, .. }) => {
 					who: *who == t.sender.account_id,
 					amount: *amount == t.args.amount,
 				},
 				// Amount to teleport is deposited in Relay's `CheckAccount`
-				RuntimeEvent::Balances(pallet_balances::Event::Minted { who, amount }) => {
+				RuntimeEvent::Balances(pallet_balances::Event::Minted { who, amount, .. }) => {
 					who: *who == <Westend as WestendPallet>::XcmPallet::check_account(),
 					amount:  *amount == t.args.amount,
 				},
@@ -159,7 +159,7 @@ fn para_to_relay_receiver_assertions(t: ParaToRelayTest) {
 		vec![
 			// Amount to reserve transfer is withdrawn from Parachain's Sovereign account
 			RuntimeEvent::Balances(
-				pallet_balances::Event::Burned { who, amount }
+				pallet_balances::Event::Burned { who, amount, .. }
 			) => {
 				who: *who == sov_penpal_on_relay.clone().into(),
 				amount: *amount == t.args.amount,
@@ -186,7 +186,7 @@ pub fn para_to_system_para_receiver_assertions(t: ParaToSystemParaTest) {
 				vec![
 					// Amount of native is withdrawn from Parachain's Sovereign account
 					RuntimeEvent::Balances(
-						pallet_balances::Event::Burned { who, amount }
+						pallet_balances::Event::Burned { who, amount, .. }
 					) => {
 						who: *who == sov_acc_of_penpal.clone().into(),
 						amount: *amount == asset_amount,
@@ -400,7 +400,7 @@ fn para_to_para_relay_hop_assertions(t: ParaToParaThroughRelayTest) {
 		vec![
 			// Withdrawn from sender parachain SA
 			RuntimeEvent::Balances(
-				pallet_balances::Event::Burned { who, amount }
+				pallet_balances::Event::Burned { who, amount, .. }
 			) => {
 				who: *who == sov_penpal_a_on_westend,
 				amount: *amount == t.args.amount,
@@ -24,12 +24,12 @@ fn relay_origin_assertions(t: RelayToSystemParaTest) {
 		Westend,
 		vec![
 			// Amount to teleport is withdrawn from Sender
-			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount }) => {
+			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount, .. }) => {
 				who: *who == t.sender.account_id,
 				amount: *amount == t.args.amount,
 			},
 			// Amount to teleport is deposited in Relay's `CheckAccount`
-			RuntimeEvent::Balances(pallet_balances::Event::Minted { who, amount }) => {
+			RuntimeEvent::Balances(pallet_balances::Event::Minted { who, amount, .. }) => {
 				who: *who == <Westend as WestendPallet>::XcmPallet::check_account(),
 				amount:  *amount == t.args.amount,
 			},
@@ -50,7 +50,7 @@ fn relay_dest_assertions(t: SystemParaToRelayTest) {
 		Westend,
 		vec![
 			// Amount is withdrawn from Relay Chain's `CheckAccount`
-			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount }) => {
+			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount, .. }) => {
 				who: *who == <Westend as WestendPallet>::XcmPallet::check_account(),
 				amount: *amount == t.args.amount,
 			},
@@ -84,7 +84,7 @@ fn para_origin_assertions(t: SystemParaToRelayTest) {
 		AssetHubWestend,
 		vec![
 			// Amount is withdrawn from Sender's account
-			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount }) => {
+			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount, .. }) => {
 				who: *who == t.sender.account_id,
 				amount: *amount == t.args.amount,
 			},
@@ -150,7 +150,7 @@ fn penpal_to_ah_foreign_assets_receiver_assertions(t: ParaToSystemParaTest) {
 		vec![
 			// native asset reserve transfer for paying fees, withdrawn from Penpal's sov account
 			RuntimeEvent::Balances(
-				pallet_balances::Event::Burned { who, amount }
+				pallet_balances::Event::Burned { who, amount, .. }
 			) => {
 				who: *who == sov_penpal_on_ahr.clone().into(),
 				amount: *amount == t.args.amount,
@@ -412,6 +412,19 @@ fn teleport_to_other_system_parachains_works() {
 	);
 }
 
+/// Native asset teleported from the Relay Chain to the System Parachain can be teleported back
+/// from the same account
+#[test]
+fn teleport_round_trip_between_relay_and_system_para_works() {
+	test_teleport_round_trip!(
+		Westend,                  // Relay Chain
+		WestendXcmConfig,         // Relay Chain XCM Configuration
+		AssetHubWestend,          // Parachain
+		AssetHubWestendXcmConfig, // Parachain XCM Configuration
+		WESTEND_ED * 1000
+	);
+}
+
 /// Bidirectional teleports of local Penpal assets to Asset Hub as foreign assets while paying
 /// fees using (reserve transferred) native asset.
 pub fn do_bidirectional_teleport_foreign_assets_between_para_and_asset_hub_using_xt(
@@ -114,7 +114,7 @@ fn multi_hop_works() {
 	let assets: Assets = (Parent, amount_to_send).into();
 	let relay_native_asset_location = RelayLocation::get();
 	let sender_as_seen_by_relay = Westend::child_location_of(PenpalA::para_id());
-	let sov_of_sender_on_relay = Westend::sovereign_account_id_of(sender_as_seen_by_relay.clone());
+	let sov_of_sender_on_relay = Westend::sovereign_account_id_of(sender_as_seen_by_relay);
 
 	// fund Parachain's sender account
 	PenpalA::mint_foreign_asset(
@@ -135,78 +135,65 @@ fn multi_hop_works() {
 	}
 	.into();
 
-	// We get them from the PenpalA closure.
-	let mut delivery_fees_amount = 0;
-	let mut remote_message = VersionedXcm::V4(Xcm(Vec::new()));
-	<PenpalA as TestExt>::execute_with(|| {
-		type Runtime = <PenpalA as Chain>::Runtime;
-		type RuntimeCall = <PenpalA as Chain>::RuntimeCall;
-		type OriginCaller = <PenpalA as Chain>::OriginCaller;
-
-		let call = RuntimeCall::PolkadotXcm(pallet_xcm::Call::transfer_assets {
-			dest: Box::new(VersionedLocation::V4(destination.clone())),
-			beneficiary: Box::new(VersionedLocation::V4(beneficiary)),
-			assets: Box::new(VersionedAssets::V4(assets.clone())),
-			fee_asset_item: 0,
-			weight_limit: Unlimited,
-		});
-		let origin = OriginCaller::system(RawOrigin::Signed(PenpalASender::get()));
-		let result = Runtime::dry_run_call(origin, call).unwrap();
-		assert_eq!(result.forwarded_xcms.len(), 1);
-		let (destination_to_query, messages_to_query) = &result.forwarded_xcms[0];
-		assert_eq!(messages_to_query.len(), 1);
-		remote_message = messages_to_query[0].clone();
-		let delivery_fees =
-			Runtime::query_delivery_fees(destination_to_query.clone(), remote_message.clone())
-				.unwrap();
-		delivery_fees_amount = get_amount_from_versioned_assets(delivery_fees);
+	type RuntimeCall = <PenpalA as Chain>::RuntimeCall;
+	type OriginCaller = <PenpalA as Chain>::OriginCaller;
+	let call = RuntimeCall::PolkadotXcm(pallet_xcm::Call::transfer_assets {
+		dest: Box::new(VersionedLocation::V4(destination.clone())),
+		beneficiary: Box::new(VersionedLocation::V4(beneficiary)),
+		assets: Box::new(VersionedAssets::V4(assets.clone())),
+		fee_asset_item: 0,
+		weight_limit: Unlimited,
 	});
+	let origin = OriginCaller::system(RawOrigin::Signed(sender.clone()));
 
-	// This is set in the Westend closure.
+	// We get the fees of every hop while walking the journey.
+	let mut delivery_fees_amount = 0;
 	let mut intermediate_execution_fees = 0;
 	let mut intermediate_delivery_fees_amount = 0;
-	let mut intermediate_remote_message = VersionedXcm::V4(Xcm::<()>(Vec::new()));
-	<Westend as TestExt>::execute_with(|| {
-		type Runtime = <Westend as Chain>::Runtime;
-		type RuntimeCall = <Westend as Chain>::RuntimeCall;
-
-		// First we get the execution fees.
-		let weight = Runtime::query_xcm_weight(remote_message.clone()).unwrap();
-		intermediate_execution_fees =
-			Runtime::query_weight_to_asset_fee(weight, VersionedAssetId::V4(Here.into())).unwrap();
-
-		// We have to do this to turn `VersionedXcm<()>` into `VersionedXcm<RuntimeCall>`.
-		let xcm_program =
-			VersionedXcm::V4(Xcm::<RuntimeCall>::from(remote_message.clone().try_into().unwrap()));
-
-		// Now we get the delivery fees to the final destination.
-		let result =
-			Runtime::dry_run_xcm(sender_as_seen_by_relay.clone().into(), xcm_program).unwrap();
-		let (destination_to_query, messages_to_query) = &result.forwarded_xcms[0];
-		// There's actually two messages here.
-		// One created when the message we sent from PenpalA arrived and was executed.
-		// The second one when we dry-run the xcm.
-		// We could've gotten the message from the queue without having to dry-run, but
-		// offchain applications would have to dry-run, so we do it here as well.
-		intermediate_remote_message = messages_to_query[0].clone();
-		let delivery_fees = Runtime::query_delivery_fees(
-			destination_to_query.clone(),
-			intermediate_remote_message.clone(),
-		)
-		.unwrap();
-		intermediate_delivery_fees_amount = get_amount_from_versioned_assets(delivery_fees);
-	});
-
-	// Get the final execution fees in the destination.
 	let mut final_execution_fees = 0;
-	<PenpalB as TestExt>::execute_with(|| {
-		type Runtime = <PenpalB as Chain>::Runtime;
-
-		let weight = Runtime::query_xcm_weight(intermediate_remote_message.clone()).unwrap();
-		final_execution_fees =
-			Runtime::query_weight_to_asset_fee(weight, VersionedAssetId::V4(Parent.into()))
+	for_each_xcm_hop!(
+		vec![
+			(PenpalA, |hop: &XcmHop| {
+				type Runtime = <PenpalA as Chain>::Runtime;
+
+				let (destination_to_query, messages_to_query) = &hop.forwarded_xcms[0];
+				let delivery_fees = Runtime::query_delivery_fees(
+					destination_to_query.clone(),
+					messages_to_query[0].clone(),
+				)
 				.unwrap();
-	});
+				delivery_fees_amount = get_amount_from_versioned_assets(delivery_fees);
+			}),
+			(Westend, |hop: &XcmHop| {
+				type Runtime = <Westend as Chain>::Runtime;
+
+				// First we get the execution fees.
+				let weight = Runtime::query_xcm_weight(hop.received_xcms[0].clone()).unwrap();
+				intermediate_execution_fees =
+					Runtime::query_weight_to_asset_fee(weight, VersionedAssetId::V4(Here.into()))
+						.unwrap();
+
+				// Now we get the delivery fees to the final destination.
+				let (destination_to_query, messages_to_query) = &hop.forwarded_xcms[0];
+				let delivery_fees = Runtime::query_delivery_fees(
+					destination_to_query.clone(),
+					messages_to_query[0].clone(),
+				)
+				.unwrap();
+				intermediate_delivery_fees_amount = get_amount_from_versioned_assets(delivery_fees);
+			}),
+			(PenpalB, |hop: &XcmHop| {
+				type Runtime = <PenpalB as Chain>::Runtime;
+
+				// Get the final execution fees in the destination.
+				let weight = Runtime::query_xcm_weight(hop.received_xcms[0].clone()).unwrap();
+				final_execution_fees =
+					Runtime::query_weight_to_asset_fee(weight, VersionedAssetId::V4(Parent.into()))
+						.unwrap();
+			}),
+		],
+		(origin, call)
+	);
 
 	// Dry-running is done.
 	PenpalA::reset_ext();
@@ -274,6 +261,207 @@ fn multi_hop_works() {
 	);
 }
 
+/// We are able to dry-run a reserve transfer through the relay chain, checking the events of
+/// every hop and estimating the fees using the `DryRunApi` and `XcmPaymentApi`.
+/// Scenario: Alice on PenpalA reserve transfers WND to Bob on PenpalB, with Westend as reserve.
+#[test]
+fn multi_hop_reserve_transfer_works() {
+	let destination = PenpalA::sibling_location_of(PenpalB::para_id());
+	let sender = PenpalASender::get();
+	let amount_to_send = 1_000_000_000_000; // One WND (12 decimals).
+	let asset_owner = PenpalAssetOwner::get();
+	let assets: Assets = (Parent, amount_to_send).into();
+	let relay_native_asset_location = RelayLocation::get();
+	let sov_of_sender_on_relay =
+		Westend::sovereign_account_id_of(Westend::child_location_of(PenpalA::para_id()));
+	let sov_of_receiver_on_relay =
+		Westend::sovereign_account_id_of(Westend::child_location_of(PenpalB::para_id()));
+
+	// fund Parachain's sender account
+	PenpalA::mint_foreign_asset(
+		<PenpalA as Chain>::RuntimeOrigin::signed(asset_owner.clone()),
+		relay_native_asset_location.clone(),
+		sender.clone(),
+		amount_to_send * 2,
+	);
+
+	// fund the Parachain Origin's SA on Relay Chain with the native tokens held in reserve
+	Westend::fund_accounts(vec![(sov_of_sender_on_relay.clone().into(), amount_to_send * 2)]);
+
+	// Init values for Parachain Destination
+	let beneficiary_id = PenpalBReceiver::get();
+	let beneficiary: Location = AccountId32 {
+		id: beneficiary_id.clone().into(),
+		network: None, // Test doesn't allow specifying a network here.
+	}
+	.into();
+
+	type RuntimeCall = <PenpalA as Chain>::RuntimeCall;
+	type OriginCaller = <PenpalA as Chain>::OriginCaller;
+	let call = RuntimeCall::PolkadotXcm(pallet_xcm::Call::limited_reserve_transfer_assets {
+		dest: Box::new(VersionedLocation::V4(destination.clone())),
+		beneficiary: Box::new(VersionedLocation::V4(beneficiary)),
+		assets: Box::new(VersionedAssets::V4(assets.clone())),
+		fee_asset_item: 0,
+		weight_limit: Unlimited,
+	});
+	let origin = OriginCaller::system(RawOrigin::Signed(sender.clone()));
+
+	// We check the events and get the fees of every hop while walking the journey.
+	let mut delivery_fees_amount = 0;
+	let mut intermediate_execution_fees = 0;
+	let mut intermediate_delivery_fees_amount = 0;
+	let mut final_execution_fees = 0;
+	for_each_xcm_hop!(
+		vec![
+			(PenpalA, |hop: &XcmHop| {
+				type Runtime = <PenpalA as Chain>::Runtime;
+				type RuntimeEvent = <PenpalA as Chain>::RuntimeEvent;
+
+				PenpalA::assert_xcm_pallet_attempted_complete(None);
+				assert_expected_events!(
+					PenpalA,
+					vec![
+						// Amount to reserve transfer is burned from the sender
+						RuntimeEvent::ForeignAssets(
+							pallet_assets::Event::Burned { owner, balance, .. }
+						) => {
+							owner: *owner == sender,
+							balance: *balance == amount_to_send,
+						},
+					]
+				);
+
+				let (destination_to_query, messages_to_query) = &hop.forwarded_xcms[0];
+				let delivery_fees = Runtime::query_delivery_fees(
+					destination_to_query.clone(),
+					messages_to_query[0].clone(),
+				)
+				.unwrap();
+				delivery_fees_amount = get_amount_from_versioned_assets(delivery_fees);
+			}),
+			(Westend, |hop: &XcmHop| {
+				type Runtime = <Westend as Chain>::Runtime;
+				type RuntimeEvent = <Westend as Chain>::RuntimeEvent;
+
+				assert_expected_events!(
+					Westend,
+					vec![
+						// Withdrawn from sender parachain SA
+						RuntimeEvent::Balances(
+							pallet_balances::Event::Burned { who, amount, .. }
+						) => {
+							who: *who == sov_of_sender_on_relay,
+							amount: *amount == amount_to_send,
+						},
+						// Deposited to receiver parachain SA
+						RuntimeEvent::Balances(
+							pallet_balances::Event::Minted { who, .. }
+						) => {
+							who: *who == sov_of_receiver_on_relay,
+						},
+					]
+				);
+
+				let weight = Runtime::query_xcm_weight(hop.received_xcms[0].clone()).unwrap();
+				intermediate_execution_fees =
+					Runtime::query_weight_to_asset_fee(weight, VersionedAssetId::V4(Here.into()))
+						.unwrap();
+
+				let (destination_to_query, messages_to_query) = &hop.forwarded_xcms[0];
+				let delivery_fees = Runtime::query_delivery_fees(
+					destination_to_query.clone(),
+					messages_to_query[0].clone(),
+				)
+				.unwrap();
+				intermediate_delivery_fees_amount = get_amount_from_versioned_assets(delivery_fees);
+			}),
+			(PenpalB, |hop: &XcmHop| {
+				type Runtime = <PenpalB as Chain>::Runtime;
+				type RuntimeEvent = <PenpalB as Chain>::RuntimeEvent;
+
+				assert_expected_events!(
+					PenpalB,
+					vec![
+						// Amount minus fees is issued to the beneficiary
+						RuntimeEvent::ForeignAssets(
+							pallet_assets::Event::Issued { owner, .. }
+						) => {
+							owner: *owner == beneficiary_id,
+						},
+					]
+				);
+
+				let weight = Runtime::query_xcm_weight(hop.received_xcms[0].clone()).unwrap();
+				final_execution_fees =
+					Runtime::query_weight_to_asset_fee(weight, VersionedAssetId::V4(Parent.into()))
+						.unwrap();
+			}),
+		],
+		(origin, call)
+	);
+
+	// Dry-running is done.
+	PenpalA::reset_ext();
+	Westend::reset_ext();
+	PenpalB::reset_ext();
+
+	// Fund accounts again.
+	PenpalA::mint_foreign_asset(
+		<PenpalA as Chain>::RuntimeOrigin::signed(asset_owner),
+		relay_native_asset_location.clone(),
+		sender.clone(),
+		amount_to_send * 2,
+	);
+	Westend::fund_accounts(vec![(sov_of_sender_on_relay.into(), amount_to_send * 2)]);
+
+	// Actually run the extrinsic.
+	let test_args = TestContext {
+		sender: PenpalASender::get(),     // Alice.
+		receiver: PenpalBReceiver::get(), // Bob in PenpalB.
+		args: TestArgs::new_para(
+			destination,
+			beneficiary_id.clone(),
+			amount_to_send,
+			assets,
+			None,
+			0,
+		),
+	};
+	let mut test = ParaToParaThroughRelayTest::new(test_args);
+
+	let sender_assets_before = PenpalA::execute_with(|| {
+		type ForeignAssets = <PenpalA as PenpalAPallet>::ForeignAssets;
+		<ForeignAssets as Inspect<_>>::balance(relay_native_asset_location.clone(), &sender)
+	});
+	let receiver_assets_before = PenpalB::execute_with(|| {
+		type ForeignAssets = <PenpalB as PenpalBPallet>::ForeignAssets;
+		<ForeignAssets as Inspect<_>>::balance(relay_native_asset_location.clone(), &beneficiary_id)
+	});
+
+	test.set_dispatchable::<PenpalA>(limited_reserve_transfer_assets_para_to_para);
+	test.assert();
+
+	let sender_assets_after = PenpalA::execute_with(|| {
+		type ForeignAssets = <PenpalA as PenpalAPallet>::ForeignAssets;
+		<ForeignAssets as Inspect<_>>::balance(relay_native_asset_location.clone(), &sender)
+	});
+	let receiver_assets_after = PenpalB::execute_with(|| {
+		type ForeignAssets = <PenpalB as PenpalBPallet>::ForeignAssets;
+		<ForeignAssets as Inspect<_>>::balance(relay_native_asset_location, &beneficiary_id)
+	});
+
+	// We know the exact fees on every hop.
+	assert_eq!(sender_assets_after, sender_assets_before - amount_to_send - delivery_fees_amount);
+	assert_eq!(
+		receiver_assets_after,
+		receiver_assets_before + amount_to_send -
+			intermediate_execution_fees -
+			intermediate_delivery_fees_amount -
+			final_execution_fees
+	);
+}
+
 fn get_amount_from_versioned_assets(assets: VersionedAssets) -> u128 {
 	let latest_assets: Assets = assets.try_into().unwrap();
 	let Fungible(amount) = latest_assets.inner()[0].fun else {
@@ -303,3 +491,16 @@ fn transfer_assets_para_to_para(test: ParaToParaThroughRelayTest) -> DispatchRes
 		test.args.weight_limit,
 	)
 }
+
+fn limited_reserve_transfer_assets_para_to_para(
+	test: ParaToParaThroughRelayTest,
+) -> DispatchResult {
+	<PenpalA as PenpalAPallet>::PolkadotXcm::limited_reserve_transfer_assets(
+		test.signed_origin,
+		bx!(test.args.dest.into()),
+		bx!(test.args.beneficiary.into()),
+		bx!(test.args.assets.into()),
+		test.args.fee_asset_item,
+		test.args.weight_limit,
+	)
+}
@@ -83,7 +83,7 @@ fn send_asset_from_penpal_rococo_through_local_asset_hub_to_westend_asset_hub(
 			vec![
 				// Amount to reserve transfer is withdrawn from Penpal's sovereign account
 				RuntimeEvent::Balances(
-					pallet_balances::Event::Burned { who, amount }
+					pallet_balances::Event::Burned { who, amount, .. }
 				) => {
 					who: *who == sov_penpal_on_ahr.clone().into(),
 					amount: *amount == transfer_amount,
@@ -256,7 +256,7 @@ fn send_wnds_from_asset_hub_rococo_to_asset_hub_westend() {
 			vec![
 				// WND is withdrawn from AHR's SA on AHW
 				RuntimeEvent::Balances(
-					pallet_balances::Event::Burned { who, amount }
+					pallet_balances::Event::Burned { who, amount, .. }
 				) => {
 					who: *who == sov_ahr_on_ahw,
 					amount: *amount == amount_to_send,
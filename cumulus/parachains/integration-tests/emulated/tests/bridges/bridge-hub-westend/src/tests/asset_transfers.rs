@@ -82,7 +82,7 @@ fn send_asset_from_penpal_westend_through_local_asset_hub_to_rococo_asset_hub(
 			vec![
 				// Amount to reserve transfer is withdrawn from Penpal's sovereign account
 				RuntimeEvent::Balances(
-					pallet_balances::Event::Burned { who, amount }
+					pallet_balances::Event::Burned { who, amount, .. }
 				) => {
 					who: *who == sov_penpal_on_ahw.clone().into(),
 					amount: *amount == transfer_amount,
@@ -252,7 +252,7 @@ fn send_rocs_from_asset_hub_westend_to_asset_hub_rococo() {
 			vec![
 				// ROC is withdrawn from AHW's SA on AHR
 				RuntimeEvent::Balances(
-					pallet_balances::Event::Burned { who, amount }
+					pallet_balances::Event::Burned { who, amount, .. }
 				) => {
 					who: *who == sov_ahw_on_ahr,
 					amount: *amount == amount_to_send,
@@ -25,12 +25,12 @@ fn relay_origin_assertions(t: RelayToSystemParaTest) {
 		Rococo,
 		vec![
 			// Amount to teleport is withdrawn from Sender
-			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount }) => {
+			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount, .. }) => {
 				who: *who == t.sender.account_id,
 				amount: *amount == t.args.amount,
 			},
 			// Amount to teleport is deposited in Relay's `CheckAccount`
-			RuntimeEvent::Balances(pallet_balances::Event::Minted { who, amount }) => {
+			RuntimeEvent::Balances(pallet_balances::Event::Minted { who, amount, .. }) => {
 				who: *who == <Rococo as RococoPallet>::XcmPallet::check_account(),
 				amount:  *amount == t.args.amount,
 			},
@@ -51,7 +51,7 @@ fn relay_dest_assertions(t: SystemParaToRelayTest) {
 		Rococo,
 		vec![
 			// Amount is withdrawn from Relay Chain's `CheckAccount`
-			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount }) => {
+			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount, .. }) => {
 				who: *who == <Rococo as RococoPallet>::XcmPallet::check_account(),
 				amount: *amount == t.args.amount,
 			},
@@ -85,7 +85,7 @@ fn para_origin_assertions(t: SystemParaToRelayTest) {
 		PeopleRococo,
 		vec![
 			// Amount is withdrawn from Sender's account
-			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount }) => {
+			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount, .. }) => {
 				who: *who == t.sender.account_id,
 				amount: *amount == t.args.amount,
 			},
@@ -25,12 +25,12 @@ fn relay_origin_assertions(t: RelayToSystemParaTest) {
 		Westend,
 		vec![
 			// Amount to teleport is withdrawn from Sender
-			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount }) => {
+			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount, .. }) => {
 				who: *who == t.sender.account_id,
 				amount: *amount == t.args.amount,
 			},
 			// Amount to teleport is deposited in Relay's `CheckAccount`
-			RuntimeEvent::Balances(pallet_balances::Event::Minted { who, amount }) => {
+			RuntimeEvent::Balances(pallet_balances::Event::Minted { who, amount, .. }) => {
 				who: *who == <Westend as WestendPallet>::XcmPallet::check_account(),
 				amount:  *amount == t.args.amount,
 			},
@@ -51,7 +51,7 @@ fn relay_dest_assertions(t: SystemParaToRelayTest) {
 		Westend,
 		vec![
 			// Amount is withdrawn from Relay Chain's `CheckAccount`
-			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount }) => {
+			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount, .. }) => {
 				who: *who == <Westend as WestendPallet>::XcmPallet::check_account(),
 				amount: *amount == t.args.amount,
 			},
@@ -85,7 +85,7 @@ fn para_origin_assertions(t: SystemParaToRelayTest) {
 		PeopleWestend,
 		vec![
 			// Amount is withdrawn from Sender's account
-			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount }) => {
+			RuntimeEvent::Balances(pallet_balances::Event::Burned { who, amount, .. }) => {
 				who: *who == t.sender.account_id,
 				amount: *amount == t.args.amount,
 			},
@@ -200,6 +200,7 @@ impl pallet_balances::Config for Runtime {
 	type DustRemoval = ();
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
 	type WeightInfo = weights::pallet_balances::WeightInfo<Runtime>;
 	type MaxReserves = ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
@@ -207,6 +208,16 @@ impl pallet_balances::Config for Runtime {
 	type RuntimeFreezeReason = RuntimeFreezeReason;
 	type FreezeIdentifier = ();
 	type MaxFreezes = ConstU32<0>;
+	type ResidueQuantum = ();
+	type ResidueThreshold = ();
+	type MaxExpiringPerBlock = ();
+	type MinTransferAmount = ();
+	type LockToFreezeMapping = ();
+	type ManagedFreezes = ();
+	type BurnableHolds = ();
+	type LenientFreezeBound = ();
+	type DoneSlashHandler = ();
+	type OnInconsistency = ();
 }
 
 parameter_types! {
@@ -418,8 +429,8 @@ impl pallet_assets::Config<ForeignAssetsInstance> for Runtime {
 parameter_types! {
 	// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
 	pub const DepositBase: Balance = deposit(1, 88);
-	// Additional storage item size of 32 bytes.
-	pub const DepositFactor: Balance = deposit(0, 32);
+	// Additional storage item size of an approval, 32+4+33 bytes = 69 bytes.
+	pub const DepositFactor: Balance = deposit(0, 69);
 	pub const MaxSignatories: u32 = 100;
 }
 
@@ -427,9 +438,18 @@ impl pallet_multisig::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
 	type Currency = Balances;
+	type OldCurrency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = MaxSignatories;
+	// Pausing multisig operations is disabled.
+	type GuardianOrigin = frame_system::EnsureNever<()>;
+	type MaxPauseDuration = ConstU32<0>;
+	type CancelPenalty = ();
+	type CancelSlashHandler = ();
+	type DepositExempt = frame_support::traits::Nothing;
+	type LenientSignatoryOrder = ();
 	type WeightInfo = weights::pallet_multisig::WeightInfo<Runtime>;
 }
 
@@ -985,6 +1005,12 @@ pub type Migrations = (
 	pallet_collator_selection::migration::v2::MigrationToV2<Runtime>,
 	// unreleased
 	pallet_multisig::migrations::v1::MigrateToV1<Runtime>,
+	pallet_multisig::migrations::v2::MigrateToV2<Runtime>,
+	pallet_multisig::migrations::v3::MigrateToV3<Runtime>,
+	pallet_multisig::migrations::v4::MigrateToV4<Runtime>,
+	pallet_multisig::migrations::v5::MigrateToV5<Runtime>,
+	pallet_multisig::migrations::v6::MigrateToV6<Runtime>,
+	pallet_multisig::migrations::v7::MigrateToV7<Runtime>,
 	// unreleased
 	InitStorageVersions,
 	// unreleased
@@ -148,6 +148,21 @@ impl<T: frame_system::Config> pallet_balances::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(u.into())))
 			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(u.into()))
 	}
+	/// Storage: `System::Account` (r:999 w:0)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `u` is `[1, 1000]`.
+	fn upgrade_accounts_skipped(u: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0 + u * (135 ±0)`
+		//  Estimated: `990 + u * (2603 ±0)`
+		// Minimum execution time: 2_108_000 picoseconds.
+		Weight::from_parts(2_231_000, 0)
+			.saturating_add(Weight::from_parts(0, 990))
+			// Standard Error: 4_102
+			.saturating_add(Weight::from_parts(3_517_372, 0).saturating_mul(u.into()))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(u.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(u.into()))
+	}
 	/// Storage: `Balances::InactiveIssuance` (r:1 w:0)
 	/// Proof: `Balances::InactiveIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
 	fn force_adjust_total_issuance() -> Weight {
@@ -175,4 +190,116 @@ impl<T: frame_system::Config> pallet_balances::WeightInfo for WeightInfo<T> {
 		Weight::from_parts(18_637_000, 0)
 			.saturating_add(Weight::from_parts(0, 0))
 	}
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(193), added: 2668, mode: `MaxEncodedLen`)
+	fn burn_held() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 18_106_000 picoseconds.
+		Weight::from_parts(18_637_000, 0)
+			.saturating_add(Weight::from_parts(0, 0))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Balances::IssuanceReconciliationProgress` (r:1 w:1)
+	/// Proof: `Balances::IssuanceReconciliationProgress` (`max_values`: Some(1), `max_size`: Some(48), added: 543, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::TotalIssuance` (r:1 w:1)
+	/// Proof: `Balances::TotalIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::InactiveIssuance` (r:1 w:0)
+	/// Proof: `Balances::InactiveIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:999 w:0)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `a` is `[0, 1000]`.
+	fn force_reconcile_total_issuance(a: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `142 + a * (136 ±0)`
+		//  Estimated: `1533 + a * (2603 ±0)`
+		// Minimum execution time: 13_979_000 picoseconds.
+		Weight::from_parts(14_195_000, 0)
+			.saturating_add(Weight::from_parts(0, 1533))
+			.saturating_add(Weight::from_parts(6_551_458, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(a.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(a.into()))
+	}
+	/// Storage: `Balances::Holds` (r:1 w:0)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(193), added: 2668, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Locks` (r:1 w:0)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Freezes` (r:1 w:0)
+	/// Proof: `Balances::Freezes` (`max_values`: None, `max_size`: Some(67), added: 2542, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn repair_account() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `329`
+		//  Estimated: `4764`
+		// Minimum execution time: 31_076_000 picoseconds.
+		Weight::from_parts(32_871_000, 0)
+			.saturating_add(Weight::from_parts(0, 4764))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Not benchmarked for this runtime yet, the reference weight of the pallet is used until the
+	/// weights are regenerated.
+	fn force_set_inactive_issuance() -> Weight {
+		<pallet_balances::weights::SubstrateWeight<T> as pallet_balances::WeightInfo>::force_set_inactive_issuance()
+	}
+	/// Not benchmarked for this runtime yet, the reference weight of the pallet is used until the
+	/// weights are regenerated.
+	fn force_adjust_inactive_issuance() -> Weight {
+		<pallet_balances::weights::SubstrateWeight<T> as pallet_balances::WeightInfo>::force_adjust_inactive_issuance()
+	}
+	/// Not benchmarked for this runtime yet, the reference weight of the pallet is used until the
+	/// weights are regenerated.
+	fn force_set_freeze() -> Weight {
+		<pallet_balances::weights::SubstrateWeight<T> as pallet_balances::WeightInfo>::force_set_freeze()
+	}
+	/// Not benchmarked for this runtime yet, the reference weight of the pallet is used until the
+	/// weights are regenerated.
+	fn purge_ghost_accounts(l: u32, ) -> Weight {
+		<pallet_balances::weights::SubstrateWeight<T> as pallet_balances::WeightInfo>::purge_ghost_accounts(l)
+	}
+	/// Not benchmarked for this runtime yet, the reference weight of the pallet is used until the
+	/// weights are regenerated.
+	fn set_auto_upgrade() -> Weight {
+		<pallet_balances::weights::SubstrateWeight<T> as pallet_balances::WeightInfo>::set_auto_upgrade()
+	}
+	/// Not benchmarked for this runtime yet, the reference weight of the pallet is used until the
+	/// weights are regenerated.
+	fn add_silent_account() -> Weight {
+		<pallet_balances::weights::SubstrateWeight<T> as pallet_balances::WeightInfo>::add_silent_account()
+	}
+	/// Not benchmarked for this runtime yet, the reference weight of the pallet is used until the
+	/// weights are regenerated.
+	fn remove_silent_account() -> Weight {
+		<pallet_balances::weights::SubstrateWeight<T> as pallet_balances::WeightInfo>::remove_silent_account()
+	}
+	/// Not benchmarked for this runtime yet, the reference weight of the pallet is used until the
+	/// weights are regenerated.
+	fn restrict_account() -> Weight {
+		<pallet_balances::weights::SubstrateWeight<T> as pallet_balances::WeightInfo>::restrict_account()
+	}
+	/// Not benchmarked for this runtime yet, the reference weight of the pallet is used until the
+	/// weights are regenerated.
+	fn unrestrict_account() -> Weight {
+		<pallet_balances::weights::SubstrateWeight<T> as pallet_balances::WeightInfo>::unrestrict_account()
+	}
+	/// Not benchmarked for this runtime yet, the reference weight of the pallet is used until the
+	/// weights are regenerated.
+	fn force_migrate_account() -> Weight {
+		<pallet_balances::weights::SubstrateWeight<T> as pallet_balances::WeightInfo>::force_migrate_account()
+	}
+	/// Not benchmarked for this runtime yet, the reference weight of the pallet is used until the
+	/// weights are regenerated.
+	fn deprecate_lock() -> Weight {
+		<pallet_balances::weights::SubstrateWeight<T> as pallet_balances::WeightInfo>::deprecate_lock()
+	}
+	/// Not benchmarked for this runtime yet, the reference weight of the pallet is used until the
+	/// weights are regenerated.
+	fn prepare_account_closure() -> Weight {
+		<pallet_balances::weights::SubstrateWeight<T> as pallet_balances::WeightInfo>::prepare_account_closure()
+	}
 }
@@ -211,6 +211,7 @@ impl pallet_balances::Config for Runtime {
 	type DustRemoval = ();
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
 	type WeightInfo = weights::pallet_balances::WeightInfo<Runtime>;
 	type MaxReserves = ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
@@ -218,6 +219,16 @@ impl pallet_balances::Config for Runtime {
 	type RuntimeFreezeReason = RuntimeFreezeReason;
 	type FreezeIdentifier = ();
 	type MaxFreezes = ConstU32<0>;
+	type ResidueQuantum = ();
+	type ResidueThreshold = ();
+	type MaxExpiringPerBlock = ();
+	type MinTransferAmount = ();
+	type LockToFreezeMapping = ();
+	type ManagedFreezes = ();
+	type BurnableHolds = ();
+	type LenientFreezeBound = ();
+	type DoneSlashHandler = ();
+	type OnInconsistency = ();
 }
 
 parameter_types! {
@@ -238,17 +249,26 @@ impl pallet_transaction_payment::Config for Runtime {
 parameter_types! {
 	// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
 	pub const DepositBase: Balance = deposit(1, 88);
-	// Additional storage item size of 32 bytes.
-	pub const DepositFactor: Balance = deposit(0, 32);
+	// Additional storage item size of an approval, 32+4+33 bytes = 69 bytes.
+	pub const DepositFactor: Balance = deposit(0, 69);
 }
 
 impl pallet_multisig::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
 	type Currency = Balances;
+	type OldCurrency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = ConstU32<100>;
+	// Pausing multisig operations is disabled.
+	type GuardianOrigin = frame_system::EnsureNever<()>;
+	type MaxPauseDuration = ConstU32<0>;
+	type CancelPenalty = ();
+	type CancelSlashHandler = ();
+	type DepositExempt = frame_support::traits::Nothing;
+	type LenientSignatoryOrder = ();
 	type WeightInfo = weights::pallet_multisig::WeightInfo<Runtime>;
 }
 
@@ -737,6 +757,12 @@ pub type UncheckedExtrinsic =
 /// All migrations executed on runtime upgrade as a nested tuple of types implementing
 /// `OnRuntimeUpgrade`. Included migrations must be idempotent.
 type Migrations = (
+	pallet_multisig::migrations::v2::MigrateToV2<Runtime>,
+	pallet_multisig::migrations::v3::MigrateToV3<Runtime>,
+	pallet_multisig::migrations::v4::MigrateToV4<Runtime>,
+	pallet_multisig::migrations::v5::MigrateToV5<Runtime>,
+	pallet_multisig::migrations::v6::MigrateToV6<Runtime>,
+	pallet_multisig::migrations::v7::MigrateToV7<Runtime>,
 	// unreleased
 	pallet_collator_selection::migration::v2::MigrationToV2<Runtime>,
 	// unreleased
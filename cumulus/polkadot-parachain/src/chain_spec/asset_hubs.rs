@@ -368,6 +368,7 @@ fn asset_hub_rococo_genesis(
 				.cloned()
 				.map(|k| (k, endowment))
 				.collect(),
+			..Default::default()
 		},
 		"parachainInfo": asset_hub_rococo_runtime::ParachainInfoConfig {
 			parachain_id: id,
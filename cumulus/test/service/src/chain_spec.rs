@@ -140,6 +140,7 @@ pub fn testnet_genesis(
 	serde_json::json!({
 		"balances": cumulus_test_runtime::BalancesConfig {
 			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect(),
+			..Default::default()
 		},
 		"sudo": cumulus_test_runtime::SudoConfig { key: Some(root_key) },
 		"parachainInfo": {
@@ -708,6 +708,7 @@ mod tests {
 		type DustRemoval = ();
 		type ExistentialDeposit = ExistentialDeposit;
 		type AccountStore = System;
+		type AuthoritativeStore = pallet_balances::SystemStore;
 		type WeightInfo = ();
 		type MaxLocks = ();
 		type MaxReserves = ();
@@ -716,6 +717,16 @@ mod tests {
 		type RuntimeFreezeReason = RuntimeFreezeReason;
 		type FreezeIdentifier = ();
 		type MaxFreezes = ConstU32<1>;
+		type ResidueQuantum = ();
+		type ResidueThreshold = ();
+		type MaxExpiringPerBlock = ();
+		type MinTransferAmount = ();
+		type LockToFreezeMapping = ();
+		type ManagedFreezes = ();
+		type BurnableHolds = ();
+		type LenientFreezeBound = ();
+		type DoneSlashHandler = ();
+		type OnInconsistency = ();
 	}
 
 	impl parachains_configuration::Config for Test {
@@ -778,6 +789,7 @@ mod tests {
 		let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
 		pallet_balances::GenesisConfig::<Test> {
 			balances: vec![(1, 10), (2, 20), (3, 30), (4, 40), (5, 50), (6, 60)],
+			..Default::default()
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
@@ -748,6 +748,7 @@ mod tests {
 		type DustRemoval = ();
 		type ExistentialDeposit = ExistentialDeposit;
 		type AccountStore = System;
+		type AuthoritativeStore = pallet_balances::SystemStore;
 		type MaxLocks = ();
 		type MaxReserves = ();
 		type ReserveIdentifier = [u8; 8];
@@ -756,6 +757,16 @@ mod tests {
 		type RuntimeFreezeReason = RuntimeFreezeReason;
 		type FreezeIdentifier = ();
 		type MaxFreezes = ConstU32<1>;
+		type ResidueQuantum = ();
+		type ResidueThreshold = ();
+		type MaxExpiringPerBlock = ();
+		type MinTransferAmount = ();
+		type LockToFreezeMapping = ();
+		type ManagedFreezes = ();
+		type BurnableHolds = ();
+		type LenientFreezeBound = ();
+		type DoneSlashHandler = ();
+		type OnInconsistency = ();
 	}
 
 	parameter_types! {
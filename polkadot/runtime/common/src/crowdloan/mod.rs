@@ -928,6 +928,7 @@ mod tests {
 		type DustRemoval = ();
 		type ExistentialDeposit = ExistentialDeposit;
 		type AccountStore = System;
+		type AuthoritativeStore = pallet_balances::SystemStore;
 		type MaxLocks = ();
 		type MaxReserves = ();
 		type ReserveIdentifier = [u8; 8];
@@ -936,6 +937,16 @@ mod tests {
 		type RuntimeFreezeReason = RuntimeFreezeReason;
 		type FreezeIdentifier = ();
 		type MaxFreezes = ConstU32<1>;
+		type ResidueQuantum = ();
+		type ResidueThreshold = ();
+		type MaxExpiringPerBlock = ();
+		type MinTransferAmount = ();
+		type LockToFreezeMapping = ();
+		type ManagedFreezes = ();
+		type BurnableHolds = ();
+		type LenientFreezeBound = ();
+		type DoneSlashHandler = ();
+		type OnInconsistency = ();
 	}
 
 	#[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -1100,6 +1111,7 @@ mod tests {
 		let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
 		pallet_balances::GenesisConfig::<Test> {
 			balances: vec![(1, 1000), (2, 2000), (3, 3000), (4, 4000)],
+			..Default::default()
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
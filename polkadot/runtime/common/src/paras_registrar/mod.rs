@@ -805,6 +805,7 @@ mod tests {
 		type RuntimeEvent = RuntimeEvent;
 		type ExistentialDeposit = ExistentialDeposit;
 		type AccountStore = System;
+		type AuthoritativeStore = pallet_balances::SystemStore;
 		type MaxLocks = ();
 		type MaxReserves = ();
 		type ReserveIdentifier = [u8; 8];
@@ -813,6 +814,16 @@ mod tests {
 		type RuntimeFreezeReason = RuntimeFreezeReason;
 		type FreezeIdentifier = ();
 		type MaxFreezes = ConstU32<1>;
+		type ResidueQuantum = ();
+		type ResidueThreshold = ();
+		type MaxExpiringPerBlock = ();
+		type MinTransferAmount = ();
+		type LockToFreezeMapping = ();
+		type ManagedFreezes = ();
+		type BurnableHolds = ();
+		type LenientFreezeBound = ();
+		type DoneSlashHandler = ();
+		type OnInconsistency = ();
 	}
 
 	impl shared::Config for Test {
@@ -870,6 +881,7 @@ mod tests {
 
 		pallet_balances::GenesisConfig::<Test> {
 			balances: vec![(1, 10_000_000), (2, 10_000_000), (3, 10_000_000)],
+			..Default::default()
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
@@ -53,4 +53,5 @@ impl mock_message_queue::Config for Runtime {
 impl pallet_balances::Config for Runtime {
 	type Balance = Balance;
 	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
 }
@@ -47,6 +47,7 @@ impl frame_system::Config for Runtime {
 #[derive_impl(pallet_balances::config_preludes::TestDefaultConfig as pallet_balances::DefaultConfig)]
 impl pallet_balances::Config for Runtime {
 	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
 }
 
 type Block = frame_system::mocking::MockBlock<Runtime>;
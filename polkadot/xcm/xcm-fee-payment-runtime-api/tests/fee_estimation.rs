@@ -104,18 +104,27 @@ fn fee_estimation_for_teleport() {
 				}),
 				RuntimeEvent::Balances(pallet_balances::Event::Minted {
 					who: 8660274132218572653,
-					amount: 100
+					amount: 100,
+					context: None
 				}),
 				RuntimeEvent::AssetsPallet(pallet_assets::Event::Burned {
 					asset_id: 1,
 					owner: 1,
 					balance: 20
 				}),
-				RuntimeEvent::Balances(pallet_balances::Event::Burned { who: 1, amount: 100 }),
+				RuntimeEvent::Balances(pallet_balances::Event::Burned {
+					who: 1,
+					amount: 100,
+					context: None
+				}),
 				RuntimeEvent::XcmPallet(pallet_xcm::Event::Attempted {
 					outcome: Outcome::Complete { used: Weight::from_parts(400, 40) },
 				}),
-				RuntimeEvent::Balances(pallet_balances::Event::Burned { who: 1, amount: 20 }),
+				RuntimeEvent::Balances(pallet_balances::Event::Burned {
+					who: 1,
+					amount: 20,
+					context: None
+				}),
 				RuntimeEvent::XcmPallet(pallet_xcm::Event::FeesPaid {
 					paying: AccountIndex64 { index: 1, network: None }.into(),
 					fees: (Here, 20u128).into(),
@@ -256,7 +265,11 @@ fn dry_run_reserve_asset_transfer() {
 				RuntimeEvent::XcmPallet(pallet_xcm::Event::Attempted {
 					outcome: Outcome::Complete { used: Weight::from_parts(200, 20) }
 				}),
-				RuntimeEvent::Balances(pallet_balances::Event::Burned { who: 1, amount: 20 }),
+				RuntimeEvent::Balances(pallet_balances::Event::Burned {
+					who: 1,
+					amount: 20,
+					context: None
+				}),
 				RuntimeEvent::XcmPallet(pallet_xcm::Event::FeesPaid {
 					paying: AccountIndex64 { index: 1, network: None }.into(),
 					fees: (Here, 20u128).into()
@@ -342,13 +355,21 @@ fn dry_run_xcm() {
 		assert_eq!(
 			dry_run_effects.emitted_events,
 			vec![
-				RuntimeEvent::Balances(pallet_balances::Event::Burned { who: 1, amount: 540 }),
+				RuntimeEvent::Balances(pallet_balances::Event::Burned {
+					who: 1,
+					amount: 540,
+					context: None
+				}),
 				RuntimeEvent::System(frame_system::Event::NewAccount { account: 2100 }),
 				RuntimeEvent::Balances(pallet_balances::Event::Endowed {
 					account: 2100,
 					free_balance: 520
 				}),
-				RuntimeEvent::Balances(pallet_balances::Event::Minted { who: 2100, amount: 520 }),
+				RuntimeEvent::Balances(pallet_balances::Event::Minted {
+					who: 2100,
+					amount: 520,
+					context: None
+				}),
 			]
 		);
 	});
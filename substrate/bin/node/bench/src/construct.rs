@@ -25,13 +25,14 @@
 //! based on randomized data.
 
 use futures::Future;
-use std::{borrow::Cow, collections::HashMap, pin::Pin, sync::Arc};
+use std::{borrow::Cow, collections::HashMap, pin::Pin, sync::Arc, time::Duration};
 
 use node_primitives::Block;
 use node_testing::bench::{BenchDb, BlockType, DatabaseType, KeyTypes};
 use sc_transaction_pool_api::{
-	ImportNotificationStream, PoolFuture, PoolStatus, ReadyTransactions, TransactionFor,
-	TransactionSource, TransactionStatusStreamFor, TxHash,
+	ImportNotificationStream, InvalidityReport, OverflowHandle, PoolFuture, PoolSnapshot,
+	PoolSnapshotImport, PoolStatus, ReadyTransactions, TransactionFor, TransactionPriority,
+	TransactionSource, TransactionStatusStreamFor, TransactionTag, TxHash, WaitReadyOutcomeFor,
 };
 use sp_consensus::{Environment, Proposer};
 use sp_inherents::InherentDataProvider;
@@ -206,6 +207,14 @@ impl sc_transaction_pool_api::InPoolTransaction for PoolTransaction {
 	fn is_propagable(&self) -> bool {
 		unimplemented!()
 	}
+
+	fn validated_at(&self) -> Option<u64> {
+		unimplemented!()
+	}
+
+	fn source(&self) -> sc_transaction_pool_api::TransactionSource {
+		unimplemented!()
+	}
 }
 
 #[derive(Clone, Debug)]
@@ -259,6 +268,13 @@ impl sc_transaction_pool_api::TransactionPool for Transactions {
 		unimplemented!()
 	}
 
+	fn watch_existing(
+		&self,
+		_hash: TxHash<Self>,
+	) -> Option<Pin<Box<TransactionStatusStreamFor<Self>>>> {
+		unimplemented!()
+	}
+
 	fn ready_at(
 		&self,
 		_at: NumberFor<Self::Block>,
@@ -278,11 +294,14 @@ impl sc_transaction_pool_api::TransactionPool for Transactions {
 		unimplemented!()
 	}
 
-	fn remove_invalid(&self, _hashes: &[TxHash<Self>]) -> Vec<Arc<Self::InPoolTransaction>> {
-		Default::default()
+	fn remove_invalid_detailed(
+		&self,
+		_hashes: &[TxHash<Self>],
+	) -> InvalidityReport<TxHash<Self>, Arc<Self::InPoolTransaction>> {
+		InvalidityReport { outcomes: Default::default(), removed: Vec::new() }
 	}
 
-	fn futures(&self) -> Vec<Self::InPoolTransaction> {
+	fn futures(&self) -> Vec<Arc<Self::InPoolTransaction>> {
 		unimplemented!()
 	}
 
@@ -290,10 +309,29 @@ impl sc_transaction_pool_api::TransactionPool for Transactions {
 		unimplemented!()
 	}
 
+	fn priority_threshold(&self, _within_bytes: usize) -> Option<TransactionPriority> {
+		unimplemented!()
+	}
+
+	fn min_ready_priority(&self) -> Option<TransactionPriority> {
+		unimplemented!()
+	}
+
+	fn min_replacement_priority(&self, _tag: &TransactionTag) -> Option<TransactionPriority> {
+		unimplemented!()
+	}
+
 	fn import_notification_stream(&self) -> ImportNotificationStream<TxHash<Self>> {
 		unimplemented!()
 	}
 
+	fn import_notification_stream_with_overflow(
+		&self,
+		_buffer_size: Option<usize>,
+	) -> (ImportNotificationStream<TxHash<Self>>, OverflowHandle<TxHash<Self>>) {
+		unimplemented!()
+	}
+
 	fn on_broadcasted(&self, _propagations: HashMap<TxHash<Self>, Vec<String>>) {
 		unimplemented!()
 	}
@@ -305,4 +343,23 @@ impl sc_transaction_pool_api::TransactionPool for Transactions {
 	fn ready_transaction(&self, _hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>> {
 		unimplemented!()
 	}
+
+	fn wait_ready(
+		&self,
+		_hash: TxHash<Self>,
+		_timeout: Option<Duration>,
+	) -> Pin<Box<dyn Future<Output = WaitReadyOutcomeFor<Self>> + Send>> {
+		unimplemented!()
+	}
+
+	fn export_state(&self) -> PoolSnapshot {
+		unimplemented!()
+	}
+
+	fn import_state(
+		&self,
+		_snapshot: PoolSnapshot,
+	) -> Pin<Box<dyn Future<Output = PoolSnapshotImport> + Send>> {
+		unimplemented!()
+	}
 }
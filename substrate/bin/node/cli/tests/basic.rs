@@ -353,6 +353,7 @@ fn full_native_block_import_works() {
 				event: RuntimeEvent::Balances(pallet_balances::Event::Withdraw {
 					who: alice().into(),
 					amount: fees,
+					context: None,
 				}),
 				topics: vec![],
 			},
@@ -370,6 +371,7 @@ fn full_native_block_import_works() {
 				event: RuntimeEvent::Balances(pallet_balances::Event::Deposit {
 					who: pallet_treasury::Pallet::<Runtime>::account_id(),
 					amount: fees * 8 / 10,
+					context: None,
 				}),
 				topics: vec![],
 			},
@@ -438,6 +440,7 @@ fn full_native_block_import_works() {
 				event: RuntimeEvent::Balances(pallet_balances::Event::Withdraw {
 					who: bob().into(),
 					amount: fees,
+					context: None,
 				}),
 				topics: vec![],
 			},
@@ -455,6 +458,7 @@ fn full_native_block_import_works() {
 				event: RuntimeEvent::Balances(pallet_balances::Event::Deposit {
 					who: pallet_treasury::Pallet::<Runtime>::account_id(),
 					amount: fees * 8 / 10,
+					context: None,
 				}),
 				topics: vec![],
 			},
@@ -488,6 +492,7 @@ fn full_native_block_import_works() {
 				event: RuntimeEvent::Balances(pallet_balances::Event::Withdraw {
 					who: alice().into(),
 					amount: fees,
+					context: None,
 				}),
 				topics: vec![],
 			},
@@ -505,6 +510,7 @@ fn full_native_block_import_works() {
 				event: RuntimeEvent::Balances(pallet_balances::Event::Deposit {
 					who: pallet_treasury::Pallet::<Runtime>::account_id(),
 					amount: fees * 8 / 10,
+					context: None,
 				}),
 				topics: vec![],
 			},
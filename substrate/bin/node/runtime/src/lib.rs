@@ -337,17 +337,26 @@ impl pallet_utility::Config for Runtime {
 parameter_types! {
 	// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
 	pub const DepositBase: Balance = deposit(1, 88);
-	// Additional storage item size of 32 bytes.
-	pub const DepositFactor: Balance = deposit(0, 32);
+	// Additional storage item size of an approval, 32+4+33 bytes = 69 bytes.
+	pub const DepositFactor: Balance = deposit(0, 69);
 }
 
 impl pallet_multisig::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
 	type Currency = Balances;
+	type OldCurrency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = ConstU32<100>;
+	// Pausing multisig operations is disabled.
+	type GuardianOrigin = frame_system::EnsureNever<()>;
+	type MaxPauseDuration = ConstU32<0>;
+	type CancelPenalty = ();
+	type CancelSlashHandler = ();
+	type DepositExempt = Nothing;
+	type LenientSignatoryOrder = ();
 	type WeightInfo = pallet_multisig::weights::SubstrateWeight<Runtime>;
 }
 
@@ -540,9 +549,20 @@ impl pallet_balances::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = frame_system::Pallet<Runtime>;
+	type AuthoritativeStore = pallet_balances::SystemStore;
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
 	type FreezeIdentifier = RuntimeFreezeReason;
 	type MaxFreezes = ConstU32<1>;
+	type ResidueQuantum = ();
+	type ResidueThreshold = ();
+	type MaxExpiringPerBlock = ();
+	type MinTransferAmount = ();
+	type LockToFreezeMapping = ();
+	type ManagedFreezes = ();
+	type BurnableHolds = ();
+	type LenientFreezeBound = ();
+	type DoneSlashHandler = ();
+	type OnInconsistency = ();
 }
 
 parameter_types! {
@@ -2559,18 +2579,27 @@ type EventRecord = frame_system::EventRecord<
 
 parameter_types! {
 	pub const BeefySetIdSessionEntries: u32 = BondingDuration::get() * SessionsPerEra::get();
+	pub const BeefyConcurrentKeyReportDeposit: Balance = DOLLARS;
 }
 
 impl pallet_beefy::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
 	type BeefyId = BeefyId;
 	type MaxAuthorities = MaxAuthorities;
 	type MaxNominators = ConstU32<0>;
 	type MaxSetIdSessionEntries = BeefySetIdSessionEntries;
 	type OnNewValidatorSet = MmrLeaf;
 	type WeightInfo = ();
+	type DisabledValidators = Session;
 	type KeyOwnerProof = <Historical as KeyOwnerProofSystem<(KeyTypeId, BeefyId)>>::Proof;
 	type EquivocationReportSystem =
 		pallet_beefy::EquivocationReportSystem<Self, Offences, Historical, ReportLongevity>;
+	type HistoricalProofProvider = pallet_beefy::ArchivedSessionProofs<Self>;
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type ConcurrentKeyReportDeposit = BeefyConcurrentKeyReportDeposit;
+	type MaxConcurrentKeyReports = ConstU32<16>;
+	type DisableStrategy = ConstBool<false>;
 }
 
 /// MMR helper types.
@@ -2762,6 +2791,35 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(5)]
+	impl pallet_balances::runtime_api::BalancesApi<Block, AccountId, Balance> for Runtime {
+		fn simulate_force_set_balance(
+			who: AccountId,
+			new_free: Balance,
+		) -> Result<pallet_balances::ForceSetOutcome<Balance>, sp_runtime::DispatchError> {
+			Balances::simulate_force_set_balance(&who, new_free)
+		}
+
+		fn transfer_all_preview(
+			who: AccountId,
+			keep_alive: bool,
+		) -> (Balance, pallet_balances::TransferAllRemainder<Balance>) {
+			Balances::transfer_all_preview(&who, keep_alive)
+		}
+
+		fn last_issuance_audit() -> Option<pallet_balances::IssuanceAudit<AccountId, Balance>> {
+			Balances::last_issuance_audit()
+		}
+
+		fn lock_summary(who: AccountId) -> pallet_balances::LockSummary<Balance> {
+			Balances::lock_summary(&who)
+		}
+
+		fn spendable_breakdown(who: AccountId) -> pallet_balances::SpendableBreakdown<Balance> {
+			Balances::spendable_breakdown(&who)
+		}
+	}
+
 	impl pallet_nomination_pools_runtime_api::NominationPoolsApi<Block, AccountId, Balance> for Runtime {
 		fn pending_rewards(who: AccountId) -> Balance {
 			NominationPools::api_pending_rewards(who).unwrap_or_default()
@@ -30,7 +30,7 @@ use futures::{
 use log::{debug, error, info, trace, warn};
 use sc_block_builder::{BlockBuilderApi, BlockBuilderBuilder};
 use sc_telemetry::{telemetry, TelemetryHandle, CONSENSUS_INFO};
-use sc_transaction_pool_api::{InPoolTransaction, TransactionPool};
+use sc_transaction_pool_api::{InPoolTransaction, InclusionOutcome, TransactionPool, TxHash};
 use sp_api::{ApiExt, CallApiAt, ProvideRuntimeApi};
 use sp_blockchain::{ApplyExtrinsicFailed::Validity, Error::ApplyExtrinsicFailed, HeaderBackend};
 use sp_consensus::{DisableProofRecording, EnableProofRecording, ProofRecording, Proposal};
@@ -336,13 +336,16 @@ where
 		self.apply_inherents(&mut block_builder, inherent_data)?;
 
 		let mode = block_builder.extrinsic_inclusion_mode();
+		let mut outcomes = Vec::new();
 		let end_reason = match mode {
 			ExtrinsicInclusionMode::AllExtrinsics =>
-				self.apply_extrinsics(&mut block_builder, deadline, block_size_limit).await?,
+				self.apply_extrinsics(&mut block_builder, deadline, block_size_limit, &mut outcomes)
+					.await?,
 			ExtrinsicInclusionMode::OnlyInherents => EndProposingReason::TransactionForbidden,
 		};
 		let (block, storage_changes, proof) = block_builder.build()?.into_inner();
 		let block_took = block_timer.elapsed();
+		self.transaction_pool.note_inclusion_outcome(block.hash(), outcomes);
 
 		let proof =
 			PR::into_proof(proof).map_err(|e| sp_blockchain::Error::Application(Box::new(e)))?;
@@ -396,11 +399,14 @@ where
 	}
 
 	/// Apply as many extrinsics as possible to the block.
+	///
+	/// What happened to every transaction tried is pushed to `outcomes`.
 	async fn apply_extrinsics(
 		&self,
 		block_builder: &mut sc_block_builder::BlockBuilder<'_, Block, C>,
 		deadline: time::Instant,
 		block_size_limit: Option<usize>,
+		outcomes: &mut Vec<(TxHash<A>, InclusionOutcome)>,
 	) -> Result<EndProposingReason, sp_blockchain::Error> {
 		// proceed with transactions
 		// We calculate soft deadline used only in case we start skipping transactions.
@@ -463,6 +469,7 @@ where
 				block_builder.estimate_block_size(self.include_proof_in_block_size_estimation);
 			if block_size + pending_tx_data.encoded_size() > block_size_limit {
 				pending_iterator.report_invalid(&pending_tx);
+				outcomes.push((pending_tx_hash, InclusionOutcome::ExhaustsResources));
 				if skipped < MAX_SKIPPED_TRANSACTIONS {
 					skipped += 1;
 					debug!(
@@ -494,9 +501,11 @@ where
 				Ok(()) => {
 					transaction_pushed = true;
 					debug!(target: LOG_TARGET, "[{:?}] Pushed to the block.", pending_tx_hash);
+					outcomes.push((pending_tx_hash, InclusionOutcome::Included));
 				},
 				Err(ApplyExtrinsicFailed(Validity(e))) if e.exhausted_resources() => {
 					pending_iterator.report_invalid(&pending_tx);
+					outcomes.push((pending_tx_hash, InclusionOutcome::ExhaustsResources));
 					if skipped < MAX_SKIPPED_TRANSACTIONS {
 						skipped += 1;
 						debug!(target: LOG_TARGET,
@@ -522,6 +531,7 @@ where
 						target: LOG_TARGET,
 						"[{:?}] Invalid transaction: {}", pending_tx_hash, e
 					);
+					outcomes.push((pending_tx_hash.clone(), InclusionOutcome::Invalid));
 					unqueue_invalid.push(pending_tx_hash);
 				},
 			}
@@ -891,6 +901,73 @@ mod tests {
 		assert_eq!(txpool.ready().count(), 0);
 	}
 
+	#[test]
+	fn should_report_inclusion_outcome_to_the_pool() {
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let txpool = BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			spawner.clone(),
+			client.clone(),
+		);
+
+		let medium = |nonce| {
+			ExtrinsicBuilder::new_fill_block(Perbill::from_parts(MEDIUM))
+				.nonce(nonce)
+				.build()
+		};
+		let huge = |nonce| {
+			ExtrinsicBuilder::new_fill_block(Perbill::from_parts(HUGE)).nonce(nonce).build()
+		};
+		let xts = vec![medium(0), medium(1), huge(2), medium(3), medium(4)];
+		let hashes = xts.iter().map(|xt| txpool.hash_of(xt)).collect::<Vec<_>>();
+
+		block_on(txpool.submit_at(client.info().genesis_hash, SOURCE, xts)).unwrap();
+		block_on(
+			txpool.maintain(chain_event(
+				client
+					.expect_header(client.info().genesis_hash)
+					.expect("there should be header"),
+			)),
+		);
+		assert!(txpool.last_inclusion_report().is_none());
+
+		let mut proposer_factory =
+			ProposerFactory::new(spawner.clone(), client.clone(), txpool.clone(), None, None);
+		let proposer = proposer_factory.init_with_now(
+			&client.expect_header(client.info().genesis_hash).unwrap(),
+			Box::new(move || time::Instant::now()),
+		);
+
+		// when
+		let deadline = time::Duration::from_secs(900);
+		let block =
+			block_on(proposer.propose(Default::default(), Default::default(), deadline, None))
+				.map(|r| r.block)
+				.unwrap();
+
+		// then
+		// the huge transaction does not fit anymore, and the ones depending on it are not tried.
+		assert_eq!(block.extrinsics().len(), 2);
+		let report = txpool.last_inclusion_report().unwrap();
+		assert_eq!(report.block_hash, block.hash());
+		assert_eq!(report.included, 2);
+		assert_eq!(report.exhausted_resources, 1);
+		assert_eq!(report.invalid, 0);
+		assert_eq!(report.missing_dependency, 2);
+		assert_eq!(
+			report.skipped,
+			vec![
+				(hashes[2], InclusionOutcome::ExhaustsResources),
+				(hashes[3], InclusionOutcome::MissingDependency),
+				(hashes[4], InclusionOutcome::MissingDependency),
+			],
+		);
+	}
+
 	#[test]
 	fn should_cease_building_block_when_block_limit_is_reached() {
 		let client = Arc::new(substrate_test_runtime_client::new());
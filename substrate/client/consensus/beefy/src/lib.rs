@@ -46,7 +46,10 @@ use sp_consensus_beefy::{
 	AuthorityIdBound, BeefyApi, ConsensusLog, PayloadProvider, ValidatorSet, BEEFY_ENGINE_ID,
 };
 use sp_keystore::KeystorePtr;
-use sp_runtime::traits::{Block, Header as HeaderT, NumberFor, Zero};
+use sp_runtime::{
+	traits::{Block, Header as HeaderT, NumberFor, Zero},
+	SaturatedConversion,
+};
 use std::{
 	collections::{BTreeMap, VecDeque},
 	marker::PhantomData,
@@ -751,3 +754,19 @@ where
 	};
 	header.digest().convert_first(|l| l.try_to(id).and_then(filter))
 }
+
+/// Scan the `header` digest log for a BEEFY genesis reset. Return either the new BEEFY genesis
+/// block number or `None` in case no reset has been signaled.
+pub(crate) fn find_genesis_reset<B, AuthorityId>(header: &B::Header) -> Option<NumberFor<B>>
+where
+	B: Block,
+	AuthorityId: AuthorityIdBound,
+{
+	let id = OpaqueDigestItemId::Consensus(&BEEFY_ENGINE_ID);
+
+	let filter = |log: ConsensusLog<AuthorityId>| match log {
+		ConsensusLog::GenesisReset { genesis_block, .. } => Some(genesis_block.saturated_into()),
+		_ => None,
+	};
+	header.digest().convert_first(|l| l.try_to(id).and_then(filter))
+}
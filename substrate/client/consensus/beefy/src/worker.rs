@@ -22,7 +22,7 @@ use crate::{
 		request_response::outgoing_requests_engine::ResponseInfo,
 	},
 	error::Error,
-	find_authorities_change,
+	find_authorities_change, find_genesis_reset,
 	fisherman::Fisherman,
 	justification::BeefyVersionedFinalityProof,
 	keystore::BeefyKeystore,
@@ -458,6 +458,13 @@ where
 			notification.tree_route,
 		);
 
+		// A genesis reset digest signals the restart without having to query the runtime.
+		if find_genesis_reset::<B, AuthorityId>(header)
+			.is_some_and(|genesis| genesis != self.persisted_state.pallet_genesis)
+		{
+			return Err(Error::ConsensusReset)
+		}
+
 		self.runtime
 			.runtime_api()
 			.beefy_genesis(header.hash())
@@ -1423,6 +1430,33 @@ pub(crate) mod tests {
 		assert_eq!(extracted, Some(validator_set));
 	}
 
+	#[test]
+	fn extract_genesis_reset_digest() {
+		let mut header = Header::new(
+			1u32.into(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Digest::default(),
+		);
+
+		// verify empty digest shows nothing
+		assert!(find_genesis_reset::<Block, ecdsa_crypto::AuthorityId>(&header).is_none());
+
+		header.digest_mut().push(DigestItem::Consensus(
+			BEEFY_ENGINE_ID,
+			ConsensusLog::<ecdsa_crypto::AuthorityId>::GenesisReset {
+				genesis_block: 42,
+				validator_set_id: 7,
+			}
+			.encode(),
+		));
+
+		// verify the new genesis is correctly extracted from digest
+		let extracted = find_genesis_reset::<Block, ecdsa_crypto::AuthorityId>(&header);
+		assert_eq!(extracted, Some(42));
+	}
+
 	#[tokio::test]
 	async fn should_finalize_correctly() {
 		let keys = [Keyring::Alice];
@@ -23,7 +23,7 @@ pub mod hash;
 
 use error::Error;
 use jsonrpsee::proc_macros::rpc;
-use sc_transaction_pool_api::TransactionStatus;
+use sc_transaction_pool_api::{TransactionPriority, TransactionStatus};
 use sp_core::Bytes;
 
 /// Substrate authoring RPC API
@@ -59,6 +59,13 @@ pub trait AuthorApi<Hash, BlockHash> {
 	#[method(name = "author_pendingExtrinsics")]
 	fn pending_extrinsics(&self) -> Result<Vec<Bytes>, Error>;
 
+	/// Returns the priority an extrinsic providing the given tag has to exceed to replace the
+	/// ready extrinsic currently providing it.
+	///
+	/// Returns `None` if no ready extrinsic provides the tag.
+	#[method(name = "author_minReplacementPriority")]
+	fn min_replacement_priority(&self, tag: Bytes) -> Result<Option<TransactionPriority>, Error>;
+
 	/// Remove given extrinsic from the pool and temporarily ban it to prevent reimporting.
 	#[method(name = "author_removeExtrinsic")]
 	fn remove_extrinsic(
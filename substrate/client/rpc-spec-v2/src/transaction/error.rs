@@ -96,6 +96,17 @@ impl<Hash> From<Error> for TransactionEvent<Hash> {
 				TransactionEvent::Invalid(TransactionError {
 					error: "The pool is not accepting future transactions".into(),
 				}),
+			Error::Pool(PoolError::FutureNonce { .. }) =>
+				TransactionEvent::Invalid(TransactionError {
+					error: "The transaction requires tags that are not provided yet".into(),
+				}),
+			Error::Pool(PoolError::StalePriority) => TransactionEvent::Invalid(TransactionError {
+				error: "The transaction does not outbid the transaction occupying its slot".into(),
+			}),
+			Error::Pool(PoolError::ValidationPanicked(e)) =>
+				TransactionEvent::Invalid(TransactionError {
+					error: format!("The runtime panicked while validating the transaction: {}", e),
+				}),
 		}
 	}
 }
@@ -20,15 +20,17 @@ use codec::Encode;
 use futures::Future;
 use sc_transaction_pool::BasicPool;
 use sc_transaction_pool_api::{
-	ImportNotificationStream, PoolFuture, PoolStatus, ReadyTransactions, TransactionFor,
-	TransactionPool, TransactionSource, TransactionStatusStreamFor, TxHash,
+	BlockHash, ImportNotificationStream, InvalidityReport, OverflowHandle, PoolFuture,
+	PoolSnapshot, PoolSnapshotImport, PoolStatus, ReadyTransactions, TransactionFor,
+	TransactionPool, TransactionPriority, TransactionSource, TransactionStatusStreamFor,
+	TransactionTag, TxHash, WaitReadyOutcomeFor,
 };
 
 use crate::hex_string;
 use futures::{FutureExt, StreamExt};
 
 use sp_runtime::traits::{Block as BlockT, NumberFor};
-use std::{collections::HashMap, pin::Pin, sync::Arc};
+use std::{collections::HashMap, pin::Pin, sync::Arc, time::Duration};
 use substrate_test_runtime_transaction_pool::TestApi;
 use tokio::sync::mpsc;
 
@@ -140,22 +142,66 @@ impl TransactionPool for MiddlewarePool {
 		.boxed()
 	}
 
-	fn remove_invalid(&self, hashes: &[TxHash<Self>]) -> Vec<Arc<Self::InPoolTransaction>> {
-		self.inner_pool.remove_invalid(hashes)
+	fn watch_existing(
+		&self,
+		hash: TxHash<Self>,
+	) -> Option<Pin<Box<TransactionStatusStreamFor<Self>>>> {
+		self.inner_pool.watch_existing(hash)
+	}
+
+	fn remove_invalid_detailed(
+		&self,
+		hashes: &[TxHash<Self>],
+	) -> InvalidityReport<TxHash<Self>, Arc<Self::InPoolTransaction>> {
+		self.inner_pool.remove_invalid_detailed(hashes)
 	}
 
 	fn status(&self) -> PoolStatus {
 		self.inner_pool.status()
 	}
 
+	fn priority_threshold(&self, within_bytes: usize) -> Option<TransactionPriority> {
+		self.inner_pool.priority_threshold(within_bytes)
+	}
+
+	fn min_ready_priority(&self) -> Option<TransactionPriority> {
+		self.inner_pool.min_ready_priority()
+	}
+
+	fn min_replacement_priority(&self, tag: &TransactionTag) -> Option<TransactionPriority> {
+		self.inner_pool.min_replacement_priority(tag)
+	}
+
 	fn import_notification_stream(&self) -> ImportNotificationStream<TxHash<Self>> {
 		self.inner_pool.import_notification_stream()
 	}
 
+	fn import_notification_stream_with_buffer(
+		&self,
+		buffer_size: usize,
+	) -> ImportNotificationStream<TxHash<Self>> {
+		self.inner_pool.import_notification_stream_with_buffer(buffer_size)
+	}
+
+	fn dropped_import_notifications(&self) -> u64 {
+		self.inner_pool.dropped_import_notifications()
+	}
+
+	fn import_notification_stream_with_overflow(
+		&self,
+		buffer_size: Option<usize>,
+	) -> (ImportNotificationStream<TxHash<Self>>, OverflowHandle<TxHash<Self>>) {
+		self.inner_pool.import_notification_stream_with_overflow(buffer_size)
+	}
+
 	fn hash_of(&self, xt: &TransactionFor<Self>) -> TxHash<Self> {
 		self.inner_pool.hash_of(xt)
 	}
 
+	fn hash_of_at(&self, xt: &TransactionFor<Self>, at: BlockHash<Self>) -> TxHash<Self> {
+		self.inner_pool.hash_of_at(xt, at)
+	}
+
 	fn on_broadcasted(&self, propagations: HashMap<TxHash<Self>, Vec<String>>) {
 		self.inner_pool.on_broadcasted(propagations)
 	}
@@ -177,11 +223,51 @@ impl TransactionPool for MiddlewarePool {
 		self.inner_pool.ready_at(at)
 	}
 
+	fn ready_at_head(
+		&self,
+		at: BlockHash<Self>,
+	) -> Pin<
+		Box<
+			dyn Future<
+					Output = Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send>,
+				> + Send,
+		>,
+	> {
+		self.inner_pool.ready_at_head(at)
+	}
+
 	fn ready(&self) -> Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send> {
 		self.inner_pool.ready()
 	}
 
-	fn futures(&self) -> Vec<Self::InPoolTransaction> {
+	fn futures(&self) -> Vec<Arc<Self::InPoolTransaction>> {
 		self.inner_pool.futures()
 	}
+
+	fn futures_paged(
+		&self,
+		offset: usize,
+		limit: usize,
+	) -> (Vec<Arc<Self::InPoolTransaction>>, usize) {
+		self.inner_pool.futures_paged(offset, limit)
+	}
+
+	fn wait_ready(
+		&self,
+		hash: TxHash<Self>,
+		timeout: Option<Duration>,
+	) -> Pin<Box<dyn Future<Output = WaitReadyOutcomeFor<Self>> + Send>> {
+		self.inner_pool.wait_ready(hash, timeout)
+	}
+
+	fn export_state(&self) -> PoolSnapshot {
+		self.inner_pool.export_state()
+	}
+
+	fn import_state(
+		&self,
+		snapshot: PoolSnapshot,
+	) -> Pin<Box<dyn Future<Output = PoolSnapshotImport> + Send>> {
+		self.inner_pool.import_state(snapshot)
+	}
 }
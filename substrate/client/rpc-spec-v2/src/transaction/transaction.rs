@@ -154,6 +154,6 @@ pub fn handle_event<Hash: Clone, BlockHash: Clone>(
 			error: "Extrinsic marked as invalid".into(),
 		})),
 		// These are the events that are not supported by the new API.
-		TransactionStatus::Broadcast(_) => None,
+		TransactionStatus::Broadcast(_) | TransactionStatus::WatchingDegraded => None,
 	}
 }
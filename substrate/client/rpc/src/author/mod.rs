@@ -34,7 +34,7 @@ use jsonrpsee::{core::async_trait, types::ErrorObject, PendingSubscriptionSink};
 use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::{
 	error::IntoPoolError, BlockHash, InPoolTransaction, TransactionFor, TransactionPool,
-	TransactionSource, TxHash,
+	TransactionPriority, TransactionSource, TxHash,
 };
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
@@ -153,6 +153,10 @@ where
 		Ok(self.pool.ready().map(|tx| tx.data().encode().into()).collect())
 	}
 
+	fn min_replacement_priority(&self, tag: Bytes) -> Result<Option<TransactionPriority>> {
+		Ok(self.pool.min_replacement_priority(&tag.to_vec()))
+	}
+
 	fn remove_extrinsic(
 		&self,
 		bytes_or_hash: Vec<hash::ExtrinsicOrHash<TxHash<P>>>,
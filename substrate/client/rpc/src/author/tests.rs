@@ -175,6 +175,25 @@ async fn author_should_return_pending_extrinsics() {
 	assert_eq!(pending, vec![xt_bytes]);
 }
 
+#[tokio::test]
+async fn author_should_return_min_replacement_priority() {
+	const METHOD: &'static str = "author_minReplacementPriority";
+	let setup = TestSetup::default();
+	let api = setup.author().into_rpc();
+
+	let tag = Bytes(b"unknown".to_vec());
+	let priority: Option<u64> = api.call(METHOD, [tag]).await.unwrap();
+	assert_eq!(priority, None);
+
+	let xt = to_hex(&uxt(AccountKeyring::Alice, 0).encode(), true);
+	api.call::<_, H256>("author_submitExtrinsic", [xt]).await.unwrap();
+	let ready = setup.pool.ready().next().unwrap();
+
+	let tag = Bytes(ready.provides()[0].clone());
+	let priority: Option<u64> = api.call(METHOD, [tag]).await.unwrap();
+	assert_eq!(priority, Some(*ready.priority()));
+}
+
 #[tokio::test]
 async fn author_should_remove_extrinsics() {
 	const METHOD: &'static str = "author_removeExtrinsic";
@@ -501,6 +501,11 @@ where
 				Err(e) => match e.into_pool_error() {
 					Ok(sc_transaction_pool_api::error::Error::AlreadyImported(_)) =>
 						TransactionImport::KnownGood,
+					Ok(e) if e.is_node_fault() => {
+						debug!("Transaction could not be validated by the local node: {}", e);
+						// the peer is innocent, the local runtime failed.
+						TransactionImport::KnownGood
+					},
 					Ok(e) => {
 						debug!("Error adding transaction to the pool: {:?}", e);
 						TransactionImport::Bad
@@ -19,7 +19,7 @@
 //! Transaction pool errors.
 
 use sp_runtime::transaction_validity::{
-	InvalidTransaction, TransactionPriority as Priority, UnknownTransaction,
+	InvalidTransaction, TransactionPriority as Priority, TransactionTag, UnknownTransaction,
 };
 
 /// Transaction pool result.
@@ -69,6 +69,26 @@ pub enum Error {
 
 	#[error("The pool is not accepting future transactions")]
 	RejectedFutureTransaction,
+
+	/// A local transaction requires tags that are not provided yet, e.g. because of a nonce gap,
+	/// and the pool is not accepting future transactions.
+	#[error("Transaction requires tags that are not provided yet: {expected_provides:?}")]
+	FutureNonce {
+		/// The tags that have to be provided by other transactions first.
+		expected_provides: Vec<TransactionTag>,
+	},
+
+	/// A local transaction does not have a higher priority than the transaction already
+	/// occupying its slot in the pool.
+	#[error("Transaction does not outbid the transaction occupying its slot")]
+	StalePriority,
+
+	/// The runtime panicked or trapped while validating the transaction.
+	///
+	/// This points at a bug of the local runtime rather than at a bad transaction, so the peer
+	/// which sent the transaction should not be held responsible, see [`Error::is_node_fault`].
+	#[error("Runtime panicked while validating the transaction: {0}")]
+	ValidationPanicked(String),
 }
 
 impl Error {
@@ -87,12 +107,20 @@ impl Error {
 			// The node might be lagging behind, or during a warp sync.
 			Error::InvalidBlockId(_) |
 			// The pool is configured to not accept future transactions.
-			Error::RejectedFutureTransaction => {
+			Error::RejectedFutureTransaction |
+			// The transactions providing the missing tags may be imported in the meantime.
+			Error::FutureNonce { .. } => {
 				true
 			}
 			_ => false
 		}
 	}
+
+	/// Returns true if the error is caused by the local node rather than by the transaction
+	/// itself, so that the peer which sent the transaction should not be penalized.
+	pub fn is_node_fault(&self) -> bool {
+		matches!(self, Error::ValidationPanicked(_))
+	}
 }
 
 /// Transaction pool error conversion.
@@ -22,19 +22,43 @@
 pub mod error;
 
 use async_trait::async_trait;
-use codec::Codec;
-use futures::{Future, Stream};
+use codec::{Codec, Decode, Encode};
+use futures::{
+	future::{BoxFuture, Either},
+	Future, FutureExt, Stream, StreamExt, TryFutureExt,
+};
+use parking_lot::Mutex;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use sp_core::offchain::TransactionPoolExt;
 use sp_runtime::traits::{Block as BlockT, Member, NumberFor};
-use std::{collections::HashMap, hash::Hash, marker::PhantomData, pin::Pin, sync::Arc};
+use std::{
+	collections::{HashMap, HashSet, VecDeque},
+	hash::Hash,
+	marker::PhantomData,
+	pin::Pin,
+	sync::Arc,
+	time::{Duration, Instant},
+};
 
 const LOG_TARGET: &str = "txpool::api";
 
 pub use sp_runtime::transaction_validity::{
-	TransactionLongevity, TransactionPriority, TransactionSource, TransactionTag,
+	TransactionLongevity, TransactionPriority, TransactionSource, TransactionTag, ValidTransaction,
 };
 
+/// The percentage by which a transaction has to outbid the priority of the transactions it
+/// replaces.
+///
+/// A replacement is only accepted if its priority exceeds the priority of the replaced
+/// transactions increased by this percentage, see [`TransactionPool::min_replacement_priority`].
+pub const REPLACEMENT_PRIORITY_BUMP_PERCENT: TransactionPriority = 0;
+
+/// Returns the priority a transaction has to exceed to replace transactions with the (summed)
+/// priority `priority`, see [`REPLACEMENT_PRIORITY_BUMP_PERCENT`].
+pub fn replacement_priority(priority: TransactionPriority) -> TransactionPriority {
+	priority.saturating_add(priority.saturating_mul(REPLACEMENT_PRIORITY_BUMP_PERCENT) / 100)
+}
+
 /// Transaction pool status.
 #[derive(Debug)]
 pub struct PoolStatus {
@@ -46,6 +70,10 @@ pub struct PoolStatus {
 	pub future: usize,
 	/// Sum of bytes of ready transaction encodings.
 	pub future_bytes: usize,
+	/// Lowest priority in the ready queue, if the ready queue limits are reached.
+	///
+	/// A new transaction has to outbid it to enter the ready queue.
+	pub min_ready_priority: Option<TransactionPriority>,
 }
 
 impl PoolStatus {
@@ -55,6 +83,190 @@ impl PoolStatus {
 	}
 }
 
+/// Percentiles of the time the transactions of a queue spent in the pool so far.
+///
+/// Percentiles are computed with the nearest-rank method and are `None` for an empty queue.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AgePercentiles {
+	/// The median age.
+	pub p50: Option<Duration>,
+	/// The age not exceeded by 95% of the transactions.
+	pub p95: Option<Duration>,
+}
+
+impl AgePercentiles {
+	/// Compute the percentiles of the given ages, in any order.
+	pub fn from_ages(mut ages: Vec<Duration>) -> Self {
+		ages.sort_unstable();
+		// The smallest age such that at least `percent` of the ages do not exceed it.
+		let nearest_rank = |percent: usize| {
+			let rank = (ages.len() * percent).div_ceil(100).max(1);
+			ages.get(rank - 1).copied()
+		};
+		Self { p50: nearest_rank(50), p95: nearest_rank(95) }
+	}
+}
+
+/// Transaction pool status, including the time the transactions spent in the pool so far.
+///
+/// See [`TransactionPool::status_detailed`].
+#[derive(Debug)]
+pub struct PoolStatusDetailed {
+	/// The regular status of the pool.
+	pub status: PoolStatus,
+	/// Age of the oldest transaction in the ready queue.
+	pub oldest_ready_age: Option<Duration>,
+	/// Age of the oldest transaction in the future queue.
+	pub oldest_future_age: Option<Duration>,
+	/// Percentiles of the age of the transactions in the ready queue.
+	pub ready_age_percentiles: AgePercentiles,
+	/// Percentiles of the age of the transactions in the future queue.
+	pub future_age_percentiles: AgePercentiles,
+}
+
+impl From<PoolStatus> for PoolStatusDetailed {
+	/// A detailed status without any information on the age of the transactions.
+	fn from(status: PoolStatus) -> Self {
+		Self {
+			status,
+			oldest_ready_age: None,
+			oldest_future_age: None,
+			ready_age_percentiles: Default::default(),
+			future_age_percentiles: Default::default(),
+		}
+	}
+}
+
+/// What pruning the transactions of a block would do to the ready and future queues.
+///
+/// See [`TransactionPool::prune_preview`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PrunePreview {
+	/// Number of ready transactions that would be pruned as included in the block.
+	pub would_prune: usize,
+	/// Number of ready transactions that would stay in the ready queue.
+	pub would_remain_ready: usize,
+	/// Number of future transactions that would be promoted to the ready queue.
+	pub would_promote_from_future: usize,
+}
+
+/// Status of the watchers of a pool, see [`TransactionPool::submit_and_watch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatcherStatus {
+	/// Number of watchers receiving all events of their transaction.
+	pub watchers: usize,
+	/// Maximum number of watchers receiving all events. Watchers created beyond it only receive
+	/// the last event of their transaction.
+	pub max_watchers: usize,
+}
+
+/// The peers a transaction was broadcast to, see [`TransactionPool::broadcast_info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BroadcastInfo {
+	/// The distinct peers the transaction was broadcast to, in the order they were first
+	/// reported. Peers reported beyond the configured maximum are not recorded.
+	pub peers: Vec<String>,
+	/// When the transaction was first reported as broadcast.
+	pub first_broadcast: Instant,
+	/// When the transaction was last reported as broadcast.
+	pub last_broadcast: Instant,
+}
+
+/// Limits applied to the iterator returned by [`TransactionPool::ready_at_with_limits`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReadyLimits {
+	/// Maximal sum of the encoded sizes of the returned transactions.
+	pub max_bytes: Option<usize>,
+	/// Maximal number of the returned transactions.
+	pub max_transactions: Option<usize>,
+	/// Maximal number of transactions exceeding the remaining byte budget which are skipped
+	/// before the budget is considered exhausted.
+	///
+	/// Bounds the work spent on a nearly full budget, at the cost of missing the transactions
+	/// which would still fit further down the ready set. `None` keeps scanning the whole ready
+	/// set. Only applies together with `max_bytes`.
+	pub max_skipped_oversized: Option<usize>,
+}
+
+/// The ready transactions returned by [`TransactionPool::ready_at_cancellable`].
+#[derive(Debug)]
+pub enum ReadyResult<R> {
+	/// The pool was maintained at the requested block and `R` yields its ready transactions.
+	Complete(R),
+	/// Waiting for the pool to be maintained at the requested block was cancelled.
+	BestEffort {
+		/// The ready transactions of the pool at the time of the cancellation.
+		ready: R,
+		/// Whether the pool was not maintained at the requested block yet when `ready` was
+		/// collected, so that `ready` may contain stale transactions and miss new ones.
+		pending_maintenance: bool,
+	},
+}
+
+impl<R> ReadyResult<R> {
+	/// Whether the pool was maintained at the requested block.
+	pub fn is_complete(&self) -> bool {
+		matches!(self, Self::Complete(_))
+	}
+
+	/// The ready transactions, regardless of the classification.
+	pub fn into_inner(self) -> R {
+		match self {
+			Self::Complete(ready) | Self::BestEffort { ready, .. } => ready,
+		}
+	}
+}
+
+/// The class of a transaction, partitioning the pool.
+///
+/// Every class may be given its own limits, see [`TransactionPool::set_class_limits`], so that
+/// transactions of one class cannot evict transactions of another one. When the limits of the
+/// whole pool are exceeded, transactions of a lower class are evicted first, following the
+/// order of the variants.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PoolClass {
+	/// Regular transactions.
+	#[default]
+	Normal,
+	/// Operational transactions, e.g. oracle feeds, evicted only after all normal ones.
+	Operational,
+	/// Transactions that are evicted last.
+	Mandatory,
+}
+
+/// Assigns a [`PoolClass`] to every transaction entering the pool.
+pub trait TransactionClassifier<Block: BlockT>: Send + Sync {
+	/// Returns the class of the transaction `tx` with the given `validity`.
+	fn classify(&self, tx: &Block::Extrinsic, validity: &ValidTransaction) -> PoolClass;
+}
+
+/// A [`TransactionClassifier`] putting all transactions into [`PoolClass::Normal`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultClassifier;
+
+impl<Block: BlockT> TransactionClassifier<Block> for DefaultClassifier {
+	fn classify(&self, _tx: &Block::Extrinsic, _validity: &ValidTransaction) -> PoolClass {
+		PoolClass::Normal
+	}
+}
+
+/// The block against which the pool validates transactions on submission and revalidation.
+///
+/// Transactions returned for block authoring are always validated against the block being built
+/// upon, regardless of the anchor.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationAnchor {
+	/// Validate against the best block.
+	#[default]
+	BestBlock,
+	/// Validate against the most recently finalized block.
+	///
+	/// Transactions are only considered valid if they are valid on the finalized state, which
+	/// trades latency for safety: a transaction valid only on a contested best fork is rejected.
+	/// Revalidation is triggered by finality instead of new best blocks.
+	FinalizedBlock,
+}
+
 /// Possible transaction status events.
 ///
 /// This events are being emitted by `TransactionPool` watchers,
@@ -76,6 +288,8 @@ impl PoolStatus {
 /// 	5. Block finalized:
 /// 		- [Finalized](TransactionStatus::Finalized)
 /// 		- [FinalityTimeout](TransactionStatus::FinalityTimeout)
+/// 	6. Watching:
+/// 		- [WatchingDegraded](TransactionStatus::WatchingDegraded)
 ///
 /// Transactions are first placed in either the `Ready` or `Future` queues of the transaction pool.
 /// Substrate validates the transaction before it enters the pool.
@@ -102,6 +316,9 @@ impl PoolStatus {
 /// such case). An `Invalid` transaction may re-enter the pool only if it is resubmitted.
 /// 4. `Retracted` transactions might be included in some next block.
 ///
+/// When the pool is watching too many transactions already, the stream starts with a
+/// `WatchingDegraded` event and afterwards only yields the last event of the transaction.
+///
 /// The `FinalityTimeout` event will be emitted when the block did not reach finality
 /// within 512 blocks. This either indicates that finality is not available for your chain,
 /// or that finality gadget is lagging behind. If you choose to wait for finality longer, you can
@@ -140,7 +357,8 @@ pub enum TransactionStatus<Hash, BlockHash> {
 	#[serde(with = "v1_compatible")]
 	InBlock((BlockHash, TxIndex)),
 	/// The block this transaction was included in has been retracted.
-	Retracted(BlockHash),
+	#[serde(with = "v1_compatible_retracted")]
+	Retracted(RetractedBlock<BlockHash>),
 	/// Maximum number of finality watchers has been reached,
 	/// old watchers are being removed.
 	FinalityTimeout(BlockHash),
@@ -154,6 +372,23 @@ pub enum TransactionStatus<Hash, BlockHash> {
 	Dropped,
 	/// Transaction is no longer valid in the current state.
 	Invalid,
+	/// The pool is watching too many transactions, only the last event of this transaction
+	/// follows.
+	WatchingDegraded,
+}
+
+/// A block retracted by a reorg, see [`TransactionStatus::Retracted`].
+///
+/// Only `hash` is part of the serialized form, `new_best` and `depth` are `None` when
+/// deserialized.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RetractedBlock<BlockHash> {
+	/// The retracted block the transaction was included in.
+	pub hash: BlockHash,
+	/// The new best block whose tree route retracted the block, if known.
+	pub new_best: Option<BlockHash>,
+	/// The number of blocks retracted by the reorg, if known.
+	pub depth: Option<u32>,
 }
 
 impl<Hash, BlockHash> TransactionStatus<Hash, BlockHash> {
@@ -188,13 +423,214 @@ impl<Hash, BlockHash> TransactionStatus<Hash, BlockHash> {
 	}
 }
 
+/// The outcome of waiting for a transaction to become ready, see
+/// [`TransactionPool::wait_ready`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum WaitReadyOutcome<Hash, BlockHash> {
+	/// The transaction is part of the ready queue.
+	Ready,
+	/// The transaction left the pool with the given status without becoming ready.
+	AlreadyTerminal(TransactionStatus<Hash, BlockHash>),
+	/// The transaction is not known to the pool.
+	Unknown,
+	/// The transaction did not become ready within the given timeout.
+	TimedOut,
+	/// The transaction is not ready yet, but the pool is serving too many waiters already to
+	/// wait for it.
+	TooManyWaiters,
+}
+
+/// What happened to a transaction reported by [`TransactionPool::remove_invalid_detailed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidityOutcome {
+	/// The transaction was removed, together with the given number of transactions that
+	/// depended on it.
+	Removed {
+		/// Number of dependant transactions removed because of this one.
+		dependants_removed: u32,
+	},
+	/// The transaction is not in the pool, e.g. because it was already removed as a dependant
+	/// of another reported transaction.
+	NotFound,
+	/// The pool kept the transaction, considering it only temporarily invalid.
+	///
+	/// Reserved for pools which keep transactions reported with transient errors, the basic
+	/// pool always removes them.
+	KeptTemporarilyInvalid,
+}
+
+/// The result of [`TransactionPool::remove_invalid_detailed`].
+#[derive(Debug, Clone)]
+pub struct InvalidityReport<Hash, Tx> {
+	/// The outcome for each of the reported hashes.
+	pub outcomes: HashMap<Hash, InvalidityOutcome>,
+	/// All removed transactions, including the dependants of the reported ones.
+	pub removed: Vec<Tx>,
+}
+
+/// A transaction of a [`PoolSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct PoolSnapshotTransaction {
+	/// The source the transaction was submitted from.
+	pub source: TransactionSource,
+	/// The encoded extrinsic.
+	pub data: Vec<u8>,
+}
+
+/// The transactions of a pool, exported by [`TransactionPool::export_state`].
+///
+/// Ready transactions are kept in the order the pool hands them out for block production,
+/// future transactions in the order they were submitted to the pool.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Encode, Decode)]
+pub struct PoolSnapshot {
+	/// Transactions of the ready queue.
+	pub ready: Vec<PoolSnapshotTransaction>,
+	/// Transactions of the future queue.
+	pub future: Vec<PoolSnapshotTransaction>,
+}
+
+/// The outcome of importing a [`PoolSnapshot`], see [`TransactionPool::import_state`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolSnapshotImport {
+	/// Number of transactions imported to the pool.
+	pub imported: usize,
+	/// Number of transactions dropped because they failed to decode or are no longer valid.
+	pub dropped: usize,
+}
+
+/// What happened to a ready transaction while a block was built, see
+/// [`TransactionPool::note_inclusion_outcome`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InclusionOutcome {
+	/// The transaction was included in the block.
+	Included,
+	/// The transaction was skipped because it would exceed the weight or size left in the block.
+	ExhaustsResources,
+	/// The transaction was skipped because it is invalid.
+	Invalid,
+	/// The transaction was skipped because a transaction it depends on was not included.
+	MissingDependency,
+}
+
+/// The maximum number of skipped transactions listed by a [`BlockInclusionReport`].
+pub const MAX_REPORTED_SKIPPED_TRANSACTIONS: usize = 64;
+
+/// Which ready transactions were included in a block and which were skipped, see
+/// [`TransactionPool::last_inclusion_report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockInclusionReport<Hash, BlockHash> {
+	/// The hash of the block.
+	pub block_hash: BlockHash,
+	/// Number of transactions included in the block.
+	pub included: usize,
+	/// Number of transactions skipped because they would exceed the resources of the block.
+	pub exhausted_resources: usize,
+	/// Number of transactions skipped because they are invalid.
+	pub invalid: usize,
+	/// Number of transactions skipped because a transaction they depend on was not included.
+	pub missing_dependency: usize,
+	/// The first [`MAX_REPORTED_SKIPPED_TRANSACTIONS`] skipped transactions, in the order they
+	/// were tried, along with the reason they were skipped for.
+	pub skipped: Vec<(Hash, InclusionOutcome)>,
+}
+
+impl<Hash, BlockHash> BlockInclusionReport<Hash, BlockHash> {
+	/// Create the report of the block `block_hash` from the outcomes of its transactions, in
+	/// the order they were tried.
+	pub fn new(
+		block_hash: BlockHash,
+		outcomes: impl IntoIterator<Item = (Hash, InclusionOutcome)>,
+	) -> Self {
+		let mut report = Self {
+			block_hash,
+			included: 0,
+			exhausted_resources: 0,
+			invalid: 0,
+			missing_dependency: 0,
+			skipped: Vec::new(),
+		};
+		for (hash, outcome) in outcomes {
+			match outcome {
+				InclusionOutcome::Included => {
+					report.included += 1;
+					continue
+				},
+				InclusionOutcome::ExhaustsResources => report.exhausted_resources += 1,
+				InclusionOutcome::Invalid => report.invalid += 1,
+				InclusionOutcome::MissingDependency => report.missing_dependency += 1,
+			}
+			if report.skipped.len() < MAX_REPORTED_SKIPPED_TRANSACTIONS {
+				report.skipped.push((hash, outcome));
+			}
+		}
+		report
+	}
+
+	/// Returns the number of skipped transactions.
+	pub fn skipped_count(&self) -> usize {
+		self.exhausted_resources + self.invalid + self.missing_dependency
+	}
+}
+
 /// The stream of transaction events.
 pub type TransactionStatusStream<Hash, BlockHash> =
 	dyn Stream<Item = TransactionStatus<Hash, BlockHash>> + Send;
 
+/// The stream of transaction events, each paired with the tag chosen by the submitter, see
+/// [`TransactionPool::submit_and_watch_tagged`].
+pub type TaggedTransactionStatusStream<Hash, BlockHash> =
+	dyn Stream<Item = (u64, TransactionStatus<Hash, BlockHash>)> + Send;
+
 /// The import notification event stream.
 pub type ImportNotificationStream<H> = futures::channel::mpsc::Receiver<H>;
 
+/// The default number of notifications an [`ImportNotificationStream`] holds before further
+/// notifications overflow.
+pub const DEFAULT_IMPORT_NOTIFICATION_BUFFER_SIZE: usize = 1024;
+
+/// Collects the notifications which did not fit into an [`ImportNotificationStream`], see
+/// [`TransactionPool::import_notification_stream_with_overflow`].
+///
+/// Each hash is kept once, until it is taken out of the handle.
+#[derive(Debug)]
+pub struct OverflowHandle<H> {
+	overflowed: Arc<Mutex<HashSet<H>>>,
+}
+
+impl<H> Clone for OverflowHandle<H> {
+	fn clone(&self) -> Self {
+		Self { overflowed: self.overflowed.clone() }
+	}
+}
+
+impl<H> Default for OverflowHandle<H> {
+	fn default() -> Self {
+		Self { overflowed: Default::default() }
+	}
+}
+
+impl<H: Hash + Eq> OverflowHandle<H> {
+	/// Record a notification which did not fit into the stream.
+	pub fn insert(&self, hash: H) {
+		self.overflowed.lock().insert(hash);
+	}
+
+	/// Take all notifications which overflowed since the last call.
+	pub fn take(&self) -> HashSet<H> {
+		std::mem::take(&mut *self.overflowed.lock())
+	}
+
+	/// Returns the number of notifications which overflowed and were not taken yet.
+	pub fn len(&self) -> usize {
+		self.overflowed.lock().len()
+	}
+
+	/// Returns `true` if no notification overflowed since the last call to [`Self::take`].
+	pub fn is_empty(&self) -> bool {
+		self.overflowed.lock().is_empty()
+	}
+}
+
 /// Transaction hash type for a pool.
 pub type TxHash<P> = <P as TransactionPool>::Hash;
 /// Block hash type for a pool.
@@ -203,6 +639,13 @@ pub type BlockHash<P> = <<P as TransactionPool>::Block as BlockT>::Hash;
 pub type TransactionFor<P> = <<P as TransactionPool>::Block as BlockT>::Extrinsic;
 /// Type of transactions event stream for a pool.
 pub type TransactionStatusStreamFor<P> = TransactionStatusStream<TxHash<P>, BlockHash<P>>;
+/// Type of tagged transactions event stream for a pool.
+pub type TaggedTransactionStatusStreamFor<P> =
+	TaggedTransactionStatusStream<TxHash<P>, BlockHash<P>>;
+/// Type of the outcome of waiting for a transaction of a pool to become ready.
+pub type WaitReadyOutcomeFor<P> = WaitReadyOutcome<TxHash<P>, BlockHash<P>>;
+/// Type of the inclusion report of a block for a pool.
+pub type BlockInclusionReportFor<P> = BlockInclusionReport<TxHash<P>, BlockHash<P>>;
 /// Transaction type for a local pool.
 pub type LocalTransactionFor<P> = <<P as LocalTransactionPool>::Block as BlockT>::Extrinsic;
 /// Transaction's index within the block in which it was included.
@@ -235,6 +678,13 @@ pub trait InPoolTransaction {
 	fn provides(&self) -> &[TransactionTag];
 	/// Return a flag indicating if the transaction should be propagated to other peers.
 	fn is_propagable(&self) -> bool;
+	/// Get the number of the block the transaction was last validated at, if known.
+	///
+	/// Together with [`Self::longevity`] this tells for how many more blocks the transaction
+	/// stays valid.
+	fn validated_at(&self) -> Option<u64>;
+	/// Get the source the transaction was submitted from.
+	fn source(&self) -> TransactionSource;
 }
 
 /// Transaction pool interface.
@@ -278,11 +728,46 @@ pub trait TransactionPool: Send + Sync {
 		xt: TransactionFor<Self>,
 	) -> PoolFuture<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error>;
 
+	/// Returns a future that imports a single transaction and starts to watch its progress in the
+	/// pool, like [`TransactionPool::submit_and_watch`], echoing the caller-chosen `tag` in every
+	/// event.
+	///
+	/// Lets submitters multiplexing many transactions attribute the events without mapping the
+	/// transaction hashes back to their requests.
+	fn submit_and_watch_tagged(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+		tag: u64,
+	) -> PoolFuture<Pin<Box<TaggedTransactionStatusStreamFor<Self>>>, Self::Error> {
+		self.submit_and_watch(at, source, xt)
+			.map_ok(move |watcher| {
+				Box::pin(watcher.map(move |status| (tag, status))) as Pin<Box<_>>
+			})
+			.boxed()
+	}
+
+	/// Starts to watch the progress of a transaction which is already known to the pool, e.g.
+	/// because it was submitted without watching it or received from a peer.
+	///
+	/// The stream starts with the current status of the transaction: `Ready` or `Future` while
+	/// it is in the pool, or `InBlock` if it was included in a block which is not finalized yet.
+	/// Returns `None` if the transaction is not known to the pool.
+	fn watch_existing(
+		&self,
+		hash: TxHash<Self>,
+	) -> Option<Pin<Box<TransactionStatusStreamFor<Self>>>>;
+
 	// *** Block production / Networking
 	/// Get an iterator for ready transactions ordered by priority.
 	///
 	/// Guarantees to return only when transaction pool got updated at `at` block.
 	/// Guarantees to return immediately when `None` is passed.
+	///
+	/// The block is only identified by its number, so the transactions are the ones ready on
+	/// whichever fork the pool was maintained at. Use [`TransactionPool::ready_at_head`] to ask
+	/// for a specific fork.
 	fn ready_at(
 		&self,
 		at: NumberFor<Self::Block>,
@@ -294,35 +779,323 @@ pub trait TransactionPool: Send + Sync {
 		>,
 	>;
 
+	/// Get an iterator for the transactions ready on top of the fork head `at`, ordered by
+	/// priority.
+	///
+	/// The head need not be the best block. A pool either returns the transactions ready on
+	/// exactly this head, or none at all; it never returns the transactions of another fork:
+	///
+	/// - A pool keeping a view per fork returns the view of `at`, creating a temporary view if
+	///   there is none yet, within its limit of concurrent views.
+	/// - A pool keeping a single view only serves the head it was last maintained at, and resolves
+	///   to an empty iterator for any other head.
+	///
+	/// The default implementation serves no head and always resolves to an empty iterator.
+	fn ready_at_head(
+		&self,
+		at: BlockHash<Self>,
+	) -> Pin<
+		Box<
+			dyn Future<
+					Output = Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send>,
+				> + Send,
+		>,
+	>
+	where
+		Self::InPoolTransaction: 'static,
+	{
+		log::debug!(target: LOG_TARGET, "Ready transactions at fork head {:?} not supported", at);
+		futures::future::ready(Box::new(std::iter::empty()) as Box<_>).boxed()
+	}
+
+	/// Get an iterator for ready transactions ordered by priority, limited by the given
+	/// [`ReadyLimits`].
+	///
+	/// Resolves under the same conditions as [`TransactionPool::ready_at`]. Transactions that do
+	/// not fit into the remaining byte budget are skipped together with the transactions depending
+	/// on them, and the iteration stops as soon as any of the limits is exhausted. The byte budget
+	/// is considered exhausted after [`ReadyLimits::max_skipped_oversized`] transactions did not
+	/// fit into it.
+	///
+	/// The default implementation wraps the iterator returned by [`TransactionPool::ready_at`]
+	/// and uses the encoded size of every transaction.
+	fn ready_at_with_limits(
+		&self,
+		at: NumberFor<Self::Block>,
+		limits: ReadyLimits,
+	) -> Pin<
+		Box<
+			dyn Future<
+					Output = Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send>,
+				> + Send,
+		>,
+	>
+	where
+		Self::InPoolTransaction: 'static,
+	{
+		let ready = self.ready_at(at);
+		async move {
+			Box::new(LimitedReadyTransactions::new(
+				ready.await,
+				limits,
+				|tx: &Arc<Self::InPoolTransaction>| tx.data().encoded_size(),
+			)) as Box<_>
+		}
+		.boxed()
+	}
+
+	/// Get an iterator for ready transactions ordered by priority, waiting for the pool to be
+	/// maintained at `at` until `cancelled` resolves.
+	///
+	/// Resolves with [`ReadyResult::Complete`] whenever [`TransactionPool::ready_at`] would
+	/// resolve before `cancelled`, including right away if the pool was already maintained at
+	/// `at`. Otherwise resolves with [`ReadyResult::BestEffort`] once `cancelled` resolves, so
+	/// that the timing policy is up to the caller.
+	///
+	/// The default implementation collects the best effort transactions through
+	/// [`TransactionPool::ready`] when called and always reports pending maintenance.
+	fn ready_at_cancellable(
+		&self,
+		at: NumberFor<Self::Block>,
+		cancelled: BoxFuture<'static, ()>,
+	) -> Pin<
+		Box<
+			dyn Future<
+					Output = ReadyResult<
+						Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send>,
+					>,
+				> + Send,
+		>,
+	>
+	where
+		Self::InPoolTransaction: 'static,
+	{
+		let ready_at = self.ready_at(at);
+		let ready = self.ready();
+		async move {
+			match futures::future::select(ready_at, cancelled).await {
+				Either::Left((ready, _)) => ReadyResult::Complete(ready),
+				Either::Right(((), _)) =>
+					ReadyResult::BestEffort { ready, pending_maintenance: true },
+			}
+		}
+		.boxed()
+	}
+
 	/// Get an iterator for ready transactions ordered by priority.
 	fn ready(&self) -> Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send>;
 
 	// *** Block production
 	/// Remove transactions identified by given hashes (and dependent transactions) from the pool.
-	fn remove_invalid(&self, hashes: &[TxHash<Self>]) -> Vec<Arc<Self::InPoolTransaction>>;
+	///
+	/// Returns the removed transactions of [`Self::remove_invalid_detailed`].
+	fn remove_invalid(&self, hashes: &[TxHash<Self>]) -> Vec<Arc<Self::InPoolTransaction>> {
+		self.remove_invalid_detailed(hashes).removed
+	}
+
+	/// Like [`Self::remove_invalid`], but also reports the outcome for each of the given hashes.
+	fn remove_invalid_detailed(
+		&self,
+		hashes: &[TxHash<Self>],
+	) -> InvalidityReport<TxHash<Self>, Arc<Self::InPoolTransaction>>;
 
 	// *** logging
 	/// Get futures transaction list.
-	fn futures(&self) -> Vec<Self::InPoolTransaction>;
+	fn futures(&self) -> Vec<Arc<Self::InPoolTransaction>>;
+
+	/// Get at most `limit` future transactions, skipping the first `offset` of them, together
+	/// with the total number of future transactions.
+	///
+	/// Pages are taken in the same order as [`Self::futures`] returns the transactions.
+	fn futures_paged(
+		&self,
+		offset: usize,
+		limit: usize,
+	) -> (Vec<Arc<Self::InPoolTransaction>>, usize) {
+		let futures = self.futures();
+		let total = futures.len();
+		(futures.into_iter().skip(offset).take(limit).collect(), total)
+	}
 
 	/// Returns pool status.
 	fn status(&self) -> PoolStatus;
 
+	/// Returns pool status including how long the transactions of each queue have been in the
+	/// pool.
+	///
+	/// The statistics are computed on demand. The default implementation only fills in
+	/// [`Self::status`].
+	fn status_detailed(&self) -> PoolStatusDetailed {
+		self.status().into()
+	}
+
+	/// Returns a future that resolves to what pruning the transactions of the block `at` would
+	/// do to the pool, without modifying it.
+	///
+	/// Lets block authors find out whether the pool would be effectively empty on top of `at`
+	/// before they start authoring. The default implementation conservatively assumes that
+	/// nothing would be pruned or promoted.
+	fn prune_preview(
+		&self,
+		_at: BlockHash<Self>,
+	) -> Pin<Box<dyn Future<Output = PrunePreview> + Send>> {
+		let ready = self.status().ready;
+		async move { PrunePreview { would_remain_ready: ready, ..Default::default() } }.boxed()
+	}
+
+	/// Returns the priority a transaction needs to be included within the first `within_bytes`
+	/// of ready transactions.
+	///
+	/// This is the priority of the first ready transaction (in the order they would be included
+	/// in a block) that does not fit into `within_bytes`. Returns `None` if all ready
+	/// transactions fit.
+	fn priority_threshold(&self, within_bytes: usize) -> Option<TransactionPriority>;
+
+	/// Returns the lowest priority in the ready queue, if the ready queue limits are reached.
+	///
+	/// See [`PoolStatus::min_ready_priority`].
+	fn min_ready_priority(&self) -> Option<TransactionPriority>;
+
+	/// Returns the priority a new transaction providing `tag` has to exceed to replace the ready
+	/// transaction currently providing it.
+	///
+	/// Takes [`REPLACEMENT_PRIORITY_BUMP_PERCENT`] into account. A transaction providing other
+	/// tags that are in use as well has to outbid the sum of all transactions it replaces.
+	/// Returns `None` if no ready transaction provides `tag`.
+	fn min_replacement_priority(&self, tag: &TransactionTag) -> Option<TransactionPriority>;
+
 	// *** logging / RPC / networking
 	/// Return an event stream of transactions imported to the pool.
+	///
+	/// The stream holds up to [`DEFAULT_IMPORT_NOTIFICATION_BUFFER_SIZE`] notifications.
+	/// Notifications which don't fit into it are dropped, see
+	/// [`Self::dropped_import_notifications`].
 	fn import_notification_stream(&self) -> ImportNotificationStream<TxHash<Self>>;
 
+	/// Return an event stream of transactions imported to the pool, like
+	/// [`Self::import_notification_stream`], which holds up to `buffer_size` notifications.
+	///
+	/// The default implementation ignores `buffer_size` and returns
+	/// [`Self::import_notification_stream`].
+	fn import_notification_stream_with_buffer(
+		&self,
+		_buffer_size: usize,
+	) -> ImportNotificationStream<TxHash<Self>> {
+		self.import_notification_stream()
+	}
+
+	/// Returns the number of import notifications dropped so far because a stream was full.
+	///
+	/// The default implementation does not track dropped notifications and returns `0`.
+	fn dropped_import_notifications(&self) -> u64 {
+		0
+	}
+
+	/// Return an event stream of transactions imported to the pool, like
+	/// [`Self::import_notification_stream`].
+	///
+	/// Notifications which don't fit into the stream are not dropped but collected in the
+	/// returned [`OverflowHandle`].
+	fn import_notification_stream_with_overflow(
+		&self,
+		buffer_size: Option<usize>,
+	) -> (ImportNotificationStream<TxHash<Self>>, OverflowHandle<TxHash<Self>>);
+
 	// *** networking
 	/// Notify the pool about transactions broadcast.
 	fn on_broadcasted(&self, propagations: HashMap<TxHash<Self>, Vec<String>>);
 
-	/// Returns transaction hash
+	/// Returns the peers the transaction with the given hash was broadcast to so far, as reported
+	/// by [`Self::on_broadcasted`].
+	///
+	/// Returns `None` if the transaction was never broadcast or has left the pool. The default
+	/// implementation does not track broadcasts.
+	fn broadcast_info(&self, _hash: &TxHash<Self>) -> Option<BroadcastInfo> {
+		None
+	}
+
+	/// Returns transaction hash, as hashed by the runtime at the current best block.
 	fn hash_of(&self, xt: &TransactionFor<Self>) -> TxHash<Self>;
 
+	/// Returns transaction hash, as hashed by the runtime at the given block.
+	///
+	/// The hashing of transactions may change with a runtime upgrade, in which case the pool
+	/// re-hashes the transactions it holds. The default implementation ignores the block and
+	/// delegates to [`Self::hash_of`].
+	fn hash_of_at(&self, xt: &TransactionFor<Self>, _at: BlockHash<Self>) -> TxHash<Self> {
+		self.hash_of(xt)
+	}
+
 	/// Return specific ready transaction by hash, if there is one.
 	fn ready_transaction(&self, hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>>;
+
+	/// Returns a future that resolves once the transaction with the given hash is ready.
+	///
+	/// Resolves immediately if the transaction is already ready or not known to the pool. A
+	/// future transaction is awaited until it gets promoted to the ready queue, leaves the pool
+	/// or the optional `timeout` elapses, whichever happens first. Resolves immediately with
+	/// [`WaitReadyOutcome::TooManyWaiters`] if the transaction has to be awaited but the pool
+	/// cannot register another waiter.
+	fn wait_ready(
+		&self,
+		hash: TxHash<Self>,
+		timeout: Option<Duration>,
+	) -> Pin<Box<dyn Future<Output = WaitReadyOutcomeFor<Self>> + Send>>;
+
+	/// Export the transactions of the ready and the future queue.
+	fn export_state(&self) -> PoolSnapshot;
+
+	/// Returns a future that imports the transactions of a snapshot to the pool.
+	///
+	/// Transactions are submitted in the order of the snapshot, ready ones first, and get
+	/// re-validated against the current best block. Transactions which fail to decode, are no
+	/// longer valid or are already part of the pool are dropped.
+	fn import_state(
+		&self,
+		snapshot: PoolSnapshot,
+	) -> Pin<Box<dyn Future<Output = PoolSnapshotImport> + Send>>;
+
+	/// Limit the transactions of the given [`PoolClass`] to `max_count` transactions taking
+	/// `max_bytes` bytes in total, across the ready and the future queue.
+	///
+	/// Once exceeded, the worst transactions of the class are evicted, leaving the other
+	/// classes untouched. The default implementation ignores the limits.
+	fn set_class_limits(&self, _class: PoolClass, _max_count: usize, _max_bytes: usize) {}
+
+	/// Note what happened to the ready transactions while building the block `at`.
+	///
+	/// Called by block builders once the block is built, with the outcomes in the order the
+	/// transactions were tried. The default implementation ignores the outcomes.
+	fn note_inclusion_outcome(
+		&self,
+		_at: BlockHash<Self>,
+		_outcomes: Vec<(TxHash<Self>, InclusionOutcome)>,
+	) {
+	}
+
+	/// Returns the inclusion report of the last block noted by
+	/// [`Self::note_inclusion_outcome`], if any.
+	fn last_inclusion_report(&self) -> Option<BlockInclusionReportFor<Self>> {
+		None
+	}
 }
 
+/// Owned access to the future transactions, as returned by [`TransactionPool::futures`] before
+/// it returned shared transactions.
+#[deprecated(note = "Use `TransactionPool::futures` or `TransactionPool::futures_paged` instead")]
+pub trait TransactionPoolOwnedFutures: TransactionPool {
+	/// Get futures transaction list, cloning every transaction.
+	fn owned_futures(&self) -> Vec<Self::InPoolTransaction>
+	where
+		Self::InPoolTransaction: Clone,
+	{
+		self.futures().into_iter().map(|tx| (*tx).clone()).collect()
+	}
+}
+
+#[allow(deprecated)]
+impl<T: TransactionPool + ?Sized> TransactionPoolOwnedFutures for T {}
+
 /// An iterator of ready transactions.
 ///
 /// The trait extends regular [`std::iter::Iterator`] trait and allows reporting
@@ -344,6 +1117,81 @@ impl<T> ReadyTransactions for std::iter::Empty<T> {
 	fn report_invalid(&mut self, _tx: &T) {}
 }
 
+/// An iterator of ready transactions which respects the given [`ReadyLimits`].
+///
+/// Transactions exceeding the remaining byte budget are reported as invalid to the wrapped
+/// iterator, so that the transactions depending on them are skipped as well. Once
+/// [`ReadyLimits::max_skipped_oversized`] transactions were skipped the budget is considered
+/// exhausted and the iteration stops, instead of scanning the rest of the ready set.
+pub struct LimitedReadyTransactions<T> {
+	inner: Box<dyn ReadyTransactions<Item = T> + Send>,
+	size_of: fn(&T) -> usize,
+	remaining_bytes: Option<usize>,
+	remaining_transactions: Option<usize>,
+	max_skipped_oversized: Option<usize>,
+	skipped: usize,
+}
+
+impl<T> LimitedReadyTransactions<T> {
+	/// Wrap the `inner` iterator, using `size_of` to determine the size of every transaction.
+	pub fn new(
+		inner: Box<dyn ReadyTransactions<Item = T> + Send>,
+		limits: ReadyLimits,
+		size_of: fn(&T) -> usize,
+	) -> Self {
+		Self {
+			inner,
+			size_of,
+			remaining_bytes: limits.max_bytes,
+			remaining_transactions: limits.max_transactions,
+			max_skipped_oversized: limits.max_skipped_oversized,
+			skipped: 0,
+		}
+	}
+}
+
+impl<T> Iterator for LimitedReadyTransactions<T> {
+	type Item = T;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if self.remaining_bytes == Some(0) || self.remaining_transactions == Some(0) {
+				return None
+			}
+
+			let tx = self.inner.next()?;
+			if let Some(remaining_bytes) = self.remaining_bytes.as_mut() {
+				let size = (self.size_of)(&tx);
+				if size > *remaining_bytes {
+					log::trace!(
+						target: LOG_TARGET,
+						"Skipping ready transaction of {size} bytes exceeding the remaining budget of {remaining_bytes} bytes.",
+					);
+					self.inner.report_invalid(&tx);
+					self.skipped += 1;
+					if self.max_skipped_oversized.is_some_and(|max| self.skipped >= max) {
+						*remaining_bytes = 0;
+						return None
+					}
+					continue
+				}
+				*remaining_bytes -= size;
+			}
+			if let Some(remaining_transactions) = self.remaining_transactions.as_mut() {
+				*remaining_transactions -= 1;
+			}
+
+			return Some(tx)
+		}
+	}
+}
+
+impl<T> ReadyTransactions for LimitedReadyTransactions<T> {
+	fn report_invalid(&mut self, tx: &T) {
+		self.inner.report_invalid(tx)
+	}
+}
+
 /// Events that the transaction pool listens for.
 pub enum ChainEvent<B: BlockT> {
 	/// New best block have been added to the chain.
@@ -423,6 +1271,9 @@ impl<T: LocalTransactionPool> LocalTransactionPool for Arc<T> {
 	}
 }
 
+/// The number of transactions [`OffchainTransactionPoolFactory`] keeps for resubmission.
+const MAX_OFFCHAIN_RETRIES: usize = 8;
+
 /// An abstraction for [`LocalTransactionPool`]
 ///
 /// We want to use a transaction pool in [`OffchainTransactionPoolFactory`] in a `Arc` without
@@ -432,7 +1283,13 @@ trait OffchainSubmitTransaction<Block: BlockT>: Send + Sync {
 	/// Submit transaction.
 	///
 	/// The transaction will end up in the pool and be propagated to others.
-	fn submit_at(&self, at: Block::Hash, extrinsic: Block::Extrinsic) -> Result<(), ()>;
+	///
+	/// Returns the pool error if the pool rejected the transaction and `None` for any other error.
+	fn submit_at(
+		&self,
+		at: Block::Hash,
+		extrinsic: Block::Extrinsic,
+	) -> Result<(), Option<error::Error>>;
 }
 
 impl<TPool: LocalTransactionPool> OffchainSubmitTransaction<TPool::Block> for TPool {
@@ -440,7 +1297,7 @@ impl<TPool: LocalTransactionPool> OffchainSubmitTransaction<TPool::Block> for TP
 		&self,
 		at: <TPool::Block as BlockT>::Hash,
 		extrinsic: <TPool::Block as BlockT>::Extrinsic,
-	) -> Result<(), ()> {
+	) -> Result<(), Option<error::Error>> {
 		log::debug!(
 			target: LOG_TARGET,
 			"(offchain call) Submitting a transaction to the pool: {:?}",
@@ -449,29 +1306,95 @@ impl<TPool: LocalTransactionPool> OffchainSubmitTransaction<TPool::Block> for TP
 
 		let result = self.submit_local(at, extrinsic);
 
-		result.map(|_| ()).map_err(|e| {
-			log::warn!(
-				target: LOG_TARGET,
-				"(offchain call) Error submitting a transaction to the pool: {}",
-				e
-			)
+		result.map(|_| ()).map_err(|e| match error::IntoPoolError::into_pool_error(e) {
+			// The transaction may still be accepted later on, so this is not worth a warning.
+			Ok(e @ (error::Error::FutureNonce { .. } | error::Error::StalePriority)) => {
+				log::debug!(
+					target: LOG_TARGET,
+					"(offchain call) Transaction not accepted by the pool: {}",
+					e
+				);
+				Some(e)
+			},
+			Ok(e) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"(offchain call) Error submitting a transaction to the pool: {}",
+					e
+				);
+				Some(e)
+			},
+			Err(e) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"(offchain call) Error submitting a transaction to the pool: {}",
+					e
+				);
+				None
+			},
 		})
 	}
 }
 
+/// Transactions that were rejected by the pool because of a nonce gap, together with the block
+/// they were submitted at.
+///
+/// Holds at most [`MAX_OFFCHAIN_RETRIES`] transactions, the oldest ones are dropped first.
+struct OffchainRetryQueue<Block: BlockT>(Mutex<VecDeque<(Block::Hash, Block::Extrinsic)>>);
+
+impl<Block: BlockT> OffchainRetryQueue<Block> {
+	fn push(&self, at: Block::Hash, extrinsic: Block::Extrinsic) {
+		let mut queue = self.0.lock();
+		if queue.len() >= MAX_OFFCHAIN_RETRIES {
+			if let Some((_, dropped)) = queue.pop_front() {
+				log::debug!(
+					target: LOG_TARGET,
+					"(offchain call) Dropping a transaction from the retry queue: {:?}",
+					dropped
+				);
+			}
+		}
+		queue.push_back((at, extrinsic));
+	}
+
+	/// Submits `extrinsic` to `pool`, queueing it if it was rejected because of a nonce gap.
+	fn submit(
+		&self,
+		pool: &dyn OffchainSubmitTransaction<Block>,
+		at: Block::Hash,
+		extrinsic: Block::Extrinsic,
+	) -> Result<(), ()> {
+		match pool.submit_at(at, extrinsic.clone()) {
+			Ok(()) => Ok(()),
+			Err(Some(error::Error::FutureNonce { .. })) => {
+				self.push(at, extrinsic);
+				Err(())
+			},
+			Err(_) => Err(()),
+		}
+	}
+}
+
 /// Factory for creating [`TransactionPoolExt`]s.
 ///
 /// This provides an easy way for creating [`TransactionPoolExt`] extensions for registering them in
 /// the wasm execution environment to send transactions from an offchain call to the  runtime.
+///
+/// Transactions that are rejected because of a nonce gap are submitted again once an extension
+/// for another block is created.
 #[derive(Clone)]
 pub struct OffchainTransactionPoolFactory<Block: BlockT> {
 	pool: Arc<dyn OffchainSubmitTransaction<Block>>,
+	retry_queue: Arc<OffchainRetryQueue<Block>>,
 }
 
 impl<Block: BlockT> OffchainTransactionPoolFactory<Block> {
 	/// Creates a new instance using the given `tx_pool`.
 	pub fn new<T: LocalTransactionPool<Block = Block> + 'static>(tx_pool: T) -> Self {
-		Self { pool: Arc::new(tx_pool) as Arc<_> }
+		Self {
+			pool: Arc::new(tx_pool) as Arc<_>,
+			retry_queue: Arc::new(OffchainRetryQueue(Default::default())),
+		}
 	}
 
 	/// Returns an instance of [`TransactionPoolExt`] bound to the given `block_hash`.
@@ -479,7 +1402,27 @@ impl<Block: BlockT> OffchainTransactionPoolFactory<Block> {
 	/// Transactions that are being submitted by this instance will be submitted with `block_hash`
 	/// as context for validation.
 	pub fn offchain_transaction_pool(&self, block_hash: Block::Hash) -> TransactionPoolExt {
-		TransactionPoolExt::new(OffchainTransactionPool { pool: self.pool.clone(), block_hash })
+		self.retry_future_transactions(block_hash);
+
+		TransactionPoolExt::new(OffchainTransactionPool {
+			pool: self.pool.clone(),
+			retry_queue: self.retry_queue.clone(),
+			block_hash,
+		})
+	}
+
+	/// Submits the queued transactions again with `block_hash` as context for validation.
+	///
+	/// Transactions that were queued at `block_hash` itself are kept as they are.
+	fn retry_future_transactions(&self, block_hash: Block::Hash) {
+		let queued = std::mem::take(&mut *self.retry_queue.0.lock());
+		for (at, extrinsic) in queued {
+			if at == block_hash {
+				self.retry_queue.push(at, extrinsic);
+			} else {
+				let _ = self.retry_queue.submit(&*self.pool, block_hash, extrinsic);
+			}
+		}
 	}
 }
 
@@ -487,6 +1430,7 @@ impl<Block: BlockT> OffchainTransactionPoolFactory<Block> {
 struct OffchainTransactionPool<Block: BlockT> {
 	block_hash: Block::Hash,
 	pool: Arc<dyn OffchainSubmitTransaction<Block>>,
+	retry_queue: Arc<OffchainRetryQueue<Block>>,
 }
 
 impl<Block: BlockT> sp_core::offchain::TransactionPool for OffchainTransactionPool<Block> {
@@ -503,7 +1447,7 @@ impl<Block: BlockT> sp_core::offchain::TransactionPool for OffchainTransactionPo
 			},
 		};
 
-		self.pool.submit_at(self.block_hash, extrinsic)
+		self.retry_queue.submit(&*self.pool, self.block_hash, extrinsic)
 	}
 }
 
@@ -530,6 +1474,28 @@ mod v1_compatible {
 	}
 }
 
+mod v1_compatible_retracted {
+	use super::RetractedBlock;
+	use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+	pub fn serialize<S, H>(data: &RetractedBlock<H>, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+		H: Serialize,
+	{
+		serde::Serialize::serialize(&data.hash, serializer)
+	}
+
+	pub fn deserialize<'de, D, H>(deserializer: D) -> Result<RetractedBlock<H>, D::Error>
+	where
+		D: Deserializer<'de>,
+		H: Deserialize<'de>,
+	{
+		let hash: H = serde::Deserialize::deserialize(deserializer)?;
+		Ok(RetractedBlock { hash, new_best: None, depth: None })
+	}
+}
+
 /// Transaction pool that rejects all submitted transactions.
 ///
 /// Could be used for example in tests.
@@ -556,6 +1522,56 @@ impl<Block: BlockT> LocalTransactionPool for RejectAllTxPool<Block> {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use sp_core::{offchain::TransactionPool as _, H256};
+	use sp_runtime::testing::{Block as RawBlock, ExtrinsicWrapper};
+
+	type Block = RawBlock<ExtrinsicWrapper<u64>>;
+
+	/// Pool that rejects transactions because of a nonce gap unless submitted at `accept_at`.
+	struct NonceGapPool {
+		accept_at: H256,
+		submitted: parking_lot::Mutex<Vec<(H256, u64)>>,
+	}
+
+	impl NonceGapPool {
+		fn new(accept_at: H256) -> Arc<Self> {
+			Arc::new(Self { accept_at, submitted: Default::default() })
+		}
+	}
+
+	impl LocalTransactionPool for NonceGapPool {
+		type Block = Block;
+
+		type Hash = H256;
+
+		type Error = error::Error;
+
+		fn submit_local(
+			&self,
+			at: H256,
+			xt: ExtrinsicWrapper<u64>,
+		) -> Result<Self::Hash, Self::Error> {
+			self.submitted.lock().push((at, *xt));
+			if at == self.accept_at {
+				Ok(H256::from_low_u64_be(*xt))
+			} else {
+				Err(error::Error::FutureNonce { expected_provides: vec![vec![*xt as u8 - 1]] })
+			}
+		}
+	}
+
+	fn submit(
+		factory: &OffchainTransactionPoolFactory<Block>,
+		at: H256,
+		xt: u64,
+	) -> Result<(), ()> {
+		let mut ext = factory.offchain_transaction_pool(at);
+		ext.submit_transaction(ExtrinsicWrapper::from(xt).encode())
+	}
+
+	fn queued(factory: &OffchainTransactionPoolFactory<Block>) -> Vec<(H256, u64)> {
+		factory.retry_queue.0.lock().iter().map(|(at, xt)| (*at, **xt)).collect()
+	}
 
 	#[test]
 	fn tx_status_compatibility() {
@@ -576,5 +1592,119 @@ mod tests {
 
 		let event_dec: TransactionStatus<u8, u8> = serde_json::from_str(exp).unwrap();
 		assert_eq!(event_dec, TransactionStatus::Finalized((1, 0)));
+
+		let event: TransactionStatus<u8, u8> = TransactionStatus::Retracted(RetractedBlock {
+			hash: 1,
+			new_best: Some(2),
+			depth: Some(3),
+		});
+		let ser = serde_json::to_string(&event).unwrap();
+
+		let exp = r#"{"retracted":1}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: TransactionStatus<u8, u8> = serde_json::from_str(exp).unwrap();
+		assert_eq!(
+			event_dec,
+			TransactionStatus::Retracted(RetractedBlock { hash: 1, new_best: None, depth: None })
+		);
+
+		let event: TransactionStatus<u8, u8> = TransactionStatus::WatchingDegraded;
+		let ser = serde_json::to_string(&event).unwrap();
+
+		let exp = r#""watchingDegraded""#;
+		assert_eq!(ser, exp);
+
+		let event_dec: TransactionStatus<u8, u8> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, TransactionStatus::WatchingDegraded);
+	}
+
+	#[test]
+	fn offchain_submission_reports_future_nonce() {
+		let pool = NonceGapPool::new(H256::repeat_byte(2));
+
+		let result = pool.submit_at(H256::repeat_byte(1), ExtrinsicWrapper::from(5));
+
+		match result {
+			Err(Some(error::Error::FutureNonce { expected_provides })) =>
+				assert_eq!(expected_provides, vec![vec![4]]),
+			other => panic!("Unexpected result: {:?}", other),
+		}
+		assert!(pool.submit_at(H256::repeat_byte(2), ExtrinsicWrapper::from(5)).is_ok());
+	}
+
+	#[test]
+	fn future_nonce_transactions_are_resubmitted_at_another_block() {
+		let (first, second) = (H256::repeat_byte(1), H256::repeat_byte(2));
+		let pool = NonceGapPool::new(second);
+		let factory = OffchainTransactionPoolFactory::new(pool.clone());
+
+		assert_eq!(submit(&factory, first, 5), Err(()));
+		assert_eq!(queued(&factory), vec![(first, 5)]);
+
+		// Nothing is resubmitted at the block the transaction was rejected at.
+		let _ = factory.offchain_transaction_pool(first);
+		assert_eq!(*pool.submitted.lock(), vec![(first, 5)]);
+		assert_eq!(queued(&factory), vec![(first, 5)]);
+
+		// The transaction is accepted once resubmitted at another block.
+		let _ = factory.offchain_transaction_pool(second);
+		assert_eq!(*pool.submitted.lock(), vec![(first, 5), (second, 5)]);
+		assert!(queued(&factory).is_empty());
+	}
+
+	#[test]
+	fn offchain_retry_queue_is_bounded() {
+		let first = H256::repeat_byte(1);
+		let factory = OffchainTransactionPoolFactory::new(NonceGapPool::new(H256::repeat_byte(2)));
+
+		for xt in 1..=MAX_OFFCHAIN_RETRIES as u64 + 2 {
+			assert_eq!(submit(&factory, first, xt), Err(()));
+		}
+
+		// The oldest transactions are dropped first.
+		let max = MAX_OFFCHAIN_RETRIES as u64;
+		assert_eq!(queued(&factory), (3..=max + 2).map(|xt| (first, xt)).collect::<Vec<_>>());
+	}
+
+	#[test]
+	fn inclusion_report_counts_all_but_lists_first_skipped_transactions() {
+		let skipped = MAX_REPORTED_SKIPPED_TRANSACTIONS as u64 + 10;
+		let outcomes = (0..skipped).map(|n| (n, InclusionOutcome::ExhaustsResources)).chain([
+			(skipped, InclusionOutcome::Included),
+			(skipped + 1, InclusionOutcome::Invalid),
+		]);
+		let report = BlockInclusionReport::new(H256::repeat_byte(1), outcomes);
+
+		assert_eq!(report.block_hash, H256::repeat_byte(1));
+		assert_eq!(report.included, 1);
+		assert_eq!(report.exhausted_resources, skipped as usize);
+		assert_eq!(report.invalid, 1);
+		assert_eq!(report.missing_dependency, 0);
+		assert_eq!(report.skipped_count(), skipped as usize + 1);
+		assert_eq!(
+			report.skipped,
+			(0..MAX_REPORTED_SKIPPED_TRANSACTIONS as u64)
+				.map(|n| (n, InclusionOutcome::ExhaustsResources))
+				.collect::<Vec<_>>(),
+		);
+	}
+
+	#[test]
+	fn age_percentiles_use_nearest_rank() {
+		let secs = |secs: &[u64]| secs.iter().copied().map(Duration::from_secs).collect();
+
+		assert_eq!(AgePercentiles::from_ages(vec![]), AgePercentiles::default());
+		assert_eq!(
+			AgePercentiles::from_ages(secs(&[7])),
+			AgePercentiles { p50: Some(Duration::from_secs(7)), p95: Some(Duration::from_secs(7)) }
+		);
+		assert_eq!(
+			AgePercentiles::from_ages(secs(&[4, 1, 3, 2])),
+			AgePercentiles { p50: Some(Duration::from_secs(2)), p95: Some(Duration::from_secs(4)) }
+		);
+		let ages = AgePercentiles::from_ages((1..=100).rev().map(Duration::from_secs).collect());
+		assert_eq!(ages.p50, Some(Duration::from_secs(50)));
+		assert_eq!(ages.p95, Some(Duration::from_secs(95)));
 	}
 }
@@ -26,11 +26,13 @@ use futures::{
 	lock::Mutex,
 	SinkExt, StreamExt,
 };
-use std::{marker::PhantomData, pin::Pin, sync::Arc};
+use linked_hash_map::LinkedHashMap;
+use std::{collections::HashMap, marker::PhantomData, pin::Pin, sync::Arc};
 
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_client_api::{blockchain::HeaderBackend, BlockBackend};
-use sp_api::{ApiExt, ProvideRuntimeApi};
+use sc_transaction_pool_api::error::Error as TxPoolError;
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
 use sp_blockchain::{HeaderMetadata, TreeRoute};
 use sp_core::traits::SpawnEssentialNamed;
 use sp_runtime::{
@@ -46,12 +48,29 @@ use crate::{
 	metrics::{ApiMetrics, ApiMetricsExt},
 };
 
+/// The number of blocks whose runtime spec version is cached.
+const SPEC_VERSIONS_CACHE_SIZE: usize = 64;
+
+/// How a runtime hashes transactions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum HashingStrategy {
+	/// The hashing of the block, for runtimes implementing a version of
+	/// [`TaggedTransactionQueue`] older than 4.
+	Native,
+	/// The [`TaggedTransactionQueue::transaction_hash`] runtime api.
+	Runtime,
+}
+
 /// The transaction pool logic for full client.
-pub struct FullChainApi<Client, Block> {
+pub struct FullChainApi<Client, Block: BlockT> {
 	client: Arc<Client>,
 	_marker: PhantomData<Block>,
 	metrics: Option<Arc<ApiMetrics>>,
 	validation_pool: Arc<Mutex<mpsc::Sender<Pin<Box<dyn Future<Output = ()> + Send>>>>>,
+	/// The runtime spec versions of recently queried blocks, the oldest first.
+	spec_versions: parking_lot::Mutex<LinkedHashMap<Block::Hash, u32>>,
+	/// The hashing strategy of every runtime spec version seen so far.
+	hashing_strategies: parking_lot::Mutex<HashMap<u32, HashingStrategy>>,
 }
 
 /// Spawn a validation task that will be used by the transaction pool to validate transactions.
@@ -76,7 +95,7 @@ fn spawn_validation_pool_task(
 	);
 }
 
-impl<Client, Block> FullChainApi<Client, Block> {
+impl<Client, Block: BlockT> FullChainApi<Client, Block> {
 	/// Create new transaction pool logic.
 	pub fn new(
 		client: Arc<Client>,
@@ -106,6 +125,8 @@ impl<Client, Block> FullChainApi<Client, Block> {
 			validation_pool: Arc::new(Mutex::new(sender)),
 			_marker: Default::default(),
 			metrics,
+			spec_versions: Default::default(),
+			hashing_strategies: Default::default(),
 		}
 	}
 }
@@ -151,6 +172,9 @@ where
 				.send(
 					async move {
 						let res = validate_transaction_blocking(&*client, at, source, uxt);
+						if matches!(res, Err(Error::Pool(TxPoolError::ValidationPanicked(_)))) {
+							metrics.report(|m| m.validations_panicked.inc());
+						}
 						let _ = tx.send(res);
 						metrics.report(|m| m.validations_finished.inc());
 					}
@@ -188,6 +212,32 @@ where
 		ex.using_encoded(|x| (<traits::HashingFor<Block> as traits::Hash>::hash(x), x.len()))
 	}
 
+	fn hash_and_length_at(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		ex: &graph::ExtrinsicFor<Self>,
+	) -> (graph::ExtrinsicHash<Self>, usize) {
+		let (hash, length) = self.hash_and_length(ex);
+		if self.hashing_strategy(at) == HashingStrategy::Native {
+			return (hash, length)
+		}
+
+		match self.client.runtime_api().transaction_hash(at, ex.clone()) {
+			Ok(runtime_hash) => (runtime_hash, length),
+			Err(e) => {
+				log::debug!(target: LOG_TARGET, "[{:?}] Failed to hash at {:?}: {}", hash, at, e);
+				(hash, length)
+			},
+		}
+	}
+
+	fn runtime_spec_version(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+	) -> error::Result<Option<u32>> {
+		self.spec_version(at).map(Some)
+	}
+
 	fn block_header(
 		&self,
 		hash: <Self::Block as BlockT>::Hash,
@@ -204,6 +254,79 @@ where
 	}
 }
 
+/// Map an error of calling into the runtime to validate a transaction.
+///
+/// The runtime panicking or trapping is reported as [`TxPoolError::ValidationPanicked`], so that
+/// the transaction is banned and its sender is not blamed for it.
+fn validation_error(e: sp_api::ApiError) -> Error {
+	let aborted = std::iter::successors(Some(&e as &dyn std::error::Error), |e| e.source())
+		.any(|e| e.to_string().contains("Execution aborted due to"));
+	if aborted {
+		Error::Pool(TxPoolError::ValidationPanicked(e.to_string()))
+	} else {
+		Error::RuntimeApi(e.to_string())
+	}
+}
+
+impl<Client, Block> FullChainApi<Client, Block>
+where
+	Block: BlockT,
+	Client: ProvideRuntimeApi<Block>,
+	Client::Api: TaggedTransactionQueue<Block>,
+{
+	/// Returns the runtime spec version at block `at`.
+	///
+	/// The spec versions of recent blocks are cached, so the runtime is queried only once per
+	/// block.
+	fn spec_version(&self, at: Block::Hash) -> error::Result<u32> {
+		if let Some(spec_version) = self.spec_versions.lock().get_refresh(&at) {
+			return Ok(*spec_version)
+		}
+
+		let spec_version = self
+			.client
+			.runtime_api()
+			.version(at)
+			.map_err(|e| Error::RuntimeApi(e.to_string()))?
+			.spec_version;
+		let mut spec_versions = self.spec_versions.lock();
+		spec_versions.insert(at, spec_version);
+		if spec_versions.len() > SPEC_VERSIONS_CACHE_SIZE {
+			spec_versions.pop_front();
+		}
+		Ok(spec_version)
+	}
+
+	/// Returns how the runtime at block `at` hashes transactions.
+	///
+	/// The strategy is only looked up once per runtime spec version. Transactions are hashed
+	/// natively if the runtime can't be queried.
+	fn hashing_strategy(&self, at: Block::Hash) -> HashingStrategy {
+		let spec_version = match self.spec_version(at) {
+			Ok(spec_version) => spec_version,
+			Err(e) => {
+				log::debug!(target: LOG_TARGET, "Failed to get runtime version at {:?}: {}", at, e);
+				return HashingStrategy::Native
+			},
+		};
+		if let Some(strategy) = self.hashing_strategies.lock().get(&spec_version) {
+			return *strategy
+		}
+
+		let strategy =
+			match self.client.runtime_api().api_version::<dyn TaggedTransactionQueue<Block>>(at) {
+				Ok(Some(version)) if version >= 4 => HashingStrategy::Runtime,
+				Ok(_) => HashingStrategy::Native,
+				Err(e) => {
+					log::debug!(target: LOG_TARGET, "Failed to get api version at {:?}: {}", at, e);
+					return HashingStrategy::Native
+				},
+			};
+		self.hashing_strategies.lock().insert(spec_version, strategy);
+		strategy
+	}
+}
+
 /// Helper function to validate a transaction using a full chain API.
 /// This method will call into the runtime to perform the validation.
 fn validate_transaction_blocking<Client, Block>(
@@ -240,8 +363,7 @@ where
 			sp_tracing::Level::TRACE, "runtime::validate_transaction";
 		{
 			if api_version >= 3 {
-				runtime_api.validate_transaction(at, source, uxt, at)
-					.map_err(|e| Error::RuntimeApi(e.to_string()))
+				runtime_api.validate_transaction(at, source, uxt, at).map_err(validation_error)
 			} else {
 				let block_number = client.to_number(&BlockId::Hash(at))
 					.map_err(|e| Error::RuntimeApi(e.to_string()))?
@@ -261,11 +383,11 @@ where
 				if api_version == 2 {
 					#[allow(deprecated)] // old validate_transaction
 					runtime_api.validate_transaction_before_version_3(at, source, uxt)
-						.map_err(|e| Error::RuntimeApi(e.to_string()))
+						.map_err(validation_error)
 				} else {
 					#[allow(deprecated)] // old validate_transaction
 					runtime_api.validate_transaction_before_version_2(at, uxt)
-						.map_err(|e| Error::RuntimeApi(e.to_string()))
+						.map_err(validation_error)
 				}
 			}
 		})
@@ -20,11 +20,20 @@
 //!
 //! For a more full-featured pool, have a look at the `pool` module.
 
-use std::{cmp::Ordering, collections::HashSet, fmt, hash, sync::Arc};
+use std::{
+	cmp::{Ordering, Reverse},
+	collections::{HashMap, HashSet},
+	fmt, hash,
+	sync::Arc,
+	time::{Duration, Instant},
+};
 
 use crate::LOG_TARGET;
 use log::{debug, trace, warn};
-use sc_transaction_pool_api::{error, InPoolTransaction, PoolStatus};
+use sc_transaction_pool_api::{
+	error, AgePercentiles, InPoolTransaction, PoolClass, PoolStatus, PoolStatusDetailed,
+	PrunePreview,
+};
 use serde::Serialize;
 use sp_core::hexdisplay::HexDisplay;
 use sp_runtime::{
@@ -96,6 +105,8 @@ pub struct Transaction<Hash, Extrinsic> {
 	pub priority: Priority,
 	/// At which block the transaction becomes invalid?
 	pub valid_till: Longevity,
+	/// At which block the transaction was last validated.
+	pub validated_at: u64,
 	/// Tags required by the transaction.
 	pub requires: Vec<Tag>,
 	/// Tags that this transaction provides.
@@ -104,6 +115,8 @@ pub struct Transaction<Hash, Extrinsic> {
 	pub propagate: bool,
 	/// Source of that transaction.
 	pub source: Source,
+	/// Class of that transaction, partitioning the pool.
+	pub class: PoolClass,
 }
 
 impl<Hash, Extrinsic> AsRef<Extrinsic> for Transaction<Hash, Extrinsic> {
@@ -143,6 +156,14 @@ impl<Hash, Extrinsic> InPoolTransaction for Transaction<Hash, Extrinsic> {
 	fn is_propagable(&self) -> bool {
 		self.propagate
 	}
+
+	fn validated_at(&self) -> Option<u64> {
+		Some(self.validated_at)
+	}
+
+	fn source(&self) -> Source {
+		self.source
+	}
 }
 
 impl<Hash: Clone, Extrinsic: Clone> Transaction<Hash, Extrinsic> {
@@ -159,9 +180,11 @@ impl<Hash: Clone, Extrinsic: Clone> Transaction<Hash, Extrinsic> {
 			priority: self.priority,
 			source: self.source,
 			valid_till: self.valid_till,
+			validated_at: self.validated_at,
 			requires: self.requires.clone(),
 			provides: self.provides.clone(),
 			propagate: self.propagate,
+			class: self.class,
 		}
 	}
 }
@@ -183,9 +206,11 @@ where
 		write!(fmt, "hash: {:?}, ", &self.hash)?;
 		write!(fmt, "priority: {:?}, ", &self.priority)?;
 		write!(fmt, "valid_till: {:?}, ", &self.valid_till)?;
+		write!(fmt, "validated_at: {:?}, ", &self.validated_at)?;
 		write!(fmt, "bytes: {:?}, ", &self.bytes)?;
 		write!(fmt, "propagate: {:?}, ", &self.propagate)?;
 		write!(fmt, "source: {:?}, ", &self.source)?;
+		write!(fmt, "class: {:?}, ", &self.class)?;
 		write!(fmt, "requires: [{}], ", join_tags(&self.requires))?;
 		write!(fmt, "provides: [{}], ", join_tags(&self.provides))?;
 		write!(fmt, "data: {:?}", &self.data)?;
@@ -267,11 +292,22 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	/// The latter contains transactions that have all the requirements satisfied and are
 	/// ready to be included in the block.
 	pub fn import(&mut self, tx: Transaction<Hash, Ex>) -> error::Result<Imported<Hash, Ex>> {
+		self.import_at(tx, Instant::now())
+	}
+
+	/// Imports transaction to the pool like [`Self::import`], taking `now` as the time of the
+	/// import.
+	pub fn import_at(
+		&mut self,
+		tx: Transaction<Hash, Ex>,
+		now: Instant,
+	) -> error::Result<Imported<Hash, Ex>> {
 		if self.is_imported(&tx.hash) {
 			return Err(error::Error::AlreadyImported(Box::new(tx.hash)))
 		}
 
-		let tx = WaitingTransaction::new(tx, self.ready.provided_tags(), &self.recently_pruned);
+		let tx =
+			WaitingTransaction::new(tx, self.ready.provided_tags(), &self.recently_pruned, now);
 		trace!(target: LOG_TARGET, "[{:?}] {:?}", tx.transaction.hash, tx);
 		debug!(
 			target: LOG_TARGET,
@@ -364,6 +400,22 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		self.future.all()
 	}
 
+	/// Returns future transactions in the pool in the order they were imported.
+	pub fn futures_by_import_order(&self) -> Vec<Arc<Transaction<Hash, Ex>>> {
+		self.future.by_import_order()
+	}
+
+	/// Returns at most `limit` future transactions in the pool in the order they were imported,
+	/// skipping the first `offset` of them, together with the total number of future
+	/// transactions.
+	pub fn futures_by_import_order_paged(
+		&self,
+		offset: usize,
+		limit: usize,
+	) -> (Vec<Arc<Transaction<Hash, Ex>>>, usize) {
+		(self.future.by_import_order_paged(offset, limit), self.future.len())
+	}
+
 	/// Returns pool transactions given list of hashes.
 	///
 	/// Includes both ready and future pool. For every hash in the `hashes`
@@ -384,8 +436,9 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	///
 	/// Removes and returns worst transactions from the queues and all transactions that depend on
 	/// them. Technically the worst transaction should be evaluated by computing the entire pending
-	/// set. We use a simplified approach to remove transactions with the lowest priority first or
-	/// those that occupy the pool for the longest time in case priority is the same.
+	/// set. We use a simplified approach to remove transactions of the lowest [`PoolClass`] first,
+	/// and within it those with the lowest priority or those that occupy the pool for the longest
+	/// time in case priority is the same.
 	pub fn enforce_limits(
 		&mut self,
 		ready: &Limit,
@@ -403,7 +456,13 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 						// while it prefers priority like need here, it also prefers older
 						// transactions for inclusion purposes and limit enforcement needs to prefer
 						// newer transactions instead and drop the older ones.
-						match worst.transaction.priority.cmp(&transaction.transaction.priority) {
+						let worst_tx = &worst.transaction;
+						let current_tx = &transaction.transaction;
+						match worst_tx
+							.class
+							.cmp(&current_tx.class)
+							.then(worst_tx.priority.cmp(&current_tx.priority))
+						{
 							Ordering::Less => worst,
 							Ordering::Equal =>
 								if worst.insertion_id > transaction.insertion_id {
@@ -428,7 +487,10 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			// find the worst transaction
 			let worst = self.future.fold(|worst, current| match worst {
 				None => Some(current.clone()),
-				Some(ref tx) if tx.imported_at > current.imported_at => Some(current.clone()),
+				Some(ref tx)
+					if (tx.transaction.class, tx.imported_at) >
+						(current.transaction.class, current.imported_at) =>
+					Some(current.clone()),
 				other => other,
 			});
 
@@ -442,6 +504,65 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		removed
 	}
 
+	/// Makes sure that the transactions of every class stay within the given per-class limits,
+	/// counting both the ready and the future queue.
+	///
+	/// Removes and returns worst transactions of the classes exceeding their limits and all
+	/// transactions that depend on them. Future transactions of a class are removed first, the
+	/// oldest one first, followed by the ready ones as in [`Self::enforce_limits`].
+	pub fn enforce_class_limits(
+		&mut self,
+		limits: &HashMap<PoolClass, Limit>,
+	) -> Vec<Arc<Transaction<Hash, Ex>>> {
+		let mut removed = vec![];
+
+		for (class, limit) in limits {
+			loop {
+				let mut count = 0;
+				let mut bytes = 0;
+				let worst_future = self.future.fold::<(Instant, Hash), _>(|worst, current| {
+					let transaction = &current.transaction;
+					if transaction.class != *class {
+						return worst
+					}
+					count += 1;
+					bytes += transaction.bytes;
+					match worst {
+						Some((imported_at, _)) if imported_at <= current.imported_at => worst,
+						_ => Some((current.imported_at, transaction.hash.clone())),
+					}
+				});
+				// The lowest priority is the worst, the newest transaction in case of a tie.
+				type ReadyKey = (Priority, Reverse<u64>);
+				let worst_ready = self.ready.fold::<(ReadyKey, Hash), _>(|worst, current| {
+					let transaction = &current.transaction.transaction;
+					if transaction.class != *class {
+						return worst
+					}
+					count += 1;
+					bytes += transaction.bytes;
+					let key = (transaction.priority, Reverse(current.transaction.insertion_id));
+					match worst {
+						Some((worst_key, _)) if worst_key <= key => worst,
+						_ => Some((key, transaction.hash.clone())),
+					}
+				});
+
+				if !limit.is_exceeded(count, bytes) {
+					break
+				}
+				let worst =
+					worst_future.map(|(_, hash)| hash).or(worst_ready.map(|(_, hash)| hash));
+				match worst {
+					Some(hash) => removed.append(&mut self.remove_subtree(&[hash])),
+					None => break,
+				}
+			}
+		}
+
+		removed
+	}
+
 	/// Removes all transactions represented by the hashes and all other transactions
 	/// that depend on them.
 	///
@@ -502,6 +623,21 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		PruneStatus { pruned, failed, promoted }
 	}
 
+	/// Returns what [`Self::prune_tags`] would do for the given tags, without modifying the pool.
+	///
+	/// Future transactions which would become ready are assumed to be imported to the ready
+	/// queue successfully.
+	pub fn prune_preview(&self, tags: impl IntoIterator<Item = Tag>) -> PrunePreview {
+		let tags = tags.into_iter().collect::<Vec<_>>();
+		let pruned = self.ready.prune_tags_preview(tags.iter().cloned());
+		let promoted = self.future.satisfy_tags_preview(tags);
+		PrunePreview {
+			would_prune: pruned.len(),
+			would_remain_ready: self.ready.len() - pruned.len(),
+			would_promote_from_future: promoted.len(),
+		}
+	}
+
 	/// Get pool status.
 	pub fn status(&self) -> PoolStatus {
 		PoolStatus {
@@ -509,8 +645,52 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			ready_bytes: self.ready.bytes(),
 			future: self.future.len(),
 			future_bytes: self.future.bytes(),
+			min_ready_priority: None,
+		}
+	}
+
+	/// Returns the status of the pool, including the age of its transactions at `now`.
+	///
+	/// The age of a transaction is the time since it was imported to the pool, including the
+	/// time it spent in the future queue before it became ready.
+	pub fn status_detailed(&self, now: Instant) -> PoolStatusDetailed {
+		let ages = |import_times: Vec<Instant>| {
+			import_times
+				.into_iter()
+				.map(|imported_at| now.saturating_duration_since(imported_at))
+				.collect::<Vec<Duration>>()
+		};
+		let ready_ages = ages(self.ready.import_times());
+		let future_ages = ages(self.future.import_times());
+		PoolStatusDetailed {
+			status: self.status(),
+			oldest_ready_age: ready_ages.iter().max().copied(),
+			oldest_future_age: future_ages.iter().max().copied(),
+			ready_age_percentiles: AgePercentiles::from_ages(ready_ages),
+			future_age_percentiles: AgePercentiles::from_ages(future_ages),
 		}
 	}
+
+	/// Returns the priority needed to be included within the first `within_bytes` of ready
+	/// transactions.
+	///
+	/// See [`ReadyTransactions::priority_threshold`].
+	pub fn ready_priority_threshold(&self, within_bytes: usize) -> Option<Priority> {
+		self.ready.priority_threshold(within_bytes)
+	}
+
+	/// Returns the lowest priority in the ready queue.
+	pub fn min_ready_priority(&self) -> Option<Priority> {
+		self.ready.min_priority()
+	}
+
+	/// Returns the priority a transaction providing `tag` has to exceed to replace the ready
+	/// transaction currently providing it.
+	///
+	/// See [`ReadyTransactions::min_replacement_priority`].
+	pub fn min_replacement_priority(&self, tag: &Tag) -> Option<Priority> {
+		self.ready.min_replacement_priority(tag)
+	}
 }
 
 /// Queue limits
@@ -527,6 +707,11 @@ impl Limit {
 	pub fn is_exceeded(&self, count: usize, bytes: usize) -> bool {
 		self.count < count || self.total_bytes < bytes
 	}
+
+	/// Returns true if any of the provided values reaches the limit.
+	pub fn is_reached(&self, count: usize, bytes: usize) -> bool {
+		self.count <= count || self.total_bytes <= bytes
+	}
 }
 
 #[cfg(test)]
@@ -545,10 +730,12 @@ mod tests {
 		hash: 1u64,
 		priority: 5u64,
 		valid_till: 64u64,
+		validated_at: 0u64,
 		requires: vec![],
 		provides: vec![],
 		propagate: true,
 		source: Source::External,
+		class: PoolClass::Normal,
 	};
 
 	#[test]
@@ -936,7 +1123,7 @@ mod tests {
 				}
 			),
 			"Transaction { \
-hash: 4, priority: 1000, valid_till: 64, bytes: 1, propagate: true, \
+hash: 4, priority: 1000, valid_till: 64, validated_at: 0, bytes: 1, propagate: true, \
 source: TransactionSource::External, requires: [03, 02], provides: [04], data: [4]}"
 				.to_owned()
 		);
@@ -1042,4 +1229,118 @@ source: TransactionSource::External, requires: [03, 02], provides: [04], data: [
 		assert_eq!(pool.reject_future_transactions, true);
 		assert_eq!(pool.future.len(), 1);
 	}
+
+	#[test]
+	fn should_evict_normal_transactions_first_when_limits_are_exceeded() {
+		// given
+		let mut pool = pool();
+		let operational = Transaction {
+			hash: 1,
+			priority: 1,
+			provides: vec![vec![1]],
+			class: PoolClass::Operational,
+			..DEFAULT_TX.clone()
+		};
+		pool.import(operational).unwrap();
+		for hash in 2..4 {
+			pool.import(Transaction {
+				hash,
+				priority: 10,
+				provides: vec![vec![hash as u8]],
+				..DEFAULT_TX.clone()
+			})
+			.unwrap();
+		}
+		let limit = Limit { count: 2, total_bytes: 1000 };
+
+		// when
+		let removed = pool.enforce_limits(&limit, &limit);
+
+		// then
+		assert_eq!(removed.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![3]);
+		assert!(pool.ready_by_hash(&1).is_some());
+	}
+
+	#[test]
+	fn should_enforce_class_limits_without_touching_other_classes() {
+		// given
+		let mut pool = pool();
+		pool.import(Transaction {
+			hash: 1,
+			priority: 1,
+			provides: vec![vec![1]],
+			class: PoolClass::Operational,
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+		for hash in 2..7 {
+			pool.import(Transaction {
+				hash,
+				priority: 10 + hash,
+				provides: vec![vec![hash as u8]],
+				..DEFAULT_TX.clone()
+			})
+			.unwrap();
+		}
+		// a future normal transaction is evicted before any ready one.
+		pool.import(Transaction {
+			hash: 7,
+			priority: 100,
+			requires: vec![vec![100]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+		let limits = HashMap::from([(PoolClass::Normal, Limit { count: 3, total_bytes: 1000 })]);
+
+		// when
+		let removed = pool.enforce_class_limits(&limits);
+
+		// then
+		let mut removed = removed.iter().map(|tx| tx.hash).collect::<Vec<_>>();
+		removed.sort();
+		assert_eq!(removed, vec![2, 3, 7]);
+		assert_eq!(pool.ready().count(), 4);
+		assert!(pool.ready_by_hash(&1).is_some());
+		assert!(pool.enforce_class_limits(&limits).is_empty());
+	}
+
+	#[test]
+	fn status_detailed_reports_age_of_transactions() {
+		// given
+		let mut pool = pool();
+		let start = Instant::now();
+		let at = |secs| start + Duration::from_secs(secs);
+		let secs = |secs| Some(Duration::from_secs(secs));
+		let ready = |hash, provides: u8| Transaction {
+			hash,
+			provides: vec![vec![provides]],
+			..DEFAULT_TX.clone()
+		};
+		let future = |hash| Transaction { hash, requires: vec![vec![9]], ..DEFAULT_TX.clone() };
+		pool.import_at(ready(1, 1), at(0)).unwrap();
+		pool.import_at(future(2), at(10)).unwrap();
+		pool.import_at(future(3), at(20)).unwrap();
+		pool.import_at(ready(4, 4), at(30)).unwrap();
+
+		// when
+		let status = pool.status_detailed(at(60));
+
+		// then
+		assert_eq!((status.status.ready, status.status.future), (2, 2));
+		assert_eq!(status.oldest_ready_age, secs(60));
+		assert_eq!(status.ready_age_percentiles, AgePercentiles { p50: secs(30), p95: secs(60) });
+		assert_eq!(status.oldest_future_age, secs(50));
+		assert_eq!(status.future_age_percentiles, AgePercentiles { p50: secs(40), p95: secs(50) });
+
+		// when the future transactions are promoted, they keep their original age.
+		pool.import_at(ready(5, 9), at(40)).unwrap();
+		let status = pool.status_detailed(at(100));
+
+		// then
+		assert_eq!((status.status.ready, status.status.future), (5, 0));
+		assert_eq!(status.oldest_ready_age, secs(100));
+		assert_eq!(status.ready_age_percentiles, AgePercentiles { p50: secs(80), p95: secs(100) });
+		assert_eq!(status.oldest_future_age, None);
+		assert_eq!(status.future_age_percentiles, AgePercentiles::default());
+	}
 }
@@ -34,7 +34,7 @@ pub struct WaitingTransaction<Hash, Ex> {
 	pub transaction: Arc<Transaction<Hash, Ex>>,
 	/// Tags that are required and have not been satisfied yet by other transactions in the pool.
 	pub missing_tags: HashSet<Tag>,
-	/// Time of import to the Future Queue.
+	/// Time of import to the pool.
 	pub imported_at: Instant,
 }
 
@@ -75,6 +75,7 @@ impl<Hash, Ex> WaitingTransaction<Hash, Ex> {
 		transaction: Transaction<Hash, Ex>,
 		provided: &HashMap<Tag, Hash>,
 		recently_pruned: &[HashSet<Tag>],
+		imported_at: Instant,
 	) -> Self {
 		let missing_tags = transaction
 			.requires
@@ -89,7 +90,7 @@ impl<Hash, Ex> WaitingTransaction<Hash, Ex> {
 			.cloned()
 			.collect();
 
-		Self { transaction: Arc::new(transaction), missing_tags, imported_at: Instant::now() }
+		Self { transaction: Arc::new(transaction), missing_tags, imported_at }
 	}
 
 	/// Marks the tag as satisfied.
@@ -195,6 +196,32 @@ impl<Hash: hash::Hash + Eq + Clone, Ex> FutureTransactions<Hash, Ex> {
 		became_ready
 	}
 
+	/// Returns the hashes of the transactions which would become ready if the given tags were
+	/// provided, including the ones unlocked by the tags those transactions provide in turn.
+	///
+	/// Unlike [`Self::satisfy_tags`] this does not modify the queue.
+	pub fn satisfy_tags_preview(&self, tags: impl IntoIterator<Item = Tag>) -> Vec<Hash> {
+		let mut provided = HashSet::new();
+		let mut to_provide = tags.into_iter().collect::<Vec<_>>();
+		let mut became_ready = vec![];
+
+		while let Some(tag) = to_provide.pop() {
+			if !provided.insert(tag.clone()) {
+				continue
+			}
+			for hash in self.wanted_tags.get(&tag).into_iter().flatten() {
+				let tx = self.waiting.get(hash).expect(WAITING_PROOF);
+				// The transaction becomes ready when its last missing tag is provided.
+				if tx.missing_tags.iter().all(|missing| provided.contains(missing)) {
+					became_ready.push(hash.clone());
+					to_provide.extend(tx.transaction.provides.iter().cloned());
+				}
+			}
+		}
+
+		became_ready
+	}
+
 	/// Removes transactions for given list of hashes.
 	///
 	/// Returns a list of actually removed transactions.
@@ -234,6 +261,28 @@ impl<Hash: hash::Hash + Eq + Clone, Ex> FutureTransactions<Hash, Ex> {
 		self.waiting.values().map(|waiting| &*waiting.transaction)
 	}
 
+	/// Returns all future transactions in the order they were imported.
+	pub fn by_import_order(&self) -> Vec<Arc<Transaction<Hash, Ex>>> {
+		self.by_import_order_paged(0, usize::MAX)
+	}
+
+	/// Returns at most `limit` future transactions in the order they were imported, skipping the
+	/// first `offset` of them.
+	pub fn by_import_order_paged(
+		&self,
+		offset: usize,
+		limit: usize,
+	) -> Vec<Arc<Transaction<Hash, Ex>>> {
+		let mut waiting = self.waiting.values().collect::<Vec<_>>();
+		waiting.sort_by_key(|waiting| waiting.imported_at);
+		waiting
+			.into_iter()
+			.skip(offset)
+			.take(limit)
+			.map(|waiting| waiting.transaction.clone())
+			.collect()
+	}
+
 	/// Removes and returns all future transactions.
 	pub fn clear(&mut self) -> Vec<Arc<Transaction<Hash, Ex>>> {
 		self.wanted_tags.clear();
@@ -249,4 +298,9 @@ impl<Hash: hash::Hash + Eq + Clone, Ex> FutureTransactions<Hash, Ex> {
 	pub fn bytes(&self) -> usize {
 		self.waiting.values().fold(0, |acc, tx| acc + tx.transaction.bytes)
 	}
+
+	/// Returns the time every transaction in this queue was imported to the pool.
+	pub fn import_times(&self) -> Vec<Instant> {
+		self.waiting.values().map(|waiting| waiting.imported_at).collect()
+	}
 }
@@ -16,32 +16,54 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{collections::HashMap, fmt::Debug, hash};
+use std::{collections::HashMap, hash, time::Instant};
 
 use crate::LOG_TARGET;
+use futures::channel::oneshot;
 use linked_hash_map::LinkedHashMap;
 use log::{debug, trace};
+use sc_transaction_pool_api::{BroadcastInfo, RetractedBlock, TransactionStatus, WaitReadyOutcome};
 use serde::Serialize;
 use sp_runtime::traits;
 
 use super::{watcher, BlockHash, ChainApi, ExtrinsicHash};
 
+/// Sender notifying a waiter about a transaction becoming ready.
+type ReadyWaiter<H, C> = oneshot::Sender<WaitReadyOutcome<H, BlockHash<C>>>;
+
 /// Extrinsic pool default listener.
 pub struct Listener<H: hash::Hash + Eq, C: ChainApi> {
 	watchers: HashMap<H, watcher::Sender<H, ExtrinsicHash<C>>>,
 	finality_watchers: LinkedHashMap<ExtrinsicHash<C>, Vec<H>>,
+	ready_waiters: HashMap<H, Vec<ReadyWaiter<H, C>>>,
+	/// The peers the extrinsics in the pool were broadcast to.
+	broadcasts: HashMap<H, BroadcastInfo>,
+	/// Maximum number of watchers receiving all events, see [`Listener::create_watcher`].
+	max_watchers: usize,
+	/// Maximum number of peers recorded per extrinsic, see [`Listener::broadcasted`].
+	max_broadcast_peers: usize,
 }
 
 /// Maximum number of blocks awaiting finality at any time.
 const MAX_FINALITY_WATCHERS: usize = 512;
 
-impl<H: hash::Hash + Eq + Debug, C: ChainApi> Default for Listener<H, C> {
-	fn default() -> Self {
-		Self { watchers: Default::default(), finality_watchers: Default::default() }
-	}
-}
+/// Maximum number of waiters for transactions to become ready at any time.
+const MAX_READY_WAITERS: usize = 4096;
 
 impl<H: hash::Hash + traits::Member + Serialize, C: ChainApi> Listener<H, C> {
+	/// Creates a new listener serving at most `max_watchers` watchers with all events and
+	/// recording at most `max_broadcast_peers` peers per broadcast extrinsic.
+	pub fn new(max_watchers: usize, max_broadcast_peers: usize) -> Self {
+		Self {
+			watchers: Default::default(),
+			finality_watchers: Default::default(),
+			ready_waiters: Default::default(),
+			broadcasts: Default::default(),
+			max_watchers,
+			max_broadcast_peers,
+		}
+	}
+
 	fn fire<F>(&mut self, hash: &H, fun: F)
 	where
 		F: FnOnce(&mut watcher::Sender<H, ExtrinsicHash<C>>),
@@ -58,26 +80,147 @@ impl<H: hash::Hash + traits::Member + Serialize, C: ChainApi> Listener<H, C> {
 		}
 	}
 
+	/// Notifies and removes all waiters of the given extrinsic.
+	fn resolve_ready_waiters(&mut self, hash: &H, outcome: WaitReadyOutcome<H, BlockHash<C>>) {
+		for waiter in self.ready_waiters.remove(hash).into_iter().flatten() {
+			let _ = waiter.send(outcome.clone());
+		}
+	}
+
+	/// Registers a waiter which is notified once the given extrinsic becomes ready or leaves the
+	/// pool.
+	///
+	/// Returns `None` if there are too many waiters already.
+	pub fn add_ready_waiter(
+		&mut self,
+		hash: H,
+	) -> Option<oneshot::Receiver<WaitReadyOutcome<H, BlockHash<C>>>> {
+		if self.ready_waiters_count() >= MAX_READY_WAITERS {
+			// Make room by removing the waiters which are not awaited anymore.
+			self.ready_waiters.retain(|_, waiters| {
+				waiters.retain(|waiter| !waiter.is_canceled());
+				!waiters.is_empty()
+			});
+			if self.ready_waiters_count() >= MAX_READY_WAITERS {
+				debug!(target: LOG_TARGET, "[{:?}] Too many ready waiters", hash);
+				return None
+			}
+		}
+
+		let (sender, receiver) = oneshot::channel();
+		self.ready_waiters.entry(hash).or_default().push(sender);
+		Some(receiver)
+	}
+
+	/// Removes the waiters of the given extrinsic which are not awaited anymore.
+	pub fn remove_canceled_ready_waiters(&mut self, hash: &H) {
+		if let Some(waiters) = self.ready_waiters.get_mut(hash) {
+			waiters.retain(|waiter| !waiter.is_canceled());
+			if waiters.is_empty() {
+				self.ready_waiters.remove(hash);
+			}
+		}
+	}
+
+	/// The number of waiters for extrinsics to become ready.
+	pub fn ready_waiters_count(&self) -> usize {
+		self.ready_waiters.values().map(Vec::len).sum()
+	}
+
 	/// Creates a new watcher for given verified extrinsic.
 	///
-	/// The watcher can be used to subscribe to life-cycle events of that extrinsic.
+	/// The watcher can be used to subscribe to life-cycle events of that extrinsic. Once the
+	/// maximum number of watchers is reached, a degraded watcher is returned instead, which is
+	/// only notified about the final event.
 	pub fn create_watcher(&mut self, hash: H) -> watcher::Watcher<H, ExtrinsicHash<C>> {
+		let degraded = self.watchers_count() >= self.max_watchers;
 		let sender = self.watchers.entry(hash.clone()).or_insert_with(watcher::Sender::default);
-		sender.new_watcher(hash)
+		if degraded {
+			debug!(target: LOG_TARGET, "[{:?}] Too many watchers, watching degraded", hash);
+			sender.new_degraded_watcher(hash)
+		} else {
+			sender.new_watcher(hash)
+		}
+	}
+
+	/// Creates a new watcher for an extrinsic which is already known to the pool.
+	///
+	/// The watcher receives the current `status` of the extrinsic right away, unless it is
+	/// degraded, see [`Listener::create_watcher`].
+	pub fn create_watcher_with_status(
+		&mut self,
+		hash: H,
+		status: TransactionStatus<H, BlockHash<C>>,
+	) -> watcher::Watcher<H, ExtrinsicHash<C>> {
+		let degraded = self.watchers_count() >= self.max_watchers;
+		let sender = self.watchers.entry(hash.clone()).or_insert_with(watcher::Sender::default);
+		if degraded {
+			debug!(target: LOG_TARGET, "[{:?}] Too many watchers, watching degraded", hash);
+			sender.new_degraded_watcher(hash)
+		} else {
+			sender.new_watcher_with_status(hash, status)
+		}
+	}
+
+	/// Returns the block the extrinsic was included in and its index in the block, if the block
+	/// is not finalized yet.
+	pub fn in_block(&self, tx: &H) -> Option<(BlockHash<C>, usize)> {
+		self.finality_watchers.iter().find_map(|(block_hash, txs)| {
+			txs.iter().position(|h| h == tx).map(|index| (*block_hash, index))
+		})
+	}
+
+	/// The number of watchers receiving all events.
+	pub fn watchers_count(&self) -> usize {
+		self.watchers.values().map(watcher::Sender::watchers_count).sum()
+	}
+
+	/// The maximum number of watchers receiving all events.
+	pub fn max_watchers(&self) -> usize {
+		self.max_watchers
 	}
 
 	/// Notify the listeners about extrinsic broadcast.
+	///
+	/// The peers are also added to the [`BroadcastInfo`] of the extrinsic, skipping the known
+	/// ones and the ones beyond the maximum number of peers.
 	pub fn broadcasted(&mut self, hash: &H, peers: Vec<String>) {
 		trace!(target: LOG_TARGET, "[{:?}] Broadcasted", hash);
+		let now = Instant::now();
+		let info = self.broadcasts.entry(hash.clone()).or_insert_with(|| BroadcastInfo {
+			peers: Vec::new(),
+			first_broadcast: now,
+			last_broadcast: now,
+		});
+		info.last_broadcast = now;
+		for peer in &peers {
+			if info.peers.len() >= self.max_broadcast_peers {
+				break
+			}
+			if !info.peers.contains(peer) {
+				info.peers.push(peer.clone());
+			}
+		}
 		self.fire(hash, |watcher| watcher.broadcast(peers));
 	}
 
+	/// Returns the peers the extrinsic was broadcast to, if it was broadcast while in the pool.
+	pub fn broadcast_info(&self, hash: &H) -> Option<BroadcastInfo> {
+		self.broadcasts.get(hash).cloned()
+	}
+
 	/// New transaction was added to the ready pool or promoted from the future pool.
 	pub fn ready(&mut self, tx: &H, old: Option<&H>) {
 		trace!(target: LOG_TARGET, "[{:?}] Ready (replaced with {:?})", tx, old);
 		self.fire(tx, |watcher| watcher.ready());
+		self.resolve_ready_waiters(tx, WaitReadyOutcome::Ready);
 		if let Some(old) = old {
+			self.broadcasts.remove(old);
 			self.fire(old, |watcher| watcher.usurped(tx.clone()));
+			self.resolve_ready_waiters(
+				old,
+				WaitReadyOutcome::AlreadyTerminal(TransactionStatus::Usurped(tx.clone())),
+			);
 		}
 	}
 
@@ -90,21 +233,33 @@ impl<H: hash::Hash + traits::Member + Serialize, C: ChainApi> Listener<H, C> {
 	/// Transaction was dropped from the pool because of the limit.
 	pub fn dropped(&mut self, tx: &H, by: Option<&H>) {
 		trace!(target: LOG_TARGET, "[{:?}] Dropped (replaced with {:?})", tx, by);
+		self.broadcasts.remove(tx);
 		self.fire(tx, |watcher| match by {
 			Some(t) => watcher.usurped(t.clone()),
 			None => watcher.dropped(),
-		})
+		});
+		let status = match by {
+			Some(t) => TransactionStatus::Usurped(t.clone()),
+			None => TransactionStatus::Dropped,
+		};
+		self.resolve_ready_waiters(tx, WaitReadyOutcome::AlreadyTerminal(status));
 	}
 
 	/// Transaction was removed as invalid.
 	pub fn invalid(&mut self, tx: &H) {
 		debug!(target: LOG_TARGET, "[{:?}] Extrinsic invalid", tx);
+		self.broadcasts.remove(tx);
 		self.fire(tx, |watcher| watcher.invalid());
+		self.resolve_ready_waiters(
+			tx,
+			WaitReadyOutcome::AlreadyTerminal(TransactionStatus::Invalid),
+		);
 	}
 
 	/// Transaction was pruned from the pool.
 	pub fn pruned(&mut self, block_hash: BlockHash<C>, tx: &H) {
 		debug!(target: LOG_TARGET, "[{:?}] Pruned at {:?}", tx, block_hash);
+		self.broadcasts.remove(tx);
 		// Get the transactions included in the given block hash.
 		let txs = self.finality_watchers.entry(block_hash).or_insert(vec![]);
 		txs.push(tx.clone());
@@ -112,6 +267,10 @@ impl<H: hash::Hash + traits::Member + Serialize, C: ChainApi> Listener<H, C> {
 		let tx_index = txs.len() - 1;
 
 		self.fire(tx, |watcher| watcher.in_block(block_hash, tx_index));
+		self.resolve_ready_waiters(
+			tx,
+			WaitReadyOutcome::AlreadyTerminal(TransactionStatus::InBlock((block_hash, tx_index))),
+		);
 
 		while self.finality_watchers.len() > MAX_FINALITY_WATCHERS {
 			if let Some((hash, txs)) = self.finality_watchers.pop_front() {
@@ -123,10 +282,10 @@ impl<H: hash::Hash + traits::Member + Serialize, C: ChainApi> Listener<H, C> {
 	}
 
 	/// The block this transaction was included in has been retracted.
-	pub fn retracted(&mut self, block_hash: BlockHash<C>) {
-		if let Some(hashes) = self.finality_watchers.remove(&block_hash) {
+	pub fn retracted(&mut self, block: RetractedBlock<BlockHash<C>>) {
+		if let Some(hashes) = self.finality_watchers.remove(&block.hash) {
 			for hash in hashes {
-				self.fire(&hash, |watcher| watcher.retracted(block_hash))
+				self.fire(&hash, |watcher| watcher.retracted(block.clone()))
 			}
 		}
 	}
@@ -16,11 +16,15 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+	collections::{HashMap, HashSet},
+	sync::Arc,
+	time::Duration,
+};
 
 use crate::LOG_TARGET;
 use futures::{channel::mpsc::Receiver, Future};
-use sc_transaction_pool_api::error;
+use sc_transaction_pool_api::{error, PrunePreview, ValidationAnchor};
 use sp_blockchain::TreeRoute;
 use sp_runtime::{
 	generic::BlockId,
@@ -91,6 +95,31 @@ pub trait ChainApi: Send + Sync {
 	/// Returns hash and encoding length of the extrinsic.
 	fn hash_and_length(&self, uxt: &ExtrinsicFor<Self>) -> (ExtrinsicHash<Self>, usize);
 
+	/// Returns hash and encoding length of the extrinsic, as hashed by the runtime at the given
+	/// block.
+	///
+	/// The hashing of transactions may change with a runtime upgrade. The default implementation
+	/// ignores the block and falls back to [`Self::hash_and_length`].
+	fn hash_and_length_at(
+		&self,
+		_at: <Self::Block as BlockT>::Hash,
+		uxt: &ExtrinsicFor<Self>,
+	) -> (ExtrinsicHash<Self>, usize) {
+		self.hash_and_length(uxt)
+	}
+
+	/// Returns the spec version of the runtime at the given block, if known.
+	///
+	/// Transactions are re-hashed whenever it differs between two blocks, see
+	/// [`Pool::rehash_at`]. The default implementation returns `None`, so transactions are never
+	/// re-hashed.
+	fn runtime_spec_version(
+		&self,
+		_at: <Self::Block as BlockT>::Hash,
+	) -> Result<Option<u32>, Self::Error> {
+		Ok(None)
+	}
+
 	/// Returns a block body given the block.
 	fn block_body(&self, at: <Self::Block as BlockT>::Hash) -> Self::BodyFuture;
 
@@ -119,6 +148,16 @@ pub struct Options {
 	pub reject_future_transactions: bool,
 	/// How long the extrinsic is banned for.
 	pub ban_time: Duration,
+	/// The block against which transactions are validated on submission and revalidation.
+	pub validation_anchor: ValidationAnchor,
+	/// Maximum number of watchers receiving all status events of their transaction.
+	///
+	/// Transactions submitted and watched beyond it are still imported, but their watchers only
+	/// receive the final event.
+	pub max_watchers: usize,
+	/// Maximum number of distinct peers recorded per broadcast transaction, see
+	/// [`sc_transaction_pool_api::TransactionPool::broadcast_info`].
+	pub max_broadcast_peers: usize,
 }
 
 impl Default for Options {
@@ -128,6 +167,9 @@ impl Default for Options {
 			future: base::Limit { count: 512, total_bytes: 1 * 1024 * 1024 },
 			reject_future_transactions: false,
 			ban_time: Duration::from_secs(60 * 30),
+			validation_anchor: ValidationAnchor::BestBlock,
+			max_watchers: 16 * 1024,
+			max_broadcast_peers: 64,
 		}
 	}
 }
@@ -256,9 +298,40 @@ impl<B: ChainApi> Pool<B> {
 			at,
 			extrinsics.len()
 		);
+		let (in_pool_hashes, future_tags) = self.provided_tags(at, parent, extrinsics).await;
+		self.prune_tags(at, future_tags, in_pool_hashes).await
+	}
+
+	/// Returns what [`Self::prune`] would do to the ready and future queues, without modifying
+	/// the pool.
+	///
+	/// The ready transactions removed by the pruning are re-validated and may get back into
+	/// the pool, which is not accounted for.
+	pub async fn prune_preview(
+		&self,
+		at: <B::Block as BlockT>::Hash,
+		parent: <B::Block as BlockT>::Hash,
+		extrinsics: &[ExtrinsicFor<B>],
+	) -> PrunePreview {
+		let (_, tags) = self.provided_tags(at, parent, extrinsics).await;
+		self.validated_pool.prune_preview(tags)
+	}
+
+	/// Returns the hashes of the given extrinsics of the block `at` and the tags they provide.
+	///
+	/// The tags of the extrinsics that are in the pool are taken from there, the ones of other
+	/// extrinsics are queried from the runtime at the `parent` block.
+	async fn provided_tags(
+		&self,
+		at: <B::Block as BlockT>::Hash,
+		parent: <B::Block as BlockT>::Hash,
+		extrinsics: &[ExtrinsicFor<B>],
+	) -> (Vec<ExtrinsicHash<B>>, Vec<Tag>) {
 		// Get details of all extrinsics that are already in the pool
-		let in_pool_hashes =
-			extrinsics.iter().map(|extrinsic| self.hash_of(extrinsic)).collect::<Vec<_>>();
+		let in_pool_hashes = extrinsics
+			.iter()
+			.map(|extrinsic| self.hash_of_at(at, extrinsic))
+			.collect::<Vec<_>>();
 		let in_pool_tags = self.validated_pool.extrinsics_tags(&in_pool_hashes);
 
 		// Zip the ones from the pool with the full list (we get pairs `(Extrinsic,
@@ -298,7 +371,7 @@ impl<B: ChainApi> Pool<B> {
 			}
 		}
 
-		self.prune_tags(at, future_tags, in_pool_hashes).await
+		(in_pool_hashes, future_tags)
 	}
 
 	/// Prunes ready transactions that provide given list of tags.
@@ -363,6 +436,49 @@ impl<B: ChainApi> Pool<B> {
 		self.validated_pool.api().hash_and_length(xt).0
 	}
 
+	/// Returns transaction hash, as hashed by the runtime at the given block.
+	pub fn hash_of_at(
+		&self,
+		at: <B::Block as BlockT>::Hash,
+		xt: &ExtrinsicFor<B>,
+	) -> ExtrinsicHash<B> {
+		self.validated_pool.api().hash_and_length_at(at, xt).0
+	}
+
+	/// Re-hashes all transactions of the pool as hashed by the runtime at block `at`.
+	///
+	/// Transactions whose hash changed, e.g. because a runtime upgrade changed the hashing of
+	/// transactions, are removed together with their dependants and re-submitted at `at`, which
+	/// announces them again under their new hash. Watchers of a re-hashed transaction are
+	/// notified with [`sc_transaction_pool_api::TransactionStatus::Usurped`] carrying the new
+	/// hash, so they can re-subscribe. Returns the old hashes mapped to the new ones.
+	pub async fn rehash_at(
+		&self,
+		at: <B::Block as BlockT>::Hash,
+	) -> Result<HashMap<ExtrinsicHash<B>, ExtrinsicHash<B>>, B::Error> {
+		let rehashed = self.validated_pool.rehashed_at(at);
+		if rehashed.is_empty() {
+			return Ok(rehashed)
+		}
+		log::debug!(target: LOG_TARGET, "Re-hashing {} transactions at {:?}", rehashed.len(), at);
+
+		let old_hashes = rehashed.keys().cloned().collect::<Vec<_>>();
+		let removed = self.validated_pool.remove_for_rehash(&old_hashes);
+		let xts = removed.iter().map(|tx| (tx.source, tx.data.clone()));
+		let (hashes, validated): (Vec<_>, Vec<_>) =
+			self.verify(at, xts, CheckBannedBeforeVerify::No).await?.into_iter().unzip();
+		let imported = hashes
+			.into_iter()
+			.zip(self.validated_pool.submit(validated))
+			.filter_map(|(hash, result)| result.ok().map(|_| hash))
+			.collect::<HashSet<_>>();
+
+		for (old, new) in &rehashed {
+			self.validated_pool.notify_rehashed(old, imported.contains(new).then_some(new));
+		}
+		Ok(rehashed)
+	}
+
 	/// Resolves block number by id.
 	fn resolve_block_number(&self, at: &BlockId<B::Block>) -> Result<NumberFor<B>, B::Error> {
 		self.validated_pool.api().block_id_to_number(at).and_then(|number| {
@@ -400,7 +516,7 @@ impl<B: ChainApi> Pool<B> {
 		xt: ExtrinsicFor<B>,
 		check: CheckBannedBeforeVerify,
 	) -> (ExtrinsicHash<B>, ValidatedTransactionFor<B>) {
-		let (hash, bytes) = self.validated_pool.api().hash_and_length(&xt);
+		let (hash, bytes) = self.validated_pool.api().hash_and_length_at(block_hash, &xt);
 
 		let ignore_banned = matches!(check, CheckBannedBeforeVerify::No);
 		if let Err(err) = self.validated_pool.check_is_known(&hash, ignore_banned) {
@@ -423,6 +539,7 @@ impl<B: ChainApi> Pool<B> {
 				if validity.provides.is_empty() {
 					ValidatedTransaction::Invalid(hash, error::Error::NoTagsProvided.into())
 				} else {
+					let class = self.validated_pool.classify(&xt, &validity);
 					ValidatedTransaction::valid_at(
 						block_number.saturated_into::<u64>(),
 						hash,
@@ -430,6 +547,7 @@ impl<B: ChainApi> Pool<B> {
 						xt,
 						bytes,
 						validity,
+						class,
 					)
 				},
 			Err(TransactionValidityError::Invalid(e)) =>
@@ -512,6 +630,32 @@ mod tests {
 		assert_matches!(res.unwrap_err(), error::Error::TemporarilyBanned);
 	}
 
+	#[test]
+	fn should_ban_transactions_panicking_validation() {
+		// given
+		let (pool, api) = pool();
+		let uxt = uxt(Transfer {
+			from: Alice.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(2)),
+			amount: 5,
+			nonce: 0,
+		});
+		let hash = pool.hash_of(&uxt);
+		api.panic.lock().insert(hash);
+
+		// when
+		let res = block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, uxt.clone()));
+
+		// then
+		let err = res.unwrap_err();
+		assert!(err.is_node_fault());
+		assert_matches!(err, error::Error::ValidationPanicked(_));
+		assert!(pool.validated_pool.is_banned(&hash));
+		let res = block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, uxt));
+		assert_matches!(res.unwrap_err(), error::Error::TemporarilyBanned);
+		assert_eq!(api.validation_requests().len(), 1);
+	}
+
 	#[test]
 	fn should_reject_unactionable_transactions() {
 		// given
@@ -735,6 +879,39 @@ mod tests {
 		assert_eq!(pool.validated_pool().status().future, 0);
 	}
 
+	#[test]
+	fn should_report_min_ready_priority_when_ready_limit_is_reached() {
+		// given
+		let limit = Limit { count: 2, total_bytes: 1000 };
+		let options = Options { ready: limit.clone(), future: limit.clone(), ..Default::default() };
+
+		let api = Arc::new(TestApi::default());
+		let pool = Pool::new(options, true.into(), api.clone());
+
+		// `IncludeData` gets priority 9001 (validate_transaction mock)
+		let xt = ExtrinsicBuilder::new_include_data(Vec::new()).build();
+		block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, xt)).unwrap();
+		assert_eq!(pool.validated_pool().status().min_ready_priority, None);
+		assert_eq!(pool.validated_pool().min_ready_priority(), None);
+
+		// when
+		// `Transfer` gets priority 4 (validate_transaction mock)
+		let xt = uxt(Transfer {
+			from: Alice.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(2)),
+			amount: 5,
+			nonce: 0,
+		});
+		block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, xt)).unwrap();
+
+		// then
+		assert_eq!(pool.validated_pool().status().ready, 2);
+		assert_eq!(pool.validated_pool().status().min_ready_priority, Some(4));
+		assert_eq!(pool.validated_pool().min_ready_priority(), Some(4));
+		assert_eq!(pool.validated_pool().priority_threshold(0), Some(9001));
+		assert_eq!(pool.validated_pool().priority_threshold(usize::MAX), None);
+	}
+
 	#[test]
 	fn should_reject_transactions_with_no_provides() {
 		// given
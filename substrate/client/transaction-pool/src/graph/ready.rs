@@ -21,13 +21,17 @@ use std::{
 	collections::{BTreeSet, HashMap, HashSet},
 	hash,
 	sync::Arc,
+	time::Instant,
 };
 
 use crate::LOG_TARGET;
 use log::{debug, trace};
-use sc_transaction_pool_api::error;
+use sc_transaction_pool_api::{error, replacement_priority};
 use serde::Serialize;
-use sp_runtime::{traits::Member, transaction_validity::TransactionTag as Tag};
+use sp_runtime::{
+	traits::Member,
+	transaction_validity::{TransactionPriority as Priority, TransactionTag as Tag},
+};
 
 use super::{
 	base_pool::Transaction,
@@ -86,6 +90,9 @@ pub struct ReadyTx<Hash, Ex> {
 	/// Some transactions might be already pruned from the queue,
 	/// so when we compute ready set we may consider this transactions ready earlier.
 	pub requires_offset: usize,
+	/// Time of import to the pool, which precedes the promotion of a transaction that was
+	/// waiting in the future queue.
+	pub imported_at: Instant,
 }
 
 impl<Hash: Clone, Ex> Clone for ReadyTx<Hash, Ex> {
@@ -94,6 +101,7 @@ impl<Hash: Clone, Ex> Clone for ReadyTx<Hash, Ex> {
 			transaction: self.transaction.clone(),
 			unlocks: self.unlocks.clone(),
 			requires_offset: self.requires_offset,
+			imported_at: self.imported_at,
 		}
 	}
 }
@@ -192,6 +200,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 		self.insertion_id += 1;
 		let insertion_id = self.insertion_id;
 		let hash = tx.transaction.hash.clone();
+		let imported_at = tx.imported_at;
 		let transaction = tx.transaction;
 
 		let (replaced, unlocks) = self.replace_previous(&transaction)?;
@@ -227,7 +236,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 		}
 
 		// insert to Ready
-		ready.insert(hash, ReadyTx { transaction, unlocks, requires_offset });
+		ready.insert(hash, ReadyTx { transaction, unlocks, requires_offset, imported_at });
 
 		Ok(replaced)
 	}
@@ -410,6 +419,44 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 		removed
 	}
 
+	/// Returns the hashes of the transactions [`Self::prune_tags`] would remove if it was called
+	/// for each of the given tags, without modifying the queue.
+	pub fn prune_tags_preview(&self, tags: impl IntoIterator<Item = Tag>) -> HashSet<Hash> {
+		let ready = self.ready.read();
+		let mut pruned = HashSet::new();
+		// The transactions which would remain unlocked by the ones in the queue, for the
+		// transactions whose `unlocks` would have been modified.
+		let mut unlocks = HashMap::<&Hash, Vec<Hash>>::new();
+
+		for tag in tags {
+			let mut to_remove = vec![tag];
+			while let Some(tag) = to_remove.pop() {
+				let Some(hash) = self.provided_tags.get(&tag) else { continue };
+				if !pruned.insert(hash.clone()) {
+					continue
+				}
+				let tx = &ready.get(hash).expect(HASH_READY).transaction.transaction;
+
+				// previous transactions are pruned once they no longer unlock anything
+				for required in &tx.requires {
+					let Some(prev_hash) = self.provided_tags.get(required) else { continue };
+					if pruned.contains(prev_hash) {
+						continue
+					}
+					let prev = ready.get(prev_hash).expect(HASH_READY);
+					let prev_unlocks =
+						unlocks.entry(prev_hash).or_insert_with(|| prev.unlocks.clone());
+					remove_item(prev_unlocks, hash);
+					if prev_unlocks.is_empty() {
+						to_remove.extend(prev.transaction.transaction.provides.iter().cloned());
+					}
+				}
+			}
+		}
+
+		pruned
+	}
+
 	/// Checks if the transaction is providing the same tags as other transactions.
 	///
 	/// In case that's true it determines if the priority of transactions that
@@ -447,7 +494,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 			};
 
 			// bail - the transaction has too low priority to replace the old ones
-			if old_priority >= tx.priority {
+			if replacement_priority(old_priority) >= tx.priority {
 				return Err(error::Error::TooLowPriority { old: old_priority, new: tx.priority })
 			}
 
@@ -481,6 +528,44 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 	pub fn bytes(&self) -> usize {
 		self.ready.bytes()
 	}
+
+	/// Returns the time every transaction in this queue was imported to the pool.
+	pub fn import_times(&self) -> Vec<Instant> {
+		self.ready.read().values().map(|tx| tx.imported_at).collect()
+	}
+
+	/// Returns the priority of the first transaction in the order of [`Self::get`] that does not
+	/// fit into `within_bytes`.
+	///
+	/// Returns `None` if all transactions fit.
+	pub fn priority_threshold(&self, within_bytes: usize) -> Option<Priority> {
+		if self.bytes() <= within_bytes {
+			return None
+		}
+
+		let mut bytes = 0;
+		self.get()
+			.find(|tx| {
+				bytes += tx.bytes;
+				bytes > within_bytes
+			})
+			.map(|tx| tx.priority)
+	}
+
+	/// Returns the lowest priority of all transactions in this queue.
+	pub fn min_priority(&self) -> Option<Priority> {
+		self.ready.read().values().map(|tx| tx.transaction.transaction.priority).min()
+	}
+
+	/// Returns the priority a transaction providing `tag` has to exceed to replace the
+	/// transaction currently providing it.
+	///
+	/// Returns `None` if no transaction in this queue provides `tag`.
+	pub fn min_replacement_priority(&self, tag: &Tag) -> Option<Priority> {
+		let hash = self.provided_tags.get(tag)?;
+		let priority = self.ready.read().get(hash)?.transaction.transaction.priority;
+		Some(replacement_priority(priority))
+	}
 }
 
 /// Iterator of ready transactions ordered by priority.
@@ -598,10 +683,12 @@ mod tests {
 			hash: id as u64,
 			priority: 1,
 			valid_till: 2,
+			validated_at: 0,
 			requires: vec![vec![1], vec![2]],
 			provides: vec![vec![3], vec![4]],
 			propagate: true,
 			source: Source::External,
+			class: Default::default(),
 		}
 	}
 
@@ -609,7 +696,7 @@ mod tests {
 		ready: &mut ReadyTransactions<H, Ex>,
 		tx: Transaction<H, Ex>,
 	) -> error::Result<Vec<Arc<Transaction<H, Ex>>>> {
-		let x = WaitingTransaction::new(tx, ready.provided_tags(), &[]);
+		let x = WaitingTransaction::new(tx, ready.provided_tags(), &[], Instant::now());
 		ready.import(x)
 	}
 
@@ -708,10 +795,12 @@ mod tests {
 			hash: 7,
 			priority: 1,
 			valid_till: u64::MAX, // use the max here for testing.
+			validated_at: 0,
 			requires: vec![tx1.provides[0].clone()],
 			provides: vec![],
 			propagate: true,
 			source: Source::External,
+			class: Default::default(),
 		};
 
 		// when
@@ -742,6 +831,32 @@ mod tests {
 		assert_eq!(it.next(), None);
 	}
 
+	#[test]
+	fn prune_tags_preview_should_match_pruning() {
+		for tags in [vec![vec![108]], vec![vec![106], vec![108]], vec![vec![3]], vec![vec![109]]] {
+			// given
+			let mut ready = ReadyTransactions::default();
+			populate_pool(&mut ready);
+
+			// when
+			let preview = ready.prune_tags_preview(tags.clone());
+			assert_eq!(ready.get().count(), 7);
+			let pruned = tags
+				.into_iter()
+				.flat_map(|tag| ready.prune_tags(tag))
+				.map(|tx| tx.hash)
+				.collect::<HashSet<_>>();
+
+			// then
+			assert_eq!(preview, pruned);
+		}
+
+		let mut ready = ReadyTransactions::default();
+		populate_pool(&mut ready);
+		// `tx4` only unlocks `tx5`, so it is pruned with it.
+		assert_eq!(ready.prune_tags_preview(vec![vec![108]]), HashSet::from([4, 5]));
+	}
+
 	#[test]
 	fn should_order_refs() {
 		let mut id = 1;
@@ -790,4 +905,65 @@ mod tests {
 		assert_eq!(it.next().as_ref().map(data), Some(7));
 		assert_eq!(it.next().as_ref().map(data), None);
 	}
+
+	#[test]
+	fn should_return_priority_thresholds() {
+		// given
+		let mut ready = ReadyTransactions::default();
+		for (id, priority, bytes) in [(1, 50, 100), (2, 40, 200), (3, 30, 300), (4, 20, 400)] {
+			let mut tx = tx(id);
+			tx.requires.clear();
+			tx.provides = vec![vec![id]];
+			tx.priority = priority;
+			tx.bytes = bytes;
+			import(&mut ready, tx).unwrap();
+		}
+
+		// then
+		assert_eq!(ready.priority_threshold(0), Some(50));
+		assert_eq!(ready.priority_threshold(100), Some(40));
+		assert_eq!(ready.priority_threshold(299), Some(40));
+		assert_eq!(ready.priority_threshold(300), Some(30));
+		assert_eq!(ready.priority_threshold(600), Some(20));
+		assert_eq!(ready.priority_threshold(999), Some(20));
+		assert_eq!(ready.priority_threshold(1000), None);
+		assert_eq!(ready.priority_threshold(usize::MAX), None);
+		assert_eq!(ready.min_priority(), Some(20));
+	}
+
+	#[test]
+	fn should_not_return_priority_thresholds_when_empty() {
+		let ready = ReadyTransactions::<u64, Vec<u8>>::default();
+
+		assert_eq!(ready.priority_threshold(0), None);
+		assert_eq!(ready.min_priority(), None);
+	}
+
+	#[test]
+	fn should_return_min_replacement_priority() {
+		// given
+		let mut ready = ReadyTransactions::default();
+		let mut tx1 = tx(1);
+		tx1.requires.clear();
+		tx1.priority = 5;
+		let mut tx2 = tx(2);
+		tx2.requires.clear();
+		tx2.provides = vec![vec![3]];
+
+		// when
+		import(&mut ready, tx1).unwrap();
+
+		// then
+		assert_eq!(ready.min_replacement_priority(&vec![3]), Some(5));
+		assert_eq!(ready.min_replacement_priority(&vec![4]), Some(5));
+		assert_eq!(ready.min_replacement_priority(&vec![5]), None);
+
+		// matching the priority is not enough to replace
+		tx2.priority = 5;
+		import(&mut ready, tx2.clone()).unwrap_err();
+		tx2.priority = 6;
+		import(&mut ready, tx2).unwrap();
+		assert_eq!(ready.min_replacement_priority(&vec![3]), Some(6));
+		assert_eq!(ready.min_replacement_priority(&vec![4]), None);
+	}
 }
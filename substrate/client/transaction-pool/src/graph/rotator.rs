@@ -123,10 +123,12 @@ mod tests {
 			hash,
 			priority: 5,
 			valid_till: 1,
+			validated_at: 0,
 			requires: vec![],
 			provides: vec![],
 			propagate: true,
 			source: TransactionSource::External,
+			class: Default::default(),
 		};
 
 		(hash, tx)
@@ -189,10 +191,12 @@ mod tests {
 				hash,
 				priority: 5,
 				valid_till,
+				validated_at: 0,
 				requires: vec![],
 				provides: vec![],
 				propagate: true,
 				source: TransactionSource::External,
+				class: Default::default(),
 			}
 		}
 
@@ -19,18 +19,30 @@
 use std::{
 	collections::{HashMap, HashSet},
 	hash,
-	sync::Arc,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
 };
 
 use crate::LOG_TARGET;
-use futures::channel::mpsc::{channel, Sender};
+use futures::channel::{
+	mpsc::{channel, Sender},
+	oneshot,
+};
 use parking_lot::{Mutex, RwLock};
-use sc_transaction_pool_api::{error, PoolStatus, ReadyTransactions};
+use sc_transaction_pool_api::{
+	error, BroadcastInfo, DefaultClassifier, OverflowHandle, PoolClass, PoolStatus,
+	PoolStatusDetailed, PrunePreview, ReadyTransactions, RetractedBlock, TransactionClassifier,
+	TransactionStatus, WaitReadyOutcome, WatcherStatus, DEFAULT_IMPORT_NOTIFICATION_BUFFER_SIZE,
+};
 use serde::Serialize;
 use sp_runtime::{
 	generic::BlockId,
 	traits::{self, SaturatedConversion},
-	transaction_validity::{TransactionSource, TransactionTag as Tag, ValidTransaction},
+	transaction_validity::{
+		TransactionPriority, TransactionSource, TransactionTag as Tag, ValidTransaction,
+	},
 };
 use std::time::Instant;
 
@@ -66,6 +78,7 @@ impl<Hash, Ex, Error> ValidatedTransaction<Hash, Ex, Error> {
 		data: Ex,
 		bytes: usize,
 		validity: ValidTransaction,
+		class: PoolClass,
 	) -> Self {
 		Self::Valid(base::Transaction {
 			data,
@@ -77,6 +90,8 @@ impl<Hash, Ex, Error> ValidatedTransaction<Hash, Ex, Error> {
 			provides: validity.provides,
 			propagate: validity.propagate,
 			valid_till: at.saturated_into::<u64>().saturating_add(validity.longevity),
+			validated_at: at,
+			class,
 		})
 	}
 }
@@ -100,6 +115,12 @@ impl From<Box<dyn Fn() -> bool + Send + Sync>> for IsValidator {
 	}
 }
 
+/// A sink of import notifications, with the handle collecting those which don't fit into it.
+struct ImportNotificationSink<Hash> {
+	sender: Sender<Hash>,
+	overflow: Option<OverflowHandle<Hash>>,
+}
+
 /// Pool that deals with validated transactions.
 pub struct ValidatedPool<B: ChainApi> {
 	api: Arc<B>,
@@ -107,8 +128,11 @@ pub struct ValidatedPool<B: ChainApi> {
 	options: Options,
 	listener: RwLock<Listener<ExtrinsicHash<B>, B>>,
 	pub(crate) pool: RwLock<base::BasePool<ExtrinsicHash<B>, ExtrinsicFor<B>>>,
-	import_notification_sinks: Mutex<Vec<Sender<ExtrinsicHash<B>>>>,
+	import_notification_sinks: Mutex<Vec<ImportNotificationSink<ExtrinsicHash<B>>>>,
+	dropped_import_notifications: AtomicU64,
 	rotator: PoolRotator<ExtrinsicHash<B>>,
+	classifier: RwLock<Arc<dyn TransactionClassifier<B::Block>>>,
+	class_limits: RwLock<HashMap<PoolClass, base::Limit>>,
 }
 
 impl<B: ChainApi> ValidatedPool<B> {
@@ -116,17 +140,39 @@ impl<B: ChainApi> ValidatedPool<B> {
 	pub fn new(options: Options, is_validator: IsValidator, api: Arc<B>) -> Self {
 		let base_pool = base::BasePool::new(options.reject_future_transactions);
 		let ban_time = options.ban_time;
+		let listener = Listener::new(options.max_watchers, options.max_broadcast_peers);
 		Self {
 			is_validator,
 			options,
-			listener: Default::default(),
+			listener: RwLock::new(listener),
 			api,
 			pool: RwLock::new(base_pool),
 			import_notification_sinks: Default::default(),
+			dropped_import_notifications: AtomicU64::new(0),
 			rotator: PoolRotator::new(ban_time),
+			classifier: RwLock::new(Arc::new(DefaultClassifier)),
+			class_limits: Default::default(),
 		}
 	}
 
+	/// Sets the classifier assigning a [`PoolClass`] to transactions validated from now on.
+	pub fn set_classifier(&self, classifier: Arc<dyn TransactionClassifier<B::Block>>) {
+		*self.classifier.write() = classifier;
+	}
+
+	/// Returns the class of the transaction `xt` with the given `validity`.
+	pub fn classify(&self, xt: &ExtrinsicFor<B>, validity: &ValidTransaction) -> PoolClass {
+		self.classifier.read().classify(xt, validity)
+	}
+
+	/// Limits the transactions of the given `class`, see
+	/// [`sc_transaction_pool_api::TransactionPool::set_class_limits`].
+	///
+	/// The limits are enforced on the next submission.
+	pub fn set_class_limits(&self, class: PoolClass, limit: base::Limit) {
+		self.class_limits.write().insert(class, limit);
+	}
+
 	/// Bans given set of hashes.
 	pub fn ban(&self, now: &Instant, hashes: impl IntoIterator<Item = ExtrinsicHash<B>>) {
 		self.rotator.ban(now, hashes)
@@ -195,18 +241,22 @@ impl<B: ChainApi> ValidatedPool<B> {
 
 				if let base::Imported::Ready { ref hash, .. } = imported {
 					let sinks = &mut self.import_notification_sinks.lock();
-					sinks.retain_mut(|sink| match sink.try_send(*hash) {
+					sinks.retain_mut(|sink| match sink.sender.try_send(*hash) {
 						Ok(()) => true,
 						Err(e) =>
-							if e.is_full() {
+							if !e.is_full() {
+								false
+							} else if let Some(overflow) = &sink.overflow {
+								overflow.insert(*hash);
+								true
+							} else {
 								log::warn!(
 									target: LOG_TARGET,
 									"[{:?}] Trying to notify an import but the channel is full",
 									hash,
 								);
+								self.dropped_import_notifications.fetch_add(1, Ordering::Relaxed);
 								true
-							} else {
-								false
 							},
 					});
 				}
@@ -227,7 +277,45 @@ impl<B: ChainApi> ValidatedPool<B> {
 	}
 
 	fn enforce_limits(&self) -> HashSet<ExtrinsicHash<B>> {
-		let status = self.pool.read().status();
+		let mut removed = self.enforce_class_limits();
+		removed.extend(self.enforce_pool_limits());
+		removed
+	}
+
+	/// Evicts transactions of the classes exceeding their limits.
+	fn enforce_class_limits(&self) -> HashSet<ExtrinsicHash<B>> {
+		let class_limits = self.class_limits.read();
+		if class_limits.is_empty() {
+			return Default::default()
+		}
+
+		let removed = {
+			let mut pool = self.pool.write();
+			let removed = pool
+				.enforce_class_limits(&class_limits)
+				.into_iter()
+				.map(|x| x.hash)
+				.collect::<HashSet<_>>();
+			// ban all removed transactions
+			self.rotator.ban(&Instant::now(), removed.iter().copied());
+			removed
+		};
+		if !removed.is_empty() {
+			log::debug!(target: LOG_TARGET, "Enforcing class limits: {} dropped", removed.len());
+		}
+
+		// run notifications
+		let mut listener = self.listener.write();
+		for h in &removed {
+			listener.dropped(h, None);
+		}
+
+		removed
+	}
+
+	/// Evicts transactions while the queues exceed the limits of the pool.
+	fn enforce_pool_limits(&self) -> HashSet<ExtrinsicHash<B>> {
+		let status = self.status();
 		let ready_limit = &self.options.ready;
 		let future_limit = &self.options.future;
 
@@ -279,7 +367,7 @@ impl<B: ChainApi> ValidatedPool<B> {
 	) -> Result<Watcher<ExtrinsicHash<B>, ExtrinsicHash<B>>, B::Error> {
 		match tx {
 			ValidatedTransaction::Valid(tx) => {
-				let hash = self.api.hash_and_length(&tx.data).0;
+				let hash = tx.hash;
 				let watcher = self.listener.write().create_watcher(hash);
 				self.submit(std::iter::once(ValidatedTransaction::Valid(tx)))
 					.pop()
@@ -294,6 +382,27 @@ impl<B: ChainApi> ValidatedPool<B> {
 		}
 	}
 
+	/// Starts to watch the progress of a transaction which is already known to the pool.
+	///
+	/// The watcher is notified about the current status of the transaction right away: `Ready`
+	/// or `Future` while it is in the pool, or `InBlock` if it was included in a block which is
+	/// not finalized yet. Returns `None` if the transaction is not known.
+	pub fn watch_existing(
+		&self,
+		hash: ExtrinsicHash<B>,
+	) -> Option<Watcher<ExtrinsicHash<B>, ExtrinsicHash<B>>> {
+		let pool = self.pool.read();
+		let mut listener = self.listener.write();
+		let status = if pool.ready_by_hash(&hash).is_some() {
+			TransactionStatus::Ready
+		} else if pool.is_imported(&hash) {
+			TransactionStatus::Future
+		} else {
+			TransactionStatus::InBlock(listener.in_block(&hash)?)
+		};
+		Some(listener.create_watcher_with_status(hash, status))
+	}
+
 	/// Resubmits revalidated transactions back to the pool.
 	///
 	/// Removes and then submits passed transactions and all dependent transactions.
@@ -447,6 +556,42 @@ impl<B: ChainApi> ValidatedPool<B> {
 		self.pool.read().ready_by_hash(hash)
 	}
 
+	/// Returns `true` if the transaction is part of the ready or future queue.
+	pub fn is_imported(&self, hash: &ExtrinsicHash<B>) -> bool {
+		self.pool.read().is_imported(hash)
+	}
+
+	/// Registers a waiter which is notified once the transaction becomes ready or leaves the pool.
+	///
+	/// Returns `None` if there are too many waiters already.
+	pub fn ready_waiter(
+		&self,
+		hash: ExtrinsicHash<B>,
+	) -> Option<oneshot::Receiver<WaitReadyOutcome<ExtrinsicHash<B>, BlockHash<B>>>> {
+		self.listener.write().add_ready_waiter(hash)
+	}
+
+	/// Removes the waiters of the given transaction which are not awaited anymore.
+	pub fn remove_canceled_ready_waiters(&self, hash: &ExtrinsicHash<B>) {
+		self.listener.write().remove_canceled_ready_waiters(hash)
+	}
+
+	/// The number of waiters for transactions to become ready.
+	pub fn ready_waiters_count(&self) -> usize {
+		self.listener.read().ready_waiters_count()
+	}
+
+	/// Returns the status of the watchers of the pool.
+	pub fn watcher_status(&self) -> WatcherStatus {
+		let listener = self.listener.read();
+		WatcherStatus { watchers: listener.watchers_count(), max_watchers: listener.max_watchers() }
+	}
+
+	/// Returns what [`Self::prune_tags`] would do for the given tags, without modifying the pool.
+	pub fn prune_preview(&self, tags: impl IntoIterator<Item = Tag>) -> PrunePreview {
+		self.pool.read().prune_preview(tags)
+	}
+
 	/// Prunes ready transactions that provide given list of tags.
 	pub fn prune_tags(
 		&self,
@@ -568,13 +713,49 @@ impl<B: ChainApi> ValidatedPool<B> {
 
 	/// Return an event stream of notifications for when transactions are imported to the pool.
 	///
+	/// The stream holds up to [`DEFAULT_IMPORT_NOTIFICATION_BUFFER_SIZE`] notifications, further
+	/// ones are dropped.
+	///
 	/// Consumers of this stream should use the `ready` method to actually get the
 	/// pending transactions in the right order.
 	pub fn import_notification_stream(&self) -> EventStream<ExtrinsicHash<B>> {
-		const CHANNEL_BUFFER_SIZE: usize = 1024;
+		self.add_import_notification_sink(None, None)
+	}
+
+	/// Like [`Self::import_notification_stream`], but holding up to `buffer_size` notifications.
+	pub fn import_notification_stream_with_buffer(
+		&self,
+		buffer_size: usize,
+	) -> EventStream<ExtrinsicHash<B>> {
+		self.add_import_notification_sink(Some(buffer_size), None)
+	}
+
+	/// Like [`Self::import_notification_stream`], but collecting the notifications which don't
+	/// fit into the stream in the returned handle.
+	pub fn import_notification_stream_with_overflow(
+		&self,
+		buffer_size: Option<usize>,
+	) -> (EventStream<ExtrinsicHash<B>>, OverflowHandle<ExtrinsicHash<B>>) {
+		let overflow = OverflowHandle::default();
+		(self.add_import_notification_sink(buffer_size, Some(overflow.clone())), overflow)
+	}
 
-		let (sink, stream) = channel(CHANNEL_BUFFER_SIZE);
-		self.import_notification_sinks.lock().push(sink);
+	/// Returns the number of import notifications dropped so far because a stream was full.
+	pub fn dropped_import_notifications(&self) -> u64 {
+		self.dropped_import_notifications.load(Ordering::Relaxed)
+	}
+
+	fn add_import_notification_sink(
+		&self,
+		buffer_size: Option<usize>,
+		overflow: Option<OverflowHandle<ExtrinsicHash<B>>>,
+	) -> EventStream<ExtrinsicHash<B>> {
+		let buffer_size = buffer_size.unwrap_or(DEFAULT_IMPORT_NOTIFICATION_BUFFER_SIZE);
+		// The channel has one more slot for its single sender.
+		let (sender, stream) = channel(buffer_size.saturating_sub(1));
+		self.import_notification_sinks
+			.lock()
+			.push(ImportNotificationSink { sender, overflow });
 		stream
 	}
 
@@ -586,6 +767,11 @@ impl<B: ChainApi> ValidatedPool<B> {
 		}
 	}
 
+	/// Returns the peers the transaction was broadcast to, see [`Self::on_broadcasted`].
+	pub fn broadcast_info(&self, hash: &ExtrinsicHash<B>) -> Option<BroadcastInfo> {
+		self.listener.read().broadcast_info(hash)
+	}
+
 	/// Remove a subtree of transactions from the pool and mark them invalid.
 	///
 	/// The transactions passed as an argument will be additionally banned
@@ -593,6 +779,22 @@ impl<B: ChainApi> ValidatedPool<B> {
 	/// Note this is not the case for the dependent transactions - those may
 	/// still be valid so we want to be able to re-import them.
 	pub fn remove_invalid(&self, hashes: &[ExtrinsicHash<B>]) -> Vec<TransactionFor<B>> {
+		self.remove_invalid_by_hash(hashes)
+			.into_iter()
+			.flat_map(|(_, removed)| removed)
+			.collect()
+	}
+
+	/// Like [`Self::remove_invalid`], but groups the removed transactions by the given hash
+	/// that caused their removal.
+	///
+	/// Each group starts with the transaction of the hash itself, followed by its dependants.
+	/// The group is empty if the transaction is not in the pool (anymore), e.g. because it was
+	/// removed as a dependant of a hash given before it.
+	pub fn remove_invalid_by_hash(
+		&self,
+		hashes: &[ExtrinsicHash<B>],
+	) -> Vec<(ExtrinsicHash<B>, Vec<TransactionFor<B>>)> {
 		// early exit in case there is no invalid transactions.
 		if hashes.is_empty() {
 			return vec![]
@@ -603,18 +805,57 @@ impl<B: ChainApi> ValidatedPool<B> {
 		// temporarily ban invalid transactions
 		self.rotator.ban(&Instant::now(), hashes.iter().cloned());
 
-		let invalid = self.pool.write().remove_subtree(hashes);
+		let invalid = {
+			let mut pool = self.pool.write();
+			hashes
+				.iter()
+				.map(|hash| (*hash, pool.remove_subtree(&[*hash])))
+				.collect::<Vec<_>>()
+		};
 
 		log::debug!(target: LOG_TARGET, "Removed invalid transactions: {:?}", invalid);
 
 		let mut listener = self.listener.write();
-		for tx in &invalid {
+		for tx in invalid.iter().flat_map(|(_, removed)| removed) {
 			listener.invalid(&tx.hash);
 		}
 
 		invalid
 	}
 
+	/// Returns the hashes of the transactions whose hash at block `at` differs from the one they
+	/// are stored under, mapped to their new hash.
+	pub fn rehashed_at(&self, at: BlockHash<B>) -> HashMap<ExtrinsicHash<B>, ExtrinsicHash<B>> {
+		let pool = self.pool.read();
+		pool.ready()
+			.map(|tx| (tx.hash, tx.data.clone()))
+			.chain(pool.futures().map(|tx| (tx.hash, tx.data.clone())))
+			.filter_map(|(hash, xt)| {
+				let new_hash = self.api.hash_and_length_at(at, &xt).0;
+				(new_hash != hash).then_some((hash, new_hash))
+			})
+			.collect()
+	}
+
+	/// Removes the transactions with the given hashes together with their dependants, in order to
+	/// re-import them under a new hash.
+	///
+	/// Unlike [`Self::remove_invalid`], the transactions are neither banned nor are their
+	/// watchers notified, see [`Self::notify_rehashed`].
+	pub fn remove_for_rehash(&self, hashes: &[ExtrinsicHash<B>]) -> Vec<TransactionFor<B>> {
+		self.pool.write().remove_subtree(hashes)
+	}
+
+	/// Notifies the watchers of the transaction `old` that it was re-imported under the hash
+	/// `new`, or that it is invalid if the re-import failed.
+	pub fn notify_rehashed(&self, old: &ExtrinsicHash<B>, new: Option<&ExtrinsicHash<B>>) {
+		let mut listener = self.listener.write();
+		match new {
+			Some(new) => listener.dropped(old, Some(new)),
+			None => listener.invalid(old),
+		}
+	}
+
 	/// Get an iterator for ready transactions ordered by priority
 	pub fn ready(&self) -> impl ReadyTransactions<Item = TransactionFor<B>> + Send {
 		self.pool.read().ready()
@@ -627,7 +868,40 @@ impl<B: ChainApi> ValidatedPool<B> {
 
 	/// Returns pool status.
 	pub fn status(&self) -> PoolStatus {
-		self.pool.read().status()
+		let pool = self.pool.read();
+		let mut status = pool.status();
+		if self.options.ready.is_reached(status.ready, status.ready_bytes) {
+			status.min_ready_priority = pool.min_ready_priority();
+		}
+		status
+	}
+
+	/// Returns pool status, including the age of the transactions at `now`.
+	pub fn status_detailed(&self, now: Instant) -> PoolStatusDetailed {
+		let pool = self.pool.read();
+		let mut detailed = pool.status_detailed(now);
+		let status = &mut detailed.status;
+		if self.options.ready.is_reached(status.ready, status.ready_bytes) {
+			status.min_ready_priority = pool.min_ready_priority();
+		}
+		detailed
+	}
+
+	/// Returns the priority needed to be included within the first `within_bytes` of ready
+	/// transactions.
+	pub fn priority_threshold(&self, within_bytes: usize) -> Option<TransactionPriority> {
+		self.pool.read().ready_priority_threshold(within_bytes)
+	}
+
+	/// Returns the lowest priority in the ready queue, if the ready queue limits are reached.
+	pub fn min_ready_priority(&self) -> Option<TransactionPriority> {
+		self.status().min_ready_priority
+	}
+
+	/// Returns the priority a transaction providing `tag` has to exceed to replace the ready
+	/// transaction currently providing it.
+	pub fn min_replacement_priority(&self, tag: &Tag) -> Option<TransactionPriority> {
+		self.pool.read().min_replacement_priority(tag)
 	}
 
 	/// Notify all watchers that transactions in the block with hash have been finalized
@@ -642,8 +916,8 @@ impl<B: ChainApi> ValidatedPool<B> {
 	}
 
 	/// Notify the listener of retracted blocks
-	pub fn on_block_retracted(&self, block_hash: BlockHash<B>) {
-		self.listener.write().retracted(block_hash)
+	pub fn on_block_retracted(&self, block: RetractedBlock<BlockHash<B>>) {
+		self.listener.write().retracted(block)
 	}
 }
 
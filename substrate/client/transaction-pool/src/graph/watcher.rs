@@ -19,7 +19,7 @@
 //! Extrinsics status updates.
 
 use futures::Stream;
-use sc_transaction_pool_api::TransactionStatus;
+use sc_transaction_pool_api::{RetractedBlock, TransactionStatus};
 use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
 
 /// Extrinsic watcher.
@@ -50,12 +50,18 @@ impl<H, BH> Watcher<H, BH> {
 #[derive(Debug)]
 pub struct Sender<H, BH> {
 	receivers: Vec<TracingUnboundedSender<TransactionStatus<H, BH>>>,
+	/// Receivers of degraded watchers, which are only notified about the final event.
+	degraded_receivers: Vec<TracingUnboundedSender<TransactionStatus<H, BH>>>,
 	is_finalized: bool,
 }
 
 impl<H, BH> Default for Sender<H, BH> {
 	fn default() -> Self {
-		Sender { receivers: Default::default(), is_finalized: false }
+		Sender {
+			receivers: Default::default(),
+			degraded_receivers: Default::default(),
+			is_finalized: false,
+		}
 	}
 }
 
@@ -67,6 +73,34 @@ impl<H: Clone, BH: Clone> Sender<H, BH> {
 		Watcher { receiver, hash }
 	}
 
+	/// Add a new watcher to this sender object, which receives `status` right away.
+	pub fn new_watcher_with_status(
+		&mut self,
+		hash: H,
+		status: TransactionStatus<H, BH>,
+	) -> Watcher<H, BH> {
+		let (tx, receiver) = tracing_unbounded("mpsc_txpool_watcher", 100_000);
+		let _ = tx.unbounded_send(status);
+		self.receivers.push(tx);
+		Watcher { receiver, hash }
+	}
+
+	/// Add a new degraded watcher to this sender object.
+	///
+	/// The watcher receives [`TransactionStatus::WatchingDegraded`] right away and afterwards
+	/// only the final event of the extrinsic.
+	pub fn new_degraded_watcher(&mut self, hash: H) -> Watcher<H, BH> {
+		let (tx, receiver) = tracing_unbounded("mpsc_txpool_degraded_watcher", 100);
+		let _ = tx.unbounded_send(TransactionStatus::WatchingDegraded);
+		self.degraded_receivers.push(tx);
+		Watcher { receiver, hash }
+	}
+
+	/// Returns the number of watchers which receive all events and are still listening.
+	pub fn watchers_count(&self) -> usize {
+		self.receivers.iter().filter(|sender| !sender.is_closed()).count()
+	}
+
 	/// Transaction became ready.
 	pub fn ready(&mut self) {
 		self.send(TransactionStatus::Ready)
@@ -101,8 +135,8 @@ impl<H: Clone, BH: Clone> Sender<H, BH> {
 	}
 
 	/// The block this extrinsic was included in has been retracted
-	pub fn retracted(&mut self, hash: BH) {
-		self.send(TransactionStatus::Retracted(hash));
+	pub fn retracted(&mut self, block: RetractedBlock<BH>) {
+		self.send(TransactionStatus::Retracted(block));
 	}
 
 	/// Extrinsic has been marked as invalid by the block builder.
@@ -125,10 +159,16 @@ impl<H: Clone, BH: Clone> Sender<H, BH> {
 
 	/// Returns true if the are no more listeners for this extrinsic or it was finalized.
 	pub fn is_done(&self) -> bool {
-		self.is_finalized || self.receivers.is_empty()
+		self.is_finalized || (self.receivers.is_empty() && self.degraded_receivers.is_empty())
 	}
 
 	fn send(&mut self, status: TransactionStatus<H, BH>) {
+		if status.is_final() {
+			self.degraded_receivers
+				.retain(|sender| sender.unbounded_send(status.clone()).is_ok())
+		} else {
+			self.degraded_receivers.retain(|sender| !sender.is_closed())
+		}
 		self.receivers.retain(|sender| sender.unbounded_send(status.clone()).is_ok())
 	}
 }
@@ -33,6 +33,7 @@ mod tests;
 
 pub use crate::api::FullChainApi;
 use async_trait::async_trait;
+use codec::{DecodeAll, Encode};
 use enactment_state::{EnactmentAction, EnactmentState};
 use futures::{
 	channel::oneshot,
@@ -51,16 +52,21 @@ use std::{
 
 use graph::{ExtrinsicHash, IsValidator};
 use sc_transaction_pool_api::{
-	error::Error as TxPoolError, ChainEvent, ImportNotificationStream, MaintainedTransactionPool,
-	PoolFuture, PoolStatus, ReadyTransactions, TransactionFor, TransactionPool, TransactionSource,
-	TransactionStatusStreamFor, TxHash,
+	error::Error as TxPoolError, BlockHash, BlockInclusionReport, BlockInclusionReportFor,
+	BroadcastInfo, ChainEvent, ImportNotificationStream, InclusionOutcome, InvalidityOutcome,
+	InvalidityReport, LimitedReadyTransactions, MaintainedTransactionPool, OverflowHandle,
+	PoolClass, PoolFuture, PoolSnapshot, PoolSnapshotImport, PoolSnapshotTransaction, PoolStatus,
+	PoolStatusDetailed, PrunePreview, ReadyLimits, ReadyResult, ReadyTransactions, RetractedBlock,
+	TransactionClassifier, TransactionFor, TransactionPool, TransactionPriority, TransactionSource,
+	TransactionStatusStreamFor, TransactionTag, TxHash, ValidationAnchor, WaitReadyOutcome,
+	WaitReadyOutcomeFor, WatcherStatus,
 };
 use sp_core::traits::SpawnEssentialNamed;
 use sp_runtime::{
 	generic::BlockId,
 	traits::{AtLeast32Bit, Block as BlockT, Extrinsic, Header as HeaderT, NumberFor, Zero},
 };
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use crate::metrics::MetricsLink as PrometheusMetrics;
 use prometheus_endpoint::Registry as PrometheusRegistry;
@@ -93,6 +99,11 @@ where
 	ready_poll: Arc<Mutex<ReadyPoll<ReadyIteratorFor<PoolApi>, Block>>>,
 	metrics: PrometheusMetrics,
 	enactment_state: Arc<Mutex<EnactmentState<Block>>>,
+	validation_anchor: ValidationAnchor,
+	/// The block at which [`TransactionPool::hash_of`] hashes transactions, updated on every
+	/// maintenance.
+	hashing_block: Mutex<Block::Hash>,
+	last_inclusion_report: Mutex<Option<BlockInclusionReport<ExtrinsicHash<PoolApi>, Block::Hash>>>,
 }
 
 struct ReadyPoll<T, Block: BlockT> {
@@ -166,6 +177,7 @@ where
 		finalized_hash: Block::Hash,
 		options: graph::Options,
 	) -> (Self, Pin<Box<dyn Future<Output = ()> + Send>>) {
+		let validation_anchor = options.validation_anchor;
 		let pool = Arc::new(graph::Pool::new(options, true.into(), pool_api.clone()));
 		let (revalidation_queue, background_task) = revalidation::RevalidationQueue::new_background(
 			pool_api.clone(),
@@ -184,6 +196,12 @@ where
 					best_block_hash,
 					finalized_hash,
 				))),
+				validation_anchor,
+				hashing_block: Mutex::new(match validation_anchor {
+					ValidationAnchor::BestBlock => best_block_hash,
+					ValidationAnchor::FinalizedBlock => finalized_hash,
+				}),
+				last_inclusion_report: Default::default(),
 			},
 			background_task,
 		)
@@ -202,6 +220,7 @@ where
 		best_block_hash: Block::Hash,
 		finalized_hash: Block::Hash,
 	) -> Self {
+		let validation_anchor = options.validation_anchor;
 		let pool = Arc::new(graph::Pool::new(options, is_validator, pool_api.clone()));
 		let (revalidation_queue, background_task) = match revalidation_type {
 			RevalidationType::Light =>
@@ -235,6 +254,12 @@ where
 				best_block_hash,
 				finalized_hash,
 			))),
+			validation_anchor,
+			hashing_block: Mutex::new(match validation_anchor {
+				ValidationAnchor::BestBlock => best_block_hash,
+				ValidationAnchor::FinalizedBlock => finalized_hash,
+			}),
+			last_inclusion_report: Default::default(),
 		}
 	}
 
@@ -247,6 +272,29 @@ where
 	pub fn api(&self) -> &PoolApi {
 		&self.api
 	}
+
+	/// Sets the classifier assigning a [`PoolClass`] to every transaction entering the pool.
+	///
+	/// Transactions already in the pool keep their class until they are revalidated.
+	pub fn set_classifier(&self, classifier: Arc<dyn TransactionClassifier<Block>>) {
+		self.pool.validated_pool().set_classifier(classifier)
+	}
+
+	/// Returns the number of watchers receiving all events of their transaction and the
+	/// configured maximum, see [`Options::max_watchers`].
+	pub fn watcher_status(&self) -> WatcherStatus {
+		self.pool.validated_pool().watcher_status()
+	}
+
+	/// Returns the block against which a transaction submitted at `at` is validated, according
+	/// to the configured [`ValidationAnchor`].
+	fn validation_block(&self, at: Block::Hash) -> Block::Hash {
+		match self.validation_anchor {
+			ValidationAnchor::BestBlock => at,
+			ValidationAnchor::FinalizedBlock =>
+				self.enactment_state.lock().recent_finalized_block(),
+		}
+	}
 }
 
 impl<PoolApi, Block> TransactionPool for BasicPool<PoolApi, Block>
@@ -266,6 +314,7 @@ where
 		xts: Vec<TransactionFor<Self>>,
 	) -> PoolFuture<Vec<Result<TxHash<Self>, Self::Error>>, Self::Error> {
 		let pool = self.pool.clone();
+		let at = self.validation_block(at);
 
 		self.metrics
 			.report(|metrics| metrics.submitted_transactions.inc_by(xts.len() as u64));
@@ -280,6 +329,7 @@ where
 		xt: TransactionFor<Self>,
 	) -> PoolFuture<TxHash<Self>, Self::Error> {
 		let pool = self.pool.clone();
+		let at = self.validation_block(at);
 
 		self.metrics.report(|metrics| metrics.submitted_transactions.inc());
 
@@ -293,6 +343,7 @@ where
 		xt: TransactionFor<Self>,
 	) -> PoolFuture<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error> {
 		let pool = self.pool.clone();
+		let at = self.validation_block(at);
 
 		self.metrics.report(|metrics| metrics.submitted_transactions.inc());
 
@@ -304,29 +355,122 @@ where
 		.boxed()
 	}
 
-	fn remove_invalid(&self, hashes: &[TxHash<Self>]) -> Vec<Arc<Self::InPoolTransaction>> {
-		let removed = self.pool.validated_pool().remove_invalid(hashes);
+	fn watch_existing(
+		&self,
+		hash: TxHash<Self>,
+	) -> Option<Pin<Box<TransactionStatusStreamFor<Self>>>> {
+		let watcher = self.pool.validated_pool().watch_existing(hash)?;
+		Some(watcher.into_stream().boxed())
+	}
+
+	fn remove_invalid_detailed(
+		&self,
+		hashes: &[TxHash<Self>],
+	) -> InvalidityReport<TxHash<Self>, Arc<Self::InPoolTransaction>> {
+		let groups = self.pool.validated_pool().remove_invalid_by_hash(hashes);
+		let mut outcomes = HashMap::with_capacity(groups.len());
+		let mut removed = Vec::new();
+		for (hash, group) in groups {
+			let outcome = match group.len() {
+				0 => InvalidityOutcome::NotFound,
+				n => InvalidityOutcome::Removed { dependants_removed: (n - 1) as u32 },
+			};
+			// a hash given twice is only removed the first time.
+			outcomes.entry(hash).or_insert(outcome);
+			removed.extend(group);
+		}
 		self.metrics
 			.report(|metrics| metrics.validations_invalid.inc_by(removed.len() as u64));
-		removed
+
+		InvalidityReport { outcomes, removed }
 	}
 
 	fn status(&self) -> PoolStatus {
 		self.pool.validated_pool().status()
 	}
 
+	fn status_detailed(&self) -> PoolStatusDetailed {
+		self.pool.validated_pool().status_detailed(Instant::now())
+	}
+
+	fn prune_preview(
+		&self,
+		at: BlockHash<Self>,
+	) -> Pin<Box<dyn Future<Output = PrunePreview> + Send>> {
+		let pool = self.pool.clone();
+		let api = self.api.clone();
+
+		async move {
+			let extrinsics = api
+				.block_body(at)
+				.await
+				.unwrap_or_else(|e| {
+					log::warn!(target: LOG_TARGET, "Prune preview: error request: {}", e);
+					None
+				})
+				.unwrap_or_default();
+
+			match api.block_header(at) {
+				Ok(Some(header)) =>
+					pool.prune_preview(at, *header.parent_hash(), &extrinsics).await,
+				// Maintenance does not prune anything without the header either.
+				_ => pool.validated_pool().prune_preview(std::iter::empty()),
+			}
+		}
+		.boxed()
+	}
+
+	fn priority_threshold(&self, within_bytes: usize) -> Option<TransactionPriority> {
+		self.pool.validated_pool().priority_threshold(within_bytes)
+	}
+
+	fn min_ready_priority(&self) -> Option<TransactionPriority> {
+		self.pool.validated_pool().min_ready_priority()
+	}
+
+	fn min_replacement_priority(&self, tag: &TransactionTag) -> Option<TransactionPriority> {
+		self.pool.validated_pool().min_replacement_priority(tag)
+	}
+
 	fn import_notification_stream(&self) -> ImportNotificationStream<TxHash<Self>> {
 		self.pool.validated_pool().import_notification_stream()
 	}
 
+	fn import_notification_stream_with_buffer(
+		&self,
+		buffer_size: usize,
+	) -> ImportNotificationStream<TxHash<Self>> {
+		self.pool.validated_pool().import_notification_stream_with_buffer(buffer_size)
+	}
+
+	fn dropped_import_notifications(&self) -> u64 {
+		self.pool.validated_pool().dropped_import_notifications()
+	}
+
+	fn import_notification_stream_with_overflow(
+		&self,
+		buffer_size: Option<usize>,
+	) -> (ImportNotificationStream<TxHash<Self>>, OverflowHandle<TxHash<Self>>) {
+		self.pool.validated_pool().import_notification_stream_with_overflow(buffer_size)
+	}
+
 	fn hash_of(&self, xt: &TransactionFor<Self>) -> TxHash<Self> {
-		self.pool.hash_of(xt)
+		let at = *self.hashing_block.lock();
+		self.pool.hash_of_at(at, xt)
+	}
+
+	fn hash_of_at(&self, xt: &TransactionFor<Self>, at: BlockHash<Self>) -> TxHash<Self> {
+		self.pool.hash_of_at(self.validation_block(at), xt)
 	}
 
 	fn on_broadcasted(&self, propagations: HashMap<TxHash<Self>, Vec<String>>) {
 		self.pool.validated_pool().on_broadcasted(propagations)
 	}
 
+	fn broadcast_info(&self, hash: &TxHash<Self>) -> Option<BroadcastInfo> {
+		self.pool.validated_pool().broadcast_info(hash)
+	}
+
 	fn ready_transaction(&self, hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>> {
 		self.pool.validated_pool().ready_by_hash(hash)
 	}
@@ -359,14 +503,242 @@ where
 			.boxed()
 	}
 
+	fn ready_at_head(&self, at: Block::Hash) -> PolledIterator<PoolApi> {
+		let best = self.enactment_state.lock().recent_best_block();
+		if at != best {
+			log::debug!(
+				target: LOG_TARGET,
+				"Ready transactions requested at {:?}, but the pool is maintained at {:?}",
+				at,
+				best,
+			);
+			return async { Box::new(std::iter::empty()) as Box<_> }.boxed()
+		}
+
+		match self.api.block_id_to_number(&BlockId::Hash(at)) {
+			Ok(Some(number)) => self.ready_at(number),
+			_ => {
+				log::debug!(
+					target: LOG_TARGET,
+					"Ready transactions requested at unknown block {:?}",
+					at,
+				);
+				async { Box::new(std::iter::empty()) as Box<_> }.boxed()
+			},
+		}
+	}
+
+	fn ready_at_with_limits(
+		&self,
+		at: NumberFor<Self::Block>,
+		limits: ReadyLimits,
+	) -> PolledIterator<PoolApi> {
+		// The pool already knows the encoded size of every transaction, no need to re-encode.
+		self.ready_at(at)
+			.map(move |ready| {
+				Box::new(LimitedReadyTransactions::new(ready, limits, |tx| tx.bytes)) as Box<_>
+			})
+			.boxed()
+	}
+
+	fn ready_at_cancellable(
+		&self,
+		at: NumberFor<Self::Block>,
+		cancelled: future::BoxFuture<'static, ()>,
+	) -> Pin<Box<dyn Future<Output = ReadyResult<ReadyIteratorFor<PoolApi>>> + Send>> {
+		let ready_at = self.ready_at(at);
+		let pool = self.pool.clone();
+		let ready_poll = self.ready_poll.clone();
+		async move {
+			match future::select(ready_at, cancelled).await {
+				future::Either::Left((ready, _)) => ReadyResult::Complete(ready),
+				future::Either::Right(((), _)) => {
+					// Collect the transactions only now, so that they include everything the
+					// pool got maintained with until the cancellation.
+					let pending_maintenance = ready_poll.lock().updated_at() < at;
+					let ready: ReadyIteratorFor<PoolApi> = Box::new(pool.validated_pool().ready());
+					ReadyResult::BestEffort { ready, pending_maintenance }
+				},
+			}
+		}
+		.boxed()
+	}
+
 	fn ready(&self) -> ReadyIteratorFor<PoolApi> {
 		Box::new(self.pool.validated_pool().ready())
 	}
 
-	fn futures(&self) -> Vec<Self::InPoolTransaction> {
+	fn futures(&self) -> Vec<Arc<Self::InPoolTransaction>> {
+		self.pool.validated_pool().pool.read().futures_by_import_order()
+	}
+
+	fn futures_paged(
+		&self,
+		offset: usize,
+		limit: usize,
+	) -> (Vec<Arc<Self::InPoolTransaction>>, usize) {
+		let pool = self.pool.validated_pool().pool.read();
+
+		pool.futures_by_import_order_paged(offset, limit)
+	}
+
+	fn wait_ready(
+		&self,
+		hash: TxHash<Self>,
+		timeout: Option<Duration>,
+	) -> Pin<Box<dyn Future<Output = WaitReadyOutcomeFor<Self>> + Send>> {
+		let validated_pool = self.pool.validated_pool();
+
+		// The waiter is registered before checking the pool, so that a promotion happening in
+		// between is not missed.
+		let receiver = validated_pool.ready_waiter(hash);
+		let waiter =
+			receiver.map(|receiver| ReadyWaiter { pool: self.pool.clone(), hash, receiver });
+
+		if validated_pool.ready_by_hash(&hash).is_some() {
+			return ready(WaitReadyOutcome::Ready).boxed()
+		}
+		if !validated_pool.is_imported(&hash) {
+			return ready(WaitReadyOutcome::Unknown).boxed()
+		}
+		let Some(mut waiter) = waiter else {
+			return ready(WaitReadyOutcome::TooManyWaiters).boxed()
+		};
+
+		async move {
+			let timeout = match timeout {
+				Some(timeout) => futures_timer::Delay::new(timeout).left_future(),
+				None => future::pending().right_future(),
+			};
+			match future::select(&mut waiter.receiver, timeout).await {
+				future::Either::Left((Ok(outcome), _)) => outcome,
+				// The pool was dropped.
+				future::Either::Left((Err(_), _)) => WaitReadyOutcome::Unknown,
+				future::Either::Right(_) => WaitReadyOutcome::TimedOut,
+			}
+		}
+		.boxed()
+	}
+
+	fn export_state(&self) -> PoolSnapshot {
 		let pool = self.pool.validated_pool().pool.read();
 
-		pool.futures().cloned().collect::<Vec<_>>()
+		PoolSnapshot {
+			ready: pool.ready().map(|tx| snapshot_transaction(&tx)).collect(),
+			future: pool
+				.futures_by_import_order()
+				.iter()
+				.map(|tx| snapshot_transaction(tx))
+				.collect(),
+		}
+	}
+
+	fn import_state(
+		&self,
+		snapshot: PoolSnapshot,
+	) -> Pin<Box<dyn Future<Output = PoolSnapshotImport> + Send>> {
+		let pool = self.pool.clone();
+		let at = self.validation_block(self.enactment_state.lock().recent_best_block());
+
+		self.metrics.report(|metrics| {
+			metrics
+				.submitted_transactions
+				.inc_by((snapshot.ready.len() + snapshot.future.len()) as u64)
+		});
+
+		async move {
+			let mut outcome = PoolSnapshotImport::default();
+			// Transactions are submitted one by one, so that they enter the pool in the order of
+			// the snapshot.
+			for tx in snapshot.ready.into_iter().chain(snapshot.future) {
+				let xt = match graph::ExtrinsicFor::<PoolApi>::decode_all(&mut &tx.data[..]) {
+					Ok(xt) => xt,
+					Err(e) => {
+						log::debug!(
+							target: LOG_TARGET,
+							"Dropping undecodable snapshot entry: {}",
+							e
+						);
+						outcome.dropped += 1;
+						continue
+					},
+				};
+				match pool.submit_one(at, tx.source, xt).await {
+					Ok(_) => outcome.imported += 1,
+					Err(e) => {
+						log::debug!(target: LOG_TARGET, "Dropping snapshot entry: {:?}", e);
+						outcome.dropped += 1;
+					},
+				}
+			}
+			outcome
+		}
+		.boxed()
+	}
+
+	fn set_class_limits(&self, class: PoolClass, max_count: usize, max_bytes: usize) {
+		self.pool.validated_pool().set_class_limits(
+			class,
+			graph::base_pool::Limit { count: max_count, total_bytes: max_bytes },
+		)
+	}
+
+	fn note_inclusion_outcome(
+		&self,
+		at: BlockHash<Self>,
+		mut outcomes: Vec<(TxHash<Self>, InclusionOutcome)>,
+	) {
+		// The ready transactions depending on skipped ones were not tried at all, they are
+		// reported as missing a dependency.
+		let tried = outcomes.iter().map(|(hash, _)| *hash).collect::<HashSet<_>>();
+		let mut missing_tags = outcomes
+			.iter()
+			.filter(|(_, outcome)| *outcome != InclusionOutcome::Included)
+			.filter_map(|(hash, _)| self.ready_transaction(hash))
+			.flat_map(|tx| tx.provides.clone())
+			.collect::<HashSet<_>>();
+		if !missing_tags.is_empty() {
+			for tx in self.pool.validated_pool().ready() {
+				if !tried.contains(&tx.hash) &&
+					tx.requires.iter().any(|tag| missing_tags.contains(tag))
+				{
+					missing_tags.extend(tx.provides.iter().cloned());
+					outcomes.push((tx.hash, InclusionOutcome::MissingDependency));
+				}
+			}
+		}
+
+		*self.last_inclusion_report.lock() = Some(BlockInclusionReport::new(at, outcomes));
+	}
+
+	fn last_inclusion_report(&self) -> Option<BlockInclusionReportFor<Self>> {
+		self.last_inclusion_report.lock().clone()
+	}
+}
+
+/// Converts a transaction of the pool into a [`PoolSnapshotTransaction`].
+fn snapshot_transaction<Hash, Ex: Encode>(
+	tx: &graph::base_pool::Transaction<Hash, Ex>,
+) -> PoolSnapshotTransaction {
+	PoolSnapshotTransaction { source: tx.source, data: tx.data.encode() }
+}
+
+/// A registered waiter for a transaction to become ready.
+///
+/// Removes itself from the registry of the pool when dropped, so that waiters which timed out or
+/// were abandoned do not accumulate.
+struct ReadyWaiter<PoolApi: graph::ChainApi> {
+	pool: Arc<graph::Pool<PoolApi>>,
+	hash: graph::ExtrinsicHash<PoolApi>,
+	receiver: oneshot::Receiver<
+		WaitReadyOutcome<graph::ExtrinsicHash<PoolApi>, graph::BlockHash<PoolApi>>,
+	>,
+}
+
+impl<PoolApi: graph::ChainApi> Drop for ReadyWaiter<PoolApi> {
+	fn drop(&mut self) {
+		self.receiver.close();
+		self.pool.validated_pool().remove_canceled_ready_waiters(&self.hash);
 	}
 }
 
@@ -435,6 +807,8 @@ where
 			traits::SaturatedConversion, transaction_validity::TransactionValidityError,
 		};
 
+		let at = self.validation_block(at);
+
 		let validity = self
 			.api
 			.validate_transaction_blocking(at, TransactionSource::Local, xt.clone())?
@@ -445,12 +819,14 @@ where
 				})
 			})?;
 
-		let (hash, bytes) = self.pool.validated_pool().api().hash_and_length(&xt);
+		let requires = validity.requires.clone();
+		let (hash, bytes) = self.pool.validated_pool().api().hash_and_length_at(at, &xt);
 		let block_number = self
 			.api
 			.block_id_to_number(&BlockId::hash(at))?
 			.ok_or_else(|| error::Error::BlockIdConversion(format!("{:?}", at)))?;
 
+		let class = self.pool.validated_pool().classify(&xt, &validity);
 		let validated = ValidatedTransaction::valid_at(
 			block_number.saturated_into::<u64>(),
 			hash,
@@ -458,9 +834,20 @@ where
 			xt,
 			bytes,
 			validity,
+			class,
 		);
 
-		self.pool.validated_pool().submit(vec![validated]).remove(0)
+		let result = self.pool.validated_pool().submit(vec![validated]).remove(0);
+
+		// Tell local submitters (e.g. offchain workers) whether the transaction may still be
+		// accepted later on.
+		result.map_err(|e| match e {
+			error::Error::Pool(TxPoolError::RejectedFutureTransaction) =>
+				TxPoolError::FutureNonce { expected_provides: requires }.into(),
+			error::Error::Pool(TxPoolError::TooLowPriority { .. }) =>
+				TxPoolError::StalePriority.into(),
+			e => e,
+		})
 	}
 }
 
@@ -561,7 +948,7 @@ async fn prune_known_txs_for_block<Block: BlockT, Api: graph::ChainApi<Block = B
 		})
 		.unwrap_or_default();
 
-	let hashes = extrinsics.iter().map(|tx| pool.hash_of(tx)).collect::<Vec<_>>();
+	let hashes = extrinsics.iter().map(|tx| pool.hash_of_at(block_hash, tx)).collect::<Vec<_>>();
 
 	log::trace!(target: LOG_TARGET, "Pruning transactions: {:?}", hashes);
 
@@ -589,6 +976,27 @@ where
 	Block: BlockT,
 	PoolApi: 'static + graph::ChainApi<Block = Block>,
 {
+	/// Re-hashes the transactions of the pool at block `to` if the runtime was upgraded since
+	/// block `from`, see [`graph::Pool::rehash_at`].
+	async fn rehash_if_upgraded(&self, from: Block::Hash, to: Block::Hash) {
+		let versions = self
+			.api
+			.runtime_spec_version(from)
+			.and_then(|from_version| Ok((from_version, self.api.runtime_spec_version(to)?)));
+		match versions {
+			Ok((Some(from_version), Some(to_version))) if from_version != to_version => {},
+			Ok(_) => return,
+			Err(e) => {
+				log::debug!(target: LOG_TARGET, "Failed to get runtime version: {}", e);
+				return
+			},
+		}
+
+		if let Err(e) = self.pool.rehash_at(to).await {
+			log::debug!(target: LOG_TARGET, "[{:?}] Error re-hashing transactions: {}", to, e);
+		}
+	}
+
 	/// Handles enactment and retraction of blocks, prunes stale transactions
 	/// (that have already been enacted) and resubmits transactions that were
 	/// retracted.
@@ -624,9 +1032,14 @@ where
 		// retracted blocks and their transactions. This order is important, because
 		// if we enact and retract the same transaction at the same time, we want to
 		// send first the retract and than the prune event.
+		let depth = tree_route.retracted().len() as u32;
 		for retracted in tree_route.retracted() {
 			// notify txs awaiting finality that it has been retracted
-			pool.validated_pool().on_block_retracted(retracted.hash);
+			pool.validated_pool().on_block_retracted(RetractedBlock {
+				hash: retracted.hash,
+				new_best: Some(*hash),
+				depth: Some(depth),
+			});
 		}
 
 		future::join_all(
@@ -664,7 +1077,7 @@ where
 				let mut resubmitted_to_report = 0;
 
 				resubmit_transactions.extend(block_transactions.into_iter().filter(|tx| {
-					let tx_hash = pool.hash_of(tx);
+					let tx_hash = pool.hash_of_at(hash, tx);
 					let contains = pruned_log.contains(&tx_hash);
 
 					// need to count all transactions, not just filtered, here
@@ -688,7 +1101,7 @@ where
 
 			if let Err(e) = pool
 				.resubmit_at(
-					*hash,
+					self.validation_block(*hash),
 					// These transactions are coming from retracted blocks, we should
 					// simply consider them external.
 					TransactionSource::External,
@@ -705,6 +1118,12 @@ where
 			}
 		}
 
+		if self.validation_anchor == ValidationAnchor::BestBlock {
+			let previous_best =
+				tree_route.retracted().first().unwrap_or_else(|| tree_route.common_block());
+			self.rehash_if_upgraded(previous_best.hash, *hash).await;
+		}
+
 		let extra_pool = pool.clone();
 		// After #5200 lands, this arguably might be moved to the
 		// handler of "all blocks notification".
@@ -713,8 +1132,11 @@ where
 			.trigger(*block_number, move || Box::new(extra_pool.validated_pool().ready()));
 
 		if next_action.revalidate {
-			let hashes = pool.validated_pool().ready().map(|tx| tx.hash).collect();
-			self.revalidation_queue.revalidate_later(*hash, hashes).await;
+			// Under the finalized anchor, revalidation is triggered by finality instead.
+			if self.validation_anchor == ValidationAnchor::BestBlock {
+				let hashes = pool.validated_pool().ready().map(|tx| tx.hash).collect();
+				self.revalidation_queue.revalidate_later(*hash, hashes).await;
+			}
 
 			self.revalidation_strategy.lock().clear();
 		}
@@ -774,7 +1196,16 @@ where
 					)
 				}
 			}
+
+			if self.validation_anchor == ValidationAnchor::FinalizedBlock {
+				self.rehash_if_upgraded(prev_finalized_block, hash).await;
+				let hashes = self.pool.validated_pool().ready().map(|tx| tx.hash).collect();
+				self.revalidation_queue.revalidate_later(hash, hashes).await;
+			}
 		}
+
+		let best = self.enactment_state.lock().recent_best_block();
+		*self.hashing_block.lock() = self.validation_block(best);
 	}
 }
 
@@ -90,6 +90,7 @@ impl Metrics {
 pub struct ApiMetrics {
 	pub validations_scheduled: Counter<U64>,
 	pub validations_finished: Counter<U64>,
+	pub validations_panicked: Counter<U64>,
 }
 
 impl ApiMetrics {
@@ -110,6 +111,13 @@ impl ApiMetrics {
 				)?,
 				registry,
 			)?,
+			validations_panicked: register(
+				Counter::new(
+					"substrate_sub_txpool_validations_panicked",
+					"Total number of transactions whose validation panicked in the runtime",
+				)?,
+				registry,
+			)?,
 		})
 	}
 }
@@ -117,6 +117,7 @@ async fn batch_revalidate<Api: ChainApi>(
 				);
 			},
 			Ok(Ok(validity)) => {
+				let class = pool.validated_pool().classify(&ext.data, &validity);
 				revalidated.insert(
 					ext_hash,
 					ValidatedTransaction::valid_at(
@@ -126,6 +127,7 @@ async fn batch_revalidate<Api: ChainApi>(
 						ext.data.clone(),
 						api.hash_and_length(&ext.data).1,
 						validity,
+						class,
 					),
 				);
 			},
@@ -373,9 +375,9 @@ mod tests {
 		graph::Pool,
 		tests::{uxt, TestApi},
 	};
-	use futures::executor::block_on;
-	use sc_transaction_pool_api::TransactionSource;
-	use substrate_test_runtime::{AccountId, Transfer, H256};
+	use futures::executor::{block_on, block_on_stream};
+	use sc_transaction_pool_api::{InPoolTransaction, TransactionSource, TransactionStatus};
+	use substrate_test_runtime::{AccountId, ExtrinsicBuilder, Transfer, H256};
 	use substrate_test_runtime_client::AccountKeyring::{Alice, Bob};
 
 	#[test]
@@ -405,6 +407,36 @@ mod tests {
 		assert_eq!(pool.validated_pool().status().ready, 1);
 	}
 
+	#[test]
+	fn revalidation_panic_invalidates_and_bans_watched_transaction() {
+		let api = Arc::new(TestApi::default());
+		let pool = Arc::new(Pool::new(Default::default(), true.into(), api.clone()));
+		let queue = Arc::new(RevalidationQueue::new(api.clone(), pool.clone()));
+
+		let uxt = uxt(Transfer {
+			from: Alice.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(2)),
+			amount: 5,
+			nonce: 0,
+		});
+
+		let hash_of_block0 = api.expect_hash_from_number(0);
+		let watcher =
+			block_on(pool.submit_and_watch(hash_of_block0, TransactionSource::External, uxt))
+				.expect("Should be valid");
+		let uxt_hash = *watcher.hash();
+
+		api.panic.lock().insert(uxt_hash);
+		block_on(queue.revalidate_later(hash_of_block0, vec![uxt_hash]));
+
+		assert_eq!(pool.validated_pool().status().ready, 0);
+		assert!(pool.validated_pool().is_banned(&uxt_hash));
+		let mut stream = block_on_stream(watcher.into_stream());
+		assert_eq!(stream.next(), Some(TransactionStatus::Ready));
+		assert_eq!(stream.next(), Some(TransactionStatus::Invalid));
+		assert_eq!(stream.next(), None);
+	}
+
 	#[test]
 	fn revalidation_queue_skips_revalidation_for_unknown_block_hash() {
 		let api = Arc::new(TestApi::default());
@@ -449,4 +481,30 @@ mod tests {
 		// number of ready shall not change
 		assert_eq!(pool.validated_pool().status().ready, 2);
 	}
+
+	#[test]
+	fn revalidation_refreshes_validated_at() {
+		let api = Arc::new(TestApi::default());
+		let pool = Arc::new(Pool::new(Default::default(), true.into(), api.clone()));
+		let queue = Arc::new(RevalidationQueue::new(api.clone(), pool.clone()));
+
+		let uxt = ExtrinsicBuilder::new_include_data(vec![42]).build();
+		let uxt_hash = block_on(pool.submit_one(
+			api.expect_hash_from_number(0),
+			TransactionSource::Local,
+			uxt,
+		))
+		.expect("Should be valid");
+
+		let in_pool = pool.validated_pool().ready_by_hash(&uxt_hash).unwrap();
+		assert_eq!(in_pool.validated_at(), Some(0));
+		assert_eq!(in_pool.source(), TransactionSource::Local);
+
+		block_on(queue.revalidate_later(api.expect_hash_from_number(5), vec![uxt_hash]));
+
+		// the transaction was revalidated at the new block, keeping its source
+		let in_pool = pool.validated_pool().ready_by_hash(&uxt_hash).unwrap();
+		assert_eq!(in_pool.validated_at(), Some(5));
+		assert_eq!(in_pool.source(), TransactionSource::Local);
+	}
 }
@@ -43,6 +43,7 @@ pub(crate) const INVALID_NONCE: u64 = 254;
 pub(crate) struct TestApi {
 	pub delay: Arc<Mutex<Option<std::sync::mpsc::Receiver<()>>>>,
 	pub invalidate: Arc<Mutex<HashSet<H256>>>,
+	pub panic: Arc<Mutex<HashSet<H256>>>,
 	pub clear_requirements: Arc<Mutex<HashSet<H256>>>,
 	pub add_requirements: Arc<Mutex<HashSet<H256>>>,
 	pub validation_requests: Arc<Mutex<Vec<Extrinsic>>>,
@@ -77,6 +78,11 @@ impl ChainApi for TestApi {
 		let hash = self.hash_and_length(&uxt).0;
 		let block_number = self.block_id_to_number(&BlockId::Hash(at)).unwrap().unwrap();
 
+		if self.panic.lock().contains(&hash) {
+			let err = error::Error::ValidationPanicked("Execution aborted due to panic".into());
+			return futures::future::ready(Err(err))
+		}
+
 		let res = match uxt {
 			Extrinsic {
 				function: RuntimeCall::Balances(BalancesCall::transfer_allow_death { .. }),
@@ -18,7 +18,7 @@
 
 //! Tests for top-level transaction pool api
 
-use codec::Encode;
+use codec::{Decode, Encode};
 use futures::{
 	executor::{block_on, block_on_stream},
 	prelude::*,
@@ -28,7 +28,12 @@ use sc_block_builder::BlockBuilderBuilder;
 use sc_client_api::client::BlockchainEvents;
 use sc_transaction_pool::*;
 use sc_transaction_pool_api::{
-	ChainEvent, MaintainedTransactionPool, TransactionPool, TransactionStatus,
+	error::{Error as TxPoolError, IntoPoolError},
+	BlockInclusionReport, ChainEvent, InPoolTransaction, InclusionOutcome, InvalidityOutcome,
+	LocalTransactionPool, MaintainedTransactionPool, PoolClass, PoolSnapshot, PoolSnapshotImport,
+	PoolSnapshotTransaction, PrunePreview, ReadyLimits, ReadyResult, ReadyTransactions,
+	RetractedBlock, TransactionClassifier, TransactionPool, TransactionStatus, WaitReadyOutcome,
+	WatcherStatus,
 };
 use sp_blockchain::HeaderBackend;
 use sp_consensus::BlockOrigin;
@@ -37,9 +42,11 @@ use sp_runtime::{
 	traits::Block as _,
 	transaction_validity::{TransactionSource, ValidTransaction},
 };
-use std::{collections::BTreeSet, pin::Pin, sync::Arc};
+use std::{collections::BTreeSet, pin::Pin, sync::Arc, time::Duration};
 use substrate_test_runtime_client::{
-	runtime::{Block, Extrinsic, ExtrinsicBuilder, Hash, Header, Nonce, Transfer, TransferData},
+	runtime::{
+		AccountId, Block, Extrinsic, ExtrinsicBuilder, Hash, Header, Nonce, Transfer, TransferData,
+	},
 	AccountKeyring::*,
 	ClientBlockImportExt,
 };
@@ -271,6 +278,14 @@ fn block_event_with_retracted(
 	}
 }
 
+fn retracted<H>(hash: Hash, new_best: Hash, depth: u32) -> Option<TransactionStatus<H, Hash>> {
+	Some(TransactionStatus::Retracted(RetractedBlock {
+		hash,
+		new_best: Some(new_best),
+		depth: Some(depth),
+	}))
+}
+
 #[test]
 fn should_prune_old_during_maintenance() {
 	let xt = uxt(Alice, 209);
@@ -287,6 +302,36 @@ fn should_prune_old_during_maintenance() {
 	assert_eq!(pool.status().ready, 0);
 }
 
+#[test]
+fn prune_preview_matches_maintenance() {
+	let (pool, api, _guard) = maintained_pool();
+	let at = api.expect_hash_from_number(0);
+
+	// `Alice 210` and `Alice 211` depend on `Alice 209`, `Bob 1` waits for `Bob 0`.
+	let xts = (209..212).map(|nonce| uxt(Alice, nonce)).collect::<Vec<_>>();
+	block_on(pool.submit_at(at, SOURCE, xts.clone())).expect("1. Imported");
+	block_on(pool.submit_one(at, SOURCE, uxt(Bob, 1))).expect("2. Imported");
+	assert_eq!(pool.status().ready, 3);
+	assert_eq!(pool.status().future, 1);
+
+	let header = api.push_block(1, vec![xts[0].clone(), xts[1].clone(), uxt(Bob, 0)], true);
+
+	let preview = block_on(pool.prune_preview(header.hash()));
+	assert_eq!(
+		preview,
+		PrunePreview { would_prune: 2, would_remain_ready: 1, would_promote_from_future: 1 }
+	);
+	// The pool is left untouched.
+	assert_eq!(pool.status().ready, 3);
+	assert_eq!(pool.status().future, 1);
+
+	block_on(pool.maintain(block_event(header)));
+	assert_eq!(pool.status().ready, preview.would_remain_ready + preview.would_promote_from_future);
+	assert_eq!(pool.status().future, 0);
+	assert!(pool.ready_transaction(&pool.hash_of(&xts[2])).is_some());
+	assert!(pool.ready_transaction(&pool.hash_of(&uxt(Bob, 1))).is_some());
+}
+
 #[test]
 fn should_revalidate_during_maintenance() {
 	let xt1 = uxt(Alice, 209);
@@ -530,6 +575,129 @@ fn finalization() {
 	assert_eq!(stream.next(), None);
 }
 
+#[test]
+fn watch_existing_replays_ready_status_and_follows_finalization() {
+	let xt = uxt(Alice, 209);
+	let api = TestApi::with_alice_nonce(209);
+	api.push_block(1, vec![], true);
+	let pool = create_basic_pool(api);
+	let api = pool.api();
+	let hash = block_on(pool.submit_one(api.expect_hash_from_number(1), SOURCE, xt.clone()))
+		.expect("1. Imported");
+	let watcher = pool.watch_existing(hash).expect("transaction is in the pool");
+
+	api.push_block(2, vec![xt.clone()], true);
+	let header = api.chain().read().block_by_number.get(&2).unwrap()[0].0.header().clone();
+	let event = ChainEvent::NewBestBlock { hash: header.hash(), tree_route: None };
+	block_on(pool.maintain(event));
+
+	let event = ChainEvent::Finalized { hash: header.hash(), tree_route: Arc::from(vec![]) };
+	block_on(pool.maintain(event));
+
+	let mut stream = futures::executor::block_on_stream(watcher);
+	assert_eq!(stream.next(), Some(TransactionStatus::Ready));
+	assert_eq!(stream.next(), Some(TransactionStatus::InBlock((header.hash(), 0))));
+	assert_eq!(stream.next(), Some(TransactionStatus::Finalized((header.hash(), 0))));
+	assert_eq!(stream.next(), None);
+}
+
+#[test]
+fn watch_existing_replays_in_block_status() {
+	let xt = uxt(Alice, 209);
+	let api = TestApi::with_alice_nonce(209);
+	api.push_block(1, vec![], true);
+	let pool = create_basic_pool(api);
+	let api = pool.api();
+	let hash = block_on(pool.submit_one(api.expect_hash_from_number(1), SOURCE, xt.clone()))
+		.expect("1. Imported");
+
+	api.push_block(2, vec![xt.clone()], true);
+	let header = api.chain().read().block_by_number.get(&2).unwrap()[0].0.header().clone();
+	let event = ChainEvent::NewBestBlock { hash: header.hash(), tree_route: None };
+	block_on(pool.maintain(event));
+	assert_eq!(pool.status().ready, 0);
+
+	let watcher = pool.watch_existing(hash).expect("block is not finalized");
+	let event = ChainEvent::Finalized { hash: header.hash(), tree_route: Arc::from(vec![]) };
+	block_on(pool.maintain(event));
+
+	let mut stream = futures::executor::block_on_stream(watcher);
+	assert_eq!(stream.next(), Some(TransactionStatus::InBlock((header.hash(), 0))));
+	assert_eq!(stream.next(), Some(TransactionStatus::Finalized((header.hash(), 0))));
+	assert_eq!(stream.next(), None);
+
+	// the transaction is forgotten once finalized.
+	assert!(pool.watch_existing(hash).is_none());
+}
+
+#[test]
+fn watch_existing_replays_future_status() {
+	let (pool, api, _guard) = maintained_pool();
+	let xt = uxt(Alice, 210);
+	let hash =
+		block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, xt)).expect("1. Imported");
+	assert_eq!(pool.status().future, 1);
+
+	let watcher = pool.watch_existing(hash).expect("transaction is in the pool");
+	block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, uxt(Alice, 209)))
+		.expect("2. Imported");
+
+	let mut stream = futures::executor::block_on_stream(watcher);
+	assert_eq!(stream.next(), Some(TransactionStatus::Future));
+	assert_eq!(stream.next(), Some(TransactionStatus::Ready));
+
+	assert!(pool.watch_existing(pool.hash_of(&uxt(Bob, 1))).is_none());
+}
+
+#[test]
+fn submit_and_watch_tagged_echoes_tags() {
+	let api = TestApi::with_alice_nonce(209);
+	api.push_block(1, vec![], true);
+	let pool = create_basic_pool(api);
+	let api = pool.api();
+	let at = api.expect_hash_from_number(1);
+
+	let (watcher1, watcher2) = block_on(futures::future::join(
+		pool.submit_and_watch_tagged(at, SOURCE, uxt(Alice, 209), 7),
+		pool.submit_and_watch_tagged(at, SOURCE, uxt(Bob, 0), 42),
+	));
+	let (watcher1, watcher2) = (watcher1.expect("1. Imported"), watcher2.expect("2. Imported"));
+
+	api.push_block(2, vec![uxt(Bob, 0), uxt(Alice, 209)], true);
+	let header = api.chain().read().block_by_number.get(&2).unwrap()[0].0.header().clone();
+	let event = ChainEvent::NewBestBlock { hash: header.hash(), tree_route: None };
+	block_on(pool.maintain(event));
+	let event = ChainEvent::Finalized { hash: header.hash(), tree_route: Arc::from(vec![]) };
+	block_on(pool.maintain(event));
+
+	// the watchers are polled together, so events can only be told apart by their tags.
+	let events = block_on_stream(futures::stream::select(watcher1, watcher2)).collect::<Vec<_>>();
+	let events_of = |tag| {
+		events
+			.iter()
+			.filter(|(t, _)| *t == tag)
+			.map(|(_, e)| e.clone())
+			.collect::<Vec<_>>()
+	};
+	assert_eq!(events.len(), 6);
+	assert_eq!(
+		events_of(7),
+		vec![
+			TransactionStatus::Ready,
+			TransactionStatus::InBlock((header.hash(), 1)),
+			TransactionStatus::Finalized((header.hash(), 1)),
+		]
+	);
+	assert_eq!(
+		events_of(42),
+		vec![
+			TransactionStatus::Ready,
+			TransactionStatus::InBlock((header.hash(), 0)),
+			TransactionStatus::Finalized((header.hash(), 0)),
+		]
+	);
+}
+
 #[test]
 fn fork_aware_finalization() {
 	sp_tracing::try_init_simple();
@@ -680,7 +848,7 @@ fn fork_aware_finalization() {
 		let mut stream = futures::executor::block_on_stream(from_dave_watcher);
 		assert_eq!(stream.next(), Some(TransactionStatus::Ready));
 		assert_eq!(stream.next(), Some(TransactionStatus::InBlock((c2, 0))));
-		assert_eq!(stream.next(), Some(TransactionStatus::Retracted(c2)));
+		assert_eq!(stream.next(), retracted(c2, c1, 2));
 		assert_eq!(stream.next(), Some(TransactionStatus::Ready));
 		assert_eq!(stream.next(), Some(TransactionStatus::InBlock((e1, 0))));
 		assert_eq!(stream.next(), Some(TransactionStatus::Finalized((e1, 0))));
@@ -691,7 +859,7 @@ fn fork_aware_finalization() {
 		let mut stream = futures::executor::block_on_stream(from_bob_watcher);
 		assert_eq!(stream.next(), Some(TransactionStatus::Ready));
 		assert_eq!(stream.next(), Some(TransactionStatus::InBlock((d2, 0))));
-		assert_eq!(stream.next(), Some(TransactionStatus::Retracted(d2)));
+		assert_eq!(stream.next(), retracted(d2, c1, 2));
 		assert_eq!(stream.next(), Some(TransactionStatus::Ready));
 		// In block e1 we submitted: [dave, bob] xts in this order.
 		assert_eq!(stream.next(), Some(TransactionStatus::InBlock((e1, 1))));
@@ -748,7 +916,7 @@ fn prune_and_retract_tx_at_same_time() {
 		let mut stream = futures::executor::block_on_stream(watcher);
 		assert_eq!(stream.next(), Some(TransactionStatus::Ready));
 		assert_eq!(stream.next(), Some(TransactionStatus::InBlock((b1, 0))));
-		assert_eq!(stream.next(), Some(TransactionStatus::Retracted(b1)));
+		assert_eq!(stream.next(), retracted(b1, b2, 1));
 		assert_eq!(stream.next(), Some(TransactionStatus::InBlock((b2, 0))));
 		assert_eq!(stream.next(), Some(TransactionStatus::Finalized((b2, 0))));
 		assert_eq!(stream.next(), None);
@@ -923,6 +1091,33 @@ fn resubmit_from_retracted_fork() {
 	assert_eq!(expected_ready, ready);
 }
 
+#[test]
+fn ready_at_head_only_serves_the_maintained_fork() {
+	let (pool, api, _guard) = maintained_pool();
+	let genesis = api.expect_hash_from_number(0);
+	let (alice, bob) = (uxt(Alice, 209), uxt(Bob, 0));
+	block_on(pool.submit_one(genesis, SOURCE, alice.clone())).expect("1. Imported");
+	block_on(pool.submit_one(genesis, SOURCE, bob.clone())).expect("2. Imported");
+
+	// Two competing forks, each including one of the transactions.
+	let a1 = api.push_block_with_parent(genesis, vec![alice.clone()], true);
+	let b1 = api.push_block_with_parent(genesis, vec![bob.clone()], true);
+	let ready_at_head = |head| {
+		block_on(pool.ready_at_head(head))
+			.map(|tx| tx.data().clone())
+			.collect::<Vec<_>>()
+	};
+
+	block_on(pool.maintain(block_event(a1.clone())));
+	assert_eq!(ready_at_head(a1.hash()), vec![bob.clone()]);
+	// The pool has no view of the other fork, so it does not pretend to.
+	assert!(ready_at_head(b1.hash()).is_empty());
+
+	block_on(pool.maintain(block_event_with_retracted(b1.clone(), a1.hash(), &api)));
+	assert_eq!(ready_at_head(b1.hash()), vec![alice]);
+	assert!(ready_at_head(a1.hash()).is_empty());
+}
+
 #[test]
 fn ready_set_should_not_resolve_before_block_update() {
 	let (pool, api, _guard) = maintained_pool();
@@ -978,6 +1173,451 @@ fn ready_set_should_eventually_resolve_when_block_update_arrives() {
 	}
 }
 
+#[test]
+fn ready_at_cancellable_should_be_best_effort_when_cancelled_before_maintenance() {
+	let (pool, api, _guard) = maintained_pool();
+	api.push_block(1, vec![], true);
+
+	let xt1 = uxt(Alice, 209);
+	block_on(pool.submit_one(api.expect_hash_from_number(1), SOURCE, xt1.clone()))
+		.expect("1. Imported");
+
+	let ready = block_on(pool.ready_at_cancellable(1, futures::future::ready(()).boxed()));
+	match ready {
+		ReadyResult::BestEffort { ready, pending_maintenance } => {
+			assert!(pending_maintenance);
+			assert_eq!(ready.map(|tx| tx.data.encode()).collect::<Vec<_>>(), vec![xt1.encode()]);
+		},
+		ReadyResult::Complete(_) => panic!("Pool was not maintained at block #1"),
+	}
+}
+
+#[test]
+fn ready_at_cancellable_should_be_complete_when_already_maintained() {
+	let (pool, api, _guard) = maintained_pool();
+	let header = api.push_block(1, vec![], true);
+
+	let xt1 = uxt(Alice, 209);
+	block_on(pool.submit_one(api.expect_hash_from_number(1), SOURCE, xt1.clone()))
+		.expect("1. Imported");
+	block_on(pool.maintain(block_event(header)));
+
+	// Even a cancellation which already fired does not degrade an up to date ready set.
+	let ready = block_on(pool.ready_at_cancellable(1, futures::future::ready(()).boxed()));
+	assert!(ready.is_complete());
+	assert_eq!(ready.into_inner().count(), 1);
+}
+
+#[test]
+fn ready_at_cancellable_should_resolve_with_the_first_of_maintenance_and_cancellation() {
+	let (pool, api, _guard) = maintained_pool();
+	let header = api.push_block(1, vec![], true);
+
+	let xt1 = uxt(Alice, 209);
+	block_on(pool.submit_one(api.expect_hash_from_number(1), SOURCE, xt1.clone()))
+		.expect("1. Imported");
+
+	let noop_waker = futures::task::noop_waker();
+	let mut context = futures::task::Context::from_waker(&noop_waker);
+
+	let (cancel, cancelled) = futures::channel::oneshot::channel::<()>();
+	let mut completed = pool.ready_at_cancellable(1, cancelled.map(|_| ()).boxed());
+	let (cancel_best_effort, cancelled) = futures::channel::oneshot::channel::<()>();
+	let mut best_effort = pool.ready_at_cancellable(1, cancelled.map(|_| ()).boxed());
+	assert!(completed.poll_unpin(&mut context).is_pending());
+	assert!(best_effort.poll_unpin(&mut context).is_pending());
+
+	// Cancelling before the maintenance completes yields the best effort set.
+	cancel_best_effort.send(()).unwrap();
+	match best_effort.poll_unpin(&mut context) {
+		Poll::Ready(ReadyResult::BestEffort { pending_maintenance, .. }) =>
+			assert!(pending_maintenance),
+		_ => panic!("Ready set should be best effort after the cancellation"),
+	}
+
+	// Maintaining the pool before cancelling yields the complete set.
+	block_on(pool.maintain(block_event(header)));
+	match completed.poll_unpin(&mut context) {
+		Poll::Ready(ReadyResult::Complete(ready)) => assert_eq!(ready.count(), 1),
+		_ => panic!("Ready set should be complete after the block update"),
+	}
+	drop(cancel);
+}
+
+#[test]
+fn ready_at_with_limits_should_respect_byte_budget() {
+	let (pool, api, _guard) = maintained_pool();
+	let xts = (209..213).map(|nonce| uxt(Alice, nonce)).collect::<Vec<_>>();
+	let tx_size = xts[0].encoded_size();
+	block_on(pool.submit_at(api.expect_hash_from_number(0), SOURCE, xts)).unwrap();
+	assert_eq!(pool.status().ready, 4);
+
+	let limits = ReadyLimits { max_bytes: Some(2 * tx_size + tx_size / 2), ..Default::default() };
+	let ready = block_on(pool.ready_at_with_limits(0, limits))
+		.map(|tx| TransferData::try_from(&tx.data).unwrap().nonce)
+		.collect::<Vec<_>>();
+	assert_eq!(ready, vec![209, 210]);
+
+	let limits = ReadyLimits { max_transactions: Some(3), ..Default::default() };
+	let ready = block_on(pool.ready_at_with_limits(0, limits))
+		.map(|tx| TransferData::try_from(&tx.data).unwrap().nonce)
+		.collect::<Vec<_>>();
+	assert_eq!(ready, vec![209, 210, 211]);
+
+	let ready = block_on(pool.ready_at_with_limits(0, ReadyLimits::default())).count();
+	assert_eq!(ready, 4);
+}
+
+#[test]
+fn ready_at_with_limits_should_exclude_dependencies_of_oversized_transactions() {
+	let api = Arc::new(TestApi::empty());
+	let large = ExtrinsicBuilder::new_include_data(vec![42u8; 1024]).build();
+	let dependent = ExtrinsicBuilder::new_include_data(vec![1]).build();
+	let independent = ExtrinsicBuilder::new_include_data(vec![2]).build();
+
+	// `dependent` requires the tag provided by `large`.
+	let (large_tag, dependent_tag) = (large.encode(), dependent.encode());
+	api.set_valid_modifier(Box::new(move |v: &mut ValidTransaction| {
+		if v.provides == vec![dependent_tag.clone()] {
+			v.requires = vec![large_tag.clone()];
+		}
+	}));
+	api.set_priority(&large, 100);
+	api.set_priority(&independent, 50);
+
+	let (pool, _background) = create_basic_pool_with_genesis(api.clone());
+	block_on(pool.submit_at(
+		api.expect_hash_from_number(0),
+		SOURCE,
+		vec![large.clone(), dependent.clone(), independent.clone()],
+	))
+	.unwrap();
+	assert_eq!(pool.status().ready, 3);
+
+	// Everything is returned without limits.
+	let ready = block_on(pool.ready_at_with_limits(0, ReadyLimits::default()))
+		.map(|tx| tx.hash)
+		.collect::<Vec<_>>();
+	assert_eq!(ready.len(), 3);
+
+	// The large transaction does not fit, so neither can the dependent one be included, even
+	// though it would fit into the budget on its own.
+	let limits = ReadyLimits { max_bytes: Some(512), ..Default::default() };
+	let ready = block_on(pool.ready_at_with_limits(0, limits))
+		.map(|tx| tx.hash)
+		.collect::<Vec<_>>();
+	assert_eq!(ready, vec![pool.hash_of(&independent)]);
+	assert!(dependent.encoded_size() + independent.encoded_size() <= 512);
+}
+
+#[test]
+fn ready_at_with_limits_should_return_fitting_transactions_after_oversized_ones() {
+	let api = Arc::new(TestApi::empty());
+	let large = (0..8u8)
+		.map(|i| ExtrinsicBuilder::new_include_data(vec![i; 1024]).build())
+		.collect::<Vec<_>>();
+	let small = ExtrinsicBuilder::new_include_data(vec![42]).build();
+	large.iter().for_each(|xt| api.set_priority(xt, 100));
+	api.set_priority(&small, 50);
+
+	let (pool, _background) = create_basic_pool_with_genesis(api.clone());
+	let xts = large.iter().cloned().chain(std::iter::once(small.clone())).collect();
+	block_on(pool.submit_at(api.expect_hash_from_number(0), SOURCE, xts)).unwrap();
+	assert_eq!(pool.status().ready, 9);
+	assert!(small.encoded_size() <= 512);
+
+	// Without a limit on the skipped transactions, the whole ready set is scanned.
+	let limits = ReadyLimits { max_bytes: Some(512), ..Default::default() };
+	let ready = block_on(pool.ready_at_with_limits(0, limits))
+		.map(|tx| tx.hash)
+		.collect::<Vec<_>>();
+	assert_eq!(ready, vec![pool.hash_of(&small)]);
+
+	// After eight transactions which do not fit, the budget is considered exhausted and the small
+	// transaction is not reached anymore.
+	let limits =
+		ReadyLimits { max_bytes: Some(512), max_skipped_oversized: Some(8), ..Default::default() };
+	assert_eq!(block_on(pool.ready_at_with_limits(0, limits)).count(), 0);
+}
+
+#[test]
+fn inclusion_report_matches_the_outcomes_of_a_proposer_loop() {
+	let (pool, api, _guard) = maintained_pool();
+	let xts = (209..213).map(|nonce| uxt(Alice, nonce)).collect::<Vec<_>>();
+	let hashes = xts.iter().map(|xt| pool.hash_of(xt)).collect::<Vec<_>>();
+	block_on(pool.submit_at(api.expect_hash_from_number(0), SOURCE, xts)).unwrap();
+	assert!(pool.last_inclusion_report().is_none());
+
+	// The first transaction is included, the second one exhausts the resources of the block,
+	// which makes the iterator skip the transactions depending on it.
+	let mut outcomes = Vec::new();
+	let mut ready = block_on(pool.ready_at(0));
+	while let Some(tx) = ready.next() {
+		if outcomes.is_empty() {
+			outcomes.push((tx.hash, InclusionOutcome::Included));
+		} else {
+			ready.report_invalid(&tx);
+			outcomes.push((tx.hash, InclusionOutcome::ExhaustsResources));
+		}
+	}
+	assert_eq!(outcomes.len(), 2);
+
+	let block_hash = api.expect_hash_from_number(0);
+	pool.note_inclusion_outcome(block_hash, outcomes);
+	let report = pool.last_inclusion_report().unwrap();
+	assert_eq!(report.block_hash, block_hash);
+	assert_eq!(report.included, 1);
+	assert_eq!(report.exhausted_resources, 1);
+	assert_eq!(report.invalid, 0);
+	assert_eq!(report.missing_dependency, 2);
+	assert_eq!(
+		report.skipped,
+		vec![
+			(hashes[1], InclusionOutcome::ExhaustsResources),
+			(hashes[2], InclusionOutcome::MissingDependency),
+			(hashes[3], InclusionOutcome::MissingDependency),
+		],
+	);
+
+	// The report of the next block replaces it.
+	pool.note_inclusion_outcome(block_hash, vec![(hashes[0], InclusionOutcome::Invalid)]);
+	assert_eq!(
+		pool.last_inclusion_report(),
+		Some(BlockInclusionReport {
+			block_hash,
+			included: 0,
+			exhausted_resources: 0,
+			invalid: 1,
+			missing_dependency: 3,
+			skipped: vec![
+				(hashes[0], InclusionOutcome::Invalid),
+				(hashes[1], InclusionOutcome::MissingDependency),
+				(hashes[2], InclusionOutcome::MissingDependency),
+				(hashes[3], InclusionOutcome::MissingDependency),
+			],
+		}),
+	);
+}
+
+#[test]
+fn futures_paged_should_page_through_large_future_queue() {
+	let api = Arc::new(TestApi::with_alice_nonce(209));
+	let at = api.expect_hash_from_number(0);
+	let options = Options {
+		future: PoolLimit { count: 4096, total_bytes: 4 * 1024 * 1024 },
+		..Default::default()
+	};
+	let (pool, _background_task) = BasicPool::new_test(api.clone(), at, at, options);
+
+	// Nonce 209 is never provided, so all of them stay in the future queue.
+	let hashes = (210..2210)
+		.map(|nonce| block_on(pool.submit_one(at, SOURCE, uxt(Alice, nonce))).unwrap())
+		.collect::<Vec<_>>();
+	assert_eq!(pool.status().future, 2000);
+	assert_eq!(pool.futures().iter().map(|tx| tx.hash).collect::<Vec<_>>(), hashes);
+
+	let mut paged = Vec::new();
+	for offset in (0..2000).step_by(300) {
+		let (page, total) = pool.futures_paged(offset, 300);
+		assert_eq!(total, 2000);
+		assert_eq!(page.len(), (2000 - offset).min(300));
+		paged.extend(page.iter().map(|tx| tx.hash));
+	}
+	assert_eq!(paged, hashes);
+
+	let (page, total) = pool.futures_paged(1999, 300);
+	assert_eq!(
+		(page.iter().map(|tx| tx.hash).collect::<Vec<_>>(), total),
+		(vec![hashes[1999]], 2000)
+	);
+	let (page, total) = pool.futures_paged(2000, 300);
+	assert!(page.is_empty());
+	assert_eq!(total, 2000);
+	let (page, total) = pool.futures_paged(0, 0);
+	assert!(page.is_empty());
+	assert_eq!(total, 2000);
+}
+
+#[test]
+fn wait_ready_resolves_when_future_transaction_becomes_ready() {
+	let (pool, api, _guard) = maintained_pool();
+
+	let xt = uxt(Alice, 210);
+	let hash = block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, xt)).unwrap();
+	assert_eq!(pool.status().future, 1);
+
+	let mut wait_ready = pool.wait_ready(hash, None);
+	let noop_waker = futures::task::noop_waker();
+	let mut context = futures::task::Context::from_waker(&noop_waker);
+	assert!(wait_ready.poll_unpin(&mut context).is_pending());
+	assert_eq!(pool.pool().validated_pool().ready_waiters_count(), 1);
+
+	// Submitting the dependency promotes the awaited transaction.
+	block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, uxt(Alice, 209))).unwrap();
+	assert_eq!(block_on(wait_ready), WaitReadyOutcome::Ready);
+	assert_eq!(pool.pool().validated_pool().ready_waiters_count(), 0);
+}
+
+#[test]
+fn wait_ready_resolves_immediately_for_ready_transaction() {
+	let (pool, api, _guard) = maintained_pool();
+
+	let xt = uxt(Alice, 209);
+	let hash = block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, xt)).unwrap();
+
+	assert_eq!(block_on(pool.wait_ready(hash, None)), WaitReadyOutcome::Ready);
+	assert_eq!(pool.pool().validated_pool().ready_waiters_count(), 0);
+}
+
+#[test]
+fn wait_ready_resolves_for_unknown_transaction() {
+	let (pool, _api, _guard) = maintained_pool();
+
+	let hash = pool.hash_of(&uxt(Alice, 209));
+	assert_eq!(block_on(pool.wait_ready(hash, None)), WaitReadyOutcome::Unknown);
+	assert_eq!(pool.pool().validated_pool().ready_waiters_count(), 0);
+}
+
+#[test]
+fn wait_ready_times_out() {
+	let (pool, api, _guard) = maintained_pool();
+
+	let xt = uxt(Alice, 210);
+	let hash = block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, xt)).unwrap();
+
+	let outcome = block_on(pool.wait_ready(hash, Some(Duration::from_millis(10))));
+	assert_eq!(outcome, WaitReadyOutcome::TimedOut);
+	assert_eq!(pool.pool().validated_pool().ready_waiters_count(), 0);
+}
+
+#[test]
+fn wait_ready_reports_too_many_waiters() {
+	let (pool, api, _guard) = maintained_pool();
+
+	let xt = uxt(Alice, 210);
+	let hash = block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, xt)).unwrap();
+	// The pool serves at most 4096 waiters.
+	let waiters: Vec<_> = (0..4096).map(|_| pool.wait_ready(hash, None)).collect();
+	assert_eq!(pool.pool().validated_pool().ready_waiters_count(), 4096);
+
+	assert_eq!(block_on(pool.wait_ready(hash, None)), WaitReadyOutcome::TooManyWaiters);
+	// Transactions which are ready already do not need a waiter.
+	let ready =
+		block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, uxt(Bob, 0))).unwrap();
+	assert_eq!(block_on(pool.wait_ready(ready, None)), WaitReadyOutcome::Ready);
+
+	drop(waiters);
+	assert_eq!(pool.pool().validated_pool().ready_waiters_count(), 0);
+}
+
+#[test]
+fn wait_ready_resolves_when_transaction_leaves_the_pool() {
+	let (pool, api, _guard) = maintained_pool();
+
+	let xt = uxt(Alice, 210);
+	let hash = block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, xt)).unwrap();
+	let wait_ready = pool.wait_ready(hash, None);
+
+	pool.pool().validated_pool().remove_invalid(&[hash]);
+	assert_eq!(block_on(wait_ready), WaitReadyOutcome::AlreadyTerminal(TransactionStatus::Invalid));
+}
+
+#[test]
+fn abandoned_wait_ready_does_not_leak() {
+	let (pool, api, _guard) = maintained_pool();
+
+	let xt = uxt(Alice, 210);
+	let hash = block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, xt)).unwrap();
+
+	let noop_waker = futures::task::noop_waker();
+	let mut context = futures::task::Context::from_waker(&noop_waker);
+	let mut waits = (0..10).map(|_| pool.wait_ready(hash, None)).collect::<Vec<_>>();
+	for wait_ready in &mut waits {
+		assert!(wait_ready.poll_unpin(&mut context).is_pending());
+	}
+	assert_eq!(pool.pool().validated_pool().ready_waiters_count(), 10);
+
+	drop(waits);
+	assert_eq!(pool.pool().validated_pool().ready_waiters_count(), 0);
+}
+
+fn submit_snapshot_transactions(pool: &BasicPool<TestApi, Block>, api: &TestApi) {
+	let at = api.expect_hash_from_number(0);
+	block_on(pool.submit_one(at, SOURCE, uxt(Alice, 209))).unwrap();
+	block_on(pool.submit_one(at, TransactionSource::Local, uxt(Alice, 210))).unwrap();
+	// Bob's transactions wait for a nonce which is never provided.
+	block_on(pool.submit_one(at, SOURCE, uxt(Bob, 2))).unwrap();
+	block_on(pool.submit_one(at, SOURCE, uxt(Bob, 1))).unwrap();
+}
+
+#[test]
+fn exported_state_round_trips_into_fresh_pool() {
+	let (pool, api, _guard) = maintained_pool();
+	submit_snapshot_transactions(&pool, &api);
+
+	let snapshot = pool.export_state();
+	assert_eq!(PoolSnapshot::decode(&mut &snapshot.encode()[..]).unwrap(), snapshot);
+	assert_eq!(
+		snapshot.ready,
+		vec![
+			PoolSnapshotTransaction { source: SOURCE, data: uxt(Alice, 209).encode() },
+			PoolSnapshotTransaction {
+				source: TransactionSource::Local,
+				data: uxt(Alice, 210).encode()
+			},
+		]
+	);
+	assert_eq!(
+		snapshot.future,
+		vec![
+			PoolSnapshotTransaction { source: SOURCE, data: uxt(Bob, 2).encode() },
+			PoolSnapshotTransaction { source: SOURCE, data: uxt(Bob, 1).encode() },
+		]
+	);
+
+	let (fresh, _api, _guard) = maintained_pool();
+	assert_eq!(
+		block_on(fresh.import_state(snapshot.clone())),
+		PoolSnapshotImport { imported: 4, dropped: 0 }
+	);
+
+	let (status, fresh_status) = (pool.status(), fresh.status());
+	assert_eq!(fresh_status.ready, status.ready);
+	assert_eq!(fresh_status.ready_bytes, status.ready_bytes);
+	assert_eq!(fresh_status.future, status.future);
+	assert_eq!(fresh_status.future_bytes, status.future_bytes);
+	assert_eq!(
+		fresh.ready().map(|tx| tx.hash).collect::<Vec<_>>(),
+		pool.ready().map(|tx| tx.hash).collect::<Vec<_>>()
+	);
+	assert_eq!(fresh.export_state(), snapshot);
+
+	// Transactions which are already part of the pool are dropped.
+	assert_eq!(
+		block_on(fresh.import_state(snapshot)),
+		PoolSnapshotImport { imported: 0, dropped: 4 }
+	);
+}
+
+#[test]
+fn importing_state_drops_invalid_transactions() {
+	let (pool, api, _guard) = maintained_pool();
+	submit_snapshot_transactions(&pool, &api);
+	let mut snapshot = pool.export_state();
+	snapshot
+		.future
+		.push(PoolSnapshotTransaction { source: SOURCE, data: vec![1, 2, 3] });
+
+	let (fresh, fresh_api, _guard) = maintained_pool();
+	fresh_api.add_invalid(&uxt(Alice, 210));
+	assert_eq!(
+		block_on(fresh.import_state(snapshot)),
+		PoolSnapshotImport { imported: 3, dropped: 2 }
+	);
+	assert_eq!(fresh.status().ready, 1);
+	assert_eq!(fresh.status().future, 2);
+}
+
 #[test]
 fn import_notification_to_pool_maintain_works() {
 	let mut client = Arc::new(substrate_test_runtime_client::new());
@@ -1028,6 +1668,32 @@ fn import_notification_to_pool_maintain_works() {
 	assert_eq!(pool.status().ready, 0);
 }
 
+#[test]
+fn submit_local_reports_future_nonce_when_rejecting_future_transactions() {
+	let client = Arc::new(substrate_test_runtime_client::new());
+	let best_hash = client.info().best_hash;
+
+	let options = Options { reject_future_transactions: true, ..Default::default() };
+	let (pool, _background_task) = BasicPool::new_test(
+		Arc::new(FullChainApi::new(client.clone(), None, &sp_core::testing::TaskExecutor::new())),
+		best_hash,
+		best_hash,
+		options,
+	);
+
+	// Alice's nonce is 0, so there is a gap before this transaction.
+	let err = pool.submit_local(best_hash, uxt(Alice, 1)).unwrap_err().into_pool_error();
+
+	let alice: AccountId = Alice.into();
+	match err {
+		Ok(TxPoolError::FutureNonce { expected_provides }) =>
+			assert_eq!(expected_provides, vec![(alice, 0 as Nonce).encode()]),
+		e => panic!("Unexpected error: {:?}", e),
+	}
+	assert!(pool.submit_local(best_hash, uxt(Alice, 0)).is_ok());
+	assert_eq!(pool.status().future, 0);
+}
+
 // When we prune transactions, we need to make sure that we remove
 #[test]
 fn pruning_a_transaction_should_remove_it_from_best_transaction() {
@@ -1225,7 +1891,7 @@ fn switching_fork_with_finalized_works() {
 		let mut stream = futures::executor::block_on_stream(from_alice_watcher);
 		assert_eq!(stream.next(), Some(TransactionStatus::Ready));
 		assert_eq!(stream.next(), Some(TransactionStatus::InBlock((b1_header.hash(), 0))));
-		assert_eq!(stream.next(), Some(TransactionStatus::Retracted(b1_header.hash())));
+		assert_eq!(stream.next(), retracted(b1_header.hash(), b2_header.hash(), 1));
 		assert_eq!(stream.next(), Some(TransactionStatus::InBlock((b2_header.hash(), 0))));
 		assert_eq!(stream.next(), Some(TransactionStatus::Finalized((b2_header.hash(), 0))));
 		assert_eq!(stream.next(), None);
@@ -1326,13 +1992,13 @@ fn switching_fork_multiple_times_works() {
 		assert_eq!(stream.next(), Some(TransactionStatus::Ready));
 		assert_eq!(stream.next(), Some(TransactionStatus::InBlock((b1_header.hash(), 0))));
 		//phase-1
-		assert_eq!(stream.next(), Some(TransactionStatus::Retracted(b1_header.hash())));
+		assert_eq!(stream.next(), retracted(b1_header.hash(), b2_header.hash(), 1));
 		assert_eq!(stream.next(), Some(TransactionStatus::InBlock((b2_header.hash(), 0))));
 		//phase-2
-		assert_eq!(stream.next(), Some(TransactionStatus::Retracted(b2_header.hash())));
+		assert_eq!(stream.next(), retracted(b2_header.hash(), b1_header.hash(), 1));
 		assert_eq!(stream.next(), Some(TransactionStatus::InBlock((b1_header.hash(), 0))));
 		//phase-3
-		assert_eq!(stream.next(), Some(TransactionStatus::Retracted(b1_header.hash())));
+		assert_eq!(stream.next(), retracted(b1_header.hash(), b2_header.hash(), 1));
 		assert_eq!(stream.next(), Some(TransactionStatus::InBlock((b2_header.hash(), 0))));
 		assert_eq!(stream.next(), Some(TransactionStatus::Finalized((b2_header.hash(), 0))));
 		assert_eq!(stream.next(), None);
@@ -1344,7 +2010,7 @@ fn switching_fork_multiple_times_works() {
 		assert_eq!(stream.next(), Some(TransactionStatus::Ready));
 		assert_eq!(stream.next(), Some(TransactionStatus::InBlock((b2_header.hash(), 1))));
 		//phase-2
-		assert_eq!(stream.next(), Some(TransactionStatus::Retracted(b2_header.hash())));
+		assert_eq!(stream.next(), retracted(b2_header.hash(), b1_header.hash(), 1));
 		assert_eq!(stream.next(), Some(TransactionStatus::Ready));
 		//phase-3
 		assert_eq!(stream.next(), Some(TransactionStatus::InBlock((b2_header.hash(), 1))));
@@ -1656,3 +2322,382 @@ fn best_block_after_finalization_does_not_retract() {
 		assert_eq!(stream.next(), None);
 	}
 }
+
+fn maintained_pool_with_anchor(
+	validation_anchor: sc_transaction_pool_api::ValidationAnchor,
+) -> (BasicPool<TestApi, Block>, Arc<TestApi>, futures::executor::ThreadPool) {
+	let api = Arc::new(TestApi::with_alice_nonce(209));
+	let genesis_hash = api.expect_hash_from_number(0);
+	let options = Options { validation_anchor, ..Default::default() };
+	let (pool, background_task) =
+		BasicPool::new_test(api.clone(), genesis_hash, genesis_hash, options);
+
+	let thread_pool = futures::executor::ThreadPool::new().unwrap();
+	thread_pool.spawn_ok(background_task);
+	(pool, api, thread_pool)
+}
+
+#[test]
+fn best_block_anchor_accepts_transaction_invalid_on_finalized() {
+	let (pool, api, _guard) =
+		maintained_pool_with_anchor(sc_transaction_pool_api::ValidationAnchor::BestBlock);
+	let xt = uxt(Alice, 209);
+
+	let header = api.push_block(1, vec![], true);
+	block_on(pool.maintain(block_event(header.clone())));
+	// Only valid on the best block.
+	api.add_invalid_at(api.expect_hash_from_number(0), &xt);
+
+	block_on(pool.submit_one(header.hash(), SOURCE, xt)).expect("Valid on best block");
+	assert_eq!(pool.status().ready, 1);
+}
+
+#[test]
+fn finalized_anchor_rejects_transaction_invalid_on_finalized() {
+	let (pool, api, _guard) =
+		maintained_pool_with_anchor(sc_transaction_pool_api::ValidationAnchor::FinalizedBlock);
+	let xt = uxt(Alice, 209);
+
+	let header = api.push_block(1, vec![], true);
+	block_on(pool.maintain(block_event(header.clone())));
+	// Only valid on the best block.
+	api.add_invalid_at(api.expect_hash_from_number(0), &xt);
+
+	assert!(block_on(pool.submit_one(header.hash(), SOURCE, xt.clone())).is_err());
+	assert_eq!(pool.status().ready, 0);
+
+	// Once the best block is finalized, the transaction is accepted.
+	let event = ChainEvent::Finalized { hash: header.hash(), tree_route: Arc::from(vec![]) };
+	block_on(pool.maintain(event));
+	block_on(pool.submit_one(header.hash(), SOURCE, xt)).expect("Valid on finalized block");
+	assert_eq!(pool.status().ready, 1);
+}
+
+#[test]
+fn finalized_anchor_revalidates_on_finality() {
+	let (pool, api, _guard) =
+		maintained_pool_with_anchor(sc_transaction_pool_api::ValidationAnchor::FinalizedBlock);
+	let xt = uxt(Alice, 209);
+
+	let watcher =
+		block_on(pool.submit_and_watch(api.expect_hash_from_number(0), SOURCE, xt.clone()))
+			.expect("Imported");
+	assert_eq!(pool.status().ready, 1);
+
+	let header = api.push_block(1, vec![], true);
+	api.add_invalid_at(header.hash(), &xt);
+	block_on(pool.maintain(block_event(header.clone())));
+
+	let event = ChainEvent::Finalized { hash: header.hash(), tree_route: Arc::from(vec![]) };
+	block_on(pool.maintain(event));
+
+	// The transaction was revalidated against the newly finalized block.
+	assert_eq!(
+		futures::executor::block_on_stream(watcher).collect::<Vec<_>>(),
+		vec![TransactionStatus::Ready, TransactionStatus::Invalid],
+	);
+}
+
+#[test]
+fn watchers_beyond_capacity_only_receive_final_event() {
+	let api = Arc::new(TestApi::with_alice_nonce(209));
+	let genesis_hash = api.expect_hash_from_number(0);
+	let options = Options { max_watchers: 1, ..Default::default() };
+	let (pool, _background_task) =
+		BasicPool::new_test(api.clone(), genesis_hash, genesis_hash, options);
+
+	let (alice, bob) = (uxt(Alice, 209), uxt(Bob, 0));
+	let full = block_on(pool.submit_and_watch(genesis_hash, SOURCE, alice.clone())).unwrap();
+	assert_eq!(pool.watcher_status(), WatcherStatus { watchers: 1, max_watchers: 1 });
+
+	// The transaction is imported nevertheless.
+	let degraded = block_on(pool.submit_and_watch(genesis_hash, SOURCE, bob.clone())).unwrap();
+	assert_eq!(pool.status().ready, 2);
+	assert_eq!(pool.watcher_status(), WatcherStatus { watchers: 1, max_watchers: 1 });
+
+	let header = api.push_block(1, vec![alice, bob], true);
+	block_on(pool.maintain(block_event(header.clone())));
+	let event = ChainEvent::Finalized { hash: header.hash(), tree_route: Arc::from(vec![]) };
+	block_on(pool.maintain(event));
+	let at = header.hash();
+
+	let mut stream = block_on_stream(full);
+	assert_eq!(stream.next(), Some(TransactionStatus::Ready));
+	assert!(matches!(stream.next(), Some(TransactionStatus::InBlock((h, _))) if h == at));
+	assert!(matches!(stream.next(), Some(TransactionStatus::Finalized((h, _))) if h == at));
+	assert_eq!(stream.next(), None);
+
+	let mut stream = block_on_stream(degraded);
+	assert_eq!(stream.next(), Some(TransactionStatus::WatchingDegraded));
+	assert!(matches!(stream.next(), Some(TransactionStatus::Finalized((h, _))) if h == at));
+	assert_eq!(stream.next(), None);
+
+	assert_eq!(pool.watcher_status().watchers, 0);
+}
+
+fn peers(names: &[&str]) -> Vec<String> {
+	names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn broadcast_info_deduplicates_peers() {
+	let (pool, api, _guard) = maintained_pool();
+	let hash =
+		block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, uxt(Alice, 209))).unwrap();
+	assert_eq!(pool.broadcast_info(&hash), None);
+
+	pool.on_broadcasted([(hash, peers(&["a", "b"]))].into_iter().collect());
+	let first = pool.broadcast_info(&hash).unwrap();
+	assert_eq!(first.peers, peers(&["a", "b"]));
+	assert_eq!(first.first_broadcast, first.last_broadcast);
+
+	pool.on_broadcasted([(hash, peers(&["b", "c", "c"]))].into_iter().collect());
+	let second = pool.broadcast_info(&hash).unwrap();
+	assert_eq!(second.peers, peers(&["a", "b", "c"]));
+	assert_eq!(second.first_broadcast, first.first_broadcast);
+	assert!(second.last_broadcast >= first.last_broadcast);
+
+	// The information is gone once the transaction leaves the pool.
+	pool.remove_invalid(&[hash]);
+	assert_eq!(pool.broadcast_info(&hash), None);
+}
+
+#[test]
+fn broadcast_info_is_capped() {
+	let api = Arc::new(TestApi::with_alice_nonce(209));
+	let genesis_hash = api.expect_hash_from_number(0);
+	let options = Options { max_broadcast_peers: 2, ..Default::default() };
+	let (pool, _background_task) =
+		BasicPool::new_test(api.clone(), genesis_hash, genesis_hash, options);
+	let hash = block_on(pool.submit_one(genesis_hash, SOURCE, uxt(Alice, 209))).unwrap();
+
+	pool.on_broadcasted([(hash, peers(&["a"]))].into_iter().collect());
+	pool.on_broadcasted([(hash, peers(&["a", "b", "c"]))].into_iter().collect());
+	assert_eq!(pool.broadcast_info(&hash).unwrap().peers, peers(&["a", "b"]));
+}
+
+#[test]
+fn removing_invalid_reports_cascaded_dependants() {
+	let (pool, api, _guard) = maintained_pool();
+
+	let hashes = (209..=212)
+		.map(|nonce| {
+			block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, uxt(Alice, nonce)))
+				.unwrap()
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(pool.status().ready, 4);
+
+	// invalidating a transaction removes the chain depending on it.
+	let report = pool.remove_invalid_detailed(&[hashes[2]]);
+	let outcomes = report.outcomes;
+	assert_eq!(outcomes.len(), 1);
+	assert_eq!(outcomes[&hashes[2]], InvalidityOutcome::Removed { dependants_removed: 1 });
+	assert_eq!(report.removed.len(), 2);
+	assert_eq!(pool.status().ready, 2);
+
+	// dependants reported after their parent were already removed with it.
+	let unknown = Hash::repeat_byte(0x42);
+	let outcomes = pool.remove_invalid_detailed(&[hashes[0], hashes[1], unknown]).outcomes;
+	assert_eq!(outcomes.len(), 3);
+	assert_eq!(outcomes[&hashes[0]], InvalidityOutcome::Removed { dependants_removed: 1 });
+	assert_eq!(outcomes[&hashes[1]], InvalidityOutcome::NotFound);
+	assert_eq!(outcomes[&unknown], InvalidityOutcome::NotFound);
+	assert_eq!(pool.status().ready, 0);
+
+	// the reported transactions are banned, unlike their dependants.
+	block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, uxt(Alice, 209))).unwrap_err();
+	block_on(pool.submit_one(api.expect_hash_from_number(0), SOURCE, uxt(Alice, 212))).unwrap();
+}
+
+#[test]
+fn min_replacement_priority_allows_to_usurp_stuck_transaction() {
+	let (pool, api, _guard) = maintained_pool();
+	let at = api.expect_hash_from_number(0);
+	let replacement = |amount| {
+		let transfer = Transfer { from: Alice.into(), to: Bob.into(), nonce: 209, amount };
+		ExtrinsicBuilder::new_transfer(transfer).build()
+	};
+
+	let stuck = uxt(Alice, 209);
+	api.set_priority(&stuck, 10);
+	let watcher = block_on(pool.submit_and_watch(at, SOURCE, stuck)).unwrap();
+	let tag = pool.ready().next().unwrap().provides()[0].clone();
+	assert_eq!(pool.min_replacement_priority(&b"unknown".to_vec()), None);
+	let min_priority = pool.min_replacement_priority(&tag).unwrap();
+	assert_eq!(min_priority, 10);
+
+	// matching the returned priority is not enough.
+	let too_low = replacement(2);
+	api.set_priority(&too_low, min_priority);
+	let error = block_on(pool.submit_one(at, SOURCE, too_low)).unwrap_err();
+	assert!(matches!(
+		error.into_pool_error(),
+		Ok(TxPoolError::TooLowPriority { old: 10, new: 10 })
+	));
+
+	// outbidding it replaces the stuck transaction.
+	let usurper = replacement(3);
+	api.set_priority(&usurper, min_priority + 1);
+	let usurper_hash = block_on(pool.submit_one(at, SOURCE, usurper)).unwrap();
+	assert_eq!(pool.status().ready, 1);
+	assert_eq!(pool.min_replacement_priority(&tag), Some(11));
+
+	let mut stream = block_on_stream(watcher);
+	assert_eq!(stream.next(), Some(TransactionStatus::Ready));
+	assert_eq!(stream.next(), Some(TransactionStatus::Usurped(usurper_hash)));
+}
+
+/// Classifies transfers as operational and everything else as normal.
+struct TransfersAreOperational;
+
+impl TransactionClassifier<Block> for TransfersAreOperational {
+	fn classify(&self, xt: &Extrinsic, _validity: &ValidTransaction) -> PoolClass {
+		if TransferData::try_from(xt).is_ok() {
+			PoolClass::Operational
+		} else {
+			PoolClass::Normal
+		}
+	}
+}
+
+#[test]
+fn operational_transactions_survive_flood_of_normal_transactions() {
+	let api = Arc::new(TestApi::with_alice_nonce(209));
+	let (pool, _background) = create_basic_pool_with_genesis(api.clone());
+	pool.set_classifier(Arc::new(TransfersAreOperational));
+	pool.set_class_limits(PoolClass::Normal, 3, usize::MAX);
+	let at = api.expect_hash_from_number(0);
+
+	let operational = uxt(Alice, 209);
+	api.set_priority(&operational, 1);
+	let operational_hash = block_on(pool.submit_one(at, SOURCE, operational)).unwrap();
+
+	// Every normal transaction outbids the operational one and the ones before it.
+	let flood = (0..10u8)
+		.map(|i| {
+			let xt = ExtrinsicBuilder::new_include_data(vec![i]).build();
+			api.set_priority(&xt, 100 + i as u64);
+			xt
+		})
+		.collect::<Vec<_>>();
+	for xt in &flood {
+		block_on(pool.submit_one(at, SOURCE, xt.clone())).unwrap();
+	}
+
+	// Only the normal class exceeded its limits, so its worst transactions were evicted.
+	assert_eq!(pool.status().ready, 4);
+	assert!(pool.ready_transaction(&operational_hash).is_some());
+	let flood = flood.iter().map(|xt| pool.hash_of(xt)).collect::<Vec<_>>();
+	let remaining = flood
+		.iter()
+		.filter(|hash| pool.ready_transaction(hash).is_some())
+		.copied()
+		.collect::<Vec<_>>();
+	assert_eq!(remaining, flood[7..].to_vec());
+}
+
+#[test]
+fn unclassified_transactions_are_normal_and_limited_together() {
+	let api = Arc::new(TestApi::empty());
+	let (pool, _background) = create_basic_pool_with_genesis(api.clone());
+	pool.set_class_limits(PoolClass::Operational, 1, usize::MAX);
+	pool.set_class_limits(PoolClass::Normal, 2, usize::MAX);
+
+	let xts = (0..3u8)
+		.map(|i| ExtrinsicBuilder::new_include_data(vec![i]).build())
+		.collect::<Vec<_>>();
+	let results = block_on(pool.submit_at(api.expect_hash_from_number(0), SOURCE, xts)).unwrap();
+
+	// Without a classifier every transaction is normal, so the operational limits are unused.
+	assert_eq!(pool.status().ready, 2);
+	assert_eq!(results.iter().filter(|result| result.is_err()).count(), 1);
+}
+
+#[test]
+fn transactions_are_rehashed_when_runtime_changes_hashing() {
+	let api = Arc::new(TestApi::with_alice_nonce(209));
+	let genesis_hash = api.expect_hash_from_number(0);
+	let (pool, _background_task) =
+		BasicPool::new_test(api.clone(), genesis_hash, genesis_hash, Default::default());
+	api.upgrade_hashing_at(2);
+
+	let (xt, dependant) = (uxt(Alice, 209), uxt(Alice, 210));
+	let watcher = block_on(pool.submit_and_watch(genesis_hash, SOURCE, xt.clone())).unwrap();
+	block_on(pool.submit_one(genesis_hash, SOURCE, dependant.clone())).unwrap();
+	let old_hash = pool.hash_of(&xt);
+	let mut import_stream = pool.import_notification_stream();
+
+	// transactions keep their hash while the runtime does not change.
+	let header = api.push_block(1, vec![], true);
+	block_on(pool.maintain(block_event(header)));
+	assert_eq!(pool.hash_of(&xt), old_hash);
+	assert!(pool.ready_transaction(&old_hash).is_some());
+
+	let header = api.push_block(2, vec![], true);
+	let upgraded = header.hash();
+	block_on(pool.maintain(block_event(header)));
+
+	// the transactions are re-hashed and announced again under their new hash.
+	let new_hash = pool.hash_of_at(&xt, upgraded);
+	assert_ne!(new_hash, old_hash);
+	assert_eq!(pool.hash_of(&xt), new_hash);
+	assert!(pool.ready_transaction(&old_hash).is_none());
+	assert!(pool.ready_transaction(&new_hash).is_some());
+	assert_eq!(pool.status().ready, 2);
+	let announced = vec![block_on(import_stream.next()), block_on(import_stream.next())];
+	assert!(announced.contains(&Some(new_hash)));
+	assert!(announced.contains(&Some(pool.hash_of(&dependant))));
+
+	// the old watcher learns about the new hash.
+	let mut stream = block_on_stream(watcher);
+	assert_eq!(stream.next(), Some(TransactionStatus::Ready));
+	assert_eq!(stream.next(), Some(TransactionStatus::Usurped(new_hash)));
+	assert_eq!(stream.next(), None);
+
+	// inclusion is recognized under the new hash.
+	let header = api.push_block(3, vec![xt, dependant], true);
+	block_on(pool.maintain(block_event(header)));
+	assert_eq!(pool.status().ready, 0);
+}
+
+fn submit_ready_transactions(
+	pool: &BasicPool<TestApi, Block>,
+	api: &TestApi,
+	count: Nonce,
+) -> Vec<Hash> {
+	let genesis_hash = api.expect_hash_from_number(0);
+	(209..209 + count)
+		.map(|nonce| block_on(pool.submit_one(genesis_hash, SOURCE, uxt(Alice, nonce))).unwrap())
+		.collect()
+}
+
+#[test]
+fn import_notifications_overflowing_the_stream_are_collected() {
+	let (pool, api, _guard) = maintained_pool();
+	let (mut stream, overflow) = pool.import_notification_stream_with_overflow(Some(1));
+
+	let hashes = submit_ready_transactions(&pool, &api, 10);
+
+	let mut received = Vec::new();
+	while let Ok(Some(hash)) = stream.try_next() {
+		received.push(hash);
+	}
+	assert_eq!(received, vec![hashes[0]]);
+	assert_eq!(overflow.len(), 9);
+	assert_eq!(overflow.take(), hashes[1..].iter().cloned().collect());
+	assert!(overflow.is_empty());
+	assert_eq!(pool.dropped_import_notifications(), 0);
+}
+
+#[test]
+fn import_notifications_overflowing_the_stream_are_counted() {
+	let (pool, api, _guard) = maintained_pool();
+	let mut stream = pool.import_notification_stream_with_buffer(1);
+	let _default_stream = pool.import_notification_stream();
+
+	let hashes = submit_ready_transactions(&pool, &api, 10);
+
+	assert_eq!(block_on(stream.next()), Some(hashes[0]));
+	assert_eq!(pool.dropped_import_notifications(), 9);
+}
@@ -1728,7 +1728,10 @@ fn multiple_transfer_alls_work_ok() {
 		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
 		// Spend the same balance multiple times
 		assert_ok!(Balances::transfer_all(RuntimeOrigin::signed(1), 1337, false));
-		assert_ok!(Balances::transfer_all(RuntimeOrigin::signed(1), 1337, false));
+		assert_noop!(
+			Balances::transfer_all(RuntimeOrigin::signed(1), 1337, false),
+			pallet_balances::Error::<Test>::NothingToTransfer,
+		);
 
 		assert_eq!(Balances::free_balance(&1), 0);
 		assert_eq!(Balances::free_balance(&1337), 100);
@@ -23,14 +23,24 @@ use super::*;
 use crate::Pallet as Balances;
 
 use frame_benchmarking::v2::*;
+use frame_support::traits::{LockableCurrency, NamedReservableCurrency, WithdrawReasons};
 use frame_system::RawOrigin;
-use sp_runtime::traits::Bounded;
+use sp_runtime::traits::{Bounded, TrailingZeroInput};
 use types::ExtraFlags;
 
 const SEED: u32 = 0;
 // existential deposit multiplier
 const ED_MULTIPLIER: u32 = 10;
 
+/// A free balance large enough that a benchmarked transfer never kills the account.
+///
+/// This is a quarter of the range of `T::Balance` rather than `max_value()`, so that the total
+/// issuance keeps headroom for the other accounts endowed by the benchmark, even when the
+/// balance type is as small as `u32`.
+fn seed_balance<T: Config<I>, I: 'static>() -> T::Balance {
+	T::Balance::max_value() / 4u32.into()
+}
+
 #[instance_benchmarks]
 mod benchmarks {
 	use super::*;
@@ -69,10 +79,10 @@ mod benchmarks {
 		let recipient: T::AccountId = account("recipient", 0, SEED);
 		let recipient_lookup = T::Lookup::unlookup(recipient.clone());
 
-		// Give the sender account max funds for transfer (their account will never reasonably be
-		// killed).
+		// Give the sender account plenty of funds for transfer (their account will never reasonably
+		// be killed).
 		let _ =
-			<Balances<T, I> as Currency<_>>::make_free_balance_be(&caller, T::Balance::max_value());
+			<Balances<T, I> as Currency<_>>::make_free_balance_be(&caller, seed_balance::<T, I>());
 
 		// Give the recipient account existential deposit (thus their account already exists).
 		let existential_deposit = T::ExistentialDeposit::get();
@@ -95,9 +105,9 @@ mod benchmarks {
 		let recipient: T::AccountId = account("recipient", 0, SEED);
 		let recipient_lookup = T::Lookup::unlookup(recipient.clone());
 
-		// Give the sender account max funds, thus a transfer will not kill account.
+		// Give the sender account plenty of funds, thus a transfer will not kill account.
 		let _ =
-			<Balances<T, I> as Currency<_>>::make_free_balance_be(&caller, T::Balance::max_value());
+			<Balances<T, I> as Currency<_>>::make_free_balance_be(&caller, seed_balance::<T, I>());
 		let existential_deposit = T::ExistentialDeposit::get();
 		let transfer_amount = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
 
@@ -286,6 +296,41 @@ mod benchmarks {
 		}
 	}
 
+	/// Benchmark `upgrade_accounts` with accounts which are already upgraded, so that only the
+	/// cost of checking them is measured.
+	#[benchmark]
+	fn upgrade_accounts_skipped(u: Linear<1, 1_000>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let who = (0..u)
+			.into_iter()
+			.map(|i| -> T::AccountId {
+				let user = account("new_user", i, SEED);
+				let account = AccountData {
+					free: T::ExistentialDeposit::get(),
+					reserved: T::ExistentialDeposit::get(),
+					frozen: Zero::zero(),
+					flags: ExtraFlags::default(),
+				};
+				frame_system::Pallet::<T>::inc_providers(&user);
+				assert!(T::AccountStore::try_mutate_exists(&user, |a| -> DispatchResult {
+					*a = Some(account);
+					Ok(())
+				})
+				.is_ok());
+				assert!(Balances::<T, I>::account(&user).flags.is_new_logic());
+				user
+			})
+			.collect();
+
+		#[extrinsic_call]
+		upgrade_accounts(RawOrigin::Signed(caller.clone()), who);
+
+		for i in 0..u {
+			let user: T::AccountId = account("new_user", i, SEED);
+			assert!(Balances::<T, I>::account(&user).flags.is_new_logic());
+		}
+	}
+
 	#[benchmark]
 	fn force_adjust_total_issuance() {
 		let ti = Balances::<T, I>::total_issuance();
@@ -335,6 +380,345 @@ mod benchmarks {
 		assert_eq!(Balances::<T, I>::free_balance(&caller), balance - burn_amount);
 	}
 
+	/// Benchmark `burn_with_hold` burning the whole balance on hold, which removes the hold.
+	///
+	/// Skipped if the hold reason used is not in `BurnableHolds`, since the call always fails
+	/// then.
+	#[benchmark]
+	fn burn_held() -> Result<(), BenchmarkError> {
+		let reason =
+			<T::RuntimeHoldReason as codec::Decode>::decode(&mut TrailingZeroInput::zeroes())
+				.map_err(|_| BenchmarkError::Stop("the runtime has no hold reason"))?;
+		if !<T::BurnableHolds as frame_support::traits::Contains<_>>::contains(&reason) {
+			return Err(BenchmarkError::Skip)
+		}
+		let existential_deposit = T::ExistentialDeposit::get();
+		let caller = whitelisted_caller();
+
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T, I> as Currency<_>>::make_free_balance_be(&caller, balance);
+		let held = balance - existential_deposit;
+		<Balances<T, I> as fungible::MutateHold<_>>::hold(&reason, &caller, held)?;
+
+		#[extrinsic_call]
+		burn_with_hold(RawOrigin::Signed(caller.clone()), held, true, Some(reason));
+
+		assert!(Holds::<T, I>::get(&caller).is_empty());
+		assert_eq!(Balances::<T, I>::free_balance(&caller), existential_deposit);
+		Ok(())
+	}
+
+	/// Benchmark `force_reconcile_total_issuance` with `a` funded accounts, walking all accounts
+	/// in a single call so that the total issuance gets updated.
+	#[benchmark]
+	fn force_reconcile_total_issuance(a: Linear<0, 1_000>) {
+		let existential_deposit = T::ExistentialDeposit::get();
+		for i in 0..a {
+			let user: T::AccountId = account("user", i, SEED);
+			let _ =
+				<Balances<T, I> as Currency<_>>::make_free_balance_be(&user, existential_deposit);
+		}
+		let expected = frame_system::Account::<T>::iter_keys()
+			.map(|who| Balances::<T, I>::account(&who).total())
+			.fold(T::Balance::zero(), |sum, balance| sum + balance);
+		let max_accounts = frame_system::Account::<T>::iter_keys().count() as u32 + 1;
+		TotalIssuance::<T, I>::set(expected + existential_deposit);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, max_accounts);
+
+		assert_eq!(Balances::<T, I>::total_issuance(), expected);
+		assert!(IssuanceReconciliationProgress::<T, I>::get().is_none());
+	}
+
+	/// Benchmark `repair_account` on an account whose `frozen` balance does not reflect its lock.
+	#[benchmark]
+	fn repair_account() {
+		let existential_deposit = T::ExistentialDeposit::get();
+		let caller = whitelisted_caller();
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup = T::Lookup::unlookup(user.clone());
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T, I> as Currency<_>>::make_free_balance_be(&user, balance);
+		<Balances<T, I> as LockableCurrency<_>>::set_lock(
+			*b"repair  ",
+			&user,
+			existential_deposit,
+			WithdrawReasons::all(),
+		);
+		let _ = Balances::<T, I>::mutate_account(&user, |a| a.frozen = Zero::zero());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), user_lookup);
+
+		assert_eq!(Balances::<T, I>::account(&user).frozen, existential_deposit);
+	}
+
+	#[benchmark]
+	fn force_set_inactive_issuance() {
+		let user: T::AccountId = account("user", 0, SEED);
+		let balance = T::ExistentialDeposit::get().saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T, I> as Currency<_>>::make_free_balance_be(&user, balance);
+		let ti = Balances::<T, I>::total_issuance();
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, ti);
+
+		assert_eq!(Balances::<T, I>::inactive_issuance(), ti);
+	}
+
+	#[benchmark]
+	fn force_adjust_inactive_issuance() {
+		let user: T::AccountId = account("user", 0, SEED);
+		let balance = T::ExistentialDeposit::get().saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T, I> as Currency<_>>::make_free_balance_be(&user, balance);
+		let inactive = Balances::<T, I>::inactive_issuance();
+		let delta = T::ExistentialDeposit::get();
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, AdjustmentDirection::Increase, delta);
+
+		assert_eq!(Balances::<T, I>::inactive_issuance(), inactive + delta);
+	}
+
+	/// Benchmark `set_auto_upgrade` enabling the upgrade while an iteration is in progress.
+	#[benchmark]
+	fn set_auto_upgrade() {
+		let user: T::AccountId = account("user", 0, SEED);
+		AutoUpgradeCursor::<T, I>::put(&user);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, true);
+
+		assert!(AutoUpgradeEnabled::<T, I>::get());
+		assert!(AutoUpgradeCursor::<T, I>::get().is_none());
+	}
+
+	/// Benchmark `purge_ghost_accounts` visiting `l` ghost entries which are all purged.
+	#[benchmark]
+	fn purge_ghost_accounts(l: Linear<0, 1_000>) {
+		let existential_deposit = T::ExistentialDeposit::get();
+		let authoritative = T::AuthoritativeStore::get();
+		for i in 0..l {
+			let user: T::AccountId = account("user", i, SEED);
+			let _ =
+				<Balances<T, I> as Currency<_>>::make_free_balance_be(&user, existential_deposit);
+			// Duplicate the data of the authoritative store, which makes the entry purgeable.
+			let data = Balances::<T, I>::account(&user);
+			match authoritative {
+				AccountStoreKind::System => Account::<T, I>::insert(&user, data),
+				AccountStoreKind::Balances => {
+					let info = system::Account::<T>::get(&user);
+					let ghost = system::AccountInfo {
+						nonce: info.nonce,
+						consumers: info.consumers,
+						providers: info.providers,
+						sufficients: info.sufficients,
+						data,
+					};
+					unhashed::put(&system::Account::<T>::hashed_key_for(&user), &ghost);
+				},
+			}
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, l, None);
+
+		if authoritative == AccountStoreKind::System {
+			assert_eq!(Account::<T, I>::iter_keys().count(), 0);
+		}
+	}
+
+	#[benchmark]
+	fn add_silent_account() {
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup = T::Lookup::unlookup(user.clone());
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, user_lookup);
+
+		assert!(SilentAccounts::<T, I>::contains_key(&user));
+	}
+
+	#[benchmark]
+	fn remove_silent_account() {
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup = T::Lookup::unlookup(user.clone());
+		SilentAccounts::<T, I>::insert(&user, ());
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, user_lookup);
+
+		assert!(!SilentAccounts::<T, I>::contains_key(&user));
+	}
+
+	#[benchmark]
+	fn restrict_account() {
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup = T::Lookup::unlookup(user.clone());
+		let balance = T::ExistentialDeposit::get().saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T, I> as Currency<_>>::make_free_balance_be(&user, balance);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, user_lookup);
+
+		assert!(Balances::<T, I>::account(&user).flags.is_restricted());
+	}
+
+	#[benchmark]
+	fn unrestrict_account() {
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup = T::Lookup::unlookup(user.clone());
+		let balance = T::ExistentialDeposit::get().saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T, I> as Currency<_>>::make_free_balance_be(&user, balance);
+		let _ = Balances::<T, I>::mutate_account(&user, |a| a.flags.set_restricted(true));
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, user_lookup);
+
+		assert!(!Balances::<T, I>::account(&user).flags.is_restricted());
+	}
+
+	/// Benchmark `force_set_freeze` setting a new freeze which expires.
+	#[benchmark]
+	fn force_set_freeze() -> Result<(), BenchmarkError> {
+		let id = <T::FreezeIdentifier as codec::Decode>::decode(&mut TrailingZeroInput::zeroes())
+			.map_err(|_| BenchmarkError::Stop("the runtime has no freeze identifier"))?;
+		let existential_deposit = T::ExistentialDeposit::get();
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup = T::Lookup::unlookup(user.clone());
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T, I> as Currency<_>>::make_free_balance_be(&user, balance);
+		let expires = frame_system::Pallet::<T>::block_number() + 10u32.into();
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, user_lookup, id, existential_deposit, Some(expires));
+
+		assert_eq!(Balances::<T, I>::account(&user).frozen, existential_deposit);
+		assert_eq!(FreezeExpiry::<T, I>::get(&user, &id), Some(expires));
+		Ok(())
+	}
+
+	/// Benchmark `force_migrate_account` moving an account with a hold, a named reserve, a lock
+	/// and a single expiring freeze onto a new account.
+	#[benchmark]
+	fn force_migrate_account() -> Result<(), BenchmarkError> {
+		let freeze_id =
+			<T::FreezeIdentifier as codec::Decode>::decode(&mut TrailingZeroInput::zeroes())
+				.map_err(|_| BenchmarkError::Stop("the runtime has no freeze identifier"))?;
+		let hold_reason =
+			<T::RuntimeHoldReason as codec::Decode>::decode(&mut TrailingZeroInput::zeroes())
+				.map_err(|_| BenchmarkError::Stop("the runtime has no hold reason"))?;
+		let reserve_id =
+			<T::ReserveIdentifier as codec::Decode>::decode(&mut TrailingZeroInput::zeroes())
+				.map_err(|_| BenchmarkError::Stop("the runtime has no reserve identifier"))?;
+		let existential_deposit = T::ExistentialDeposit::get();
+		let from: T::AccountId = account("from", 0, SEED);
+		let from_lookup = T::Lookup::unlookup(from.clone());
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T, I> as Currency<_>>::make_free_balance_be(&from, balance);
+
+		<Balances<T, I> as fungible::MutateHold<_>>::hold(
+			&hold_reason,
+			&from,
+			existential_deposit,
+		)?;
+		<Balances<T, I> as NamedReservableCurrency<_>>::reserve_named(
+			&reserve_id,
+			&from,
+			existential_deposit,
+		)?;
+		<Balances<T, I> as LockableCurrency<_>>::set_lock(
+			*b"bench   ",
+			&from,
+			existential_deposit,
+			WithdrawReasons::all(),
+		);
+		let expires = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		Balances::<T, I>::force_set_freeze(
+			RawOrigin::Root.into(),
+			from_lookup.clone(),
+			freeze_id,
+			existential_deposit,
+			Some(expires),
+		)?;
+		SilentAccounts::<T, I>::insert(&from, ());
+		let data = Balances::<T, I>::account(&from);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, from_lookup, to_lookup);
+
+		assert_eq!(Balances::<T, I>::account(&to), data);
+		assert_eq!(FreezeExpiry::<T, I>::get(&to, &freeze_id), Some(expires));
+		assert!(SilentAccounts::<T, I>::contains_key(&to));
+		Ok(())
+	}
+
+	/// Benchmark `deprecate_lock` turning a lock into a freeze the account does not have yet.
+	///
+	/// Skipped if the runtime maps no lock to a freeze, since the call always fails then.
+	#[benchmark]
+	fn deprecate_lock() -> Result<(), BenchmarkError> {
+		let id: LockIdentifier = [0; 8];
+		let freeze = T::LockToFreezeMapping::convert(id).ok_or(BenchmarkError::Skip)?;
+		let existential_deposit = T::ExistentialDeposit::get();
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_lookup = T::Lookup::unlookup(user.clone());
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T, I> as Currency<_>>::make_free_balance_be(&user, balance);
+		<Balances<T, I> as LockableCurrency<_>>::set_lock(
+			id,
+			&user,
+			existential_deposit,
+			WithdrawReasons::all(),
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, user_lookup, id);
+
+		assert!(Balances::<T, I>::locks(&user).is_empty());
+		assert_eq!(
+			<Balances<T, I> as fungible::InspectFreeze<_>>::balance_frozen(&freeze, &user),
+			existential_deposit
+		);
+		Ok(())
+	}
+
+	/// Benchmark `prepare_account_closure` removing a single expiring freeze.
+	///
+	/// Skipped if the freeze used is not in `ManagedFreezes`, since the call always fails then.
+	#[benchmark]
+	fn prepare_account_closure() -> Result<(), BenchmarkError> {
+		let freeze_id =
+			<T::FreezeIdentifier as codec::Decode>::decode(&mut TrailingZeroInput::zeroes())
+				.map_err(|_| BenchmarkError::Stop("the runtime has no freeze identifier"))?;
+		if !<T::ManagedFreezes as frame_support::traits::Contains<_>>::contains(&freeze_id) {
+			return Err(BenchmarkError::Skip)
+		}
+		let existential_deposit = T::ExistentialDeposit::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let balance = existential_deposit.saturating_mul(ED_MULTIPLIER.into());
+		let _ = <Balances<T, I> as Currency<_>>::make_free_balance_be(&caller, balance);
+		let expires = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		Balances::<T, I>::force_set_freeze(
+			RawOrigin::Root.into(),
+			caller_lookup,
+			freeze_id,
+			existential_deposit,
+			Some(expires),
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert!(Freezes::<T, I>::get(&caller).is_empty());
+		assert_eq!(FreezeExpiry::<T, I>::get(&caller, &freeze_id), None);
+		Ok(())
+	}
+
 	impl_benchmark_test_suite! {
 		Balances,
 		crate::tests::ExtBuilder::default().build(),
@@ -0,0 +1,148 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion of balances between instances of this pallet with different decimals.
+
+use crate::{Config, CreditOf, Pallet};
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::marker::PhantomData;
+use frame_support::{
+	storage::with_storage_layer,
+	traits::{
+		fungible::{Balanced, Mutate},
+		tokens::{Fortitude, Precision, Preservation},
+		Get, Imbalance, OnUnbalanced,
+	},
+};
+use scale_info::TypeInfo;
+use sp_runtime::{ArithmeticError, DispatchError, RuntimeDebug};
+
+type BalanceOf<T, I> = <T as Config<I>>::Balance;
+
+/// An amount converted by [`DecimalsConversion::convert`].
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct ConvertedAmount<FromBalance, ToBalance> {
+	/// The converted amount, in units of the target instance.
+	pub value: ToBalance,
+	/// The part of the amount which is below the precision of the target instance, in units of
+	/// the source instance.
+	pub remainder: FromBalance,
+}
+
+/// The error of converting an amount between instances.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum ConversionError {
+	/// The converted amount does not fit into the balance type of the target instance.
+	Overflow,
+}
+
+impl From<ConversionError> for DispatchError {
+	fn from(e: ConversionError) -> Self {
+		match e {
+			ConversionError::Overflow => ArithmeticError::Overflow.into(),
+		}
+	}
+}
+
+/// Converts balances of instance `FromInstance`, which uses `FromDecimals` decimals, into
+/// balances of instance `ToInstance`, which uses `ToDecimals` decimals.
+///
+/// Amounts are never rounded silently: the part of an amount below the precision of the target
+/// instance is returned as [`ConvertedAmount::remainder`]. When transferring, it is handed to
+/// `Residue`, which burns it by default.
+pub struct DecimalsConversion<T, FromInstance, ToInstance, FromDecimals, ToDecimals, Residue = ()>(
+	PhantomData<(T, FromInstance, ToInstance, FromDecimals, ToDecimals, Residue)>,
+);
+
+impl<T, FromInstance, ToInstance, FromDecimals, ToDecimals, Residue>
+	DecimalsConversion<T, FromInstance, ToInstance, FromDecimals, ToDecimals, Residue>
+where
+	T: Config<FromInstance> + Config<ToInstance>,
+	FromInstance: 'static,
+	ToInstance: 'static,
+	FromDecimals: Get<u8>,
+	ToDecimals: Get<u8>,
+	Residue: OnUnbalanced<CreditOf<T, FromInstance>>,
+{
+	/// Convert `amount` into units of `ToInstance`.
+	pub fn convert(
+		amount: BalanceOf<T, FromInstance>,
+	) -> Result<
+		ConvertedAmount<BalanceOf<T, FromInstance>, BalanceOf<T, ToInstance>>,
+		ConversionError,
+	> {
+		let (from, to) = (FromDecimals::get(), ToDecimals::get());
+		let amount: u128 = amount.try_into().map_err(|_| ConversionError::Overflow)?;
+		let (value, remainder) = if to >= from {
+			let factor = 10u128.checked_pow((to - from).into()).ok_or(ConversionError::Overflow)?;
+			(amount.checked_mul(factor).ok_or(ConversionError::Overflow)?, 0)
+		} else {
+			match 10u128.checked_pow((from - to).into()) {
+				Some(factor) => (amount / factor, amount % factor),
+				// Every representable amount is below the precision of the target instance.
+				None => (0, amount),
+			}
+		};
+
+		Ok(ConvertedAmount {
+			value: value.try_into().map_err(|_| ConversionError::Overflow)?,
+			remainder: remainder.try_into().map_err(|_| ConversionError::Overflow)?,
+		})
+	}
+
+	/// Convert `amount` of `ToInstance` back into units of `FromInstance`.
+	pub fn convert_back(
+		amount: BalanceOf<T, ToInstance>,
+	) -> Result<
+		ConvertedAmount<BalanceOf<T, ToInstance>, BalanceOf<T, FromInstance>>,
+		ConversionError,
+	> {
+		DecimalsConversion::<T, ToInstance, FromInstance, ToDecimals, FromDecimals, ()>::convert(
+			amount,
+		)
+	}
+
+	/// Burn `amount` from `source` in `FromInstance` and mint the converted amount into `dest` in
+	/// `ToInstance`.
+	///
+	/// The remainder is withdrawn from `source` as well and handed to `Residue`. Either both
+	/// sides of the transfer happen or none.
+	pub fn convert_and_transfer(
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: BalanceOf<T, FromInstance>,
+		preservation: Preservation,
+	) -> Result<ConvertedAmount<BalanceOf<T, FromInstance>, BalanceOf<T, ToInstance>>, DispatchError>
+	{
+		with_storage_layer(|| {
+			let converted = Self::convert(amount)?;
+			let credit = <Pallet<T, FromInstance> as Balanced<_>>::withdraw(
+				source,
+				amount,
+				Precision::Exact,
+				preservation,
+				Fortitude::Polite,
+			)?;
+			let (residue, burned) = credit.split(converted.remainder);
+			Residue::on_unbalanced(residue);
+			// Dropping the credit reduces the total issuance of `FromInstance`.
+			drop(burned);
+			<Pallet<T, ToInstance> as Mutate<_>>::mint_into(dest, converted.value)?;
+			Ok(converted)
+		})
+	}
+}
@@ -38,7 +38,10 @@ pub use imbalances::{NegativeImbalance, PositiveImbalance};
 // wrapping these imbalances in a private module is necessary to ensure absolute privacy
 // of the inner member.
 mod imbalances {
-	use super::{result, Config, Imbalance, RuntimeDebug, Saturating, TryDrop, Zero};
+	use super::{
+		result, AdjustmentDirection, Config, Imbalance, IssuanceAuditOperation, Pallet,
+		RuntimeDebug, Saturating, TryDrop, Zero,
+	};
 	use frame_support::traits::SameOrOther;
 	use sp_std::mem;
 
@@ -51,6 +54,12 @@ mod imbalances {
 	impl<T: Config<I>, I: 'static> PositiveImbalance<T, I> {
 		/// Create a new positive imbalance from a balance.
 		pub fn new(amount: T::Balance) -> Self {
+			Pallet::<T, I>::audit_issuance(
+				IssuanceAuditOperation::PositiveImbalance,
+				None,
+				AdjustmentDirection::Increase,
+				amount,
+			);
 			PositiveImbalance(amount)
 		}
 	}
@@ -64,6 +73,12 @@ mod imbalances {
 	impl<T: Config<I>, I: 'static> NegativeImbalance<T, I> {
 		/// Create a new negative imbalance from a balance.
 		pub fn new(amount: T::Balance) -> Self {
+			Pallet::<T, I>::audit_issuance(
+				IssuanceAuditOperation::NegativeImbalance,
+				None,
+				AdjustmentDirection::Decrease,
+				amount,
+			);
 			NegativeImbalance(amount)
 		}
 	}
@@ -122,7 +137,7 @@ mod imbalances {
 			if a > b {
 				SameOrOther::Same(Self(a - b))
 			} else if b > a {
-				SameOrOther::Other(NegativeImbalance::new(b - a))
+				SameOrOther::Other(NegativeImbalance(b - a))
 			} else {
 				SameOrOther::None
 			}
@@ -186,7 +201,7 @@ mod imbalances {
 			if a > b {
 				SameOrOther::Same(Self(a - b))
 			} else if b > a {
-				SameOrOther::Other(PositiveImbalance::new(b - a))
+				SameOrOther::Other(PositiveImbalance(b - a))
 			} else {
 				SameOrOther::None
 			}
@@ -263,6 +278,12 @@ where
 				Zero::zero()
 			});
 		});
+		Self::audit_issuance(
+			IssuanceAuditOperation::Burn,
+			None,
+			AdjustmentDirection::Decrease,
+			amount,
+		);
 		PositiveImbalance::new(amount)
 	}
 
@@ -279,6 +300,12 @@ where
 				Self::Balance::max_value()
 			})
 		});
+		Self::audit_issuance(
+			IssuanceAuditOperation::Issue,
+			None,
+			AdjustmentDirection::Increase,
+			amount,
+		);
 		NegativeImbalance::new(amount)
 	}
 
@@ -298,6 +325,7 @@ where
 		if amount.is_zero() {
 			return Ok(())
 		}
+		Self::ensure_unrestricted(who)?;
 		ensure!(new_balance >= Self::account(who).frozen, Error::<T, I>::LiquidityRestrictions);
 		Ok(())
 	}
@@ -380,7 +408,11 @@ where
 			|account, is_new| -> Result<Self::PositiveImbalance, DispatchError> {
 				ensure!(!is_new, Error::<T, I>::DeadAccount);
 				account.free = account.free.checked_add(&value).ok_or(ArithmeticError::Overflow)?;
-				Self::deposit_event(Event::Deposit { who: who.clone(), amount: value });
+				Self::deposit_event(Event::Deposit {
+					who: who.clone(),
+					amount: value,
+					context: current_context(),
+				});
 				Ok(PositiveImbalance::new(value))
 			},
 		)
@@ -413,7 +445,11 @@ where
 					None => return Ok(Self::PositiveImbalance::zero()),
 				};
 
-				Self::deposit_event(Event::Deposit { who: who.clone(), amount: value });
+				Self::deposit_event(Event::Deposit {
+					who: who.clone(),
+					amount: value,
+					context: current_context(),
+				});
 				Ok(PositiveImbalance::new(value))
 			},
 		)
@@ -449,7 +485,11 @@ where
 
 				account.free = new_free_account;
 
-				Self::deposit_event(Event::Withdraw { who: who.clone(), amount: value });
+				Self::deposit_event(Event::Withdraw {
+					who: who.clone(),
+					amount: value,
+					context: current_context(),
+				});
 				Ok(NegativeImbalance::new(value))
 			},
 		)
@@ -561,7 +601,9 @@ where
 			},
 		};
 
-		Self::deposit_event(Event::Unreserved { who: who.clone(), amount: actual });
+		if !actual.is_zero() {
+			Self::deposit_event(Event::Unreserved { who: who.clone(), amount: actual });
+		}
 		value - actual
 	}
 
@@ -19,8 +19,7 @@
 use super::*;
 use frame_support::traits::{
 	tokens::{
-		Fortitude,
-		Preservation::{self, Preserve, Protect},
+		Fortitude, Precision, Preservation,
 		Provenance::{self, Minted},
 	},
 	AccountTouch,
@@ -49,27 +48,7 @@ impl<T: Config<I>, I: 'static> fungible::Inspect<T::AccountId> for Pallet<T, I>
 		preservation: Preservation,
 		force: Fortitude,
 	) -> Self::Balance {
-		let a = Self::account(who);
-		let mut untouchable = Zero::zero();
-		if force == Polite {
-			// Frozen balance applies to total. Anything on hold therefore gets discounted from the
-			// limit given by the freezes.
-			untouchable = a.frozen.saturating_sub(a.reserved);
-		}
-		// If we want to keep our provider ref..
-		if preservation == Preserve
-			// ..or we don't want the account to die and our provider ref is needed for it to live..
-			|| preservation == Protect && !a.free.is_zero() &&
-				frame_system::Pallet::<T>::providers(who) == 1
-			// ..or we don't care about the account dying but our provider ref is required..
-			|| preservation == Expendable && !a.free.is_zero() &&
-				!frame_system::Pallet::<T>::can_dec_provider(who)
-		{
-			// ..then the ED needed..
-			untouchable = untouchable.max(T::ExistentialDeposit::get());
-		}
-		// Liquid balance is what is neither on hold nor frozen/required for provider.
-		a.free.saturating_sub(untouchable)
+		Self::reducible_breakdown(who, preservation, force).0
 	}
 	fn can_deposit(
 		who: &T::AccountId,
@@ -114,6 +93,9 @@ impl<T: Config<I>, I: 'static> fungible::Inspect<T::AccountId> for Pallet<T, I>
 		}
 
 		let account = Self::account(who);
+		if account.flags.is_restricted() {
+			return WithdrawConsequence::Frozen
+		}
 		let new_free_balance = match account.free.checked_sub(&amount) {
 			Some(x) => x,
 			None => return WithdrawConsequence::BalanceLow,
@@ -152,6 +134,12 @@ impl<T: Config<I>, I: 'static> fungible::Inspect<T::AccountId> for Pallet<T, I>
 
 impl<T: Config<I>, I: 'static> fungible::Unbalanced<T::AccountId> for Pallet<T, I> {
 	fn handle_dust(dust: fungible::Dust<T::AccountId, Self>) {
+		Self::audit_issuance(
+			IssuanceAuditOperation::Dust,
+			None,
+			AdjustmentDirection::Decrease,
+			dust.0,
+		);
 		T::DustRemoval::on_unbalanced(dust.into_credit());
 	}
 	fn write_balance(
@@ -172,6 +160,35 @@ impl<T: Config<I>, I: 'static> fungible::Unbalanced<T::AccountId> for Pallet<T,
 		Ok(maybe_dust)
 	}
 
+	fn decrease_balance(
+		who: &T::AccountId,
+		mut amount: Self::Balance,
+		precision: Precision,
+		preservation: Preservation,
+		force: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		// Forced reductions, like slashes and placing holds, are restricted elsewhere, if at all.
+		if force == Polite && !amount.is_zero() {
+			Self::ensure_unrestricted(who)?;
+		}
+
+		let old_balance = <Self as fungible::Inspect<_>>::balance(who);
+		let reducible = <Self as fungible::Inspect<_>>::reducible_balance(who, preservation, force);
+		match precision {
+			Precision::BestEffort => amount = amount.min(reducible),
+			Precision::Exact => ensure!(reducible >= amount, TokenError::FundsUnavailable),
+		}
+		if amount.is_zero() {
+			return Ok(amount)
+		}
+
+		let new_balance = old_balance.checked_sub(&amount).ok_or(TokenError::FundsUnavailable)?;
+		if let Some(dust) = Self::write_balance(who, new_balance)? {
+			Self::handle_dust(fungible::Dust(dust));
+		}
+		Ok(old_balance.saturating_sub(new_balance))
+	}
+
 	fn set_total_issuance(amount: Self::Balance) {
 		TotalIssuance::<T, I>::mutate(|t| *t = amount);
 	}
@@ -190,18 +207,71 @@ impl<T: Config<I>, I: 'static> fungible::Unbalanced<T::AccountId> for Pallet<T,
 
 impl<T: Config<I>, I: 'static> fungible::Mutate<T::AccountId> for Pallet<T, I> {
 	fn done_mint_into(who: &T::AccountId, amount: Self::Balance) {
-		Self::deposit_event(Event::<T, I>::Minted { who: who.clone(), amount });
+		if amount.is_zero() {
+			return
+		}
+		Self::audit_issuance(
+			IssuanceAuditOperation::Mint,
+			Some(who),
+			AdjustmentDirection::Increase,
+			amount,
+		);
+		if Self::silenced(who, AdjustmentDirection::Increase, amount) {
+			return
+		}
+		Self::deposit_event(Event::<T, I>::Minted {
+			who: who.clone(),
+			amount,
+			context: current_context(),
+		});
 	}
 	fn done_burn_from(who: &T::AccountId, amount: Self::Balance) {
-		Self::deposit_event(Event::<T, I>::Burned { who: who.clone(), amount });
+		if amount.is_zero() {
+			return
+		}
+		Self::audit_issuance(
+			IssuanceAuditOperation::BurnFrom,
+			Some(who),
+			AdjustmentDirection::Decrease,
+			amount,
+		);
+		if Self::silenced(who, AdjustmentDirection::Decrease, amount) {
+			return
+		}
+		Self::deposit_event(Event::<T, I>::Burned {
+			who: who.clone(),
+			amount,
+			context: current_context(),
+		});
 	}
 	fn done_shelve(who: &T::AccountId, amount: Self::Balance) {
+		if amount.is_zero() {
+			return
+		}
+		Self::audit_issuance(
+			IssuanceAuditOperation::BurnFrom,
+			Some(who),
+			AdjustmentDirection::Decrease,
+			amount,
+		);
 		Self::deposit_event(Event::<T, I>::Suspended { who: who.clone(), amount });
 	}
 	fn done_restore(who: &T::AccountId, amount: Self::Balance) {
+		if amount.is_zero() {
+			return
+		}
+		Self::audit_issuance(
+			IssuanceAuditOperation::Mint,
+			Some(who),
+			AdjustmentDirection::Increase,
+			amount,
+		);
 		Self::deposit_event(Event::<T, I>::Restored { who: who.clone(), amount });
 	}
 	fn done_transfer(source: &T::AccountId, dest: &T::AccountId, amount: Self::Balance) {
+		if amount.is_zero() {
+			return
+		}
 		Self::deposit_event(Event::<T, I>::Transfer {
 			from: source.clone(),
 			to: dest.clone(),
@@ -210,7 +280,41 @@ impl<T: Config<I>, I: 'static> fungible::Mutate<T::AccountId> for Pallet<T, I> {
 	}
 }
 
-impl<T: Config<I>, I: 'static> fungible::MutateHold<T::AccountId> for Pallet<T, I> {}
+impl<T: Config<I>, I: 'static> fungible::MutateHold<T::AccountId> for Pallet<T, I> {
+	fn hold(reason: &Self::Reason, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		// Holding nothing succeeds even where no hold could be created, e.g. for dead accounts.
+		if amount.is_zero() {
+			return Ok(())
+		}
+		<Self as fungible::InspectHold<_>>::ensure_can_hold(reason, who, amount)?;
+		<Self as fungible::Unbalanced<_>>::decrease_balance(
+			who,
+			amount,
+			Precision::Exact,
+			Protect,
+			Force,
+		)?;
+		<Self as fungible::UnbalancedHold<_>>::increase_balance_on_hold(
+			reason,
+			who,
+			amount,
+			Precision::BestEffort,
+		)?;
+		Self::done_hold(reason, who, amount);
+		Ok(())
+	}
+	fn done_burn_held(_reason: &Self::Reason, who: &T::AccountId, amount: Self::Balance) {
+		if amount.is_zero() {
+			return
+		}
+		Self::audit_issuance(
+			IssuanceAuditOperation::BurnFrom,
+			Some(who),
+			AdjustmentDirection::Decrease,
+			amount,
+		);
+	}
+}
 
 impl<T: Config<I>, I: 'static> fungible::InspectHold<T::AccountId> for Pallet<T, I> {
 	type Reason = T::RuntimeHoldReason;
@@ -236,6 +340,20 @@ impl<T: Config<I>, I: 'static> fungible::InspectHold<T::AccountId> for Pallet<T,
 			.find(|x| &x.id == reason)
 			.map_or_else(Zero::zero, |x| x.amount)
 	}
+	fn ensure_can_hold(
+		reason: &Self::Reason,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		// Held funds may be transferred out of the account, so holds are restricted as well.
+		Self::ensure_unrestricted(who)?;
+		ensure!(
+			amount <= <Self as fungible::Inspect<_>>::reducible_balance(who, Protect, Force),
+			TokenError::FundsUnavailable
+		);
+		ensure!(Self::hold_available(reason, who), TokenError::CannotCreateHold);
+		Ok(())
+	}
 	fn hold_available(reason: &Self::Reason, who: &T::AccountId) -> bool {
 		if frame_system::Pallet::<T>::providers(who) == 0 {
 			return false
@@ -271,6 +389,9 @@ impl<T: Config<I>, I: 'static> fungible::UnbalancedHold<T::AccountId> for Pallet
 					.map_err(|_| Error::<T, I>::TooManyHolds)?;
 			}
 		}
+		if delta.is_zero() {
+			return Ok(())
+		}
 
 		new_account.reserved = if increase {
 			new_account.reserved.checked_add(&delta).ok_or(ArithmeticError::Overflow)?
@@ -318,7 +439,10 @@ impl<T: Config<I>, I: 'static> fungible::MutateFreeze<T::AccountId> for Pallet<T
 				.try_push(IdAmount { id: *id, amount })
 				.map_err(|_| Error::<T, I>::TooManyFreezes)?;
 		}
-		Self::update_freezes(who, locks.as_bounded_slice())
+		Self::update_freezes(who, locks.as_bounded_slice())?;
+		// A freeze set through `MutateFreeze` never expires, even if it was forced to before.
+		FreezeExpiry::<T, I>::remove(who, id);
+		Ok(())
 	}
 
 	fn extend_freeze(id: &Self::Id, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
@@ -333,13 +457,18 @@ impl<T: Config<I>, I: 'static> fungible::MutateFreeze<T::AccountId> for Pallet<T
 				.try_push(IdAmount { id: *id, amount })
 				.map_err(|_| Error::<T, I>::TooManyFreezes)?;
 		}
-		Self::update_freezes(who, locks.as_bounded_slice())
+		Self::update_freezes(who, locks.as_bounded_slice())?;
+		// A freeze set through `MutateFreeze` never expires, even if it was forced to before.
+		FreezeExpiry::<T, I>::remove(who, id);
+		Ok(())
 	}
 
 	fn thaw(id: &Self::Id, who: &T::AccountId) -> DispatchResult {
 		let mut locks = Freezes::<T, I>::get(who);
 		locks.retain(|l| &l.id != id);
-		Self::update_freezes(who, locks.as_bounded_slice())
+		Self::update_freezes(who, locks.as_bounded_slice())?;
+		FreezeExpiry::<T, I>::remove(who, id);
+		Ok(())
 	}
 }
 
@@ -348,20 +477,68 @@ impl<T: Config<I>, I: 'static> fungible::Balanced<T::AccountId> for Pallet<T, I>
 	type OnDropDebt = fungible::IncreaseIssuance<T::AccountId, Self>;
 
 	fn done_deposit(who: &T::AccountId, amount: Self::Balance) {
-		Self::deposit_event(Event::<T, I>::Deposit { who: who.clone(), amount });
+		if amount.is_zero() {
+			return
+		}
+		Self::audit_issuance(
+			IssuanceAuditOperation::Deposit,
+			Some(who),
+			AdjustmentDirection::Increase,
+			amount,
+		);
+		if Self::silenced(who, AdjustmentDirection::Increase, amount) {
+			return
+		}
+		Self::deposit_event(Event::<T, I>::Deposit {
+			who: who.clone(),
+			amount,
+			context: current_context(),
+		});
 	}
 	fn done_withdraw(who: &T::AccountId, amount: Self::Balance) {
-		Self::deposit_event(Event::<T, I>::Withdraw { who: who.clone(), amount });
+		if amount.is_zero() {
+			return
+		}
+		Self::audit_issuance(
+			IssuanceAuditOperation::Withdraw,
+			Some(who),
+			AdjustmentDirection::Decrease,
+			amount,
+		);
+		if Self::silenced(who, AdjustmentDirection::Decrease, amount) {
+			return
+		}
+		Self::deposit_event(Event::<T, I>::Withdraw {
+			who: who.clone(),
+			amount,
+			context: current_context(),
+		});
 	}
 	fn done_issue(amount: Self::Balance) {
+		if amount.is_zero() {
+			return
+		}
 		Self::deposit_event(Event::<T, I>::Issued { amount });
 	}
 	fn done_rescind(amount: Self::Balance) {
+		if amount.is_zero() {
+			return
+		}
 		Self::deposit_event(Event::<T, I>::Rescinded { amount });
 	}
 }
 
-impl<T: Config<I>, I: 'static> fungible::BalancedHold<T::AccountId> for Pallet<T, I> {}
+impl<T: Config<I>, I: 'static> fungible::BalancedHold<T::AccountId> for Pallet<T, I> {
+	fn done_slash(reason: &Self::Reason, who: &T::AccountId, amount: Self::Balance) {
+		T::DoneSlashHandler::done_slash(reason, who, amount);
+		Self::audit_issuance(
+			IssuanceAuditOperation::Withdraw,
+			Some(who),
+			AdjustmentDirection::Decrease,
+			amount,
+		);
+	}
+}
 
 impl<T: Config<I>, I: 'static> AccountTouch<(), T::AccountId> for Pallet<T, I> {
 	type Balance = T::Balance;
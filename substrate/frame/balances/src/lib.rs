@@ -126,6 +126,49 @@
 //! # fn main() {}
 //! ```
 //!
+//! ## Reentrancy
+//!
+//! Pallets executing user-programmable logic, such as `pallet-revive`, may be re-entered by the
+//! hooks of this pallet (e.g. [`Config::DustRemoval`]) while a transfer is still in progress.
+//! The regular transfer paths handle the dust of the source account before the destination
+//! account is credited, so such a hook observes a half-applied transfer.
+//!
+//! [`Pallet::transfer_no_reentry`] is the entry point meant for these callers: both accounts are
+//! mutated before any secondary effect is applied, see its documentation for the exact contract.
+//!
+//! ## Zero amounts
+//!
+//! Operations moving a zero amount succeed without touching storage or emitting events. This holds
+//! for the transfer and burn dispatchables, for `force_unreserve` and for the main [`fungible`]
+//! entry points such as [`fungible::Mutate::transfer`], [`fungible::Mutate::burn_from`],
+//! [`fungible::MutateHold::hold`] and [`fungible::MutateHold::release`]. In particular, a zero
+//! transfer never endows a dead account.
+//!
+//! There are a few deliberate exceptions:
+//!
+//! - [`fungible::Mutate::mint_into`] of a zero amount into a dead account fails with
+//!   [`TokenError::BelowMinimum`](sp_runtime::TokenError::BelowMinimum), as the `fungible`
+//!   conformance tests require minting below the minimum balance to fail.
+//! - Calls which set a value rather than move an amount, such as `force_set_balance`,
+//!   `force_set_freeze` and [`fungible::MutateFreeze::set_freeze`], treat zero as the new value:
+//!   they reap the account or thaw the freeze respectively.
+//! - `force_adjust_total_issuance` and `force_adjust_inactive_issuance` reject a zero `delta` with
+//!   [`Error::DeltaZero`].
+//!
+//! ## Issuance audit
+//!
+//! To debug a drift of the total issuance, the `issuance-audit` cargo feature records every
+//! change of the total issuance made or expected by this pallet within a block, such as the
+//! creation of imbalances, dust removal and minting or burning through `fungible`. At the end of
+//! the block the recorded net change is checked against the change of [`TotalIssuance`], and an
+//! [`Event::Unexpected`] with [`UnexpectedKind::IssuanceDrift`] is deposited if they differ, e.g.
+//! because an imbalance was leaked. The ledger of the last block is available through
+//! [`Pallet::last_issuance_audit`] and the [`runtime_api::BalancesApi`].
+//!
+//! Writes to the total issuance through [`fungible::Unbalanced::set_total_issuance`] by other
+//! pallets are not recorded and are reported as a drift. The feature adds storage accesses to
+//! every balance operation and is not meant for production runtimes.
+//!
 //! ## Genesis config
 //!
 //! The Balances pallet depends on the [`GenesisConfig`].
@@ -139,30 +182,42 @@
 //! `insecure_zero_ed` cargo feature is enabled. However this is not a configuration which is
 //! generally supported, nor will it be.
 //!
+//! Even with the feature enabled, an account needs at least one unit of free balance to be kept
+//! alive by this pallet, unless it is *sufficient*: either matched by
+//! [`Config::AccountSufficiency`] or registered in [`SufficientAccounts`] through
+//! [`Pallet::set_account_sufficiency`]. Only sufficient accounts may exist with a zero balance,
+//! which bounds the state an attacker can create by spreading dust across many accounts.
+//!
 //! [`frame_tokens`]: ../polkadot_sdk_docs/reference_docs/frame_tokens/index.html
 
 #![cfg_attr(not(feature = "std"), no_std)]
 mod benchmarking;
+mod conversion;
 mod impl_currency;
 mod impl_fungible;
 pub mod migration;
+pub mod runtime_api;
 mod tests;
 mod types;
 pub mod weights;
 
-use codec::{Codec, MaxEncodedLen};
+use codec::{Codec, DecodeAll, MaxEncodedLen};
+pub use conversion::{ConversionError, ConvertedAmount, DecimalsConversion};
 use frame_support::{
 	ensure,
 	pallet_prelude::DispatchResult,
+	storage::{unhashed, with_storage_layer, with_transaction},
 	traits::{
 		tokens::{
 			fungible, BalanceStatus as Status, DepositConsequence,
 			Fortitude::{self, Force, Polite},
-			Preservation::{Expendable, Preserve, Protect},
-			WithdrawConsequence,
+			Preservation::{self, Expendable, Preserve, Protect},
+			Provenance, WithdrawConsequence,
 		},
-		Currency, Defensive, Get, OnUnbalanced, ReservableCurrency, StoredMap,
+		Currency, Defensive, Get, LockIdentifier, LockableCurrency, OnUnbalanced,
+		ReservableCurrency, StoredMap,
 	},
+	weights::WeightMeter,
 	BoundedSlice, WeakBoundedVec,
 };
 use frame_system as system;
@@ -170,24 +225,83 @@ pub use impl_currency::{NegativeImbalance, PositiveImbalance};
 use scale_info::TypeInfo;
 use sp_runtime::{
 	traits::{
-		AtLeast32BitUnsigned, Bounded, CheckedAdd, CheckedSub, MaybeSerializeDeserialize,
-		Saturating, StaticLookup, Zero,
+		AtLeast32BitUnsigned, Bounded, CheckedAdd, CheckedSub, Convert, MaybeSerializeDeserialize,
+		One, Saturating, StaticLookup, Zero,
 	},
 	ArithmeticError, DispatchError, FixedPointOperand, Perbill, RuntimeDebug, TokenError,
+	TransactionOutcome,
 };
 use sp_std::{cmp, fmt::Debug, mem, prelude::*, result};
+#[cfg(any(feature = "std", feature = "try-runtime"))]
+pub use types::StoreAuditReport;
 pub use types::{
-	AccountData, AdjustmentDirection, BalanceLock, DustCleaner, ExtraFlags, IdAmount, Reasons,
-	ReserveData,
+	AccountData, AccountStoreKind, AdjustmentDirection, BalanceLock, BalancesStore,
+	ClosureBlockers, DustCleaner, ExtraFlags, ForceSetOutcome, IdAmount, IssuanceAudit,
+	IssuanceAuditEntry, IssuanceAuditOperation, IssuanceReconciliation, LockSummary,
+	PreservationBreakdown, Reasons, ReserveData, SilentActivity, SpendableBreakdown, SystemStore,
+	TransferAllRemainder, TransferRemark, UnexpectedKind, WithdrawBlocked,
+	MAX_ISSUANCE_AUDIT_ENTRIES, MAX_TRANSFER_REMARK_LEN,
 };
+use types::{GhostEntry, MutationEffects};
+#[cfg(any(test, feature = "try-runtime"))]
+pub use types::{TryStateSample, TRY_STATE_SAMPLING_KEY};
 pub use weights::WeightInfo;
 
 pub use pallet::*;
 
 const LOG_TARGET: &str = "runtime::balances";
 
+/// The minimum number of accounts an `upgrade_accounts` call has to actually upgrade for its fee
+/// to be waived.
+pub const MIN_UPGRADES_FOR_FEE_WAIVER: u32 = 10;
+
 type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
 
+/// Well-known tags for the `context` of the [`Event::Deposit`], [`Event::Withdraw`],
+/// [`Event::Minted`] and [`Event::Burned`] events, set through [`with_context`].
+pub mod contexts {
+	/// The payment of transaction fees, including their refund.
+	pub const TRANSACTION_PAYMENT: u16 = 1;
+	/// The execution of XCM programs, e.g. asset transactors and fee payment.
+	pub const XCM: u16 = 2;
+	/// Bridges, e.g. the rewards of relayers.
+	pub const BRIDGES: u16 = 3;
+}
+
+environmental::environmental!(ambient_context: u16);
+
+/// Run `f` with `context` attributed to the `Deposit`, `Withdraw`, `Minted` and `Burned` events it
+/// causes, letting indexers tell apart the pallets funnelling through these events.
+///
+/// Calls may be nested, in which case the innermost `context` applies. See [`contexts`] for the
+/// tags of in-tree users.
+pub fn with_context<R>(context: u16, f: impl FnOnce() -> R) -> R {
+	let mut context = context;
+	ambient_context::using(&mut context, f)
+}
+
+/// The context set by the innermost [`with_context`] currently running, if any.
+pub fn current_context() -> Option<u16> {
+	ambient_context::with(|context| *context)
+}
+
+/// Hook to react on inconsistencies detected by the pallet.
+///
+/// Called alongside the deposit of an [`Event::Unexpected`], i.e. while the block that caused the
+/// inconsistency is still being built, so that the runtime can take protective measures.
+pub trait OnInconsistency<AccountId> {
+	/// The balance of `who` was altered by an operation that should not have done so.
+	fn balance_updated(who: &AccountId);
+
+	/// Mutating the account of `who` failed unexpectedly.
+	fn failed_mutation(who: &AccountId);
+}
+
+impl<AccountId> OnInconsistency<AccountId> for () {
+	fn balance_updated(_: &AccountId) {}
+	fn failed_mutation(_: &AccountId) {}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -220,6 +334,8 @@ pub mod pallet {
 
 			type Balance = u64;
 			type ExistentialDeposit = ConstU64<1>;
+			#[cfg(feature = "insecure_zero_ed")]
+			type AccountSufficiency = ();
 
 			type ReserveIdentifier = ();
 			type FreezeIdentifier = ();
@@ -230,6 +346,18 @@ pub mod pallet {
 			type MaxReserves = ConstU32<100>;
 			type MaxFreezes = ConstU32<100>;
 
+			type OnInconsistency = ();
+
+			type ResidueQuantum = ConstU64<0>;
+			type ResidueThreshold = ConstU64<0>;
+			type MaxExpiringPerBlock = ConstU32<0>;
+			type MinTransferAmount = ConstU64<0>;
+			type LockToFreezeMapping = ();
+			type ManagedFreezes = ();
+			type BurnableHolds = ();
+			type LenientFreezeBound = ();
+			type DoneSlashHandler = ();
+
 			type WeightInfo = ();
 		}
 	}
@@ -253,6 +381,13 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 
 		/// The balance of an account.
+		///
+		/// Any unsigned type of at least 32 bits is supported, including `u32` for runtimes that
+		/// want to keep their state small. The total issuance must always fit into this type:
+		/// genesis panics if the endowed balances sum past `max_value()`, minting beyond it fails
+		/// with `ArithmeticError::Overflow` and the `Currency` imbalances saturate at it. With
+		/// `u32` this caps the total issuance at `u32::MAX` (about 4.29 * 10^9) base units, so the
+		/// existential deposit, fees and token decimals should be scaled accordingly.
 		type Balance: Parameter
 			+ Member
 			+ AtLeast32BitUnsigned
@@ -281,10 +416,28 @@ pub mod pallet {
 		#[pallet::no_default_bounds]
 		type ExistentialDeposit: Get<Self::Balance>;
 
+		/// The accounts which may exist with a zero balance under the `insecure_zero_ed` feature.
+		///
+		/// All other accounts need at least one unit of free balance, unless they are registered
+		/// in [`SufficientAccounts`]. The set should not change for accounts which exist, as
+		/// their provider references are not updated when it does.
+		#[cfg(feature = "insecure_zero_ed")]
+		#[pallet::no_default_bounds]
+		type AccountSufficiency: frame_support::traits::Contains<Self::AccountId>;
+
 		/// The means of storing the balances of an account.
 		#[pallet::no_default]
 		type AccountStore: StoredMap<Self::AccountId, AccountData<Self::Balance>>;
 
+		/// The store `AccountStore` keeps the account data in.
+		///
+		/// Use [`SystemStore`] if `AccountStore` is `frame_system`, and
+		/// [`BalancesStore`] if it is a `StorageMapShim` over [`Account`]. Tells
+		/// `purge_ghost_accounts` which of the two stores is not in use.
+		#[pallet::no_default]
+		#[pallet::constant]
+		type AuthoritativeStore: Get<AccountStoreKind>;
+
 		/// The ID type for reserves.
 		///
 		/// Use of reserves is deprecated in favour of holds. See `https://github.com/paritytech/substrate/pull/12951/`
@@ -309,6 +462,91 @@ pub mod pallet {
 		/// The maximum number of individual freeze locks that can exist on an account at any time.
 		#[pallet::constant]
 		type MaxFreezes: Get<u32>;
+
+		/// Hook called whenever an [`Event::Unexpected`] is deposited.
+		#[pallet::no_default_bounds]
+		type OnInconsistency: OnInconsistency<Self::AccountId>;
+
+		/// The quantum to which the free balance of an account is rounded down when sweeping
+		/// residues.
+		///
+		/// See [`Config::ResidueThreshold`]. Zero disables residue sweeping.
+		#[pallet::constant]
+		#[pallet::no_default_bounds]
+		type ResidueQuantum: Get<Self::Balance>;
+
+		/// The maximum residue that is swept from an account's free balance.
+		///
+		/// Whenever the free balance of a surviving account is reduced and still exceeds the
+		/// existential deposit, the remainder of `free` modulo [`Config::ResidueQuantum`] is
+		/// removed and handed to [`Config::DustRemoval`] if it is no greater than this value. The
+		/// sweep never takes an account below the existential deposit or its frozen balance. Zero
+		/// disables residue sweeping.
+		///
+		/// Only reductions of the free balance trigger a sweep. A residue created by crediting an
+		/// account, e.g. by a fee refund, is kept until its free balance is reduced the next time.
+		#[pallet::constant]
+		#[pallet::no_default_bounds]
+		type ResidueThreshold: Get<Self::Balance>;
+
+		/// The maximum number of freezes set through [`Pallet::force_set_freeze`] that can expire
+		/// in a single block.
+		#[pallet::constant]
+		type MaxExpiringPerBlock: Get<u32>;
+
+		/// The minimum amount that can be sent with [`Pallet::transfer_allow_death`],
+		/// [`Pallet::transfer_keep_alive`] and [`Pallet::transfer_all`].
+		///
+		/// Unlike the existential deposit this also applies to transfers into existing accounts,
+		/// which makes it costlier to spam them with dust. It is not enforced by the `fungible`
+		/// traits nor by [`Pallet::force_transfer`]. Zero disables the check.
+		#[pallet::constant]
+		#[pallet::no_default_bounds]
+		type MinTransferAmount: Get<Self::Balance>;
+
+		/// The freeze a lock is turned into by [`Pallet::deprecate_lock`], if any.
+		///
+		/// Use `()` to keep all locks as they are.
+		#[pallet::no_default_bounds]
+		type LockToFreezeMapping: Convert<LockIdentifier, Option<Self::FreezeIdentifier>>;
+
+		/// The freezes an account may remove itself through [`Pallet::prepare_account_closure`].
+		///
+		/// Only freezes which do not secure any obligation towards another party belong here.
+		/// Use `()` to leave all freezes to the pallets which set them.
+		#[pallet::no_default_bounds]
+		type ManagedFreezes: frame_support::traits::Contains<Self::FreezeIdentifier>;
+
+		/// The holds an account may burn itself through [`Pallet::burn_with_hold`].
+		///
+		/// Only holds which do not secure any obligation towards another party belong here, e.g.
+		/// a deposit the account placed voluntarily. Use `()` to let no hold be burned.
+		#[pallet::no_default_bounds]
+		type BurnableHolds: frame_support::traits::Contains<Self::RuntimeHoldReason>;
+
+		/// Whether `MaxFreezes` may be smaller than the number of freeze reasons.
+		///
+		/// By default the integrity test fails if `MaxFreezes` is below the variant count of
+		/// `RuntimeFreezeReason`. If this is `true` only a warning is logged instead, which allows
+		/// adding a freeze reason before raising `MaxFreezes` in a later runtime upgrade. Freezes
+		/// beyond `MaxFreezes` are rejected with [`Error::TooManyFreezes`] either way.
+		#[pallet::constant]
+		type LenientFreezeBound: Get<bool>;
+
+		/// Handler for funds on hold which were slashed through [`fungible::BalancedHold`].
+		///
+		/// It is called once the hold and the account are updated, before the slashed credit is
+		/// returned to the caller. Slashing funds on hold deposits no event of this pallet, so any
+		/// event deposited while handling the credit, e.g. [`Event::Deposit`] when it is resolved
+		/// into another account, comes after the call. Slashes of reserves through the
+		/// `Currency` traits are not reported, as they lack a hold reason; they deposit
+		/// [`Event::Slashed`] instead.
+		#[pallet::no_default_bounds]
+		type DoneSlashHandler: fungible::hold::DoneSlash<
+			Self::RuntimeHoldReason,
+			Self::AccountId,
+			Self::Balance,
+		>;
 	}
 
 	/// The in-code storage version.
@@ -343,16 +581,20 @@ pub mod pallet {
 			amount: T::Balance,
 			destination_status: Status,
 		},
-		/// Some amount was deposited (e.g. for transaction fees).
-		Deposit { who: T::AccountId, amount: T::Balance },
-		/// Some amount was withdrawn from the account (e.g. for transaction fees).
-		Withdraw { who: T::AccountId, amount: T::Balance },
+		/// Some amount was deposited (e.g. for transaction fees). `context` is set through
+		/// [`with_context`] by the pallet on whose behalf it was deposited, if any.
+		Deposit { who: T::AccountId, amount: T::Balance, context: Option<u16> },
+		/// Some amount was withdrawn from the account (e.g. for transaction fees). `context` is
+		/// set through [`with_context`] by the pallet on whose behalf it was withdrawn, if any.
+		Withdraw { who: T::AccountId, amount: T::Balance, context: Option<u16> },
 		/// Some amount was removed from the account (e.g. for misbehavior).
 		Slashed { who: T::AccountId, amount: T::Balance },
-		/// Some amount was minted into an account.
-		Minted { who: T::AccountId, amount: T::Balance },
-		/// Some amount was burned from an account.
-		Burned { who: T::AccountId, amount: T::Balance },
+		/// Some amount was minted into an account. `context` is set through [`with_context`] by
+		/// the pallet on whose behalf it was minted, if any.
+		Minted { who: T::AccountId, amount: T::Balance, context: Option<u16> },
+		/// Some amount was burned from an account. `context` is set through [`with_context`] by
+		/// the pallet on whose behalf it was burned, if any.
+		Burned { who: T::AccountId, amount: T::Balance, context: Option<u16> },
 		/// Some amount was suspended from an account (it can be restored later).
 		Suspended { who: T::AccountId, amount: T::Balance },
 		/// Some amount was restored into an account.
@@ -373,6 +615,55 @@ pub mod pallet {
 		Thawed { who: T::AccountId, amount: T::Balance },
 		/// The `TotalIssuance` was forcefully changed.
 		TotalIssuanceForced { old: T::Balance, new: T::Balance },
+		/// An unexpected/defensive event was triggered.
+		Unexpected(UnexpectedKind),
+		/// A residue below `ResidueThreshold` was swept from the free balance of a surviving
+		/// account.
+		ResidueSwept { who: T::AccountId, amount: T::Balance },
+		/// Default-valued or duplicate entries were removed from the account store not in use.
+		/// `last` is the last visited account, `None` if there were no accounts left to visit.
+		GhostAccountsPurged { purged: u32, last: Option<T::AccountId> },
+		/// An account was given `count` locks, more than `MaxLocks`. The locks were stored
+		/// nevertheless.
+		LockLimitExceeded { who: T::AccountId, count: u32 },
+		/// A `transfer_all` without `keep_alive` could not drain the account, leaving `remaining`
+		/// (free and reserved) balance in it.
+		TransferAllIncomplete { who: T::AccountId, remaining: T::Balance },
+		/// `count` events of silent accounts were suppressed in this block, changing their
+		/// balances by `net_delta` in `direction` overall.
+		SilentAccountActivity { count: u32, net_delta: T::Balance, direction: AdjustmentDirection },
+		/// An account was restricted, no balance can be withdrawn from it anymore.
+		AccountRestricted { who: T::AccountId },
+		/// An account is no longer restricted.
+		AccountUnrestricted { who: T::AccountId },
+		/// The balance of `from`, together with its holds, freezes, locks and reserves, was moved
+		/// to `to`.
+		AccountMigrated { from: T::AccountId, to: T::AccountId },
+		/// A transfer carried a remark. Deposited right after the corresponding `Transfer`
+		/// event. The remark itself is only part of the call data.
+		TransferWithRemark {
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: T::Balance,
+			remark_hash: [u8; 32],
+		},
+		/// The lock `id` of `who` was turned into the freeze `freeze` of at least `amount`.
+		LockDeprecated {
+			who: T::AccountId,
+			id: LockIdentifier,
+			freeze: T::FreezeIdentifier,
+			amount: T::Balance,
+		},
+		/// A `transfer_all` moved `amount` from `from` to `to`. `killed` tells whether `from` was
+		/// drained and reaped. Deposited after the corresponding `Transfer` event.
+		TransferAll { from: T::AccountId, to: T::AccountId, amount: T::Balance, killed: bool },
+		/// `amount` held for `reason` was burned from the account `who`.
+		BurnedHeld { who: T::AccountId, reason: T::RuntimeHoldReason, amount: T::Balance },
+		/// The `InactiveIssuance` was forcefully changed.
+		InactiveIssuanceForced { old: T::Balance, new: T::Balance },
+		/// The account `who` is ready to be closed, after `removed_freezes` freezes in
+		/// `ManagedFreezes` were removed.
+		AccountClosurePrepared { who: T::AccountId, removed_freezes: u32 },
 	}
 
 	#[pallet::error]
@@ -401,6 +692,27 @@ pub mod pallet {
 		IssuanceDeactivated,
 		/// The delta cannot be zero.
 		DeltaZero,
+		/// Number of freezes expiring in the same block exceed `MaxExpiringPerBlock`.
+		TooManyExpiringFreezes,
+		/// The transferred amount is below `MinTransferAmount`.
+		TransferTooSmall,
+		/// The destination of an account migration already has some balance, holds, freezes,
+		/// locks or reserves.
+		MigrationDestinationInUse,
+		/// The account has no lock with the given identifier.
+		UnknownLock,
+		/// No freeze is mapped to the lock by `LockToFreezeMapping`.
+		LockNotConvertible,
+		/// The account has no transferable balance.
+		NothingToTransfer,
+		/// The total issuance would fall below the inactive issuance.
+		IssuanceBelowInactive,
+		/// The inactive issuance cannot exceed the total issuance.
+		InactiveAboveIssuance,
+		/// The account cannot be closed, as it still has the given holds, locks and reserves.
+		ClosureBlocked(ClosureBlockers),
+		/// The hold reason is not in `BurnableHolds`.
+		HoldNotBurnable,
 	}
 
 	/// The total units issued in the system.
@@ -423,6 +735,7 @@ pub mod pallet {
 	/// ```nocompile
 	///  impl pallet_balances::Config for Runtime {
 	///    type AccountStore = StorageMapShim<Self::Account<Runtime>, frame_system::Provider<Runtime>, AccountId, Self::AccountData<Balance>>
+	///    type AuthoritativeStore = pallet_balances::BalancesStore;
 	///  }
 	/// ```
 	///
@@ -433,6 +746,7 @@ pub mod pallet {
 	/// ```nocompile
 	///  impl pallet_balances::Config for Runtime {
 	///   type AccountStore = System
+	///   type AuthoritativeStore = pallet_balances::SystemStore;
 	///  }
 	/// ```
 	///
@@ -458,6 +772,12 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// The number of times an account was given more locks than `MaxLocks`.
+	///
+	/// Locks beyond the bound are stored nevertheless, see [`Event::LockLimitExceeded`].
+	#[pallet::storage]
+	pub type LockOverflowCount<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
 	/// Named reserves on some account balances.
 	///
 	/// Use of reserves is deprecated in favour of holds. See `https://github.com/paritytech/substrate/pull/12951/`
@@ -494,21 +814,124 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// Whether accounts still using the old logic should be upgraded in `on_idle`.
+	///
+	/// Set through [`Pallet::set_auto_upgrade`] and cleared once all accounts were visited.
+	#[pallet::storage]
+	pub type AutoUpgradeEnabled<T: Config<I>, I: 'static = ()> = StorageValue<_, bool, ValueQuery>;
+
+	/// The last account visited by the `on_idle` account upgrade.
+	///
+	/// Iteration resumes after this account in the next block. `None` means that iteration starts
+	/// from the beginning.
+	#[pallet::storage]
+	pub type AutoUpgradeCursor<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, T::AccountId, OptionQuery>;
+
+	/// The block in which a freeze set through [`Pallet::force_set_freeze`] is thawed.
+	#[pallet::storage]
+	pub type FreezeExpiry<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::FreezeIdentifier,
+		BlockNumberFor<T>,
+		OptionQuery,
+	>;
+
+	/// The freezes which expire in a block.
+	///
+	/// Entries whose [`FreezeExpiry`] changed in the meantime are skipped.
+	#[pallet::storage]
+	pub type FreezeExpiryAgenda<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<(T::AccountId, T::FreezeIdentifier), T::MaxExpiringPerBlock>,
+		ValueQuery,
+	>;
+
+	/// The progress of [`Pallet::force_reconcile_total_issuance`].
+	///
+	/// Cleared once all accounts were visited, or when the balance of an already visited account
+	/// changes, restarting the reconciliation.
+	#[pallet::storage]
+	pub type IssuanceReconciliationProgress<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, IssuanceReconciliation<T::AccountId, T::Balance>, OptionQuery>;
+
+	/// Accounts whose `Deposit`, `Withdraw`, `Minted` and `Burned` events are suppressed.
+	///
+	/// Managed through [`Pallet::add_silent_account`] and [`Pallet::remove_silent_account`].
+	#[pallet::storage]
+	pub type SilentAccounts<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// The balance changes of [`SilentAccounts`] within the current block.
+	///
+	/// Emitted as [`Event::SilentAccountActivity`] and cleared at the end of the block.
+	#[pallet::storage]
+	pub type BlockSilentActivity<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, SilentActivity<T::Balance>, OptionQuery>;
+
+	/// The changes of the total issuance recorded within the current block.
+	///
+	/// Started at the beginning of the block, and reconciled against the [`TotalIssuance`] and
+	/// cleared at the end of the block.
+	#[cfg(feature = "issuance-audit")]
+	#[pallet::storage]
+	pub type BlockIssuanceAudit<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, IssuanceAudit<T::AccountId, T::Balance>, OptionQuery>;
+
+	/// The [`BlockIssuanceAudit`] of the previous block, kept for offchain inspection.
+	#[cfg(feature = "issuance-audit")]
+	#[pallet::storage]
+	pub type LastIssuanceAudit<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, IssuanceAudit<T::AccountId, T::Balance>, OptionQuery>;
+
+	/// The accounts which may exist with a zero balance under the `insecure_zero_ed` feature, on
+	/// top of the ones matched by [`Config::AccountSufficiency`].
+	///
+	/// Managed through [`Pallet::set_account_sufficiency`].
+	#[cfg(feature = "insecure_zero_ed")]
+	#[pallet::storage]
+	pub type SufficientAccounts<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
 		pub balances: Vec<(T::AccountId, T::Balance)>,
+		/// The reserved balances of accounts in `balances`, on top of their free balance.
+		#[serde(default)]
+		pub reserved_balances: Vec<(T::AccountId, T::Balance)>,
+		/// Accounts in `balances` which are built with the old logic, i.e. as if they were not
+		/// upgraded through [`Pallet::upgrade_accounts`] yet.
+		///
+		/// Their reserved balance does not get the consumer reference added by the upgrade. This
+		/// allows testing the upgrade path on a network forked off from a live chain.
+		#[serde(default)]
+		pub legacy_accounts: Vec<T::AccountId>,
 	}
 
 	impl<T: Config<I>, I: 'static> Default for GenesisConfig<T, I> {
 		fn default() -> Self {
-			Self { balances: Default::default() }
+			Self {
+				balances: Default::default(),
+				reserved_balances: Default::default(),
+				legacy_accounts: Default::default(),
+			}
 		}
 	}
 
 	#[pallet::genesis_build]
 	impl<T: Config<I>, I: 'static> BuildGenesisConfig for GenesisConfig<T, I> {
 		fn build(&self) {
-			let total = self.balances.iter().fold(Zero::zero(), |acc: T::Balance, &(_, n)| acc + n);
+			let total = self
+				.balances
+				.iter()
+				.chain(self.reserved_balances.iter())
+				.try_fold(T::Balance::zero(), |acc, &(_, n)| acc.checked_add(&n))
+				.expect("the total issuance of the genesis balances overflows `T::Balance`");
 
 			<TotalIssuance<T, I>>::put(total);
 
@@ -532,16 +955,73 @@ pub mod pallet {
 				"duplicate balances in genesis."
 			);
 
+			let reserved = self
+				.reserved_balances
+				.iter()
+				.cloned()
+				.collect::<sp_std::collections::btree_map::BTreeMap<_, _>>();
+			assert!(
+				reserved.len() == self.reserved_balances.len(),
+				"duplicate reserved balances in genesis."
+			);
+			assert!(
+				reserved.keys().all(|who| endowed_accounts.contains(who)),
+				"reserved balances in genesis must belong to endowed accounts."
+			);
+
+			let legacy_accounts = self
+				.legacy_accounts
+				.iter()
+				.cloned()
+				.collect::<sp_std::collections::btree_set::BTreeSet<_>>();
+			assert!(
+				legacy_accounts.is_subset(&endowed_accounts),
+				"legacy accounts in genesis must be endowed accounts."
+			);
+
 			for &(ref who, free) in self.balances.iter() {
 				frame_system::Pallet::<T>::inc_providers(who);
-				assert!(T::AccountStore::insert(who, AccountData { free, ..Default::default() })
-					.is_ok());
+				let reserved = reserved.get(who).copied().unwrap_or_else(Zero::zero);
+				let flags = if legacy_accounts.contains(who) {
+					ExtraFlags::old_logic()
+				} else {
+					if !reserved.is_zero() {
+						// Upgraded accounts hold a consumer reference for their reserved balance.
+						frame_system::Pallet::<T>::inc_consumers_without_limit(who)
+							.expect("a provider reference was just added; qed");
+					}
+					ExtraFlags::default()
+				};
+				assert!(T::AccountStore::insert(
+					who,
+					AccountData { free, reserved, flags, ..Default::default() }
+				)
+				.is_ok());
 			}
 		}
 	}
 
 	#[pallet::hooks]
 	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			// Accounts for reporting the activity of silent accounts in `on_finalize`.
+			let weight =
+				Self::thaw_expired_freezes(n).saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			#[cfg(feature = "issuance-audit")]
+			let weight = weight.saturating_add(Self::start_issuance_audit());
+			weight
+		}
+
+		fn on_finalize(_n: BlockNumberFor<T>) {
+			Self::report_silent_activity();
+			#[cfg(feature = "issuance-audit")]
+			Self::reconcile_issuance_audit();
+		}
+
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::auto_upgrade_accounts(remaining_weight)
+		}
+
 		fn integrity_test() {
 			#[cfg(not(feature = "insecure_zero_ed"))]
 			assert!(
@@ -549,33 +1029,117 @@ pub mod pallet {
 				"The existential deposit must be greater than zero!"
 			);
 
-			assert!(
-				T::MaxFreezes::get() >= <T::RuntimeFreezeReason as VariantCount>::VARIANT_COUNT,
-				"MaxFreezes should be greater than or equal to the number of freeze reasons: {} < {}",
-				T::MaxFreezes::get(), <T::RuntimeFreezeReason as VariantCount>::VARIANT_COUNT,
-			);
+			let max_freezes = T::MaxFreezes::get();
+			let freeze_reasons = <T::RuntimeFreezeReason as VariantCount>::VARIANT_COUNT;
+			if T::LenientFreezeBound::get() {
+				if max_freezes < freeze_reasons {
+					log::warn!(
+						target: LOG_TARGET,
+						"MaxFreezes is smaller than the number of freeze reasons: {} < {}",
+						max_freezes,
+						freeze_reasons,
+					);
+				}
+			} else {
+				assert!(
+					max_freezes >= freeze_reasons,
+					"MaxFreezes should be greater than or equal to the number of freeze reasons: \
+					{} < {}",
+					max_freezes,
+					freeze_reasons,
+				);
+			}
 		}
 
 		#[cfg(feature = "try-runtime")]
-		fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
-			Holds::<T, I>::iter_keys().try_for_each(|k| {
-				if Holds::<T, I>::decode_len(k).unwrap_or(0) >
-					T::RuntimeHoldReason::VARIANT_COUNT as usize
-				{
-					Err("Found `Hold` with too many elements")
-				} else {
-					Ok(())
-				}
-			})?;
+		fn try_state(n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			Self::do_try_state(TryStateSample::for_block(n))
+		}
+	}
 
-			Freezes::<T, I>::iter_keys().try_for_each(|k| {
-				if Freezes::<T, I>::decode_len(k).unwrap_or(0) > T::MaxFreezes::get() as usize {
-					Err("Found `Freeze` with too many elements")
-				} else {
-					Ok(())
-				}
-			})?;
+	#[cfg(any(test, feature = "try-runtime"))]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Ensure the correctness of the state of this pallet for the accounts in `sample`.
+		///
+		/// The inactive issuance must not exceed the total issuance. All other invariants are
+		/// checked per account, so that a [`TryStateSample::Slice`] verifies a deterministic
+		/// subset of the accounts and [`TryStateSample::Full`] all of them.
+		pub fn do_try_state(sample: TryStateSample) -> Result<(), sp_runtime::TryRuntimeError> {
+			if InactiveIssuance::<T, I>::get() > TotalIssuance::<T, I>::get() {
+				return Err("`InactiveIssuance` exceeds `TotalIssuance`".into())
+			}
+
+			Holds::<T, I>::iter_keys()
+				.filter(|who| sample.contains(who))
+				.try_for_each(|who| Self::try_state_holds(&who))?;
+
+			let exceeding = Self::accounts_exceeding_max_freezes(sample);
+			if exceeding > 0 {
+				log::error!(
+					target: LOG_TARGET,
+					"Found {} accounts with more than `MaxFreezes` freezes",
+					exceeding,
+				);
+				return Err("Found `Freeze` with too many elements".into())
+			}
+			Freezes::<T, I>::iter_keys()
+				.filter(|who| sample.contains(who))
+				.try_for_each(|who| Self::try_state_freezes(&who))?;
+
+			let exceeding = Self::accounts_exceeding_max_locks(sample);
+			if exceeding > 0 {
+				log::warn!(
+					target: LOG_TARGET,
+					"Found {} accounts with more than `MaxLocks` locks",
+					exceeding,
+				);
+			}
+
+			Ok(())
+		}
+
+		/// The number of accounts in `sample` holding more locks than `MaxLocks`.
+		///
+		/// Locks are force-inserted beyond the bound, so [`Self::do_try_state`] only warns about
+		/// such accounts.
+		pub fn accounts_exceeding_max_locks(sample: TryStateSample) -> usize {
+			Locks::<T, I>::iter_keys()
+				.filter(|who| sample.contains(who))
+				.filter(|who| {
+					Locks::<T, I>::decode_len(who).unwrap_or(0) > T::MaxLocks::get() as usize
+				})
+				.count()
+		}
+
+		/// The number of accounts in `sample` with more freezes than `MaxFreezes`.
+		///
+		/// Freezes are bounded by `MaxFreezes`, so this is only non-zero if the bound was lowered
+		/// below the number of freezes of an account.
+		pub fn accounts_exceeding_max_freezes(sample: TryStateSample) -> usize {
+			Freezes::<T, I>::iter_keys()
+				.filter(|who| sample.contains(who))
+				.filter(|who| {
+					Freezes::<T, I>::decode_len(who).unwrap_or(0) > T::MaxFreezes::get() as usize
+				})
+				.count()
+		}
+
+		fn try_state_holds(who: &T::AccountId) -> Result<(), sp_runtime::TryRuntimeError> {
+			if Holds::<T, I>::decode_len(who).unwrap_or(0) >
+				T::RuntimeHoldReason::VARIANT_COUNT as usize
+			{
+				return Err("Found `Hold` with too many elements".into())
+			}
+			if Self::account(who).reserved < Self::held_total(who) {
+				return Err("Found `reserved` balance below the sum of the holds".into())
+			}
+			Ok(())
+		}
 
+		fn try_state_freezes(who: &T::AccountId) -> Result<(), sp_runtime::TryRuntimeError> {
+			if Self::account(who).frozen != Self::frozen_requirement(who) {
+				return Err("Found `frozen` balance not matching the largest lock or freeze".into())
+			}
 			Ok(())
 		}
 	}
@@ -588,6 +1152,9 @@ pub mod pallet {
 		/// If the sender's account is below the existential deposit as a result
 		/// of the transfer, the account will be reaped.
 		///
+		/// A zero `value` is a no-op: no account is endowed and no event is emitted, regardless
+		/// of [`Config::MinTransferAmount`].
+		///
 		/// The dispatch origin for this call must be `Signed` by the transactor.
 		#[pallet::call_index(0)]
 		pub fn transfer_allow_death(
@@ -597,12 +1164,18 @@ pub mod pallet {
 		) -> DispatchResult {
 			let source = ensure_signed(origin)?;
 			let dest = T::Lookup::lookup(dest)?;
+			if value.is_zero() {
+				return Ok(())
+			}
+			Self::ensure_min_transfer_amount(value)?;
 			<Self as fungible::Mutate<_>>::transfer(&source, &dest, value, Expendable)?;
 			Ok(())
 		}
 
 		/// Exactly as `transfer_allow_death`, except the origin must be root and the source account
 		/// may be specified.
+		///
+		/// This bypasses [`Config::MinTransferAmount`]. A zero `value` is a no-op.
 		#[pallet::call_index(2)]
 		pub fn force_transfer(
 			origin: OriginFor<T>,
@@ -613,6 +1186,9 @@ pub mod pallet {
 			ensure_root(origin)?;
 			let source = T::Lookup::lookup(source)?;
 			let dest = T::Lookup::lookup(dest)?;
+			if value.is_zero() {
+				return Ok(())
+			}
 			<Self as fungible::Mutate<_>>::transfer(&source, &dest, value, Expendable)?;
 			Ok(())
 		}
@@ -622,6 +1198,11 @@ pub mod pallet {
 		///
 		/// 99% of the time you want [`transfer_allow_death`] instead.
 		///
+		/// If the transfer is blocked, the error tells whether the free balance is too low, or
+		/// whether a hold, a freeze or the existential deposit prevent it, see
+		/// [`Pallet::withdraw_failure_reason`]. Like for [`transfer_allow_death`], a zero `value`
+		/// is a no-op.
+		///
 		/// [`transfer_allow_death`]: struct.Pallet.html#method.transfer
 		#[pallet::call_index(3)]
 		pub fn transfer_keep_alive(
@@ -631,6 +1212,11 @@ pub mod pallet {
 		) -> DispatchResult {
 			let source = ensure_signed(origin)?;
 			let dest = T::Lookup::lookup(dest)?;
+			if value.is_zero() {
+				return Ok(())
+			}
+			Self::ensure_min_transfer_amount(value)?;
+			Self::ensure_withdrawable(&source, value, Preserve)?;
 			<Self as fungible::Mutate<_>>::transfer(&source, &dest, value, Preserve)?;
 			Ok(())
 		}
@@ -650,6 +1236,13 @@ pub mod pallet {
 		///   of the funds the account has, causing the sender account to be killed (false), or
 		///   transfer everything except at least the existential deposit, which will guarantee to
 		///   keep the sender account alive (true).
+		///
+		/// Emits [`Event::TransferAll`], telling whether the account was reaped. If `keep_alive`
+		/// is `false` but the account survives the transfer, e.g. because part of its balance is
+		/// locked, frozen, on hold or reserved, [`Event::TransferAllIncomplete`] is emitted with
+		/// the balance left in the account. The call still succeeds.
+		///
+		/// Fails with [`Error::NothingToTransfer`] if no balance is transferable at all.
 		#[pallet::call_index(4)]
 		pub fn transfer_all(
 			origin: OriginFor<T>,
@@ -657,24 +1250,35 @@ pub mod pallet {
 			keep_alive: bool,
 		) -> DispatchResult {
 			let transactor = ensure_signed(origin)?;
+			let (reducible_balance, _) = Self::transfer_all_preview(&transactor, keep_alive);
+			ensure!(!reducible_balance.is_zero(), Error::<T, I>::NothingToTransfer);
 			let keep_alive = if keep_alive { Preserve } else { Expendable };
-			let reducible_balance = <Self as fungible::Inspect<_>>::reducible_balance(
-				&transactor,
-				keep_alive,
-				Fortitude::Polite,
-			);
 			let dest = T::Lookup::lookup(dest)?;
+			Self::ensure_min_transfer_amount(reducible_balance)?;
 			<Self as fungible::Mutate<_>>::transfer(
 				&transactor,
 				&dest,
 				reducible_balance,
 				keep_alive,
 			)?;
+			let remaining = <Self as fungible::Inspect<_>>::total_balance(&transactor);
+			Self::deposit_event(Event::TransferAll {
+				from: transactor.clone(),
+				to: dest,
+				amount: reducible_balance,
+				killed: remaining.is_zero(),
+			});
+			if keep_alive == Expendable && !remaining.is_zero() {
+				Self::deposit_event(Event::TransferAllIncomplete { who: transactor, remaining });
+			}
 			Ok(())
 		}
 
 		/// Unreserve some balance from a user by force.
 		///
+		/// Emits [`Event::Unreserved`] only if some balance was actually unreserved, so a zero
+		/// `amount` is a no-op.
+		///
 		/// Can only be called by ROOT.
 		#[pallet::call_index(5)]
 		pub fn force_unreserve(
@@ -693,9 +1297,12 @@ pub mod pallet {
 		/// - `origin`: Must be `Signed`.
 		/// - `who`: The account to be upgraded.
 		///
-		/// This will waive the transaction fee if at least all but 10% of the accounts needed to
-		/// be upgraded. (We let some not have to be upgraded just in order to allow for the
-		/// possibility of churn).
+		/// This will waive the transaction fee if at least [`MIN_UPGRADES_FOR_FEE_WAIVER`] accounts
+		/// and all but 10% of the accounts needed to be upgraded. (We let some not have to be
+		/// upgraded just in order to allow for the possibility of churn).
+		///
+		/// Accounts which did not need to be upgraded are only charged the weight of checking
+		/// them.
 		#[pallet::call_index(6)]
 		#[pallet::weight(T::WeightInfo::upgrade_accounts(who.len() as u32))]
 		pub fn upgrade_accounts(
@@ -713,11 +1320,15 @@ pub mod pallet {
 					upgrade_count.saturating_inc();
 				}
 			}
+			let skip_count = (who.len() as u32).saturating_sub(upgrade_count);
+			let actual_weight = Self::upgrade_accounts_weight(upgrade_count, skip_count);
 			let proportion_upgraded = Perbill::from_rational(upgrade_count, who.len() as u32);
-			if proportion_upgraded >= Perbill::from_percent(90) {
-				Ok(Pays::No.into())
+			if upgrade_count >= MIN_UPGRADES_FOR_FEE_WAIVER &&
+				proportion_upgraded >= Perbill::from_percent(90)
+			{
+				Ok((Some(actual_weight), Pays::No).into())
 			} else {
-				Ok(Pays::Yes.into())
+				Ok((Some(actual_weight), Pays::Yes).into())
 			}
 		}
 
@@ -736,27 +1347,8 @@ pub mod pallet {
 		) -> DispatchResult {
 			ensure_root(origin)?;
 			let who = T::Lookup::lookup(who)?;
-			let existential_deposit = Self::ed();
-
-			let wipeout = new_free < existential_deposit;
-			let new_free = if wipeout { Zero::zero() } else { new_free };
-
-			// First we try to modify the account's balance to the forced balance.
-			let old_free = Self::mutate_account_handling_dust(&who, |account| {
-				let old_free = account.free;
-				account.free = new_free;
-				old_free
-			})?;
-
-			// This will adjust the total issuance, which was not done by the `mutate_account`
-			// above.
-			if new_free > old_free {
-				mem::drop(PositiveImbalance::<T, I>::new(new_free - old_free));
-			} else if new_free < old_free {
-				mem::drop(NegativeImbalance::<T, I>::new(old_free - new_free));
-			}
-
-			Self::deposit_event(Event::BalanceSet { who, free: new_free });
+			let outcome = Self::do_force_set_balance(&who, new_free)?;
+			Self::deposit_event(Event::BalanceSet { who, free: outcome.new_free });
 			Ok(())
 		}
 
@@ -783,8 +1375,14 @@ pub mod pallet {
 				AdjustmentDirection::Decrease => old.saturating_sub(delta),
 			};
 
-			ensure!(InactiveIssuance::<T, I>::get() <= new, Error::<T, I>::IssuanceDeactivated);
+			Self::ensure_issuance_covers_inactive(old, new)?;
 			TotalIssuance::<T, I>::set(new);
+			Self::audit_issuance(
+				IssuanceAuditOperation::Forced,
+				None,
+				direction,
+				old.max(new) - old.min(new),
+			);
 
 			Self::deposit_event(Event::<T, I>::TotalIssuanceForced { old, new });
 
@@ -798,6 +1396,11 @@ pub mod pallet {
 		///
 		/// Unlike sending funds to a _burn_ address, which merely makes the funds inaccessible,
 		/// this `burn` operation will reduce total issuance by the amount _burned_.
+		///
+		/// A zero `value` is a no-op and emits no [`Event::Burned`].
+		///
+		/// Deprecated in favour of [`Pallet::burn_with_hold`], which can also burn balance on
+		/// hold. This call stays dispatchable for existing callers.
 		#[pallet::call_index(10)]
 		#[pallet::weight(if *keep_alive {T::WeightInfo::burn_allow_death() } else {T::WeightInfo::burn_keep_alive()})]
 		pub fn burn(
@@ -806,6 +1409,9 @@ pub mod pallet {
 			keep_alive: bool,
 		) -> DispatchResult {
 			let source = ensure_signed(origin)?;
+			if value.is_zero() {
+				return Ok(())
+			}
 			let preservation = if keep_alive { Preserve } else { Expendable };
 			<Self as fungible::Mutate<_>>::burn_from(
 				&source,
@@ -816,35 +1422,777 @@ pub mod pallet {
 			)?;
 			Ok(())
 		}
-	}
 
-	impl<T: Config<I>, I: 'static> Pallet<T, I> {
-		fn ed() -> T::Balance {
-			T::ExistentialDeposit::get()
+		/// Enable or disable the upgrade of old-logic accounts in `on_idle`.
+		///
+		/// While enabled, all accounts are visited in the idle time of each block, resuming where
+		/// the previous block stopped. The flag is cleared once every account was visited.
+		/// Enabling it again restarts the iteration from the beginning.
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::set_auto_upgrade())]
+		pub fn set_auto_upgrade(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+			ensure_root(origin)?;
+			AutoUpgradeEnabled::<T, I>::put(enabled);
+			AutoUpgradeCursor::<T, I>::kill();
+			Ok(())
 		}
-		/// Ensure the account `who` is using the new logic.
+
+		/// Remove stale balance data from the account store which is not in use.
 		///
-		/// Returns `true` if the account did get upgraded, `false` if it didn't need upgrading.
-		pub fn ensure_upgraded(who: &T::AccountId) -> bool {
-			let mut a = T::AccountStore::get(who);
-			if a.flags.is_new_logic() {
-				return false
-			}
-			a.flags.set_new_logic();
-			if !a.reserved.is_zero() && a.frozen.is_zero() {
-				if system::Pallet::<T>::providers(who) == 0 {
-					// Gah!! We have no provider refs :(
-					// This shouldn't practically happen, but we need a failsafe anyway: let's give
-					// them enough for an ED.
-					log::warn!(
-						target: LOG_TARGET,
-						"account with a non-zero reserve balance has no provider refs, account_id: '{:?}'.",
-						who
-					);
-					a.free = a.free.max(Self::ed());
-					system::Pallet::<T>::inc_providers(who);
+		/// Visits up to `limit` entries of the store not used by `AccountStore`, starting after
+		/// `cursor`, and removes those which are default-valued or exactly duplicate the data of
+		/// the authoritative store. Entries holding divergent data are never removed, they are
+		/// only reported by `audit_account_stores`. The last visited account is emitted in
+		/// [`Event::GhostAccountsPurged`] and can be passed as `cursor` to continue.
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::purge_ghost_accounts(*limit))]
+		pub fn purge_ghost_accounts(
+			origin: OriginFor<T>,
+			limit: u32,
+			cursor: Option<T::AccountId>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let authoritative = T::AuthoritativeStore::get();
+			let mut accounts = Self::unused_store_keys(authoritative, cursor.as_ref())
+				.take(limit as usize)
+				.collect::<Vec<_>>();
+
+			let mut purged = 0u32;
+			for who in &accounts {
+				if Self::ghost_entry(who, authoritative).is_some_and(|entry| entry.purgeable) {
+					Self::purge_ghost_entry(who, authoritative);
+					purged.saturating_inc();
 				}
-				let _ = system::Pallet::<T>::inc_consumers_without_limit(who).defensive();
+			}
+
+			Self::deposit_event(Event::GhostAccountsPurged { purged, last: accounts.pop() });
+			Ok(())
+		}
+
+		/// Set the freeze `id` of `who` to `amount`, optionally thawing it in block `expires`.
+		///
+		/// An expiry which is not in the future thaws the freeze in the next block. A freeze set
+		/// without expiry, or thawed before it expires, no longer expires. A zero `amount` thaws
+		/// the freeze right away.
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::force_set_freeze())]
+		pub fn force_set_freeze(
+			origin: OriginFor<T>,
+			who: AccountIdLookupOf<T>,
+			id: T::FreezeIdentifier,
+			#[pallet::compact] amount: T::Balance,
+			expires: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			<Self as fungible::MutateFreeze<_>>::set_freeze(&id, &who, amount)?;
+			if amount.is_zero() {
+				return Ok(())
+			}
+
+			// Setting the freeze removed any previous expiry.
+			if let Some(expires) = expires {
+				let now = frame_system::Pallet::<T>::block_number();
+				let expires = expires.max(now.saturating_add(One::one()));
+				FreezeExpiryAgenda::<T, I>::try_append(expires, (who.clone(), id))
+					.map_err(|_| Error::<T, I>::TooManyExpiringFreezes)?;
+				FreezeExpiry::<T, I>::insert(&who, &id, expires);
+			}
+			Ok(())
+		}
+
+		/// Set the total issuance to the sum of the total balances of all accounts.
+		///
+		/// Visits up to `max_accounts` accounts, resuming after the last account visited by the
+		/// previous call. Once all accounts were visited, the total issuance is set to their sum
+		/// and [`Event::TotalIssuanceForced`] is emitted. Whenever the balance of an account which
+		/// was already visited changes in the meantime, the reconciliation restarts from the
+		/// first account with the next call.
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::force_reconcile_total_issuance(*max_accounts))]
+		pub fn force_reconcile_total_issuance(
+			origin: OriginFor<T>,
+			max_accounts: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let (mut accounts, mut sum) = match IssuanceReconciliationProgress::<T, I>::get() {
+				Some(IssuanceReconciliation { last, sum }) => {
+					let from = system::Account::<T>::hashed_key_for(&last);
+					(system::Account::<T>::iter_keys_from(from), sum)
+				},
+				None => (system::Account::<T>::iter_keys(), Zero::zero()),
+			};
+
+			let mut visited = 0;
+			let mut last = None;
+			while visited < max_accounts {
+				let Some(who) = accounts.next() else {
+					let old = TotalIssuance::<T, I>::get();
+					Self::ensure_issuance_covers_inactive(old, sum)?;
+					TotalIssuance::<T, I>::set(sum);
+					Self::audit_issuance(
+						IssuanceAuditOperation::Forced,
+						None,
+						if sum >= old {
+							AdjustmentDirection::Increase
+						} else {
+							AdjustmentDirection::Decrease
+						},
+						sum.max(old) - sum.min(old),
+					);
+					IssuanceReconciliationProgress::<T, I>::kill();
+					Self::deposit_event(Event::<T, I>::TotalIssuanceForced { old, new: sum });
+					return Ok(Some(T::WeightInfo::force_reconcile_total_issuance(visited)).into())
+				};
+				sum = sum.saturating_add(Self::account(&who).total());
+				visited += 1;
+				last = Some(who);
+			}
+			if let Some(last) = last {
+				IssuanceReconciliationProgress::<T, I>::put(IssuanceReconciliation { last, sum });
+			}
+			Ok(Some(T::WeightInfo::force_reconcile_total_issuance(visited)).into())
+		}
+
+		/// Make the balance of an account consistent with its holds, freezes and locks.
+		///
+		/// The `reserved` balance is raised to the sum of the holds and the `frozen` balance is
+		/// set to the largest lock or freeze, fixing the consumer reference of the account along
+		/// the way, see [`Pallet::repair_account_data`]. An [`Event::Unexpected`] is emitted if
+		/// anything was repaired.
+		///
+		/// The dispatch origin for this call must be `Signed`. The fee is waived if the account
+		/// needed repairing.
+		#[pallet::call_index(15)]
+		pub fn repair_account(
+			origin: OriginFor<T>,
+			who: AccountIdLookupOf<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			if Self::repair_account_data(&who)? {
+				Ok(Pays::No.into())
+			} else {
+				Ok(Pays::Yes.into())
+			}
+		}
+
+		/// Suppress the `Deposit`, `Withdraw`, `Minted` and `Burned` events of `who`.
+		///
+		/// Meant for accounts touched by almost every extrinsic, like fee collectors. Their
+		/// balance changes are summed up into a single [`Event::SilentAccountActivity`] per block
+		/// instead. Transfers, holds and all other events of the account are still emitted.
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::add_silent_account())]
+		pub fn add_silent_account(
+			origin: OriginFor<T>,
+			who: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			SilentAccounts::<T, I>::insert(who, ());
+			Ok(())
+		}
+
+		/// Emit all events of `who` again, undoing [`Pallet::add_silent_account`].
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::remove_silent_account())]
+		pub fn remove_silent_account(
+			origin: OriginFor<T>,
+			who: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			SilentAccounts::<T, I>::remove(who);
+			Ok(())
+		}
+
+		/// Prevent any balance from being withdrawn from `who`, without touching the balance.
+		///
+		/// Transfers, burns, withdrawals (including those for transaction fees), new holds and
+		/// reserves of a restricted account fail with [`TokenError::Frozen`]. Deposits and
+		/// incoming transfers are still allowed. Holds are restricted as well, since held funds
+		/// may be transferred out of the account through the hold. Slashing and releasing
+		/// existing holds are unaffected.
+		///
+		/// The restriction is kept in the [`ExtraFlags`] of the account, so it is lost once the
+		/// account is reaped. Fails with [`Error::DeadAccount`] if `who` does not exist.
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::restrict_account())]
+		pub fn restrict_account(origin: OriginFor<T>, who: AccountIdLookupOf<T>) -> DispatchResult {
+			ensure_root(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			Self::set_restricted(&who, true)?;
+			Self::deposit_event(Event::AccountRestricted { who });
+			Ok(())
+		}
+
+		/// Allow balance to be withdrawn from `who` again, undoing [`Pallet::restrict_account`].
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::unrestrict_account())]
+		pub fn unrestrict_account(
+			origin: OriginFor<T>,
+			who: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			Self::set_restricted(&who, false)?;
+			Self::deposit_event(Event::AccountUnrestricted { who });
+			Ok(())
+		}
+
+		/// Move the whole balance of `from` to the new account `to`.
+		///
+		/// The `AccountData` is moved as is, together with all holds, freezes, locks and reserves
+		/// as well as the provider and consumer references this pallet holds for `from`. Since
+		/// nothing is minted or burned, the total issuance is unchanged. Fails with
+		/// [`Error::DeadAccount`] if `from` has no balance and with
+		/// [`Error::MigrationDestinationInUse`] if `to` has any balance, holds, freezes, locks or
+		/// reserves.
+		///
+		/// NOTE: Only the storage of this pallet is migrated. Storage of other pallets which is
+		/// keyed by `from`, e.g. the reasons behind its holds and locks, is out of scope and must
+		/// be migrated separately.
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::call_index(20)]
+		#[pallet::weight(
+			// The benchmark moves a single expiring freeze, account for the other ones.
+			T::WeightInfo::force_migrate_account().saturating_add(
+				T::DbWeight::get()
+					.reads_writes(2, 3)
+					.saturating_mul(T::MaxFreezes::get().saturating_sub(1).into())
+			)
+		)]
+		pub fn force_migrate_account(
+			origin: OriginFor<T>,
+			from: AccountIdLookupOf<T>,
+			to: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let from = T::Lookup::lookup(from)?;
+			let to = T::Lookup::lookup(to)?;
+			Self::do_migrate_account(&from, &to)?;
+			Self::deposit_event(Event::AccountMigrated { from, to });
+			Ok(())
+		}
+
+		/// Exactly as `transfer_allow_death`, but attaching a `remark` to the transfer, e.g. a
+		/// payment reference.
+		///
+		/// Emits [`Event::TransferWithRemark`] with the blake2-256 hash of the remark, in
+		/// addition to the regular [`Event::Transfer`]. A zero `value` is a no-op, the remark is
+		/// not deposited either.
+		#[pallet::call_index(21)]
+		#[pallet::weight(Pallet::<T, I>::remark_weight(
+			T::WeightInfo::transfer_allow_death(),
+			remark.len() as u32,
+		))]
+		pub fn transfer_allow_death_with_remark(
+			origin: OriginFor<T>,
+			dest: AccountIdLookupOf<T>,
+			#[pallet::compact] value: T::Balance,
+			remark: TransferRemark,
+		) -> DispatchResult {
+			let source = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+			if value.is_zero() {
+				return Ok(())
+			}
+			Self::ensure_min_transfer_amount(value)?;
+			<Self as fungible::Mutate<_>>::transfer(&source, &dest, value, Expendable)?;
+			Self::deposit_transfer_remark(source, dest, value, &remark);
+			Ok(())
+		}
+
+		/// Exactly as `transfer_keep_alive`, but attaching a `remark` to the transfer, e.g. a
+		/// payment reference.
+		///
+		/// Emits [`Event::TransferWithRemark`] with the blake2-256 hash of the remark, in
+		/// addition to the regular [`Event::Transfer`]. A zero `value` is a no-op, the remark is
+		/// not deposited either.
+		#[pallet::call_index(22)]
+		#[pallet::weight(Pallet::<T, I>::remark_weight(
+			T::WeightInfo::transfer_keep_alive(),
+			remark.len() as u32,
+		))]
+		pub fn transfer_keep_alive_with_remark(
+			origin: OriginFor<T>,
+			dest: AccountIdLookupOf<T>,
+			#[pallet::compact] value: T::Balance,
+			remark: TransferRemark,
+		) -> DispatchResult {
+			let source = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+			if value.is_zero() {
+				return Ok(())
+			}
+			Self::ensure_min_transfer_amount(value)?;
+			Self::ensure_withdrawable(&source, value, Preserve)?;
+			<Self as fungible::Mutate<_>>::transfer(&source, &dest, value, Preserve)?;
+			Self::deposit_transfer_remark(source, dest, value, &remark);
+			Ok(())
+		}
+
+		/// Turn the lock `id` of `who` into the freeze that [`Config::LockToFreezeMapping`] maps
+		/// it to.
+		///
+		/// The freeze is extended to the amount of the lock and no longer expires, so the `frozen`
+		/// balance of the account does not change. Fails with [`Error::LockNotConvertible`] if
+		/// no freeze is mapped to the lock and with [`Error::TooManyFreezes`] if the freeze has
+		/// to be added to an account which already has `MaxFreezes` freezes.
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::deprecate_lock())]
+		pub fn deprecate_lock(
+			origin: OriginFor<T>,
+			who: AccountIdLookupOf<T>,
+			id: LockIdentifier,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			let freeze =
+				T::LockToFreezeMapping::convert(id).ok_or(Error::<T, I>::LockNotConvertible)?;
+			let amount = Self::locks(&who)
+				.iter()
+				.find(|lock| lock.id == id)
+				.map(|lock| lock.amount)
+				.ok_or(Error::<T, I>::UnknownLock)?;
+
+			<Self as fungible::MutateFreeze<_>>::extend_freeze(&freeze, &who, amount)?;
+			FreezeExpiry::<T, I>::remove(&who, &freeze);
+			<Self as LockableCurrency<_>>::remove_lock(id, &who);
+
+			Self::deposit_event(Event::LockDeprecated { who, id, freeze, amount });
+			Ok(())
+		}
+
+		/// Burn the specified balance from the origin account, reducing total issuance.
+		///
+		/// With `from_hold` set to `None` this behaves exactly like [`Pallet::burn`] and burns
+		/// liquid free balance. With `Some(reason)` exactly `value` of the balance on hold for
+		/// `reason` is burned instead and [`Event::BurnedHeld`] is emitted; `keep_alive` is then
+		/// ignored since the free balance is left untouched. Fails with
+		/// [`Error::HoldNotBurnable`] if `reason` is not in [`Config::BurnableHolds`] and with
+		/// [`TokenError::FundsUnavailable`] if less than `value` is held for `reason`.
+		///
+		/// A zero `value` is a no-op in either case and emits no event.
+		#[pallet::call_index(24)]
+		#[pallet::weight(match (from_hold, keep_alive) {
+			(Some(_), _) => T::WeightInfo::burn_held(),
+			(None, true) => T::WeightInfo::burn_keep_alive(),
+			(None, false) => T::WeightInfo::burn_allow_death(),
+		})]
+		pub fn burn_with_hold(
+			origin: OriginFor<T>,
+			#[pallet::compact] value: T::Balance,
+			keep_alive: bool,
+			from_hold: Option<T::RuntimeHoldReason>,
+		) -> DispatchResult {
+			let source = ensure_signed(origin)?;
+			if value.is_zero() {
+				return Ok(())
+			}
+			let Some(reason) = from_hold else {
+				let preservation = if keep_alive { Preserve } else { Expendable };
+				<Self as fungible::Mutate<_>>::burn_from(
+					&source,
+					value,
+					preservation,
+					Precision::Exact,
+					Polite,
+				)?;
+				return Ok(())
+			};
+
+			use frame_support::traits::Contains;
+			ensure!(T::BurnableHolds::contains(&reason), Error::<T, I>::HoldNotBurnable);
+			let held = <Self as fungible::InspectHold<_>>::balance_on_hold(&reason, &source);
+			ensure!(value <= held, TokenError::FundsUnavailable);
+			let amount = <Self as fungible::MutateHold<_>>::burn_held(
+				&reason,
+				&source,
+				value,
+				Precision::Exact,
+				Polite,
+			)?;
+			Self::deposit_event(Event::BurnedHeld { who: source, reason, amount });
+			Ok(())
+		}
+
+		/// Set the inactive issuance to `new`.
+		///
+		/// Fails with [`Error::InactiveAboveIssuance`] if `new` exceeds the total issuance. Emits
+		/// [`Event::InactiveIssuanceForced`].
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::force_set_inactive_issuance())]
+		pub fn force_set_inactive_issuance(
+			origin: OriginFor<T>,
+			#[pallet::compact] new: T::Balance,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			Self::do_force_set_inactive_issuance(new)
+		}
+
+		/// Adjust the inactive issuance in a saturating way.
+		///
+		/// Always needs a positive `delta`. Fails with [`Error::InactiveAboveIssuance`] if the
+		/// inactive issuance would exceed the total issuance. Emits
+		/// [`Event::InactiveIssuanceForced`].
+		///
+		/// The dispatch origin for this call is `root`.
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::force_adjust_inactive_issuance())]
+		pub fn force_adjust_inactive_issuance(
+			origin: OriginFor<T>,
+			direction: AdjustmentDirection,
+			#[pallet::compact] delta: T::Balance,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			ensure!(delta > Zero::zero(), Error::<T, I>::DeltaZero);
+
+			let old = InactiveIssuance::<T, I>::get();
+			let new = match direction {
+				AdjustmentDirection::Increase => old.saturating_add(delta),
+				AdjustmentDirection::Decrease => old.saturating_sub(delta),
+			};
+			Self::do_force_set_inactive_issuance(new)
+		}
+
+		/// Remove the freezes of the caller in `ManagedFreezes`, so that a following
+		/// `transfer_all` without `keep_alive` can reap the account.
+		///
+		/// Fails with [`Error::ClosureBlocked`], without removing any freeze, if the account has
+		/// any hold, lock, other freeze or reserve, as those are managed by other pallets. The
+		/// error tells how many of each are left. Emits [`Event::AccountClosurePrepared`].
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		#[pallet::call_index(27)]
+		#[pallet::weight(
+			// The benchmark removes a single expiring freeze, account for the other ones.
+			T::WeightInfo::prepare_account_closure().saturating_add(
+				T::DbWeight::get()
+					.writes(1)
+					.saturating_mul(T::MaxFreezes::get().saturating_sub(1).into())
+			)
+		)]
+		pub fn prepare_account_closure(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let blockers = Self::closure_blockers(&who);
+			ensure!(blockers.is_empty(), Error::<T, I>::ClosureBlocked(blockers));
+
+			// Only freezes in `ManagedFreezes` are left.
+			let freezes = Freezes::<T, I>::get(&who);
+			if !freezes.is_empty() {
+				Self::update_freezes(&who, BoundedSlice::truncate_from(&[]))?;
+				for freeze in freezes.iter() {
+					FreezeExpiry::<T, I>::remove(&who, &freeze.id);
+				}
+			}
+
+			Self::deposit_event(Event::AccountClosurePrepared {
+				who,
+				removed_freezes: freezes.len() as u32,
+			});
+			Ok(())
+		}
+	}
+
+	#[cfg(any(feature = "std", feature = "try-runtime"))]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Compare the authoritative account store to the one which is not in use.
+		///
+		/// Chains which switched `AccountStore` in the past may still carry balance data in the
+		/// store that is no longer read. This reports such entries, see [`StoreAuditReport`].
+		/// Default-valued and duplicate entries can be removed with `purge_ghost_accounts`.
+		///
+		/// Fails if [`Config::AuthoritativeStore`] does not match the store `AccountStore` reads
+		/// from.
+		pub fn audit_account_stores() -> Result<StoreAuditReport<T::AccountId>, DispatchError> {
+			let authoritative = T::AuthoritativeStore::get();
+			ensure!(
+				Self::probe_account_store()? == authoritative,
+				DispatchError::Other("`AuthoritativeStore` does not match `AccountStore`")
+			);
+			let authoritative_entries = match authoritative {
+				AccountStoreKind::Balances => Account::<T, I>::iter_keys().count(),
+				AccountStoreKind::System => system::Account::<T>::iter_keys().count(),
+			};
+			let mut report = StoreAuditReport {
+				authoritative,
+				authoritative_entries: authoritative_entries as u32,
+				ghost_entries: 0,
+				purgeable: 0,
+				orphaned: Vec::new(),
+				divergent: Vec::new(),
+			};
+
+			for who in Self::unused_store_keys(authoritative, None) {
+				let Some(entry) = Self::ghost_entry(&who, authoritative) else { continue };
+				report.ghost_entries.saturating_inc();
+				if entry.purgeable {
+					report.purgeable.saturating_inc();
+				}
+				if !entry.in_authoritative {
+					report.orphaned.push(who);
+				} else if !entry.purgeable {
+					report.divergent.push(who);
+				}
+			}
+			Ok(report)
+		}
+
+		/// The store [`Config::AccountStore`] reads the account data from.
+		///
+		/// Determined by writing a marker into [`Account`] and reading it back through the
+		/// account store, within a storage transaction which is rolled back.
+		fn probe_account_store() -> Result<AccountStoreKind, DispatchError> {
+			let probe = T::AccountId::decode(&mut sp_runtime::traits::TrailingZeroInput::zeroes())
+				.expect("infinite length input; no invalid inputs for type; qed");
+			with_transaction(|| {
+				let marker = if T::AccountStore::get(&probe).free.is_zero() {
+					One::one()
+				} else {
+					Zero::zero()
+				};
+				Account::<T, I>::insert(&probe, AccountData { free: marker, ..Default::default() });
+				let kind = if T::AccountStore::get(&probe).free == marker {
+					AccountStoreKind::Balances
+				} else {
+					AccountStoreKind::System
+				};
+				TransactionOutcome::Rollback(Ok(kind))
+			})
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		fn ed() -> T::Balance {
+			T::ExistentialDeposit::get()
+		}
+
+		/// Restart [`Pallet::force_reconcile_total_issuance`] if it already visited `who`.
+		///
+		/// Accounts are visited in the order of their keys in `frame_system::Account`, so `who`
+		/// was visited if its key is not greater than the key of the last visited account.
+		fn restart_reconciliation_if_visited(who: &T::AccountId) {
+			let Some(progress) = IssuanceReconciliationProgress::<T, I>::get() else { return };
+			if system::Account::<T>::hashed_key_for(who) <=
+				system::Account::<T>::hashed_key_for(&progress.last)
+			{
+				IssuanceReconciliationProgress::<T, I>::kill();
+			}
+		}
+
+		/// Set or clear the restriction flag of the existing account `who`.
+		fn set_restricted(who: &T::AccountId, restricted: bool) -> DispatchResult {
+			Self::try_mutate_account(who, |account, is_new| -> DispatchResult {
+				ensure!(!is_new, Error::<T, I>::DeadAccount);
+				account.flags.set_restricted(restricted);
+				Ok(())
+			})?;
+			Ok(())
+		}
+
+		/// Ensure that a total issuance changed from `old` to `new` still covers the inactive
+		/// issuance.
+		///
+		/// Fails with [`Error::IssuanceDeactivated`] if the inactive issuance already exceeded
+		/// `old` and `new` does not restore the invariant, and with
+		/// [`Error::IssuanceBelowInactive`] if `new` would fall below the inactive issuance.
+		fn ensure_issuance_covers_inactive(old: T::Balance, new: T::Balance) -> DispatchResult {
+			let inactive = InactiveIssuance::<T, I>::get();
+			if new >= inactive {
+				return Ok(())
+			}
+			if old < inactive {
+				return Err(Error::<T, I>::IssuanceDeactivated.into())
+			}
+			Err(Error::<T, I>::IssuanceBelowInactive.into())
+		}
+
+		/// Set the inactive issuance to `new`, which must not exceed the total issuance.
+		fn do_force_set_inactive_issuance(new: T::Balance) -> DispatchResult {
+			ensure!(new <= TotalIssuance::<T, I>::get(), Error::<T, I>::InactiveAboveIssuance);
+			let old = InactiveIssuance::<T, I>::get();
+			InactiveIssuance::<T, I>::set(new);
+			Self::deposit_event(Event::<T, I>::InactiveIssuanceForced { old, new });
+			Ok(())
+		}
+
+		/// Move the balance of `from` and everything attached to it to `to`, see
+		/// [`Pallet::force_migrate_account`].
+		fn do_migrate_account(from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+			ensure!(!Self::account(from).total().is_zero(), Error::<T, I>::DeadAccount);
+			let dest = Self::account(to);
+			ensure!(
+				dest.total().is_zero() &&
+					dest.frozen.is_zero() &&
+					Holds::<T, I>::get(to).is_empty() &&
+					Freezes::<T, I>::get(to).is_empty() &&
+					Locks::<T, I>::get(to).is_empty() &&
+					Reserves::<T, I>::get(to).is_empty(),
+				Error::<T, I>::MigrationDestinationInUse
+			);
+
+			Self::restart_reconciliation_if_visited(from);
+			Self::restart_reconciliation_if_visited(to);
+			Self::ensure_upgraded(from);
+			let data = T::AccountStore::get(from);
+
+			// Only the references held by this pallet move, following `try_mutate_account`.
+			let providers = data.free >= Self::existence_threshold(from) &&
+				Self::have_providers_or_no_zero_ed(from);
+			let consumers = !data.reserved.is_zero() || !data.frozen.is_zero();
+
+			// The references must be moved first, since the account store may require `to` to
+			// have a provider. This may reap `from`, which is why its data was read beforehand.
+			system::Pallet::<T>::transplant_refs(from, to, providers as u32, consumers as u32)?;
+			T::AccountStore::insert(to, data)?;
+			T::AccountStore::remove(from)?;
+
+			let holds = Holds::<T, I>::take(from);
+			if !holds.is_empty() {
+				Holds::<T, I>::insert(to, holds);
+			}
+			let freezes = Freezes::<T, I>::take(from);
+			if !freezes.is_empty() {
+				Freezes::<T, I>::insert(to, freezes);
+			}
+			let locks = Locks::<T, I>::take(from);
+			if !locks.is_empty() {
+				Locks::<T, I>::insert(to, locks);
+			}
+			let reserves = Reserves::<T, I>::take(from);
+			if !reserves.is_empty() {
+				Reserves::<T, I>::insert(to, reserves);
+			}
+
+			let expiries: Vec<_> = FreezeExpiry::<T, I>::drain_prefix(from).collect();
+			for (id, at) in expiries {
+				FreezeExpiryAgenda::<T, I>::mutate(at, |agenda| {
+					agenda
+						.iter_mut()
+						.filter(|(who, expiring)| who == from && *expiring == id)
+						.for_each(|(who, _)| *who = to.clone());
+				});
+				FreezeExpiry::<T, I>::insert(to, id, at);
+			}
+			if SilentAccounts::<T, I>::take(from).is_some() {
+				SilentAccounts::<T, I>::insert(to, ());
+			}
+			Ok(())
+		}
+
+		/// Ensure no balance may be withdrawn from `who` because of [`Pallet::restrict_account`].
+		pub(crate) fn ensure_unrestricted(who: &T::AccountId) -> DispatchResult {
+			ensure!(!Self::account(who).flags.is_restricted(), TokenError::Frozen);
+			Ok(())
+		}
+
+		/// Ensure that `amount` can be withdrawn from `who` with the given `preservation`.
+		///
+		/// Fails with the error matching [`Pallet::withdraw_failure_reason`]:
+		/// - [`WithdrawBlocked::InsufficientFree`]: [`TokenError::FundsUnavailable`].
+		/// - [`WithdrawBlocked::BlockedByHold`]: [`Error::LiquidityRestrictions`].
+		/// - [`WithdrawBlocked::BlockedByFreeze`]: [`TokenError::Frozen`].
+		/// - [`WithdrawBlocked::WouldDustBelowEd`]: [`TokenError::NotExpendable`].
+		fn ensure_withdrawable(
+			who: &T::AccountId,
+			amount: T::Balance,
+			preservation: Preservation,
+		) -> DispatchResult {
+			match Self::withdraw_failure_reason(who, amount, preservation) {
+				WithdrawBlocked::Ok => Ok(()),
+				WithdrawBlocked::InsufficientFree { .. } =>
+					Err(TokenError::FundsUnavailable.into()),
+				WithdrawBlocked::BlockedByHold { .. } =>
+					Err(Error::<T, I>::LiquidityRestrictions.into()),
+				WithdrawBlocked::BlockedByFreeze { .. } => Err(TokenError::Frozen.into()),
+				WithdrawBlocked::WouldDustBelowEd { .. } => Err(TokenError::NotExpendable.into()),
+			}
+		}
+
+		/// Ensure `value` is not below [`Config::MinTransferAmount`].
+		fn ensure_min_transfer_amount(value: T::Balance) -> DispatchResult {
+			ensure!(value >= T::MinTransferAmount::get(), Error::<T, I>::TransferTooSmall);
+			Ok(())
+		}
+
+		/// The weight of a transfer weighing `transfer` which carries a remark of `remark_len`
+		/// bytes. Hashing the remark and depositing its event is covered by the
+		/// `remark_with_event` weight of `frame_system`.
+		fn remark_weight(transfer: Weight, remark_len: u32) -> Weight {
+			transfer.saturating_add(
+				<T::SystemWeightInfo as frame_system::WeightInfo>::remark_with_event(remark_len),
+			)
+		}
+
+		/// The weight of an `upgrade_accounts` call which upgraded `upgraded` accounts and skipped
+		/// `skipped` accounts which did not need to be upgraded.
+		pub(crate) fn upgrade_accounts_weight(upgraded: u32, skipped: u32) -> Weight {
+			let skipping = T::WeightInfo::upgrade_accounts_skipped(skipped)
+				.saturating_sub(T::WeightInfo::upgrade_accounts_skipped(0));
+			T::WeightInfo::upgrade_accounts(upgraded)
+				.saturating_add(skipping)
+				.min(T::WeightInfo::upgrade_accounts(upgraded.saturating_add(skipped)))
+		}
+
+		/// Deposit [`Event::TransferWithRemark`] for a transfer which carried `remark`.
+		fn deposit_transfer_remark(
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: T::Balance,
+			remark: &TransferRemark,
+		) {
+			let remark_hash = sp_io::hashing::blake2_256(remark);
+			Self::deposit_event(Event::TransferWithRemark { from, to, amount, remark_hash });
+		}
+
+		/// Ensure the account `who` is using the new logic.
+		///
+		/// Returns `true` if the account did get upgraded, `false` if it didn't need upgrading.
+		pub fn ensure_upgraded(who: &T::AccountId) -> bool {
+			let mut a = T::AccountStore::get(who);
+			if a.flags.is_new_logic() {
+				return false
+			}
+			a.flags.set_new_logic();
+			if !a.reserved.is_zero() && a.frozen.is_zero() {
+				if system::Pallet::<T>::providers(who) == 0 {
+					// Gah!! We have no provider refs :(
+					// This shouldn't practically happen, but we need a failsafe anyway: let's give
+					// them enough for an ED.
+					log::warn!(
+						target: LOG_TARGET,
+						"account with a non-zero reserve balance has no provider refs, account_id: '{:?}'.",
+						who
+					);
+					a.free = a.free.max(Self::ed());
+					system::Pallet::<T>::inc_providers(who);
+					Self::restart_reconciliation_if_visited(who);
+				}
+				let _ = system::Pallet::<T>::inc_consumers_without_limit(who).defensive();
 			}
 			// Should never fail - we're only setting a bit.
 			let _ = T::AccountStore::try_mutate_exists(who, |account| -> DispatchResult {
@@ -855,6 +2203,173 @@ pub mod pallet {
 			return true
 		}
 
+		/// Upgrade as many accounts as `remaining_weight` allows, continuing from the stored
+		/// cursor, if [`AutoUpgradeEnabled`] is set.
+		///
+		/// Accounts are taken from `frame_system::Account`, which holds an entry for every account
+		/// with a provider, and each is charged as one account of `upgrade_accounts`.
+		pub(crate) fn auto_upgrade_accounts(remaining_weight: Weight) -> Weight {
+			let mut meter = WeightMeter::with_limit(remaining_weight);
+			if meter.try_consume(T::DbWeight::get().reads(1)).is_err() ||
+				!AutoUpgradeEnabled::<T, I>::get()
+			{
+				return meter.consumed()
+			}
+			if meter.try_consume(T::DbWeight::get().reads_writes(1, 1)).is_err() {
+				return meter.consumed()
+			}
+
+			let per_account = T::WeightInfo::upgrade_accounts(1);
+			let mut accounts = match AutoUpgradeCursor::<T, I>::get() {
+				Some(last) => system::Account::<T>::iter_keys_from(
+					system::Account::<T>::hashed_key_for(&last),
+				),
+				None => system::Account::<T>::iter_keys(),
+			};
+			let mut last = None;
+			while meter.can_consume(per_account) {
+				let Some(who) = accounts.next() else {
+					AutoUpgradeEnabled::<T, I>::kill();
+					AutoUpgradeCursor::<T, I>::kill();
+					return meter.consumed()
+				};
+				meter.consume(per_account);
+				Self::ensure_upgraded(&who);
+				last = Some(who);
+			}
+			if let Some(who) = last {
+				AutoUpgradeCursor::<T, I>::put(who);
+			}
+			meter.consumed()
+		}
+
+		/// Thaw the freezes expiring in block `n`.
+		///
+		/// At most [`Config::MaxExpiringPerBlock`] freezes are visited.
+		pub(crate) fn thaw_expired_freezes(n: BlockNumberFor<T>) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let mut weight = db_weight.reads(1);
+			if !FreezeExpiryAgenda::<T, I>::contains_key(n) {
+				return weight
+			}
+
+			weight.saturating_accrue(db_weight.reads_writes(1, 1));
+			for (who, id) in FreezeExpiryAgenda::<T, I>::take(n) {
+				weight.saturating_accrue(db_weight.reads(1));
+				if FreezeExpiry::<T, I>::get(&who, &id) != Some(n) {
+					continue
+				}
+				// Removes the expiry as well.
+				let _ = <Self as fungible::MutateFreeze<_>>::thaw(&id, &who).defensive();
+				weight.saturating_accrue(db_weight.reads_writes(3, 3));
+			}
+			weight
+		}
+
+		/// Whether the events about the balance of `who` are suppressed.
+		///
+		/// If so, the change of its balance by `amount` in `direction` is added to the
+		/// [`BlockSilentActivity`] instead.
+		pub(crate) fn silenced(
+			who: &T::AccountId,
+			direction: AdjustmentDirection,
+			amount: T::Balance,
+		) -> bool {
+			if !SilentAccounts::<T, I>::contains_key(who) {
+				return false
+			}
+			BlockSilentActivity::<T, I>::mutate(|activity| {
+				let activity = activity.get_or_insert_with(Default::default);
+				activity.count.saturating_inc();
+				match direction {
+					AdjustmentDirection::Increase => activity.credited.saturating_accrue(amount),
+					AdjustmentDirection::Decrease => activity.debited.saturating_accrue(amount),
+				}
+			});
+			true
+		}
+
+		/// Emit the [`BlockSilentActivity`] as [`Event::SilentAccountActivity`], if any.
+		pub(crate) fn report_silent_activity() {
+			let Some(SilentActivity { count, credited, debited }) =
+				BlockSilentActivity::<T, I>::take()
+			else {
+				return
+			};
+			let (net_delta, direction) = if credited >= debited {
+				(credited - debited, AdjustmentDirection::Increase)
+			} else {
+				(debited - credited, AdjustmentDirection::Decrease)
+			};
+			Self::deposit_event(Event::<T, I>::SilentAccountActivity {
+				count,
+				net_delta,
+				direction,
+			});
+		}
+
+		/// The accounts of the store which is not `authoritative`, starting after `after`.
+		fn unused_store_keys(
+			authoritative: AccountStoreKind,
+			after: Option<&T::AccountId>,
+		) -> Box<dyn Iterator<Item = T::AccountId>> {
+			match (authoritative, after) {
+				(AccountStoreKind::System, Some(last)) =>
+					Box::new(Account::<T, I>::iter_keys_from(Account::<T, I>::hashed_key_for(last))),
+				(AccountStoreKind::System, None) => Box::new(Account::<T, I>::iter_keys()),
+				(AccountStoreKind::Balances, Some(last)) =>
+					Box::new(system::Account::<T>::iter_keys_from(
+						system::Account::<T>::hashed_key_for(last),
+					)),
+				(AccountStoreKind::Balances, None) => Box::new(system::Account::<T>::iter_keys()),
+			}
+		}
+
+		/// The balance data of `who` in the store which is not `authoritative`, if there is any.
+		///
+		/// For `frame_system::Account`, any data besides the default of `T::AccountData` counts,
+		/// including trailing bytes left behind by a change of `T::AccountData`, as long as it
+		/// decodes as [`AccountData`].
+		fn ghost_entry(who: &T::AccountId, authoritative: AccountStoreKind) -> Option<GhostEntry> {
+			let (ghost, in_authoritative, data) = match authoritative {
+				AccountStoreKind::System => {
+					if !Account::<T, I>::contains_key(who) {
+						return None
+					}
+					(
+						Account::<T, I>::get(who),
+						system::Account::<T>::contains_key(who),
+						T::AccountStore::get(who),
+					)
+				},
+				AccountStoreKind::Balances => {
+					let raw = unhashed::get_raw(&system::Account::<T>::hashed_key_for(who))?;
+					let mut cleared = system::Account::<T>::get(who);
+					cleared.data = Default::default();
+					if raw == cleared.encode() {
+						return None
+					}
+					let info =
+						system::AccountInfo::<T::Nonce, AccountData<T::Balance>>::decode_all(
+							&mut &raw[..],
+						)
+						.ok()?;
+					(info.data, Account::<T, I>::contains_key(who), Account::<T, I>::get(who))
+				},
+			};
+			let purgeable = ghost == Default::default() || (in_authoritative && ghost == data);
+			Some(GhostEntry { in_authoritative, purgeable })
+		}
+
+		/// Remove the balance data of `who` from the store which is not `authoritative`.
+		fn purge_ghost_entry(who: &T::AccountId, authoritative: AccountStoreKind) {
+			match authoritative {
+				AccountStoreKind::System => Account::<T, I>::remove(who),
+				AccountStoreKind::Balances =>
+					system::Account::<T>::mutate(who, |info| info.data = Default::default()),
+			}
+		}
+
 		/// Get the free balance of an account.
 		pub fn free_balance(who: impl sp_std::borrow::Borrow<T::AccountId>) -> T::Balance {
 			Self::account(who.borrow()).free
@@ -881,6 +2396,319 @@ pub mod pallet {
 			Self::account(who.borrow()).reserved
 		}
 
+		/// Transfer `value` of free balance from `source` to `dest`, deferring all secondary
+		/// effects until both accounts have been mutated.
+		///
+		/// Returns the amount transferred.
+		///
+		/// # Reentrancy contract
+		///
+		/// Callers which may be re-entered, such as contract execution environments, can rely on:
+		///
+		/// - Both account mutations are applied within a single storage layer. Either both of them
+		///   are committed, or neither is.
+		/// - [`Config::DustRemoval`] is only invoked after both accounts have been committed, so
+		///   any code re-entering this pallet from the dust handler observes the transfer as fully
+		///   applied, including when it transfers between the same accounts again.
+		/// - The `Transfer` event is deposited before the `Endowed`, `DustLost` and `ResidueSwept`
+		///   events of the source and destination accounts, in that order.
+		/// - No hook of this pallet runs while either account is mid-mutation.
+		///
+		/// The account reference counting of `frame_system` is not covered: the
+		/// `OnNewAccount` and `OnKilledAccount` hooks of `frame_system` are invoked when the
+		/// providers of an account change, which necessarily happens during the mutation.
+		pub fn transfer_no_reentry(
+			source: &T::AccountId,
+			dest: &T::AccountId,
+			value: T::Balance,
+			preservation: Preservation,
+		) -> Result<T::Balance, DispatchError> {
+			let reducible =
+				<Self as fungible::Inspect<_>>::reducible_balance(source, preservation, Polite);
+			ensure!(value <= reducible, TokenError::FundsUnavailable);
+			<Self as fungible::Inspect<_>>::can_deposit(dest, value, Provenance::Extant)
+				.into_result()?;
+			if source == dest {
+				return Ok(value)
+			}
+
+			let (source_effects, dest_effects) = with_storage_layer(|| {
+				let ((), source_effects) =
+					Self::try_mutate_account_deferring_effects(source, |account, _| {
+						account.free =
+							account.free.checked_sub(&value).ok_or(TokenError::FundsUnavailable)?;
+						Ok::<_, DispatchError>(())
+					})?;
+				let ((), dest_effects) =
+					Self::try_mutate_account_deferring_effects(dest, |account, _| {
+						account.free =
+							account.free.checked_add(&value).ok_or(ArithmeticError::Overflow)?;
+						Ok::<_, DispatchError>(())
+					})?;
+				Ok::<_, DispatchError>((source_effects, dest_effects))
+			})?;
+
+			// Both accounts are committed, from here on hooks may run.
+			Self::deposit_event(Event::Transfer {
+				from: source.clone(),
+				to: dest.clone(),
+				amount: value,
+			});
+			let source_dust = Self::deposit_mutation_events(source, source_effects);
+			let dest_dust = Self::deposit_mutation_events(dest, dest_effects);
+			for dust in source_dust.into_iter().chain(dest_dust) {
+				<Self as fungible::Unbalanced<_>>::handle_raw_dust(dust);
+			}
+
+			Ok(value)
+		}
+
+		/// Preview the effects of [`Pallet::force_set_balance`] setting the free balance of `who`
+		/// to `new_free`, without changing any state.
+		///
+		/// The balance is set within a storage layer which is rolled back. Returns the error the
+		/// dispatchable would fail with, if any.
+		pub fn simulate_force_set_balance(
+			who: &T::AccountId,
+			new_free: T::Balance,
+		) -> Result<ForceSetOutcome<T::Balance>, DispatchError> {
+			with_transaction(|| {
+				TransactionOutcome::Rollback(Self::do_force_set_balance(who, new_free))
+			})
+		}
+
+		/// The total balance of `who` excluding the balance on hold.
+		///
+		/// This is [`fungible::Inspect::total_balance`] minus
+		/// [`fungible::InspectHold::total_balance_on_hold`].
+		pub fn total_balance_excluding_holds(who: &T::AccountId) -> T::Balance {
+			Self::account(who).free
+		}
+
+		/// Preview [`Pallet::transfer_all`] from `who` with the given `keep_alive`.
+		///
+		/// Returns the balance that would be transferred and a breakdown of the balance that would
+		/// remain in the account. The call transfers exactly this balance.
+		pub fn transfer_all_preview(
+			who: &T::AccountId,
+			keep_alive: bool,
+		) -> (T::Balance, TransferAllRemainder<T::Balance>) {
+			let preservation = if keep_alive { Preserve } else { Expendable };
+			Self::reducible_breakdown(who, preservation, Polite)
+		}
+
+		/// Tell why withdrawing `amount` from `who` with the given `preservation` would fail.
+		///
+		/// This decomposes the [`fungible::Inspect::reducible_balance`] of `who`, so it returns
+		/// [`WithdrawBlocked::Ok`] exactly if `amount` does not exceed the reducible balance.
+		pub fn withdraw_failure_reason(
+			who: &T::AccountId,
+			amount: T::Balance,
+			preservation: Preservation,
+		) -> WithdrawBlocked<T::Balance> {
+			let (reducible, remainder) = Self::reducible_breakdown(who, preservation, Polite);
+			if amount <= reducible {
+				return WithdrawBlocked::Ok
+			}
+			let free = Self::account(who).free;
+			if amount > free {
+				return if amount <= free.saturating_add(remainder.held) {
+					WithdrawBlocked::BlockedByHold { held: remainder.held }
+				} else {
+					WithdrawBlocked::InsufficientFree { shortfall: amount - free }
+				}
+			}
+			let unfrozen = free - remainder.frozen_excess;
+			if amount > unfrozen {
+				return WithdrawBlocked::BlockedByFreeze { freeze_excess: amount - unfrozen }
+			}
+			WithdrawBlocked::WouldDustBelowEd { shortfall: amount - reducible }
+		}
+
+		/// The holds, locks, freezes and reserves which keep `who` from being closed through
+		/// [`Pallet::prepare_account_closure`].
+		pub fn closure_blockers(who: &T::AccountId) -> ClosureBlockers {
+			use frame_support::traits::Contains;
+			let count = |n: usize| n.min(u8::MAX as usize) as u8;
+
+			let holds = Holds::<T, I>::get(who);
+			let reserves = Reserves::<T, I>::get(who);
+			let unmanaged_freezes = Freezes::<T, I>::get(who)
+				.iter()
+				.filter(|f| !T::ManagedFreezes::contains(&f.id))
+				.count();
+			// Holds and named reserves are part of the reserved balance, the rest is reserved
+			// without a name.
+			let accounted = holds
+				.iter()
+				.map(|h| h.amount)
+				.chain(reserves.iter().map(|r| r.amount))
+				.fold(Zero::zero(), |sum: T::Balance, amount| sum.saturating_add(amount));
+			let unnamed_reserve = Self::account(who).reserved > accounted;
+
+			ClosureBlockers {
+				holds: count(holds.len()),
+				locks: count(Locks::<T, I>::get(who).len().saturating_add(unmanaged_freezes)),
+				reserves: count(reserves.len().saturating_add(unnamed_reserve as usize)),
+			}
+		}
+
+		/// A typed view of the locks of `who`.
+		///
+		/// Also tells whether the `frozen` balance of `who` is consistent with its locks and
+		/// freezes.
+		pub fn lock_summary(who: &T::AccountId) -> LockSummary<T::Balance> {
+			let locks = Self::locks(who).into_inner();
+			let max_amount = locks.iter().fold(Zero::zero(), |max, lock| lock.amount.max(max));
+			let reasons = locks.iter().map(|lock| lock.reasons).reduce(|a, b| a | b);
+			let frozen_consistent = Self::account(who).frozen == Self::frozen_requirement(who);
+			LockSummary { max_amount, reasons, locks, frozen_consistent }
+		}
+
+		/// Split the balance of `who` into its parts and the reducible balance under every
+		/// [`Preservation`] and [`Fortitude`].
+		///
+		/// This is the balance a wallet would show as transferable. It is computed with the same
+		/// arithmetic as [`fungible::Inspect::reducible_balance`], which keeps in place the larger
+		/// of the frozen balance not covered by holds and the existential deposit to retain:
+		///
+		/// `untouchable = max(frozen - on_hold, ed_required)`
+		///
+		/// For example, with an existential deposit of 10, an account with a free balance of 100,
+		/// 30 on hold and a freeze of 50 can reduce its free balance by `100 - max(50 - 30, 10) =
+		/// 80` with [`Preserve`] and [`Polite`]. If the hold grows to 45, only 5 of the free
+		/// balance remains frozen and the existential deposit decides: `100 - max(5, 10) = 90`.
+		/// With [`Force`] the freeze is ignored, so in both cases only the existential deposit has
+		/// to stay in the account.
+		pub fn spendable_breakdown(who: &T::AccountId) -> SpendableBreakdown<T::Balance> {
+			let a = Self::account(who);
+			let breakdown = |preservation| {
+				let ed_required = Self::required_ed(who, &a, preservation);
+				let reducible = |force| {
+					let untouchable = Self::frozen_beyond_holds(&a, force).max(ed_required);
+					a.free.saturating_sub(untouchable)
+				};
+				PreservationBreakdown {
+					ed_required,
+					polite: reducible(Polite),
+					force: reducible(Force),
+				}
+			};
+			SpendableBreakdown {
+				free: a.free,
+				frozen: a.frozen,
+				on_hold: a.reserved,
+				expendable: breakdown(Expendable),
+				preserve: breakdown(Preserve),
+				protect: breakdown(Protect),
+			}
+		}
+
+		/// Split the balance of `who` into the reducible balance and the balance that has to stay
+		/// in place, see [`fungible::Inspect::reducible_balance`].
+		///
+		/// This is shared by `reducible_balance` and [`Pallet::transfer_all_preview`], and builds
+		/// on the same parts as [`Pallet::spendable_breakdown`], so that they cannot diverge.
+		pub(crate) fn reducible_breakdown(
+			who: &T::AccountId,
+			preservation: Preservation,
+			force: Fortitude,
+		) -> (T::Balance, TransferAllRemainder<T::Balance>) {
+			let a = Self::account(who);
+			let frozen = Self::frozen_beyond_holds(&a, force);
+			let untouchable = frozen.max(Self::required_ed(who, &a, preservation));
+			let frozen_excess = frozen.min(a.free);
+			let ed_retained = untouchable.min(a.free).saturating_sub(frozen_excess);
+			// Liquid balance is what is neither on hold nor frozen/required for provider.
+			let reducible = a.free.saturating_sub(untouchable);
+			(reducible, TransferAllRemainder { ed_retained, held: a.reserved, frozen_excess })
+		}
+
+		/// The frozen balance of `a` which is not covered by its balance on hold, or zero if
+		/// freezes are ignored with `force`.
+		fn frozen_beyond_holds(a: &AccountData<T::Balance>, force: Fortitude) -> T::Balance {
+			if force == Polite {
+				// Frozen balance applies to total. Anything on hold therefore gets discounted from
+				// the limit given by the freezes.
+				a.frozen.saturating_sub(a.reserved)
+			} else {
+				Zero::zero()
+			}
+		}
+
+		/// The existential deposit `who` has to retain with `preservation`, or zero if the
+		/// account may be reaped.
+		fn required_ed(
+			who: &T::AccountId,
+			a: &AccountData<T::Balance>,
+			preservation: Preservation,
+		) -> T::Balance {
+			// If we want to keep our provider ref..
+			if preservation == Preserve
+				// ..or we don't want the account to die and our provider ref is needed for it to
+				// live..
+				|| preservation == Protect && !a.free.is_zero() &&
+					frame_system::Pallet::<T>::providers(who) == 1
+				// ..or we don't care about the account dying but our provider ref is required..
+				|| preservation == Expendable && !a.free.is_zero() &&
+					!frame_system::Pallet::<T>::can_dec_provider(who)
+			{
+				// ..then the ED needed.
+				T::ExistentialDeposit::get()
+			} else {
+				Zero::zero()
+			}
+		}
+
+		/// Set the free balance of `who` to `new_free`, or to zero if it is below the existential
+		/// deposit, and adjust the total issuance accordingly.
+		///
+		/// Returns the effects of doing so. It is shared by [`Pallet::force_set_balance`] and
+		/// [`Pallet::simulate_force_set_balance`], so that they cannot diverge.
+		pub(crate) fn do_force_set_balance(
+			who: &T::AccountId,
+			new_free: T::Balance,
+		) -> Result<ForceSetOutcome<T::Balance>, DispatchError> {
+			let new_free = if new_free < Self::ed() { Zero::zero() } else { new_free };
+			let old = Self::account(who);
+			let providers = frame_system::Pallet::<T>::providers(who) as i32;
+			let consumers = frame_system::Pallet::<T>::consumers(who) as i32;
+
+			let (_, dust) = Self::mutate_account(who, |account| account.free = new_free)?;
+			if let Some(dust) = dust {
+				<Self as fungible::Unbalanced<_>>::handle_raw_dust(dust);
+			}
+
+			// This will adjust the total issuance, which was not done by the `mutate_account`
+			// above, apart from the dust.
+			let (issuance_delta, issuance_direction) = if new_free >= old.free {
+				(new_free - old.free, AdjustmentDirection::Increase)
+			} else {
+				(old.free - new_free, AdjustmentDirection::Decrease)
+			};
+			if !issuance_delta.is_zero() {
+				match issuance_direction {
+					AdjustmentDirection::Increase =>
+						mem::drop(PositiveImbalance::<T, I>::new(issuance_delta)),
+					AdjustmentDirection::Decrease =>
+						mem::drop(NegativeImbalance::<T, I>::new(issuance_delta)),
+				}
+			}
+
+			let new = Self::account(who);
+			let existed = !old.free.is_zero() || !old.reserved.is_zero();
+			let exists = !new.free.is_zero() || !new.reserved.is_zero();
+			Ok(ForceSetOutcome {
+				new_free: new.free,
+				issuance_delta,
+				issuance_direction,
+				reaped: existed && !exists,
+				dust,
+				providers_delta: frame_system::Pallet::<T>::providers(who) as i32 - providers,
+				consumers_delta: frame_system::Pallet::<T>::consumers(who) as i32 - consumers,
+			})
+		}
+
 		/// Get both the free and reserved balances of an account.
 		pub(crate) fn account(who: &T::AccountId) -> AccountData<T::Balance> {
 			T::AccountStore::get(who)
@@ -956,11 +2784,68 @@ pub mod pallet {
 			true
 		}
 
-		/// Returns `true` when `who` has some providers or `insecure_zero_ed` feature is disabled.
-		/// Returns `false` otherwise.
+		/// Returns `true` when `who` has some providers, needs a non-zero balance to exist or
+		/// `insecure_zero_ed` feature is disabled. Returns `false` otherwise.
 		#[cfg(feature = "insecure_zero_ed")]
 		fn have_providers_or_no_zero_ed(who: &T::AccountId) -> bool {
-			frame_system::Pallet::<T>::providers(who) > 0
+			!Self::existence_threshold(who).is_zero() ||
+				frame_system::Pallet::<T>::providers(who) > 0
+		}
+
+		/// The free balance `who` needs to be provided for by this pallet.
+		///
+		/// This is the existential deposit.
+		#[cfg(not(feature = "insecure_zero_ed"))]
+		fn existence_threshold(_: &T::AccountId) -> T::Balance {
+			Self::ed()
+		}
+
+		/// The free balance `who` needs to be provided for by this pallet.
+		///
+		/// This is the existential deposit if `who` is sufficient, and at least one unit otherwise.
+		#[cfg(feature = "insecure_zero_ed")]
+		fn existence_threshold(who: &T::AccountId) -> T::Balance {
+			if Self::is_sufficient(who) {
+				Self::ed()
+			} else {
+				Self::ed().max(One::one())
+			}
+		}
+
+		/// Whether `who` may exist with a zero balance, see [`Config::AccountSufficiency`] and
+		/// [`SufficientAccounts`].
+		#[cfg(feature = "insecure_zero_ed")]
+		pub fn is_sufficient(who: &T::AccountId) -> bool {
+			use frame_support::traits::Contains;
+			T::AccountSufficiency::contains(who) || SufficientAccounts::<T, I>::contains_key(who)
+		}
+
+		/// Register `who` in [`SufficientAccounts`], or remove it.
+		///
+		/// Removing an account holding less than one unit of free balance gives up the provider
+		/// reference of this pallet, which fails if it is still needed. Accounts matched by
+		/// [`Config::AccountSufficiency`] stay sufficient either way.
+		#[cfg(feature = "insecure_zero_ed")]
+		pub fn set_account_sufficiency(who: &T::AccountId, sufficient: bool) -> DispatchResult {
+			if sufficient {
+				SufficientAccounts::<T, I>::insert(who, ());
+				return Ok(())
+			}
+			if !SufficientAccounts::<T, I>::contains_key(who) {
+				return Ok(())
+			}
+			if frame_system::Pallet::<T>::providers(who).is_zero() {
+				// The account does not exist, so there is no reference to give up.
+				SufficientAccounts::<T, I>::remove(who);
+				return Ok(())
+			}
+			// The account is mutated, so that its references follow the new threshold.
+			with_storage_layer(|| {
+				Self::try_mutate_account_handling_dust(who, |_, _| -> DispatchResult {
+					SufficientAccounts::<T, I>::remove(who);
+					Ok(())
+				})
+			})
 		}
 
 		/// Mutate an account to some new value, or delete it entirely with `None`. Will enforce
@@ -980,18 +2865,35 @@ pub mod pallet {
 			who: &T::AccountId,
 			f: impl FnOnce(&mut AccountData<T::Balance>, bool) -> Result<R, E>,
 		) -> Result<(R, Option<T::Balance>), E> {
+			Self::try_mutate_account_deferring_effects(who, f)
+				.map(|(result, effects)| (result, Self::deposit_mutation_events(who, effects)))
+		}
+
+		/// Same as [`Self::try_mutate_account`], but neither deposits any event nor returns the
+		/// dust. Instead, all secondary effects of the mutation are returned, so that they can be
+		/// applied through [`Self::deposit_mutation_events`] once the caller is done mutating.
+		pub(crate) fn try_mutate_account_deferring_effects<R, E: From<DispatchError>>(
+			who: &T::AccountId,
+			f: impl FnOnce(&mut AccountData<T::Balance>, bool) -> Result<R, E>,
+		) -> Result<(R, MutationEffects<T::Balance>), E> {
+			Self::restart_reconciliation_if_visited(who);
 			Self::ensure_upgraded(who);
-			let result = T::AccountStore::try_mutate_exists(who, |maybe_account| {
+			T::AccountStore::try_mutate_exists(who, |maybe_account| {
 				let is_new = maybe_account.is_none();
 				let mut account = maybe_account.take().unwrap_or_default();
-				let did_provide =
-					account.free >= Self::ed() && Self::have_providers_or_no_zero_ed(who);
+				let did_provide = account.free >= Self::existence_threshold(who) &&
+					Self::have_providers_or_no_zero_ed(who);
 				let did_consume =
 					!is_new && (!account.reserved.is_zero() || !account.frozen.is_zero());
 
+				let old_free = account.free;
 				let result = f(&mut account, is_new)?;
+				// Only reductions are swept, so that crediting the residue to an account through
+				// `DustRemoval` can never trigger another sweep.
+				let maybe_residue =
+					if account.free < old_free { Self::sweep_residue(&mut account) } else { None };
 
-				let does_provide = account.free >= Self::ed();
+				let does_provide = account.free >= Self::existence_threshold(who);
 				let does_consume = !account.reserved.is_zero() || !account.frozen.is_zero();
 
 				if !did_provide && does_provide {
@@ -1037,7 +2939,7 @@ pub mod pallet {
 				//
 				// We should never be dropping if reserved is non-zero. Reserved being non-zero
 				// should imply that we have a consumer ref, so this is economically safe.
-				let ed = Self::ed();
+				let ed = Self::existence_threshold(who);
 				let maybe_dust = if account.free < ed && account.reserved.is_zero() {
 					if account.free.is_zero() {
 						None
@@ -1051,22 +2953,61 @@ pub mod pallet {
 					*maybe_account = Some(account);
 					None
 				};
-				Ok((maybe_endowed, maybe_dust, result))
-			});
-			result.map(|(maybe_endowed, maybe_dust, result)| {
-				if let Some(endowed) = maybe_endowed {
-					Self::deposit_event(Event::Endowed {
-						account: who.clone(),
-						free_balance: endowed,
-					});
-				}
-				if let Some(amount) = maybe_dust {
-					Pallet::<T, I>::deposit_event(Event::DustLost { account: who.clone(), amount });
-				}
-				(result, maybe_dust)
+				let effects = MutationEffects {
+					endowed: maybe_endowed,
+					dust: maybe_dust,
+					residue: maybe_residue,
+				};
+				Ok((result, effects))
 			})
 		}
 
+		/// Deposit the events for the secondary `effects` of mutating the account of `who`.
+		///
+		/// Returns the amount which should be handled as dust.
+		pub(crate) fn deposit_mutation_events(
+			who: &T::AccountId,
+			effects: MutationEffects<T::Balance>,
+		) -> Option<T::Balance> {
+			if let Some(endowed) = effects.endowed {
+				Self::deposit_event(Event::Endowed { account: who.clone(), free_balance: endowed });
+			}
+			if let Some(amount) = effects.dust {
+				Pallet::<T, I>::deposit_event(Event::DustLost { account: who.clone(), amount });
+			}
+			if let Some(amount) = effects.residue {
+				Self::deposit_event(Event::ResidueSwept { who: who.clone(), amount });
+			}
+			// The residue has already been removed from the account; it leaves the system
+			// through the same path as dust.
+			effects.dust.or(effects.residue)
+		}
+
+		/// Remove the residue of `account.free` modulo `ResidueQuantum` if it does not exceed
+		/// `ResidueThreshold`.
+		///
+		/// Returns the amount removed, if any. The account is never taken below the existential
+		/// deposit or its frozen balance.
+		fn sweep_residue(account: &mut AccountData<T::Balance>) -> Option<T::Balance> {
+			let quantum = T::ResidueQuantum::get();
+			let threshold = T::ResidueThreshold::get();
+			if quantum.is_zero() || threshold.is_zero() {
+				return None
+			}
+			// The frozen balance applies to the total balance, so this is stricter than needed
+			// when some balance is on hold.
+			let floor = Self::ed().max(account.frozen);
+			if account.free <= floor {
+				return None
+			}
+			let residue = account.free % quantum;
+			if residue.is_zero() || residue > threshold || account.free - residue < floor {
+				return None
+			}
+			account.free -= residue;
+			Some(residue)
+		}
+
 		/// Update the account entry for `who`, given the locks.
 		pub(crate) fn update_locks(who: &T::AccountId, locks: &[BalanceLock<T::Balance>]) {
 			let bounded_locks = WeakBoundedVec::<_, T::MaxLocks>::force_from(
@@ -1080,6 +3021,11 @@ pub mod pallet {
 					"Warning: A user has more currency locks than expected. \
 					A runtime configuration adjustment may be needed."
 				);
+				LockOverflowCount::<T, I>::mutate(|count| *count = count.saturating_add(1));
+				Self::deposit_event(Event::LockLimitExceeded {
+					who: who.clone(),
+					count: locks.len() as u32,
+				});
 			}
 			let freezes = Freezes::<T, I>::get(who);
 			let mut prev_frozen = Zero::zero();
@@ -1097,9 +3043,10 @@ pub mod pallet {
 				}
 				after_frozen = b.frozen;
 			});
-			debug_assert!(res.is_ok());
-			if let Ok((_, maybe_dust)) = res {
-				debug_assert!(maybe_dust.is_none(), "Not altering main balance; qed");
+			match res {
+				Ok((_, None)) => {},
+				Ok((_, Some(_dust))) => Self::unexpected(who, UnexpectedKind::BalanceUpdated),
+				Err(_) => Self::unexpected(who, UnexpectedKind::FailedToMutateAccount),
 			}
 
 			match locks.is_empty() {
@@ -1134,7 +3081,9 @@ pub mod pallet {
 				}
 				after_frozen = b.frozen;
 			})?;
-			debug_assert!(maybe_dust.is_none(), "Not altering main balance; qed");
+			if maybe_dust.is_some() {
+				Self::unexpected(who, UnexpectedKind::BalanceUpdated);
+			}
 			if freezes.is_empty() {
 				Freezes::<T, I>::remove(who);
 			} else {
@@ -1150,6 +3099,147 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// The sum of the holds on the account of `who`.
+		pub(crate) fn held_total(who: &T::AccountId) -> T::Balance {
+			Holds::<T, I>::get(who)
+				.iter()
+				.fold(Zero::zero(), |sum: T::Balance, hold| sum.saturating_add(hold.amount))
+		}
+
+		/// The largest lock or freeze on the account of `who`, i.e. its expected `frozen` balance.
+		pub(crate) fn frozen_requirement(who: &T::AccountId) -> T::Balance {
+			let locks = Locks::<T, I>::get(who).into_iter().map(|lock| lock.amount);
+			let freezes = Freezes::<T, I>::get(who).into_iter().map(|freeze| freeze.amount);
+			locks.chain(freezes).fold(Zero::zero(), |max, amount| max.max(amount))
+		}
+
+		/// Make the `reserved` and `frozen` balances of `who` consistent with its holds, freezes
+		/// and locks.
+		///
+		/// `reserved` also covers reserves made through [`ReservableCurrency`], so it is only ever
+		/// raised to the sum of the holds. The difference is taken from the free balance above the
+		/// existential deposit as far as it goes, the rest is minted. `frozen` is set to the
+		/// largest lock or freeze. The consumer reference of the account is fixed by
+		/// [`Self::mutate_account`].
+		///
+		/// Returns whether anything was repaired, in which case an [`Event::Unexpected`] is
+		/// deposited.
+		pub fn repair_account_data(who: &T::AccountId) -> Result<bool, DispatchError> {
+			let held = Self::held_total(who);
+			let frozen = Self::frozen_requirement(who);
+			let consumers = system::Pallet::<T>::consumers(who);
+			// A repair reducing the free balance may sweep a residue, which leaves the system like
+			// any other dust.
+			let (changed, minted) = Self::mutate_account_handling_dust(who, |a| {
+				let missing = held.saturating_sub(a.reserved);
+				let moved = missing.min(a.free.saturating_sub(Self::ed()));
+				a.free = a.free.saturating_sub(moved);
+				a.reserved = a.reserved.saturating_add(missing);
+				let changed = !missing.is_zero() || a.frozen != frozen;
+				a.frozen = frozen;
+				(changed, missing.saturating_sub(moved))
+			})?;
+			if !minted.is_zero() {
+				TotalIssuance::<T, I>::mutate(|t| *t = t.saturating_add(minted));
+				Self::audit_issuance(
+					IssuanceAuditOperation::Forced,
+					Some(who),
+					AdjustmentDirection::Increase,
+					minted,
+				);
+			}
+
+			let repaired = changed || system::Pallet::<T>::consumers(who) != consumers;
+			if repaired {
+				Self::unexpected(who, UnexpectedKind::AccountRepaired);
+			}
+			Ok(repaired)
+		}
+
+		/// Report an unexpected situation concerning the account of `who`.
+		///
+		/// Deposits an [`Event::Unexpected`] and notifies [`Config::OnInconsistency`].
+		fn unexpected(who: &T::AccountId, kind: UnexpectedKind) {
+			log::error!(target: LOG_TARGET, "Unexpected {:?} for account {:?}.", kind, who);
+			Self::deposit_event(Event::Unexpected(kind.clone()));
+			match kind {
+				UnexpectedKind::BalanceUpdated => T::OnInconsistency::balance_updated(who),
+				UnexpectedKind::FailedToMutateAccount => T::OnInconsistency::failed_mutation(who),
+				// The inconsistency was resolved by the repair itself.
+				UnexpectedKind::AccountRepaired => {},
+				// Not concerning a single account, see `reconcile_issuance_audit`.
+				UnexpectedKind::IssuanceDrift => {},
+			}
+		}
+
+		/// Record a change of the total issuance in the [`BlockIssuanceAudit`].
+		///
+		/// A no-op unless the `issuance-audit` feature is enabled.
+		#[allow(unused_variables)]
+		pub(crate) fn audit_issuance(
+			operation: IssuanceAuditOperation,
+			who: Option<&T::AccountId>,
+			direction: AdjustmentDirection,
+			amount: T::Balance,
+		) {
+			#[cfg(feature = "issuance-audit")]
+			{
+				if amount.is_zero() {
+					return
+				}
+				BlockIssuanceAudit::<T, I>::mutate(|audit| {
+					if let Some(audit) = audit {
+						let who = who.cloned();
+						audit.record(IssuanceAuditEntry { operation, who, direction, amount });
+					}
+				});
+			}
+		}
+
+		/// Start the [`BlockIssuanceAudit`] of the current block.
+		#[cfg(feature = "issuance-audit")]
+		fn start_issuance_audit() -> Weight {
+			LastIssuanceAudit::<T, I>::kill();
+			BlockIssuanceAudit::<T, I>::put(IssuanceAudit::new(TotalIssuance::<T, I>::get()));
+			// Accounts for reconciling the audit in `on_finalize`.
+			T::DbWeight::get().reads_writes(3, 4)
+		}
+
+		/// Check that the [`TotalIssuance`] changed within the block as recorded by the
+		/// [`BlockIssuanceAudit`], which is moved into [`LastIssuanceAudit`].
+		///
+		/// Deposits an [`Event::Unexpected`] if it did not.
+		#[cfg(feature = "issuance-audit")]
+		fn reconcile_issuance_audit() {
+			let Some(audit) = BlockIssuanceAudit::<T, I>::take() else { return };
+			let issuance = TotalIssuance::<T, I>::get();
+			if !audit.reconciles(issuance) {
+				log::error!(
+					target: LOG_TARGET,
+					"Total issuance changed from {:?} to {:?}, but the audit recorded an increase \
+					of {:?} and a decrease of {:?}.",
+					audit.initial_issuance,
+					issuance,
+					audit.increased,
+					audit.decreased,
+				);
+				Self::deposit_event(Event::Unexpected(UnexpectedKind::IssuanceDrift));
+			}
+			LastIssuanceAudit::<T, I>::put(audit);
+		}
+
+		/// The [`LastIssuanceAudit`].
+		#[cfg(feature = "issuance-audit")]
+		pub fn last_issuance_audit() -> Option<IssuanceAudit<T::AccountId, T::Balance>> {
+			LastIssuanceAudit::<T, I>::get()
+		}
+
+		/// Always `None`, as the `issuance-audit` feature is disabled.
+		#[cfg(not(feature = "issuance-audit"))]
+		pub fn last_issuance_audit() -> Option<IssuanceAudit<T::AccountId, T::Balance>> {
+			None
+		}
+
 		/// Move the reserved balance of one account into the balance of another, according to
 		/// `status`. This will respect freezes/locks only if `fortitude` is `Polite`.
 		///
@@ -1221,5 +3311,50 @@ pub mod pallet {
 			});
 			Ok(actual)
 		}
+
+		/// Move the reserved balance of `slashed` into the balances of several `beneficiaries`,
+		/// each according to its [`Status`], as [`Pallet::do_transfer_reserved`] does for one.
+		///
+		/// With [`Precision::Exact`] the total is checked against the reducible reserved balance
+		/// of `slashed` up front. With [`Precision::BestEffort`] the beneficiaries are paid in
+		/// order until the reserved balance runs out. Either all moves apply or, if any of them
+		/// fails, e.g. because a beneficiary does not exist, none do.
+		///
+		/// Returns the total amount moved.
+		pub fn repatriate_reserved_split(
+			slashed: &T::AccountId,
+			beneficiaries: &[(T::AccountId, T::Balance, Status)],
+			precision: Precision,
+			fortitude: Fortitude,
+		) -> Result<T::Balance, DispatchError> {
+			if precision == Precision::Exact {
+				let total = beneficiaries
+					.iter()
+					.try_fold(T::Balance::zero(), |total, (_, value, _)| total.checked_add(value))
+					.ok_or(ArithmeticError::Overflow)?;
+				let max = <Self as fungible::InspectHold<_>>::reducible_total_balance_on_hold(
+					slashed, fortitude,
+				);
+				ensure!(total <= max, TokenError::FundsUnavailable);
+			}
+
+			with_transaction(|| {
+				let mut moved = T::Balance::zero();
+				for (beneficiary, value, status) in beneficiaries {
+					match Self::do_transfer_reserved(
+						slashed,
+						beneficiary,
+						*value,
+						precision,
+						fortitude,
+						*status,
+					) {
+						Ok(actual) => moved.saturating_accrue(actual),
+						Err(e) => return TransactionOutcome::Rollback(Err(e)),
+					}
+				}
+				TransactionOutcome::Commit(Ok(moved))
+			})
+		}
 	}
 }
@@ -101,3 +101,42 @@ impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for ResetInactive<T, I> {
 		}
 	}
 }
+
+/// Confirms that the flags of existing accounts keep their meaning after bit 126 of
+/// [`ExtraFlags`] was assigned to the restriction of an account.
+///
+/// No account could be restricted before, so the stored flags only ever had bit 127 set, which
+/// decodes unchanged. This migration does not touch storage; with `try-runtime` it verifies that
+/// no account carries the restriction flag before and after the upgrade.
+pub struct VerifyRestrictionFlag<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> VerifyRestrictionFlag<T, I> {
+	#[cfg(feature = "try-runtime")]
+	fn restricted_accounts() -> Result<usize, sp_runtime::TryRuntimeError> {
+		let accounts: Box<dyn Iterator<Item = T::AccountId>> = match T::AuthoritativeStore::get() {
+			AccountStoreKind::Balances => Box::new(Account::<T, I>::iter_keys()),
+			AccountStoreKind::System => Box::new(frame_system::Account::<T>::iter_keys()),
+		};
+		Ok(accounts
+			.filter(|who| Pallet::<T, I>::account(who).flags.is_restricted())
+			.count())
+	}
+}
+
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for VerifyRestrictionFlag<T, I> {
+	fn on_runtime_upgrade() -> Weight {
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		ensure!(Self::restricted_accounts()? == 0, "accounts are restricted before the upgrade");
+		Ok(Vec::new())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		ensure!(Self::restricted_accounts()? == 0, "accounts are restricted after the upgrade");
+		Ok(())
+	}
+}
@@ -0,0 +1,65 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the Balances pallet.
+
+use crate::{
+	ForceSetOutcome, IssuanceAudit, LockSummary, SpendableBreakdown, TransferAllRemainder,
+};
+use codec::Codec;
+use sp_runtime::DispatchError;
+
+sp_api::decl_runtime_apis! {
+	#[api_version(5)]
+	pub trait BalancesApi<AccountId, Balance>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Preview the effects of a `force_set_balance` of `who` to `new_free`.
+		///
+		/// Returns the error the call would fail with, if any.
+		fn simulate_force_set_balance(
+			who: AccountId,
+			new_free: Balance,
+		) -> Result<ForceSetOutcome<Balance>, DispatchError>;
+
+		/// Preview `transfer_all` from `who` with the given `keep_alive`.
+		///
+		/// Returns the balance that would be transferred and what would remain in the account.
+		#[api_version(2)]
+		fn transfer_all_preview(
+			who: AccountId,
+			keep_alive: bool,
+		) -> (Balance, TransferAllRemainder<Balance>);
+
+		/// The changes of the total issuance recorded within the last block.
+		///
+		/// Always `None` unless the runtime is built with the `issuance-audit` feature.
+		#[api_version(3)]
+		fn last_issuance_audit() -> Option<IssuanceAudit<AccountId, Balance>>;
+
+		/// A typed view of the locks of `who`, see [`crate::Pallet::lock_summary`].
+		#[api_version(4)]
+		fn lock_summary(who: AccountId) -> LockSummary<Balance>;
+
+		/// The balance of `who` split into its parts and the balance which could be transferred
+		/// under every preservation and fortitude, see [`crate::Pallet::spendable_breakdown`].
+		#[api_version(5)]
+		fn spendable_breakdown(who: AccountId) -> SpendableBreakdown<Balance>;
+	}
+}
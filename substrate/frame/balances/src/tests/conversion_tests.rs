@@ -0,0 +1,252 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for converting balances between instances with different decimals.
+
+use crate::{
+	self as pallet_balances, ConversionError, ConvertedAmount, CreditOf, DecimalsConversion,
+	TotalIssuance,
+};
+use frame_support::{
+	assert_noop, assert_ok, derive_impl,
+	instances::Instance1,
+	parameter_types,
+	traits::{
+		fungible::Inspect, tokens::Preservation, ConstU128, ConstU64, ConstU8, Imbalance,
+		OnUnbalanced, StorageMapShim,
+	},
+};
+use sp_runtime::{ArithmeticError, BuildStorage, TokenError};
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		Native: pallet_balances,
+		Bridged: pallet_balances::<Instance1>,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = u64;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU64<1>;
+	#[cfg(feature = "insecure_zero_ed")]
+	type AccountSufficiency = ();
+	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
+	type WeightInfo = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type OnInconsistency = ();
+	type ResidueQuantum = ();
+	type ResidueThreshold = ();
+	type MaxExpiringPerBlock = ();
+	type MinTransferAmount = ();
+	type LockToFreezeMapping = ();
+	type ManagedFreezes = ();
+	type BurnableHolds = ();
+	type LenientFreezeBound = ();
+	type DoneSlashHandler = ();
+}
+
+impl pallet_balances::Config<Instance1> for Runtime {
+	type Balance = u128;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU128<1>;
+	#[cfg(feature = "insecure_zero_ed")]
+	type AccountSufficiency = ();
+	type AccountStore = StorageMapShim<
+		pallet_balances::Account<Runtime, Instance1>,
+		u64,
+		pallet_balances::AccountData<u128>,
+	>;
+	type AuthoritativeStore = pallet_balances::BalancesStore;
+	type WeightInfo = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type OnInconsistency = ();
+	type ResidueQuantum = ();
+	type ResidueThreshold = ();
+	type MaxExpiringPerBlock = ();
+	type MinTransferAmount = ();
+	type LockToFreezeMapping = ();
+	type ManagedFreezes = ();
+	type BurnableHolds = ();
+	type LenientFreezeBound = ();
+	type DoneSlashHandler = ();
+}
+
+parameter_types! {
+	pub static ResidueReceived: u128 = 0;
+}
+
+/// Records the residue of conversions out of the bridged instance and burns it.
+pub struct RecordResidue;
+
+impl OnUnbalanced<CreditOf<Runtime, Instance1>> for RecordResidue {
+	fn on_nonzero_unbalanced(amount: CreditOf<Runtime, Instance1>) {
+		ResidueReceived::mutate(|r| *r += amount.peek());
+	}
+}
+
+/// The native instance uses 10 decimals, the bridged one 18.
+const QUANTUM: u128 = 100_000_000;
+
+type ToBridged = DecimalsConversion<Runtime, (), Instance1, ConstU8<10>, ConstU8<18>>;
+type ToNative = DecimalsConversion<Runtime, Instance1, (), ConstU8<18>, ConstU8<10>, RecordResidue>;
+
+fn new_test_ext(native: Vec<(u64, u64)>, bridged: Vec<(u64, u128)>) -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Runtime> { balances: native, ..Default::default() }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	pallet_balances::GenesisConfig::<Runtime, Instance1> {
+		balances: bridged,
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	ResidueReceived::set(0);
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Deterministic pseudo-random amounts.
+fn amounts() -> impl Iterator<Item = u128> {
+	(0u32..1_000).map(|i| u128::from_le_bytes(sp_core::hashing::blake2_128(&i.to_le_bytes())))
+}
+
+#[test]
+fn scaling_up_is_exact_and_round_trips() {
+	for x in amounts().map(|x| x as u64) {
+		let up = ToBridged::convert(x).unwrap();
+		assert_eq!(up, ConvertedAmount { value: x as u128 * QUANTUM, remainder: 0 });
+		assert_eq!(
+			ToBridged::convert_back(up.value).unwrap(),
+			ConvertedAmount { value: x, remainder: 0 }
+		);
+	}
+}
+
+#[test]
+fn scaling_down_round_trip_is_bounded_by_one_quantum() {
+	for x in amounts().map(|x| x % (u64::MAX as u128 * QUANTUM)) {
+		let down = ToNative::convert(x).unwrap();
+		let back = ToNative::convert_back(down.value).unwrap();
+		assert_eq!(back.remainder, 0);
+		assert!(back.value <= x);
+		assert!(x - back.value < QUANTUM);
+		assert_eq!(back.value + down.remainder, x);
+	}
+}
+
+#[test]
+fn equal_decimals_convert_unchanged() {
+	type Identity = DecimalsConversion<Runtime, (), Instance1, ConstU8<12>, ConstU8<12>>;
+	for x in amounts().map(|x| x as u64) {
+		assert_eq!(Identity::convert(x), Ok(ConvertedAmount { value: x as u128, remainder: 0 }));
+	}
+}
+
+#[test]
+fn overflow_is_rejected_at_the_extremes() {
+	// The converted amount does not fit into the native balance type.
+	assert_eq!(ToNative::convert(u128::MAX), Err(ConversionError::Overflow));
+	let limit = (u64::MAX as u128 + 1) * QUANTUM;
+	assert_eq!(ToNative::convert(limit), Err(ConversionError::Overflow));
+	assert_ok!(ToNative::convert(limit - 1));
+
+	// The scaled amount does not fit into `u128`.
+	type Wide = DecimalsConversion<Runtime, (), Instance1, ConstU8<0>, ConstU8<20>>;
+	assert_eq!(Wide::convert(u64::MAX), Err(ConversionError::Overflow));
+	assert_eq!(Wide::convert(1), Ok(ConvertedAmount { value: 10u128.pow(20), remainder: 0 }));
+
+	// The scaling factor itself does not fit into `u128`.
+	type Huge = DecimalsConversion<Runtime, (), Instance1, ConstU8<0>, ConstU8<39>>;
+	assert_eq!(Huge::convert(0), Err(ConversionError::Overflow));
+	type Tiny = DecimalsConversion<Runtime, Instance1, (), ConstU8<39>, ConstU8<0>>;
+	assert_eq!(Tiny::convert(u128::MAX), Ok(ConvertedAmount { value: 0, remainder: u128::MAX }));
+}
+
+#[test]
+fn convert_and_transfer_works_in_both_directions() {
+	new_test_ext(vec![(1, 100)], vec![(2, 100 * QUANTUM)]).execute_with(|| {
+		let amount = 5 * QUANTUM + 7;
+		assert_eq!(
+			ToNative::convert_and_transfer(&2, &3, amount, Preservation::Preserve),
+			Ok(ConvertedAmount { value: 5, remainder: 7 })
+		);
+		assert_eq!(Bridged::balance(&2), 95 * QUANTUM - 7);
+		assert_eq!(Native::balance(&3), 5);
+		assert_eq!(TotalIssuance::<Runtime, Instance1>::get(), 95 * QUANTUM - 7);
+		assert_eq!(TotalIssuance::<Runtime>::get(), 105);
+		assert_eq!(ResidueReceived::get(), 7);
+
+		assert_eq!(
+			ToBridged::convert_and_transfer(&1, &4, 10, Preservation::Preserve),
+			Ok(ConvertedAmount { value: 10 * QUANTUM, remainder: 0 })
+		);
+		assert_eq!(Native::balance(&1), 90);
+		assert_eq!(Bridged::balance(&4), 10 * QUANTUM);
+		assert_eq!(TotalIssuance::<Runtime>::get(), 95);
+		assert_eq!(TotalIssuance::<Runtime, Instance1>::get(), 105 * QUANTUM - 7);
+	});
+}
+
+#[test]
+fn convert_and_transfer_is_atomic() {
+	new_test_ext(vec![(1, u64::MAX - 10)], vec![(2, 100 * QUANTUM)]).execute_with(|| {
+		// The source cannot pay.
+		assert_noop!(
+			ToNative::convert_and_transfer(&2, &3, 101 * QUANTUM, Preservation::Expendable),
+			TokenError::FundsUnavailable
+		);
+		// Minting into the target instance overflows its total issuance, so the withdrawal from
+		// the source is reverted as well.
+		assert_noop!(
+			ToNative::convert_and_transfer(&2, &3, 11 * QUANTUM, Preservation::Expendable),
+			ArithmeticError::Overflow
+		);
+		// The amount does not fit into the target instance.
+		assert_noop!(
+			ToNative::convert_and_transfer(&2, &3, u128::MAX, Preservation::Expendable),
+			ArithmeticError::Overflow
+		);
+		assert_eq!(ResidueReceived::get(), 0);
+	});
+}
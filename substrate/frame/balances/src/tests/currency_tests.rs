@@ -18,14 +18,15 @@
 //! Tests regarding the functionality of the `Currency` trait set implementations.
 
 use super::*;
-use crate::{Event, NegativeImbalance};
+use crate::{Event, LockOverflowCount, NegativeImbalance, TryStateSample};
 use frame_support::{
 	traits::{
+		tokens::{Fortitude, Precision},
 		BalanceStatus::{Free, Reserved},
 		Currency,
 		ExistenceRequirement::{self, AllowDeath, KeepAlive},
-		Hooks, InspectLockableCurrency, LockIdentifier, LockableCurrency, NamedReservableCurrency,
-		ReservableCurrency, WithdrawReasons,
+		Get, Hooks, InspectLockableCurrency, LockIdentifier, LockableCurrency,
+		NamedReservableCurrency, ReservableCurrency, WithdrawReasons,
 	},
 	StorageNoopGuard,
 };
@@ -106,6 +107,38 @@ fn inspect_lock_should_work() {
 		})
 }
 
+#[test]
+fn dust_causing_lock_update_triggers_inconsistency_hook() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		InconsistencyTriggered::set(false);
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 50));
+
+		// Raising the ED leaves the account below it, so updating its locks dusts it.
+		ExistentialDeposit::set(100);
+		Balances::set_lock(ID_1, &1, 10, WithdrawReasons::all());
+
+		System::assert_has_event(RuntimeEvent::Balances(Event::Unexpected(
+			crate::UnexpectedKind::BalanceUpdated,
+		)));
+		assert!(InconsistencyTriggered::get());
+	});
+}
+
+#[test]
+fn lock_update_without_dust_does_not_trigger_inconsistency_hook() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		InconsistencyTriggered::set(false);
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 50));
+
+		Balances::set_lock(ID_1, &1, 10, WithdrawReasons::all());
+
+		assert!(!System::events()
+			.iter()
+			.any(|record| matches!(record.event, RuntimeEvent::Balances(Event::Unexpected(_)))));
+		assert!(!InconsistencyTriggered::get());
+	});
+}
+
 #[test]
 fn account_should_be_reaped() {
 	ExtBuilder::default()
@@ -308,6 +341,90 @@ fn lock_should_work_tx_fee() {
 		});
 }
 
+#[test]
+fn fee_events_carry_transaction_payment_context() {
+	ExtBuilder::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build_and_execute_with(|| {
+			pallet_transaction_payment::NextFeeMultiplier::<Test>::put(
+				Multiplier::saturating_from_integer(1),
+			);
+			let info = info_from_weight(Weight::from_parts(5, 0));
+			let pre = <ChargeTransactionPayment<Test> as SignedExtension>::pre_dispatch(
+				ChargeTransactionPayment::from(0),
+				&4,
+				CALL,
+				&info,
+				1,
+			)
+			.unwrap();
+			let fee = 40 - Balances::free_balance(4);
+			System::assert_has_event(RuntimeEvent::Balances(Event::Withdraw {
+				who: 4,
+				amount: fee,
+				context: Some(contexts::TRANSACTION_PAYMENT),
+			}));
+
+			// The overpaid fee is refunded within the same context.
+			let post_info = frame_support::dispatch::PostDispatchInfo {
+				actual_weight: Some(Weight::from_parts(2, 0)),
+				pays_fee: Default::default(),
+			};
+			assert_ok!(<ChargeTransactionPayment<Test> as SignedExtension>::post_dispatch(
+				Some(pre),
+				&info,
+				&post_info,
+				1,
+				&Ok(()),
+			));
+			System::assert_has_event(RuntimeEvent::Balances(Event::Deposit {
+				who: 4,
+				amount: 3,
+				context: Some(contexts::TRANSACTION_PAYMENT),
+			}));
+
+			// The context does not outlive the fee payment.
+			assert_eq!(crate::current_context(), None);
+			assert_ok!(Balances::withdraw(&4, 1, WithdrawReasons::TRANSFER, KeepAlive));
+			System::assert_last_event(RuntimeEvent::Balances(Event::Withdraw {
+				who: 4,
+				amount: 1,
+				context: None,
+			}));
+		});
+}
+
+#[test]
+fn nested_contexts_apply_innermost() {
+	ExtBuilder::default().existential_deposit(1).build_and_execute_with(|| {
+		with_context(contexts::XCM, || {
+			assert_ok!(<Balances as fungible::Mutate<_>>::mint_into(&1, 10));
+			with_context(contexts::BRIDGES, || {
+				assert_ok!(<Balances as fungible::Mutate<_>>::burn_from(
+					&1,
+					5,
+					Preservation::Expendable,
+					Precision::Exact,
+					Fortitude::Polite,
+				));
+			});
+			assert_eq!(crate::current_context(), Some(contexts::XCM));
+		});
+
+		System::assert_has_event(RuntimeEvent::Balances(Event::Minted {
+			who: 1,
+			amount: 10,
+			context: Some(contexts::XCM),
+		}));
+		System::assert_last_event(RuntimeEvent::Balances(Event::Burned {
+			who: 1,
+			amount: 5,
+			context: Some(contexts::BRIDGES),
+		}));
+	});
+}
+
 #[test]
 fn lock_block_number_extension_should_work() {
 	ExtBuilder::default()
@@ -402,6 +519,7 @@ fn reward_should_work() {
 		System::assert_last_event(RuntimeEvent::Balances(crate::Event::Deposit {
 			who: 1,
 			amount: 10,
+			context: None,
 		}));
 		assert_eq!(Balances::total_balance(&1), 20);
 		assert_eq!(Balances::total_issuance(), 120);
@@ -415,6 +533,7 @@ fn balance_works() {
 		System::assert_has_event(RuntimeEvent::Balances(crate::Event::Deposit {
 			who: 1,
 			amount: 42,
+			context: None,
 		}));
 		assert_eq!(Balances::free_balance(1), 42);
 		assert_eq!(Balances::reserved_balance(1), 0);
@@ -483,6 +602,7 @@ fn withdrawing_balance_should_work() {
 		System::assert_last_event(RuntimeEvent::Balances(crate::Event::Withdraw {
 			who: 2,
 			amount: 11,
+			context: None,
 		}));
 		assert_eq!(Balances::free_balance(2), 100);
 		assert_eq!(Balances::total_issuance(), 100);
@@ -617,6 +737,93 @@ fn transferring_reserved_balance_to_nonexistent_should_fail() {
 	});
 }
 
+#[test]
+fn repatriating_reserved_balance_split_should_work() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 111);
+		let _ = Balances::deposit_creating(&2, 1);
+		let _ = Balances::deposit_creating(&3, 1);
+		assert_ok!(Balances::reserve(&1, 110));
+
+		let beneficiaries = [(2, 40, Free), (1, 20, Free), (3, 30, Reserved)];
+		assert_ok!(
+			Balances::repatriate_reserved_split(
+				&1,
+				&beneficiaries,
+				Precision::Exact,
+				Fortitude::Polite,
+			),
+			90
+		);
+		assert_eq!(Balances::reserved_balance(1), 20);
+		assert_eq!(Balances::free_balance(1), 21);
+		assert_eq!(Balances::free_balance(2), 41);
+		assert_eq!(Balances::reserved_balance(3), 30);
+		System::assert_has_event(RuntimeEvent::Balances(crate::Event::ReserveRepatriated {
+			from: 1,
+			to: 2,
+			amount: 40,
+			destination_status: Free,
+		}));
+		System::assert_last_event(RuntimeEvent::Balances(crate::Event::ReserveRepatriated {
+			from: 1,
+			to: 3,
+			amount: 30,
+			destination_status: Reserved,
+		}));
+
+		// more than is reserved.
+		assert_noop!(
+			Balances::repatriate_reserved_split(
+				&1,
+				&[(2, 10, Free), (3, 11, Free)],
+				Precision::Exact,
+				Fortitude::Polite,
+			),
+			TokenError::FundsUnavailable
+		);
+		// the beneficiaries are paid in order until the reserved balance runs out.
+		assert_ok!(
+			Balances::repatriate_reserved_split(
+				&1,
+				&[(2, 10, Free), (3, 11, Free)],
+				Precision::BestEffort,
+				Fortitude::Polite,
+			),
+			20
+		);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(2), 51);
+		assert_eq!(Balances::free_balance(3), 11);
+	});
+}
+
+#[test]
+fn repatriating_reserved_balance_split_is_all_or_nothing() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 111);
+		let _ = Balances::deposit_creating(&2, 1);
+		let _ = Balances::deposit_creating(&4, 1);
+		assert_ok!(Balances::reserve(&1, 110));
+
+		// account 3 does not exist, so nothing is moved, not even to account 2.
+		for status in [Free, Reserved] {
+			assert_noop!(
+				Balances::repatriate_reserved_split(
+					&1,
+					&[(2, 40, Free), (3, 30, status), (4, 20, Free)],
+					Precision::Exact,
+					Fortitude::Polite,
+				),
+				Error::<Test, _>::DeadAccount
+			);
+		}
+		assert_eq!(Balances::reserved_balance(1), 110);
+		assert_eq!(Balances::free_balance(2), 1);
+		assert_eq!(Balances::free_balance(4), 1);
+	});
+}
+
 #[test]
 fn transferring_incomplete_reserved_balance_should_work() {
 	ExtBuilder::default().build_and_execute_with(|| {
@@ -713,7 +920,7 @@ fn burn_must_work() {
 fn cannot_set_genesis_value_below_ed() {
 	EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = 11);
 	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
-	let _ = crate::GenesisConfig::<Test> { balances: vec![(1, 10)] }
+	let _ = crate::GenesisConfig::<Test> { balances: vec![(1, 10)], ..Default::default() }
 		.assimilate_storage(&mut t)
 		.unwrap();
 }
@@ -722,9 +929,69 @@ fn cannot_set_genesis_value_below_ed() {
 #[should_panic = "duplicate balances in genesis."]
 fn cannot_set_genesis_value_twice() {
 	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
-	let _ = crate::GenesisConfig::<Test> { balances: vec![(1, 10), (2, 20), (1, 15)] }
-		.assimilate_storage(&mut t)
-		.unwrap();
+	let _ = crate::GenesisConfig::<Test> {
+		balances: vec![(1, 10), (2, 20), (1, 15)],
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+}
+
+#[test]
+fn genesis_legacy_accounts_are_built_with_old_logic() {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	crate::GenesisConfig::<Test> {
+		balances: vec![(1, 100), (2, 100)],
+		reserved_balances: vec![(1, 10), (2, 10)],
+		legacy_accounts: vec![1],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(Balances::total_issuance(), 220);
+
+		assert!(!Balances::account(&1).flags.is_new_logic());
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(System::consumers(&1), 0);
+
+		assert!(Balances::account(&2).flags.is_new_logic());
+		assert_eq!(Balances::reserved_balance(2), 10);
+		assert_eq!(System::consumers(&2), 1);
+
+		assert_ok!(Balances::upgrade_accounts(RuntimeOrigin::signed(2), vec![1]));
+		System::assert_last_event(RuntimeEvent::Balances(Event::Upgraded { who: 1 }));
+		assert!(Balances::account(&1).flags.is_new_logic());
+		assert_eq!(System::providers(&1), 1);
+		assert_eq!(System::consumers(&1), 1);
+	});
+}
+
+#[test]
+#[should_panic = "legacy accounts in genesis must be endowed accounts."]
+fn cannot_set_genesis_legacy_account_without_balance() {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let _ = crate::GenesisConfig::<Test> {
+		balances: vec![(1, 10)],
+		legacy_accounts: vec![2],
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+}
+
+#[test]
+#[should_panic = "reserved balances in genesis must belong to endowed accounts."]
+fn cannot_set_genesis_reserve_without_balance() {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let _ = crate::GenesisConfig::<Test> {
+		balances: vec![(1, 10)],
+		reserved_balances: vec![(2, 10)],
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
 }
 
 #[test]
@@ -1396,7 +1663,7 @@ fn self_transfer_noop() {
 		assert_eq!(
 			events(),
 			[
-				Event::Deposit { who: 1, amount: 100 }.into(),
+				Event::Deposit { who: 1, amount: 100, context: None }.into(),
 				SysEvent::NewAccount { account: 1 }.into(),
 				Event::Endowed { account: 1, free_balance: 100 }.into(),
 			]
@@ -1421,3 +1688,37 @@ fn self_transfer_noop() {
 		}
 	});
 }
+
+#[test]
+fn exceeding_max_locks_is_counted() {
+	ExtBuilder::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build_and_execute_with(|| {
+			let max_locks: u32 = <Test as Config>::MaxLocks::get();
+			for i in 0..max_locks as u64 {
+				Balances::set_lock(i.to_le_bytes(), &1, 1, WithdrawReasons::all());
+			}
+			assert_eq!(LockOverflowCount::<Test>::get(), 0);
+			assert_eq!(Balances::accounts_exceeding_max_locks(TryStateSample::Full), 0);
+
+			Balances::set_lock(*b"overflow", &1, 1, WithdrawReasons::all());
+			assert_eq!(Balances::locks(&1).len() as u32, max_locks + 1);
+			assert_eq!(LockOverflowCount::<Test>::get(), 1);
+			System::assert_last_event(RuntimeEvent::Balances(Event::LockLimitExceeded {
+				who: 1,
+				count: max_locks + 1,
+			}));
+
+			// The invariant is only reported, not enforced.
+			assert_eq!(Balances::accounts_exceeding_max_locks(TryStateSample::Full), 1);
+			assert_ok!(Balances::do_try_state(TryStateSample::Full));
+
+			// Every update beyond the bound is counted.
+			Balances::extend_lock(*b"overflow", &1, 2, WithdrawReasons::all());
+			assert_eq!(LockOverflowCount::<Test>::get(), 2);
+			Balances::remove_lock(*b"overflow", &1);
+			assert_eq!(LockOverflowCount::<Test>::get(), 2);
+			assert_eq!(Balances::accounts_exceeding_max_locks(TryStateSample::Full), 0);
+		});
+}
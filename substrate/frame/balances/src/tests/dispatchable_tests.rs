@@ -20,10 +20,22 @@
 use super::*;
 use crate::{
 	AdjustmentDirection::{Decrease as Dec, Increase as Inc},
-	Event,
+	ClosureBlockers, Event, ForceSetOutcome, FreezeExpiry, Holds, InactiveIssuance,
+	IssuanceReconciliationProgress, TransferAllRemainder, TransferRemark, TryStateSample,
+	UnexpectedKind, WithdrawBlocked, MAX_TRANSFER_REMARK_LEN, MIN_UPGRADES_FOR_FEE_WAIVER,
 };
-use frame_support::traits::{fungible::Unbalanced, tokens::Preservation::Expendable};
-use fungible::{hold::Mutate as HoldMutate, Inspect, Mutate};
+use frame_support::{
+	dispatch::Pays,
+	traits::{
+		fungible::Unbalanced,
+		tokens::{
+			Precision::Exact,
+			Preservation::{Expendable, Preserve},
+		},
+		LockIdentifier, LockableCurrency, NamedReservableCurrency, WithdrawReasons,
+	},
+};
+use fungible::{hold::Mutate as HoldMutate, Inspect, InspectHold, Mutate, MutateFreeze};
 
 /// Alice account ID for more readable tests.
 const ALICE: u64 = 1;
@@ -118,6 +130,124 @@ fn transfer_keep_alive_all_free_succeed() {
 	});
 }
 
+#[test]
+fn withdraw_failure_reason_works() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 100));
+		let reason = |amount| Balances::withdraw_failure_reason(&1, amount, Preserve);
+
+		assert_eq!(reason(90), WithdrawBlocked::Ok);
+		assert_eq!(reason(95), WithdrawBlocked::WouldDustBelowEd { shortfall: 5 });
+		assert_eq!(reason(101), WithdrawBlocked::InsufficientFree { shortfall: 1 });
+		// The existential deposit only needs to be kept if the account should be preserved.
+		assert_eq!(Balances::withdraw_failure_reason(&1, 100, Expendable), WithdrawBlocked::Ok);
+
+		// A freeze below the existential deposit leaves the existential deposit binding.
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &1, 5));
+		assert_eq!(reason(93), WithdrawBlocked::WouldDustBelowEd { shortfall: 3 });
+		assert_eq!(reason(96), WithdrawBlocked::BlockedByFreeze { freeze_excess: 1 });
+
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &1, 50));
+		assert_eq!(reason(50), WithdrawBlocked::Ok);
+		assert_eq!(reason(60), WithdrawBlocked::BlockedByFreeze { freeze_excess: 10 });
+		assert_eq!(reason(100), WithdrawBlocked::BlockedByFreeze { freeze_excess: 50 });
+
+		// The held balance counts towards the frozen balance.
+		assert_ok!(Balances::hold(&TestId::Foo, &1, 30));
+		assert_eq!(reason(50), WithdrawBlocked::Ok);
+		assert_eq!(reason(55), WithdrawBlocked::BlockedByFreeze { freeze_excess: 5 });
+		assert_eq!(reason(71), WithdrawBlocked::BlockedByHold { held: 30 });
+		assert_eq!(reason(100), WithdrawBlocked::BlockedByHold { held: 30 });
+		assert_eq!(reason(120), WithdrawBlocked::InsufficientFree { shortfall: 50 });
+	});
+}
+
+#[test]
+fn transfer_keep_alive_reports_what_blocks_the_transfer() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 100));
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &1, 50));
+		assert_ok!(Balances::hold(&TestId::Foo, &1, 30));
+
+		assert_noop!(Balances::transfer_keep_alive(Some(1).into(), 2, 55), TokenError::Frozen);
+		assert_noop!(
+			Balances::transfer_keep_alive(Some(1).into(), 2, 71),
+			Error::<Test>::LiquidityRestrictions,
+		);
+		assert_noop!(
+			Balances::transfer_keep_alive(Some(1).into(), 2, 101),
+			TokenError::FundsUnavailable,
+		);
+
+		assert_ok!(Balances::thaw(&TestId::Foo, &1));
+		assert_noop!(
+			Balances::transfer_keep_alive(Some(1).into(), 2, 61),
+			TokenError::NotExpendable,
+		);
+		assert_ok!(Balances::transfer_keep_alive(Some(1).into(), 2, 60));
+		assert_eq!(Balances::free_balance(1), 10);
+	});
+}
+
+#[test]
+fn transfer_with_empty_remark_works() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		let _ = Balances::mint_into(&1, 111);
+		let remark = TransferRemark::default();
+		assert_ok!(Balances::transfer_allow_death_with_remark(Some(1).into(), 2, 69, remark));
+		assert_eq!(Balances::total_balance(&1), 42);
+		assert_eq!(Balances::total_balance(&2), 69);
+		// The regular event is still emitted, right before the one carrying the remark hash.
+		let events = System::events().into_iter().map(|r| r.event).collect::<Vec<_>>();
+		assert_eq!(
+			events[events.len() - 2..],
+			[
+				RuntimeEvent::Balances(Event::Transfer { from: 1, to: 2, amount: 69 }),
+				RuntimeEvent::Balances(Event::TransferWithRemark {
+					from: 1,
+					to: 2,
+					amount: 69,
+					remark_hash: sp_io::hashing::blake2_256(&[]),
+				}),
+			]
+		);
+	});
+}
+
+#[test]
+fn transfer_with_max_length_remark_works() {
+	ExtBuilder::default().existential_deposit(1).build_and_execute_with(|| {
+		let _ = Balances::mint_into(&1, 100);
+		let remark = TransferRemark::try_from(vec![7; MAX_TRANSFER_REMARK_LEN as usize]).unwrap();
+		// A longer remark is rejected when decoding the call.
+		assert!(TransferRemark::try_from(vec![7; MAX_TRANSFER_REMARK_LEN as usize + 1]).is_err());
+		assert_noop!(
+			Balances::transfer_keep_alive_with_remark(Some(1).into(), 2, 100, remark.clone()),
+			TokenError::NotExpendable
+		);
+
+		assert_ok!(Balances::transfer_keep_alive_with_remark(
+			Some(1).into(),
+			2,
+			60,
+			remark.clone()
+		));
+		assert_eq!(Balances::total_balance(&1), 40);
+		assert_eq!(Balances::total_balance(&2), 60);
+		System::assert_has_event(RuntimeEvent::Balances(Event::Transfer {
+			from: 1,
+			to: 2,
+			amount: 60,
+		}));
+		System::assert_last_event(RuntimeEvent::Balances(Event::TransferWithRemark {
+			from: 1,
+			to: 2,
+			amount: 60,
+			remark_hash: sp_io::hashing::blake2_256(&remark),
+		}));
+	});
+}
+
 #[test]
 fn transfer_all_works_1() {
 	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
@@ -195,6 +325,124 @@ fn set_balance_handles_total_issuance() {
 	});
 }
 
+/// Simulate `force_set_balance` of `who` to `new_free`, dispatch it and check that the simulated
+/// outcome matches the actual effects.
+fn assert_force_set_simulation_matches(who: u64, new_free: u64) -> ForceSetOutcome<u64> {
+	let providers = System::providers(&who) as i32;
+	let consumers = System::consumers(&who) as i32;
+	let issuance = Balances::total_issuance();
+	let existed = System::account_exists(&who);
+
+	let outcome = Balances::simulate_force_set_balance(&who, new_free).unwrap();
+	assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), who, new_free));
+
+	assert_eq!(Balances::free_balance(&who), outcome.new_free);
+	let expected_issuance = match outcome.issuance_direction {
+		Inc => issuance + outcome.issuance_delta,
+		Dec => issuance - outcome.issuance_delta,
+	};
+	assert_eq!(Balances::total_issuance(), expected_issuance);
+	assert_eq!(outcome.reaped, existed && !System::account_exists(&who));
+	assert_eq!(outcome.dust, None);
+	assert_eq!(System::providers(&who) as i32, providers + outcome.providers_delta);
+	assert_eq!(System::consumers(&who) as i32, consumers + outcome.consumers_delta);
+	outcome
+}
+
+#[test]
+fn simulate_force_set_balance_wipeout() {
+	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 1_000));
+
+		let outcome = assert_force_set_simulation_matches(1, 50);
+		assert_eq!(
+			outcome,
+			ForceSetOutcome {
+				new_free: 0,
+				issuance_delta: 1_000,
+				issuance_direction: Dec,
+				reaped: true,
+				dust: None,
+				providers_delta: -1,
+				consumers_delta: 0,
+			}
+		);
+	});
+}
+
+#[test]
+fn simulate_force_set_balance_increase() {
+	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 1_000));
+		let outcome = assert_force_set_simulation_matches(1, 2_500);
+		assert_eq!((outcome.issuance_delta, outcome.issuance_direction), (1_500, Inc));
+		assert_eq!(outcome.providers_delta, 0);
+
+		// Endowing a new account adds a provider.
+		let outcome = assert_force_set_simulation_matches(2, 500);
+		assert_eq!((outcome.issuance_delta, outcome.issuance_direction), (500, Inc));
+		assert_eq!(outcome.providers_delta, 1);
+		assert!(!outcome.reaped);
+	});
+}
+
+#[test]
+fn simulate_force_set_balance_decrease() {
+	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 1_000));
+
+		let outcome = assert_force_set_simulation_matches(1, 100);
+		assert_eq!(outcome.new_free, 100);
+		assert_eq!((outcome.issuance_delta, outcome.issuance_direction), (900, Dec));
+		assert_eq!(outcome.providers_delta, 0);
+		assert!(!outcome.reaped);
+	});
+}
+
+#[test]
+fn simulate_force_set_balance_noop() {
+	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 1_000));
+		let outcome = assert_force_set_simulation_matches(1, 1_000);
+		assert_eq!(outcome.issuance_delta, 0);
+		assert_eq!((outcome.providers_delta, outcome.consumers_delta), (0, 0));
+
+		// Setting a non-existent account below ED does not create or reap anything.
+		let outcome = assert_force_set_simulation_matches(2, 50);
+		assert_eq!(outcome.new_free, 0);
+		assert_eq!(outcome.issuance_delta, 0);
+		assert!(!outcome.reaped);
+	});
+}
+
+#[test]
+fn simulate_force_set_balance_with_reserves() {
+	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 1_000));
+		assert_ok!(<Balances as frame_support::traits::ReservableCurrency<_>>::reserve(&1, 300));
+
+		// The reserve holds a consumer reference, so the last provider cannot be removed.
+		assert_eq!(
+			Balances::simulate_force_set_balance(&1, 50),
+			Err(DispatchError::ConsumerRemaining)
+		);
+		assert_noop!(
+			Balances::force_set_balance(RuntimeOrigin::root(), 1, 50),
+			DispatchError::ConsumerRemaining
+		);
+
+		// With another provider the free balance is wiped out, but the account is kept alive by
+		// its reserves.
+		System::inc_providers(&1);
+		let outcome = assert_force_set_simulation_matches(1, 50);
+		assert_eq!(outcome.new_free, 0);
+		assert_eq!((outcome.issuance_delta, outcome.issuance_direction), (700, Dec));
+		assert_eq!(outcome.providers_delta, -1);
+		assert!(!outcome.reaped);
+		assert_eq!(Balances::reserved_balance(1), 300);
+	});
+}
+
 #[test]
 fn upgrade_accounts_should_work() {
 	ExtBuilder::default()
@@ -230,6 +478,86 @@ fn upgrade_accounts_should_work() {
 		});
 }
 
+/// Creates the accounts `who` with a reserved balance, using the old logic.
+fn create_old_accounts(who: impl IntoIterator<Item = u64>) {
+	for who in who {
+		System::inc_providers(&who);
+		assert_ok!(<Test as Config>::AccountStore::try_mutate_exists(
+			&who,
+			|a| -> DispatchResult {
+				*a = Some(AccountData {
+					free: 5,
+					reserved: 5,
+					frozen: Zero::zero(),
+					flags: crate::types::ExtraFlags::old_logic(),
+				});
+				Ok(())
+			}
+		));
+	}
+}
+
+#[test]
+fn upgrade_accounts_only_charges_skipped_accounts_for_checking_them() {
+	use crate::weights::WeightInfo;
+
+	ExtBuilder::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build_and_execute_with(|| {
+			create_old_accounts(100..110);
+			let weights = <Test as Config>::WeightInfo::upgrade_accounts;
+			let skipped_weights = <Test as Config>::WeightInfo::upgrade_accounts_skipped;
+
+			// 10 accounts are upgraded, 1 and 2 are already upgraded.
+			let who = (100..110).chain([1, 2]).collect::<Vec<_>>();
+			let post_info = Balances::upgrade_accounts(Some(1).into(), who).unwrap();
+			let actual_weight = post_info.actual_weight.unwrap();
+			let skipping = skipped_weights(2).saturating_sub(skipped_weights(0));
+			assert_eq!(actual_weight, weights(10).saturating_add(skipping));
+			assert_eq!(actual_weight, Balances::upgrade_accounts_weight(10, 2));
+			assert!(actual_weight.all_lt(weights(12)));
+
+			// Only checking accounts is charged if none of them needed an upgrade.
+			let post_info = Balances::upgrade_accounts(Some(1).into(), vec![100, 1]).unwrap();
+			let skipping = skipped_weights(2).saturating_sub(skipped_weights(0));
+			assert_eq!(post_info.actual_weight, Some(weights(0).saturating_add(skipping)));
+			assert_eq!(post_info.pays_fee, Pays::Yes);
+
+			// The actual weight never exceeds the declared one.
+			assert!(Balances::upgrade_accounts_weight(0, 1_000).all_lte(weights(1_000)));
+		});
+}
+
+#[test]
+fn upgrade_accounts_fee_waiver_requires_enough_upgrades() {
+	ExtBuilder::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build_and_execute_with(|| {
+			let upgrade =
+				|who: Vec<u64>| Balances::upgrade_accounts(Some(1).into(), who).unwrap().pays_fee;
+
+			// All accounts are upgraded, but there are too few of them.
+			create_old_accounts(100..100 + MIN_UPGRADES_FOR_FEE_WAIVER as u64 - 1);
+			let who = (100..100 + MIN_UPGRADES_FOR_FEE_WAIVER as u64 - 1).collect();
+			assert_eq!(upgrade(who), Pays::Yes);
+
+			// A single account which is already upgraded is not free either.
+			assert_eq!(upgrade(vec![1]), Pays::Yes);
+
+			// Enough accounts are upgraded, padded within the 10% of churn.
+			create_old_accounts(200..200 + MIN_UPGRADES_FOR_FEE_WAIVER as u64);
+			let who = (200..200 + MIN_UPGRADES_FOR_FEE_WAIVER as u64).chain([1]).collect();
+			assert_eq!(upgrade(who), Pays::No);
+
+			// Enough accounts are upgraded, but padded beyond the 10% of churn.
+			create_old_accounts(300..300 + MIN_UPGRADES_FOR_FEE_WAIVER as u64);
+			let who = (300..300 + MIN_UPGRADES_FOR_FEE_WAIVER as u64).chain([1, 2]).collect();
+			assert_eq!(upgrade(who), Pays::Yes);
+		});
+}
+
 #[test]
 #[docify::export]
 fn force_adjust_total_issuance_example() {
@@ -327,7 +655,7 @@ fn force_adjust_total_issuance_rejects_more_than_inactive() {
 		// Errors with more than 48:
 		assert_noop!(
 			Balances::force_adjust_total_issuance(RawOrigin::Root.into(), Dec, 1),
-			Error::<Test>::IssuanceDeactivated,
+			Error::<Test>::IssuanceBelowInactive,
 		);
 		// Increasing again increases the inactive issuance:
 		assert_ok!(Balances::force_adjust_total_issuance(RawOrigin::Root.into(), Inc, 10),);
@@ -336,6 +664,251 @@ fn force_adjust_total_issuance_rejects_more_than_inactive() {
 	});
 }
 
+/// The accounts of the monied mock in the order in which they are reconciled.
+fn reconciliation_order() -> Vec<u64> {
+	frame_system::Account::<Test>::iter_keys().collect()
+}
+
+#[test]
+fn force_reconcile_total_issuance_works() {
+	ExtBuilder::default().monied(true).build_and_execute_with(|| {
+		TotalIssuance::<Test>::set(1337);
+
+		assert_ok!(Balances::force_reconcile_total_issuance(RawOrigin::Root.into(), 10));
+		assert_eq!(Balances::total_issuance(), 110);
+		assert_eq!(IssuanceReconciliationProgress::<Test>::get(), None);
+		System::assert_last_event(RuntimeEvent::Balances(Event::TotalIssuanceForced {
+			old: 1337,
+			new: 110,
+		}));
+	});
+}
+
+#[test]
+fn force_reconcile_total_issuance_resumes_from_cursor() {
+	ExtBuilder::default().monied(true).build_and_execute_with(|| {
+		let order = reconciliation_order();
+		assert_eq!(order.len(), 5);
+		TotalIssuance::<Test>::set(1337);
+
+		assert_ok!(Balances::force_reconcile_total_issuance(RawOrigin::Root.into(), 2));
+		let progress = IssuanceReconciliationProgress::<Test>::get().unwrap();
+		assert_eq!(progress.last, order[1]);
+		let sum = Balances::total_balance(&order[0]) + Balances::total_balance(&order[1]);
+		assert_eq!(progress.sum, sum);
+		assert_eq!(Balances::total_issuance(), 1337);
+
+		assert_ok!(Balances::force_reconcile_total_issuance(RawOrigin::Root.into(), 2));
+		assert_eq!(IssuanceReconciliationProgress::<Test>::get().unwrap().last, order[3]);
+		assert_eq!(Balances::total_issuance(), 1337);
+
+		assert_ok!(Balances::force_reconcile_total_issuance(RawOrigin::Root.into(), 2));
+		assert_eq!(IssuanceReconciliationProgress::<Test>::get(), None);
+		assert_eq!(Balances::total_issuance(), 110);
+	});
+}
+
+#[test]
+fn force_reconcile_total_issuance_restarts_when_visited_account_changes() {
+	ExtBuilder::default().monied(true).build_and_execute_with(|| {
+		let order = reconciliation_order();
+		TotalIssuance::<Test>::set(1337);
+
+		assert_ok!(Balances::force_reconcile_total_issuance(RawOrigin::Root.into(), 2));
+		// Changing an account which was not visited yet keeps the progress.
+		assert_ok!(Balances::mint_into(&order[4], 5));
+		assert!(IssuanceReconciliationProgress::<Test>::get().is_some());
+		// Changing an account which was already visited restarts the reconciliation.
+		assert_ok!(Balances::mint_into(&order[0], 5));
+		assert_eq!(IssuanceReconciliationProgress::<Test>::get(), None);
+
+		assert_ok!(Balances::force_reconcile_total_issuance(RawOrigin::Root.into(), 2));
+		assert_eq!(IssuanceReconciliationProgress::<Test>::get().unwrap().last, order[1]);
+		assert_ok!(Balances::force_reconcile_total_issuance(RawOrigin::Root.into(), 10));
+		assert_eq!(Balances::total_issuance(), 120);
+	});
+}
+
+#[test]
+fn force_reconcile_total_issuance_requires_root() {
+	ExtBuilder::default().monied(true).build_and_execute_with(|| {
+		assert_noop!(
+			Balances::force_reconcile_total_issuance(RawOrigin::Signed(ALICE).into(), 10),
+			BadOrigin,
+		);
+	});
+}
+
+#[test]
+fn force_reconcile_total_issuance_rejects_less_than_inactive() {
+	ExtBuilder::default().monied(true).build_and_execute_with(|| {
+		TotalIssuance::<Test>::set(1337);
+		Balances::deactivate(111u32.into());
+
+		assert_noop!(
+			Balances::force_reconcile_total_issuance(RawOrigin::Root.into(), 10),
+			Error::<Test>::IssuanceBelowInactive,
+		);
+
+		Balances::reactivate(1u32.into());
+		assert_ok!(Balances::force_reconcile_total_issuance(RawOrigin::Root.into(), 10));
+		assert_eq!(Balances::total_issuance(), 110);
+		assert_eq!(Balances::active_issuance(), 0);
+	});
+}
+
+#[test]
+fn force_adjust_total_issuance_rejects_already_deactivated_issuance() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1337, 64));
+		// The inactive issuance exceeds the total issuance, e.g. after a mis-accounting pallet.
+		InactiveIssuance::<Test>::set(80);
+
+		assert_noop!(
+			Balances::force_adjust_total_issuance(RawOrigin::Root.into(), Dec, 1),
+			Error::<Test>::IssuanceDeactivated,
+		);
+		// Increasing the issuance is rejected as long as it stays below the inactive issuance.
+		assert_noop!(
+			Balances::force_adjust_total_issuance(RawOrigin::Root.into(), Inc, 15),
+			Error::<Test>::IssuanceDeactivated,
+		);
+		// Increasing it up to the inactive issuance restores the invariant:
+		assert_ok!(Balances::force_adjust_total_issuance(RawOrigin::Root.into(), Inc, 16));
+		assert_eq!(Balances::total_issuance(), 80);
+		assert_eq!(Balances::active_issuance(), 0);
+		assert_noop!(
+			Balances::force_adjust_total_issuance(RawOrigin::Root.into(), Dec, 1),
+			Error::<Test>::IssuanceBelowInactive,
+		);
+	});
+}
+
+#[test]
+fn force_set_inactive_issuance_works() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1337, 64));
+
+		// Works up to the total issuance:
+		assert_ok!(Balances::force_set_inactive_issuance(RawOrigin::Root.into(), 64));
+		assert_eq!(Balances::inactive_issuance(), 64);
+		assert_eq!(Balances::active_issuance(), 0);
+		System::assert_last_event(RuntimeEvent::Balances(Event::InactiveIssuanceForced {
+			old: 0,
+			new: 64,
+		}));
+		// Errors above it:
+		assert_noop!(
+			Balances::force_set_inactive_issuance(RawOrigin::Root.into(), 65),
+			Error::<Test>::InactiveAboveIssuance,
+		);
+
+		assert_ok!(Balances::force_set_inactive_issuance(RawOrigin::Root.into(), 0));
+		assert_eq!(Balances::active_issuance(), 64);
+		System::assert_last_event(RuntimeEvent::Balances(Event::InactiveIssuanceForced {
+			old: 64,
+			new: 0,
+		}));
+	});
+}
+
+#[test]
+fn force_set_inactive_issuance_requires_root() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		assert_noop!(
+			Balances::force_set_inactive_issuance(RawOrigin::Signed(ALICE).into(), 0),
+			BadOrigin,
+		);
+		assert_noop!(
+			Balances::force_adjust_inactive_issuance(RawOrigin::Signed(ALICE).into(), Inc, 1),
+			BadOrigin,
+		);
+	});
+}
+
+#[test]
+fn force_set_inactive_issuance_restores_the_invariant() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1337, 64));
+		InactiveIssuance::<Test>::set(80);
+		assert!(Balances::do_try_state(TryStateSample::Full).is_err());
+
+		// Lowering the inactive issuance is rejected as long as it exceeds the total issuance.
+		assert_noop!(
+			Balances::force_adjust_inactive_issuance(RawOrigin::Root.into(), Dec, 15),
+			Error::<Test>::InactiveAboveIssuance,
+		);
+		assert_ok!(Balances::force_adjust_inactive_issuance(RawOrigin::Root.into(), Dec, 16));
+		assert_eq!(Balances::inactive_issuance(), 64);
+		assert_ok!(Balances::do_try_state(TryStateSample::Full));
+
+		// The total issuance can be adjusted again.
+		assert_ok!(Balances::force_adjust_total_issuance(RawOrigin::Root.into(), Inc, 1));
+	});
+}
+
+#[test]
+fn force_adjust_inactive_issuance_works() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1337, 64));
+		Balances::deactivate(16u32.into());
+
+		// Works up to the total issuance:
+		assert_ok!(Balances::force_adjust_inactive_issuance(RawOrigin::Root.into(), Inc, 48));
+		assert_eq!(Balances::inactive_issuance(), 64);
+		System::assert_last_event(RuntimeEvent::Balances(Event::InactiveIssuanceForced {
+			old: 16,
+			new: 64,
+		}));
+		// Errors above it:
+		assert_noop!(
+			Balances::force_adjust_inactive_issuance(RawOrigin::Root.into(), Inc, 1),
+			Error::<Test>::InactiveAboveIssuance,
+		);
+
+		// Decrement saturates:
+		assert_ok!(Balances::force_adjust_inactive_issuance(RawOrigin::Root.into(), Dec, 100));
+		assert_eq!(Balances::inactive_issuance(), 0);
+		// Increment saturates and is then rejected:
+		assert_noop!(
+			Balances::force_adjust_inactive_issuance(
+				RawOrigin::Root.into(),
+				Inc,
+				Balance::max_value(),
+			),
+			Error::<Test>::InactiveAboveIssuance,
+		);
+	});
+}
+
+#[test]
+fn force_adjust_inactive_issuance_rejects_zero_delta() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		assert_noop!(
+			Balances::force_adjust_inactive_issuance(RawOrigin::Root.into(), Inc, 0),
+			Error::<Test>::DeltaZero,
+		);
+		assert_noop!(
+			Balances::force_adjust_inactive_issuance(RawOrigin::Root.into(), Dec, 0),
+			Error::<Test>::DeltaZero,
+		);
+	});
+}
+
+#[test]
+fn try_state_rejects_inactive_above_total_issuance() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1337, 64));
+
+		InactiveIssuance::<Test>::set(64);
+		assert_ok!(Balances::do_try_state(TryStateSample::Full));
+
+		InactiveIssuance::<Test>::set(65);
+		assert!(format!("{:?}", Balances::do_try_state(TryStateSample::Full).unwrap_err())
+			.contains("`InactiveIssuance` exceeds `TotalIssuance`"));
+	});
+}
+
 #[test]
 fn burn_works() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -357,6 +930,7 @@ fn burn_works() {
 		System::assert_last_event(RuntimeEvent::Balances(Event::Burned {
 			who: account,
 			amount: burn_amount_1,
+			context: None,
 		}));
 		assert_eq!(Balances::total_issuance(), init_issuance - burn_amount_1);
 		assert_eq!(Balances::total_balance(&account), init_balance - burn_amount_1);
@@ -374,8 +948,644 @@ fn burn_works() {
 		System::assert_last_event(RuntimeEvent::Balances(Event::Burned {
 			who: account,
 			amount: burn_amount_2,
+			context: None,
 		}));
 		assert_eq!(Balances::total_issuance(), init_issuance - burn_amount_1 - burn_amount_2);
 		assert!(Balances::total_balance(&account).is_zero());
 	});
 }
+
+#[test]
+fn burn_with_hold_burns_the_whole_hold() {
+	ExtBuilder::default().build().execute_with(|| {
+		let account = 1;
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), account, 100));
+		assert_ok!(Balances::hold(&TestId::Foo, &account, 30));
+		assert_eq!(System::consumers(&account), 1);
+		let init_issuance = Balances::total_issuance();
+
+		assert_ok!(Balances::burn_with_hold(Some(account).into(), 30, true, Some(TestId::Foo)));
+		System::assert_last_event(RuntimeEvent::Balances(Event::BurnedHeld {
+			who: account,
+			reason: TestId::Foo,
+			amount: 30,
+		}));
+		assert!(Holds::<Test>::get(&account).is_empty());
+		assert_eq!(System::consumers(&account), 0);
+		assert_eq!(Balances::free_balance(&account), 70);
+		assert_eq!(Balances::total_issuance(), init_issuance - 30);
+	});
+}
+
+#[test]
+fn burn_with_hold_fails_above_the_held_balance() {
+	ExtBuilder::default().build().execute_with(|| {
+		let account = 1;
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), account, 100));
+		assert_ok!(Balances::hold(&TestId::Foo, &account, 30));
+
+		assert_noop!(
+			Balances::burn_with_hold(Some(account).into(), 31, true, Some(TestId::Foo)),
+			TokenError::FundsUnavailable,
+		);
+		assert_noop!(
+			Balances::burn_with_hold(Some(account).into(), 1, true, Some(TestId::Bar)),
+			TokenError::FundsUnavailable,
+		);
+	});
+}
+
+#[test]
+fn burn_with_hold_rejects_holds_which_are_not_burnable() {
+	ExtBuilder::default().build().execute_with(|| {
+		let account = 1;
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), account, 100));
+		assert_ok!(Balances::hold(&TestId::Baz, &account, 30));
+
+		assert_noop!(
+			Balances::burn_with_hold(Some(account).into(), 30, true, Some(TestId::Baz)),
+			Error::<Test>::HoldNotBurnable,
+		);
+		assert_eq!(Balances::balance_on_hold(&TestId::Baz, &account), 30);
+	});
+}
+
+#[test]
+fn burn_with_hold_without_reason_burns_free_balance() {
+	ExtBuilder::default().build().execute_with(|| {
+		let account = 1;
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), account, 100));
+		assert_ok!(Balances::hold(&TestId::Foo, &account, 30));
+
+		assert_ok!(Balances::burn_with_hold(Some(account).into(), 10, true, None));
+		System::assert_last_event(RuntimeEvent::Balances(Event::Burned {
+			who: account,
+			amount: 10,
+			context: None,
+		}));
+		assert_eq!(Balances::free_balance(&account), 60);
+		assert_eq!(Balances::balance_on_hold(&TestId::Foo, &account), 30);
+	});
+}
+
+#[test]
+fn transfers_below_min_transfer_amount_should_fail() {
+	ExtBuilder::default().monied(true).build_and_execute_with(|| {
+		MinTransferAmount::set(5);
+
+		assert_noop!(
+			Balances::transfer_allow_death(Some(3).into(), 1, 4),
+			Error::<Test>::TransferTooSmall,
+		);
+		assert_noop!(
+			Balances::transfer_keep_alive(Some(3).into(), 1, 4),
+			Error::<Test>::TransferTooSmall,
+		);
+
+		assert_ok!(Balances::transfer_allow_death(Some(3).into(), 1, 5));
+		assert_ok!(Balances::transfer_keep_alive(Some(3).into(), 1, 5));
+		assert_eq!(Balances::free_balance(1), 20);
+		assert_eq!(Balances::free_balance(3), 20);
+	});
+}
+
+#[test]
+fn zero_min_transfer_amount_allows_any_transfer() {
+	ExtBuilder::default().monied(true).build_and_execute_with(|| {
+		MinTransferAmount::set(0);
+
+		assert_ok!(Balances::transfer_allow_death(Some(3).into(), 1, 1));
+		assert_ok!(Balances::transfer_keep_alive(Some(3).into(), 1, 1));
+		assert_eq!(Balances::free_balance(1), 12);
+	});
+}
+
+#[test]
+fn transfer_all_below_min_transfer_amount_should_fail() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		MinTransferAmount::set(5);
+		Balances::set_balance(&1, 14);
+
+		// only 4 units are reducible when keeping the account alive.
+		assert_noop!(
+			Balances::transfer_all(Some(1).into(), 2, true),
+			Error::<Test>::TransferTooSmall,
+		);
+
+		// the whole balance is reducible otherwise.
+		assert_ok!(Balances::transfer_all(Some(1).into(), 2, false));
+		assert_eq!(Balances::free_balance(1), 0);
+		assert_eq!(Balances::free_balance(2), 14);
+
+		// exactly the minimum is reducible.
+		Balances::set_balance(&1, 15);
+		assert_ok!(Balances::transfer_all(Some(1).into(), 2, true));
+		assert_eq!(Balances::free_balance(1), 10);
+		assert_eq!(Balances::free_balance(2), 19);
+	});
+}
+
+#[test]
+fn force_transfer_and_fungible_transfer_bypass_min_transfer_amount() {
+	ExtBuilder::default().monied(true).build_and_execute_with(|| {
+		MinTransferAmount::set(5);
+
+		assert_ok!(Balances::force_transfer(RawOrigin::Root.into(), 3, 1, 1));
+		assert_ok!(<Balances as Mutate<_>>::transfer(&3, &1, 1, Expendable));
+		assert_eq!(Balances::free_balance(1), 12);
+		assert_eq!(Balances::free_balance(3), 28);
+	});
+}
+
+#[test]
+fn min_transfer_amount_is_exposed_in_metadata() {
+	MinTransferAmount::set(5);
+
+	let constant = Pallet::<Test>::pallet_constants_metadata()
+		.into_iter()
+		.find(|c| c.name == "MinTransferAmount")
+		.expect("`MinTransferAmount` is a pallet constant");
+	assert_eq!(constant.value, 5u64.encode());
+}
+
+/// Calls `transfer_all` from `ALICE` to 2 without `keep_alive` and checks that it reports
+/// `remaining` to be left in the account, or a complete transfer if `remaining` is zero.
+fn transfer_all_and_check_remaining(remaining: u64) {
+	System::reset_events();
+	let dest_balance = Balances::free_balance(2);
+	assert_ok!(Balances::transfer_all(Some(ALICE).into(), 2, false));
+	assert_eq!(Balances::total_balance(&ALICE), remaining);
+	let events = events();
+	assert!(events.contains(&RuntimeEvent::Balances(Event::TransferAll {
+		from: ALICE,
+		to: 2,
+		amount: Balances::free_balance(2) - dest_balance,
+		killed: remaining == 0,
+	})));
+	let incomplete = events.into_iter().find_map(|e| match e {
+		RuntimeEvent::Balances(Event::TransferAllIncomplete { who, remaining }) => {
+			assert_eq!(who, ALICE);
+			Some(remaining)
+		},
+		_ => None,
+	});
+	assert_eq!(incomplete, (remaining > 0).then_some(remaining));
+	ensure_ti_valid();
+}
+
+#[test]
+fn transfer_all_leaves_locked_balance() {
+	const ID: LockIdentifier = *b"1       ";
+
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		Balances::set_balance(&ALICE, 100);
+		Balances::set_lock(ID, &ALICE, 30, WithdrawReasons::all());
+
+		transfer_all_and_check_remaining(30);
+		assert_eq!(Balances::free_balance(ALICE), 30);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(2), 70);
+
+		// the remainder becomes transferable once unlocked.
+		Balances::remove_lock(ID, &ALICE);
+		transfer_all_and_check_remaining(0);
+		assert!(!System::account_exists(&ALICE));
+		assert_eq!(Balances::free_balance(2), 100);
+	});
+}
+
+#[test]
+fn transfer_all_leaves_frozen_balance() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		Balances::set_balance(&ALICE, 100);
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &ALICE, 30));
+
+		transfer_all_and_check_remaining(30);
+		assert_eq!(Balances::free_balance(ALICE), 30);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(2), 70);
+
+		// the remainder becomes transferable once thawed.
+		assert_ok!(Balances::thaw(&TestId::Foo, &ALICE));
+		transfer_all_and_check_remaining(0);
+		assert!(!System::account_exists(&ALICE));
+		assert_eq!(Balances::free_balance(2), 100);
+	});
+}
+
+#[test]
+fn transfer_all_leaves_held_balance_and_existential_deposit() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		Balances::set_balance(&ALICE, 100);
+		assert_ok!(Balances::hold(&TestId::Foo, &ALICE, 30));
+
+		// the hold needs a consumer reference, which keeps the existential deposit in place.
+		transfer_all_and_check_remaining(40);
+		assert_eq!(Balances::free_balance(ALICE), 10);
+		assert_eq!(Balances::reserved_balance(ALICE), 30);
+		assert_eq!(Balances::free_balance(2), 60);
+
+		// the remainder becomes transferable once released.
+		assert_ok!(Balances::release(&TestId::Foo, &ALICE, 30, Exact));
+		transfer_all_and_check_remaining(0);
+		assert!(!System::account_exists(&ALICE));
+		assert_eq!(Balances::free_balance(2), 100);
+	});
+}
+
+#[test]
+fn transfer_all_leaves_named_reserve_and_existential_deposit() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		Balances::set_balance(&ALICE, 100);
+		assert_ok!(Balances::reserve_named(&TestId::Foo, &ALICE, 30));
+
+		// the reserve needs a consumer reference, which keeps the existential deposit in place.
+		transfer_all_and_check_remaining(40);
+		assert_eq!(Balances::free_balance(ALICE), 10);
+		assert_eq!(Balances::reserved_balance(ALICE), 30);
+		assert_eq!(Balances::free_balance(2), 60);
+
+		// the remainder becomes transferable once unreserved.
+		assert_eq!(Balances::unreserve_named(&TestId::Foo, &ALICE, 30), 0);
+		transfer_all_and_check_remaining(0);
+		assert!(!System::account_exists(&ALICE));
+		assert_eq!(Balances::free_balance(2), 100);
+	});
+}
+
+#[test]
+fn transfer_all_with_keep_alive_does_not_report_remaining_balance() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		Balances::set_balance(&ALICE, 100);
+		assert_ok!(Balances::transfer_all(Some(ALICE).into(), 2, true));
+
+		assert_eq!(Balances::free_balance(ALICE), 10);
+		assert!(!events()
+			.iter()
+			.any(|e| matches!(e, RuntimeEvent::Balances(Event::TransferAllIncomplete { .. }))));
+	});
+}
+
+#[test]
+fn transfer_all_fails_without_transferable_balance() {
+	enum Setup {
+		Plain,
+		Freeze(u64),
+		Hold(u64),
+		HoldAndFreeze(u64, u64),
+	}
+	use Setup::*;
+
+	// setup, keep_alive, expected transferred amount and whether `ALICE` is reaped.
+	let cases = [
+		(Plain, false, Ok((100, true))),
+		(Plain, true, Ok((90, false))),
+		(Freeze(95), false, Ok((5, false))),
+		(Freeze(95), true, Ok((5, false))),
+		(Freeze(100), false, Err(Error::<Test>::NothingToTransfer)),
+		(Freeze(100), true, Err(Error::<Test>::NothingToTransfer)),
+		(Hold(80), false, Ok((10, false))),
+		(Hold(90), false, Err(Error::<Test>::NothingToTransfer)),
+		(HoldAndFreeze(40, 95), false, Ok((5, false))),
+		(HoldAndFreeze(40, 100), false, Err(Error::<Test>::NothingToTransfer)),
+		(HoldAndFreeze(90, 40), false, Err(Error::<Test>::NothingToTransfer)),
+	];
+	for (setup, keep_alive, expected) in cases {
+		ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+			Balances::set_balance(&ALICE, 100);
+			match setup {
+				Plain => {},
+				Freeze(frozen) => assert_ok!(Balances::set_freeze(&TestId::Foo, &ALICE, frozen)),
+				Hold(held) => assert_ok!(Balances::hold(&TestId::Foo, &ALICE, held)),
+				HoldAndFreeze(held, frozen) => {
+					assert_ok!(Balances::hold(&TestId::Foo, &ALICE, held));
+					assert_ok!(Balances::set_freeze(&TestId::Foo, &ALICE, frozen));
+				},
+			}
+			System::reset_events();
+
+			match expected {
+				Ok((amount, killed)) => {
+					assert_ok!(Balances::transfer_all(Some(ALICE).into(), 2, keep_alive));
+					assert_eq!(Balances::free_balance(2), amount);
+					assert_eq!(Balances::total_balance(&ALICE), 100 - amount);
+					assert_eq!(System::account_exists(&ALICE), !killed);
+					System::assert_has_event(
+						Event::TransferAll { from: ALICE, to: 2, amount, killed }.into(),
+					);
+				},
+				Err(error) => {
+					assert_noop!(Balances::transfer_all(Some(ALICE).into(), 2, keep_alive), error);
+					assert!(System::account_exists(&ALICE));
+				},
+			}
+		});
+	}
+}
+
+/// Previews `transfer_all` from `ALICE` to 2, checks that the preview matches the outcome of the
+/// call and returns the previewed remainder.
+fn transfer_all_matching_preview(keep_alive: bool) -> TransferAllRemainder<u64> {
+	let (transferable, remainder) = Balances::transfer_all_preview(&ALICE, keep_alive);
+	let TransferAllRemainder { ed_retained, held, frozen_excess } = remainder.clone();
+	let total = Balances::total_balance(&ALICE);
+	assert_eq!(transferable + ed_retained + held + frozen_excess, total);
+	assert_eq!(Balances::total_balance_excluding_holds(&ALICE), total - held);
+
+	let dest_balance = Balances::free_balance(2);
+	assert_ok!(Balances::transfer_all(Some(ALICE).into(), 2, keep_alive));
+	assert_eq!(Balances::free_balance(2), dest_balance + transferable);
+	assert_eq!(Balances::total_balance(&ALICE), ed_retained + held + frozen_excess);
+	remainder
+}
+
+#[test]
+fn transfer_all_preview_without_holds_or_freezes() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		Balances::set_balance(&ALICE, 100);
+		assert_eq!(
+			Balances::transfer_all_preview(&ALICE, true),
+			(90, TransferAllRemainder { ed_retained: 10, held: 0, frozen_excess: 0 })
+		);
+
+		assert_eq!(transfer_all_matching_preview(false), TransferAllRemainder::default());
+		assert!(!System::account_exists(&ALICE));
+	});
+}
+
+#[test]
+fn transfer_all_preview_with_holds() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		Balances::set_balance(&ALICE, 100);
+		assert_ok!(Balances::hold(&TestId::Foo, &ALICE, 30));
+
+		// the hold needs a consumer reference, which keeps the existential deposit in place.
+		assert_eq!(
+			Balances::transfer_all_preview(&ALICE, false),
+			(60, TransferAllRemainder { ed_retained: 10, held: 30, frozen_excess: 0 })
+		);
+		transfer_all_matching_preview(false);
+		assert_eq!(Balances::free_balance(2), 60);
+	});
+}
+
+#[test]
+fn transfer_all_preview_with_freezes() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		Balances::set_balance(&ALICE, 100);
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &ALICE, 30));
+
+		// the frozen balance covers the existential deposit.
+		assert_eq!(
+			Balances::transfer_all_preview(&ALICE, false),
+			(70, TransferAllRemainder { ed_retained: 0, held: 0, frozen_excess: 30 })
+		);
+		transfer_all_matching_preview(false);
+		assert_eq!(Balances::free_balance(2), 70);
+
+		// a freeze below the existential deposit only covers part of it.
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &ALICE, 5));
+		assert_eq!(
+			transfer_all_matching_preview(false),
+			TransferAllRemainder { ed_retained: 5, held: 0, frozen_excess: 5 }
+		);
+		assert_eq!(Balances::free_balance(2), 90);
+	});
+}
+
+#[test]
+fn transfer_all_preview_with_holds_and_freezes() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		Balances::set_balance(&ALICE, 100);
+		assert_ok!(Balances::hold(&TestId::Foo, &ALICE, 40));
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &ALICE, 70));
+
+		// the held balance counts towards the freeze.
+		assert_eq!(
+			Balances::transfer_all_preview(&ALICE, false),
+			(30, TransferAllRemainder { ed_retained: 0, held: 40, frozen_excess: 30 })
+		);
+		transfer_all_matching_preview(false);
+		assert_eq!(Balances::free_balance(2), 30);
+
+		// a freeze fully covered by the held balance leaves the existential deposit.
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &ALICE, 40));
+		assert_eq!(
+			transfer_all_matching_preview(false),
+			TransferAllRemainder { ed_retained: 10, held: 40, frozen_excess: 0 }
+		);
+		assert_eq!(Balances::free_balance(2), 50);
+	});
+}
+
+#[test]
+fn transfer_all_preview_retains_existential_deposit_for_consumers() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		Balances::set_balance(&ALICE, 100);
+		assert_ok!(System::inc_consumers(&ALICE));
+
+		// the account cannot be reaped while it has consumers, even without `keep_alive`.
+		assert_eq!(
+			transfer_all_matching_preview(false),
+			TransferAllRemainder { ed_retained: 10, held: 0, frozen_excess: 0 }
+		);
+		assert!(System::account_exists(&ALICE));
+		assert_eq!(Balances::free_balance(2), 90);
+
+		System::dec_consumers(&ALICE);
+		assert_eq!(transfer_all_matching_preview(false), TransferAllRemainder::default());
+		assert!(!System::account_exists(&ALICE));
+	});
+}
+
+/// Overwrite the account data of `ALICE` and drop its consumer reference, bypassing the pallet
+/// like a partially applied migration would.
+fn corrupt_alice(f: impl FnOnce(&mut AccountData<u64>)) {
+	assert_ok!(TestAccountStore::mutate(&ALICE, f));
+	System::dec_consumers(&ALICE);
+	assert_eq!(System::consumers(&ALICE), 0);
+}
+
+fn repair_alice() -> Pays {
+	let info = Balances::repair_account(RuntimeOrigin::signed(2), ALICE).unwrap();
+	info.pays_fee
+}
+
+#[test]
+fn repair_account_restores_reserved_balance_of_holds() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		Balances::set_balance(&ALICE, 100);
+		assert_ok!(Balances::hold(&TestId::Foo, &ALICE, 20));
+		assert_ok!(Balances::hold(&TestId::Bar, &ALICE, 10));
+		let issuance = Balances::total_issuance();
+
+		// the held funds were moved back to the free balance.
+		corrupt_alice(|a| {
+			a.free = 100;
+			a.reserved = 0;
+		});
+		assert!(format!("{:?}", Balances::do_try_state(TryStateSample::Full).unwrap_err())
+			.contains("Found `reserved` balance below the sum of the holds"));
+
+		assert_eq!(repair_alice(), Pays::No);
+		System::assert_has_event(RuntimeEvent::Balances(Event::Unexpected(
+			UnexpectedKind::AccountRepaired,
+		)));
+		assert_eq!(Balances::free_balance(&ALICE), 70);
+		assert_eq!(Balances::reserved_balance(&ALICE), 30);
+		assert_eq!(System::consumers(&ALICE), 1);
+		assert_eq!(Balances::total_issuance(), issuance);
+		assert_ok!(Balances::do_try_state(TryStateSample::Full));
+
+		// the account can no longer be reaped while holding funds.
+		assert_ok!(Balances::transfer_all(Some(ALICE).into(), 2, false));
+		assert!(System::account_exists(&ALICE));
+		assert_eq!(Balances::reserved_balance(&ALICE), 30);
+		assert_eq!(repair_alice(), Pays::Yes);
+	});
+}
+
+#[test]
+fn repair_account_mints_holds_not_covered_by_free_balance() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		Balances::set_balance(&ALICE, 100);
+		assert_ok!(Balances::hold(&TestId::Foo, &ALICE, 30));
+		let issuance = Balances::total_issuance();
+
+		corrupt_alice(|a| {
+			a.free = 20;
+			a.reserved = 0;
+		});
+
+		// only the free balance above the existential deposit is moved.
+		assert_eq!(repair_alice(), Pays::No);
+		assert_eq!(Balances::free_balance(&ALICE), 10);
+		assert_eq!(Balances::reserved_balance(&ALICE), 30);
+		assert_eq!(Balances::total_issuance(), issuance + 20);
+		assert_eq!(System::consumers(&ALICE), 1);
+		assert_ok!(Balances::do_try_state(TryStateSample::Full));
+	});
+}
+
+#[test]
+fn repair_account_restores_frozen_balance_of_locks_and_freezes() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		Balances::set_balance(&ALICE, 100);
+		Balances::set_lock(*b"lock    ", &ALICE, 50, WithdrawReasons::all());
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &ALICE, 30));
+
+		corrupt_alice(|a| a.frozen = 0);
+		assert!(format!("{:?}", Balances::do_try_state(TryStateSample::Full).unwrap_err())
+			.contains("Found `frozen` balance not matching the largest lock or freeze"));
+
+		assert_eq!(repair_alice(), Pays::No);
+		assert_eq!(Balances::account(&ALICE).frozen, 50);
+		assert_eq!(System::consumers(&ALICE), 1);
+		assert_ok!(Balances::do_try_state(TryStateSample::Full));
+
+		// a frozen balance above the largest lock or freeze is lowered.
+		assert_ok!(TestAccountStore::mutate(&ALICE, |a| a.frozen = 80));
+		assert_eq!(repair_alice(), Pays::No);
+		assert_eq!(Balances::account(&ALICE).frozen, 50);
+		assert_eq!(System::consumers(&ALICE), 1);
+	});
+}
+
+#[test]
+fn repair_account_restores_missing_consumer_reference() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		Balances::set_balance(&ALICE, 100);
+		assert_ok!(Balances::hold(&TestId::Foo, &ALICE, 30));
+
+		// a consistent account is left untouched and pays the fee.
+		System::reset_events();
+		assert_eq!(repair_alice(), Pays::Yes);
+		assert!(System::events().is_empty());
+
+		corrupt_alice(|_| {});
+		assert_eq!(repair_alice(), Pays::No);
+		assert_eq!(System::consumers(&ALICE), 1);
+		assert_eq!(Balances::reserved_balance(&ALICE), 30);
+	});
+}
+
+#[test]
+fn prepare_account_closure_removes_managed_freezes() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		Balances::set_balance(&ALICE, 100);
+		let root = RawOrigin::Root.into();
+		assert_ok!(Balances::force_set_freeze(root, ALICE, TestId::Foo, 50, Some(5)));
+		assert_eq!(System::consumers(&ALICE), 1);
+
+		// the freeze keeps the account alive.
+		assert_ok!(Balances::transfer_all(Some(ALICE).into(), 2, false));
+		assert!(System::account_exists(&ALICE));
+
+		assert_ok!(Balances::prepare_account_closure(Some(ALICE).into()));
+		System::assert_last_event(RuntimeEvent::Balances(Event::AccountClosurePrepared {
+			who: ALICE,
+			removed_freezes: 1,
+		}));
+		assert_eq!(Balances::balance_frozen(&TestId::Foo, &ALICE), 0);
+		assert!(!FreezeExpiry::<Test>::contains_key(ALICE, TestId::Foo));
+		assert_eq!(System::consumers(&ALICE), 0);
+
+		assert_ok!(Balances::transfer_all(Some(ALICE).into(), 2, false));
+		assert!(!System::account_exists(&ALICE));
+
+		// an account without any freeze is ready right away.
+		assert_ok!(Balances::prepare_account_closure(Some(2).into()));
+		System::assert_last_event(RuntimeEvent::Balances(Event::AccountClosurePrepared {
+			who: 2,
+			removed_freezes: 0,
+		}));
+	});
+}
+
+#[test]
+fn prepare_account_closure_reports_every_blocker() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		let blockers = |holds, locks, reserves| ClosureBlockers { holds, locks, reserves };
+		Balances::set_balance(&ALICE, 1000);
+
+		// the managed freeze does not count.
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &ALICE, 10));
+		assert_ok!(Balances::hold(&TestId::Foo, &ALICE, 10));
+		assert_eq!(Balances::closure_blockers(&ALICE), blockers(1, 0, 0));
+		assert_ok!(Balances::hold(&TestId::Bar, &ALICE, 10));
+		assert_eq!(Balances::closure_blockers(&ALICE), blockers(2, 0, 0));
+
+		// locks and other freezes are counted together.
+		Balances::set_lock(*b"lock    ", &ALICE, 10, WithdrawReasons::all());
+		assert_eq!(Balances::closure_blockers(&ALICE), blockers(2, 1, 0));
+		assert_ok!(Balances::set_freeze(&TestId::Bar, &ALICE, 10));
+		assert_eq!(Balances::closure_blockers(&ALICE), blockers(2, 2, 0));
+
+		// so are named reserves and the reserved balance outside of them.
+		assert_ok!(Balances::reserve_named(&TestId::Foo, &ALICE, 10));
+		assert_eq!(Balances::closure_blockers(&ALICE), blockers(2, 2, 1));
+		assert_ok!(<Balances as frame_support::traits::ReservableCurrency<_>>::reserve(&ALICE, 10));
+		assert_eq!(Balances::closure_blockers(&ALICE), blockers(2, 2, 2));
+
+		// nothing is removed while the closure is blocked.
+		assert_noop!(
+			Balances::prepare_account_closure(Some(ALICE).into()),
+			Error::<Test>::ClosureBlocked(blockers(2, 2, 2)),
+		);
+
+		assert_ok!(Balances::release(&TestId::Foo, &ALICE, 10, Exact));
+		assert_ok!(Balances::release(&TestId::Bar, &ALICE, 10, Exact));
+		Balances::remove_lock(*b"lock    ", &ALICE);
+		assert_ok!(Balances::thaw(&TestId::Bar, &ALICE));
+		Balances::unreserve_named(&TestId::Foo, &ALICE, 10);
+		assert_eq!(Balances::closure_blockers(&ALICE), blockers(0, 0, 1));
+		<Balances as frame_support::traits::ReservableCurrency<_>>::unreserve(&ALICE, 10);
+		assert!(Balances::closure_blockers(&ALICE).is_empty());
+
+		assert_ok!(Balances::prepare_account_closure(Some(ALICE).into()));
+		System::assert_last_event(RuntimeEvent::Balances(Event::AccountClosurePrepared {
+			who: ALICE,
+			removed_freezes: 1,
+		}));
+	});
+}
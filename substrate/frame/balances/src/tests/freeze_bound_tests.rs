@@ -0,0 +1,135 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for a runtime with fewer `MaxFreezes` than freeze reasons.
+
+use super::TestId;
+use crate::{self as pallet_balances, Error, Freezes, TryStateSample};
+use frame_support::{
+	assert_noop, assert_ok, derive_impl, parameter_types, storage,
+	traits::{
+		fungible::{InspectFreeze, MutateFreeze},
+		ConstU32, ConstU64, Get, Hooks, VariantCount,
+	},
+};
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		Balances: pallet_balances,
+	}
+);
+
+parameter_types! {
+	pub static LenientFreezeBound: bool = true;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = u64;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU64<1>;
+	#[cfg(feature = "insecure_zero_ed")]
+	type AccountSufficiency = ();
+	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
+	type WeightInfo = ();
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ConstU32<2>;
+	type ReserveIdentifier = TestId;
+	type FreezeIdentifier = TestId;
+	type MaxFreezes = ConstU32<2>;
+	type RuntimeHoldReason = TestId;
+	type RuntimeFreezeReason = TestId;
+	type OnInconsistency = ();
+	type ResidueQuantum = ();
+	type ResidueThreshold = ();
+	type MaxExpiringPerBlock = ();
+	type MinTransferAmount = ();
+	type LockToFreezeMapping = ();
+	type ManagedFreezes = ();
+	type BurnableHolds = ();
+	type LenientFreezeBound = LenientFreezeBound;
+	type DoneSlashHandler = ();
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Runtime> { balances: vec![(1, 100)], ..Default::default() }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+#[test]
+fn lenient_freeze_bound_passes_integrity_test() {
+	assert!(<Runtime as pallet_balances::Config>::MaxFreezes::get() < TestId::VARIANT_COUNT);
+	<Balances as Hooks<u64>>::integrity_test();
+}
+
+#[test]
+#[should_panic(expected = "MaxFreezes should be greater than or equal to the number of freeze")]
+fn strict_freeze_bound_fails_integrity_test() {
+	LenientFreezeBound::set(false);
+	<Balances as Hooks<u64>>::integrity_test();
+}
+
+#[test]
+fn freezes_beyond_max_freezes_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &1, 10));
+		assert_ok!(Balances::extend_freeze(&TestId::Bar, &1, 20));
+
+		assert!(!Balances::can_freeze(&TestId::Baz, &1));
+		assert_noop!(Balances::set_freeze(&TestId::Baz, &1, 30), Error::<Runtime>::TooManyFreezes);
+		assert_noop!(
+			Balances::extend_freeze(&TestId::Baz, &1, 30),
+			Error::<Runtime>::TooManyFreezes
+		);
+
+		// Existing freezes can still be changed.
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &1, 30));
+		assert_eq!(Balances::balance_frozen(&TestId::Foo, &1), 30);
+		assert_ok!(Balances::do_try_state(TryStateSample::Full));
+
+		// A freeze becomes available again once one is thawed.
+		assert_ok!(Balances::thaw(&TestId::Bar, &1));
+		assert_ok!(Balances::set_freeze(&TestId::Baz, &1, 30));
+	});
+}
+
+#[test]
+fn try_state_reports_accounts_exceeding_max_freezes() {
+	new_test_ext().execute_with(|| {
+		storage::unhashed::put(&Freezes::<Runtime>::hashed_key_for(1), &vec![0u8; 3]);
+
+		assert_eq!(Balances::accounts_exceeding_max_freezes(TryStateSample::Full), 1);
+		assert!(format!("{:?}", Balances::do_try_state(TryStateSample::Full).unwrap_err())
+			.contains("Found `Freeze` with too many elements"));
+	});
+}
@@ -0,0 +1,180 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests regarding freezes which expire after a given block.
+
+use super::*;
+use crate::{Event, FreezeExpiry, FreezeExpiryAgenda};
+use frame_support::traits::{
+	Currency, Get, Hooks, LockIdentifier, LockableCurrency, WithdrawReasons,
+};
+use fungible::{InspectFreeze, MutateFreeze};
+
+const LOCK_ID: LockIdentifier = *b"1       ";
+
+fn thawed_events() -> Vec<u64> {
+	events()
+		.into_iter()
+		.filter_map(|e| match e {
+			RuntimeEvent::Balances(Event::Thawed { who: 1, amount }) => Some(amount),
+			_ => None,
+		})
+		.collect()
+}
+
+#[test]
+fn force_set_freeze_thaws_on_expiry() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 100);
+		assert_noop!(
+			Balances::force_set_freeze(RuntimeOrigin::signed(1), 1, TestId::Foo, 50, Some(5)),
+			BadOrigin
+		);
+		assert_ok!(Balances::force_set_freeze(RuntimeOrigin::root(), 1, TestId::Foo, 50, Some(5)));
+		assert_eq!(Balances::balance_frozen(&TestId::Foo, &1), 50);
+		assert_eq!(FreezeExpiry::<Test>::get(1, TestId::Foo), Some(5));
+		events();
+
+		Balances::on_initialize(4);
+		assert_eq!(Balances::balance_frozen(&TestId::Foo, &1), 50);
+
+		Balances::on_initialize(5);
+		assert_eq!(Balances::balance_frozen(&TestId::Foo, &1), 0);
+		assert_eq!(Balances::account(&1).frozen, 0);
+		assert_eq!(thawed_events(), vec![50]);
+		assert_eq!(FreezeExpiry::<Test>::get(1, TestId::Foo), None);
+		assert!(!FreezeExpiryAgenda::<Test>::contains_key(5));
+	});
+}
+
+#[test]
+fn freeze_expiring_in_the_past_thaws_in_the_next_block() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 100);
+		System::set_block_number(10);
+		assert_ok!(Balances::force_set_freeze(RuntimeOrigin::root(), 1, TestId::Foo, 50, Some(3)));
+		assert_eq!(FreezeExpiry::<Test>::get(1, TestId::Foo), Some(11));
+		events();
+
+		Balances::on_initialize(11);
+		assert_eq!(Balances::balance_frozen(&TestId::Foo, &1), 0);
+		assert_eq!(thawed_events(), vec![50]);
+	});
+}
+
+#[test]
+fn expiring_freeze_overlapping_with_locks() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 100);
+
+		// The lock covers the expiring freeze entirely.
+		Balances::set_lock(LOCK_ID, &1, 80, WithdrawReasons::all());
+		assert_ok!(Balances::force_set_freeze(RuntimeOrigin::root(), 1, TestId::Foo, 50, Some(5)));
+		events();
+		Balances::on_initialize(5);
+		assert_eq!(Balances::balance_frozen(&TestId::Foo, &1), 0);
+		assert_eq!(Balances::account(&1).frozen, 80);
+		assert!(thawed_events().is_empty());
+
+		// The expiring freeze exceeds the lock.
+		assert_ok!(Balances::force_set_freeze(RuntimeOrigin::root(), 1, TestId::Foo, 90, Some(6)));
+		events();
+		Balances::on_initialize(6);
+		assert_eq!(Balances::balance_frozen(&TestId::Foo, &1), 0);
+		assert_eq!(Balances::account(&1).frozen, 80);
+		assert_eq!(thawed_events(), vec![10]);
+	});
+}
+
+#[test]
+fn manually_thawed_freeze_no_longer_expires() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 100);
+		assert_ok!(Balances::force_set_freeze(RuntimeOrigin::root(), 1, TestId::Foo, 50, Some(5)));
+		assert_ok!(Balances::thaw(&TestId::Foo, &1));
+		assert_eq!(FreezeExpiry::<Test>::get(1, TestId::Foo), None);
+
+		// A freeze set through `MutateFreeze` does not expire.
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &1, 30));
+		Balances::on_initialize(5);
+		assert_eq!(Balances::balance_frozen(&TestId::Foo, &1), 30);
+
+		// Neither does one forced without expiry.
+		assert_ok!(Balances::force_set_freeze(RuntimeOrigin::root(), 1, TestId::Bar, 40, Some(7)));
+		assert_ok!(Balances::force_set_freeze(RuntimeOrigin::root(), 1, TestId::Bar, 40, None));
+		Balances::on_initialize(7);
+		assert_eq!(Balances::balance_frozen(&TestId::Bar, &1), 40);
+	});
+}
+
+#[test]
+fn freeze_set_again_through_mutate_freeze_no_longer_expires() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 100);
+		assert_ok!(Balances::force_set_freeze(RuntimeOrigin::root(), 1, TestId::Foo, 50, Some(5)));
+		assert_ok!(Balances::force_set_freeze(RuntimeOrigin::root(), 1, TestId::Bar, 50, Some(5)));
+
+		// Setting or extending a freeze with the same id drops its expiry.
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &1, 30));
+		assert_ok!(Balances::extend_freeze(&TestId::Bar, &1, 40));
+		assert_eq!(FreezeExpiry::<Test>::get(1, TestId::Foo), None);
+		assert_eq!(FreezeExpiry::<Test>::get(1, TestId::Bar), None);
+
+		Balances::on_initialize(5);
+		assert_eq!(Balances::balance_frozen(&TestId::Foo, &1), 30);
+		assert_eq!(Balances::balance_frozen(&TestId::Bar, &1), 50);
+	});
+}
+
+#[test]
+fn expiring_freezes_per_block_are_bounded() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		for who in 1..=3 {
+			let _ = Balances::deposit_creating(&who, 100);
+		}
+		for who in 1..=2 {
+			assert_ok!(Balances::force_set_freeze(
+				RuntimeOrigin::root(),
+				who,
+				TestId::Foo,
+				50,
+				Some(5)
+			));
+		}
+		assert_noop!(
+			Balances::force_set_freeze(RuntimeOrigin::root(), 3, TestId::Foo, 50, Some(5)),
+			Error::<Test>::TooManyExpiringFreezes
+		);
+		assert_ok!(Balances::force_set_freeze(RuntimeOrigin::root(), 3, TestId::Foo, 50, Some(6)));
+
+		let db_weight = <Test as frame_system::Config>::DbWeight::get();
+		// including the report of the silent account activity in `on_finalize`
+		let silent_activity = db_weight.reads_writes(1, 1);
+		assert_eq!(Balances::on_initialize(4), db_weight.reads(1) + silent_activity);
+		assert_eq!(
+			Balances::on_initialize(5),
+			db_weight.reads(1) +
+				db_weight.reads_writes(1, 1) +
+				db_weight.reads_writes(4, 3) * 2 +
+				silent_activity
+		);
+		for who in 1..=2 {
+			assert_eq!(Balances::balance_frozen(&TestId::Foo, &who), 0);
+		}
+		assert_eq!(Balances::balance_frozen(&TestId::Foo, &3), 50);
+	});
+}
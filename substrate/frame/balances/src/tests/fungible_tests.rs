@@ -18,11 +18,14 @@
 //! Tests regarding the functionality of the `fungible` trait set implementations.
 
 use super::*;
-use frame_support::traits::tokens::{
-	Fortitude::{Force, Polite},
-	Precision::{BestEffort, Exact},
-	Preservation::{Expendable, Preserve, Protect},
-	Restriction::Free,
+use frame_support::traits::{
+	tokens::{
+		Fortitude::{Force, Polite},
+		Precision::{BestEffort, Exact},
+		Preservation::{Expendable, Preserve, Protect},
+		Restriction::Free,
+	},
+	BalanceStatus, ReservableCurrency,
 };
 use fungible::{Inspect, InspectFreeze, InspectHold, Mutate, MutateFreeze, MutateHold, Unbalanced};
 
@@ -493,3 +496,81 @@ fn withdraw_precision_exact_works() {
 			);
 		});
 }
+
+#[test]
+fn slash_of_partial_hold_is_reported() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		SlashObservations::take();
+		Balances::set_balance(&1, 100);
+		assert_ok!(Balances::hold(&TestId::Foo, &1, 30));
+
+		let (credit, remaining) =
+			<Balances as fungible::BalancedHold<_>>::slash(&TestId::Foo, &1, 10);
+		assert_eq!((credit.peek(), remaining), (10, 0));
+		assert_eq!(Balances::balance_on_hold(&TestId::Foo, &1), 20);
+		assert_eq!(RecordSlashes::<Test>::slashes(), vec![(TestId::Foo, 1, 10)]);
+	});
+}
+
+#[test]
+fn slash_exceeding_hold_is_reported_with_actual_amount() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		SlashObservations::take();
+		Balances::set_balance(&1, 100);
+		assert_ok!(Balances::hold(&TestId::Foo, &1, 30));
+		assert_ok!(Balances::hold(&TestId::Bar, &1, 20));
+
+		let (credit, remaining) =
+			<Balances as fungible::BalancedHold<_>>::slash(&TestId::Foo, &1, 50);
+		assert_eq!((credit.peek(), remaining), (30, 20));
+		assert_eq!(Balances::balance_on_hold(&TestId::Bar, &1), 20);
+		assert_eq!(RecordSlashes::<Test>::slashes(), vec![(TestId::Foo, 1, 30)]);
+	});
+}
+
+#[test]
+fn transferring_reserved_balance_is_not_reported_as_slash() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		SlashObservations::take();
+		Balances::set_balance(&1, 100);
+		Balances::set_balance(&2, 100);
+		assert_ok!(<Balances as ReservableCurrency<_>>::reserve(&1, 30));
+
+		assert_ok!(
+			Balances::repatriate_reserved_split(
+				&1,
+				&[(2, 10, BalanceStatus::Free), (2, 10, BalanceStatus::Reserved)],
+				Exact,
+				Polite,
+			),
+			20
+		);
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert!(RecordSlashes::<Test>::slashes().is_empty());
+		assert!(SlashObservations::take().is_empty());
+	});
+}
+
+#[test]
+fn slash_is_reported_after_storage_mutation_and_before_events() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		SlashObservations::take();
+		Balances::set_balance(&1, 100);
+		Balances::set_balance(&2, 100);
+		assert_ok!(Balances::hold(&TestId::Foo, &1, 30));
+		let _ = events();
+
+		let (credit, _) = <Balances as fungible::BalancedHold<_>>::slash(&TestId::Foo, &1, 10);
+		// the handler sees the reduced hold, while no event was deposited yet.
+		assert_eq!(SlashObservations::take(), vec![(20, 0)]);
+		assert!(events().is_empty());
+
+		// events of handling the credit come after the handler.
+		assert!(<Balances as fungible::Balanced<_>>::resolve(&2, credit).is_ok());
+		assert_eq!(
+			events(),
+			[RuntimeEvent::Balances(crate::Event::Deposit { who: 2, amount: 10, context: None })]
+		);
+		assert_eq!(RecordSlashes::<Test>::slashes(), vec![(TestId::Foo, 1, 10)]);
+	});
+}
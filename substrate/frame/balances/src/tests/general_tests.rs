@@ -141,3 +141,287 @@ fn try_state_works() {
 			.contains("Found `Freeze` with too many elements"));
 	});
 }
+
+#[test]
+fn try_state_sampling_is_deterministic() {
+	use crate::{TryStateSample, TRY_STATE_SAMPLING_KEY};
+	use frame_support::storage;
+
+	ExtBuilder::default().build_and_execute_with(|| {
+		// Without a configured number of slices, every block checks everything.
+		assert_eq!(TryStateSample::for_block(7u64), TryStateSample::Full);
+		storage::unhashed::put(TRY_STATE_SAMPLING_KEY, &1u32);
+		assert_eq!(TryStateSample::for_block(7u64), TryStateSample::Full);
+
+		// The slice rotates with the block number.
+		storage::unhashed::put(TRY_STATE_SAMPLING_KEY, &4u32);
+		assert_eq!(TryStateSample::for_block(7u64), TryStateSample::Slice { index: 3, count: 4 });
+		assert_eq!(TryStateSample::for_block(8u64), TryStateSample::Slice { index: 0, count: 4 });
+
+		// Every account is in exactly one slice, and always in the same one.
+		let slices = (0..4).map(|index| TryStateSample::Slice { index, count: 4 });
+		for who in 0..100u64 {
+			let containing = slices.clone().filter(|s| s.contains(&who)).collect::<Vec<_>>();
+			assert_eq!(containing.len(), 1);
+			assert!(containing[0].contains(&who));
+			assert!(TryStateSample::Full.contains(&who));
+		}
+	});
+}
+
+#[test]
+fn try_state_sampling_catches_violations() {
+	use crate::{Config, Holds, TryStateSample};
+	use frame_support::{storage, traits::VariantCount};
+
+	ExtBuilder::default().build_and_execute_with(|| {
+		let who = 1u64;
+		storage::unhashed::put(
+			&Holds::<Test>::hashed_key_for(who),
+			&vec![0u8; <Test as Config>::RuntimeHoldReason::VARIANT_COUNT as usize + 1],
+		);
+
+		let count = 4;
+		let (sampled, others): (Vec<_>, Vec<_>) = (0..count)
+			.map(|index| TryStateSample::Slice { index, count })
+			.partition(|sample| sample.contains(&who));
+
+		// The violation is caught by the slice containing the account...
+		assert_eq!(sampled.len(), 1);
+		assert!(format!("{:?}", Balances::do_try_state(sampled[0]).unwrap_err())
+			.contains("Found `Hold` with too many elements"));
+
+		// ...but not by the others, while the full mode catches it regardless.
+		for sample in others {
+			assert_ok!(Balances::do_try_state(sample));
+		}
+		assert!(Balances::do_try_state(TryStateSample::Full).is_err());
+	});
+}
+
+#[test]
+fn on_idle_upgrades_old_accounts_across_blocks() {
+	use crate::{
+		tests::{events, RuntimeEvent, RuntimeOrigin},
+		weights::WeightInfo,
+		AutoUpgradeCursor, AutoUpgradeEnabled, Config, Event,
+	};
+	use frame_support::{
+		traits::{Get, Hooks, StoredMap},
+		weights::Weight,
+	};
+	use sp_runtime::traits::BadOrigin;
+
+	ExtBuilder::default().monied(true).build_and_execute_with(|| {
+		let accounts = [1, 2, 3, 4, 12];
+		for who in accounts {
+			assert_ok!(<Test as Config>::AccountStore::mutate(&who, |a| {
+				a.flags = ExtraFlags::old_logic()
+			}));
+			assert!(!Balances::account(&who).flags.is_new_logic());
+		}
+		events();
+
+		// Nothing happens while the flag is not set.
+		let db_weight = <Test as frame_system::Config>::DbWeight::get();
+		let base = db_weight.reads_writes(2, 1);
+		let per_account = <Test as Config>::WeightInfo::upgrade_accounts(1);
+		let budget = base + per_account * 2;
+		Balances::on_idle(1, budget);
+		assert!(events().is_empty());
+
+		assert_noop!(Balances::set_auto_upgrade(RuntimeOrigin::signed(1), true), BadOrigin);
+		assert_ok!(Balances::set_auto_upgrade(RuntimeOrigin::root(), true));
+
+		// Not enough weight for a single account.
+		assert_eq!(Balances::on_idle(1, base + per_account - Weight::from_parts(1, 0)), base);
+		assert!(AutoUpgradeCursor::<Test>::get().is_none());
+
+		// Two accounts are upgraded per block, resuming from the cursor.
+		let mut upgraded = Vec::new();
+		for n in 2..=4 {
+			assert!(AutoUpgradeEnabled::<Test>::get());
+			assert!(Balances::on_idle(n, budget).all_lte(budget));
+			upgraded.extend(events().into_iter().filter_map(|e| match e {
+				RuntimeEvent::Balances(Event::Upgraded { who }) => Some(who),
+				_ => None,
+			}));
+		}
+		assert_eq!(upgraded.len(), accounts.len());
+
+		// The iteration finished in the third block.
+		assert!(!AutoUpgradeEnabled::<Test>::get());
+		assert!(AutoUpgradeCursor::<Test>::get().is_none());
+		upgraded.sort();
+		assert_eq!(upgraded, accounts);
+		for who in accounts {
+			assert!(Balances::account(&who).flags.is_new_logic());
+		}
+
+		// Once disabled, `on_idle` does not do anything anymore.
+		assert_eq!(Balances::on_idle(5, budget), db_weight.reads(1));
+		assert!(events().is_empty());
+	});
+}
+
+/// Purges the ghost entries of all accounts, following the cursor of the emitted events.
+fn purge_all_ghost_accounts(limit: u32) -> u32 {
+	use crate::{
+		tests::{events, RuntimeEvent, RuntimeOrigin},
+		Event,
+	};
+
+	let (mut purged, mut cursor) = (0, None);
+	loop {
+		events();
+		assert_ok!(Balances::purge_ghost_accounts(RuntimeOrigin::root(), limit, cursor));
+		let Some((count, last)) = events().into_iter().find_map(|e| match e {
+			RuntimeEvent::Balances(Event::GhostAccountsPurged { purged, last }) =>
+				Some((purged, last)),
+			_ => None,
+		}) else {
+			panic!("`GhostAccountsPurged` is always emitted");
+		};
+		purged += count;
+		match last {
+			Some(last) => cursor = Some(last),
+			None => return purged,
+		}
+	}
+}
+
+fn audit_account_stores() -> crate::StoreAuditReport<u64> {
+	let mut report = Balances::audit_account_stores().unwrap();
+	report.orphaned.sort();
+	report.divergent.sort();
+	report
+}
+
+#[test]
+fn audit_and_purge_ghost_accounts_in_balances_store() {
+	use crate::{tests::RuntimeOrigin, Account, AccountStoreKind, StoreAuditReport};
+	use sp_runtime::traits::BadOrigin;
+
+	UseSystem::set(true);
+	ExtBuilder::default().build().execute_with(|| {
+		for who in 1..=3 {
+			Balances::set_balance(&who, who * 100);
+		}
+		// An exact duplicate of the authoritative data.
+		Account::<Test>::insert(2, System::account(2).data);
+		// Divergent data.
+		Account::<Test>::insert(3, AccountData { free: 1, ..Default::default() });
+		// Default-valued and non-default data of accounts unknown to the authoritative store.
+		Account::<Test>::insert(4, AccountData::default());
+		Account::<Test>::insert(5, AccountData { free: 50, ..Default::default() });
+
+		assert_eq!(
+			audit_account_stores(),
+			StoreAuditReport {
+				authoritative: AccountStoreKind::System,
+				authoritative_entries: 3,
+				ghost_entries: 4,
+				purgeable: 2,
+				orphaned: vec![4, 5],
+				divergent: vec![3],
+			}
+		);
+
+		assert_noop!(Balances::purge_ghost_accounts(RuntimeOrigin::signed(1), 10, None), BadOrigin);
+		assert_eq!(purge_all_ghost_accounts(1), 2);
+
+		// Divergent data is never removed.
+		assert!(!Account::<Test>::contains_key(2));
+		assert!(!Account::<Test>::contains_key(4));
+		assert_eq!(Account::<Test>::get(3).free, 1);
+		assert_eq!(Account::<Test>::get(5).free, 50);
+		assert_eq!(
+			audit_account_stores(),
+			StoreAuditReport {
+				authoritative: AccountStoreKind::System,
+				authoritative_entries: 3,
+				ghost_entries: 2,
+				purgeable: 0,
+				orphaned: vec![5],
+				divergent: vec![3],
+			}
+		);
+		for who in 1..=3 {
+			assert_eq!(Balances::free_balance(who), who * 100);
+		}
+	});
+}
+
+#[test]
+fn audit_account_stores_rejects_a_mismatched_store() {
+	use crate::{tests::AuthoritativeStoreOverride, Account, AccountStoreKind};
+
+	UseSystem::set(true);
+	AuthoritativeStoreOverride::set(Some(AccountStoreKind::Balances));
+	ExtBuilder::default().build().execute_with(|| {
+		Balances::set_balance(&1, 100);
+
+		assert_eq!(
+			Balances::audit_account_stores(),
+			Err(DispatchError::Other("`AuthoritativeStore` does not match `AccountStore`")),
+		);
+		// The probe leaves no trace.
+		assert_eq!(Account::<Test>::iter_keys().count(), 0);
+	});
+	AuthoritativeStoreOverride::set(None);
+}
+
+#[test]
+fn audit_and_purge_ghost_accounts_in_system_store() {
+	use crate::{Account, AccountStoreKind, StoreAuditReport};
+
+	UseSystem::set(false);
+	ExtBuilder::default().build().execute_with(|| {
+		for who in 1..=3 {
+			Balances::set_balance(&who, who * 100);
+		}
+		// An exact duplicate of the authoritative data.
+		frame_system::Account::<Test>::mutate(2, |info| info.data = Account::<Test>::get(2));
+		// Divergent data.
+		frame_system::Account::<Test>::mutate(3, |info| info.data.free = 1);
+		// Non-default data of an account unknown to the authoritative store.
+		let data = AccountData { free: 40, ..Default::default() };
+		frame_system::Account::<Test>::insert(4, AccountInfo { data, ..Default::default() });
+		// Default-valued system data is not a ghost entry.
+		System::inc_providers(&5);
+
+		assert_eq!(
+			audit_account_stores(),
+			StoreAuditReport {
+				authoritative: AccountStoreKind::Balances,
+				authoritative_entries: 3,
+				ghost_entries: 3,
+				purgeable: 1,
+				orphaned: vec![4],
+				divergent: vec![3],
+			}
+		);
+
+		assert_eq!(purge_all_ghost_accounts(10), 1);
+
+		// Only the balance data is removed, the account itself is kept.
+		assert_eq!(System::account(2).data, AccountData::default());
+		assert_eq!(System::providers(&2), 1);
+		assert_eq!(System::account(3).data.free, 1);
+		assert_eq!(System::account(4).data.free, 40);
+		assert_eq!(
+			audit_account_stores(),
+			StoreAuditReport {
+				authoritative: AccountStoreKind::Balances,
+				authoritative_entries: 3,
+				ghost_entries: 2,
+				purgeable: 0,
+				orphaned: vec![4],
+				divergent: vec![3],
+			}
+		);
+		for who in 1..=3 {
+			assert_eq!(Balances::free_balance(who), who * 100);
+		}
+	});
+}
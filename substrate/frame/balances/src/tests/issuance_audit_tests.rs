@@ -0,0 +1,174 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests regarding the `issuance-audit` feature.
+
+use super::*;
+use crate::{
+	AdjustmentDirection, BlockIssuanceAudit, Event, IssuanceAuditEntry, IssuanceAuditOperation,
+	UnexpectedKind,
+};
+use frame_support::traits::{
+	fungible::{Balanced, Mutate},
+	tokens::{Fortitude, Precision},
+	Currency, ExistenceRequirement, Hooks, WithdrawReasons,
+};
+use std::mem;
+
+fn issuance_drift_reported() -> bool {
+	events()
+		.into_iter()
+		.any(|e| e == RuntimeEvent::Balances(Event::Unexpected(UnexpectedKind::IssuanceDrift)))
+}
+
+#[test]
+fn balanced_operations_reconcile() {
+	ExtBuilder::default()
+		.existential_deposit(10)
+		.monied(true)
+		.build_and_execute_with(|| {
+			Balances::on_initialize(1);
+
+			drop(Balances::deposit_creating(&1, 50));
+			drop(
+				<Balances as Currency<_>>::withdraw(
+					&2,
+					30,
+					WithdrawReasons::TRANSFER,
+					ExistenceRequirement::KeepAlive,
+				)
+				.unwrap(),
+			);
+			drop(<Balances as Currency<_>>::issue(20));
+			assert_ok!(Balances::mint_into(&3, 40));
+			assert_ok!(Balances::burn_from(
+				&4,
+				15,
+				Preservation::Preserve,
+				Precision::Exact,
+				Fortitude::Polite
+			));
+			drop(Balances::deposit(&1, 5, Precision::Exact).unwrap());
+			// dusts the remaining 5 of account 12
+			assert_ok!(<Balances as Currency<_>>::transfer(
+				&12,
+				&1,
+				95,
+				ExistenceRequirement::AllowDeath
+			));
+
+			Balances::on_finalize(1);
+			assert!(!issuance_drift_reported());
+			assert!(BlockIssuanceAudit::<Test>::get().is_none());
+
+			let audit = Balances::last_issuance_audit().unwrap();
+			assert_eq!(audit.increased, 50 + 20 + 40 + 5);
+			assert_eq!(audit.decreased, 30 + 20 + 15 + 5);
+			assert!(audit.reconciles(Balances::total_issuance()));
+			ensure_ti_valid();
+		});
+}
+
+#[test]
+fn leaked_positive_imbalance_is_detected() {
+	ExtBuilder::default()
+		.existential_deposit(10)
+		.monied(true)
+		.build_and_execute_with(|| {
+			let initial_issuance = Balances::total_issuance();
+			Balances::on_initialize(1);
+
+			// the balance is credited, but the total issuance is never increased
+			mem::forget(Balances::deposit_creating(&1, 50));
+			assert_eq!(Balances::total_issuance(), initial_issuance);
+
+			Balances::on_finalize(1);
+			assert!(issuance_drift_reported());
+
+			let audit = Balances::last_issuance_audit().unwrap();
+			assert_eq!(audit.initial_issuance, initial_issuance);
+			assert_eq!(
+				audit.entries.into_inner(),
+				vec![IssuanceAuditEntry {
+					operation: IssuanceAuditOperation::PositiveImbalance,
+					who: None,
+					direction: AdjustmentDirection::Increase,
+					amount: 50,
+				}]
+			);
+		});
+}
+
+#[test]
+fn leaked_credit_is_detected() {
+	ExtBuilder::default()
+		.existential_deposit(10)
+		.monied(true)
+		.build_and_execute_with(|| {
+			Balances::on_initialize(1);
+
+			let credit = <Balances as Balanced<_>>::withdraw(
+				&2,
+				30,
+				Precision::Exact,
+				Preservation::Preserve,
+				Fortitude::Polite,
+			)
+			.unwrap();
+			mem::forget(credit);
+
+			Balances::on_finalize(1);
+			assert!(issuance_drift_reported());
+
+			let audit = Balances::last_issuance_audit().unwrap();
+			assert_eq!(
+				audit.entries.into_inner(),
+				vec![IssuanceAuditEntry {
+					operation: IssuanceAuditOperation::Withdraw,
+					who: Some(2),
+					direction: AdjustmentDirection::Decrease,
+					amount: 30,
+				}]
+			);
+		});
+}
+
+#[test]
+fn audit_is_per_block() {
+	ExtBuilder::default()
+		.existential_deposit(10)
+		.monied(true)
+		.build_and_execute_with(|| {
+			Balances::on_initialize(1);
+			mem::forget(Balances::deposit_creating(&1, 50));
+			Balances::on_finalize(1);
+			assert!(issuance_drift_reported());
+
+			// the next block starts with the drifted issuance and reconciles
+			System::set_block_number(2);
+			Balances::on_initialize(2);
+			assert!(Balances::last_issuance_audit().is_none());
+			drop(Balances::deposit_creating(&1, 50));
+			Balances::on_finalize(2);
+			assert!(!issuance_drift_reported());
+			assert_eq!(Balances::last_issuance_audit().unwrap().entries.len(), 1);
+
+			// nothing is recorded outside of a block
+			drop(Balances::deposit_creating(&1, 50));
+			assert!(BlockIssuanceAudit::<Test>::get().is_none());
+		});
+}
@@ -0,0 +1,145 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests regarding the lock summary and the conversion of locks into freezes.
+
+use super::*;
+use crate::{Event, FreezeExpiry, Reasons};
+use frame_support::traits::{Currency, LockableCurrency, WithdrawReasons};
+use fungible::{InspectFreeze, MutateFreeze};
+
+const FOO: LockIdentifier = *b"foo     ";
+const BAR: LockIdentifier = *b"bar     ";
+const BAZ: LockIdentifier = *b"baz     ";
+
+#[test]
+fn lock_summary_works_with_mixed_locks_and_freezes() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 100);
+		let summary = Balances::lock_summary(&1);
+		assert_eq!(summary.max_amount, 0);
+		assert_eq!(summary.reasons, None);
+		assert!(summary.locks.is_empty());
+		assert!(summary.frozen_consistent);
+
+		Balances::set_lock(FOO, &1, 30, WithdrawReasons::TRANSACTION_PAYMENT);
+		let summary = Balances::lock_summary(&1);
+		assert_eq!(summary.max_amount, 30);
+		assert_eq!(summary.reasons, Some(Reasons::Fee));
+
+		Balances::set_lock(BAZ, &1, 50, WithdrawReasons::TRANSFER);
+		assert_ok!(Balances::set_freeze(&TestId::Bar, &1, 70));
+		let summary = Balances::lock_summary(&1);
+		assert_eq!(summary.max_amount, 50);
+		assert_eq!(summary.reasons, Some(Reasons::All));
+		assert_eq!(summary.locks.iter().map(|l| l.id).collect::<Vec<_>>(), vec![FOO, BAZ]);
+		assert_eq!(Balances::account(&1).frozen, 70);
+		assert!(summary.frozen_consistent);
+	});
+}
+
+#[test]
+fn lock_summary_detects_inconsistent_frozen_balance() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 100);
+		Balances::set_lock(FOO, &1, 30, WithdrawReasons::all());
+		assert!(Balances::lock_summary(&1).frozen_consistent);
+
+		assert_ok!(TestAccountStore::mutate(&1, |a| a.frozen = 20));
+		assert!(!Balances::lock_summary(&1).frozen_consistent);
+	});
+}
+
+#[test]
+fn deprecate_lock_preserves_frozen_balance() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 100);
+		Balances::set_lock(FOO, &1, 40, WithdrawReasons::all());
+		Balances::set_lock(BAZ, &1, 80, WithdrawReasons::all());
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &1, 20));
+		assert_eq!(Balances::account(&1).frozen, 80);
+
+		assert_noop!(Balances::deprecate_lock(RuntimeOrigin::signed(1), 1, FOO), BadOrigin);
+		assert_ok!(Balances::deprecate_lock(RuntimeOrigin::root(), 1, FOO));
+		System::assert_last_event(RuntimeEvent::Balances(Event::LockDeprecated {
+			who: 1,
+			id: FOO,
+			freeze: TestId::Foo,
+			amount: 40,
+		}));
+
+		assert_eq!(Balances::balance_frozen(&TestId::Foo, &1), 40);
+		let summary = Balances::lock_summary(&1);
+		assert_eq!(summary.locks.iter().map(|l| l.id).collect::<Vec<_>>(), vec![BAZ]);
+		assert_eq!(Balances::account(&1).frozen, 80);
+		assert!(summary.frozen_consistent);
+	});
+}
+
+#[test]
+fn deprecate_lock_keeps_larger_freeze_and_drops_expiry() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 100);
+		Balances::set_lock(BAR, &1, 30, WithdrawReasons::all());
+		assert_ok!(Balances::force_set_freeze(RuntimeOrigin::root(), 1, TestId::Bar, 60, Some(5)));
+		assert_eq!(FreezeExpiry::<Test>::get(1, TestId::Bar), Some(5));
+
+		assert_ok!(Balances::deprecate_lock(RuntimeOrigin::root(), 1, BAR));
+		assert_eq!(Balances::balance_frozen(&TestId::Bar, &1), 60);
+		assert_eq!(FreezeExpiry::<Test>::get(1, TestId::Bar), None);
+		assert!(Balances::locks(&1).is_empty());
+		assert_eq!(Balances::account(&1).frozen, 60);
+	});
+}
+
+#[test]
+fn deprecate_lock_fails_for_unknown_or_unmapped_lock() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 100);
+		Balances::set_lock(BAZ, &1, 30, WithdrawReasons::all());
+
+		assert_noop!(
+			Balances::deprecate_lock(RuntimeOrigin::root(), 1, FOO),
+			Error::<Test>::UnknownLock
+		);
+		assert_noop!(
+			Balances::deprecate_lock(RuntimeOrigin::root(), 1, BAZ),
+			Error::<Test>::LockNotConvertible
+		);
+	});
+}
+
+#[test]
+fn deprecate_lock_fails_when_freezes_are_full() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 100);
+		Balances::set_lock(BAR, &1, 30, WithdrawReasons::all());
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &1, 10));
+		assert_ok!(Balances::set_freeze(&TestId::Baz, &1, 20));
+
+		assert_noop!(
+			Balances::deprecate_lock(RuntimeOrigin::root(), 1, BAR),
+			Error::<Test>::TooManyFreezes
+		);
+		assert_eq!(Balances::lock_summary(&1).max_amount, 30);
+
+		// Freezes which already exist can always be extended.
+		Balances::set_lock(FOO, &1, 40, WithdrawReasons::all());
+		assert_ok!(Balances::deprecate_lock(RuntimeOrigin::root(), 1, FOO));
+		assert_eq!(Balances::balance_frozen(&TestId::Foo, &1), 40);
+	});
+}
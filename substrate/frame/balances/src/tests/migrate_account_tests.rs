@@ -0,0 +1,144 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests regarding the migration of a balance to a new account.
+
+use super::*;
+use crate::{Event, FreezeExpiry, FreezeExpiryAgenda, Holds, Locks, Reserves, TryStateSample};
+use frame_support::traits::{
+	fungible::{InspectFreeze, InspectHold, MutateHold},
+	tokens::Precision,
+	LockIdentifier, LockableCurrency, NamedReservableCurrency, WithdrawReasons,
+};
+
+const LOCK_ID: LockIdentifier = *b"1       ";
+
+#[test]
+fn force_migrate_account_moves_everything() {
+	ExtBuilder::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build_and_execute_with(|| {
+			assert_ok!(<Balances as MutateHold<_>>::hold(&TestId::Foo, &1, 2));
+			assert_ok!(Balances::reserve_named(&TestId::Bar, &1, 1));
+			Balances::set_lock(LOCK_ID, &1, 3, WithdrawReasons::all());
+			assert_ok!(Balances::force_set_freeze(
+				RuntimeOrigin::root(),
+				1,
+				TestId::Baz,
+				4,
+				Some(10)
+			));
+			let data = Balances::account(&1);
+			let issuance = Balances::total_issuance();
+			let (providers, consumers) = (System::providers(&1), System::consumers(&1));
+
+			assert_noop!(
+				Balances::force_migrate_account(RuntimeOrigin::signed(1), 1, 5),
+				BadOrigin
+			);
+			assert_ok!(Balances::force_migrate_account(RuntimeOrigin::root(), 1, 5));
+			System::assert_last_event(RuntimeEvent::Balances(Event::AccountMigrated {
+				from: 1,
+				to: 5,
+			}));
+
+			assert_eq!(Balances::account(&5), data);
+			assert_eq!(Balances::account(&1), Default::default());
+			assert_eq!(Balances::total_issuance(), issuance);
+			assert_eq!((System::providers(&5), System::consumers(&5)), (providers, consumers));
+			assert!(!System::account_exists(&1));
+
+			assert!(!Holds::<Test>::contains_key(&1));
+			assert!(!Reserves::<Test>::contains_key(&1));
+			assert!(!Locks::<Test>::contains_key(&1));
+			assert_eq!(Balances::balance_on_hold(&TestId::Foo, &5), 2);
+			assert_eq!(Balances::reserved_balance_named(&TestId::Bar, &5), 1);
+			assert_eq!(Balances::locks(&5).len(), 1);
+			assert_eq!(Balances::balance_frozen(&TestId::Baz, &5), 4);
+			assert_eq!(FreezeExpiry::<Test>::get(&5, TestId::Baz), Some(10));
+			assert_eq!(FreezeExpiry::<Test>::get(&1, TestId::Baz), None);
+			assert_eq!(FreezeExpiryAgenda::<Test>::get(10).into_inner(), vec![(5, TestId::Baz)]);
+
+			assert_ok!(Balances::do_try_state(TryStateSample::Full));
+			ensure_ti_valid();
+		});
+}
+
+#[test]
+fn force_migrate_account_leaves_foreign_references_behind() {
+	ExtBuilder::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build_and_execute_with(|| {
+			assert_ok!(<Balances as MutateHold<_>>::hold(&TestId::Foo, &1, 2));
+			// Another pallet holds a provider and a consumer reference on `1`.
+			System::inc_providers(&1);
+			assert_ok!(System::inc_consumers(&1));
+			assert_eq!((System::providers(&1), System::consumers(&1)), (2, 2));
+
+			assert_ok!(Balances::force_migrate_account(RuntimeOrigin::root(), 1, 5));
+
+			assert_eq!((System::providers(&5), System::consumers(&5)), (1, 1));
+			assert_eq!((System::providers(&1), System::consumers(&1)), (1, 1));
+			assert!(System::account_exists(&1));
+			assert_eq!(Balances::account(&1), Default::default());
+			assert_eq!(Balances::balance_on_hold(&TestId::Foo, &5), 2);
+			assert_ok!(Balances::do_try_state(TryStateSample::Full));
+			ensure_ti_valid();
+		});
+}
+
+#[test]
+fn hold_can_be_released_after_migration() {
+	ExtBuilder::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build_and_execute_with(|| {
+			assert_ok!(<Balances as MutateHold<_>>::hold(&TestId::Foo, &1, 4));
+			assert_ok!(Balances::force_migrate_account(RuntimeOrigin::root(), 1, 5));
+
+			assert_ok!(Balances::release(&TestId::Foo, &5, 4, Precision::Exact));
+			assert_eq!(Balances::balance_on_hold(&TestId::Foo, &5), 0);
+			assert_eq!(Balances::free_balance(&5), 10);
+			assert_eq!(Balances::reserved_balance(&5), 0);
+			assert_eq!(System::consumers(&5), 0);
+			assert_ok!(Balances::do_try_state(TryStateSample::Full));
+			ensure_ti_valid();
+		});
+}
+
+#[test]
+fn force_migrate_account_requires_empty_destination() {
+	ExtBuilder::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build_and_execute_with(|| {
+			assert_noop!(
+				Balances::force_migrate_account(RuntimeOrigin::root(), 1, 2),
+				Error::<Test>::MigrationDestinationInUse
+			);
+			assert_noop!(
+				Balances::force_migrate_account(RuntimeOrigin::root(), 1, 1),
+				Error::<Test>::MigrationDestinationInUse
+			);
+			assert_noop!(
+				Balances::force_migrate_account(RuntimeOrigin::root(), 5, 6),
+				Error::<Test>::DeadAccount
+			);
+		});
+}
@@ -19,36 +19,57 @@
 
 #![cfg(test)]
 
-use crate::{self as pallet_balances, AccountData, Config, CreditOf, Error, Pallet, TotalIssuance};
+use crate::{
+	self as pallet_balances, contexts, with_context, AccountData, Config, CreditOf, Error, Pallet,
+	TotalIssuance,
+};
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	assert_err, assert_noop, assert_ok, assert_storage_noop, derive_impl,
 	dispatch::{DispatchInfo, GetDispatchInfo},
 	parameter_types,
 	traits::{
-		fungible, ConstU32, ConstU8, Imbalance as ImbalanceT, OnUnbalanced, StorageMapShim,
-		StoredMap, VariantCount, WhitelistedStorageKeys,
+		fungible, tokens::Preservation, ConstU32, ConstU8, Imbalance as ImbalanceT, LockIdentifier,
+		OnUnbalanced, StorageMapShim, StoredMap, VariantCount, WhitelistedStorageKeys,
 	},
 	weights::{IdentityFee, Weight},
 };
 use frame_system::{self as system, RawOrigin};
-use pallet_transaction_payment::{ChargeTransactionPayment, FungibleAdapter, Multiplier};
+use pallet_transaction_payment::{
+	ChargeTransactionPayment, FungibleAdapter, Multiplier, OnChargeTransaction,
+};
 use scale_info::TypeInfo;
 use sp_core::hexdisplay::HexDisplay;
 use sp_io;
 use sp_runtime::{
-	traits::{BadOrigin, SignedExtension, Zero},
+	traits::{BadOrigin, DispatchInfoOf, PostDispatchInfoOf, SignedExtension, Zero},
+	transaction_validity::TransactionValidityError,
 	ArithmeticError, BuildStorage, DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug,
 	TokenError,
 };
-use std::collections::BTreeSet;
+use std::{collections::BTreeSet, marker::PhantomData};
 
+mod conversion_tests;
 mod currency_tests;
 mod dispatchable_tests;
+mod freeze_bound_tests;
+mod freeze_expiry_tests;
 mod fungible_conformance_tests;
 mod fungible_tests;
 mod general_tests;
+#[cfg(feature = "issuance-audit")]
+mod issuance_audit_tests;
+mod lock_summary_tests;
+mod migrate_account_tests;
 mod reentrancy_tests;
+mod residue_tests;
+mod restricted_account_tests;
+mod silent_account_tests;
+mod spendable_breakdown_tests;
+mod u32_balance_tests;
+mod zero_amount_tests;
+#[cfg(feature = "insecure_zero_ed")]
+mod zero_ed_tests;
 
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -89,6 +110,9 @@ parameter_types! {
 			frame_support::weights::Weight::from_parts(1024, u64::MAX),
 		);
 	pub static ExistentialDeposit: u64 = 1;
+	pub static ResidueQuantum: u64 = 0;
+	pub static ResidueThreshold: u64 = 0;
+	pub static MinTransferAmount: u64 = 0;
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
@@ -100,13 +124,61 @@ impl frame_system::Config for Test {
 #[derive_impl(pallet_transaction_payment::config_preludes::TestDefaultConfig)]
 impl pallet_transaction_payment::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
-	type OnChargeTransaction = FungibleAdapter<Pallet<Test>, ()>;
+	type OnChargeTransaction = TransactionPaymentAdapter;
 	type OperationalFeeMultiplier = ConstU8<5>;
 	type WeightToFee = IdentityFee<u64>;
 	type LengthToFee = IdentityFee<u64>;
 	type FeeMultiplierUpdate = ();
 }
 
+type FeeAdapter = FungibleAdapter<Pallet<Test>, ()>;
+
+/// Charges fees through the [`FungibleAdapter`], attributing its balance events to
+/// [`contexts::TRANSACTION_PAYMENT`].
+pub struct TransactionPaymentAdapter;
+impl OnChargeTransaction<Test> for TransactionPaymentAdapter {
+	type Balance = u64;
+	type LiquidityInfo = <FeeAdapter as OnChargeTransaction<Test>>::LiquidityInfo;
+
+	fn withdraw_fee(
+		who: &u64,
+		call: &RuntimeCall,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		fee: u64,
+		tip: u64,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		with_context(contexts::TRANSACTION_PAYMENT, || {
+			<FeeAdapter as OnChargeTransaction<Test>>::withdraw_fee(
+				who,
+				call,
+				dispatch_info,
+				fee,
+				tip,
+			)
+		})
+	}
+
+	fn correct_and_deposit_fee(
+		who: &u64,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		post_info: &PostDispatchInfoOf<RuntimeCall>,
+		corrected_fee: u64,
+		tip: u64,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		with_context(contexts::TRANSACTION_PAYMENT, || {
+			<FeeAdapter as OnChargeTransaction<Test>>::correct_and_deposit_fee(
+				who,
+				dispatch_info,
+				post_info,
+				corrected_fee,
+				tip,
+				already_withdrawn,
+			)
+		})
+	}
+}
+
 pub(crate) type Balance = u64;
 
 impl Config for Test {
@@ -114,7 +186,10 @@ impl Config for Test {
 	type DustRemoval = DustTrap;
 	type RuntimeEvent = RuntimeEvent;
 	type ExistentialDeposit = ExistentialDeposit;
+	#[cfg(feature = "insecure_zero_ed")]
+	type AccountSufficiency = ();
 	type AccountStore = TestAccountStore;
+	type AuthoritativeStore = TestAuthoritativeStore;
 	type MaxLocks = ConstU32<50>;
 	type MaxReserves = ConstU32<2>;
 	type ReserveIdentifier = TestId;
@@ -123,6 +198,49 @@ impl Config for Test {
 	type RuntimeFreezeReason = RuntimeFreezeReason;
 	type FreezeIdentifier = TestId;
 	type MaxFreezes = ConstU32<2>;
+	type OnInconsistency = FlagOnInconsistency;
+	type ResidueQuantum = ResidueQuantum;
+	type ResidueThreshold = ResidueThreshold;
+	type MaxExpiringPerBlock = ConstU32<2>;
+	type MinTransferAmount = MinTransferAmount;
+	type LockToFreezeMapping = LockToFreeze;
+	type ManagedFreezes = ManagedFoo;
+	type BurnableHolds = BurnableFooAndBar;
+	type LenientFreezeBound = ();
+	type DoneSlashHandler = (RecordSlashes<Test>, SlashObserver);
+}
+
+/// Maps the locks `foo` and `bar` to the freezes of the same name, and the all-zero lock used by
+/// the benchmarks to `Baz`.
+pub struct LockToFreeze;
+
+impl sp_runtime::traits::Convert<LockIdentifier, Option<TestId>> for LockToFreeze {
+	fn convert(id: LockIdentifier) -> Option<TestId> {
+		match &id {
+			b"foo     " => Some(TestId::Foo),
+			b"bar     " => Some(TestId::Bar),
+			[0, 0, 0, 0, 0, 0, 0, 0] => Some(TestId::Baz),
+			_ => None,
+		}
+	}
+}
+
+/// Lets accounts remove the freeze `Foo` when preparing their closure.
+pub struct ManagedFoo;
+
+impl frame_support::traits::Contains<TestId> for ManagedFoo {
+	fn contains(id: &TestId) -> bool {
+		*id == TestId::Foo
+	}
+}
+
+/// Lets accounts burn the holds `Foo` and `Bar` themselves.
+pub struct BurnableFooAndBar;
+
+impl frame_support::traits::Contains<TestId> for BurnableFooAndBar {
+	fn contains(id: &TestId) -> bool {
+		matches!(id, TestId::Foo | TestId::Bar)
+	}
 }
 
 #[derive(Clone)]
@@ -171,6 +289,7 @@ impl ExtBuilder {
 			} else {
 				vec![]
 			},
+			..Default::default()
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
@@ -190,12 +309,23 @@ impl ExtBuilder {
 
 parameter_types! {
 	static DustTrapTarget: Option<u64> = None;
+	/// A `(source, dest, value)` transfer which the dust trap attempts once, re-entering the
+	/// pallet through [`Pallet::transfer_no_reentry`].
+	pub static DustTrapReentry: Option<(u64, u64, u64)> = None;
+	/// The free balances of `(source, dest)` of [`DustTrapReentry`] observed by the dust trap.
+	pub static DustTrapObservations: Vec<(u64, u64)> = vec![];
 }
 
 pub struct DustTrap;
 
 impl OnUnbalanced<CreditOf<Test, ()>> for DustTrap {
 	fn on_nonzero_unbalanced(amount: CreditOf<Test, ()>) {
+		if let Some((source, dest, value)) = DustTrapReentry::take() {
+			DustTrapObservations::mutate(|o| {
+				o.push((Balances::free_balance(source), Balances::free_balance(dest)))
+			});
+			let _ = Balances::transfer_no_reentry(&source, &dest, value, Preservation::Expendable);
+		}
 		match DustTrapTarget::get() {
 			None => drop(amount),
 			Some(a) => {
@@ -206,8 +336,86 @@ impl OnUnbalanced<CreditOf<Test, ()>> for DustTrap {
 	}
 }
 
+parameter_types! {
+	/// The balance on hold of the slashed account and the number of events deposited so far,
+	/// as observed by [`SlashObserver`] when a slash is reported.
+	pub static SlashObservations: Vec<(u64, u32)> = vec![];
+}
+
+/// The slashes recorded by [`RecordSlashes`], oldest first.
+#[frame_support::storage_alias]
+type SlashLog<T: Config<I>, I: 'static> = StorageValue<
+	Pallet<T, I>,
+	Vec<(
+		<T as Config<I>>::RuntimeHoldReason,
+		<T as frame_system::Config>::AccountId,
+		<T as Config<I>>::Balance,
+	)>,
+	ValueQuery,
+>;
+
+/// A [`Config::DoneSlashHandler`] which records the reason, account and amount of every slash
+/// in storage.
+pub struct RecordSlashes<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> RecordSlashes<T, I> {
+	/// The slashes recorded so far, oldest first.
+	pub fn slashes() -> Vec<(T::RuntimeHoldReason, T::AccountId, T::Balance)> {
+		SlashLog::<T, I>::get()
+	}
+}
+
+impl<T: Config<I>, I: 'static>
+	fungible::hold::DoneSlash<T::RuntimeHoldReason, T::AccountId, T::Balance> for RecordSlashes<T, I>
+{
+	fn done_slash(reason: &T::RuntimeHoldReason, who: &T::AccountId, amount: T::Balance) {
+		SlashLog::<T, I>::append((*reason, who.clone(), amount));
+	}
+}
+
+/// Records the state of the runtime whenever a slash of held funds is reported.
+pub struct SlashObserver;
+
+impl fungible::hold::DoneSlash<TestId, u64, u64> for SlashObserver {
+	fn done_slash(reason: &TestId, who: &u64, _amount: u64) {
+		let on_hold = <Balances as fungible::InspectHold<_>>::balance_on_hold(reason, who);
+		SlashObservations::mutate(|o| o.push((on_hold, System::event_count())));
+	}
+}
+
+parameter_types! {
+	pub static InconsistencyTriggered: bool = false;
+}
+
+/// Flips [`InconsistencyTriggered`] whenever the pallet reports an inconsistency.
+pub struct FlagOnInconsistency;
+
+impl crate::OnInconsistency<u64> for FlagOnInconsistency {
+	fn balance_updated(_: &u64) {
+		InconsistencyTriggered::set(true);
+	}
+
+	fn failed_mutation(_: &u64) {
+		InconsistencyTriggered::set(true);
+	}
+}
+
 parameter_types! {
 	pub static UseSystem: bool = false;
+	/// Overrides the store reported by [`TestAuthoritativeStore`].
+	pub static AuthoritativeStoreOverride: Option<crate::AccountStoreKind> = None;
+}
+
+/// The store [`TestAccountStore`] keeps the account data in, unless overridden.
+pub struct TestAuthoritativeStore;
+impl frame_support::traits::Get<crate::AccountStoreKind> for TestAuthoritativeStore {
+	fn get() -> crate::AccountStoreKind {
+		AuthoritativeStoreOverride::get().unwrap_or(if UseSystem::get() {
+			crate::AccountStoreKind::System
+		} else {
+			crate::AccountStoreKind::Balances
+		})
+	}
 }
 
 type BalancesAccountStore = StorageMapShim<super::Account<Test>, u64, super::AccountData<u64>>;
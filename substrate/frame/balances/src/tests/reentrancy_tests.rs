@@ -66,6 +66,7 @@ fn transfer_dust_removal_tst1_should_work() {
 			System::assert_has_event(RuntimeEvent::Balances(crate::Event::Deposit {
 				who: 1,
 				amount: 50,
+				context: None,
 			}));
 		});
 }
@@ -107,6 +108,7 @@ fn transfer_dust_removal_tst2_should_work() {
 			System::assert_has_event(RuntimeEvent::Balances(crate::Event::Deposit {
 				who: 1,
 				amount: 50,
+				context: None,
 			}));
 		});
 }
@@ -153,6 +155,7 @@ fn repatriating_reserved_balance_dust_removal_should_work() {
 			System::assert_has_event(RuntimeEvent::Balances(crate::Event::Deposit {
 				who: 1,
 				amount: 50,
+				context: None,
 			}));
 		});
 }
@@ -177,7 +180,7 @@ fn emit_events_with_no_existential_deposit_suicide_with_dust() {
 		// no events
 		assert_eq!(
 			events(),
-			[RuntimeEvent::Balances(crate::Event::Withdraw { who: 1, amount: 98 })]
+			[RuntimeEvent::Balances(crate::Event::Withdraw { who: 1, amount: 98, context: None })]
 		);
 
 		let res = Balances::withdraw(&1, 1, BestEffort, Expendable, Force);
@@ -188,8 +191,77 @@ fn emit_events_with_no_existential_deposit_suicide_with_dust() {
 			[
 				RuntimeEvent::System(system::Event::KilledAccount { account: 1 }),
 				RuntimeEvent::Balances(crate::Event::DustLost { account: 1, amount: 1 }),
-				RuntimeEvent::Balances(crate::Event::Withdraw { who: 1, amount: 1 })
+				RuntimeEvent::Balances(crate::Event::Withdraw { who: 1, amount: 1, context: None })
 			]
 		);
 	});
 }
+
+#[test]
+fn transfer_no_reentry_defers_dust_removal_until_both_accounts_are_mutated() {
+	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RawOrigin::Root.into(), 2, 500));
+		DustTrapObservations::take();
+		// The dust handler re-enters the pallet and transfers between the same accounts.
+		DustTrapReentry::set(Some((3, 2, 200)));
+
+		// Account 2 drops below the existential deposit and is reaped.
+		assert_ok!(Balances::transfer_no_reentry(&2, &3, 450, Expendable), 450);
+
+		// The dust handler observed the transfer as fully applied.
+		assert_eq!(DustTrapObservations::take(), vec![(0, 450)]);
+		// The reentrant transfer succeeded on top of the committed state.
+		assert_eq!(Balances::free_balance(2), 200);
+		assert_eq!(Balances::free_balance(3), 250);
+		ensure_ti_valid();
+	});
+}
+
+#[test]
+fn transfer_no_reentry_deposits_events_after_the_transfer() {
+	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RawOrigin::Root.into(), 2, 500));
+		DustTrapReentry::set(Some((3, 2, 200)));
+		System::reset_events();
+
+		assert_ok!(Balances::transfer_no_reentry(&2, &3, 450, Expendable));
+
+		let balances_events = events()
+			.into_iter()
+			.filter(|e| matches!(e, RuntimeEvent::Balances(_)))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			balances_events,
+			[
+				RuntimeEvent::Balances(crate::Event::Transfer { from: 2, to: 3, amount: 450 }),
+				RuntimeEvent::Balances(crate::Event::DustLost { account: 2, amount: 50 }),
+				RuntimeEvent::Balances(crate::Event::Endowed { account: 3, free_balance: 450 }),
+				// Emitted by the reentrant transfer from within the dust handler.
+				RuntimeEvent::Balances(crate::Event::Transfer { from: 3, to: 2, amount: 200 }),
+				RuntimeEvent::Balances(crate::Event::Endowed { account: 2, free_balance: 200 }),
+			]
+		);
+	});
+}
+
+#[test]
+fn transfer_no_reentry_checks_funds_before_mutating() {
+	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RawOrigin::Root.into(), 2, 500));
+
+		assert_noop!(
+			Balances::transfer_no_reentry(&2, &3, 501, Expendable),
+			TokenError::FundsUnavailable
+		);
+		// Keeping the source alive limits the transferable amount.
+		assert_noop!(
+			Balances::transfer_no_reentry(&2, &3, 450, Protect),
+			TokenError::FundsUnavailable
+		);
+		// The destination must receive at least the existential deposit.
+		assert_noop!(
+			Balances::transfer_no_reentry(&2, &3, 50, Expendable),
+			TokenError::BelowMinimum
+		);
+	});
+}
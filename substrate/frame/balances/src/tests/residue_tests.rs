@@ -0,0 +1,260 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests regarding the sweeping of sub-quantum residues from free balances.
+
+use super::*;
+use frame_support::traits::{LockableCurrency, WithdrawReasons};
+use fungible::{Inspect, Mutate, MutateFreeze};
+
+fn set_residue_params(quantum: u64, threshold: u64) {
+	ResidueQuantum::set(quantum);
+	ResidueThreshold::set(threshold);
+}
+
+#[test]
+fn residue_sweeping_is_disabled_by_default() {
+	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
+		Balances::set_balance(&1, 10_000);
+		assert_ok!(Balances::transfer_allow_death(Some(1).into(), 2, 3_995));
+
+		assert_eq!(Balances::free_balance(1), 6_005);
+		assert_eq!(Balances::free_balance(2), 3_995);
+		assert!(!events()
+			.iter()
+			.any(|e| matches!(e, RuntimeEvent::Balances(crate::Event::ResidueSwept { .. }))));
+		ensure_ti_valid();
+	});
+}
+
+#[test]
+fn residue_sweeping_requires_quantum_and_threshold() {
+	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
+		Balances::set_balance(&1, 10_000);
+
+		set_residue_params(1_000, 0);
+		assert_ok!(Balances::transfer_allow_death(Some(1).into(), 2, 995));
+		assert_eq!(Balances::free_balance(1), 9_005);
+
+		set_residue_params(0, 10);
+		assert_ok!(Balances::transfer_allow_death(Some(1).into(), 2, 1_000));
+		assert_eq!(Balances::free_balance(1), 8_005);
+		ensure_ti_valid();
+	});
+}
+
+#[test]
+fn residue_is_swept_to_dust_removal() {
+	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
+		set_residue_params(1_000, 10);
+		Balances::set_balance(&1, 10_010);
+		System::reset_events();
+
+		assert_ok!(Balances::transfer_allow_death(Some(1).into(), 2, 3_005));
+
+		// `7_005 % 1_000 == 5` is swept, while the recipient is only credited and keeps its
+		// residue.
+		assert_eq!(Balances::free_balance(1), 7_000);
+		assert_eq!(Balances::free_balance(2), 3_005);
+		System::assert_has_event(RuntimeEvent::Balances(crate::Event::ResidueSwept {
+			who: 1,
+			amount: 5,
+		}));
+		// The dust trap drops the credit, so the residue leaves the total issuance.
+		assert_eq!(Balances::total_issuance(), 10_005);
+		ensure_ti_valid();
+	});
+}
+
+#[test]
+fn residue_is_swept_to_dust_trap_account() {
+	ExtBuilder::default()
+		.existential_deposit(100)
+		.dust_trap(42)
+		.build_and_execute_with(|| {
+			set_residue_params(1_000, 10);
+			Balances::set_balance(&42, 1_000);
+			Balances::set_balance(&1, 10_000);
+
+			assert_ok!(Balances::transfer_allow_death(Some(1).into(), 2, 2_993));
+
+			assert_eq!(Balances::free_balance(1), 7_000);
+			assert_eq!(Balances::free_balance(42), 1_007);
+			assert_eq!(Balances::total_issuance(), 11_000);
+			ensure_ti_valid();
+		});
+}
+
+#[test]
+fn residue_rounding_math_works() {
+	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
+		set_residue_params(1_000, 10);
+		Balances::set_balance(&1, 100_000);
+
+		// Residue exactly at the threshold is swept.
+		assert_ok!(Balances::transfer_allow_death(Some(1).into(), 2, 990));
+		assert_eq!(Balances::free_balance(1), 99_000);
+
+		// Residue just above the threshold is kept.
+		assert_ok!(Balances::transfer_allow_death(Some(1).into(), 2, 989));
+		assert_eq!(Balances::free_balance(1), 98_011);
+
+		// Exact multiples of the quantum are left untouched.
+		assert_ok!(Balances::transfer_allow_death(Some(1).into(), 2, 11));
+		assert_eq!(Balances::free_balance(1), 98_000);
+		ensure_ti_valid();
+	});
+}
+
+#[test]
+fn residue_sweeping_never_goes_below_ed() {
+	ExtBuilder::default().existential_deposit(1_003).build_and_execute_with(|| {
+		set_residue_params(1_000, 10);
+		Balances::set_balance(&1, 10_000);
+
+		// Sweeping `1_005` down to `1_000` would take the account below ED.
+		assert_ok!(Balances::transfer_allow_death(Some(1).into(), 2, 8_995));
+		assert_eq!(Balances::free_balance(1), 1_005);
+
+		// Sweeping down to exactly ED is fine.
+		ExistentialDeposit::set(1_000);
+		assert_ok!(Balances::transfer_allow_death(Some(1).into(), 2, 1));
+		// `1_004` is swept down to exactly the new ED.
+		assert_eq!(Balances::free_balance(1), 1_000);
+		assert!(System::account_exists(&1));
+		ensure_ti_valid();
+	});
+}
+
+#[test]
+fn residue_sweeping_never_goes_below_frozen_balance() {
+	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
+		set_residue_params(1_000, 10);
+		Balances::set_balance(&1, 10_010);
+
+		// Sweeping `7_005` down to `7_000` would break the lock.
+		Balances::set_lock(*b"lock    ", &1, 7_003, WithdrawReasons::all());
+		assert_ok!(Balances::transfer_allow_death(Some(1).into(), 2, 3_005));
+		assert_eq!(Balances::free_balance(1), 7_005);
+
+		// The same holds for freezes.
+		Balances::remove_lock(*b"lock    ", &1);
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &1, 6_003));
+		assert_ok!(Balances::transfer_allow_death(Some(1).into(), 2, 1_000));
+		assert_eq!(Balances::free_balance(1), 6_005);
+
+		// Sweeping down to exactly the frozen balance is fine.
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &1, 5_000));
+		assert_ok!(Balances::transfer_allow_death(Some(1).into(), 2, 1_000));
+		assert_eq!(Balances::free_balance(1), 5_000);
+		ensure_ti_valid();
+	});
+}
+
+#[test]
+fn residue_sweeping_does_not_change_reap_semantics() {
+	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
+		set_residue_params(1_000, 10);
+		Balances::set_balance(&1, 1_050);
+
+		// Going below ED still reaps the account and reports the dust as before.
+		assert_ok!(Balances::transfer_allow_death(Some(1).into(), 2, 1_000));
+		assert_eq!(Balances::free_balance(1), 0);
+		System::assert_has_event(RuntimeEvent::Balances(crate::Event::DustLost {
+			account: 1,
+			amount: 50,
+		}));
+		ensure_ti_valid();
+	});
+}
+
+#[test]
+fn residue_is_not_swept_when_free_is_unchanged() {
+	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
+		Balances::set_balance(&1, 10_005);
+		set_residue_params(1_000, 10);
+
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &1, 500));
+		assert_eq!(Balances::free_balance(1), 10_005);
+		ensure_ti_valid();
+	});
+}
+
+#[test]
+fn force_set_balance_reports_the_swept_balance() {
+	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
+		Balances::set_balance(&1, 10_000);
+		set_residue_params(1_000, 10);
+		let issuance = Balances::total_issuance();
+
+		let outcome = Balances::simulate_force_set_balance(&1, 7_005).unwrap();
+		assert_eq!(outcome.new_free, 7_000);
+		assert_eq!(outcome.dust, Some(5));
+		assert_eq!(
+			(outcome.issuance_delta, outcome.issuance_direction),
+			(2_995, crate::AdjustmentDirection::Decrease)
+		);
+		// The simulation does not change any state.
+		assert_eq!(Balances::free_balance(1), 10_000);
+		assert_eq!(Balances::total_issuance(), issuance);
+
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 1, 7_005));
+		assert_eq!(Balances::free_balance(1), 7_000);
+		System::assert_has_event(RuntimeEvent::Balances(crate::Event::BalanceSet {
+			who: 1,
+			free: 7_000,
+		}));
+		System::assert_has_event(RuntimeEvent::Balances(crate::Event::ResidueSwept {
+			who: 1,
+			amount: 5,
+		}));
+		assert_eq!(Balances::total_issuance(), issuance - 3_000);
+		ensure_ti_valid();
+	});
+}
+
+#[test]
+fn repair_account_drops_the_swept_residue_from_the_issuance() {
+	ExtBuilder::default().existential_deposit(100).build_and_execute_with(|| {
+		Balances::set_balance(&1, 10_005);
+		assert_ok!(<Balances as fungible::MutateHold<_>>::hold(&TestId::Foo, &1, 1_000));
+		// The held funds were moved back to the free balance, bypassing the pallet.
+		assert_ok!(TestAccountStore::mutate(&1, |a| {
+			a.free = 10_005;
+			a.reserved = 0;
+		}));
+		System::dec_consumers(&1);
+		let issuance = Balances::total_issuance();
+		set_residue_params(1_000, 10);
+		System::reset_events();
+
+		assert_ok!(Balances::repair_account(RuntimeOrigin::signed(2), 1));
+
+		assert_eq!(Balances::free_balance(1), 9_000);
+		assert_eq!(Balances::reserved_balance(1), 1_000);
+		System::assert_has_event(RuntimeEvent::Balances(crate::Event::ResidueSwept {
+			who: 1,
+			amount: 5,
+		}));
+		assert!(!events().iter().any(|e| matches!(
+			e,
+			RuntimeEvent::Balances(crate::Event::Unexpected(crate::UnexpectedKind::BalanceUpdated))
+		)));
+		assert_eq!(Balances::total_issuance(), issuance - 5);
+		ensure_ti_valid();
+	});
+}
@@ -0,0 +1,197 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests regarding restricted accounts, from which no balance can be withdrawn.
+
+use super::*;
+use crate::{Event, ExtraFlags};
+use frame_support::traits::{
+	fungible::{Inspect, InspectHold, Mutate, MutateHold},
+	tokens::{Fortitude, Precision},
+	Currency, ExistenceRequirement, ReservableCurrency, WithdrawReasons,
+};
+
+fn restricted_ext(f: impl Fn()) {
+	ExtBuilder::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build_and_execute_with(|| {
+			assert_ok!(Balances::restrict_account(RuntimeOrigin::root(), 1));
+			f();
+		});
+}
+
+#[test]
+fn accounts_are_restricted_by_root() {
+	ExtBuilder::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build_and_execute_with(|| {
+			assert_noop!(Balances::restrict_account(RuntimeOrigin::signed(1), 1), BadOrigin);
+			assert_ok!(Balances::restrict_account(RuntimeOrigin::root(), 1));
+			System::assert_last_event(RuntimeEvent::Balances(Event::AccountRestricted { who: 1 }));
+			assert!(Balances::account(&1).flags.is_restricted());
+			assert!(Balances::account(&1).flags.is_new_logic());
+
+			assert_noop!(Balances::unrestrict_account(RuntimeOrigin::signed(1), 1), BadOrigin);
+			assert_ok!(Balances::unrestrict_account(RuntimeOrigin::root(), 1));
+			System::assert_last_event(RuntimeEvent::Balances(Event::AccountUnrestricted {
+				who: 1,
+			}));
+			assert!(!Balances::account(&1).flags.is_restricted());
+			assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), 2, 5));
+		});
+}
+
+#[test]
+fn dead_accounts_cannot_be_restricted() {
+	ExtBuilder::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build_and_execute_with(|| {
+			assert_noop!(
+				Balances::restrict_account(RuntimeOrigin::root(), 5),
+				Error::<Test>::DeadAccount
+			);
+			assert_noop!(
+				Balances::unrestrict_account(RuntimeOrigin::root(), 5),
+				Error::<Test>::DeadAccount
+			);
+		});
+}
+
+#[test]
+fn restricted_accounts_cannot_transfer() {
+	restricted_ext(|| {
+		assert_noop!(
+			Balances::transfer_allow_death(RuntimeOrigin::signed(1), 2, 5),
+			TokenError::Frozen
+		);
+		assert_noop!(
+			Balances::transfer_keep_alive(RuntimeOrigin::signed(1), 2, 5),
+			TokenError::Frozen
+		);
+		assert_noop!(
+			<Balances as Currency<_>>::transfer(&1, &2, 5, ExistenceRequirement::AllowDeath),
+			TokenError::Frozen
+		);
+		assert_noop!(
+			<Balances as Mutate<_>>::transfer(&1, &2, 5, Preservation::Expendable),
+			TokenError::Frozen
+		);
+
+		// Incoming transfers are still allowed.
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), 1, 5));
+		assert_eq!(Balances::free_balance(1), 15);
+	});
+}
+
+#[test]
+fn restricted_accounts_cannot_transfer_all() {
+	restricted_ext(|| {
+		assert_noop!(
+			Balances::transfer_all(RuntimeOrigin::signed(1), 2, false),
+			TokenError::Frozen
+		);
+		assert_noop!(Balances::transfer_all(RuntimeOrigin::signed(1), 2, true), TokenError::Frozen);
+		assert_eq!(Balances::free_balance(1), 10);
+	});
+}
+
+#[test]
+fn restricted_accounts_cannot_burn_or_withdraw() {
+	restricted_ext(|| {
+		assert_noop!(Balances::burn(RuntimeOrigin::signed(1), 5, false), TokenError::Frozen);
+		assert_noop!(
+			Balances::burn_from(
+				&1,
+				5,
+				Preservation::Expendable,
+				Precision::Exact,
+				Fortitude::Polite
+			),
+			TokenError::Frozen
+		);
+		assert_noop!(
+			<Balances as Currency<_>>::withdraw(
+				&1,
+				5,
+				WithdrawReasons::TRANSACTION_PAYMENT,
+				ExistenceRequirement::AllowDeath
+			),
+			TokenError::Frozen
+		);
+
+		// Deposits are still allowed.
+		assert_ok!(Balances::mint_into(&1, 5));
+		assert_eq!(Balances::free_balance(1), 15);
+	});
+}
+
+#[test]
+fn restricted_accounts_cannot_place_holds_or_reserves() {
+	ExtBuilder::default()
+		.existential_deposit(1)
+		.monied(true)
+		.build_and_execute_with(|| {
+			assert_ok!(Balances::hold(&TestId::Foo, &1, 4));
+			assert_ok!(Balances::restrict_account(RuntimeOrigin::root(), 1));
+
+			// Held funds could be transferred out of the account, so no new holds are placed.
+			assert_noop!(Balances::hold(&TestId::Bar, &1, 2), TokenError::Frozen);
+			assert!(!Balances::can_hold(&TestId::Bar, &1, 2));
+			assert_noop!(Balances::reserve(&1, 2), TokenError::Frozen);
+			assert!(!Balances::can_reserve(&1, 2));
+
+			// Existing holds may be released, returning the funds to the free balance.
+			assert_ok!(Balances::release(&TestId::Foo, &1, 4, Precision::Exact));
+			assert_eq!(Balances::balance_on_hold(&TestId::Foo, &1), 0);
+			assert_eq!(Balances::free_balance(1), 10);
+			assert_eq!(
+				Balances::reducible_balance(&1, Preservation::Expendable, Fortitude::Polite),
+				10
+			);
+		});
+}
+
+#[test]
+fn restricted_accounts_can_still_be_slashed() {
+	restricted_ext(|| {
+		let (slashed, remaining) = Balances::slash(&1, 4);
+		assert_eq!((slashed.peek(), remaining), (4, 0));
+		assert_eq!(Balances::free_balance(1), 6);
+	});
+}
+
+#[test]
+fn old_flags_decode_unchanged() {
+	for old in [0u128, 0x80000000_00000000_00000000_00000000u128] {
+		let data = AccountData { free: 10u64, reserved: 0, frozen: 0, flags: ExtraFlags(old) };
+		let decoded = AccountData::<u64>::decode(&mut &data.encode()[..]).unwrap();
+		assert_eq!(decoded.flags.0, old);
+		assert!(!decoded.flags.is_restricted());
+	}
+	assert!(ExtraFlags::default().is_new_logic());
+	assert!(!ExtraFlags::default().is_restricted());
+	assert!(!ExtraFlags::old_logic().is_restricted());
+
+	let mut flags = ExtraFlags::default();
+	flags.set_restricted(true);
+	assert!(flags.is_new_logic() && flags.is_restricted());
+	flags.set_restricted(false);
+	assert_eq!(flags, ExtraFlags::default());
+}
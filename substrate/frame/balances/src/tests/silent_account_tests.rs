@@ -0,0 +1,172 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests regarding the suppression of the events of silent accounts.
+
+use super::{currency_tests::CALL, *};
+use crate::{AdjustmentDirection, BlockSilentActivity, Event, SilentAccounts};
+use frame_support::traits::{
+	fungible::{Balanced, Mutate},
+	tokens::{Fortitude, Precision},
+	Hooks, ReservableCurrency,
+};
+
+fn balances_events() -> Vec<Event<Test>> {
+	events()
+		.into_iter()
+		.filter_map(|e| match e {
+			RuntimeEvent::Balances(e) => Some(e),
+			_ => None,
+		})
+		.collect()
+}
+
+#[test]
+fn silent_accounts_are_managed_by_root() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		assert_noop!(Balances::add_silent_account(RuntimeOrigin::signed(1), 1), BadOrigin);
+		assert_ok!(Balances::add_silent_account(RuntimeOrigin::root(), 1));
+		assert!(SilentAccounts::<Test>::contains_key(1));
+
+		assert_noop!(Balances::remove_silent_account(RuntimeOrigin::signed(1), 1), BadOrigin);
+		assert_ok!(Balances::remove_silent_account(RuntimeOrigin::root(), 1));
+		assert!(!SilentAccounts::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn events_of_silent_accounts_are_aggregated() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		assert_ok!(Balances::add_silent_account(RuntimeOrigin::root(), 1));
+		events();
+
+		assert_ok!(Balances::mint_into(&1, 100));
+		drop(Balances::deposit(&1, 50, Precision::Exact).unwrap());
+		drop(
+			Balances::withdraw(&1, 20, Precision::Exact, Preservation::Preserve, Fortitude::Polite)
+				.unwrap(),
+		);
+		assert_ok!(Balances::burn_from(
+			&1,
+			10,
+			Preservation::Preserve,
+			Precision::Exact,
+			Fortitude::Polite
+		));
+
+		// the balance math is not affected
+		assert_eq!(Balances::free_balance(1), 120);
+		ensure_ti_valid();
+		assert!(!balances_events().iter().any(|e| matches!(
+			e,
+			Event::Deposit { .. } |
+				Event::Withdraw { .. } |
+				Event::Minted { .. } |
+				Event::Burned { .. }
+		)));
+
+		Balances::on_finalize(1);
+		assert_eq!(
+			balances_events(),
+			vec![Event::SilentAccountActivity {
+				count: 4,
+				net_delta: 120,
+				direction: AdjustmentDirection::Increase,
+			}]
+		);
+		assert_eq!(BlockSilentActivity::<Test>::get(), None);
+
+		// nothing to report in the next block
+		Balances::on_finalize(2);
+		assert_eq!(balances_events(), vec![]);
+	});
+}
+
+#[test]
+fn events_of_other_accounts_are_emitted() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		assert_ok!(Balances::add_silent_account(RuntimeOrigin::root(), 1));
+		events();
+
+		assert_ok!(Balances::mint_into(&2, 100));
+		drop(
+			Balances::withdraw(&2, 20, Precision::Exact, Preservation::Preserve, Fortitude::Polite)
+				.unwrap(),
+		);
+
+		assert!(balances_events().contains(&Event::Minted { who: 2, amount: 100, context: None }));
+		Balances::on_finalize(1);
+		assert_eq!(balances_events(), vec![]);
+
+		// once removed, the events of the account are emitted again
+		assert_ok!(Balances::remove_silent_account(RuntimeOrigin::root(), 1));
+		assert_ok!(Balances::mint_into(&1, 100));
+		assert!(balances_events().contains(&Event::Minted { who: 1, amount: 100, context: None }));
+	});
+}
+
+#[test]
+fn transfers_and_reserves_of_silent_accounts_are_emitted() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		assert_ok!(Balances::mint_into(&1, 100));
+		assert_ok!(Balances::add_silent_account(RuntimeOrigin::root(), 1));
+		events();
+
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), 2, 20));
+		assert_ok!(Balances::reserve(&1, 30));
+
+		let events = balances_events();
+		assert!(events.contains(&Event::Transfer { from: 1, to: 2, amount: 20 }));
+		assert!(events.contains(&Event::Reserved { who: 1, amount: 30 }));
+		Balances::on_finalize(1);
+		assert_eq!(balances_events(), vec![]);
+	});
+}
+
+#[test]
+fn transaction_fees_of_silent_accounts_are_aggregated() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		pallet_transaction_payment::NextFeeMultiplier::<Test>::put(
+			Multiplier::saturating_from_integer(1),
+		);
+		assert_ok!(Balances::mint_into(&1, 1_000_000_000));
+		assert_ok!(Balances::add_silent_account(RuntimeOrigin::root(), 1));
+		events();
+
+		assert!(<ChargeTransactionPayment<Test> as SignedExtension>::pre_dispatch(
+			ChargeTransactionPayment::from(0),
+			&1,
+			CALL,
+			&info_from_weight(Weight::from_parts(1, 0)),
+			1,
+		)
+		.is_ok());
+		let fee = 1_000_000_000 - Balances::free_balance(1);
+		assert!(fee > 0);
+		assert!(!balances_events().iter().any(|e| matches!(e, Event::Withdraw { .. })));
+
+		Balances::on_finalize(1);
+		assert_eq!(
+			balances_events(),
+			vec![Event::SilentAccountActivity {
+				count: 1,
+				net_delta: fee,
+				direction: AdjustmentDirection::Decrease,
+			}]
+		);
+	});
+}
@@ -0,0 +1,109 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests regarding the breakdown of the spendable balance of an account.
+
+use super::*;
+use crate::PreservationBreakdown;
+use frame_support::traits::{
+	fungible::{Inspect, InspectHold, Mutate, MutateFreeze, MutateHold},
+	tokens::{
+		Fortitude::{Force, Polite},
+		Preservation::{Expendable, Preserve, Protect},
+	},
+	LockableCurrency, WithdrawReasons,
+};
+
+const LOCK: LockIdentifier = *b"lock    ";
+
+#[test]
+fn spendable_breakdown_discounts_holds_from_freezes() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		Balances::set_balance(&1, 130);
+		assert_ok!(Balances::hold(&TestId::Foo, &1, 30));
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &1, 50));
+
+		let part =
+			|ed_required, polite, force| PreservationBreakdown { ed_required, polite, force };
+		let breakdown = Balances::spendable_breakdown(&1);
+		assert_eq!((breakdown.free, breakdown.frozen, breakdown.on_hold), (100, 50, 30));
+		// 20 of the free balance is frozen, which is more than the existential deposit.
+		assert_eq!(breakdown.preserve, part(10, 80, 90));
+		// the hold and the freeze keep the account alive.
+		assert_eq!(breakdown.expendable, breakdown.preserve);
+		assert_eq!(breakdown.protect, breakdown.preserve);
+
+		// once the hold covers most of the freeze, the existential deposit decides.
+		assert_ok!(Balances::hold(&TestId::Foo, &1, 15));
+		Balances::set_balance(&1, 100);
+		let breakdown = Balances::spendable_breakdown(&1);
+		assert_eq!((breakdown.free, breakdown.frozen, breakdown.on_hold), (100, 50, 45));
+		assert_eq!(breakdown.preserve, part(10, 90, 90));
+	});
+}
+
+#[test]
+fn spendable_breakdown_matches_reducible_balance() {
+	ExtBuilder::default().existential_deposit(10).build_and_execute_with(|| {
+		// every combination of locks, freezes, holds and a free balance below the existential
+		// deposit, one account each.
+		for case in 0u64..16 {
+			let (locks, freezes, holds, below_ed) =
+				(case & 1 != 0, case & 2 != 0, case & 4 != 0, case & 8 != 0);
+			let who = 100 + case;
+			Balances::set_balance(&who, 200);
+			if holds {
+				assert_ok!(Balances::hold(&TestId::Foo, &who, 40));
+			}
+			if locks {
+				Balances::set_lock(LOCK, &who, 60, WithdrawReasons::all());
+			}
+			if freezes {
+				assert_ok!(Balances::set_freeze(&TestId::Foo, &who, 120));
+			}
+			if below_ed {
+				assert_ok!(TestAccountStore::mutate(&who, |a| a.free = 5));
+			}
+
+			let breakdown = Balances::spendable_breakdown(&who);
+			let a = Balances::account(&who);
+			assert_eq!(breakdown.free, a.free);
+			assert_eq!(breakdown.frozen, a.frozen);
+			assert_eq!(breakdown.on_hold, Balances::total_balance_on_hold(&who));
+
+			// anything keeping a consumer reference requires the account to stay alive.
+			let encumbered = locks || freezes || holds;
+			for preservation in [Expendable, Preserve, Protect] {
+				let ed_required = if preservation == Expendable && !encumbered { 0 } else { 10 };
+				let part = breakdown.for_preservation(preservation);
+				assert_eq!(part.ed_required, ed_required);
+
+				let frozen = breakdown.frozen.saturating_sub(breakdown.on_hold);
+				let polite = breakdown.free.saturating_sub(frozen.max(ed_required));
+				let force = breakdown.free.saturating_sub(ed_required);
+				assert_eq!((part.polite, part.force), (polite, force));
+
+				for fortitude in [Polite, Force] {
+					assert_eq!(
+						breakdown.reducible(preservation, fortitude),
+						Balances::reducible_balance(&who, preservation, fortitude),
+					);
+				}
+			}
+		}
+	});
+}
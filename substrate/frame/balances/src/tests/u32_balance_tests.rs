@@ -0,0 +1,226 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for a minimal runtime using `u32` balances.
+
+use super::TestId;
+use crate::{self as pallet_balances, AdjustmentDirection, Event, TotalIssuance, TryStateSample};
+use frame_support::{
+	assert_noop, assert_ok, derive_impl,
+	traits::{
+		fungible::{Inspect, Mutate, MutateHold},
+		tokens::{Fortitude, Precision, Preservation},
+		ConstU32, Currency,
+		ExistenceRequirement::AllowDeath,
+		Imbalance, LockableCurrency, ReservableCurrency, WithdrawReasons,
+	},
+};
+use frame_system::RawOrigin;
+use sp_runtime::{ArithmeticError, BuildStorage, TokenError};
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		Balances: pallet_balances,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u32>;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = u32;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU32<2>;
+	#[cfg(feature = "insecure_zero_ed")]
+	type AccountSufficiency = ();
+	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
+	type WeightInfo = ();
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ConstU32<2>;
+	type ReserveIdentifier = TestId;
+	type FreezeIdentifier = TestId;
+	type MaxFreezes = ConstU32<2>;
+	type RuntimeHoldReason = TestId;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type OnInconsistency = ();
+	type ResidueQuantum = ();
+	type ResidueThreshold = ();
+	type MaxExpiringPerBlock = ();
+	type MinTransferAmount = ();
+	type LockToFreezeMapping = ();
+	type ManagedFreezes = ();
+	type BurnableHolds = ();
+	type LenientFreezeBound = ();
+	type DoneSlashHandler = ();
+}
+
+const ID_1: frame_support::traits::LockIdentifier = *b"1       ";
+
+fn new_test_ext(balances: Vec<(u64, u32)>) -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Runtime> { balances, ..Default::default() }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Check that the total issuance matches the sum of all accounts and run the `try_state` checks.
+fn check_state() {
+	let sum = frame_system::Account::<Runtime>::iter_values()
+		.map(|info| info.data.free as u64 + info.data.reserved as u64)
+		.sum::<u64>();
+	assert_eq!(TotalIssuance::<Runtime>::get() as u64, sum);
+	assert_ok!(Balances::do_try_state(TryStateSample::Full));
+}
+
+#[test]
+fn genesis_up_to_max_issuance_works() {
+	new_test_ext(vec![(1, u32::MAX - 10), (2, 10)]).execute_with(|| {
+		assert_eq!(Balances::total_issuance(), u32::MAX);
+		assert_eq!(Balances::free_balance(1), u32::MAX - 10);
+		check_state();
+	});
+}
+
+#[test]
+#[should_panic(expected = "the total issuance of the genesis balances overflows `T::Balance`")]
+fn genesis_beyond_max_issuance_panics() {
+	new_test_ext(vec![(1, u32::MAX), (2, 2)]);
+}
+
+#[test]
+fn transfers_work_near_max_issuance() {
+	new_test_ext(vec![(1, u32::MAX - 2), (2, 2)]).execute_with(|| {
+		assert_ok!(Balances::transfer_allow_death(Some(1).into(), 3, u32::MAX / 2));
+		assert_eq!(Balances::free_balance(3), u32::MAX / 2);
+
+		assert_noop!(
+			Balances::transfer_keep_alive(Some(2).into(), 3, 2),
+			TokenError::NotExpendable
+		);
+		assert_noop!(
+			Balances::transfer_allow_death(Some(2).into(), 3, 3),
+			TokenError::FundsUnavailable
+		);
+
+		assert_ok!(Balances::transfer_all(Some(3).into(), 1, false));
+		assert_eq!(Balances::free_balance(1), u32::MAX - 2);
+		assert!(!frame_system::Account::<Runtime>::contains_key(3));
+		assert_eq!(Balances::total_issuance(), u32::MAX);
+		check_state();
+	});
+}
+
+#[test]
+fn reserves_holds_and_locks_work() {
+	new_test_ext(vec![(1, u32::MAX)]).execute_with(|| {
+		assert_ok!(Balances::reserve(&1, u32::MAX / 4));
+		assert_ok!(<Balances as MutateHold<_>>::hold(&TestId::Foo, &1, u32::MAX / 4));
+		assert_eq!(Balances::reserved_balance(1), u32::MAX / 4 * 2);
+		assert_eq!(Balances::free_balance(1), u32::MAX - u32::MAX / 4 * 2);
+
+		Balances::set_lock(ID_1, &1, u32::MAX, WithdrawReasons::all());
+		assert_noop!(
+			<Balances as Currency<_>>::transfer(&1, &2, 2, AllowDeath),
+			TokenError::Frozen
+		);
+		Balances::remove_lock(ID_1, &1);
+
+		assert_eq!(Balances::unreserve(&1, u32::MAX / 4), 0);
+		assert_ok!(<Balances as MutateHold<_>>::release(
+			&TestId::Foo,
+			&1,
+			u32::MAX / 4,
+			Precision::Exact
+		));
+		assert_eq!(Balances::free_balance(1), u32::MAX);
+		check_state();
+	});
+}
+
+#[test]
+fn minting_beyond_max_issuance_fails() {
+	new_test_ext(vec![(1, 100)]).execute_with(|| {
+		assert_ok!(<Balances as Mutate<_>>::mint_into(&2, u32::MAX - 100));
+		assert_eq!(Balances::total_issuance(), u32::MAX);
+		assert_noop!(<Balances as Mutate<_>>::mint_into(&3, 2), ArithmeticError::Overflow);
+
+		assert_ok!(<Balances as Mutate<_>>::burn_from(
+			&2,
+			u32::MAX / 2,
+			Preservation::Expendable,
+			Precision::Exact,
+			Fortitude::Polite
+		));
+		assert_eq!(Balances::total_issuance(), u32::MAX - u32::MAX / 2);
+		assert_eq!(
+			<Balances as Inspect<_>>::reducible_balance(
+				&2,
+				Preservation::Preserve,
+				Fortitude::Polite
+			),
+			u32::MAX - 100 - u32::MAX / 2 - 2
+		);
+		check_state();
+	});
+}
+
+#[test]
+fn currency_issuance_saturates_at_max() {
+	new_test_ext(vec![(1, u32::MAX - 10)]).execute_with(|| {
+		let credit = Balances::issue(100);
+		assert_eq!(credit.peek(), 10);
+		assert_eq!(Balances::total_issuance(), u32::MAX);
+
+		Balances::resolve_creating(&2, credit);
+		assert_eq!(Balances::free_balance(2), 10);
+		check_state();
+	});
+}
+
+#[test]
+fn root_calls_work_near_max_issuance() {
+	new_test_ext(vec![(1, u32::MAX - 10)]).execute_with(|| {
+		assert_ok!(Balances::force_set_balance(RawOrigin::Root.into(), 2, 10));
+		assert_eq!(Balances::total_issuance(), u32::MAX);
+		check_state();
+
+		assert_ok!(Balances::force_adjust_total_issuance(
+			RawOrigin::Root.into(),
+			AdjustmentDirection::Increase,
+			5
+		));
+		System::assert_last_event(RuntimeEvent::Balances(Event::TotalIssuanceForced {
+			old: u32::MAX,
+			new: u32::MAX,
+		}));
+
+		assert_ok!(Balances::force_set_balance(RawOrigin::Root.into(), 1, 0));
+		assert_eq!(Balances::total_issuance(), 10);
+		check_state();
+	});
+}
@@ -0,0 +1,195 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests that operations moving a zero amount are no-ops.
+//!
+//! `assert_storage_noop!` also covers events, since they are kept in storage.
+
+use super::*;
+use crate::{AdjustmentDirection, TransferRemark};
+use frame_support::traits::{
+	fungible::{Balanced, InspectFreeze, Mutate, MutateFreeze, MutateHold},
+	tokens::{Fortitude, Precision},
+	Currency, ExistenceRequirement, ReservableCurrency,
+};
+
+/// An account which does not exist in the monied genesis.
+const DEAD: u64 = 99;
+
+#[test]
+fn zero_transfers_are_no_ops() {
+	ExtBuilder::default().monied(true).build_and_execute_with(|| {
+		// Even below the minimum transfer amount.
+		MinTransferAmount::set(5);
+		let remark = TransferRemark::default();
+
+		assert_storage_noop!(assert_ok!(Balances::transfer_allow_death(Some(1).into(), DEAD, 0)));
+		assert_storage_noop!(assert_ok!(Balances::transfer_keep_alive(Some(1).into(), DEAD, 0)));
+		assert_storage_noop!(assert_ok!(Balances::force_transfer(
+			RawOrigin::Root.into(),
+			1,
+			DEAD,
+			0
+		)));
+		assert_storage_noop!(assert_ok!(Balances::transfer_allow_death_with_remark(
+			Some(1).into(),
+			DEAD,
+			0,
+			remark.clone()
+		)));
+		assert_storage_noop!(assert_ok!(Balances::transfer_keep_alive_with_remark(
+			Some(1).into(),
+			DEAD,
+			0,
+			remark
+		)));
+		assert!(!System::account_exists(&DEAD));
+
+		// A dead source account does not matter either.
+		assert_storage_noop!(assert_ok!(Balances::transfer_allow_death(Some(DEAD).into(), 1, 0)));
+
+		// The origin is still checked.
+		assert_noop!(Balances::force_transfer(Some(1).into(), 1, DEAD, 0), BadOrigin);
+	});
+}
+
+#[test]
+fn zero_burns_are_no_ops() {
+	ExtBuilder::default().monied(true).build_and_execute_with(|| {
+		assert_storage_noop!(assert_ok!(Balances::burn(Some(1).into(), 0, false)));
+		assert_storage_noop!(assert_ok!(Balances::burn(Some(1).into(), 0, true)));
+		assert_storage_noop!(assert_ok!(Balances::burn_with_hold(Some(1).into(), 0, false, None)));
+		assert_storage_noop!(assert_ok!(Balances::burn_with_hold(
+			Some(1).into(),
+			0,
+			false,
+			Some(TestId::Foo)
+		)));
+		assert_storage_noop!(assert_ok!(Balances::burn(Some(DEAD).into(), 0, false)));
+	});
+}
+
+#[test]
+fn unreserving_nothing_emits_no_event() {
+	ExtBuilder::default().monied(true).build_and_execute_with(|| {
+		assert_storage_noop!(assert_ok!(Balances::force_unreserve(RawOrigin::Root.into(), 1, 0)));
+		// Nothing is reserved, so nothing is unreserved.
+		assert_storage_noop!(assert_ok!(Balances::force_unreserve(RawOrigin::Root.into(), 1, 5)));
+
+		assert_ok!(Balances::reserve(&1, 5));
+		events();
+		assert_ok!(Balances::force_unreserve(RawOrigin::Root.into(), 1, 10));
+		assert_eq!(
+			events(),
+			[RuntimeEvent::Balances(crate::Event::Unreserved { who: 1, amount: 5 })],
+		);
+	});
+}
+
+#[test]
+fn zero_fungible_operations_are_no_ops() {
+	ExtBuilder::default().monied(true).build_and_execute_with(|| {
+		assert_storage_noop!(assert_eq!(
+			<Balances as Mutate<_>>::transfer(&1, &DEAD, 0, Preservation::Expendable),
+			Ok(0)
+		));
+		assert_storage_noop!(assert_eq!(
+			<Balances as Mutate<_>>::transfer(&1, &DEAD, 0, Preservation::Preserve),
+			Ok(0)
+		));
+		assert_storage_noop!(assert_eq!(Balances::mint_into(&1, 0), Ok(0)));
+		for who in [1, DEAD] {
+			assert_storage_noop!(assert_eq!(
+				Balances::burn_from(
+					&who,
+					0,
+					Preservation::Expendable,
+					Precision::Exact,
+					Fortitude::Polite,
+				),
+				Ok(0)
+			));
+		}
+		assert_storage_noop!(drop(Balances::deposit(&1, 0, Precision::Exact).unwrap()));
+		assert_storage_noop!(drop(
+			<Balances as Balanced<_>>::withdraw(
+				&1,
+				0,
+				Precision::Exact,
+				Preservation::Expendable,
+				Fortitude::Polite,
+			)
+			.unwrap()
+		));
+		assert_storage_noop!(assert_ok!(<Balances as Currency<_>>::transfer(
+			&1,
+			&DEAD,
+			0,
+			ExistenceRequirement::AllowDeath
+		)));
+		assert!(!System::account_exists(&DEAD));
+	});
+}
+
+#[test]
+fn zero_holds_are_no_ops() {
+	ExtBuilder::default().monied(true).build_and_execute_with(|| {
+		assert_storage_noop!(assert_ok!(Balances::hold(&TestId::Foo, &1, 0)));
+		assert_storage_noop!(assert_eq!(
+			Balances::release(&TestId::Foo, &1, 0, Precision::Exact),
+			Ok(0)
+		));
+		// No hold could be created for a dead account, yet holding nothing succeeds.
+		assert_storage_noop!(assert_ok!(Balances::hold(&TestId::Foo, &DEAD, 0)));
+		assert_storage_noop!(assert_eq!(
+			Balances::release(&TestId::Foo, &DEAD, 0, Precision::Exact),
+			Ok(0)
+		));
+
+		assert_ok!(Balances::hold(&TestId::Foo, &1, 5));
+		assert_storage_noop!(assert_ok!(Balances::hold(&TestId::Foo, &1, 0)));
+		assert_storage_noop!(assert_eq!(
+			Balances::burn_held(&TestId::Foo, &1, 0, Precision::Exact, Fortitude::Polite),
+			Ok(0)
+		));
+	});
+}
+
+#[test]
+fn zero_amounts_follow_fungible_semantics_where_required() {
+	ExtBuilder::default().monied(true).build_and_execute_with(|| {
+		// Minting below the minimum balance fails, as required by the conformance tests.
+		assert_noop!(Balances::mint_into(&DEAD, 0), TokenError::BelowMinimum);
+
+		// Setters treat zero as the new value.
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &1, 5));
+		assert_ok!(Balances::set_freeze(&TestId::Foo, &1, 0));
+		assert_eq!(Balances::balance_frozen(&TestId::Foo, &1), 0);
+		assert_ok!(Balances::force_set_balance(RawOrigin::Root.into(), 2, 0));
+		assert!(!System::account_exists(&2));
+
+		// Forced adjustments of the issuance reject a zero delta.
+		assert_noop!(
+			Balances::force_adjust_total_issuance(
+				RawOrigin::Root.into(),
+				AdjustmentDirection::Increase,
+				0
+			),
+			Error::<Test>::DeltaZero,
+		);
+	});
+}
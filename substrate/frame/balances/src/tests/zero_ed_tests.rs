@@ -0,0 +1,198 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for a runtime with a zero existential deposit, using the `insecure_zero_ed` feature.
+
+use super::TestId;
+use crate::{self as pallet_balances, SufficientAccounts};
+use frame_support::{
+	assert_noop, assert_ok, derive_impl,
+	traits::{fungible::Mutate, ConstU32, ConstU64, Contains},
+};
+use frame_system::RawOrigin;
+use sp_runtime::{BuildStorage, DispatchError};
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+frame_support::construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		Balances: pallet_balances,
+	}
+);
+
+/// The account holding the initial balance.
+const RICH: u64 = 1;
+/// An account which is not sufficient.
+const USER: u64 = 2;
+/// An account which is sufficient through [`IsContract`].
+const CONTRACT: u64 = 1000;
+
+/// Accounts from `1000` on are sufficient, like contract accounts.
+pub struct IsContract;
+
+impl Contains<u64> for IsContract {
+	fn contains(who: &u64) -> bool {
+		*who >= CONTRACT
+	}
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = u64;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU64<0>;
+	type AccountSufficiency = IsContract;
+	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
+	type WeightInfo = ();
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ConstU32<2>;
+	type ReserveIdentifier = TestId;
+	type FreezeIdentifier = TestId;
+	type MaxFreezes = ConstU32<2>;
+	type RuntimeHoldReason = TestId;
+	type RuntimeFreezeReason = TestId;
+	type OnInconsistency = ();
+	type ResidueQuantum = ();
+	type ResidueThreshold = ();
+	type MaxExpiringPerBlock = ();
+	type MinTransferAmount = ();
+	type LockToFreezeMapping = ();
+	type ManagedFreezes = ();
+	type BurnableHolds = ();
+	type LenientFreezeBound = ();
+	type DoneSlashHandler = ();
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Runtime> { balances: vec![(RICH, 100)], ..Default::default() }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Send `amount` from `who` back to [`RICH`].
+fn send_back(who: u64, amount: u64) {
+	assert_ok!(Balances::transfer_allow_death(Some(who).into(), RICH, amount));
+}
+
+#[test]
+fn non_sufficient_accounts_need_one_unit() {
+	new_test_ext().execute_with(|| {
+		assert!(!Balances::is_sufficient(&USER));
+
+		assert_ok!(Balances::transfer_allow_death(Some(RICH).into(), USER, 1));
+		assert_eq!(System::providers(&USER), 1);
+		assert_eq!(Balances::free_balance(USER), 1);
+
+		// Going down to zero reaps the account.
+		send_back(USER, 1);
+		assert!(!System::account_exists(&USER));
+		assert_eq!(System::providers(&USER), 0);
+
+		// The same holds for setting the balance directly.
+		assert_ok!(Balances::force_set_balance(RawOrigin::Root.into(), USER, 1));
+		assert_eq!(System::providers(&USER), 1);
+		assert_ok!(Balances::force_set_balance(RawOrigin::Root.into(), USER, 0));
+		assert!(!System::account_exists(&USER));
+	});
+}
+
+#[test]
+fn sufficient_accounts_may_hold_zero() {
+	new_test_ext().execute_with(|| {
+		assert!(Balances::is_sufficient(&CONTRACT));
+
+		assert_ok!(Balances::transfer_allow_death(Some(RICH).into(), CONTRACT, 1));
+		assert_eq!(System::providers(&CONTRACT), 1);
+
+		// The account survives with a zero balance, keeping its single provider reference.
+		send_back(CONTRACT, 1);
+		assert!(System::account_exists(&CONTRACT));
+		assert_eq!(System::providers(&CONTRACT), 1);
+		assert_eq!(Balances::free_balance(CONTRACT), 0);
+
+		assert_ok!(Balances::mint_into(&CONTRACT, 1));
+		assert_eq!(System::providers(&CONTRACT), 1);
+		send_back(CONTRACT, 1);
+		assert_eq!(System::providers(&CONTRACT), 1);
+	});
+}
+
+#[test]
+fn registered_accounts_are_sufficient() {
+	new_test_ext().execute_with(|| {
+		// Registering does not create the account.
+		assert_ok!(Balances::set_account_sufficiency(&USER, true));
+		assert!(Balances::is_sufficient(&USER));
+		assert!(SufficientAccounts::<Runtime>::contains_key(USER));
+		assert!(!System::account_exists(&USER));
+
+		assert_ok!(Balances::transfer_allow_death(Some(RICH).into(), USER, 1));
+		send_back(USER, 1);
+		assert!(System::account_exists(&USER));
+		assert_eq!(System::providers(&USER), 1);
+
+		// Revoking the sufficiency of an account without balance reaps it.
+		assert_ok!(Balances::set_account_sufficiency(&USER, false));
+		assert!(!Balances::is_sufficient(&USER));
+		assert!(!System::account_exists(&USER));
+		assert_eq!(System::providers(&USER), 0);
+	});
+}
+
+#[test]
+fn revoking_sufficiency_keeps_accounts_with_one_unit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::set_account_sufficiency(&USER, true));
+		assert_ok!(Balances::transfer_allow_death(Some(RICH).into(), USER, 1));
+
+		assert_ok!(Balances::set_account_sufficiency(&USER, false));
+		assert_eq!(System::providers(&USER), 1);
+		assert_eq!(Balances::free_balance(USER), 1);
+
+		// Accounts matched by the config stay sufficient.
+		assert_ok!(Balances::set_account_sufficiency(&CONTRACT, false));
+		assert!(Balances::is_sufficient(&CONTRACT));
+	});
+}
+
+#[test]
+fn revoking_sufficiency_fails_while_references_are_needed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::set_account_sufficiency(&USER, true));
+		assert_ok!(Balances::transfer_allow_death(Some(RICH).into(), USER, 1));
+		send_back(USER, 1);
+		assert_ok!(System::inc_consumers(&USER));
+
+		assert_noop!(
+			Balances::set_account_sufficiency(&USER, false),
+			DispatchError::ConsumerRemaining
+		);
+		assert!(Balances::is_sufficient(&USER));
+	});
+}
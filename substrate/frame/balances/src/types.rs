@@ -20,9 +20,16 @@
 use crate::{Config, CreditOf, Event, Pallet};
 use codec::{Decode, Encode, MaxEncodedLen};
 use core::ops::BitOr;
-use frame_support::traits::{Imbalance, LockIdentifier, OnUnbalanced, WithdrawReasons};
+use frame_support::{
+	traits::{
+		tokens::{Fortitude, Preservation},
+		ConstU32, Get, Imbalance, LockIdentifier, OnUnbalanced, WithdrawReasons,
+	},
+	BoundedVec, PalletError,
+};
 use scale_info::TypeInfo;
-use sp_runtime::{RuntimeDebug, Saturating};
+use sp_runtime::{traits::Zero, RuntimeDebug, Saturating};
+use sp_std::vec::Vec;
 
 /// Simplified reasons for withdrawing balance.
 #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
@@ -66,6 +73,9 @@ pub struct BalanceLock<Balance> {
 	/// The amount which the free balance may not drop below when this lock is in effect.
 	pub amount: Balance,
 	/// If true, then the lock remains in effect even for payment of transaction fees.
+	///
+	/// This is a plain value, not a bitfield to be extended. Freezes, which apply to all
+	/// reasons, should be used instead of new locks.
 	pub reasons: Reasons,
 }
 
@@ -103,13 +113,23 @@ pub struct AccountData<Balance> {
 	/// actions where the account owner cannot reasonably benefit from the balance reduction, such
 	/// as slashing.
 	pub frozen: Balance,
-	/// Extra information about this account. The MSB is a flag indicating whether the new ref-
-	/// counting logic is in place for this account.
+	/// Extra information about this account, see [`ExtraFlags`] for the meaning of its bits.
 	pub flags: ExtraFlags,
 }
 
 const IS_NEW_LOGIC: u128 = 0x80000000_00000000_00000000_00000000u128;
+const IS_RESTRICTED: u128 = 0x40000000_00000000_00000000_00000000u128;
 
+/// Flags of an account, stored as part of its [`AccountData`].
+///
+/// The bits are part of the consensus-relevant account data, so their positions must never
+/// change. Counting from the most significant bit:
+///
+/// - Bit 127 is set once the account uses the new ref-counting logic.
+/// - Bit 126 is set while the account is restricted, see [`Pallet::restrict_account`].
+/// - All other bits are unused and zero.
+///
+/// [`Pallet::restrict_account`]: crate::Pallet::restrict_account
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 pub struct ExtraFlags(pub(crate) u128);
 impl Default for ExtraFlags {
@@ -127,6 +147,16 @@ impl ExtraFlags {
 	pub fn is_new_logic(&self) -> bool {
 		(self.0 & IS_NEW_LOGIC) == IS_NEW_LOGIC
 	}
+	pub fn set_restricted(&mut self, restricted: bool) {
+		if restricted {
+			self.0 = self.0 | IS_RESTRICTED
+		} else {
+			self.0 = self.0 & !IS_RESTRICTED
+		}
+	}
+	pub fn is_restricted(&self) -> bool {
+		(self.0 & IS_RESTRICTED) == IS_RESTRICTED
+	}
 }
 
 impl<Balance: Saturating + Copy + Ord> AccountData<Balance> {
@@ -161,3 +191,394 @@ pub enum AdjustmentDirection {
 	/// Decrease the amount.
 	Decrease,
 }
+
+/// The effects of setting the free balance of an account through `force_set_balance`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ForceSetOutcome<Balance> {
+	/// The free balance the account ends up with, after sweeping any residue. Zero if the
+	/// requested balance is below the existential deposit.
+	pub new_free: Balance,
+	/// By how much the total issuance is adjusted, not accounting for any `dust`.
+	pub issuance_delta: Balance,
+	/// Whether the total issuance is increased or decreased by `issuance_delta`.
+	pub issuance_direction: AdjustmentDirection,
+	/// Whether the account is removed from storage.
+	pub reaped: bool,
+	/// The amount handed to `DustRemoval`, if any, including a swept residue.
+	pub dust: Option<Balance>,
+	/// The change in the provider references of the account.
+	pub providers_delta: i32,
+	/// The change in the consumer references of the account.
+	pub consumers_delta: i32,
+}
+
+/// The balance left in an account after transferring all of its transferable balance, e.g.
+/// through `transfer_all`.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct TransferAllRemainder<Balance> {
+	/// The part of the free balance retained as existential deposit, beyond `frozen_excess`.
+	pub ed_retained: Balance,
+	/// The balance on hold, including reserves.
+	pub held: Balance,
+	/// The part of the free balance retained by freezes and locks exceeding `held`.
+	pub frozen_excess: Balance,
+}
+
+/// The balance of an account split into its parts, together with the balance which could be
+/// reduced under every combination of [`Preservation`] and [`Fortitude`], see
+/// `Pallet::spendable_breakdown`.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct SpendableBreakdown<Balance> {
+	/// The free balance.
+	pub free: Balance,
+	/// The largest lock or freeze. It applies to the total balance, so the balance on hold counts
+	/// towards it.
+	pub frozen: Balance,
+	/// The balance on hold, including reserves.
+	pub on_hold: Balance,
+	/// The reducible balance with [`Preservation::Expendable`].
+	pub expendable: PreservationBreakdown<Balance>,
+	/// The reducible balance with [`Preservation::Preserve`].
+	pub preserve: PreservationBreakdown<Balance>,
+	/// The reducible balance with [`Preservation::Protect`].
+	pub protect: PreservationBreakdown<Balance>,
+}
+
+impl<Balance: Copy> SpendableBreakdown<Balance> {
+	/// The part of the breakdown which applies to `preservation`.
+	pub fn for_preservation(&self, preservation: Preservation) -> &PreservationBreakdown<Balance> {
+		match preservation {
+			Preservation::Expendable => &self.expendable,
+			Preservation::Preserve => &self.preserve,
+			Preservation::Protect => &self.protect,
+		}
+	}
+
+	/// The reducible balance with the given `preservation` and `force`.
+	pub fn reducible(&self, preservation: Preservation, force: Fortitude) -> Balance {
+		let breakdown = self.for_preservation(preservation);
+		match force {
+			Fortitude::Polite => breakdown.polite,
+			Fortitude::Force => breakdown.force,
+		}
+	}
+}
+
+/// The reducible balance of an account under a single [`Preservation`].
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PreservationBreakdown<Balance> {
+	/// The existential deposit which has to stay in the account, or zero if it may be reaped.
+	pub ed_required: Balance,
+	/// The reducible balance with [`Fortitude::Polite`], which respects locks and freezes.
+	pub polite: Balance,
+	/// The reducible balance with [`Fortitude::Force`], which ignores locks and freezes.
+	pub force: Balance,
+}
+
+/// The encumbrances which keep an account from being closed, see
+/// `Pallet::prepare_account_closure`.
+///
+/// Each count saturates at `u8::MAX`.
+#[derive(
+	Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, PalletError,
+)]
+pub struct ClosureBlockers {
+	/// The number of holds.
+	pub holds: u8,
+	/// The number of locks, plus the number of freezes not in `Config::ManagedFreezes`.
+	pub locks: u8,
+	/// The number of named reserves, plus one if some balance is reserved outside of them.
+	pub reserves: u8,
+}
+
+impl ClosureBlockers {
+	/// Whether nothing keeps the account from being closed.
+	pub fn is_empty(&self) -> bool {
+		*self == Self::default()
+	}
+}
+
+/// Why a withdrawal of some amount from an account would fail, see
+/// `Pallet::withdraw_failure_reason`.
+///
+/// Only the most specific reason is reported, in the order of the variants below. Account
+/// restrictions are not considered.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum WithdrawBlocked<Balance> {
+	/// The free and held balance together fall short of the amount by `shortfall`.
+	InsufficientFree { shortfall: Balance },
+	/// The amount exceeds the free balance but not the free and `held` balance together.
+	BlockedByHold { held: Balance },
+	/// The amount exceeds the free balance left by the freezes and locks by `freeze_excess`.
+	BlockedByFreeze { freeze_excess: Balance },
+	/// The amount exceeds the balance left by the existential deposit, which has to be kept, by
+	/// `shortfall`.
+	WouldDustBelowEd { shortfall: Balance },
+	/// Nothing blocks the withdrawal.
+	Ok,
+}
+
+/// The balance changes of silent accounts within the current block, whose `Deposit`,
+/// `Withdraw`, `Minted` and `Burned` events were suppressed.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct SilentActivity<Balance> {
+	/// The number of suppressed events.
+	pub count: u32,
+	/// The total amount deposited or minted into silent accounts.
+	pub credited: Balance,
+	/// The total amount withdrawn or burned from silent accounts.
+	pub debited: Balance,
+}
+
+/// The kind of an unexpected, defensive, situation encountered by the pallet.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum UnexpectedKind {
+	/// The balance of an account was altered or dusted by an operation that should not have done
+	/// so.
+	BalanceUpdated,
+	/// Mutating an account failed unexpectedly. This might leave the storage items of this pallet
+	/// and the underlying account store out of sync.
+	FailedToMutateAccount,
+	/// The reserved or frozen balance of an account did not match its holds, freezes and locks
+	/// and was repaired.
+	AccountRepaired,
+	/// The total issuance changed by a different amount within the block than recorded by the
+	/// issuance audit. Only reported with the `issuance-audit` feature.
+	IssuanceDrift,
+}
+
+/// The maximum number of entries kept in the [`IssuanceAudit`] of a block.
+pub const MAX_ISSUANCE_AUDIT_ENTRIES: u32 = 1024;
+
+/// A typed view of the locks of an account, see [`Pallet::lock_summary`].
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct LockSummary<Balance> {
+	/// The amount of the largest lock.
+	pub max_amount: Balance,
+	/// The union of the reasons of all locks, `None` if there are no locks.
+	pub reasons: Option<Reasons>,
+	/// The locks, one per lock identifier.
+	pub locks: Vec<BalanceLock<Balance>>,
+	/// Whether the `frozen` balance equals the largest lock or freeze, as checked by the
+	/// try-state checks.
+	pub frozen_consistent: bool,
+}
+
+/// The maximum length of the [`TransferRemark`] of a transfer.
+pub const MAX_TRANSFER_REMARK_LEN: u32 = 128;
+
+/// A memo attached to a transfer, e.g. a payment reference for reconciliation.
+pub type TransferRemark = BoundedVec<u8, ConstU32<MAX_TRANSFER_REMARK_LEN>>;
+
+/// An operation recorded by the issuance audit.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum IssuanceAuditOperation {
+	/// A `PositiveImbalance` was created, to be added to the total issuance when dropped.
+	PositiveImbalance,
+	/// A `NegativeImbalance` was created, to be removed from the total issuance when dropped.
+	NegativeImbalance,
+	/// Funds were added to the total issuance through `Currency::issue`.
+	Issue,
+	/// Funds were removed from the total issuance through `Currency::burn`.
+	Burn,
+	/// Funds were minted into an account through `fungible::Mutate`.
+	Mint,
+	/// Funds were burned from an account or its holds through `fungible::Mutate`.
+	BurnFrom,
+	/// Funds were deposited into an account, creating a debt.
+	Deposit,
+	/// Funds were withdrawn or slashed from an account, creating a credit.
+	Withdraw,
+	/// Dust was removed from an account, creating a credit.
+	Dust,
+	/// The total issuance was written directly by the pallet.
+	Forced,
+}
+
+/// An entry of the [`IssuanceAudit`].
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct IssuanceAuditEntry<AccountId, Balance> {
+	/// The operation.
+	pub operation: IssuanceAuditOperation,
+	/// The account affected by the operation, if known.
+	pub who: Option<AccountId>,
+	/// Whether the operation increases or decreases the total issuance.
+	pub direction: AdjustmentDirection,
+	/// The amount by which the total issuance changes.
+	pub amount: Balance,
+}
+
+/// The ledger of all changes of the total issuance recorded within a block.
+///
+/// Once all imbalances created within the block are dropped, the total issuance is expected to
+/// have changed by `increased - decreased`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct IssuanceAudit<AccountId, Balance> {
+	/// The total issuance at the start of the block.
+	pub initial_issuance: Balance,
+	/// The sum of all recorded increases.
+	pub increased: Balance,
+	/// The sum of all recorded decreases.
+	pub decreased: Balance,
+	/// The recorded entries, in order.
+	pub entries:
+		BoundedVec<IssuanceAuditEntry<AccountId, Balance>, ConstU32<MAX_ISSUANCE_AUDIT_ENTRIES>>,
+	/// The number of entries which did not fit into `entries`. They are still accounted for in
+	/// `increased` and `decreased`.
+	pub overflow: u32,
+}
+
+impl<AccountId, Balance: Saturating + Zero + Copy + PartialEq> IssuanceAudit<AccountId, Balance> {
+	/// An empty ledger for a block starting with `initial_issuance`.
+	pub(crate) fn new(initial_issuance: Balance) -> Self {
+		Self {
+			initial_issuance,
+			increased: Zero::zero(),
+			decreased: Zero::zero(),
+			entries: Default::default(),
+			overflow: 0,
+		}
+	}
+
+	/// Record an entry.
+	pub(crate) fn record(&mut self, entry: IssuanceAuditEntry<AccountId, Balance>) {
+		match entry.direction {
+			AdjustmentDirection::Increase => self.increased.saturating_accrue(entry.amount),
+			AdjustmentDirection::Decrease => self.decreased.saturating_accrue(entry.amount),
+		}
+		if self.entries.try_push(entry).is_err() {
+			self.overflow.saturating_inc();
+		}
+	}
+
+	/// Whether the change from `initial_issuance` to `issuance` matches the recorded entries.
+	pub fn reconciles(&self, issuance: Balance) -> bool {
+		self.initial_issuance.saturating_add(self.increased) ==
+			issuance.saturating_add(self.decreased)
+	}
+}
+
+/// A store holding the account data of this pallet.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum AccountStoreKind {
+	/// The `Account` map of this pallet.
+	Balances,
+	/// The `data` of `frame_system::Account`.
+	System,
+}
+
+/// [`Config::AuthoritativeStore`] of a pallet keeping the account data in `frame_system`.
+pub struct SystemStore;
+
+impl Get<AccountStoreKind> for SystemStore {
+	fn get() -> AccountStoreKind {
+		AccountStoreKind::System
+	}
+}
+
+/// [`Config::AuthoritativeStore`] of a pallet keeping the account data in its `Account` map.
+pub struct BalancesStore;
+
+impl Get<AccountStoreKind> for BalancesStore {
+	fn get() -> AccountStoreKind {
+		AccountStoreKind::Balances
+	}
+}
+
+/// The progress of a reconciliation of the total issuance with the balances of all accounts.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct IssuanceReconciliation<AccountId, Balance> {
+	/// The last visited account.
+	pub last: AccountId,
+	/// The sum of the total balances of all visited accounts.
+	pub sum: Balance,
+}
+
+/// The balance data of an account found in the account store which is not in use.
+pub(crate) struct GhostEntry {
+	/// Whether the account is also present in the authoritative store.
+	pub in_authoritative: bool,
+	/// Whether the data is default-valued or exactly duplicates the authoritative data, and can
+	/// thus be removed without losing information.
+	pub purgeable: bool,
+}
+
+/// The result of comparing the authoritative account store to the one not in use, see
+/// [`Pallet::audit_account_stores`].
+#[cfg(any(feature = "std", feature = "try-runtime"))]
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct StoreAuditReport<AccountId> {
+	/// The store [`Config::AccountStore`] reads the account data from.
+	pub authoritative: AccountStoreKind,
+	/// The number of entries in the authoritative store.
+	pub authoritative_entries: u32,
+	/// The number of entries holding balance data in the store not in use.
+	pub ghost_entries: u32,
+	/// The number of ghost entries which are default-valued or duplicate the authoritative data.
+	pub purgeable: u32,
+	/// The accounts which are only present in the store not in use.
+	pub orphaned: Vec<AccountId>,
+	/// The accounts present in both stores with differing data, which is not default-valued in
+	/// the store not in use.
+	pub divergent: Vec<AccountId>,
+}
+
+/// The secondary effects of mutating an account, which are applied once the mutation completed.
+pub(crate) struct MutationEffects<Balance> {
+	/// The free balance the account was created with, if it was created.
+	pub endowed: Option<Balance>,
+	/// The dust left over when the account was reaped.
+	pub dust: Option<Balance>,
+	/// The residue swept from the free balance of the surviving account.
+	pub residue: Option<Balance>,
+}
+
+/// The well-known unhashed storage key of the number of slices used by [`TryStateSample`].
+///
+/// It is not written by the pallet. It can be put into a state snapshot to make `try_state` check
+/// only `1 / N` of the accounts in every block, where `N` is the SCALE encoded `u32` stored at
+/// this key. The checked slice rotates with the block number, so that `N` consecutive blocks
+/// cover all accounts. If it is absent, or not greater than one, all accounts are checked.
+#[cfg(any(test, feature = "try-runtime"))]
+pub const TRY_STATE_SAMPLING_KEY: &[u8] = b":pallet_balances:try_state_sampling:";
+
+/// The accounts checked by [`Pallet::do_try_state`].
+#[cfg(any(test, feature = "try-runtime"))]
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum TryStateSample {
+	/// Check all accounts.
+	Full,
+	/// Check only the accounts whose key hash falls into slice `index` out of `count`.
+	Slice { index: u32, count: u32 },
+}
+
+#[cfg(any(test, feature = "try-runtime"))]
+impl TryStateSample {
+	/// The sample to check in block `n`, according to [`TRY_STATE_SAMPLING_KEY`].
+	pub fn for_block<BlockNumber: sp_runtime::traits::AtLeast32BitUnsigned>(
+		n: BlockNumber,
+	) -> Self {
+		use sp_runtime::traits::UniqueSaturatedInto;
+
+		match frame_support::storage::unhashed::get::<u32>(TRY_STATE_SAMPLING_KEY) {
+			Some(count) if count > 1 =>
+				Self::Slice { index: (n % count.into()).unique_saturated_into(), count },
+			_ => Self::Full,
+		}
+	}
+
+	/// Whether the account `who` is part of this sample.
+	///
+	/// Accounts are assigned to slices by the hash of their encoding, so that the assignment is
+	/// deterministic and independent of the storage layout.
+	pub fn contains<AccountId: Encode>(&self, who: &AccountId) -> bool {
+		match *self {
+			Self::Full => true,
+			Self::Slice { index, count } => {
+				let hash = who.using_encoded(sp_io::hashing::blake2_256);
+				let slot = u32::from_le_bytes([hash[0], hash[1], hash[2], hash[3]]);
+				slot % count.max(1) == index
+			},
+		}
+	}
+}
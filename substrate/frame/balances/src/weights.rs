@@ -57,9 +57,25 @@ pub trait WeightInfo {
 	fn transfer_all() -> Weight;
 	fn force_unreserve() -> Weight;
 	fn upgrade_accounts(u: u32, ) -> Weight;
+	fn upgrade_accounts_skipped(u: u32, ) -> Weight;
 	fn force_adjust_total_issuance() -> Weight;
 	fn burn_allow_death() -> Weight;
 	fn burn_keep_alive() -> Weight;
+	fn burn_held() -> Weight;
+	fn force_reconcile_total_issuance(a: u32, ) -> Weight;
+	fn repair_account() -> Weight;
+	fn force_set_inactive_issuance() -> Weight;
+	fn force_adjust_inactive_issuance() -> Weight;
+	fn force_set_freeze() -> Weight;
+	fn purge_ghost_accounts(l: u32, ) -> Weight;
+	fn set_auto_upgrade() -> Weight;
+	fn add_silent_account() -> Weight;
+	fn remove_silent_account() -> Weight;
+	fn restrict_account() -> Weight;
+	fn unrestrict_account() -> Weight;
+	fn force_migrate_account() -> Weight;
+	fn deprecate_lock() -> Weight;
+	fn prepare_account_closure() -> Weight;
 }
 
 /// Weights for `pallet_balances` using the Substrate node and recommended hardware.
@@ -157,6 +173,20 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(u.into())))
 			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(u.into()))
 	}
+	/// Storage: `System::Account` (r:999 w:0)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `u` is `[1, 1000]`.
+	fn upgrade_accounts_skipped(u: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0 + u * (135 ±0)`
+		//  Estimated: `990 + u * (2603 ±0)`
+		// Minimum execution time: 2_108_000 picoseconds.
+		Weight::from_parts(2_231_000, 990)
+			// Standard Error: 4_102
+			.saturating_add(Weight::from_parts(3_517_372, 0).saturating_mul(u.into()))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(u.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(u.into()))
+	}
 	fn force_adjust_total_issuance() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
@@ -178,6 +208,179 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		// Minimum execution time: 20_055_000 picoseconds.
 		Weight::from_parts(20_711_000, 0)
 	}
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(193), added: 2668, mode: `MaxEncodedLen`)
+	fn burn_held() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 20_055_000 picoseconds.
+		Weight::from_parts(20_711_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Balances::IssuanceReconciliationProgress` (r:1 w:1)
+	/// Proof: `Balances::IssuanceReconciliationProgress` (`max_values`: Some(1), `max_size`: Some(48), added: 543, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::TotalIssuance` (r:1 w:1)
+	/// Proof: `Balances::TotalIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::InactiveIssuance` (r:1 w:0)
+	/// Proof: `Balances::InactiveIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:999 w:0)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `a` is `[0, 1000]`.
+	fn force_reconcile_total_issuance(a: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `142 + a * (135 ±0)`
+		//  Estimated: `1533 + a * (2603 ±0)`
+		// Minimum execution time: 16_333_000 picoseconds.
+		Weight::from_parts(16_588_000, 1533)
+			.saturating_add(Weight::from_parts(6_986_829, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(a.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(a.into()))
+	}
+	/// Storage: `Balances::Holds` (r:1 w:0)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(193), added: 2668, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Locks` (r:1 w:0)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Freezes` (r:1 w:0)
+	/// Proof: `Balances::Freezes` (`max_values`: None, `max_size`: Some(67), added: 2542, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn repair_account() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `329`
+		//  Estimated: `4764`
+		// Minimum execution time: 33_500_000 picoseconds.
+		Weight::from_parts(34_883_000, 4764)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Balances::TotalIssuance` (r:1 w:0)
+	/// Proof: `Balances::TotalIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::InactiveIssuance` (r:1 w:1)
+	/// Proof: `Balances::InactiveIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	fn force_set_inactive_issuance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `1501`
+		// Minimum execution time: 7_012_000 picoseconds.
+		Weight::from_parts(7_348_000, 1501)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Balances::TotalIssuance` (r:1 w:0)
+	/// Proof: `Balances::TotalIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::InactiveIssuance` (r:1 w:1)
+	/// Proof: `Balances::InactiveIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	fn force_adjust_inactive_issuance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `1501`
+		// Minimum execution time: 7_264_000 picoseconds.
+		Weight::from_parts(7_611_000, 1501)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Balances::Freezes` (r:1 w:1)
+	/// Storage: `Balances::Locks` (r:1 w:0)
+	/// Storage: `Balances::IssuanceReconciliationProgress` (r:1 w:0)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::FreezeExpiryAgenda` (r:1 w:1)
+	/// Storage: `Balances::FreezeExpiry` (r:0 w:1)
+	fn force_set_freeze() -> Weight {
+		// Estimated from `repair_account` until the benchmark is run.
+		Weight::from_parts(34_883_000, 4764)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `System::Account` (r:1001 w:0)
+	/// Storage: `Balances::Account` (r:1001 w:1001)
+	/// The range of component `l` is `[0, 1000]`.
+	fn purge_ghost_accounts(l: u32, ) -> Weight {
+		// Estimated from `force_reconcile_total_issuance` until the benchmark is run.
+		Weight::from_parts(16_588_000, 1533)
+			.saturating_add(Weight::from_parts(13_973_658, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(l.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(l.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(l.into()))
+	}
+	/// Storage: `Balances::AutoUpgradeEnabled` (r:0 w:1)
+	/// Storage: `Balances::AutoUpgradeCursor` (r:0 w:1)
+	fn set_auto_upgrade() -> Weight {
+		// Estimated from `force_adjust_total_issuance` until the benchmark is run.
+		Weight::from_parts(6_594_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Balances::SilentAccounts` (r:0 w:1)
+	fn add_silent_account() -> Weight {
+		// Estimated from `force_adjust_total_issuance` until the benchmark is run.
+		Weight::from_parts(6_594_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Balances::SilentAccounts` (r:0 w:1)
+	fn remove_silent_account() -> Weight {
+		// Estimated from `force_adjust_total_issuance` until the benchmark is run.
+		Weight::from_parts(6_594_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Balances::IssuanceReconciliationProgress` (r:1 w:0)
+	/// Storage: `System::Account` (r:1 w:1)
+	fn restrict_account() -> Weight {
+		// Estimated from `repair_account` until the benchmark is run.
+		Weight::from_parts(34_883_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Balances::IssuanceReconciliationProgress` (r:1 w:0)
+	/// Storage: `System::Account` (r:1 w:1)
+	fn unrestrict_account() -> Weight {
+		// Estimated from `repair_account` until the benchmark is run.
+		Weight::from_parts(34_883_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Balances::IssuanceReconciliationProgress` (r:1 w:1)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Storage: `Balances::Freezes` (r:2 w:2)
+	/// Storage: `Balances::Locks` (r:2 w:2)
+	/// Storage: `Balances::Reserves` (r:2 w:2)
+	/// Storage: `Balances::FreezeExpiry` (r:1 w:2)
+	/// Storage: `Balances::FreezeExpiryAgenda` (r:1 w:1)
+	/// Storage: `Balances::SilentAccounts` (r:1 w:2)
+	fn force_migrate_account() -> Weight {
+		// Estimated from `repair_account` until the benchmark is run.
+		Weight::from_parts(34_883_000, 4764)
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().writes(16_u64))
+	}
+	/// Storage: `Balances::IssuanceReconciliationProgress` (r:1 w:0)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Storage: `Balances::Freezes` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::FreezeExpiry` (r:0 w:1)
+	fn deprecate_lock() -> Weight {
+		// Estimated from `repair_account` until the benchmark is run.
+		Weight::from_parts(34_883_000, 4764)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Balances::Holds` (r:1 w:0)
+	/// Storage: `Balances::Reserves` (r:1 w:0)
+	/// Storage: `Balances::Freezes` (r:1 w:1)
+	/// Storage: `Balances::Locks` (r:1 w:0)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::FreezeExpiry` (r:0 w:1)
+	fn prepare_account_closure() -> Weight {
+		// Estimated from `repair_account` until the benchmark is run.
+		Weight::from_parts(34_883_000, 4764)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -274,6 +477,20 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(u.into())))
 			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(u.into()))
 	}
+	/// Storage: `System::Account` (r:999 w:0)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `u` is `[1, 1000]`.
+	fn upgrade_accounts_skipped(u: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0 + u * (135 ±0)`
+		//  Estimated: `990 + u * (2603 ±0)`
+		// Minimum execution time: 2_108_000 picoseconds.
+		Weight::from_parts(2_231_000, 990)
+			// Standard Error: 4_102
+			.saturating_add(Weight::from_parts(3_517_372, 0).saturating_mul(u.into()))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(u.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(u.into()))
+	}
 	fn force_adjust_total_issuance() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
@@ -295,4 +512,177 @@ impl WeightInfo for () {
 		// Minimum execution time: 20_055_000 picoseconds.
 		Weight::from_parts(20_711_000, 0)
 	}
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(193), added: 2668, mode: `MaxEncodedLen`)
+	fn burn_held() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 20_055_000 picoseconds.
+		Weight::from_parts(20_711_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Balances::IssuanceReconciliationProgress` (r:1 w:1)
+	/// Proof: `Balances::IssuanceReconciliationProgress` (`max_values`: Some(1), `max_size`: Some(48), added: 543, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::TotalIssuance` (r:1 w:1)
+	/// Proof: `Balances::TotalIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::InactiveIssuance` (r:1 w:0)
+	/// Proof: `Balances::InactiveIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:999 w:0)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `a` is `[0, 1000]`.
+	fn force_reconcile_total_issuance(a: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `142 + a * (135 ±0)`
+		//  Estimated: `1533 + a * (2603 ±0)`
+		// Minimum execution time: 16_333_000 picoseconds.
+		Weight::from_parts(16_588_000, 1533)
+			.saturating_add(Weight::from_parts(6_986_829, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(a.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(a.into()))
+	}
+	/// Storage: `Balances::Holds` (r:1 w:0)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(193), added: 2668, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Locks` (r:1 w:0)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Freezes` (r:1 w:0)
+	/// Proof: `Balances::Freezes` (`max_values`: None, `max_size`: Some(67), added: 2542, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn repair_account() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `329`
+		//  Estimated: `4764`
+		// Minimum execution time: 33_500_000 picoseconds.
+		Weight::from_parts(34_883_000, 4764)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Balances::TotalIssuance` (r:1 w:0)
+	/// Proof: `Balances::TotalIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::InactiveIssuance` (r:1 w:1)
+	/// Proof: `Balances::InactiveIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	fn force_set_inactive_issuance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `1501`
+		// Minimum execution time: 7_012_000 picoseconds.
+		Weight::from_parts(7_348_000, 1501)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Balances::TotalIssuance` (r:1 w:0)
+	/// Proof: `Balances::TotalIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::InactiveIssuance` (r:1 w:1)
+	/// Proof: `Balances::InactiveIssuance` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	fn force_adjust_inactive_issuance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `1501`
+		// Minimum execution time: 7_264_000 picoseconds.
+		Weight::from_parts(7_611_000, 1501)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Balances::Freezes` (r:1 w:1)
+	/// Storage: `Balances::Locks` (r:1 w:0)
+	/// Storage: `Balances::IssuanceReconciliationProgress` (r:1 w:0)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::FreezeExpiryAgenda` (r:1 w:1)
+	/// Storage: `Balances::FreezeExpiry` (r:0 w:1)
+	fn force_set_freeze() -> Weight {
+		// Estimated from `repair_account` until the benchmark is run.
+		Weight::from_parts(34_883_000, 4764)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `System::Account` (r:1001 w:0)
+	/// Storage: `Balances::Account` (r:1001 w:1001)
+	/// The range of component `l` is `[0, 1000]`.
+	fn purge_ghost_accounts(l: u32, ) -> Weight {
+		// Estimated from `force_reconcile_total_issuance` until the benchmark is run.
+		Weight::from_parts(16_588_000, 1533)
+			.saturating_add(Weight::from_parts(13_973_658, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(l.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(l.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(l.into()))
+	}
+	/// Storage: `Balances::AutoUpgradeEnabled` (r:0 w:1)
+	/// Storage: `Balances::AutoUpgradeCursor` (r:0 w:1)
+	fn set_auto_upgrade() -> Weight {
+		// Estimated from `force_adjust_total_issuance` until the benchmark is run.
+		Weight::from_parts(6_594_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Balances::SilentAccounts` (r:0 w:1)
+	fn add_silent_account() -> Weight {
+		// Estimated from `force_adjust_total_issuance` until the benchmark is run.
+		Weight::from_parts(6_594_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Balances::SilentAccounts` (r:0 w:1)
+	fn remove_silent_account() -> Weight {
+		// Estimated from `force_adjust_total_issuance` until the benchmark is run.
+		Weight::from_parts(6_594_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Balances::IssuanceReconciliationProgress` (r:1 w:0)
+	/// Storage: `System::Account` (r:1 w:1)
+	fn restrict_account() -> Weight {
+		// Estimated from `repair_account` until the benchmark is run.
+		Weight::from_parts(34_883_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Balances::IssuanceReconciliationProgress` (r:1 w:0)
+	/// Storage: `System::Account` (r:1 w:1)
+	fn unrestrict_account() -> Weight {
+		// Estimated from `repair_account` until the benchmark is run.
+		Weight::from_parts(34_883_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Balances::IssuanceReconciliationProgress` (r:1 w:1)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Storage: `Balances::Freezes` (r:2 w:2)
+	/// Storage: `Balances::Locks` (r:2 w:2)
+	/// Storage: `Balances::Reserves` (r:2 w:2)
+	/// Storage: `Balances::FreezeExpiry` (r:1 w:2)
+	/// Storage: `Balances::FreezeExpiryAgenda` (r:1 w:1)
+	/// Storage: `Balances::SilentAccounts` (r:1 w:2)
+	fn force_migrate_account() -> Weight {
+		// Estimated from `repair_account` until the benchmark is run.
+		Weight::from_parts(34_883_000, 4764)
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().writes(16_u64))
+	}
+	/// Storage: `Balances::IssuanceReconciliationProgress` (r:1 w:0)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Storage: `Balances::Freezes` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::FreezeExpiry` (r:0 w:1)
+	fn deprecate_lock() -> Weight {
+		// Estimated from `repair_account` until the benchmark is run.
+		Weight::from_parts(34_883_000, 4764)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Balances::Holds` (r:1 w:0)
+	/// Storage: `Balances::Reserves` (r:1 w:0)
+	/// Storage: `Balances::Freezes` (r:1 w:1)
+	/// Storage: `Balances::Locks` (r:1 w:0)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::FreezeExpiry` (r:0 w:1)
+	fn prepare_account_closure() -> Weight {
+		// Estimated from `repair_account` until the benchmark is run.
+		Weight::from_parts(34_883_000, 4764)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
 }
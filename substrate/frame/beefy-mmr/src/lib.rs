@@ -40,7 +40,7 @@ use codec::Decode;
 use pallet_mmr::{LeafDataProvider, ParentNumberAndHash};
 use sp_consensus_beefy::{
 	mmr::{BeefyAuthoritySet, BeefyDataProvider, BeefyNextAuthoritySet, MmrLeaf, MmrLeafVersion},
-	ValidatorSet as BeefyValidatorSet,
+	SessionIndex, ValidatorSet as BeefyValidatorSet,
 };
 
 use frame_support::{crypto::ecdsa::ECDSAExt, traits::Get};
@@ -135,6 +135,11 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type BeefyNextAuthorities<T: Config> =
 		StorageValue<_, BeefyNextAuthoritySet<MerkleRootOf<T>>, ValueQuery>;
+
+	/// The session and the block from which the current BEEFY authority set is active.
+	#[pallet::storage]
+	pub type BeefyAuthoritiesActivation<T: Config> =
+		StorageValue<_, (SessionIndex, BlockNumberFor<T>), ValueQuery>;
 }
 
 impl<T: Config> LeafDataProvider for Pallet<T> {
@@ -155,20 +160,27 @@ impl<T: Config> LeafDataProvider for Pallet<T> {
 	}
 }
 
-impl<T> sp_consensus_beefy::OnNewValidatorSet<<T as pallet_beefy::Config>::BeefyId> for Pallet<T>
+impl<T>
+	sp_consensus_beefy::OnNewValidatorSetExt<
+		<T as pallet_beefy::Config>::BeefyId,
+		BlockNumberFor<T>,
+	> for Pallet<T>
 where
 	T: pallet::Config,
 {
 	/// Compute and cache BEEFY authority sets based on updated BEEFY validator sets.
-	fn on_new_validator_set(
+	fn on_new_validator_set_ext(
 		current_set: &BeefyValidatorSet<<T as pallet_beefy::Config>::BeefyId>,
 		next_set: &BeefyValidatorSet<<T as pallet_beefy::Config>::BeefyId>,
+		session_index: SessionIndex,
+		activation_block: BlockNumberFor<T>,
 	) {
 		let current = Pallet::<T>::compute_authority_set(current_set);
 		let next = Pallet::<T>::compute_authority_set(next_set);
 		// cache the result
 		BeefyAuthorities::<T>::put(&current);
 		BeefyNextAuthorities::<T>::put(&next);
+		BeefyAuthoritiesActivation::<T>::put((session_index, activation_block));
 	}
 }
 
@@ -183,6 +195,11 @@ impl<T: Config> Pallet<T> {
 		BeefyNextAuthorities::<T>::get()
 	}
 
+	/// Return the session and the block from which the current BEEFY authority set is active.
+	pub fn authority_set_activation() -> (SessionIndex, BlockNumberFor<T>) {
+		BeefyAuthoritiesActivation::<T>::get()
+	}
+
 	/// Returns details of a BEEFY authority set.
 	///
 	/// Details contain authority set id, authority set length and a merkle root,
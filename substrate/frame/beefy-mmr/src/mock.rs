@@ -50,6 +50,7 @@ construct_runtime!(
 	pub enum Test
 	{
 		System: frame_system,
+		Balances: pallet_balances,
 		Session: pallet_session,
 		Mmr: pallet_mmr,
 		Beefy: pallet_beefy,
@@ -60,6 +61,13 @@ construct_runtime!(
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
 impl frame_system::Config for Test {
 	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
 }
 
 impl pallet_session::Config for Test {
@@ -96,14 +104,22 @@ impl pallet_mmr::Config for Test {
 }
 
 impl pallet_beefy::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
 	type BeefyId = BeefyId;
 	type MaxAuthorities = ConstU32<100>;
 	type MaxNominators = ConstU32<1000>;
 	type MaxSetIdSessionEntries = ConstU64<100>;
 	type OnNewValidatorSet = BeefyMmr;
 	type WeightInfo = ();
+	type DisabledValidators = ();
 	type KeyOwnerProof = sp_core::Void;
 	type EquivocationReportSystem = ();
+	type HistoricalProofProvider = ();
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type ConcurrentKeyReportDeposit = ConstU64<1>;
+	type MaxConcurrentKeyReports = ConstU32<2>;
+	type DisableStrategy = frame_support::traits::ConstBool<false>;
 }
 
 parameter_types! {
@@ -169,6 +169,9 @@ fn should_update_authorities() {
 		);
 		assert_eq!(want, auth_set.keyset_commitment);
 
+		// genesis set is active from the start
+		assert_eq!((0, 0), BeefyMmr::authority_set_activation());
+
 		// next authority set should have same validators but different id
 		assert_eq!(1, next_auth_set.id);
 		assert_eq!(auth_set.len, next_auth_set.len);
@@ -182,6 +185,10 @@ fn should_update_authorities() {
 		// check new auth are expected ones
 		assert_eq!(announced_set, auth_set);
 		assert_eq!(1, auth_set.id);
+		// check the activation of the new set
+		let session = pallet_beefy::SetIdSession::<Test>::get(1).unwrap();
+		assert_eq!(1, session);
+		assert_eq!((session, 1), BeefyMmr::authority_set_activation());
 		// check next auth set
 		assert_eq!(2, next_auth_set.id);
 		let want = array_bytes::hex_n_into_unchecked::<_, H256, 32>(
@@ -198,6 +205,10 @@ fn should_update_authorities() {
 		// check new auth are expected ones
 		assert_eq!(announced_set, auth_set);
 		assert_eq!(2, auth_set.id);
+		// check the activation of the new set
+		let session = pallet_beefy::SetIdSession::<Test>::get(2).unwrap();
+		assert_eq!(2, session);
+		assert_eq!((session, 2), BeefyMmr::authority_set_activation());
 		// check next auth set
 		assert_eq!(3, next_auth_set.id);
 		let want = array_bytes::hex_n_into_unchecked::<_, H256, 32>(
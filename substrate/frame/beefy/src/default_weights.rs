@@ -48,9 +48,53 @@ impl crate::WeightInfo for () {
 			.saturating_add(DbWeight::get().writes(10 + 3 * max_nominators_per_validator as u64))
 			// fetching set id -> session index mappings
 			.saturating_add(DbWeight::get().reads(2))
+			// checking the rounds of earlier batches
+			.saturating_add(DbWeight::get().reads(1))
+			// counting the processed report
+			.saturating_add(DbWeight::get().reads_writes(1, 1))
+	}
+
+	fn report_equivocation_batch(
+		proofs_count: u32,
+		validator_count: u32,
+		max_nominators_per_validator: u32,
+	) -> Weight {
+		// the key ownership proof is checked and the offence is reported once, while each
+		// additional equivocation proof has to be checked, looked up separately and its round
+		// recorded.
+		let additional_proofs = proofs_count.saturating_sub(1) as u64;
+
+		Self::report_equivocation(validator_count, max_nominators_per_validator)
+			.saturating_add(
+				Weight::from_parts(95u64 * WEIGHT_REF_TIME_PER_MICROS, 0)
+					.saturating_mul(additional_proofs),
+			)
+			.saturating_add(DbWeight::get().reads_writes(2 * additional_proofs, additional_proofs))
 	}
 
 	fn set_new_genesis() -> Weight {
 		DbWeight::get().writes(1)
 	}
+
+	fn note_concurrent_session(reports: u32) -> Weight {
+		// checking the proof of possession
+		Weight::from_parts(50u64 * WEIGHT_REF_TIME_PER_MICROS, 0)
+			// looking for a previous report of the reporter
+			.saturating_add(
+				Weight::from_parts(100u64 * WEIGHT_REF_TIME_PER_NANOS, 0)
+					.saturating_mul(reports as u64),
+			)
+			// authorities, set id and reports
+			.saturating_add(DbWeight::get().reads_writes(3, 1))
+			// holding the deposit
+			.saturating_add(DbWeight::get().reads_writes(2, 2))
+	}
+
+	fn clear_concurrent_key_reports(reports: u32) -> Weight {
+		// draining the reports
+		DbWeight::get()
+			.reads_writes(1, 1)
+			// releasing every deposit
+			.saturating_add(DbWeight::get().reads_writes(2, 2).saturating_mul(reports as u64))
+	}
 }
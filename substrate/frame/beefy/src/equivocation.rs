@@ -41,19 +41,36 @@ use log::{error, info};
 use sp_consensus_beefy::{DoubleVotingProof, ValidatorSetId, KEY_TYPE as BEEFY_KEY_TYPE};
 use sp_runtime::{
 	transaction_validity::{
-		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
-		TransactionValidityError, ValidTransaction,
+		InvalidTransaction, TransactionLongevity, TransactionPriority, TransactionSource,
+		TransactionValidity, TransactionValidityError, ValidTransaction,
 	},
 	DispatchError, KeyTypeId, Perbill, RuntimeAppPublic,
 };
-use sp_session::{GetSessionNumber, GetValidatorCount};
+use sp_session::{GetSessionNumber, GetValidatorCount, MembershipProof, ValidatorCount};
 use sp_staking::{
 	offence::{Kind, Offence, OffenceReportSystem, ReportOffence},
 	SessionIndex,
 };
-use sp_std::prelude::*;
+use sp_std::{collections::btree_set::BTreeSet, prelude::*};
 
-use super::{Call, Config, Error, Pallet, LOG_TARGET};
+use super::{
+	ArchivedSessionRoots, BatchReportedRounds, Call, Config, Error, OffenceKind, Pallet,
+	SessionRootProvider, ARCHIVED_SESSION_ROOTS_FACTOR, LOG_TARGET,
+};
+
+/// Custom [`InvalidTransaction`] code of an unsigned equivocation report for a set which can't be
+/// reported anymore, or whose session doesn't match the key ownership proof.
+pub const INVALID_EQUIVOCATION_PROOF_SESSION: u8 = 1;
+
+/// Records the equivocation offences which were successfully reported.
+///
+/// Implemented by the pallet, so that [`EquivocationReportSystem`] can keep track of the
+/// processed reports without depending on the calls which submitted them.
+pub trait OffenceRecorder {
+	/// Record that an offence of `kind` was reported for the validator set `set_id` in
+	/// `session`.
+	fn record_offence(kind: OffenceKind, set_id: ValidatorSetId, session: SessionIndex);
+}
 
 /// A round number and set id which point on the time of an offence.
 #[derive(Copy, Clone, PartialOrd, Ord, Eq, PartialEq, Encode, Decode)]
@@ -78,6 +95,16 @@ where
 	pub validator_set_count: u32,
 	/// The authority which produced this equivocation.
 	pub offender: Offender,
+	/// Whether the offender was already disabled in the session of the offence at the time it
+	/// was reported.
+	///
+	/// The offence is still recorded so that the evidence trail is complete, but offence handlers
+	/// can use this flag to avoid punishing the same authority repeatedly.
+	pub offender_already_disabled: bool,
+	/// The number of distinct rounds in which the offender equivocated.
+	///
+	/// This is `1` for a single equivocation and scales the slash fraction of batched reports.
+	pub rounds: u32,
 }
 
 impl<Offender: Clone, N> Offence<Offender> for EquivocationOffence<Offender, N>
@@ -103,11 +130,13 @@ where
 		self.time_slot
 	}
 
-	// The formula is min((3k / n)^2, 1)
-	// where k = offenders_number and n = validators_number
+	// The formula is min(r * (3k / n)^2, 1)
+	// where k = offenders_number, n = validators_number and r = rounds
 	fn slash_fraction(&self, offenders_count: u32) -> Perbill {
 		// Perbill type domain is [0, 1] by definition
-		Perbill::from_rational(3 * offenders_count, self.validator_set_count).square()
+		let fraction =
+			Perbill::from_rational(3 * offenders_count, self.validator_set_count).square();
+		Perbill::from_parts(fraction.deconstruct().saturating_mul(self.rounds.max(1)))
 	}
 }
 
@@ -131,6 +160,21 @@ pub type EquivocationEvidenceFor<T> = (
 	<T as Config>::KeyOwnerProof,
 );
 
+/// Batched equivocation evidence convenience alias.
+///
+/// All proofs must be for the same offender and set id, so that they are covered by the single
+/// key ownership proof.
+pub type BatchEquivocationEvidenceFor<T> = (
+	Vec<
+		DoubleVotingProof<
+			BlockNumberFor<T>,
+			<T as Config>::BeefyId,
+			<<T as Config>::BeefyId as RuntimeAppPublic>::Signature,
+		>,
+	>,
+	<T as Config>::KeyOwnerProof,
+);
+
 impl<T, R, P, L> OffenceReportSystem<Option<T::AccountId>, EquivocationEvidenceFor<T>>
 	for EquivocationReportSystem<T, R, P, L>
 where
@@ -142,6 +186,11 @@ where
 	>,
 	P: KeyOwnerProofSystem<(KeyTypeId, T::BeefyId), Proof = T::KeyOwnerProof>,
 	P::IdentificationTuple: Clone,
+	T::HistoricalProofProvider: KeyOwnerProofSystem<
+		(KeyTypeId, T::BeefyId),
+		Proof = T::KeyOwnerProof,
+		IdentificationTuple = P::IdentificationTuple,
+	>,
 	L: Get<u64>,
 {
 	type Longevity = L;
@@ -169,8 +218,10 @@ where
 		let (equivocation_proof, key_owner_proof) = evidence;
 
 		// Check the membership proof to extract the offender's id
-		let key = (BEEFY_KEY_TYPE, equivocation_proof.offender_id().clone());
-		let offender = P::check_proof(key, key_owner_proof).ok_or(InvalidTransaction::BadProof)?;
+		let offender_id = equivocation_proof.offender_id();
+		let key = (BEEFY_KEY_TYPE, offender_id.clone());
+		let offender = check_key_owner_proof::<T, P>(key, key_owner_proof)
+			.ok_or(InvalidTransaction::BadProof)?;
 
 		// Check if the offence has already been reported, and if so then we can discard the report.
 		let time_slot = TimeSlot {
@@ -178,7 +229,7 @@ where
 			round: *equivocation_proof.round_number(),
 		};
 
-		if R::is_known_offence(&[offender], &time_slot) {
+		if is_known_round::<T, _, _>(R::is_known_offence, &offender, offender_id, time_slot) {
 			Err(InvalidTransaction::Stale.into())
 		} else {
 			Ok(())
@@ -192,6 +243,10 @@ where
 		let (equivocation_proof, key_owner_proof) = evidence;
 		let reporter = reporter.or_else(|| pallet_authorship::Pallet::<T>::author());
 		let offender = equivocation_proof.offender_id().clone();
+		let offender_already_disabled = Pallet::<T>::is_authority_disabled(
+			equivocation_proof.set_id(),
+			equivocation_proof.offender_id(),
+		);
 
 		// We check the equivocation within the context of its set id (and
 		// associated session) and round. We also need to know the validator
@@ -203,7 +258,7 @@ where
 		let validator_set_count = key_owner_proof.validator_count();
 
 		// Validate the key ownership proof extracting the id of the offender.
-		let offender = P::check_proof((BEEFY_KEY_TYPE, offender), key_owner_proof)
+		let offender = check_key_owner_proof::<T, P>((BEEFY_KEY_TYPE, offender), key_owner_proof)
 			.ok_or(Error::<T>::InvalidKeyOwnershipProof)?;
 
 		// Validate equivocation proof (check votes are different and signatures are valid).
@@ -213,26 +268,273 @@ where
 
 		// Check that the session id for the membership proof is within the
 		// bounds of the set id reported in the equivocation.
-		let set_id_session_index =
-			crate::SetIdSession::<T>::get(set_id).ok_or(Error::<T>::InvalidEquivocationProof)?;
-		if session_index != set_id_session_index {
+		if !Pallet::<T>::is_session_of_set(set_id, session_index) {
 			return Err(Error::<T>::InvalidEquivocationProof.into())
 		}
 
+		// Rounds which were covered by a batch are not punished again.
+		let time_slot = TimeSlot { set_id, round };
+		if BatchReportedRounds::<T>::contains_key(set_id, (equivocation_proof.offender_id(), round))
+		{
+			return Err(Error::<T>::DuplicateOffenceReport.into())
+		}
+
+		let offence = EquivocationOffence {
+			time_slot,
+			session_index,
+			validator_set_count,
+			offender,
+			offender_already_disabled,
+			rounds: 1,
+		};
+
+		if offender_already_disabled {
+			info!(
+				target: LOG_TARGET,
+				"Reporting equivocation of already disabled authority in set {:?}.",
+				set_id,
+			);
+		}
+
+		R::report_offence(reporter.into_iter().collect(), offence)
+			.map_err(|_| Error::<T>::DuplicateOffenceReport)?;
+		Pallet::<T>::record_offence(OffenceKind::DoubleVoting, set_id, session_index);
+		Pallet::<T>::disable_equivocating_authority(set_id, equivocation_proof.offender_id());
+
+		Ok(())
+	}
+}
+
+impl<T, R, P, L> OffenceReportSystem<Option<T::AccountId>, BatchEquivocationEvidenceFor<T>>
+	for EquivocationReportSystem<T, R, P, L>
+where
+	T: Config + pallet_authorship::Config + frame_system::offchain::SendTransactionTypes<Call<T>>,
+	R: ReportOffence<
+		T::AccountId,
+		P::IdentificationTuple,
+		EquivocationOffence<P::IdentificationTuple, BlockNumberFor<T>>,
+	>,
+	P: KeyOwnerProofSystem<(KeyTypeId, T::BeefyId), Proof = T::KeyOwnerProof>,
+	P::IdentificationTuple: Clone,
+	T::HistoricalProofProvider: KeyOwnerProofSystem<
+		(KeyTypeId, T::BeefyId),
+		Proof = T::KeyOwnerProof,
+		IdentificationTuple = P::IdentificationTuple,
+	>,
+	L: Get<u64>,
+{
+	type Longevity = L;
+
+	fn publish_evidence(_evidence: BatchEquivocationEvidenceFor<T>) -> Result<(), ()> {
+		// There is no unsigned batch report, batches are only submitted by signed reporters.
+		error!(target: LOG_TARGET, "Batched equivocation reports can not be published unsigned.");
+		Err(())
+	}
+
+	fn check_evidence(
+		evidence: BatchEquivocationEvidenceFor<T>,
+	) -> Result<(), TransactionValidityError> {
+		let (equivocation_proofs, key_owner_proof) = evidence;
+		let first = equivocation_proofs.first().ok_or(InvalidTransaction::BadProof)?;
+
+		// Check the membership proof to extract the offender's id
+		let key = (BEEFY_KEY_TYPE, first.offender_id().clone());
+		let offender = check_key_owner_proof::<T, P>(key, key_owner_proof)
+			.ok_or(InvalidTransaction::BadProof)?;
+
+		// Discard the report if all of its equivocations have already been reported.
+		let all_known = equivocation_proofs.iter().all(|proof| {
+			let time_slot = TimeSlot { set_id: proof.set_id(), round: *proof.round_number() };
+			is_known_round::<T, _, _>(
+				R::is_known_offence,
+				&offender,
+				proof.offender_id(),
+				time_slot,
+			)
+		});
+		if all_known {
+			Err(InvalidTransaction::Stale.into())
+		} else {
+			Ok(())
+		}
+	}
+
+	fn process_evidence(
+		reporter: Option<T::AccountId>,
+		evidence: BatchEquivocationEvidenceFor<T>,
+	) -> Result<(), DispatchError> {
+		let (equivocation_proofs, key_owner_proof) = evidence;
+		let reporter = reporter.or_else(|| pallet_authorship::Pallet::<T>::author());
+		let first = equivocation_proofs.first().ok_or(Error::<T>::InvalidEquivocationProof)?;
+		let offender_id = first.offender_id().clone();
+		let set_id = first.set_id();
+
+		// All proofs must be valid and for the same offender and set id, so that the single key
+		// ownership proof covers all of them. Duplicated rounds are only counted once.
+		let mut rounds = BTreeSet::new();
+		for equivocation_proof in &equivocation_proofs {
+			if equivocation_proof.offender_id() != &offender_id ||
+				equivocation_proof.set_id() != set_id ||
+				!sp_consensus_beefy::check_equivocation_proof(equivocation_proof)
+			{
+				return Err(Error::<T>::InvalidEquivocationProof.into())
+			}
+			rounds.insert(*equivocation_proof.round_number());
+		}
+
+		let session_index = key_owner_proof.session();
+		let validator_set_count = key_owner_proof.validator_count();
+
+		// Validate the key ownership proof extracting the id of the offender.
+		let key = (BEEFY_KEY_TYPE, offender_id.clone());
+		let offender = check_key_owner_proof::<T, P>(key, key_owner_proof)
+			.ok_or(Error::<T>::InvalidKeyOwnershipProof)?;
+
+		// Check that the session id for the membership proof is within the
+		// bounds of the set id reported in the equivocations.
+		if !Pallet::<T>::is_session_of_set(set_id, session_index) {
+			return Err(Error::<T>::InvalidEquivocationProof.into())
+		}
+
+		// Rounds which have already been reported, on their own or in a batch, are not punished
+		// again.
+		rounds.retain(|round| {
+			let time_slot = TimeSlot { set_id, round: *round };
+			!is_known_round::<T, _, _>(R::is_known_offence, &offender, &offender_id, time_slot)
+		});
+		let round = *rounds.first().ok_or(Error::<T>::DuplicateOffenceReport)?;
+
+		let offender_already_disabled = Pallet::<T>::is_authority_disabled(set_id, &offender_id);
 		let offence = EquivocationOffence {
 			time_slot: TimeSlot { set_id, round },
 			session_index,
 			validator_set_count,
 			offender,
+			offender_already_disabled,
+			rounds: rounds.len() as u32,
 		};
 
 		R::report_offence(reporter.into_iter().collect(), offence)
 			.map_err(|_| Error::<T>::DuplicateOffenceReport)?;
+		Pallet::<T>::record_offence(OffenceKind::DoubleVotingBatch, set_id, session_index);
+		// The offence is only known at its first round, so record the others.
+		for round in rounds.into_iter().skip(1) {
+			BatchReportedRounds::<T>::insert(set_id, (offender_id.clone(), round), ());
+		}
+		Pallet::<T>::disable_equivocating_authority(set_id, &offender_id);
 
 		Ok(())
 	}
 }
 
+/// Whether the equivocation of `offender` at `time_slot` was already reported, either as an
+/// offence of its own, according to `is_known_offence`, or as part of a batch.
+fn is_known_round<T: Config, O: Clone, F>(
+	is_known_offence: F,
+	offender: &O,
+	offender_id: &T::BeefyId,
+	time_slot: TimeSlot<BlockNumberFor<T>>,
+) -> bool
+where
+	F: Fn(&[O], &TimeSlot<BlockNumberFor<T>>) -> bool,
+{
+	is_known_offence(&[offender.clone()], &time_slot) ||
+		BatchReportedRounds::<T>::contains_key(
+			time_slot.set_id,
+			(offender_id.clone(), time_slot.round),
+		)
+}
+
+/// Checks the key ownership proof with `P`, falling back to `T::HistoricalProofProvider` for
+/// sessions which `P` doesn't cover anymore.
+fn check_key_owner_proof<T, P>(
+	key: (KeyTypeId, T::BeefyId),
+	key_owner_proof: T::KeyOwnerProof,
+) -> Option<P::IdentificationTuple>
+where
+	T: Config,
+	P: KeyOwnerProofSystem<(KeyTypeId, T::BeefyId), Proof = T::KeyOwnerProof>,
+	T::HistoricalProofProvider: KeyOwnerProofSystem<
+		(KeyTypeId, T::BeefyId),
+		Proof = T::KeyOwnerProof,
+		IdentificationTuple = P::IdentificationTuple,
+	>,
+{
+	P::check_proof(key.clone(), key_owner_proof.clone())
+		.or_else(|| T::HistoricalProofProvider::check_proof(key, key_owner_proof))
+}
+
+/// Key ownership proof system of `pallet_session::historical`, which checks proofs of sessions
+/// against the roots archived in [`ArchivedSessionRoots`].
+///
+/// Meant to be used as [`Config::HistoricalProofProvider`] along with the historical session
+/// pallet as key ownership proof system of the [`EquivocationReportSystem`].
+pub struct ArchivedSessionProofs<T>(sp_std::marker::PhantomData<T>);
+
+impl<T, D> KeyOwnerProofSystem<(KeyTypeId, D)> for ArchivedSessionProofs<T>
+where
+	T: Config + pallet_session::historical::Config,
+	D: AsRef<[u8]>,
+{
+	type Proof = MembershipProof;
+	type IdentificationTuple = pallet_session::historical::IdentificationTuple<T>;
+
+	fn prove(key: (KeyTypeId, D)) -> Option<Self::Proof> {
+		<pallet_session::historical::Pallet<T> as KeyOwnerProofSystem<_>>::prove(key)
+	}
+
+	fn check_proof(key: (KeyTypeId, D), proof: Self::Proof) -> Option<Self::IdentificationTuple> {
+		let (_, root, validator_count) = ArchivedSessionRoots::<T>::get(proof.session)?;
+		if validator_count != proof.validator_count {
+			return None
+		}
+
+		pallet_session::historical::Pallet::<T>::check_proof_with_root(root, key, &proof.trie_nodes)
+	}
+}
+
+impl<T> SessionRootProvider<T::Hash> for ArchivedSessionProofs<T>
+where
+	T: Config + pallet_session::historical::Config,
+{
+	fn session_root(session: SessionIndex) -> Option<(T::Hash, ValidatorCount)> {
+		pallet_session::historical::HistoricalSessions::<T>::get(session)
+	}
+}
+
+/// Priority and longevity of an unsigned equivocation report for an offence of set `set_id`.
+///
+/// The `SetIdSession` entry of a set is pruned `max_set_id_session_entries` set changes after it
+/// got created, and the report can't be processed anymore afterwards. Offences of older sets thus
+/// get a higher priority, and the report only stays valid until the entry would be removed, given
+/// that `longevity` covers all of the retained entries. Returns `None` if the entry of `set_id`
+/// is already pruned or if `set_id` is ahead of `current_set_id`.
+///
+/// For sets with an archived session root, pass the number of sets kept in
+/// [`ArchivedSessionRoots`] and a `longevity` scaled by the same factor instead.
+pub fn unsigned_report_validity(
+	set_id: ValidatorSetId,
+	current_set_id: ValidatorSetId,
+	max_set_id_session_entries: u64,
+	longevity: TransactionLongevity,
+) -> Option<(TransactionPriority, TransactionLongevity)> {
+	let max_entries = max_set_id_session_entries.max(1);
+	if set_id > current_set_id {
+		return None
+	}
+
+	// Number of set changes left until the entry of `set_id` gets pruned.
+	let remaining = set_id.saturating_add(max_entries).saturating_sub(current_set_id);
+	if remaining == 0 {
+		return None
+	}
+
+	let age = max_entries - remaining;
+	let priority = (TransactionPriority::MAX / max_entries).saturating_mul(age + 1);
+	let longevity = (longevity / max_entries).saturating_mul(remaining).max(1);
+	Some((priority, longevity))
+}
+
 /// Methods for the `ValidateUnsigned` implementation:
 /// It restricts calls to `report_equivocation_unsigned` to local calls (i.e. extrinsics generated
 /// on this node) or that already in a block. This guarantees that only block authors can include
@@ -252,21 +554,41 @@ impl<T: Config> Pallet<T> {
 				},
 			}
 
+			let set_id = equivocation_proof.set_id();
+			let longevity = <T::EquivocationReportSystem as OffenceReportSystem<
+				_,
+				EquivocationEvidenceFor<T>,
+			>>::Longevity::get();
+			// Sets with an archived session root stay reportable until the archive is pruned.
+			let session = key_owner_proof.session();
+			let max_set_id_session_entries = T::MaxSetIdSessionEntries::get();
+			let (retained_sets, longevity) = if ArchivedSessionRoots::<T>::contains_key(session) {
+				(
+					max_set_id_session_entries.max(1).saturating_mul(ARCHIVED_SESSION_ROOTS_FACTOR),
+					longevity.saturating_mul(ARCHIVED_SESSION_ROOTS_FACTOR),
+				)
+			} else {
+				(max_set_id_session_entries, longevity)
+			};
+			let validity = unsigned_report_validity(
+				set_id,
+				super::ValidatorSetId::<T>::get(),
+				retained_sets,
+				longevity,
+			)
+			.filter(|_| Pallet::<T>::is_session_of_set(set_id, session));
+			let Some((priority, longevity)) = validity else {
+				return InvalidTransaction::Custom(INVALID_EQUIVOCATION_PROOF_SESSION).into()
+			};
+
 			let evidence = (*equivocation_proof.clone(), key_owner_proof.clone());
 			T::EquivocationReportSystem::check_evidence(evidence)?;
 
-			let longevity =
-				<T::EquivocationReportSystem as OffenceReportSystem<_, _>>::Longevity::get();
-
 			ValidTransaction::with_tag_prefix("BeefyEquivocation")
-				// We assign the maximum priority for any equivocation report.
-				.priority(TransactionPriority::MAX)
-				// Only one equivocation report for the same offender at the same slot.
-				.and_provides((
-					equivocation_proof.offender_id().clone(),
-					equivocation_proof.set_id(),
-					*equivocation_proof.round_number(),
-				))
+				// Reports of older sets are closer to becoming unprocessable.
+				.priority(priority)
+				// Only one equivocation report for the same offender in the same set.
+				.and_provides((equivocation_proof.offender_id().clone(), set_id))
 				.longevity(longevity)
 				// We don't propagate this. This can never be included on a remote node.
 				.propagate(false)
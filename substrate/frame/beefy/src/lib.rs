@@ -17,12 +17,16 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use codec::{Encode, MaxEncodedLen};
+use codec::{Decode, Encode, MaxEncodedLen};
 
 use frame_support::{
-	dispatch::{DispatchResultWithPostInfo, Pays},
+	dispatch::{DispatchClass, DispatchResultWithPostInfo, Pays},
 	pallet_prelude::*,
-	traits::{Get, OneSessionHandler},
+	traits::{
+		fungible::{Inspect, MutateHold},
+		tokens::Precision,
+		DisabledValidators, Get, KeyOwnerProofSystem, OneSessionHandler,
+	},
 	weights::Weight,
 	BoundedSlice, BoundedVec, Parameter,
 };
@@ -31,18 +35,19 @@ use frame_system::{
 	pallet_prelude::{BlockNumberFor, OriginFor},
 };
 use log;
+use scale_info::TypeInfo;
 use sp_runtime::{
 	generic::DigestItem,
-	traits::{IsMember, Member, One},
-	RuntimeAppPublic,
+	traits::{IsMember, Keccak256, Member, One, SaturatedConversion, Saturating},
+	KeyTypeId, RuntimeAppPublic, RuntimeDebug,
 };
-use sp_session::{GetSessionNumber, GetValidatorCount};
+use sp_session::{GetSessionNumber, GetValidatorCount, ValidatorCount};
 use sp_staking::{offence::OffenceReportSystem, SessionIndex};
 use sp_std::prelude::*;
 
 use sp_consensus_beefy::{
-	AuthorityIndex, BeefyAuthorityId, ConsensusLog, DoubleVotingProof, OnNewValidatorSet,
-	ValidatorSet, BEEFY_ENGINE_ID, GENESIS_AUTHORITY_SET_ID,
+	check_commitment_signature, AuthorityIndex, BeefyAuthorityId, ConsensusLog, DoubleVotingProof,
+	OnNewValidatorSetExt, ValidatorSet, VoteMessage, BEEFY_ENGINE_ID, GENESIS_AUTHORITY_SET_ID,
 };
 
 mod default_weights;
@@ -52,13 +57,156 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-pub use crate::equivocation::{EquivocationOffence, EquivocationReportSystem, TimeSlot};
+pub use crate::equivocation::{
+	unsigned_report_validity, ArchivedSessionProofs, EquivocationOffence, EquivocationReportSystem,
+	OffenceRecorder, TimeSlot, INVALID_EQUIVOCATION_PROOF_SESSION,
+};
 pub use pallet::*;
 
-use crate::equivocation::EquivocationEvidenceFor;
+use crate::equivocation::{BatchEquivocationEvidenceFor, EquivocationEvidenceFor};
 
 const LOG_TARGET: &str = "runtime::beefy";
 
+/// The maximum number of equivocation proofs in a batched equivocation report.
+pub const MAX_BATCHED_EQUIVOCATION_PROOFS: u32 = 16;
+
+/// The number of sets for which [`ArchivedSessionRoots`] keeps the session root, as a multiple of
+/// [`Config::MaxSetIdSessionEntries`].
+pub const ARCHIVED_SESSION_ROOTS_FACTOR: u64 = 4;
+
+/// The number of BEEFY consensus digests kept in [`RecentConsensusDigests`].
+pub const MAX_RECENT_CONSENSUS_DIGESTS: u32 = 8;
+
+/// Context of the payload signed as proof of possession in a concurrent key usage report.
+const CONCURRENT_SESSION_CONTEXT: &[u8] = b"beefy-concurrent-session";
+
+type BalanceOf<T> =
+	<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Returns the payload a BEEFY key has to sign as proof of possession when `reporter` reports
+/// its concurrent usage during the validator set `set_id` in `session`, see
+/// [`Pallet::note_concurrent_session`].
+///
+/// The payload is bound to the reporter, so that a proof submitted by one reporter cannot be
+/// replayed by others.
+pub fn concurrent_session_payload<AccountId: Encode>(
+	set_id: sp_consensus_beefy::ValidatorSetId,
+	session: SessionIndex,
+	reporter: &AccountId,
+) -> Vec<u8> {
+	(CONCURRENT_SESSION_CONTEXT, set_id, session, reporter).encode()
+}
+
+/// The number of equivocation reports successfully processed for a session, per kind of report.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct OffenceCountsEntry {
+	/// Reports of a single double voting proof.
+	pub double_voting: u32,
+	/// Batched reports of several double voting proofs of the same offender.
+	pub double_voting_batch: u32,
+}
+
+/// The kind of proof an equivocation offence was reported with.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum OffenceKind {
+	/// A single double voting proof.
+	DoubleVoting,
+	/// A batch of double voting proofs of the same offender.
+	DoubleVotingBatch,
+}
+
+/// The kind of a BEEFY consensus digest deposited by this pallet, see
+/// [`RecentConsensusDigests`].
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum ConsensusLogKind {
+	/// A [`ConsensusLog::AuthoritiesChange`] digest.
+	AuthoritiesChange,
+	/// A [`ConsensusLog::OnDisabled`] digest.
+	OnDisabled,
+	/// A [`ConsensusLog::GenesisReset`] digest.
+	GenesisReset,
+}
+
+/// The last equivocation offence which was successfully reported, see [`LastOffence`].
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct OffenceRecord<BlockNumber> {
+	/// The kind of proof the offence was reported with.
+	pub kind: OffenceKind,
+	/// The validator set id of the equivocation.
+	pub set_id: sp_consensus_beefy::ValidatorSetId,
+	/// The session of the equivocation.
+	pub session: SessionIndex,
+	/// The block in which the offence was reported.
+	pub block_number: BlockNumber,
+}
+
+/// The data of the current validator set needed to pre-validate the vote of a fork voting proof,
+/// see [`pre_validate_fork_vote`].
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ForkVotingContext<AuthorityId, BlockNumber> {
+	/// The id of the current validator set.
+	pub current_set_id: sp_consensus_beefy::ValidatorSetId,
+	/// The authorities of the current validator set.
+	pub current_authorities: Vec<AuthorityId>,
+	/// The block BEEFY consensus started at, if it started.
+	pub genesis_block: Option<BlockNumber>,
+}
+
+/// The reason a vote was rejected by [`pre_validate_fork_vote`].
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum ForkVotePreValidationError {
+	/// The vote is not for the current validator set.
+	WrongSetId,
+	/// The voter is not an authority of the current validator set.
+	NotAnAuthority,
+	/// The vote targets a block before the BEEFY genesis, or BEEFY did not start yet.
+	BeforeGenesis,
+	/// The signature of the vote does not match its commitment.
+	InvalidSignature,
+}
+
+/// Cheaply check the vote of a fork voting proof against `context`.
+///
+/// Checks that the vote is for the current validator set, that the voter is part of it, that the
+/// voted block is not before the BEEFY genesis and that the vote is signed by the voter. It does
+/// not check whether the voted block is canonical, so passing this check does not make the proof
+/// valid. The signature is checked last, as it is the most expensive check.
+pub fn pre_validate_fork_vote<Id, BlockNumber>(
+	vote: &VoteMessage<BlockNumber, Id, <Id as RuntimeAppPublic>::Signature>,
+	context: &ForkVotingContext<Id, BlockNumber>,
+) -> Result<(), ForkVotePreValidationError>
+where
+	Id: BeefyAuthorityId<Keccak256> + PartialEq,
+	BlockNumber: Clone + Encode + PartialOrd,
+{
+	if vote.commitment.validator_set_id != context.current_set_id {
+		return Err(ForkVotePreValidationError::WrongSetId)
+	}
+	if !context.current_authorities.contains(&vote.id) {
+		return Err(ForkVotePreValidationError::NotAnAuthority)
+	}
+	match &context.genesis_block {
+		Some(genesis) if vote.commitment.block_number >= *genesis => {},
+		_ => return Err(ForkVotePreValidationError::BeforeGenesis),
+	}
+	if !check_commitment_signature::<_, _, Keccak256>(&vote.commitment, &vote.id, &vote.signature) {
+		return Err(ForkVotePreValidationError::InvalidSignature)
+	}
+	Ok(())
+}
+
+/// Provides the session data roots kept in [`ArchivedSessionRoots`].
+pub trait SessionRootProvider<Hash> {
+	/// The root of the session data of `session` and its validator count, if still known.
+	fn session_root(session: SessionIndex) -> Option<(Hash, ValidatorCount)>;
+}
+
+impl<Hash> SessionRootProvider<Hash> for () {
+	fn session_root(_session: SessionIndex) -> Option<(Hash, ValidatorCount)> {
+		None
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -66,6 +214,9 @@ pub mod pallet {
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
 		/// Authority identifier type
 		type BeefyId: Member
 			+ Parameter
@@ -96,11 +247,24 @@ pub mod pallet {
 		/// For some applications it might be beneficial to make the BEEFY validator set available
 		/// externally apart from having it in the storage. For instance you might cache a light
 		/// weight MMR root over validators and make it available for Light Clients.
-		type OnNewValidatorSet: OnNewValidatorSet<<Self as Config>::BeefyId>;
+		///
+		/// Any [`sp_consensus_beefy::OnNewValidatorSet`] can be used, if it doesn't need the
+		/// session index and the activation block of the new set.
+		type OnNewValidatorSet: OnNewValidatorSetExt<
+			<Self as Config>::BeefyId,
+			BlockNumberFor<Self>,
+		>;
 
 		/// Weights for this pallet.
 		type WeightInfo: WeightInfo;
 
+		/// A way to check whether a given validator is disabled in the current session.
+		///
+		/// Equivocation reports against disabled authorities are still processed, but the
+		/// resulting offence is tagged with
+		/// [`EquivocationOffence::offender_already_disabled`].
+		type DisabledValidators: DisabledValidators;
+
 		/// The proof of key ownership, used for validating equivocation reports
 		/// The proof must include the session index and validator count of the
 		/// session at which the equivocation occurred.
@@ -109,10 +273,47 @@ pub mod pallet {
 		/// The equivocation handling subsystem.
 		///
 		/// Defines methods to publish, check and process an equivocation offence.
-		type EquivocationReportSystem: OffenceReportSystem<
-			Option<Self::AccountId>,
-			EquivocationEvidenceFor<Self>,
-		>;
+		type EquivocationReportSystem: OffenceReportSystem<Option<Self::AccountId>, EquivocationEvidenceFor<Self>>
+			+ OffenceReportSystem<Option<Self::AccountId>, BatchEquivocationEvidenceFor<Self>>;
+
+		/// The key ownership proof system used for sessions which the key ownership proof system
+		/// of the `EquivocationReportSystem` doesn't cover anymore.
+		///
+		/// It provides the session roots archived in [`ArchivedSessionRoots`] when a session
+		/// starts, and checks proofs of older sessions against them, e.g.
+		/// [`ArchivedSessionProofs`]. Equivocations discovered late can thus still be reported
+		/// after the historical session data got pruned.
+		type HistoricalProofProvider: KeyOwnerProofSystem<(KeyTypeId, Self::BeefyId), Proof = Self::KeyOwnerProof>
+			+ SessionRootProvider<Self::Hash>;
+
+		/// The currency used to hold the deposit of concurrent key usage reports.
+		type Currency: MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// The deposit held from the reporter of a concurrent key usage until the session ends.
+		#[pallet::constant]
+		type ConcurrentKeyReportDeposit: Get<BalanceOf<Self>>;
+
+		/// The maximum number of reports of the concurrent usage of a single key per session.
+		#[pallet::constant]
+		type MaxConcurrentKeyReports: Get<u32>;
+
+		/// Whether a current authority is disabled for the rest of the session as soon as its
+		/// double voting is reported, rather than only once the staking layer rotates it out.
+		///
+		/// Disabling deposits a [`ConsensusLog::OnDisabled`] digest, so that BEEFY voters ignore
+		/// further votes of the authority.
+		type DisableStrategy: Get<bool>;
+	}
+
+	/// A reason for the BEEFY pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// The funds are held as deposit for reporting the concurrent usage of a BEEFY key.
+		#[codec(index = 0)]
+		ConcurrentKeyReport,
 	}
 
 	#[pallet::pallet]
@@ -147,12 +348,106 @@ pub mod pallet {
 	pub type SetIdSession<T: Config> =
 		StorageMap<_, Twox64Concat, sp_consensus_beefy::ValidatorSetId, SessionIndex>;
 
+	/// A mapping from session index to the BEEFY set ID of the session, along with the root of
+	/// its session data and its validator count.
+	///
+	/// Populated from `HistoricalProofProvider` when a session starts. Entries are kept for
+	/// [`ARCHIVED_SESSION_ROOTS_FACTOR`] times as many sets as `SetIdSession` keeps, so that key
+	/// ownership proofs of sets which are already pruned from `SetIdSession` can still be
+	/// checked.
+	///
+	/// TWOX-NOTE: `SessionIndex` is not under user control.
+	#[pallet::storage]
+	pub type ArchivedSessionRoots<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		SessionIndex,
+		(sp_consensus_beefy::ValidatorSetId, T::Hash, ValidatorCount),
+	>;
+
+	/// The number of equivocation reports processed per session of the reported equivocation.
+	///
+	/// Entries are kept as long as the session is part of `SetIdSession` or
+	/// `ArchivedSessionRoots`.
+	///
+	/// TWOX-NOTE: `SessionIndex` is taken from a validated key ownership proof.
+	#[pallet::storage]
+	pub type OffenceCounts<T: Config> =
+		StorageMap<_, Twox64Concat, SessionIndex, OffenceCountsEntry, ValueQuery>;
+
+	/// The rounds of batched equivocation reports which were not reported as an offence of their
+	/// own, per set id and offender.
+	///
+	/// A batch is reported as a single offence at its first new round, so its other rounds are
+	/// kept here to not punish them again. Entries are kept as long as the session of their set
+	/// is part of `SetIdSession` or `ArchivedSessionRoots`.
+	///
+	/// TWOX-NOTE: `ValidatorSetId` is taken from a validated equivocation proof.
+	#[pallet::storage]
+	pub type BatchReportedRounds<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		sp_consensus_beefy::ValidatorSetId,
+		Blake2_128Concat,
+		(T::BeefyId, BlockNumberFor<T>),
+		(),
+	>;
+
+	/// The last equivocation offence which was successfully reported, of any kind.
+	#[pallet::storage]
+	pub type LastOffence<T: Config> = StorageValue<_, OffenceRecord<BlockNumberFor<T>>>;
+
+	/// The number of equivocation offences successfully reported per kind of proof, over the
+	/// lifetime of the chain.
+	#[pallet::storage]
+	pub type OffenceCount<T: Config> = StorageMap<_, Twox64Concat, OffenceKind, u32, ValueQuery>;
+
+	/// The reporters of a concurrent usage of a BEEFY key in the current session, along with the
+	/// deposit held from them.
+	///
+	/// Cleared when a new session starts, releasing the deposits.
+	#[pallet::storage]
+	pub type ConcurrentKeyReports<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::BeefyId,
+		BoundedVec<(T::AccountId, BalanceOf<T>), T::MaxConcurrentKeyReports>,
+		ValueQuery,
+	>;
+
+	/// The indices of the current authorities which were disabled for double voting in the
+	/// current session, see [`Config::DisableStrategy`]. Always sorted.
+	///
+	/// Cleared when a new session starts.
+	#[pallet::storage]
+	pub type DisabledThisSession<T: Config> =
+		StorageValue<_, BoundedVec<AuthorityIndex, T::MaxAuthorities>, ValueQuery>;
+
 	/// Block number where BEEFY consensus is enabled/started.
 	/// By changing this (through privileged `set_new_genesis()`), BEEFY consensus is effectively
 	/// restarted from the newly set block number.
 	#[pallet::storage]
 	pub type GenesisBlock<T: Config> = StorageValue<_, Option<BlockNumberFor<T>>, ValueQuery>;
 
+	/// Whether the BEEFY digests announcing the genesis configuration still have to be deposited.
+	///
+	/// Digests deposited while building the genesis state never make it into a header, so they
+	/// are deposited by the first block instead.
+	#[pallet::storage]
+	pub(super) type GenesisDigestsPending<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// The block number and kind of the last [`MAX_RECENT_CONSENSUS_DIGESTS`] BEEFY consensus
+	/// digests deposited by this pallet, oldest first.
+	///
+	/// Lets a restarting node find the recent authority changes and disablings without replaying
+	/// the headers. The oldest entry is evicted once the buffer is full.
+	#[pallet::storage]
+	pub type RecentConsensusDigests<T: Config> = StorageValue<
+		_,
+		BoundedVec<(BlockNumberFor<T>, ConsensusLogKind), ConstU32<MAX_RECENT_CONSENSUS_DIGESTS>>,
+		ValueQuery,
+	>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		/// Initial set of BEEFY authorities.
@@ -162,6 +457,15 @@ pub mod pallet {
 		/// *Note:* Ideally use block number where GRANDPA authorities are changed,
 		/// to guarantee the client gets a finality notification for exactly this block.
 		pub genesis_block: Option<BlockNumberFor<T>>,
+		/// Validator set id of the initial `authorities`.
+		///
+		/// Defaults to [`GENESIS_AUTHORITY_SET_ID`]. Setting it allows a chain forked off from an
+		/// existing one to continue with that chain's validator set ids, so that its finality
+		/// proofs stay verifiable. Requires `authorities` to be set. If the session keys also
+		/// provide the BEEFY authorities, they must match `authorities` and this pallet must come
+		/// before the session pallet in `construct_runtime!`. The set is announced to clients by a
+		/// [`ConsensusLog::AuthoritiesChange`] digest in the first block.
+		pub initial_set_id: Option<sp_consensus_beefy::ValidatorSetId>,
 	}
 
 	impl<T: Config> Default for GenesisConfig<T> {
@@ -169,31 +473,66 @@ pub mod pallet {
 			// BEEFY genesis will be first BEEFY-MANDATORY block,
 			// use block number one instead of chain-genesis.
 			let genesis_block = Some(One::one());
-			Self { authorities: Vec::new(), genesis_block }
+			Self { authorities: Vec::new(), genesis_block, initial_set_id: None }
 		}
 	}
 
 	#[pallet::genesis_build]
 	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
 		fn build(&self) {
-			Pallet::<T>::initialize(&self.authorities)
+			let set_id = match self.initial_set_id {
+				Some(set_id) => {
+					assert!(
+						!self.authorities.is_empty(),
+						"BEEFY `initial_set_id` requires initial authorities",
+					);
+					set_id
+				},
+				None => GENESIS_AUTHORITY_SET_ID,
+			};
+			Pallet::<T>::initialize(&self.authorities, set_id)
 				// we panic here as runtime maintainers can simply reconfigure genesis and restart
 				// the chain easily
 				.expect("Authorities vec too big");
 			GenesisBlock::<T>::put(&self.genesis_block);
+			if self.genesis_block.is_some() || self.initial_set_id.is_some() {
+				GenesisDigestsPending::<T>::put(true);
+			}
 		}
 	}
 
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The concurrent usage of `key` was reported, it might be run by several nodes.
+		/// `reports` is the number of reports of the key in the current session.
+		PossibleDuplicateKey { key: T::BeefyId, reports: u32 },
+	}
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// A key ownership proof provided as part of an equivocation report is invalid.
 		InvalidKeyOwnershipProof,
-		/// An equivocation proof provided as part of an equivocation report is invalid.
+		/// An equivocation proof provided as part of an equivocation report is invalid, or the
+		/// proofs of a batched report are not all for the same offender and set.
 		InvalidEquivocationProof,
 		/// A given equivocation report is valid but already previously reported.
 		DuplicateOffenceReport,
 		/// Submitted configuration is invalid.
 		InvalidConfiguration,
+		/// The reported key is not a BEEFY authority of the current set.
+		NotAnAuthority,
+		/// The proof of possession of a concurrent key usage report is invalid.
+		InvalidProofOfPossession,
+		/// The concurrent usage of the key was already reported by this account in the current
+		/// session.
+		DuplicateConcurrentKeyReport,
+		/// The concurrent usage of the key was already reported by the maximum number of accounts
+		/// in the current session.
+		TooManyConcurrentKeyReports,
+		/// The set id of an equivocation report is not (or no longer) mapped to a session, so the
+		/// report can't be processed.
+		InvalidEquivocationProofSession,
 	}
 
 	#[pallet::call]
@@ -220,10 +559,12 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let reporter = ensure_signed(origin)?;
 
+			let session = key_owner_proof.session();
 			T::EquivocationReportSystem::process_evidence(
 				Some(reporter),
 				(*equivocation_proof, key_owner_proof),
 			)?;
+			OffenceCounts::<T>::mutate(session, |counts| counts.double_voting.saturating_inc());
 			// Waive the fee since the report is valid and beneficial
 			Ok(Pays::No.into())
 		}
@@ -255,16 +596,60 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			ensure_none(origin)?;
 
+			let session = key_owner_proof.session();
 			T::EquivocationReportSystem::process_evidence(
 				None,
 				(*equivocation_proof, key_owner_proof),
 			)?;
+			OffenceCounts::<T>::mutate(session, |counts| counts.double_voting.saturating_inc());
+			Ok(Pays::No.into())
+		}
+
+		/// Report several equivocations of the same authority within the same set. This method
+		/// will verify that all equivocation proofs are for the same offender and set id, so
+		/// that a single key ownership proof suffices, and validate it against the offender.
+		///
+		/// All proofs are reported as a single offence, whose severity scales with the number of
+		/// distinct rounds in which the offender equivocated. Duplicated rounds are only counted
+		/// once.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::report_equivocation_batch(
+			equivocation_proofs.len() as u32,
+			key_owner_proof.validator_count(),
+			T::MaxNominators::get(),
+		))]
+		pub fn report_equivocation_batch(
+			origin: OriginFor<T>,
+			equivocation_proofs: BoundedVec<
+				DoubleVotingProof<
+					BlockNumberFor<T>,
+					T::BeefyId,
+					<T::BeefyId as RuntimeAppPublic>::Signature,
+				>,
+				ConstU32<MAX_BATCHED_EQUIVOCATION_PROOFS>,
+			>,
+			key_owner_proof: T::KeyOwnerProof,
+		) -> DispatchResultWithPostInfo {
+			let reporter = ensure_signed(origin)?;
+
+			let session = key_owner_proof.session();
+			T::EquivocationReportSystem::process_evidence(
+				Some(reporter),
+				(equivocation_proofs.into_inner(), key_owner_proof),
+			)?;
+			OffenceCounts::<T>::mutate(session, |counts| {
+				counts.double_voting_batch.saturating_inc()
+			});
+			// Waive the fee since the report is valid and beneficial
 			Ok(Pays::No.into())
 		}
 
 		/// Reset BEEFY consensus by setting a new BEEFY genesis at `delay_in_blocks` blocks in the
 		/// future.
 		///
+		/// Deposits a [`ConsensusLog::GenesisReset`] digest, so that clients following the
+		/// headers learn about the reset without querying the state.
+		///
 		/// Note: `delay_in_blocks` has to be at least 1.
 		#[pallet::call_index(2)]
 		#[pallet::weight(<T as Config>::WeightInfo::set_new_genesis())]
@@ -276,12 +661,89 @@ pub mod pallet {
 			ensure!(delay_in_blocks >= One::one(), Error::<T>::InvalidConfiguration);
 			let genesis_block = frame_system::Pallet::<T>::block_number() + delay_in_blocks;
 			GenesisBlock::<T>::put(Some(genesis_block));
+			Self::deposit_genesis_reset_log(genesis_block);
+			Ok(())
+		}
+
+		/// Report that `beefy_key` is used by several nodes at the same time, e.g. because votes
+		/// from it were announced by distinct peers within the current session.
+		///
+		/// `proof_of_possession` is a signature of `beefy_key` over
+		/// [`concurrent_session_payload`] of the current validator set id, the current session and
+		/// the reporter. This does not report an offence, it only emits
+		/// [`Event::PossibleDuplicateKey`] so that the operator can react before the key
+		/// equivocates. [`Config::ConcurrentKeyReportDeposit`] is held from the reporter until
+		/// the session ends.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::note_concurrent_session(
+			T::MaxConcurrentKeyReports::get(),
+		))]
+		pub fn note_concurrent_session(
+			origin: OriginFor<T>,
+			beefy_key: T::BeefyId,
+			proof_of_possession: <T::BeefyId as RuntimeAppPublic>::Signature,
+		) -> DispatchResult {
+			let reporter = ensure_signed(origin)?;
+			ensure!(Self::is_member(&beefy_key), Error::<T>::NotAnAuthority);
+			let payload = concurrent_session_payload(
+				ValidatorSetId::<T>::get(),
+				pallet_session::Pallet::<T>::current_index(),
+				&reporter,
+			);
+			ensure!(
+				<T::BeefyId as BeefyAuthorityId<Keccak256>>::verify(
+					&beefy_key,
+					&proof_of_possession,
+					&payload,
+				),
+				Error::<T>::InvalidProofOfPossession,
+			);
+
+			let deposit = T::ConcurrentKeyReportDeposit::get();
+			let reports = ConcurrentKeyReports::<T>::try_mutate(
+				&beefy_key,
+				|reports| -> Result<u32, DispatchError> {
+					ensure!(
+						!reports.iter().any(|(who, _)| who == &reporter),
+						Error::<T>::DuplicateConcurrentKeyReport,
+					);
+					reports
+						.try_push((reporter.clone(), deposit))
+						.map_err(|_| Error::<T>::TooManyConcurrentKeyReports)?;
+					T::Currency::hold(&HoldReason::ConcurrentKeyReport.into(), &reporter, deposit)?;
+					Ok(reports.len() as u32)
+				},
+			)?;
+
+			Self::deposit_event(Event::PossibleDuplicateKey { key: beefy_key, reports });
 			Ok(())
 		}
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			if !GenesisDigestsPending::<T>::take() {
+				return T::DbWeight::get().reads(1)
+			}
+			// Let clients pick up a non-default initial set id from the first header. This pallet
+			// comes before the session pallet, so the genesis set is still the current one.
+			if ValidatorSetId::<T>::get() != GENESIS_AUTHORITY_SET_ID {
+				if let Some(validator_set) = Self::validator_set() {
+					let log = DigestItem::Consensus(
+						BEEFY_ENGINE_ID,
+						ConsensusLog::AuthoritiesChange(validator_set).encode(),
+					);
+					frame_system::Pallet::<T>::deposit_log(log);
+					Self::note_consensus_digest(ConsensusLogKind::AuthoritiesChange);
+				}
+			}
+			if let Some(genesis_block) = GenesisBlock::<T>::get() {
+				Self::deposit_genesis_reset_log(genesis_block);
+			}
+			T::DbWeight::get().reads_writes(5, 3)
+		}
+
 		#[cfg(feature = "try-runtime")]
 		fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
 			Self::do_try_state()
@@ -364,6 +826,163 @@ impl<T: Config> Pallet<T> {
 		ValidatorSet::<T::BeefyId>::new(validators, id)
 	}
 
+	/// Return the data needed to pre-validate fork voting proofs with [`pre_validate_fork_vote`].
+	pub fn fork_voting_context() -> ForkVotingContext<T::BeefyId, BlockNumberFor<T>> {
+		ForkVotingContext {
+			current_set_id: ValidatorSetId::<T>::get(),
+			current_authorities: Authorities::<T>::get().into_inner(),
+			genesis_block: GenesisBlock::<T>::get(),
+		}
+	}
+
+	/// Returns whether `key` is in the current [`Authorities`].
+	///
+	/// The authorities are kept in the order of the validator set, which defines their
+	/// [`AuthorityIndex`], rather than sorted by key. Membership is therefore checked with a
+	/// linear scan, which is bounded by [`Config::MaxAuthorities`].
+	pub fn is_current_authority(key: T::BeefyId) -> bool {
+		Authorities::<T>::get().contains(&key)
+	}
+
+	/// Returns whether `key` is in the [`NextAuthorities`].
+	///
+	/// See [`Pallet::is_current_authority`] for the complexity.
+	pub fn is_next_authority(key: T::BeefyId) -> bool {
+		NextAuthorities::<T>::get().contains(&key)
+	}
+
+	/// Return the [`AuthorityIndex`] of `key` in the current [`Authorities`], as used by the
+	/// consensus logs, if it is a current authority.
+	///
+	/// See [`Pallet::is_current_authority`] for the complexity.
+	pub fn authority_index(key: T::BeefyId) -> Option<AuthorityIndex> {
+		Authorities::<T>::get()
+			.iter()
+			.position(|id| *id == key)
+			.map(|index| index as AuthorityIndex)
+	}
+
+	/// Return the number of equivocation reports processed for `session`.
+	pub fn offence_counts(session: SessionIndex) -> OffenceCountsEntry {
+		OffenceCounts::<T>::get(session)
+	}
+
+	/// Return the number of equivocation reports processed for each of the last `n` sessions,
+	/// up to and including the current one, ordered by session.
+	pub fn recent_offence_counts(n: u32) -> Vec<(SessionIndex, OffenceCountsEntry)> {
+		let current = SetIdSession::<T>::get(ValidatorSetId::<T>::get()).unwrap_or_default();
+		let first = current.saturating_add(1).saturating_sub(n);
+		(first..=current)
+			.map(|session| (session, OffenceCounts::<T>::get(session)))
+			.collect()
+	}
+
+	/// Return the last equivocation offence which was successfully reported, if any.
+	pub fn last_offence() -> Option<OffenceRecord<BlockNumberFor<T>>> {
+		LastOffence::<T>::get()
+	}
+
+	/// Return the number of equivocation offences successfully reported with proofs of `kind`.
+	pub fn offence_count(kind: OffenceKind) -> u32 {
+		OffenceCount::<T>::get(kind)
+	}
+
+	/// Return the block number and kind of the recent BEEFY consensus digests deposited by this
+	/// pallet, oldest first, see [`RecentConsensusDigests`].
+	pub fn recent_digests() -> Vec<(BlockNumberFor<T>, ConsensusLogKind)> {
+		RecentConsensusDigests::<T>::get().into_inner()
+	}
+
+	/// Returns whether `authority` of the validator set `set_id` is disabled.
+	///
+	/// Disabling is only tracked for the current session, so authorities of any other set are
+	/// never considered disabled.
+	pub(crate) fn is_authority_disabled(
+		set_id: sp_consensus_beefy::ValidatorSetId,
+		authority: &T::BeefyId,
+	) -> bool {
+		if set_id != ValidatorSetId::<T>::get() {
+			return false
+		}
+		Self::authority_index(authority.clone()).map_or(false, |index| {
+			T::DisabledValidators::is_disabled(index) ||
+				DisabledThisSession::<T>::get().binary_search(&index).is_ok()
+		})
+	}
+
+	/// Disable `authority` of the validator set `set_id` for the rest of the session after its
+	/// double voting was reported, if [`Config::DisableStrategy`] is enabled.
+	///
+	/// Only authorities of the current set are disabled. The [`ConsensusLog::OnDisabled`] digest
+	/// is deposited only the first time an authority is disabled within the session.
+	pub(crate) fn disable_equivocating_authority(
+		set_id: sp_consensus_beefy::ValidatorSetId,
+		authority: &T::BeefyId,
+	) {
+		if !T::DisableStrategy::get() || set_id != ValidatorSetId::<T>::get() {
+			return
+		}
+		let Some(index) = Self::authority_index(authority.clone()) else { return };
+		let newly_disabled =
+			DisabledThisSession::<T>::mutate(|disabled| match disabled.binary_search(&index) {
+				Ok(_) => false,
+				Err(pos) => disabled.try_insert(pos, index).is_ok(),
+			});
+		if newly_disabled {
+			Self::deposit_disabled_log(index);
+		}
+	}
+
+	/// Deposit the [`ConsensusLog::OnDisabled`] digest for the authority at `index`.
+	fn deposit_disabled_log(index: AuthorityIndex) {
+		let log = DigestItem::Consensus(
+			BEEFY_ENGINE_ID,
+			ConsensusLog::<T::BeefyId>::OnDisabled(index).encode(),
+		);
+		frame_system::Pallet::<T>::deposit_log(log);
+		Self::note_consensus_digest(ConsensusLogKind::OnDisabled);
+	}
+
+	/// Deposit the [`ConsensusLog::GenesisReset`] digest for BEEFY consensus starting at
+	/// `genesis_block` while the current validator set is active.
+	fn deposit_genesis_reset_log(genesis_block: BlockNumberFor<T>) {
+		let log = DigestItem::Consensus(
+			BEEFY_ENGINE_ID,
+			ConsensusLog::<T::BeefyId>::GenesisReset {
+				genesis_block: genesis_block.saturated_into(),
+				validator_set_id: ValidatorSetId::<T>::get(),
+			}
+			.encode(),
+		);
+		frame_system::Pallet::<T>::deposit_log(log);
+		Self::note_consensus_digest(ConsensusLogKind::GenesisReset);
+	}
+
+	/// Record a BEEFY consensus digest of `kind` deposited in the current block in
+	/// [`RecentConsensusDigests`], evicting the oldest entry if the buffer is full.
+	fn note_consensus_digest(kind: ConsensusLogKind) {
+		let now = frame_system::Pallet::<T>::block_number();
+		RecentConsensusDigests::<T>::mutate(|digests| {
+			if digests.is_full() {
+				digests.remove(0);
+			}
+			let _ = digests.try_push((now, kind));
+		});
+	}
+
+	/// Returns whether `session` is the session of the validator set `set_id`, according to
+	/// [`SetIdSession`] or, for sets which are already pruned from it, [`ArchivedSessionRoots`].
+	pub(crate) fn is_session_of_set(
+		set_id: sp_consensus_beefy::ValidatorSetId,
+		session: SessionIndex,
+	) -> bool {
+		match SetIdSession::<T>::get(set_id) {
+			Some(set_id_session) => set_id_session == session,
+			None => ArchivedSessionRoots::<T>::get(session)
+				.map_or(false, |(archived_set_id, ..)| archived_set_id == set_id),
+		}
+	}
+
 	/// Submits an extrinsic to report an equivocation. This method will create
 	/// an unsigned extrinsic with a call to `report_equivocation_unsigned` and
 	/// will push the transaction to the pool. Only useful in an offchain context.
@@ -378,9 +997,39 @@ impl<T: Config> Pallet<T> {
 		T::EquivocationReportSystem::publish_evidence((equivocation_proof, key_owner_proof)).ok()
 	}
 
+	/// Clear the concurrent key usage reports of the ending session, releasing their deposits.
+	///
+	/// Only keys of the current authorities can be reported, so this releases at most
+	/// `MaxAuthorities * MaxConcurrentKeyReports` deposits. The session handler cannot return a
+	/// weight, so the weight is registered with the system pallet instead.
+	fn clear_concurrent_key_reports() {
+		let mut released = 0u32;
+		for (_, reports) in ConcurrentKeyReports::<T>::drain() {
+			for (reporter, deposit) in reports {
+				released.saturating_inc();
+				let reason = HoldReason::ConcurrentKeyReport.into();
+				if let Err(e) =
+					T::Currency::release(&reason, &reporter, deposit, Precision::BestEffort)
+				{
+					log::error!(
+						target: LOG_TARGET,
+						"failed to release concurrent key report deposit of {:?}: {:?}",
+						reporter,
+						e,
+					);
+				}
+			}
+		}
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			T::WeightInfo::clear_concurrent_key_reports(released),
+			DispatchClass::Mandatory,
+		);
+	}
+
 	fn change_authorities(
 		new: BoundedVec<T::BeefyId, T::MaxAuthorities>,
 		queued: BoundedVec<T::BeefyId, T::MaxAuthorities>,
+		session_index: SessionIndex,
 	) {
 		Authorities::<T>::put(&new);
 
@@ -395,18 +1044,24 @@ impl<T: Config> Pallet<T> {
 				ConsensusLog::AuthoritiesChange(validator_set.clone()).encode(),
 			);
 			frame_system::Pallet::<T>::deposit_log(log);
+			Self::note_consensus_digest(ConsensusLogKind::AuthoritiesChange);
 
 			let next_id = new_id + 1;
 			if let Some(next_validator_set) = ValidatorSet::<T::BeefyId>::new(queued, next_id) {
-				<T::OnNewValidatorSet as OnNewValidatorSet<_>>::on_new_validator_set(
+				<T::OnNewValidatorSet as OnNewValidatorSetExt<_, _>>::on_new_validator_set_ext(
 					&validator_set,
 					&next_validator_set,
+					session_index,
+					frame_system::Pallet::<T>::block_number(),
 				);
 			}
 		}
 	}
 
-	fn initialize(authorities: &Vec<T::BeefyId>) -> Result<(), ()> {
+	fn initialize(
+		authorities: &Vec<T::BeefyId>,
+		id: sp_consensus_beefy::ValidatorSetId,
+	) -> Result<(), ()> {
 		if authorities.is_empty() {
 			return Ok(())
 		}
@@ -419,7 +1074,6 @@ impl<T: Config> Pallet<T> {
 			BoundedSlice::<T::BeefyId, T::MaxAuthorities>::try_from(authorities.as_slice())
 				.map_err(|_| ())?;
 
-		let id = GENESIS_AUTHORITY_SET_ID;
 		Authorities::<T>::put(bounded_authorities);
 		ValidatorSetId::<T>::put(id);
 		// Like `pallet_session`, initialize the next validator set as well.
@@ -430,9 +1084,11 @@ impl<T: Config> Pallet<T> {
 			if let Some(next_validator_set) =
 				ValidatorSet::<T::BeefyId>::new(authorities.clone(), next_id)
 			{
-				<T::OnNewValidatorSet as OnNewValidatorSet<_>>::on_new_validator_set(
+				<T::OnNewValidatorSet as OnNewValidatorSetExt<_, _>>::on_new_validator_set_ext(
 					&validator_set,
 					&next_validator_set,
+					0,
+					frame_system::Pallet::<T>::block_number(),
 				);
 			}
 		}
@@ -440,12 +1096,24 @@ impl<T: Config> Pallet<T> {
 		// NOTE: initialize first session of first set. this is necessary for
 		// the genesis set and session since we only update the set -> session
 		// mapping whenever a new session starts, i.e. through `on_new_session`.
-		SetIdSession::<T>::insert(0, 0);
+		SetIdSession::<T>::insert(id, 0);
 
 		Ok(())
 	}
 }
 
+impl<T: Config> OffenceRecorder for Pallet<T> {
+	fn record_offence(
+		kind: OffenceKind,
+		set_id: sp_consensus_beefy::ValidatorSetId,
+		session: SessionIndex,
+	) {
+		let block_number = frame_system::Pallet::<T>::block_number();
+		LastOffence::<T>::put(OffenceRecord { kind, set_id, session, block_number });
+		OffenceCount::<T>::mutate(kind, |count| count.saturating_inc());
+	}
+}
+
 impl<T: Config> sp_runtime::BoundToRuntimeAppPublic for Pallet<T> {
 	type Public = T::BeefyId;
 }
@@ -461,9 +1129,14 @@ where
 		I: Iterator<Item = (&'a T::AccountId, T::BeefyId)>,
 	{
 		let authorities = validators.map(|(_, k)| k).collect::<Vec<_>>();
+		// BEEFY may have already been initialized with the same authorities by its own genesis
+		// config, e.g. to start from a non-default validator set id.
+		if !authorities.is_empty() && Authorities::<T>::get().as_slice() == authorities.as_slice() {
+			return
+		}
 		// we panic here as runtime maintainers can simply reconfigure genesis and restart the
 		// chain easily
-		Self::initialize(&authorities).expect("Authorities vec too big");
+		Self::initialize(&authorities, GENESIS_AUTHORITY_SET_ID).expect("Authorities vec too big");
 	}
 
 	fn on_new_session<'a, I: 'a>(_changed: bool, validators: I, queued_validators: I)
@@ -494,28 +1167,56 @@ where
 		let bounded_next_queued_authorities =
 			BoundedVec::<_, T::MaxAuthorities>::truncate_from(next_queued_authorities);
 
+		Self::clear_concurrent_key_reports();
+		DisabledThisSession::<T>::kill();
+
+		let session_index = pallet_session::Pallet::<T>::current_index();
 		// Always issue a change on each `session`, even if validator set hasn't changed.
 		// We want to have at least one BEEFY mandatory block per session.
-		Self::change_authorities(bounded_next_authorities, bounded_next_queued_authorities);
+		Self::change_authorities(
+			bounded_next_authorities,
+			bounded_next_queued_authorities,
+			session_index,
+		);
 
 		let validator_set_id = ValidatorSetId::<T>::get();
 		// Update the mapping for the new set id that corresponds to the latest session (i.e. now).
-		let session_index = pallet_session::Pallet::<T>::current_index();
 		SetIdSession::<T>::insert(validator_set_id, &session_index);
+		// Archive the session root before the entry gets pruned, so that key ownership proofs
+		// of the session can be checked even after the historical session data is gone.
+		if let Some((root, validator_count)) =
+			<T::HistoricalProofProvider as SessionRootProvider<_>>::session_root(session_index)
+		{
+			ArchivedSessionRoots::<T>::insert(
+				session_index,
+				(validator_set_id, root, validator_count),
+			);
+		}
 		// Prune old entry if limit reached.
 		let max_set_id_session_entries = T::MaxSetIdSessionEntries::get().max(1);
 		if validator_set_id >= max_set_id_session_entries {
-			SetIdSession::<T>::remove(validator_set_id - max_set_id_session_entries);
+			let pruned_set_id = validator_set_id - max_set_id_session_entries;
+			if let Some(pruned_session) = SetIdSession::<T>::take(pruned_set_id) {
+				if !ArchivedSessionRoots::<T>::contains_key(pruned_session) {
+					OffenceCounts::<T>::remove(pruned_session);
+					let _ = BatchReportedRounds::<T>::clear_prefix(pruned_set_id, u32::MAX, None);
+				}
+			}
+		}
+		// Every session enacts a new set, so the archive is pruned by session index.
+		let max_archived_entries =
+			max_set_id_session_entries.saturating_mul(ARCHIVED_SESSION_ROOTS_FACTOR);
+		if let Some(pruned_session) = (session_index as u64).checked_sub(max_archived_entries) {
+			let pruned_session = pruned_session as SessionIndex;
+			if let Some((pruned_set_id, ..)) = ArchivedSessionRoots::<T>::take(pruned_session) {
+				OffenceCounts::<T>::remove(pruned_session);
+				let _ = BatchReportedRounds::<T>::clear_prefix(pruned_set_id, u32::MAX, None);
+			}
 		}
 	}
 
 	fn on_disabled(i: u32) {
-		let log = DigestItem::Consensus(
-			BEEFY_ENGINE_ID,
-			ConsensusLog::<T::BeefyId>::OnDisabled(i as AuthorityIndex).encode(),
-		);
-
-		frame_system::Pallet::<T>::deposit_log(log);
+		Self::deposit_disabled_log(i as AuthorityIndex);
 	}
 }
 
@@ -525,7 +1226,24 @@ impl<T: Config> IsMember<T::BeefyId> for Pallet<T> {
 	}
 }
 
+/// [`IsMember`] of the [`NextAuthorities`], while the pallet itself checks the current
+/// [`Authorities`].
+pub struct IsNextMember<T>(PhantomData<T>);
+
+impl<T: Config> IsMember<T::BeefyId> for IsNextMember<T> {
+	fn is_member(authority_id: &T::BeefyId) -> bool {
+		NextAuthorities::<T>::get().iter().any(|id| id == authority_id)
+	}
+}
+
 pub trait WeightInfo {
 	fn report_equivocation(validator_count: u32, max_nominators_per_validator: u32) -> Weight;
+	fn report_equivocation_batch(
+		proofs_count: u32,
+		validator_count: u32,
+		max_nominators_per_validator: u32,
+	) -> Weight;
 	fn set_new_genesis() -> Weight;
+	fn note_concurrent_session(reports: u32) -> Weight;
+	fn clear_concurrent_key_reports(reports: u32) -> Weight;
 }
@@ -23,7 +23,9 @@ use frame_election_provider_support::{
 };
 use frame_support::{
 	construct_runtime, derive_impl, parameter_types,
-	traits::{ConstU32, ConstU64, KeyOwnerProofSystem, OnFinalize, OnInitialize},
+	traits::{
+		ConstU32, ConstU64, DisabledValidators, KeyOwnerProofSystem, OnFinalize, OnInitialize,
+	},
 };
 use pallet_session::historical as pallet_session_historical;
 use sp_core::{crypto::KeyTypeId, ConstU128};
@@ -31,11 +33,15 @@ use sp_runtime::{
 	app_crypto::ecdsa::Public, curve::PiecewiseLinear, impl_opaque_keys, testing::TestXt,
 	traits::OpaqueKeys, BuildStorage, Perbill,
 };
-use sp_staking::{EraIndex, SessionIndex};
+use sp_staking::{
+	offence::{OffenceError, ReportOffence},
+	EraIndex, SessionIndex,
+};
 use sp_state_machine::BasicExternalities;
 
 use crate as pallet_beefy;
 
+use sp_consensus_beefy::ValidatorSetId;
 pub use sp_consensus_beefy::{ecdsa_crypto::AuthorityId as BeefyId, ConsensusLog, BEEFY_ENGINE_ID};
 
 impl_opaque_keys! {
@@ -83,15 +89,69 @@ parameter_types! {
 }
 
 impl pallet_beefy::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
 	type BeefyId = BeefyId;
 	type MaxAuthorities = ConstU32<100>;
 	type MaxNominators = ConstU32<1000>;
 	type MaxSetIdSessionEntries = MaxSetIdSessionEntries;
 	type OnNewValidatorSet = ();
 	type WeightInfo = ();
+	type DisabledValidators = MockDisabledValidators;
 	type KeyOwnerProof = <Historical as KeyOwnerProofSystem<(KeyTypeId, BeefyId)>>::Proof;
 	type EquivocationReportSystem =
-		super::EquivocationReportSystem<Self, Offences, Historical, ReportLongevity>;
+		super::EquivocationReportSystem<Self, OffencesRecorder, Historical, ReportLongevity>;
+	type HistoricalProofProvider = super::ArchivedSessionProofs<Self>;
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type ConcurrentKeyReportDeposit = ConstU128<1_000>;
+	type MaxConcurrentKeyReports = ConstU32<2>;
+	type DisableStrategy = DisableEquivocators;
+}
+
+parameter_types! {
+	pub static DisableEquivocators: bool = false;
+	pub static DisabledAuthorities: Vec<u32> = vec![];
+	pub static ReportedOffenderDisabledFlags: Vec<bool> = vec![];
+	pub static ReportedOffenceRounds: Vec<u32> = vec![];
+}
+
+pub struct MockDisabledValidators;
+impl DisabledValidators for MockDisabledValidators {
+	fn is_disabled(index: u32) -> bool {
+		DisabledAuthorities::get().contains(&index)
+	}
+
+	fn disabled_validators() -> Vec<u32> {
+		DisabledAuthorities::get()
+	}
+}
+
+type MockOffence = super::EquivocationOffence<
+	pallet_session::historical::IdentificationTuple<Test>,
+	frame_system::pallet_prelude::BlockNumberFor<Test>,
+>;
+
+/// Records the `offender_already_disabled` flag and the number of rounds of every offence before
+/// handing it to the offences pallet.
+pub struct OffencesRecorder;
+impl ReportOffence<u64, pallet_session::historical::IdentificationTuple<Test>, MockOffence>
+	for OffencesRecorder
+{
+	fn report_offence(reporters: Vec<u64>, offence: MockOffence) -> Result<(), OffenceError> {
+		let offender_already_disabled = offence.offender_already_disabled;
+		let rounds = offence.rounds;
+		Offences::report_offence(reporters, offence)?;
+		ReportedOffenderDisabledFlags::mutate(|flags| flags.push(offender_already_disabled));
+		ReportedOffenceRounds::mutate(|reported| reported.push(rounds));
+		Ok(())
+	}
+
+	fn is_known_offence(
+		offenders: &[pallet_session::historical::IdentificationTuple<Test>],
+		time_slot: &super::TimeSlot<frame_system::pallet_prelude::BlockNumberFor<Test>>,
+	) -> bool {
+		<Offences as ReportOffence<_, _, MockOffence>>::is_known_offence(offenders, time_slot)
+	}
 }
 
 parameter_types! {
@@ -129,11 +189,22 @@ impl pallet_balances::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type ExistentialDeposit = ConstU128<1>;
 	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
 	type WeightInfo = ();
-	type RuntimeHoldReason = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type RuntimeFreezeReason = ();
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
+	type ResidueQuantum = ();
+	type ResidueThreshold = ();
+	type MaxExpiringPerBlock = ();
+	type MinTransferAmount = ();
+	type LockToFreezeMapping = ();
+	type ManagedFreezes = ();
+	type BurnableHolds = ();
+	type LenientFreezeBound = ();
+	type DoneSlashHandler = ();
+	type OnInconsistency = ();
 }
 
 impl pallet_timestamp::Config for Test {
@@ -211,6 +282,7 @@ impl pallet_offences::Config for Test {
 #[derive(Default)]
 pub struct ExtBuilder {
 	authorities: Vec<BeefyId>,
+	initial_set_id: Option<ValidatorSetId>,
 }
 
 impl ExtBuilder {
@@ -221,13 +293,20 @@ impl ExtBuilder {
 		self
 	}
 
+	/// Configure the BEEFY genesis with the authorities and the given initial set id.
+	#[cfg(test)]
+	pub(crate) fn initial_set_id(mut self, id: ValidatorSetId) -> Self {
+		self.initial_set_id = Some(id);
+		self
+	}
+
 	pub fn build(self) -> sp_io::TestExternalities {
 		let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
 
 		let balances: Vec<_> =
 			(0..self.authorities.len()).map(|i| (i as u64, 10_000_000)).collect();
 
-		pallet_balances::GenesisConfig::<Test> { balances }
+		pallet_balances::GenesisConfig::<Test> { balances, ..Default::default() }
 			.assimilate_storage(&mut t)
 			.unwrap();
 
@@ -244,6 +323,16 @@ impl ExtBuilder {
 			}
 		});
 
+		if let Some(initial_set_id) = self.initial_set_id {
+			pallet_beefy::GenesisConfig::<Test> {
+				authorities: self.authorities.clone(),
+				initial_set_id: Some(initial_set_id),
+				..Default::default()
+			}
+			.assimilate_storage(&mut t)
+			.unwrap();
+		}
+
 		pallet_session::GenesisConfig::<Test> { keys: session_keys }
 			.assimilate_storage(&mut t)
 			.unwrap();
@@ -21,17 +21,22 @@ use std::vec;
 use frame_support::{
 	assert_err, assert_ok,
 	dispatch::{GetDispatchInfo, Pays},
-	traits::{Currency, KeyOwnerProofSystem, OnInitialize},
+	traits::{
+		fungible::InspectHold, Currency, KeyOwnerProofSystem, OnInitialize, OneSessionHandler,
+	},
 };
 use sp_consensus_beefy::{
 	check_equivocation_proof,
 	known_payloads::MMR_ROOT_ID,
 	test_utils::{generate_equivocation_proof, Keyring as BeefyKeyring},
-	Payload, ValidatorSet, KEY_TYPE as BEEFY_KEY_TYPE,
+	Commitment, Payload, ValidatorSet, ValidatorSetId, VoteMessage, KEY_TYPE as BEEFY_KEY_TYPE,
 };
-use sp_runtime::DigestItem;
+use sp_runtime::{traits::IsMember, BuildStorage, DigestItem, RuntimeAppPublic};
 
-use crate::{self as beefy, mock::*, Call, Config, Error, Weight, WeightInfo};
+use crate::{
+	self as beefy, mock::*, pre_validate_fork_vote, Call, Config, ConsensusLogKind, Error,
+	ForkVotePreValidationError, OffenceKind, Weight, WeightInfo, MAX_RECENT_CONSENSUS_DIGESTS,
+};
 
 fn init_block(block: u64) {
 	System::set_block_number(block);
@@ -123,6 +128,51 @@ fn session_change_updates_next_authorities() {
 		});
 }
 
+#[test]
+fn authority_membership_across_session_rotation() {
+	ExtBuilder::default()
+		.add_authorities(mock_authorities(vec![1, 2, 3, 4]))
+		.build_and_execute(|| {
+			let is_current = |id| Beefy::is_current_authority(mock_beefy_id(id));
+			let is_next = |id| Beefy::is_next_authority(mock_beefy_id(id));
+			let index = |id| Beefy::authority_index(mock_beefy_id(id));
+
+			init_block(1);
+
+			// 1 and 3 are current only, 2 and 4 are current and next.
+			assert!(is_current(1) && !is_next(1));
+			assert!(is_current(2) && is_next(2));
+			assert!(is_current(3) && !is_next(3));
+			assert_eq!(
+				(index(1), index(2), index(3), index(4)),
+				(Some(0), Some(1), Some(2), Some(3))
+			);
+			// 5 is neither.
+			assert!(!is_current(5) && !is_next(5));
+			assert_eq!(index(5), None);
+
+			// rotate into `[2, 4]`, queueing `[4, 5]`.
+			let accounts = [1u64, 2, 3];
+			let validators =
+				vec![(&accounts[0], mock_beefy_id(2)), (&accounts[1], mock_beefy_id(4))];
+			let queued = vec![(&accounts[1], mock_beefy_id(4)), (&accounts[2], mock_beefy_id(5))];
+			Beefy::on_new_session(true, validators.into_iter(), queued.into_iter());
+
+			// 2 is current only, 4 is both, 5 is next only, 1 and 3 are neither.
+			assert!(is_current(2) && !is_next(2));
+			assert!(is_current(4) && is_next(4));
+			assert!(!is_current(5) && is_next(5));
+			assert!(!is_current(1) && !is_next(1));
+			assert!(!is_current(3) && !is_next(3));
+			assert_eq!((index(2), index(4), index(5)), (Some(0), Some(1), None));
+
+			assert!(Beefy::is_member(&mock_beefy_id(2)));
+			assert!(!beefy::IsNextMember::<Test>::is_member(&mock_beefy_id(2)));
+			assert!(!Beefy::is_member(&mock_beefy_id(5)));
+			assert!(beefy::IsNextMember::<Test>::is_member(&mock_beefy_id(5)));
+		});
+}
+
 #[test]
 fn validator_set_at_genesis() {
 	let want = vec![mock_beefy_id(1), mock_beefy_id(2)];
@@ -205,6 +255,123 @@ fn cleans_up_old_set_id_session_mappings() {
 		});
 }
 
+#[test]
+fn genesis_with_initial_set_id_works() {
+	let authorities = mock_authorities(vec![1, 2, 3, 4]);
+
+	ExtBuilder::default()
+		.add_authorities(authorities.clone())
+		.initial_set_id(42)
+		.build_and_execute(|| {
+			let vs = Beefy::validator_set().unwrap();
+			assert_eq!(vs.id(), 42);
+			assert_eq!(vs.validators(), &authorities[..]);
+
+			// the genesis session is tied to the initial set id only.
+			assert_eq!(beefy::SetIdSession::<Test>::get(42), Some(0));
+			assert_eq!(beefy::SetIdSession::<Test>::get(0), None);
+
+			// The set is announced by the first block, not the genesis state.
+			assert!(System::digest().logs.is_empty());
+			System::set_block_number(1);
+			Beefy::on_initialize(1);
+			let want = beefy_log(ConsensusLog::AuthoritiesChange(vs));
+			assert_eq!(System::digest().logs.first(), Some(&want));
+			assert_eq!(Beefy::recent_digests()[0], (1, ConsensusLogKind::AuthoritiesChange));
+
+			init_block(1);
+
+			let vs = Beefy::validator_set().unwrap();
+			assert_eq!(vs.id(), 43);
+			assert_eq!(beefy::SetIdSession::<Test>::get(43), Some(1));
+		});
+}
+
+#[test]
+fn genesis_deposits_genesis_reset_digest() {
+	let storage = beefy::GenesisConfig::<Test> { genesis_block: Some(5), ..Default::default() }
+		.build_storage()
+		.unwrap();
+
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		// Nothing deposited while building the genesis state reaches a header.
+		assert!(System::digest().logs.is_empty());
+		assert!(Beefy::recent_digests().is_empty());
+
+		System::initialize(&1, &System::parent_hash(), &Default::default());
+		Beefy::on_initialize(1);
+
+		// `GenesisReset` to block 5 while validator set 0 is active.
+		let mut encoded = vec![4];
+		encoded.extend(5u64.to_le_bytes());
+		encoded.extend(0u64.to_le_bytes());
+		assert_eq!(System::digest().logs, vec![DigestItem::Consensus(BEEFY_ENGINE_ID, encoded)]);
+		assert_eq!(Beefy::recent_digests(), vec![(1, ConsensusLogKind::GenesisReset)]);
+
+		// The digest is only deposited by the first block.
+		System::initialize(&2, &System::parent_hash(), &Default::default());
+		Beefy::on_initialize(2);
+		assert!(System::digest().logs.is_empty());
+	});
+}
+
+#[test]
+#[should_panic(expected = "BEEFY `initial_set_id` requires initial authorities")]
+fn genesis_with_initial_set_id_requires_authorities() {
+	let _ = beefy::GenesisConfig::<Test> { initial_set_id: Some(42), ..Default::default() }
+		.build_storage();
+}
+
+#[test]
+fn report_equivocation_with_initial_set_id_works() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default()
+		.add_authorities(authorities)
+		.initial_set_id(42)
+		.build_and_execute(|| {
+			start_era(1);
+
+			let block_num = System::block_number();
+			let validator_set = Beefy::validator_set().unwrap();
+			let set_id = validator_set.id();
+			assert_eq!(set_id, 42 + 3);
+			assert_eq!(beefy::SetIdSession::<Test>::get(set_id), Some(3));
+
+			let equivocation_key = &validator_set.validators()[0];
+			let equivocation_keyring = BeefyKeyring::from_public(equivocation_key).unwrap();
+			let key_owner_proof = Historical::prove((BEEFY_KEY_TYPE, &equivocation_key)).unwrap();
+
+			let payload1 = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
+			let payload2 = Payload::from_single_entry(MMR_ROOT_ID, vec![128]);
+
+			// the set id the session would have had without an initial set id is unknown.
+			let equivocation_proof = generate_equivocation_proof(
+				(block_num, payload1.clone(), 3, &equivocation_keyring),
+				(block_num, payload2.clone(), 3, &equivocation_keyring),
+			);
+			assert_err!(
+				Beefy::report_equivocation_unsigned(
+					RuntimeOrigin::none(),
+					Box::new(equivocation_proof),
+					key_owner_proof.clone(),
+				),
+				Error::<Test>::InvalidEquivocationProof,
+			);
+
+			let equivocation_proof = generate_equivocation_proof(
+				(block_num, payload1, set_id, &equivocation_keyring),
+				(block_num, payload2, set_id, &equivocation_keyring),
+			);
+			assert_ok!(Beefy::report_equivocation_unsigned(
+				RuntimeOrigin::none(),
+				Box::new(equivocation_proof),
+				key_owner_proof,
+			));
+			assert_eq!(Beefy::offence_counts(3).double_voting, 1);
+		});
+}
+
 /// Returns a list with 3 authorities with known keys:
 /// Alice, Bob and Charlie.
 pub fn test_authorities() -> Vec<BeefyId> {
@@ -671,7 +838,7 @@ fn report_equivocation_validate_unsigned_prevents_duplicates() {
 		);
 
 		// the transaction is valid when passed as local
-		let tx_tag = (equivocation_key, set_id, 3u64);
+		let tx_tag = (equivocation_key, set_id);
 
 		assert_eq!(
 			<Beefy as sp_runtime::traits::ValidateUnsigned>::validate_unsigned(
@@ -679,10 +846,13 @@ fn report_equivocation_validate_unsigned_prevents_duplicates() {
 				&call,
 			),
 			TransactionValidity::Ok(ValidTransaction {
-				priority: TransactionPriority::max_value(),
+				// the offence is of the current set, which is the last one to be pruned from the
+				// archived session roots
+				priority: TransactionPriority::max_value() /
+					(MaxSetIdSessionEntries::get() as u64 * beefy::ARCHIVED_SESSION_ROOTS_FACTOR),
 				requires: vec![],
 				provides: vec![("BeefyEquivocation", tx_tag).encode()],
-				longevity: ReportLongevity::get(),
+				longevity: ReportLongevity::get() * beefy::ARCHIVED_SESSION_ROOTS_FACTOR,
 				propagate: false,
 			})
 		);
@@ -716,26 +886,75 @@ fn report_equivocation_validate_unsigned_prevents_duplicates() {
 }
 
 #[test]
-fn report_equivocation_has_valid_weight() {
-	// the weight depends on the size of the validator set,
-	// but there's a lower bound of 100 validators.
-	assert!((1..=100)
-		.map(|validators| <Test as Config>::WeightInfo::report_equivocation(validators, 1000))
-		.collect::<Vec<_>>()
-		.windows(2)
-		.all(|w| w[0] == w[1]));
+fn report_equivocation_validate_unsigned_prioritizes_older_sets() {
+	use sp_runtime::{
+		traits::ValidateUnsigned,
+		transaction_validity::{InvalidTransaction, TransactionSource},
+	};
 
-	// after 100 validators the weight should keep increasing
-	// with every extra validator.
-	assert!((100..=1000)
-		.map(|validators| <Test as Config>::WeightInfo::report_equivocation(validators, 1000))
-		.collect::<Vec<_>>()
-		.windows(2)
-		.all(|w| w[0].ref_time() < w[1].ref_time()));
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		let equivocation_call = || {
+			let block_num = System::block_number();
+			let validator_set = Beefy::validator_set().unwrap();
+			let equivocation_key = &validator_set.validators()[0];
+			let equivocation_keyring = BeefyKeyring::from_public(equivocation_key).unwrap();
+
+			let payload1 = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
+			let payload2 = Payload::from_single_entry(MMR_ROOT_ID, vec![128]);
+			let equivocation_proof = generate_equivocation_proof(
+				(block_num, payload1, validator_set.id(), &equivocation_keyring),
+				(block_num, payload2, validator_set.id(), &equivocation_keyring),
+			);
+			let key_owner_proof = Historical::prove((BEEFY_KEY_TYPE, &equivocation_key)).unwrap();
+
+			Call::report_equivocation_unsigned {
+				equivocation_proof: Box::new(equivocation_proof),
+				key_owner_proof,
+			}
+		};
+
+		start_era(1);
+		let old_set_id = Beefy::validator_set().unwrap().id();
+		let old_session = Session::current_index();
+		let old_call = equivocation_call();
+
+		start_era(2);
+		let new_call = equivocation_call();
+
+		let old = Beefy::validate_unsigned(TransactionSource::Local, &old_call).unwrap();
+		let new = Beefy::validate_unsigned(TransactionSource::Local, &new_call).unwrap();
+
+		// the offence of the older set is closer to being pruned
+		assert!(old.priority > new.priority);
+		assert!(old.longevity < new.longevity);
+
+		// prune the session mapping of the old set, its session root is still archived
+		start_era(2 + MaxSetIdSessionEntries::get() / 3);
+		assert!(beefy::SetIdSession::<Test>::get(old_set_id).is_none());
+		assert!(beefy::ArchivedSessionRoots::<Test>::get(old_session).is_some());
+
+		let archived = Beefy::validate_unsigned(TransactionSource::Local, &old_call).unwrap();
+		let new = Beefy::validate_unsigned(TransactionSource::Local, &new_call).unwrap();
+		assert!(archived.priority > new.priority);
+		assert!(archived.longevity < new.longevity);
+
+		// prune the archived session root of the old set
+		let max_archived_entries =
+			MaxSetIdSessionEntries::get() * beefy::ARCHIVED_SESSION_ROOTS_FACTOR as u32;
+		start_era(2 + max_archived_entries / 3);
+		assert!(beefy::ArchivedSessionRoots::<Test>::get(old_session).is_none());
+
+		assert_err!(
+			Beefy::validate_unsigned(TransactionSource::Local, &old_call),
+			InvalidTransaction::Custom(beefy::INVALID_EQUIVOCATION_PROOF_SESSION),
+		);
+	});
 }
 
 #[test]
-fn valid_equivocation_reports_dont_pay_fees() {
+fn report_equivocation_with_archived_session_root_works() {
 	let authorities = test_authorities();
 
 	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
@@ -743,83 +962,1148 @@ fn valid_equivocation_reports_dont_pay_fees() {
 
 		let block_num = System::block_number();
 		let validator_set = Beefy::validator_set().unwrap();
-		let authorities = validator_set.validators();
-		let set_id = validator_set.id();
-
-		let equivocation_authority_index = 0;
-		let equivocation_key = &authorities[equivocation_authority_index];
+		let old_set_id = validator_set.id();
+		let equivocation_key = &validator_set.validators()[0];
 		let equivocation_keyring = BeefyKeyring::from_public(equivocation_key).unwrap();
 
-		// generate equivocation proof
+		// create the key ownership proof in the "old" set
+		let key_owner_proof = Historical::prove((BEEFY_KEY_TYPE, &equivocation_key)).unwrap();
+		let session = key_owner_proof.session;
+
+		// rotate past the pruning horizon of the set id session mapping and the historical roots
+		start_era(2 + MaxSetIdSessionEntries::get() / 3);
+		assert!(beefy::SetIdSession::<Test>::get(old_set_id).is_none());
+		assert!(Historical::historical_root(session).is_none());
+		assert!(Historical::check_proof(
+			(BEEFY_KEY_TYPE, equivocation_key.clone()),
+			key_owner_proof.clone()
+		)
+		.is_none());
+		assert_eq!(
+			beefy::ArchivedSessionRoots::<Test>::get(session).map(|(set_id, ..)| set_id),
+			Some(old_set_id),
+		);
+
 		let payload1 = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
 		let payload2 = Payload::from_single_entry(MMR_ROOT_ID, vec![128]);
 		let equivocation_proof = generate_equivocation_proof(
-			(block_num, payload1, set_id, &equivocation_keyring),
-			(block_num, payload2, set_id, &equivocation_keyring),
+			(block_num, payload1, old_set_id, &equivocation_keyring),
+			(block_num, payload2, old_set_id, &equivocation_keyring),
 		);
 
-		// create the key ownership proof.
+		// the old but valid proof is checked against the archived session root
+		assert_ok!(Beefy::report_equivocation(
+			RuntimeOrigin::signed(1),
+			Box::new(equivocation_proof),
+			key_owner_proof,
+		));
+
+		// and the offence is still reported
+		assert_eq!(ReportedOffenceRounds::get(), vec![1]);
+		assert_eq!(beefy::OffenceCounts::<Test>::get(session).double_voting, 1);
+	});
+}
+
+#[test]
+fn archived_session_roots_are_pruned() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let block_num = System::block_number();
+		let validator_set = Beefy::validator_set().unwrap();
+		let old_set_id = validator_set.id();
+		let equivocation_key = &validator_set.validators()[0];
+		let equivocation_keyring = BeefyKeyring::from_public(equivocation_key).unwrap();
+
 		let key_owner_proof = Historical::prove((BEEFY_KEY_TYPE, &equivocation_key)).unwrap();
+		let session = key_owner_proof.session;
 
-		// check the dispatch info for the call.
-		let info = Call::<Test>::report_equivocation_unsigned {
-			equivocation_proof: Box::new(equivocation_proof.clone()),
-			key_owner_proof: key_owner_proof.clone(),
-		}
-		.get_dispatch_info();
+		// rotate past the pruning horizon of the archive
+		let max_archived_entries =
+			MaxSetIdSessionEntries::get() * beefy::ARCHIVED_SESSION_ROOTS_FACTOR as u32;
+		start_era(2 + max_archived_entries / 3);
+		assert!(beefy::ArchivedSessionRoots::<Test>::get(session).is_none());
 
-		// it should have non-zero weight and the fee has to be paid.
-		assert!(info.weight.any_gt(Weight::zero()));
-		assert_eq!(info.pays_fee, Pays::Yes);
+		// the archive stays bounded
+		let current_session = Session::current_index();
+		assert!(beefy::ArchivedSessionRoots::<Test>::get(current_session).is_some());
+		assert_eq!(
+			beefy::ArchivedSessionRoots::<Test>::iter().count() as u32,
+			max_archived_entries,
+		);
 
-		// report the equivocation.
-		let post_info = Beefy::report_equivocation_unsigned(
+		let payload1 = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
+		let payload2 = Payload::from_single_entry(MMR_ROOT_ID, vec![128]);
+		let equivocation_proof = generate_equivocation_proof(
+			(block_num, payload1, old_set_id, &equivocation_keyring),
+			(block_num, payload2, old_set_id, &equivocation_keyring),
+		);
+
+		assert_err!(
+			Beefy::report_equivocation(
+				RuntimeOrigin::signed(1),
+				Box::new(equivocation_proof),
+				key_owner_proof,
+			),
+			Error::<Test>::InvalidKeyOwnershipProof,
+		);
+	});
+}
+
+#[test]
+fn unsigned_report_validity_follows_set_id_session_pruning() {
+	use sp_runtime::transaction_validity::TransactionPriority;
+
+	let max_entries = 4;
+	let longevity = 40;
+
+	// the current set is the last one to be pruned
+	assert_eq!(
+		beefy::unsigned_report_validity(8, 8, max_entries, longevity),
+		Some((TransactionPriority::MAX / 4, 40)),
+	);
+	// the oldest retained set gets pruned with the next set change
+	assert_eq!(
+		beefy::unsigned_report_validity(5, 8, max_entries, longevity),
+		Some((TransactionPriority::MAX / 4 * 4, 10)),
+	);
+	// pruned and future sets are rejected
+	assert_eq!(beefy::unsigned_report_validity(4, 8, max_entries, longevity), None);
+	assert_eq!(beefy::unsigned_report_validity(9, 8, max_entries, longevity), None);
+}
+
+#[test]
+fn report_equivocation_of_disabled_authority_is_tagged() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let block_num = System::block_number();
+		let validator_set = Beefy::validator_set().unwrap();
+		let authorities = validator_set.validators();
+		let set_id = validator_set.id();
+
+		let equivocation_authority_index = 1;
+		let equivocation_key = &authorities[equivocation_authority_index];
+		let equivocation_keyring = BeefyKeyring::from_public(equivocation_key).unwrap();
+		let equivocation_proof_at = |round| {
+			let payload1 = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
+			let payload2 = Payload::from_single_entry(MMR_ROOT_ID, vec![128]);
+			generate_equivocation_proof(
+				(round, payload1, set_id, &equivocation_keyring),
+				(round, payload2, set_id, &equivocation_keyring),
+			)
+		};
+		let key_owner_proof = Historical::prove((BEEFY_KEY_TYPE, &equivocation_key)).unwrap();
+
+		// the offender is disabled in the current session
+		DisabledAuthorities::set(vec![equivocation_authority_index as u32]);
+
+		// the report is still processed and the offence is tagged
+		assert_ok!(Beefy::report_equivocation_unsigned(
 			RuntimeOrigin::none(),
-			Box::new(equivocation_proof.clone()),
+			Box::new(equivocation_proof_at(block_num)),
 			key_owner_proof.clone(),
-		)
-		.unwrap();
+		));
+		assert_eq!(ReportedOffenderDisabledFlags::get(), vec![true]);
 
-		// the original weight should be kept, but given that the report
-		// is valid the fee is waived.
-		assert!(post_info.actual_weight.is_none());
-		assert_eq!(post_info.pays_fee, Pays::No);
+		// duplicates are detected while the offender is disabled...
+		assert_err!(
+			Beefy::report_equivocation_unsigned(
+				RuntimeOrigin::none(),
+				Box::new(equivocation_proof_at(block_num)),
+				key_owner_proof.clone(),
+			),
+			Error::<Test>::DuplicateOffenceReport,
+		);
 
-		// report the equivocation again which is invalid now since it is
-		// duplicate.
-		let post_info = Beefy::report_equivocation_unsigned(
+		// ...and after it got enabled again
+		DisabledAuthorities::set(vec![]);
+		assert_err!(
+			Beefy::report_equivocation_unsigned(
+				RuntimeOrigin::none(),
+				Box::new(equivocation_proof_at(block_num)),
+				key_owner_proof.clone(),
+			),
+			Error::<Test>::DuplicateOffenceReport,
+		);
+		assert_eq!(ReportedOffenderDisabledFlags::get(), vec![true]);
+
+		// a new offence of the enabled offender is not tagged
+		assert_ok!(Beefy::report_equivocation_unsigned(
 			RuntimeOrigin::none(),
-			Box::new(equivocation_proof),
+			Box::new(equivocation_proof_at(block_num + 1)),
 			key_owner_proof,
-		)
-		.err()
-		.unwrap()
-		.post_info;
-
-		// the fee is not waived and the original weight is kept.
-		assert!(post_info.actual_weight.is_none());
-		assert_eq!(post_info.pays_fee, Pays::Yes);
-	})
+		));
+		assert_eq!(ReportedOffenderDisabledFlags::get(), vec![true, false]);
+	});
 }
 
 #[test]
-fn set_new_genesis_works() {
+fn report_equivocation_batch_works() {
 	let authorities = test_authorities();
 
 	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
 		start_era(1);
 
-		let new_genesis_delay = 10u64;
-		// the call for setting new genesis should work
-		assert_ok!(Beefy::set_new_genesis(RuntimeOrigin::root(), new_genesis_delay,));
-		let expected = System::block_number() + new_genesis_delay;
-		// verify new genesis was set
-		assert_eq!(beefy::GenesisBlock::<Test>::get(), Some(expected));
+		let block_num = System::block_number();
+		let validator_set = Beefy::validator_set().unwrap();
+		let authorities = validator_set.validators();
+		let set_id = validator_set.id();
+		let validators = Session::validators();
 
-		// setting delay < 1 should fail
+		let equivocation_authority_index = 1;
+		let equivocation_key = &authorities[equivocation_authority_index];
+		let equivocation_keyring = BeefyKeyring::from_public(equivocation_key).unwrap();
+		let equivocation_proof_at = |round| {
+			let payload1 = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
+			let payload2 = Payload::from_single_entry(MMR_ROOT_ID, vec![128]);
+			generate_equivocation_proof(
+				(round, payload1, set_id, &equivocation_keyring),
+				(round, payload2, set_id, &equivocation_keyring),
+			)
+		};
+		let key_owner_proof = Historical::prove((BEEFY_KEY_TYPE, &equivocation_key)).unwrap();
+
+		// a single key ownership proof covers equivocations in three rounds
+		let proofs = vec![
+			equivocation_proof_at(block_num),
+			equivocation_proof_at(block_num + 1),
+			equivocation_proof_at(block_num + 2),
+		];
+		let post_info = Beefy::report_equivocation_batch(
+			RuntimeOrigin::signed(1),
+			proofs.clone().try_into().unwrap(),
+			key_owner_proof.clone(),
+		)
+		.unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+
+		// all of them are reported as a single offence
+		assert_eq!(ReportedOffenceRounds::get(), vec![3]);
+
+		// reporting the same equivocations again is a duplicate
 		assert_err!(
-			Beefy::set_new_genesis(RuntimeOrigin::root(), 0u64,),
-			Error::<Test>::InvalidConfiguration,
-		);
+			Beefy::report_equivocation_batch(
+				RuntimeOrigin::signed(1),
+				vec![equivocation_proof_at(block_num)].try_into().unwrap(),
+				key_owner_proof,
+			),
+			Error::<Test>::DuplicateOffenceReport,
+		);
+
+		start_era(2);
+
+		// the offender got slashed
+		let equivocation_validator_id = validators[equivocation_authority_index];
+		assert_eq!(Balances::total_balance(&equivocation_validator_id), 10_000_000 - 10_000);
+		assert_eq!(Staking::slashable_balance_of(&equivocation_validator_id), 0);
+	});
+}
+
+#[test]
+fn report_equivocation_batch_rejects_mixed_offenders() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let block_num = System::block_number();
+		let validator_set = Beefy::validator_set().unwrap();
+		let authorities = validator_set.validators();
+		let set_id = validator_set.id();
+
+		let equivocation_proof = |round, key| {
+			let keyring = BeefyKeyring::from_public(key).unwrap();
+			let payload1 = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
+			let payload2 = Payload::from_single_entry(MMR_ROOT_ID, vec![128]);
+			generate_equivocation_proof(
+				(round, payload1, set_id, &keyring),
+				(round, payload2, set_id, &keyring),
+			)
+		};
+		let key_owner_proof = Historical::prove((BEEFY_KEY_TYPE, &authorities[1])).unwrap();
+
+		// one of the proofs is for a different offender
+		let proofs = vec![
+			equivocation_proof(block_num, &authorities[1]),
+			equivocation_proof(block_num + 1, &authorities[0]),
+			equivocation_proof(block_num + 2, &authorities[1]),
+		];
+		assert_err!(
+			Beefy::report_equivocation_batch(
+				RuntimeOrigin::signed(1),
+				proofs.try_into().unwrap(),
+				key_owner_proof.clone(),
+			),
+			Error::<Test>::InvalidEquivocationProof,
+		);
+
+		// an empty batch is invalid too
+		assert_err!(
+			Beefy::report_equivocation_batch(
+				RuntimeOrigin::signed(1),
+				Default::default(),
+				key_owner_proof,
+			),
+			Error::<Test>::InvalidEquivocationProof,
+		);
+
+		// the whole batch got rejected
+		assert!(ReportedOffenceRounds::get().is_empty());
+	});
+}
+
+#[test]
+fn report_equivocation_batch_deduplicates_rounds() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let block_num = System::block_number();
+		let validator_set = Beefy::validator_set().unwrap();
+		let authorities = validator_set.validators();
+		let set_id = validator_set.id();
+
+		let equivocation_key = &authorities[0];
+		let equivocation_keyring = BeefyKeyring::from_public(equivocation_key).unwrap();
+		let equivocation_proof_at = |round| {
+			let payload1 = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
+			let payload2 = Payload::from_single_entry(MMR_ROOT_ID, vec![128]);
+			generate_equivocation_proof(
+				(round, payload1, set_id, &equivocation_keyring),
+				(round, payload2, set_id, &equivocation_keyring),
+			)
+		};
+		let key_owner_proof = Historical::prove((BEEFY_KEY_TYPE, &equivocation_key)).unwrap();
+
+		// the first round is already reported on its own
+		assert_ok!(Beefy::report_equivocation_unsigned(
+			RuntimeOrigin::none(),
+			Box::new(equivocation_proof_at(block_num)),
+			key_owner_proof.clone(),
+		));
+		assert_eq!(ReportedOffenceRounds::get(), vec![1]);
+
+		// the batch contains the reported round and another one twice
+		let proofs = vec![
+			equivocation_proof_at(block_num),
+			equivocation_proof_at(block_num + 1),
+			equivocation_proof_at(block_num + 1),
+		];
+		assert_ok!(Beefy::report_equivocation_batch(
+			RuntimeOrigin::signed(1),
+			proofs.try_into().unwrap(),
+			key_owner_proof,
+		));
+
+		// only the new round is punished, and only once
+		assert_eq!(ReportedOffenceRounds::get(), vec![1, 1]);
+	});
+}
+
+#[test]
+fn report_equivocation_batch_rounds_are_not_reported_again() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let block_num = System::block_number();
+		let validator_set = Beefy::validator_set().unwrap();
+		let authorities = validator_set.validators();
+		let set_id = validator_set.id();
+
+		let equivocation_key = &authorities[0];
+		let equivocation_keyring = BeefyKeyring::from_public(equivocation_key).unwrap();
+		let equivocation_proof_at = |round| {
+			let payload1 = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
+			let payload2 = Payload::from_single_entry(MMR_ROOT_ID, vec![128]);
+			generate_equivocation_proof(
+				(round, payload1, set_id, &equivocation_keyring),
+				(round, payload2, set_id, &equivocation_keyring),
+			)
+		};
+		let key_owner_proof = Historical::prove((BEEFY_KEY_TYPE, &equivocation_key)).unwrap();
+
+		// a batch over three rounds is reported as a single offence
+		let proofs = vec![
+			equivocation_proof_at(block_num),
+			equivocation_proof_at(block_num + 1),
+			equivocation_proof_at(block_num + 2),
+		];
+		assert_ok!(Beefy::report_equivocation_batch(
+			RuntimeOrigin::signed(1),
+			proofs.try_into().unwrap(),
+			key_owner_proof.clone(),
+		));
+		assert_eq!(ReportedOffenceRounds::get(), vec![3]);
+
+		// a later report of one of the other rounds is stale
+		let call = Call::report_equivocation_unsigned {
+			equivocation_proof: Box::new(equivocation_proof_at(block_num + 1)),
+			key_owner_proof: key_owner_proof.clone(),
+		};
+		assert_err!(
+			<Beefy as sp_runtime::traits::ValidateUnsigned>::validate_unsigned(
+				TransactionSource::Local,
+				&call,
+			),
+			InvalidTransaction::Stale,
+		);
+		assert_err!(
+			Beefy::report_equivocation_unsigned(
+				RuntimeOrigin::none(),
+				Box::new(equivocation_proof_at(block_num + 1)),
+				key_owner_proof.clone(),
+			),
+			Error::<Test>::DuplicateOffenceReport,
+		);
+
+		// and so is a later batch which only covers reported rounds
+		let proofs = vec![equivocation_proof_at(block_num + 2), equivocation_proof_at(block_num)];
+		assert_err!(
+			Beefy::report_equivocation_batch(
+				RuntimeOrigin::signed(1),
+				proofs.try_into().unwrap(),
+				key_owner_proof,
+			),
+			Error::<Test>::DuplicateOffenceReport,
+		);
+
+		// the validator is only punished for the first batch
+		assert_eq!(ReportedOffenceRounds::get(), vec![3]);
+	});
+}
+
+#[test]
+fn report_equivocation_batch_weight_scales_with_proofs() {
+	let weights = (1..=16)
+		.map(|proofs| <Test as Config>::WeightInfo::report_equivocation_batch(proofs, 100, 1000))
+		.collect::<Vec<_>>();
+
+	// a batch of one costs the same as a single report
+	assert_eq!(weights[0], <Test as Config>::WeightInfo::report_equivocation(100, 1000));
+
+	// every additional proof adds the same weight
+	let step = weights[1] - weights[0];
+	assert!(step.any_gt(Weight::zero()));
+	assert!(weights.windows(2).all(|w| w[1] - w[0] == step));
+}
+
+#[test]
+fn offence_counts_are_tracked_per_session() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let block_num = System::block_number();
+		let validator_set = Beefy::validator_set().unwrap();
+		let authorities = validator_set.validators();
+		let set_id = validator_set.id();
+		let session = Session::current_index();
+
+		let equivocation_proof = |round, authority_index: usize| {
+			let keyring = BeefyKeyring::from_public(&authorities[authority_index]).unwrap();
+			let payload1 = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
+			let payload2 = Payload::from_single_entry(MMR_ROOT_ID, vec![128]);
+			generate_equivocation_proof(
+				(round, payload1, set_id, &keyring),
+				(round, payload2, set_id, &keyring),
+			)
+		};
+		let key_owner_proof = |authority_index: usize| {
+			Historical::prove((BEEFY_KEY_TYPE, &authorities[authority_index])).unwrap()
+		};
+
+		assert_ok!(Beefy::report_equivocation(
+			RuntimeOrigin::signed(1),
+			Box::new(equivocation_proof(block_num, 0)),
+			key_owner_proof(0),
+		));
+		assert_ok!(Beefy::report_equivocation_unsigned(
+			RuntimeOrigin::none(),
+			Box::new(equivocation_proof(block_num, 1)),
+			key_owner_proof(1),
+		));
+		assert_ok!(Beefy::report_equivocation_batch(
+			RuntimeOrigin::signed(1),
+			vec![equivocation_proof(block_num + 1, 0), equivocation_proof(block_num + 2, 0)]
+				.try_into()
+				.unwrap(),
+			key_owner_proof(0),
+		));
+		// failed reports are not counted
+		assert_err!(
+			Beefy::report_equivocation(
+				RuntimeOrigin::signed(1),
+				Box::new(equivocation_proof(block_num, 0)),
+				key_owner_proof(0),
+			),
+			Error::<Test>::DuplicateOffenceReport,
+		);
+
+		let counts = beefy::OffenceCountsEntry { double_voting: 2, double_voting_batch: 1 };
+		assert_eq!(Beefy::offence_counts(session), counts);
+		assert_eq!(
+			Beefy::recent_offence_counts(2),
+			vec![(session - 1, Default::default()), (session, counts.clone())],
+		);
+
+		// the counts are retained as long as the set id to session mapping
+		let max_set_id_session_entries = MaxSetIdSessionEntries::get();
+		start_session(session + max_set_id_session_entries - 1);
+		assert_eq!(Beefy::offence_counts(session), counts);
+		assert_eq!(
+			Beefy::recent_offence_counts(max_set_id_session_entries).first(),
+			Some(&(session, counts)),
+		);
+
+		start_session(session + max_set_id_session_entries);
+		assert!(beefy::SetIdSession::<Test>::get(set_id).is_none());
+		assert!(!beefy::OffenceCounts::<Test>::contains_key(session));
+	});
+}
+
+#[test]
+fn last_offence_and_offence_count_are_recorded_per_kind() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let block_num = System::block_number();
+		let validator_set = Beefy::validator_set().unwrap();
+		let authorities = validator_set.validators();
+		let set_id = validator_set.id();
+		let session = Session::current_index();
+
+		let equivocation_proof = |round, authority_index: usize| {
+			let keyring = BeefyKeyring::from_public(&authorities[authority_index]).unwrap();
+			let payload1 = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
+			let payload2 = Payload::from_single_entry(MMR_ROOT_ID, vec![128]);
+			generate_equivocation_proof(
+				(round, payload1, set_id, &keyring),
+				(round, payload2, set_id, &keyring),
+			)
+		};
+		let key_owner_proof = |authority_index: usize| {
+			Historical::prove((BEEFY_KEY_TYPE, &authorities[authority_index])).unwrap()
+		};
+		let record = |kind| beefy::OffenceRecord {
+			kind,
+			set_id,
+			session,
+			block_number: System::block_number(),
+		};
+
+		assert_eq!(Beefy::last_offence(), None);
+		assert_eq!(Beefy::offence_count(OffenceKind::DoubleVoting), 0);
+		assert_eq!(Beefy::offence_count(OffenceKind::DoubleVotingBatch), 0);
+
+		assert_ok!(Beefy::report_equivocation(
+			RuntimeOrigin::signed(1),
+			Box::new(equivocation_proof(block_num, 0)),
+			key_owner_proof(0),
+		));
+		assert_eq!(Beefy::last_offence(), Some(record(OffenceKind::DoubleVoting)));
+		assert_eq!(Beefy::offence_count(OffenceKind::DoubleVoting), 1);
+
+		assert_ok!(Beefy::report_equivocation_unsigned(
+			RuntimeOrigin::none(),
+			Box::new(equivocation_proof(block_num, 1)),
+			key_owner_proof(1),
+		));
+		assert_eq!(Beefy::last_offence(), Some(record(OffenceKind::DoubleVoting)));
+		assert_eq!(Beefy::offence_count(OffenceKind::DoubleVoting), 2);
+
+		assert_ok!(Beefy::report_equivocation_batch(
+			RuntimeOrigin::signed(1),
+			vec![equivocation_proof(block_num + 1, 0), equivocation_proof(block_num + 2, 0)]
+				.try_into()
+				.unwrap(),
+			key_owner_proof(0),
+		));
+		assert_eq!(Beefy::last_offence(), Some(record(OffenceKind::DoubleVotingBatch)));
+		assert_eq!(Beefy::offence_count(OffenceKind::DoubleVoting), 2);
+		assert_eq!(Beefy::offence_count(OffenceKind::DoubleVotingBatch), 1);
+
+		// failed reports are not recorded
+		assert_err!(
+			Beefy::report_equivocation(
+				RuntimeOrigin::signed(1),
+				Box::new(equivocation_proof(block_num, 0)),
+				key_owner_proof(0),
+			),
+			Error::<Test>::DuplicateOffenceReport,
+		);
+		assert_eq!(Beefy::last_offence(), Some(record(OffenceKind::DoubleVotingBatch)));
+		assert_eq!(Beefy::offence_count(OffenceKind::DoubleVoting), 2);
+
+		// unlike the per session counts, the record and the counters outlive the session
+		start_session(session + MaxSetIdSessionEntries::get());
+		assert_eq!(Beefy::last_offence().map(|record| record.session), Some(session));
+		assert_eq!(Beefy::offence_count(OffenceKind::DoubleVotingBatch), 1);
+	});
+}
+
+#[test]
+fn report_equivocation_has_valid_weight() {
+	// the weight depends on the size of the validator set,
+	// but there's a lower bound of 100 validators.
+	assert!((1..=100)
+		.map(|validators| <Test as Config>::WeightInfo::report_equivocation(validators, 1000))
+		.collect::<Vec<_>>()
+		.windows(2)
+		.all(|w| w[0] == w[1]));
+
+	// after 100 validators the weight should keep increasing
+	// with every extra validator.
+	assert!((100..=1000)
+		.map(|validators| <Test as Config>::WeightInfo::report_equivocation(validators, 1000))
+		.collect::<Vec<_>>()
+		.windows(2)
+		.all(|w| w[0].ref_time() < w[1].ref_time()));
+}
+
+#[test]
+fn valid_equivocation_reports_dont_pay_fees() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let block_num = System::block_number();
+		let validator_set = Beefy::validator_set().unwrap();
+		let authorities = validator_set.validators();
+		let set_id = validator_set.id();
+
+		let equivocation_authority_index = 0;
+		let equivocation_key = &authorities[equivocation_authority_index];
+		let equivocation_keyring = BeefyKeyring::from_public(equivocation_key).unwrap();
+
+		// generate equivocation proof
+		let payload1 = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
+		let payload2 = Payload::from_single_entry(MMR_ROOT_ID, vec![128]);
+		let equivocation_proof = generate_equivocation_proof(
+			(block_num, payload1, set_id, &equivocation_keyring),
+			(block_num, payload2, set_id, &equivocation_keyring),
+		);
+
+		// create the key ownership proof.
+		let key_owner_proof = Historical::prove((BEEFY_KEY_TYPE, &equivocation_key)).unwrap();
+
+		// check the dispatch info for the call.
+		let info = Call::<Test>::report_equivocation_unsigned {
+			equivocation_proof: Box::new(equivocation_proof.clone()),
+			key_owner_proof: key_owner_proof.clone(),
+		}
+		.get_dispatch_info();
+
+		// it should have non-zero weight and the fee has to be paid.
+		assert!(info.weight.any_gt(Weight::zero()));
+		assert_eq!(info.pays_fee, Pays::Yes);
+
+		// report the equivocation.
+		let post_info = Beefy::report_equivocation_unsigned(
+			RuntimeOrigin::none(),
+			Box::new(equivocation_proof.clone()),
+			key_owner_proof.clone(),
+		)
+		.unwrap();
+
+		// the original weight should be kept, but given that the report
+		// is valid the fee is waived.
+		assert!(post_info.actual_weight.is_none());
+		assert_eq!(post_info.pays_fee, Pays::No);
+
+		// report the equivocation again which is invalid now since it is
+		// duplicate.
+		let post_info = Beefy::report_equivocation_unsigned(
+			RuntimeOrigin::none(),
+			Box::new(equivocation_proof),
+			key_owner_proof,
+		)
+		.err()
+		.unwrap()
+		.post_info;
+
+		// the fee is not waived and the original weight is kept.
+		assert!(post_info.actual_weight.is_none());
+		assert_eq!(post_info.pays_fee, Pays::Yes);
+	})
+}
+
+#[test]
+fn set_new_genesis_works() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let new_genesis_delay = 10u64;
+		// the call for setting new genesis should work
+		assert_ok!(Beefy::set_new_genesis(RuntimeOrigin::root(), new_genesis_delay,));
+		let expected = System::block_number() + new_genesis_delay;
+		// verify new genesis was set
+		assert_eq!(beefy::GenesisBlock::<Test>::get(), Some(expected));
+		// and signaled to clients following the headers
+		let want = beefy_log(ConsensusLog::GenesisReset {
+			genesis_block: expected,
+			validator_set_id: beefy::ValidatorSetId::<Test>::get(),
+		});
+		assert_eq!(System::digest().logs.last(), Some(&want));
+		assert_eq!(
+			Beefy::recent_digests().last(),
+			Some(&(System::block_number(), ConsensusLogKind::GenesisReset)),
+		);
+
+		// setting delay < 1 should fail
+		assert_err!(
+			Beefy::set_new_genesis(RuntimeOrigin::root(), 0u64,),
+			Error::<Test>::InvalidConfiguration,
+		);
+	});
+}
+
+fn concurrent_session_proof(
+	keyring: &BeefyKeyring<BeefyId>,
+	set_id: ValidatorSetId,
+	reporter: u64,
+) -> <BeefyId as RuntimeAppPublic>::Signature {
+	keyring.sign(&beefy::concurrent_session_payload(set_id, Session::current_index(), &reporter))
+}
+
+fn concurrent_key_report_deposit(who: u64) -> u128 {
+	let reason = RuntimeHoldReason::Beefy(beefy::HoldReason::ConcurrentKeyReport);
+	Balances::balance_on_hold(&reason, &who)
+}
+
+#[test]
+fn note_concurrent_session_works() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let set_id = beefy::ValidatorSetId::<Test>::get();
+		let key = beefy::Authorities::<Test>::get()[0].clone();
+		let keyring = BeefyKeyring::from_public(&key).unwrap();
+
+		assert_ok!(Beefy::note_concurrent_session(
+			RuntimeOrigin::signed(0),
+			key.clone(),
+			concurrent_session_proof(&keyring, set_id, 0),
+		));
+		System::assert_last_event(
+			beefy::Event::PossibleDuplicateKey { key: key.clone(), reports: 1 }.into(),
+		);
+		assert_ok!(Beefy::note_concurrent_session(
+			RuntimeOrigin::signed(1),
+			key.clone(),
+			concurrent_session_proof(&keyring, set_id, 1),
+		));
+		System::assert_last_event(
+			beefy::Event::PossibleDuplicateKey { key: key.clone(), reports: 2 }.into(),
+		);
+
+		assert_eq!(beefy::ConcurrentKeyReports::<Test>::get(&key).len(), 2);
+		assert_eq!(concurrent_key_report_deposit(0), 1_000);
+		assert_eq!(concurrent_key_report_deposit(1), 1_000);
+
+		// `MaxConcurrentKeyReports` is reached.
+		assert_err!(
+			Beefy::note_concurrent_session(
+				RuntimeOrigin::signed(2),
+				key,
+				concurrent_session_proof(&keyring, set_id, 2),
+			),
+			Error::<Test>::TooManyConcurrentKeyReports,
+		);
+		assert_eq!(concurrent_key_report_deposit(2), 0);
+	});
+}
+
+#[test]
+fn note_concurrent_session_rejects_invalid_reports() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let set_id = beefy::ValidatorSetId::<Test>::get();
+		let key = beefy::Authorities::<Test>::get()[0].clone();
+		let keyring = BeefyKeyring::from_public(&key).unwrap();
+
+		// the proof has to be signed by the reported key, for the current set.
+		let other_keyring = BeefyKeyring::from_public(&beefy::Authorities::<Test>::get()[1]);
+		assert_err!(
+			Beefy::note_concurrent_session(
+				RuntimeOrigin::signed(0),
+				key.clone(),
+				concurrent_session_proof(&other_keyring.unwrap(), set_id, 0),
+			),
+			Error::<Test>::InvalidProofOfPossession,
+		);
+		assert_err!(
+			Beefy::note_concurrent_session(
+				RuntimeOrigin::signed(0),
+				key.clone(),
+				concurrent_session_proof(&keyring, set_id - 1, 0),
+			),
+			Error::<Test>::InvalidProofOfPossession,
+		);
+		// the proof of another reporter cannot be replayed.
+		assert_err!(
+			Beefy::note_concurrent_session(
+				RuntimeOrigin::signed(0),
+				key.clone(),
+				concurrent_session_proof(&keyring, set_id, 1),
+			),
+			Error::<Test>::InvalidProofOfPossession,
+		);
+		// nor can a proof of an earlier session.
+		let payload =
+			beefy::concurrent_session_payload(set_id, Session::current_index() - 1, &0u64);
+		assert_err!(
+			Beefy::note_concurrent_session(
+				RuntimeOrigin::signed(0),
+				key.clone(),
+				keyring.sign(&payload)
+			),
+			Error::<Test>::InvalidProofOfPossession,
+		);
+
+		// only keys of authorities can be reported.
+		let keyring = BeefyKeyring::<BeefyId>::Dave;
+		assert_err!(
+			Beefy::note_concurrent_session(
+				RuntimeOrigin::signed(0),
+				keyring.public(),
+				concurrent_session_proof(&keyring, set_id, 0),
+			),
+			Error::<Test>::NotAnAuthority,
+		);
+
+		assert!(beefy::ConcurrentKeyReports::<Test>::iter().next().is_none());
+		assert_eq!(concurrent_key_report_deposit(0), 0);
+	});
+}
+
+#[test]
+fn note_concurrent_session_rejects_duplicate_report() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let set_id = beefy::ValidatorSetId::<Test>::get();
+		let key = beefy::Authorities::<Test>::get()[0].clone();
+		let keyring = BeefyKeyring::from_public(&key).unwrap();
+		let proof = concurrent_session_proof(&keyring, set_id, 0);
+
+		assert_ok!(Beefy::note_concurrent_session(
+			RuntimeOrigin::signed(0),
+			key.clone(),
+			proof.clone(),
+		));
+		assert_err!(
+			Beefy::note_concurrent_session(RuntimeOrigin::signed(0), key.clone(), proof),
+			Error::<Test>::DuplicateConcurrentKeyReport,
+		);
+
+		assert_eq!(beefy::ConcurrentKeyReports::<Test>::get(&key).len(), 1);
+		assert_eq!(concurrent_key_report_deposit(0), 1_000);
+	});
+}
+
+#[test]
+fn concurrent_key_report_deposit_is_released_on_new_session() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let set_id = beefy::ValidatorSetId::<Test>::get();
+		let key = beefy::Authorities::<Test>::get()[0].clone();
+		let keyring = BeefyKeyring::from_public(&key).unwrap();
+		let proof = concurrent_session_proof(&keyring, set_id, 0);
+
+		assert_ok!(Beefy::note_concurrent_session(
+			RuntimeOrigin::signed(0),
+			key.clone(),
+			proof.clone(),
+		));
+		assert_eq!(concurrent_key_report_deposit(0), 1_000);
+
+		start_session(Session::current_index() + 1);
+
+		assert!(beefy::ConcurrentKeyReports::<Test>::get(&key).is_empty());
+		assert_eq!(concurrent_key_report_deposit(0), 0);
+		assert_eq!(Balances::free_balance(0), 10_000_000);
+
+		// the key can be reported again in the new session.
+		let set_id = beefy::ValidatorSetId::<Test>::get();
+		let proof = concurrent_session_proof(&keyring, set_id, 0);
+		assert_ok!(Beefy::note_concurrent_session(RuntimeOrigin::signed(0), key, proof));
+		assert_eq!(concurrent_key_report_deposit(0), 1_000);
+	});
+}
+
+fn fork_vote(
+	block_number: u64,
+	set_id: ValidatorSetId,
+	keyring: &BeefyKeyring<BeefyId>,
+) -> VoteMessage<u64, BeefyId, <BeefyId as RuntimeAppPublic>::Signature> {
+	let payload = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
+	let commitment = Commitment { validator_set_id: set_id, block_number, payload };
+	let signature = keyring.sign(&commitment.encode());
+	VoteMessage { commitment, id: keyring.public(), signature }
+}
+
+#[test]
+fn fork_voting_context_works() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let context = Beefy::fork_voting_context();
+		assert_eq!(context.current_set_id, beefy::ValidatorSetId::<Test>::get());
+		assert_eq!(context.current_authorities, beefy::Authorities::<Test>::get().into_inner());
+		assert_eq!(context.genesis_block, Some(1));
+
+		let keyring = BeefyKeyring::from_public(&context.current_authorities[0]).unwrap();
+		let vote = fork_vote(5, context.current_set_id, &keyring);
+		assert_ok!(pre_validate_fork_vote(&vote, &context));
+	});
+}
+
+#[test]
+fn pre_validate_fork_vote_rejects_wrong_set_id() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let context = Beefy::fork_voting_context();
+		let keyring = BeefyKeyring::from_public(&context.current_authorities[0]).unwrap();
+		let vote = fork_vote(5, context.current_set_id + 1, &keyring);
+		assert_err!(
+			pre_validate_fork_vote(&vote, &context),
+			ForkVotePreValidationError::WrongSetId,
+		);
+	});
+}
+
+#[test]
+fn pre_validate_fork_vote_rejects_unknown_voter() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let context = Beefy::fork_voting_context();
+		let vote = fork_vote(5, context.current_set_id, &BeefyKeyring::Dave);
+		assert_err!(
+			pre_validate_fork_vote(&vote, &context),
+			ForkVotePreValidationError::NotAnAuthority,
+		);
+	});
+}
+
+#[test]
+fn pre_validate_fork_vote_rejects_vote_before_genesis() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let mut context = Beefy::fork_voting_context();
+		let keyring = BeefyKeyring::from_public(&context.current_authorities[0]).unwrap();
+		let vote = fork_vote(5, context.current_set_id, &keyring);
+
+		context.genesis_block = Some(6);
+		assert_err!(
+			pre_validate_fork_vote(&vote, &context),
+			ForkVotePreValidationError::BeforeGenesis,
+		);
+		context.genesis_block = Some(5);
+		assert_ok!(pre_validate_fork_vote(&vote, &context));
+
+		// no vote is valid before BEEFY started.
+		beefy::GenesisBlock::<Test>::put(None::<u64>);
+		assert_err!(
+			pre_validate_fork_vote(&vote, &Beefy::fork_voting_context()),
+			ForkVotePreValidationError::BeforeGenesis,
+		);
+	});
+}
+
+#[test]
+fn pre_validate_fork_vote_rejects_invalid_signature() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let context = Beefy::fork_voting_context();
+		let keyring = BeefyKeyring::from_public(&context.current_authorities[0]).unwrap();
+		let mut vote = fork_vote(5, context.current_set_id, &keyring);
+		vote.commitment.block_number = 6;
+		assert_err!(
+			pre_validate_fork_vote(&vote, &context),
+			ForkVotePreValidationError::InvalidSignature,
+		);
+
+		// a vote signed by another authority.
+		let other = BeefyKeyring::from_public(&context.current_authorities[1]).unwrap();
+		let mut vote = fork_vote(5, context.current_set_id, &keyring);
+		vote.signature = fork_vote(5, context.current_set_id, &other).signature;
+		assert_err!(
+			pre_validate_fork_vote(&vote, &context),
+			ForkVotePreValidationError::InvalidSignature,
+		);
+	});
+}
+
+#[test]
+fn equivocating_authority_is_disabled_for_the_rest_of_the_session() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+		DisableEquivocators::set(true);
+
+		let block_num = System::block_number();
+		let validator_set = Beefy::validator_set().unwrap();
+		let authorities = validator_set.validators();
+		let set_id = validator_set.id();
+
+		let equivocation_authority_index = 1;
+		let equivocation_key = &authorities[equivocation_authority_index];
+		let equivocation_keyring = BeefyKeyring::from_public(equivocation_key).unwrap();
+		let equivocation_proof_at = |round| {
+			let payload1 = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
+			let payload2 = Payload::from_single_entry(MMR_ROOT_ID, vec![128]);
+			generate_equivocation_proof(
+				(round, payload1, set_id, &equivocation_keyring),
+				(round, payload2, set_id, &equivocation_keyring),
+			)
+		};
+		let key_owner_proof = Historical::prove((BEEFY_KEY_TYPE, &equivocation_key)).unwrap();
+		let disabled_log = beefy_log(ConsensusLog::OnDisabled(equivocation_authority_index as u32));
+		let disabled_logs =
+			|| System::digest().logs.iter().filter(|log| **log == disabled_log).count();
+
+		assert_ok!(Beefy::report_equivocation_unsigned(
+			RuntimeOrigin::none(),
+			Box::new(equivocation_proof_at(block_num)),
+			key_owner_proof.clone(),
+		));
+		assert_eq!(disabled_logs(), 1);
+		assert_eq!(beefy::DisabledThisSession::<Test>::get().into_inner(), vec![1]);
+		assert_eq!(ReportedOffenderDisabledFlags::get(), vec![false]);
+
+		// further offences are still reported, tagged as those of a disabled offender, but the
+		// authority is not disabled again.
+		assert_ok!(Beefy::report_equivocation_unsigned(
+			RuntimeOrigin::none(),
+			Box::new(equivocation_proof_at(block_num + 1)),
+			key_owner_proof,
+		));
+		assert_eq!(disabled_logs(), 1);
+		assert_eq!(beefy::DisabledThisSession::<Test>::get().into_inner(), vec![1]);
+		assert_eq!(ReportedOffenderDisabledFlags::get(), vec![false, true]);
+
+		// the disabling ends with the session.
+		start_session(Session::current_index() + 1);
+		assert!(beefy::DisabledThisSession::<Test>::get().is_empty());
+		assert_eq!(disabled_logs(), 0);
+	});
+}
+
+#[test]
+fn equivocating_authority_is_not_disabled_by_default() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_era(1);
+
+		let block_num = System::block_number();
+		let validator_set = Beefy::validator_set().unwrap();
+		let authorities = validator_set.validators();
+		let set_id = validator_set.id();
+
+		let equivocation_keyring = BeefyKeyring::from_public(&authorities[1]).unwrap();
+		let payload1 = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
+		let payload2 = Payload::from_single_entry(MMR_ROOT_ID, vec![128]);
+		let equivocation_proof = generate_equivocation_proof(
+			(block_num, payload1, set_id, &equivocation_keyring),
+			(block_num, payload2, set_id, &equivocation_keyring),
+		);
+		let key_owner_proof = Historical::prove((BEEFY_KEY_TYPE, &authorities[1])).unwrap();
+
+		assert_ok!(Beefy::report_equivocation_unsigned(
+			RuntimeOrigin::none(),
+			Box::new(equivocation_proof),
+			key_owner_proof,
+		));
+		assert!(beefy::DisabledThisSession::<Test>::get().is_empty());
+		assert!(!System::digest().logs.contains(&beefy_log(ConsensusLog::OnDisabled(1))));
+	});
+}
+
+#[test]
+fn recent_consensus_digests_are_recorded() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		assert!(Beefy::recent_digests().is_empty());
+
+		start_session(1);
+		let first = System::block_number();
+		start_session(2);
+		let second = System::block_number();
+		start_session(3);
+		let third = System::block_number();
+		Beefy::on_disabled(1);
+
+		assert_eq!(
+			Beefy::recent_digests(),
+			vec![
+				(first, ConsensusLogKind::AuthoritiesChange),
+				(second, ConsensusLogKind::AuthoritiesChange),
+				(third, ConsensusLogKind::AuthoritiesChange),
+				(third, ConsensusLogKind::OnDisabled),
+			]
+		);
+	});
+}
+
+#[test]
+fn recent_consensus_digests_evict_the_oldest_entry() {
+	let authorities = test_authorities();
+
+	ExtBuilder::default().add_authorities(authorities).build_and_execute(|| {
+		start_session(1);
+		let oldest = System::block_number();
+		start_session(MAX_RECENT_CONSENSUS_DIGESTS);
+		let digests = Beefy::recent_digests();
+		assert_eq!(digests.len() as u32, MAX_RECENT_CONSENSUS_DIGESTS);
+		assert_eq!(digests[0], (oldest, ConsensusLogKind::AuthoritiesChange));
+
+		// one more digest evicts the oldest entry.
+		Beefy::on_disabled(0);
+		let digests = Beefy::recent_digests();
+		assert_eq!(digests.len() as u32, MAX_RECENT_CONSENSUS_DIGESTS);
+		assert!(digests[0].0 > oldest);
+		assert_eq!(digests.last(), Some(&(System::block_number(), ConsensusLogKind::OnDisabled)));
+		assert!(digests.windows(2).all(|pair| pair[0].0 <= pair[1].0));
 	});
 }
@@ -20,7 +20,9 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use super::*;
-use frame_benchmarking::v1::{account, benchmarks};
+use frame_benchmarking::v1::{
+	account, benchmarks, whitelisted_caller, BenchmarkError, BenchmarkResult,
+};
 use frame_system::RawOrigin;
 use sp_runtime::traits::Bounded;
 
@@ -37,7 +39,7 @@ fn setup_multi<T: Config>(
 		let signatory = account("signatory", i, SEED);
 		// Give them some balance for a possible deposit
 		let balance = BalanceOf::<T>::max_value();
-		T::Currency::make_free_balance_be(&signatory, balance);
+		T::Currency::set_balance(&signatory, balance);
 		signatories.push(signatory);
 	}
 	signatories.sort();
@@ -47,7 +49,31 @@ fn setup_multi<T: Config>(
 	Ok((signatories, Box::new(call)))
 }
 
+/// The largest metadata, which is charged the most for storage and the mismatch check.
+fn max_metadata() -> Metadata {
+	vec![0; MAX_METADATA_LEN as usize]
+		.try_into()
+		.expect("has the maximum length; qed")
+}
+
+/// A note commitment, stored with an approval.
+fn note() -> H256 {
+	H256::repeat_byte(1)
+}
+
+/// A call filter with the maximum number of entries, none of which allows calls of the pallet
+/// `excluded`, so that checking a call of it visits all of them.
+fn full_call_filter(excluded: PalletIndex) -> CallFilter {
+	(1..=MAX_CALL_FILTER_ENTRIES as u8)
+		.map(|i| (excluded.wrapping_add(i), Some(0)))
+		.collect::<Vec<_>>()
+		.try_into()
+		.expect("has the maximum length; qed")
+}
+
 benchmarks! {
+	where_clause { where <T as Config>::RuntimeCall: From<Call<T>> }
+
 	as_multi_threshold_1 {
 		// Transaction Length
 		let z in 0 .. 10_000;
@@ -79,11 +105,35 @@ benchmarks! {
 		// Whitelist caller account from further DB operations.
 		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
 		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
-	}: as_multi(RawOrigin::Signed(caller), s as u16, signatories, None, call, Weight::zero())
+	}: as_multi(RawOrigin::Signed(caller), s as u16, signatories, None, call, Weight::zero(), Some(max_metadata()), Some(note()))
 	verify {
 		assert!(Multisigs::<T>::contains_key(multi_account_id, call_hash));
 	}
 
+	as_multi_create_exempt {
+		// Signatories, need at least 2 total people
+		let s in 2 .. T::MaxSignatories::get();
+		// Transaction Length
+		let z in 0 .. 10_000;
+		let (mut signatories, call) = setup_multi::<T>(s, z)?;
+		if !T::DepositExempt::contains(&call) {
+			// The runtime exempts no remark, so only the regular path can be taken.
+			return Err(BenchmarkError::Override(BenchmarkResult::from_weight(
+				T::WeightInfo::as_multi_create(s, z),
+			)))
+		}
+		let call_hash = call.using_encoded(blake2_256);
+		let multi_account_id = Multisig::<T>::multi_account_id(&signatories, s.try_into().unwrap());
+		let caller = signatories.pop().ok_or("signatories should have len 2 or more")?;
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
+	}: as_multi(RawOrigin::Signed(caller), s as u16, signatories, None, call, Weight::zero(), Some(max_metadata()), Some(note()))
+	verify {
+		let multisig = Multisigs::<T>::get(multi_account_id, call_hash).ok_or("multisig not created")?;
+		assert!(multisig.deposit.is_zero());
+	}
+
 	as_multi_approve {
 		// Signatories, need at least 3 people (so we don't complete the multisig)
 		let s in 3 .. T::MaxSignatories::get();
@@ -97,15 +147,16 @@ benchmarks! {
 		// before the call, get the timepoint
 		let timepoint = Multisig::<T>::timepoint();
 		// Create the multi
-		Multisig::<T>::as_multi(RawOrigin::Signed(caller).into(), s as u16, signatories, None, call.clone(), Weight::zero())?;
+		Multisig::<T>::as_multi(RawOrigin::Signed(caller).into(), s as u16, signatories, None, call.clone(), Weight::zero(), Some(max_metadata()), Some(note()))?;
 		let caller2 = signatories2.remove(0);
 		// Whitelist caller account from further DB operations.
 		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller2);
 		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
-	}: as_multi(RawOrigin::Signed(caller2), s as u16, signatories2, Some(timepoint), call, Weight::zero())
+	}: as_multi(RawOrigin::Signed(caller2), s as u16, signatories2, Some(timepoint), call, Weight::zero(), Some(max_metadata()), Some(note()))
 	verify {
 		let multisig = Multisigs::<T>::get(multi_account_id, call_hash).ok_or("multisig not created")?;
 		assert_eq!(multisig.approvals.len(), 2);
+		assert!(multisig.approvals.iter().all(|(_, _, maybe_note)| *maybe_note == Some(note())));
 	}
 
 	as_multi_complete {
@@ -121,24 +172,76 @@ benchmarks! {
 		// before the call, get the timepoint
 		let timepoint = Multisig::<T>::timepoint();
 		// Create the multi
-		Multisig::<T>::as_multi(RawOrigin::Signed(caller).into(), s as u16, signatories, None, call.clone(), Weight::zero())?;
+		Multisig::<T>::as_multi(RawOrigin::Signed(caller).into(), s as u16, signatories, None, call.clone(), Weight::zero(), Some(max_metadata()), Some(note()))?;
 		// Everyone except the first person approves
 		for i in 1 .. s - 1 {
 			let mut signatories_loop = signatories2.clone();
 			let caller_loop = signatories_loop.remove(i as usize);
 			let o = RawOrigin::Signed(caller_loop).into();
-			Multisig::<T>::as_multi(o, s as u16, signatories_loop, Some(timepoint), call.clone(), Weight::zero())?;
+			Multisig::<T>::as_multi(o, s as u16, signatories_loop, Some(timepoint), call.clone(), Weight::zero(), Some(max_metadata()), Some(note()))?;
 		}
 		let caller2 = signatories2.remove(0);
 		assert!(Multisigs::<T>::contains_key(&multi_account_id, call_hash));
 		// Whitelist caller account from further DB operations.
 		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller2);
 		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
-	}: as_multi(RawOrigin::Signed(caller2), s as u16, signatories2, Some(timepoint), call, Weight::MAX)
+	}: as_multi(RawOrigin::Signed(caller2), s as u16, signatories2, Some(timepoint), call, Weight::MAX, Some(max_metadata()), Some(note()))
 	verify {
 		assert!(!Multisigs::<T>::contains_key(&multi_account_id, call_hash));
 	}
 
+	as_multi_complete_nested {
+		// Number of multisigs nested above the multisig being completed.
+		let d in 1 .. RECOMMENDED_MAX_NESTING_DEPTH;
+		let (signatories, mut call) = setup_multi::<T>(2, 10_000)?;
+		let timepoint = Multisig::<T>::timepoint();
+		// Each level is a 2-of-2 multisig of the multisig below it and another account.
+		let mut levels = Vec::new();
+		let base_id = Multisig::<T>::multi_account_id(&signatories, 2);
+		let mut multi_account_id = base_id.clone();
+		for i in 0 .. d {
+			let other: T::AccountId = account("nested", i, SEED);
+			T::Currency::set_balance(&other, BalanceOf::<T>::max_value());
+			let mut nested_signatories = vec![multi_account_id.clone(), other.clone()];
+			nested_signatories.sort();
+			let nested_id = Multisig::<T>::multi_account_id(&nested_signatories, 2);
+			levels.push((multi_account_id, nested_id.clone(), other));
+			multi_account_id = nested_id;
+		}
+		// Starting from the top, the other account creates the operation of each level, which
+		// the multisig below approves by calling `as_multi`.
+		let mut nested_ids = Vec::new();
+		for (approver, nested_id, other) in levels.into_iter().rev() {
+			let o = RawOrigin::Signed(other.clone()).into();
+			Multisig::<T>::as_multi(o, 2, vec![approver], None, call.clone(), Weight::zero(), None, None)?;
+			nested_ids.push((nested_id, call.using_encoded(blake2_256)));
+			let max_weight = call.get_dispatch_info().weight;
+			call = Box::new(Call::<T>::as_multi {
+				threshold: 2,
+				other_signatories: vec![other],
+				maybe_timepoint: Some(timepoint),
+				call,
+				max_weight,
+				metadata: None,
+				note: None,
+			}.into());
+		}
+		let (caller, caller2) = (signatories[0].clone(), signatories[1].clone());
+		let call_hash = call.using_encoded(blake2_256);
+		let o = RawOrigin::Signed(caller.clone()).into();
+		Multisig::<T>::as_multi(o, 2, vec![caller2.clone()], None, call.clone(), Weight::zero(), None, None)?;
+		let max_weight = call.get_dispatch_info().weight;
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller2);
+		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
+	}: as_multi(RawOrigin::Signed(caller2), 2, vec![caller], Some(timepoint), call, max_weight, None, None)
+	verify {
+		assert!(!Multisigs::<T>::contains_key(&base_id, call_hash));
+		for (nested_id, nested_call_hash) in nested_ids {
+			assert!(!Multisigs::<T>::contains_key(&nested_id, nested_call_hash));
+		}
+	}
+
 	approve_as_multi_create {
 		// Signatories, need at least 2 people
 		let s in 2 .. T::MaxSignatories::get();
@@ -152,7 +255,7 @@ benchmarks! {
 		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
 		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
 		// Create the multi
-	}: approve_as_multi(RawOrigin::Signed(caller), s as u16, signatories, None, call_hash, Weight::zero())
+	}: approve_as_multi(RawOrigin::Signed(caller), s as u16, signatories, None, call_hash, Weight::zero(), Some(max_metadata()), Some(note()))
 	verify {
 		assert!(Multisigs::<T>::contains_key(multi_account_id, call_hash));
 	}
@@ -176,13 +279,47 @@ benchmarks! {
 			signatories,
 			None,
 			call,
-			Weight::zero()
+			Weight::zero(),
+			Some(max_metadata()),
+			Some(note()),
+		)?;
+		let caller2 = signatories2.remove(0);
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller2);
+		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
+	}: approve_as_multi(RawOrigin::Signed(caller2), s as u16, signatories2, Some(timepoint), call_hash, Weight::zero(), Some(max_metadata()), Some(note()))
+	verify {
+		let multisig = Multisigs::<T>::get(multi_account_id, call_hash).ok_or("multisig not created")?;
+		assert_eq!(multisig.approvals.len(), 2);
+		assert!(multisig.approvals.iter().all(|(_, _, maybe_note)| *maybe_note == Some(note())));
+	}
+
+	approve_as_multi_latest {
+		// Signatories, need at least 2 people
+		let s in 2 .. T::MaxSignatories::get();
+		// Transaction Length, not a component
+		let z = 10_000;
+		let (mut signatories, call) = setup_multi::<T>(s, z)?;
+		let mut signatories2 = signatories.clone();
+		let multi_account_id = Multisig::<T>::multi_account_id(&signatories, s.try_into().unwrap());
+		let caller = signatories.pop().ok_or("signatories should have len 2 or more")?;
+		let call_hash = call.using_encoded(blake2_256);
+		// Create the multi
+		Multisig::<T>::as_multi(
+			RawOrigin::Signed(caller).into(),
+			s as u16,
+			signatories,
+			None,
+			call,
+			Weight::zero(),
+			Some(max_metadata()),
+			Some(note()),
 		)?;
 		let caller2 = signatories2.remove(0);
 		// Whitelist caller account from further DB operations.
 		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller2);
 		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
-	}: approve_as_multi(RawOrigin::Signed(caller2), s as u16, signatories2, Some(timepoint), call_hash, Weight::zero())
+	}: _(RawOrigin::Signed(caller2), s as u16, signatories2, call_hash, Weight::zero())
 	verify {
 		let multisig = Multisigs::<T>::get(multi_account_id, call_hash).ok_or("multisig not created")?;
 		assert_eq!(multisig.approvals.len(), 2);
@@ -200,7 +337,7 @@ benchmarks! {
 		let timepoint = Multisig::<T>::timepoint();
 		// Create the multi
 		let o = RawOrigin::Signed(caller.clone()).into();
-		Multisig::<T>::as_multi(o, s as u16, signatories.clone(), None, call, Weight::zero())?;
+		Multisig::<T>::as_multi(o, s as u16, signatories.clone(), None, call, Weight::zero(), Some(max_metadata()), Some(note()))?;
 		assert!(Multisigs::<T>::contains_key(&multi_account_id, call_hash));
 		// Whitelist caller account from further DB operations.
 		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
@@ -210,5 +347,171 @@ benchmarks! {
 		assert!(!Multisigs::<T>::contains_key(multi_account_id, call_hash));
 	}
 
+	cancel_as_multi_penalized {
+		// Signatories, need at least 2 people
+		let s in 2 .. T::MaxSignatories::get();
+		// Transaction Length, not a component
+		let z = 10_000;
+		let (mut signatories, call) = setup_multi::<T>(s, z)?;
+		let all_signatories = signatories.clone();
+		let multi_account_id = Multisig::<T>::multi_account_id(&signatories, s.try_into().unwrap());
+		let caller = signatories.pop().ok_or("signatories should have len 2 or more")?;
+		let call_hash = call.using_encoded(blake2_256);
+		let timepoint = Multisig::<T>::timepoint();
+		// Create the multi
+		let o = RawOrigin::Signed(caller.clone()).into();
+		Multisig::<T>::as_multi(o, s as u16, signatories.clone(), None, call, Weight::zero(), Some(max_metadata()), Some(note()))?;
+		// Approve it by more than half of the signatories, so that cancelling it is penalized.
+		for approver in all_signatories.iter().take((s / 2) as usize) {
+			let others = all_signatories.iter().filter(|a| *a != approver).cloned().collect();
+			let o = RawOrigin::Signed(approver.clone()).into();
+			Multisig::<T>::approve_as_multi(o, s as u16, others, Some(timepoint), call_hash, Weight::zero(), Some(max_metadata()), Some(note()))?;
+		}
+		let multisig = Multisigs::<T>::get(&multi_account_id, call_hash).ok_or("multisig not created")?;
+		assert!(multisig.approvals.len() * 2 > s as usize);
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
+	}: cancel_as_multi(RawOrigin::Signed(caller), s as u16, signatories, timepoint, call_hash)
+	verify {
+		assert!(!Multisigs::<T>::contains_key(multi_account_id, call_hash));
+	}
+
+	pause_operation {
+		let s = T::MaxSignatories::get();
+		let (mut signatories, call) = setup_multi::<T>(s, 10_000)?;
+		let multi_account_id = Multisig::<T>::multi_account_id(&signatories, s.try_into().unwrap());
+		let caller = signatories.pop().ok_or("signatories should have len 2 or more")?;
+		let call_hash = call.using_encoded(blake2_256);
+		let o = RawOrigin::Signed(caller).into();
+		Multisig::<T>::as_multi(o, s as u16, signatories, None, call, Weight::zero(), Some(max_metadata()), Some(note()))?;
+		let origin =
+			T::GuardianOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, multi_account_id.clone(), call_hash)
+	verify {
+		let multisig = Multisigs::<T>::get(multi_account_id, call_hash).ok_or("multisig not created")?;
+		assert!(multisig.paused_until.is_some());
+	}
+
+	unpause_operation {
+		let s = T::MaxSignatories::get();
+		let (mut signatories, call) = setup_multi::<T>(s, 10_000)?;
+		let multi_account_id = Multisig::<T>::multi_account_id(&signatories, s.try_into().unwrap());
+		let caller = signatories.pop().ok_or("signatories should have len 2 or more")?;
+		let call_hash = call.using_encoded(blake2_256);
+		let o = RawOrigin::Signed(caller).into();
+		Multisig::<T>::as_multi(o, s as u16, signatories, None, call, Weight::zero(), Some(max_metadata()), Some(note()))?;
+		let origin =
+			T::GuardianOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		Multisig::<T>::pause_operation(origin.clone(), multi_account_id.clone(), call_hash)?;
+	}: _<T::RuntimeOrigin>(origin, multi_account_id.clone(), call_hash)
+	verify {
+		let multisig = Multisigs::<T>::get(multi_account_id, call_hash).ok_or("multisig not created")?;
+		assert!(multisig.paused_until.is_none());
+	}
+
+	as_multi_announce {
+		// Signatories, need at least 2 total people
+		let s in 2 .. T::MaxSignatories::get();
+		let (mut signatories, call) = setup_multi::<T>(s, 10_000)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let multi_account_id = Multisig::<T>::multi_account_id(&signatories, 1);
+		let caller = signatories.pop().ok_or("signatories should have len 2 or more")?;
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
+	}: as_multi_announced(RawOrigin::Signed(caller), 1, signatories, None, call_hash, 10u32.into())
+	verify {
+		let multisig = Multisigs::<T>::get(multi_account_id, call_hash).ok_or("multisig not created")?;
+		assert!(multisig.execute_after.is_some());
+	}
+
+	execute_announced {
+		// Transaction Length
+		let z in 0 .. 10_000;
+		let s = T::MaxSignatories::get();
+		let (mut signatories, call) = setup_multi::<T>(s, z)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let multi_account_id = Multisig::<T>::multi_account_id(&signatories, 1);
+		let caller = signatories.pop().ok_or("signatories should have len 2 or more")?;
+		let o = RawOrigin::Signed(caller.clone()).into();
+		Multisig::<T>::as_multi_announced(o, 1, signatories, None, call_hash, 10u32.into())?;
+		frame_system::Pallet::<T>::set_block_number(
+			frame_system::Pallet::<T>::block_number() + 10u32.into(),
+		);
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
+	}: _(RawOrigin::Signed(caller), multi_account_id.clone(), call_hash, call)
+	verify {
+		assert!(!Multisigs::<T>::contains_key(multi_account_id, call_hash));
+	}
+
+	cancel_announcement {
+		// Signatories, need at least 2 people
+		let s in 2 .. T::MaxSignatories::get();
+		let (mut signatories, call) = setup_multi::<T>(s, 10_000)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let multi_account_id = Multisig::<T>::multi_account_id(&signatories, 1);
+		let mut signatories2 = signatories.clone();
+		let caller = signatories.pop().ok_or("signatories should have len 2 or more")?;
+		let o = RawOrigin::Signed(caller).into();
+		Multisig::<T>::as_multi_announced(o, 1, signatories, None, call_hash, 10u32.into())?;
+		// Cancel as another signatory than the depositor.
+		let caller2 = signatories2.remove(0);
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller2);
+		frame_benchmarking::benchmarking::add_to_whitelist(caller_key.into());
+	}: _(RawOrigin::Signed(caller2), 1, signatories2, call_hash)
+	verify {
+		assert!(!Multisigs::<T>::contains_key(multi_account_id, call_hash));
+	}
+
+	migrate_deposit {
+		let s = T::MaxSignatories::get();
+		let (mut signatories, call) = setup_multi::<T>(s, 10_000)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let multi_account_id = Multisig::<T>::multi_account_id(&signatories, s as u16);
+		let depositor = signatories.pop().ok_or("signatories should have len 2 or more")?;
+		let o = RawOrigin::Signed(depositor).into();
+		Multisig::<T>::approve_as_multi(o, s as u16, signatories, None, call_hash, Weight::zero(), None, None)?;
+		// Turn the deposit back into a reserve, as taken by a former version of the pallet.
+		Multisig::<T>::unmigrate_deposit(&multi_account_id, call_hash)?;
+		let caller = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), multi_account_id.clone(), call_hash)
+	verify {
+		let multisig = Multisigs::<T>::get(multi_account_id, call_hash).ok_or("multisig not found")?;
+		assert!(multisig.deposit_held);
+	}
+
+	set_call_filter {
+		let caller: T::AccountId = whitelisted_caller();
+		let filter = full_call_filter(0);
+	}: _(RawOrigin::Signed(caller.clone()), filter.clone())
+	verify {
+		assert_eq!(AccountCallFilter::<T>::get(caller), Some(filter));
+	}
+
+	clear_call_filter {
+		let caller: T::AccountId = whitelisted_caller();
+		AccountCallFilter::<T>::insert(&caller, full_call_filter(0));
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!AccountCallFilter::<T>::contains_key(caller));
+	}
+
+	check_call_filter {
+		let (signatories, call) = setup_multi::<T>(2, 10_000)?;
+		let multi_account_id = Multisig::<T>::multi_account_id(&signatories, 2);
+		let pallet = call.using_encoded(|c| c[0]);
+		AccountCallFilter::<T>::insert(&multi_account_id, full_call_filter(pallet));
+		let mut allowed = true;
+	}: {
+		allowed = Multisig::<T>::call_allowed(&multi_account_id, &call);
+	}
+	verify {
+		assert!(!allowed);
+	}
+
 	impl_benchmark_test_suite!(Multisig, crate::tests::new_test_ext(), crate::tests::Test);
 }
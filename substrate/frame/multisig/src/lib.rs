@@ -38,7 +38,66 @@
 //! * `as_multi` - Approve and if possible dispatch a call from a composite origin formed from a
 //!   number of signed origins.
 //! * `approve_as_multi` - Approve a call from a composite origin.
-//! * `cancel_as_multi` - Cancel a call from a composite origin.
+//! * `cancel_as_multi` - Cancel a call from a composite origin, possibly forfeiting part of the
+//!   deposit.
+//! * `pause_operation` - Pause approvals and execution of an operation, by the guardian.
+//! * `unpause_operation` - Lift the pause of an operation, by the guardian.
+//! * `as_multi_announced` - Approve a call and, once approved by the threshold, announce it for
+//!   execution after a delay.
+//! * `execute_announced` - Dispatch an announced call once its delay has passed.
+//! * `cancel_announcement` - Cancel an announced call before its delay has passed.
+//! * `migrate_deposit` - Turn the reserved deposit of an operation into a held one.
+//! * `set_call_filter` - Restrict the calls a multisig account can dispatch, by the account itself.
+//! * `clear_call_filter` - Lift the restriction of a multisig account, by the account itself.
+//!
+//! ### Deposits
+//!
+//! The deposit of an operation is held from its depositor with
+//! [`HoldReason::MultisigDeposit`]. Operations opened before deposits were held still have their
+//! deposit reserved through [`Config::OldCurrency`]. These deposits are unreserved as usual once
+//! the operation ends, or can be turned into holds by anyone through `migrate_deposit`.
+//!
+//! A depositor who cancels an operation after more than half of its required approvals were
+//! collected forfeits [`Config::CancelPenalty`] of the deposit to
+//! [`Config::CancelSlashHandler`]. The rest is returned as usual.
+//!
+//! Operations opened through `as_multi` with a call matched by [`Config::DepositExempt`] take
+//! no deposit at all. Since there is nothing to refund, any of their signatories may cancel them.
+//! Operations opened through `approve_as_multi` only know the hash of their call and always take
+//! a deposit.
+//!
+//! ### Approvals
+//!
+//! Each approval of an operation is stored with the block at which it was given and an optional
+//! hash committing to an off-chain note of the approver, see [`Pallet::operation_details`].
+//! Approvals stored before blocks were recorded carry the block at which their operation was
+//! opened instead.
+//!
+//! ### Announced Operations
+//!
+//! Instead of being dispatched right away, an operation may be announced for execution after a
+//! delay, giving the signatories time to react. This also allows operations with a threshold of
+//! one to go through storage and be observed before they take effect. Announced operations hold
+//! the deposit of their depositor until they are executed or cancelled. Any signatory may cancel
+//! the announcement before the delay has passed; afterwards anyone may execute it.
+//!
+//! ### Call Filters
+//!
+//! A multisig account can restrict the calls it dispatches to a list of pallets and calls of
+//! them, e.g. to a spending multisig which can only transfer funds. The restriction applies once
+//! an operation is dispatched, so an operation whose call is not allowed stays open until it is
+//! cancelled. Only the outermost call of an operation is inspected.
+//!
+//! ### Nested Multisigs
+//!
+//! A multisig account can itself be a signatory of another multisig, approving operations of
+//! the latter through an `as_multi` call of its own. The `max_weight` given to each `as_multi`
+//! must cover the weight of its call, which for a nested approval includes the `max_weight`
+//! given to the nested `as_multi`.
+//! The final approval within the multisig of plain accounts completes the operations of all
+//! levels above it in one dispatch, so nesting should not go deeper than
+//! [`RECOMMENDED_MAX_NESTING_DEPTH`], the depth covered by the
+//! `as_multi_complete_nested` benchmark.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -51,20 +110,26 @@ pub mod weights;
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	dispatch::{
-		DispatchErrorWithPostInfo, DispatchResult, DispatchResultWithPostInfo, GetDispatchInfo,
-		PostDispatchInfo,
+		extract_actual_weight, DispatchErrorWithPostInfo, DispatchResult,
+		DispatchResultWithPostInfo, GetDispatchInfo, PostDispatchInfo,
 	},
 	ensure,
-	traits::{Currency, Get, ReservableCurrency},
+	traits::{
+		fungible::{self, Balanced, BalancedHold, Inspect, Mutate, MutateHold},
+		tokens::{Fortitude, Precision, Preservation},
+		ConstU32, Contains, EnsureOrigin, Get, Imbalance, IsSubType, OnUnbalanced,
+		ReservableCurrency,
+	},
 	weights::Weight,
 	BoundedVec,
 };
 use frame_system::{self as system, pallet_prelude::BlockNumberFor, RawOrigin};
 use scale_info::TypeInfo;
+use sp_core::H256;
 use sp_io::hashing::blake2_256;
 use sp_runtime::{
-	traits::{Dispatchable, TrailingZeroInput, Zero},
-	DispatchError, RuntimeDebug,
+	traits::{Dispatchable, SaturatedConversion, Saturating, TrailingZeroInput, Zero},
+	DispatchError, Perbill, RuntimeDebug,
 };
 use sp_std::prelude::*;
 pub use weights::WeightInfo;
@@ -86,7 +151,9 @@ macro_rules! log {
 }
 
 type BalanceOf<T> =
-	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+type CreditOf<T> =
+	fungible::Credit<<T as frame_system::Config>::AccountId, <T as Config>::Currency>;
 
 /// A global extrinsic index, formed as the extrinsic index within a block, together with that
 /// block's height. This allows a transaction in which a multisig operation of a particular
@@ -101,6 +168,10 @@ pub struct Timepoint<BlockNumber> {
 	index: u32,
 }
 
+/// An approval of a multisig operation: the approving account, the block at which it approved
+/// and an optional commitment to an off-chain note supplied by the approver.
+pub type Approval<AccountId, BlockNumber> = (AccountId, BlockNumber, Option<H256>);
+
 /// An open multisig operation.
 #[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 #[scale_info(skip_type_params(MaxApprovals))]
@@ -110,16 +181,89 @@ where
 {
 	/// The extrinsic when the multisig operation was opened.
 	when: Timepoint<BlockNumber>,
-	/// The amount held in reserve of the `depositor`, to be returned once the operation ends.
+	/// The amount held from the `depositor`, to be returned once the operation ends.
 	deposit: Balance,
 	/// The account who opened it (i.e. the first to approve it).
 	depositor: AccountId,
-	/// The approvals achieved so far, including the depositor. Always sorted.
-	approvals: BoundedVec<AccountId, MaxApprovals>,
+	/// The approvals achieved so far, including the depositor. Always sorted by account.
+	approvals: BoundedVec<Approval<AccountId, BlockNumber>, MaxApprovals>,
+	/// An opaque reference supplied by the depositor, e.g. to an off-chain discussion.
+	metadata: Option<Metadata>,
+	/// The block at which a pause by the guardian expires, if the operation was paused.
+	paused_until: Option<BlockNumber>,
+	/// The block from which anyone may execute the operation, if it was announced.
+	execute_after: Option<BlockNumber>,
+	/// Whether the `deposit` is held, rather than reserved through [`Config::OldCurrency`].
+	deposit_held: bool,
+	/// The maximum number of signatories of the operation, if capped below `MaxSignatories`
+	/// when it was opened.
+	///
+	/// Keeps applying if `MaxSignatories` is raised later on.
+	max_signatories_override: Option<u16>,
+}
+
+impl<BlockNumber: PartialOrd, Balance, AccountId: Ord, MaxApprovals: Get<u32>>
+	Multisig<BlockNumber, Balance, AccountId, MaxApprovals>
+{
+	/// Whether the operation is paused at block `now`.
+	fn is_paused(&self, now: &BlockNumber) -> bool {
+		self.paused_until.as_ref().map_or(false, |until| now < until)
+	}
+
+	/// The position of the approval of `who`, or the position at which to insert it.
+	fn approval_position(&self, who: &AccountId) -> Result<usize, usize> {
+		self.approvals.binary_search_by(|(approver, ..)| approver.cmp(who))
+	}
+}
+
+/// The details of an open multisig operation, as returned by [`Pallet::operation_details`].
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct OperationDetails<BlockNumber, Balance, AccountId> {
+	/// The extrinsic when the multisig operation was opened.
+	pub when: Timepoint<BlockNumber>,
+	/// The account who opened it.
+	pub depositor: AccountId,
+	/// The deposit held from the `depositor`.
+	pub deposit: Balance,
+	/// The approvals achieved so far, sorted by account.
+	pub approvals: Vec<Approval<AccountId, BlockNumber>>,
+	/// An opaque reference supplied by the depositor, e.g. to an off-chain discussion.
+	pub metadata: Option<Metadata>,
+	/// The block at which a pause by the guardian expires, if the operation was paused.
+	pub paused_until: Option<BlockNumber>,
+	/// The block from which anyone may execute the operation, if it was announced.
+	pub execute_after: Option<BlockNumber>,
 }
 
 type CallHash = [u8; 32];
 
+/// The maximum length of the [`Metadata`] of a multisig operation.
+pub const MAX_METADATA_LEN: u32 = 64;
+
+/// An opaque reference attached to a multisig operation for off-chain coordination.
+pub type Metadata = BoundedVec<u8, ConstU32<MAX_METADATA_LEN>>;
+
+/// The index of a pallet within the runtime, the first byte of the encoding of its calls.
+pub type PalletIndex = u8;
+
+/// The index of a call within its pallet, the second byte of the encoding of the call.
+pub type CallIndex = u8;
+
+/// The maximum number of entries of a [`CallFilter`].
+pub const MAX_CALL_FILTER_ENTRIES: u32 = 16;
+
+/// The calls a multisig account is restricted to, see [`Pallet::set_call_filter`].
+///
+/// An entry `(pallet, None)` allows all calls of `pallet`, `(pallet, Some(call))` only the call
+/// `call` of it.
+pub type CallFilter =
+	BoundedVec<(PalletIndex, Option<CallIndex>), ConstU32<MAX_CALL_FILTER_ENTRIES>>;
+
+/// The recommended maximum number of multisigs nested above a multisig of plain accounts.
+///
+/// This is not enforced. See the module docs for nested multisigs.
+pub const RECOMMENDED_MAX_NESTING_DEPTH: u32 = 3;
+
 enum CallOrHash<T: Config> {
 	Call(<T as Config>::RuntimeCall),
 	Hash([u8; 32]),
@@ -140,12 +284,22 @@ pub mod pallet {
 		type RuntimeCall: Parameter
 			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
 			+ GetDispatchInfo
-			+ From<frame_system::Call<Self>>;
+			+ From<frame_system::Call<Self>>
+			+ IsSubType<Call<Self>>;
 
 		/// The currency mechanism.
-		type Currency: ReservableCurrency<Self::AccountId>;
+		type Currency: Mutate<Self::AccountId>
+			+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>
+			+ BalancedHold<Self::AccountId>;
 
-		/// The base amount of currency needed to reserve for creating a multisig execution or to
+		/// The currency which reserved the deposits of operations opened before deposits were
+		/// held. Usually the same as `Currency`.
+		type OldCurrency: ReservableCurrency<Self::AccountId, Balance = BalanceOf<Self>>;
+
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// The base amount of currency needed to hold for creating a multisig execution or to
 		/// store a dispatch call for later.
 		///
 		/// This is held for an additional storage item whose value size is
@@ -156,7 +310,9 @@ pub mod pallet {
 
 		/// The amount of currency needed per unit threshold when creating a multisig execution.
 		///
-		/// This is held for adding 32 bytes more into a pre-existing storage value.
+		/// This is held for adding an [`Approval`] into a pre-existing storage value, whose size
+		/// is `sizeof(AccountId) + sizeof(BlockNumber) + 33` bytes. The metadata of an operation
+		/// is charged one `DepositFactor` per started 32 bytes as well.
 		#[pallet::constant]
 		type DepositFactor: Get<BalanceOf<Self>>;
 
@@ -164,12 +320,54 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxSignatories: Get<u32>;
 
+		/// Whether `other_signatories` may be given in any order.
+		///
+		/// If `true`, unsorted signatories without duplicates are sorted before use. Otherwise
+		/// they are rejected with [`Error::SignatoriesOutOfOrderAt`]. The multisig account is
+		/// derived from the sorted signatories either way.
+		///
+		/// Use `()` to require sorted signatories.
+		#[pallet::constant]
+		type LenientSignatoryOrder: Get<bool>;
+
+		/// The origin allowed to pause and unpause open multisig operations.
+		///
+		/// Use `EnsureNever` to disable pausing.
+		type GuardianOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The number of blocks after which a pause expires.
+		#[pallet::constant]
+		type MaxPauseDuration: Get<BlockNumberFor<Self>>;
+
+		/// The part of the deposit forfeited by a depositor who cancels an operation after more
+		/// than half of its required approvals were collected.
+		///
+		/// Use `()` to always return the full deposit.
+		#[pallet::constant]
+		type CancelPenalty: Get<Perbill>;
+
+		/// Handler for the deposit forfeited on cancellation, e.g. the treasury.
+		type CancelSlashHandler: OnUnbalanced<CreditOf<Self>>;
+
+		/// The calls which can be opened as a multisig operation without a deposit.
+		///
+		/// Use `Nothing` to take a deposit for every operation.
+		type DepositExempt: Contains<<Self as Config>::RuntimeCall>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
 
+	/// A reason for the multisig pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// The funds are held as the deposit of an open multisig operation.
+		#[codec(index = 0)]
+		MultisigDeposit,
+	}
+
 	/// The in-code storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(7);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -186,9 +384,14 @@ pub mod pallet {
 		Multisig<BlockNumberFor<T>, BalanceOf<T>, T::AccountId, T::MaxSignatories>,
 	>;
 
+	/// The calls multisig accounts are restricted to, see [`Pallet::set_call_filter`].
+	#[pallet::storage]
+	pub type AccountCallFilter<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, CallFilter, OptionQuery>;
+
 	#[pallet::error]
 	pub enum Error<T> {
-		/// Threshold must be 2 or greater.
+		/// Threshold must be 2 or greater, or 1 or greater for announced operations.
 		MinimumThreshold,
 		/// Call is already approved by this signatory.
 		AlreadyApproved,
@@ -216,13 +419,48 @@ pub mod pallet {
 		MaxWeightTooLow,
 		/// The data to be stored is already stored.
 		AlreadyStored,
+		/// The metadata given does not match the metadata of the multisig operation.
+		MetadataMismatch,
+		/// The multisig operation is paused by the guardian.
+		OperationPaused,
+		/// The multisig operation is already paused.
+		AlreadyPaused,
+		/// The multisig operation is not paused.
+		NotPaused,
+		/// The multisig operation is announced and can no longer be approved.
+		OperationAnnounced,
+		/// The multisig operation does not have enough approvals to be announced.
+		ApprovalsMissing,
+		/// The multisig operation is not announced.
+		NotAnnounced,
+		/// The delay of the announced multisig operation has not passed yet.
+		AnnouncementNotDue,
+		/// The delay of the announced multisig operation has passed, so it can no longer be
+		/// cancelled.
+		AnnouncementDue,
+		/// The call given does not match the call hash of the multisig operation.
+		CallHashMismatch,
+		/// The deposit of the multisig operation is already held.
+		DepositAlreadyHeld,
+		/// The call is not allowed by the call filter of the multisig account.
+		CallFiltered,
+		/// The signatories were not provided in ascending order of their raw account IDs, or
+		/// contain duplicates. Carries the index of the first signatory out of order.
+		SignatoriesOutOfOrderAt(u16),
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// A new multisig operation has begun.
-		NewMultisig { approving: T::AccountId, multisig: T::AccountId, call_hash: CallHash },
+		NewMultisig {
+			approving: T::AccountId,
+			multisig: T::AccountId,
+			call_hash: CallHash,
+			metadata_hash: Option<[u8; 32]>,
+		},
+		/// Metadata was attached to a new multisig operation.
+		MultisigMetadata { multisig: T::AccountId, call_hash: CallHash, metadata: Metadata },
 		/// A multisig operation has been approved by someone.
 		MultisigApproval {
 			approving: T::AccountId,
@@ -230,13 +468,15 @@ pub mod pallet {
 			multisig: T::AccountId,
 			call_hash: CallHash,
 		},
-		/// A multisig operation has been executed.
+		/// A multisig operation has been executed. `weight` is the weight actually consumed by
+		/// the dispatched call.
 		MultisigExecuted {
 			approving: T::AccountId,
 			timepoint: Timepoint<BlockNumberFor<T>>,
 			multisig: T::AccountId,
 			call_hash: CallHash,
 			result: DispatchResult,
+			weight: Weight,
 		},
 		/// A multisig operation has been cancelled.
 		MultisigCancelled {
@@ -245,6 +485,59 @@ pub mod pallet {
 			multisig: T::AccountId,
 			call_hash: CallHash,
 		},
+		/// The call dispatched by a multisig operation failed. Deposited right after the
+		/// corresponding `MultisigExecuted` event.
+		MultisigExecutionFailed {
+			multisig: T::AccountId,
+			call_hash: CallHash,
+			error: DispatchError,
+		},
+		/// A multisig operation has been paused by the guardian until block `until`.
+		MultisigPaused { multisig: T::AccountId, call_hash: CallHash, until: BlockNumberFor<T> },
+		/// A multisig operation has been unpaused by the guardian.
+		MultisigUnpaused { multisig: T::AccountId, call_hash: CallHash },
+		/// The pause of a multisig operation has expired and was lifted.
+		MultisigPauseExpired { multisig: T::AccountId, call_hash: CallHash },
+		/// A multisig operation has been announced for execution from block `execute_after`.
+		MultisigAnnounced {
+			approving: T::AccountId,
+			multisig: T::AccountId,
+			call_hash: CallHash,
+			execute_after: BlockNumberFor<T>,
+		},
+		/// An announced multisig operation has been executed. `weight` is the weight actually
+		/// consumed by the dispatched call.
+		MultisigAnnouncementExecuted {
+			multisig: T::AccountId,
+			call_hash: CallHash,
+			result: DispatchResult,
+			weight: Weight,
+		},
+		/// An announced multisig operation has been cancelled.
+		MultisigAnnouncementCancelled {
+			cancelling: T::AccountId,
+			multisig: T::AccountId,
+			call_hash: CallHash,
+		},
+		/// The reserved deposit of a multisig operation is now held.
+		DepositMigrated {
+			multisig: T::AccountId,
+			call_hash: CallHash,
+			depositor: T::AccountId,
+			deposit: BalanceOf<T>,
+		},
+		/// The depositor of a cancelled multisig operation forfeited `penalty` of its deposit.
+		/// Deposited right after the corresponding `MultisigCancelled` event.
+		MultisigCancelledWithPenalty {
+			multisig: T::AccountId,
+			call_hash: CallHash,
+			depositor: T::AccountId,
+			penalty: BalanceOf<T>,
+		},
+		/// The calls of a multisig account were restricted to `filter`.
+		CallFilterSet { multisig: T::AccountId, filter: CallFilter },
+		/// The call filter of a multisig account was removed.
+		CallFilterCleared { multisig: T::AccountId },
 	}
 
 	#[pallet::hooks]
@@ -271,6 +564,7 @@ pub mod pallet {
 				T::WeightInfo::as_multi_threshold_1(call.using_encoded(|c| c.len() as u32))
 					// AccountData for inner call origin accountdata.
 					.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+					.saturating_add(T::WeightInfo::check_call_filter())
 					.saturating_add(dispatch_info.weight),
 				dispatch_info.class,
 			)
@@ -288,24 +582,23 @@ pub mod pallet {
 			let signatories = Self::ensure_sorted_and_insert(other_signatories, who)?;
 
 			let id = Self::multi_account_id(&signatories, 1);
+			ensure!(Self::call_allowed(&id, &call), Error::<T>::CallFiltered);
 
 			let call_len = call.using_encoded(|c| c.len());
+			let base_weight = T::WeightInfo::as_multi_threshold_1(call_len as u32)
+				.saturating_add(T::WeightInfo::check_call_filter());
 			let result = call.dispatch(RawOrigin::Signed(id).into());
 
 			result
 				.map(|post_dispatch_info| {
 					post_dispatch_info
 						.actual_weight
-						.map(|actual_weight| {
-							T::WeightInfo::as_multi_threshold_1(call_len as u32)
-								.saturating_add(actual_weight)
-						})
+						.map(|actual_weight| base_weight.saturating_add(actual_weight))
 						.into()
 				})
 				.map_err(|err| match err.post_info.actual_weight {
 					Some(actual_weight) => {
-						let weight_used = T::WeightInfo::as_multi_threshold_1(call_len as u32)
-							.saturating_add(actual_weight);
+						let weight_used = base_weight.saturating_add(actual_weight);
 						let post_info = Some(weight_used).into();
 						DispatchErrorWithPostInfo { post_info, error: err.error }
 					},
@@ -318,7 +611,7 @@ pub mod pallet {
 		///
 		/// If there are enough, then dispatch the call.
 		///
-		/// Payment: `DepositBase` will be reserved if this is the first approval, plus
+		/// Payment: `DepositBase` will be held if this is the first approval, plus
 		/// `threshold` times `DepositFactor`. It is returned once this dispatch happens or
 		/// is cancelled.
 		///
@@ -331,6 +624,12 @@ pub mod pallet {
 		/// not the first approval, then it must be `Some`, with the timepoint (block number and
 		/// transaction index) of the first approval transaction.
 		/// - `call`: The call to be executed.
+		/// - `metadata`: An opaque reference stored with the operation if this is the first
+		/// approval. Otherwise, if given, it must match the stored metadata.
+		/// - `note`: An optional commitment to an off-chain note, stored with the approval of the
+		/// sender.
+		///
+		/// Fails with `OperationPaused` while the operation is paused by the guardian.
 		///
 		/// NOTE: Unless this is the final approval, you will generally want to use
 		/// `approve_as_multi` instead, since it only requires a hash of the call.
@@ -341,7 +640,7 @@ pub mod pallet {
 		///
 		/// ## Complexity
 		/// - `O(S + Z + Call)`.
-		/// - Up to one balance-reserve or unreserve operation.
+		/// - Up to one balance-hold or release operation.
 		/// - One passthrough operation, one insert, both `O(S)` where `S` is the number of
 		///   signatories. `S` is capped by `MaxSignatories`, with weight being proportional.
 		/// - One call encode & hash, both of complexity `O(Z)` where `Z` is tx-len.
@@ -351,7 +650,9 @@ pub mod pallet {
 		/// - One event.
 		/// - The weight of the `call`.
 		/// - Storage: inserts one item, value size bounded by `MaxSignatories`, with a deposit
-		///   taken for its lifetime of `DepositBase + threshold * DepositFactor`.
+		///   taken for its lifetime of `DepositBase + threshold * DepositFactor`, plus one
+		///   `DepositFactor` per started 32 bytes of `metadata`. No deposit is taken if `call` is
+		///   matched by `DepositExempt`.
 		#[pallet::call_index(1)]
 		#[pallet::weight({
 			let s = other_signatories.len() as u32;
@@ -360,6 +661,7 @@ pub mod pallet {
 			T::WeightInfo::as_multi_create(s, z)
 			.max(T::WeightInfo::as_multi_approve(s, z))
 			.max(T::WeightInfo::as_multi_complete(s, z))
+			.saturating_add(T::WeightInfo::check_call_filter())
 			.saturating_add(*max_weight)
 		})]
 		pub fn as_multi(
@@ -369,6 +671,8 @@ pub mod pallet {
 			maybe_timepoint: Option<Timepoint<BlockNumberFor<T>>>,
 			call: Box<<T as Config>::RuntimeCall>,
 			max_weight: Weight,
+			metadata: Option<Metadata>,
+			note: Option<H256>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			Self::operate(
@@ -378,13 +682,16 @@ pub mod pallet {
 				maybe_timepoint,
 				CallOrHash::Call(*call),
 				max_weight,
+				metadata,
+				note,
+				None,
 			)
 		}
 
 		/// Register approval for a dispatch to be made from a deterministic composite account if
 		/// approved by a total of `threshold - 1` of `other_signatories`.
 		///
-		/// Payment: `DepositBase` will be reserved if this is the first approval, plus
+		/// Payment: `DepositBase` will be held if this is the first approval, plus
 		/// `threshold` times `DepositFactor`. It is returned once this dispatch happens or
 		/// is cancelled.
 		///
@@ -397,12 +704,18 @@ pub mod pallet {
 		/// not the first approval, then it must be `Some`, with the timepoint (block number and
 		/// transaction index) of the first approval transaction.
 		/// - `call_hash`: The hash of the call to be executed.
+		/// - `metadata`: An opaque reference stored with the operation if this is the first
+		/// approval. Otherwise, if given, it must match the stored metadata.
+		/// - `note`: An optional commitment to an off-chain note, stored with the approval of the
+		/// sender.
+		///
+		/// Fails with `OperationPaused` while the operation is paused by the guardian.
 		///
 		/// NOTE: If this is the final approval, you will want to use `as_multi` instead.
 		///
 		/// ## Complexity
 		/// - `O(S)`.
-		/// - Up to one balance-reserve or unreserve operation.
+		/// - Up to one balance-hold or release operation.
 		/// - One passthrough operation, one insert, both `O(S)` where `S` is the number of
 		///   signatories. `S` is capped by `MaxSignatories`, with weight being proportional.
 		/// - One encode & hash, both of complexity `O(S)`.
@@ -410,7 +723,8 @@ pub mod pallet {
 		/// - I/O: 1 read `O(S)`, up to 1 mutate `O(S)`. Up to one remove.
 		/// - One event.
 		/// - Storage: inserts one item, value size bounded by `MaxSignatories`, with a deposit
-		///   taken for its lifetime of `DepositBase + threshold * DepositFactor`.
+		///   taken for its lifetime of `DepositBase + threshold * DepositFactor`, plus one
+		///   `DepositFactor` per started 32 bytes of `metadata`.
 		#[pallet::call_index(2)]
 		#[pallet::weight({
 			let s = other_signatories.len() as u32;
@@ -426,6 +740,8 @@ pub mod pallet {
 			maybe_timepoint: Option<Timepoint<BlockNumberFor<T>>>,
 			call_hash: [u8; 32],
 			max_weight: Weight,
+			metadata: Option<Metadata>,
+			note: Option<H256>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			Self::operate(
@@ -435,32 +751,94 @@ pub mod pallet {
 				maybe_timepoint,
 				CallOrHash::Hash(call_hash),
 				max_weight,
+				metadata,
+				note,
+				None,
 			)
 		}
 
-		/// Cancel a pre-existing, on-going multisig transaction. Any deposit reserved previously
-		/// for this operation will be unreserved on success.
+		/// Register approval for the operation with the given call hash that is already underway,
+		/// without having to know the timepoint of its first approval.
+		///
+		/// This is useful when an operation has been cancelled and created anew, since the
+		/// approver does not need to refresh a cached timepoint. Storage holds at most one
+		/// operation per multisig account and call hash, so the timepoint is never ambiguous.
 		///
 		/// The dispatch origin for this call must be _Signed_.
 		///
 		/// - `threshold`: The total number of approvals for this dispatch before it is executed.
 		/// - `other_signatories`: The accounts (other than the sender) who can approve this
 		/// dispatch. May not be empty.
+		/// - `call_hash`: The hash of the call to be executed.
+		///
+		/// Fails with `NotFound` if no operation with `call_hash` is underway and with
+		/// `OperationPaused` while the operation is paused by the guardian.
+		///
+		/// NOTE: If this is the final approval, you will want to use `as_multi` instead.
+		///
+		/// ## Complexity
+		/// Same as `approve_as_multi` when approving an operation that is underway.
+		#[pallet::call_index(6)]
+		#[pallet::weight({
+			let s = other_signatories.len() as u32;
+
+			T::WeightInfo::approve_as_multi_latest(s).saturating_add(*max_weight)
+		})]
+		pub fn approve_as_multi_latest(
+			origin: OriginFor<T>,
+			threshold: u16,
+			other_signatories: Vec<T::AccountId>,
+			call_hash: [u8; 32],
+			max_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let timepoint =
+				Self::pending_timepoint(&who, threshold, &other_signatories, &call_hash)?;
+			Self::operate(
+				who,
+				threshold,
+				other_signatories,
+				Some(timepoint),
+				CallOrHash::Hash(call_hash),
+				max_weight,
+				None,
+				None,
+				None,
+			)
+		}
+
+		/// Cancel a pre-existing, on-going multisig transaction. Any deposit held previously
+		/// for this operation will be released on success.
+		///
+		/// If more than half of the `threshold` approvals were already collected,
+		/// `CancelPenalty` of the deposit is forfeited to `CancelSlashHandler` instead of being
+		/// released.
+		///
+		/// The dispatch origin for this call must be _Signed_, by the depositor of the operation,
+		/// or by any of its signatories if the operation was opened without a deposit.
+		///
+		/// - `threshold`: The total number of approvals for this dispatch before it is executed.
+		/// - `other_signatories`: The accounts (other than the sender) who can approve this
+		/// dispatch. May not be empty.
 		/// - `timepoint`: The timepoint (block number and transaction index) of the first approval
 		/// transaction for this dispatch.
 		/// - `call_hash`: The hash of the call to be executed.
 		///
 		/// ## Complexity
 		/// - `O(S)`.
-		/// - Up to one balance-reserve or unreserve operation.
+		/// - Up to one balance-hold or release operation, and up to one slash.
 		/// - One passthrough operation, one insert, both `O(S)` where `S` is the number of
 		///   signatories. `S` is capped by `MaxSignatories`, with weight being proportional.
 		/// - One encode & hash, both of complexity `O(S)`.
-		/// - One event.
+		/// - Up to two events.
 		/// - I/O: 1 read `O(S)`, one remove.
 		/// - Storage: removes one item.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::cancel_as_multi(other_signatories.len() as u32))]
+		#[pallet::weight(if T::CancelPenalty::get().is_zero() {
+			T::WeightInfo::cancel_as_multi(other_signatories.len() as u32)
+		} else {
+			T::WeightInfo::cancel_as_multi_penalized(other_signatories.len() as u32)
+		})]
 		pub fn cancel_as_multi(
 			origin: OriginFor<T>,
 			threshold: u16,
@@ -479,20 +857,426 @@ pub mod pallet {
 
 			let m = <Multisigs<T>>::get(&id, call_hash).ok_or(Error::<T>::NotFound)?;
 			ensure!(m.when == timepoint, Error::<T>::WrongTimepoint);
-			ensure!(m.depositor == who, Error::<T>::NotOwner);
+			// Any signatory may cancel an operation without a deposit at stake.
+			ensure!(m.depositor == who || m.deposit.is_zero(), Error::<T>::NotOwner);
+			ensure!(m.execute_after.is_none(), Error::<T>::OperationAnnounced);
 
-			let err_amount = T::Currency::unreserve(&m.depositor, m.deposit);
-			debug_assert!(err_amount.is_zero());
+			let penalty = if m.approvals.len() * 2 > threshold as usize {
+				T::CancelPenalty::get() * m.deposit
+			} else {
+				Zero::zero()
+			};
+			let penalty = Self::release_deposit_with_penalty(&m, penalty);
 			<Multisigs<T>>::remove(&id, &call_hash);
 
 			Self::deposit_event(Event::MultisigCancelled {
-				cancelling: who,
+				cancelling: who.clone(),
 				timepoint,
+				multisig: id.clone(),
+				call_hash,
+			});
+			if !penalty.is_zero() {
+				Self::deposit_event(Event::MultisigCancelledWithPenalty {
+					multisig: id,
+					call_hash,
+					depositor: who,
+					penalty,
+				});
+			}
+			Ok(())
+		}
+
+		/// Pause an open multisig operation, blocking any approval and its execution until it is
+		/// unpaused or the pause expires after `MaxPauseDuration` blocks. The operation can still
+		/// be cancelled by its depositor.
+		///
+		/// The dispatch origin for this call must be `GuardianOrigin`.
+		///
+		/// - `multisig`: The multisig account of the operation.
+		/// - `call_hash`: The hash of the call of the operation.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::pause_operation())]
+		pub fn pause_operation(
+			origin: OriginFor<T>,
+			multisig: T::AccountId,
+			call_hash: [u8; 32],
+		) -> DispatchResult {
+			T::GuardianOrigin::ensure_origin(origin)?;
+
+			let now = <system::Pallet<T>>::block_number();
+			let until = now.saturating_add(T::MaxPauseDuration::get());
+			<Multisigs<T>>::try_mutate(&multisig, call_hash, |maybe_m| -> DispatchResult {
+				let m = maybe_m.as_mut().ok_or(Error::<T>::NotFound)?;
+				ensure!(!m.is_paused(&now), Error::<T>::AlreadyPaused);
+				m.paused_until = Some(until);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::MultisigPaused { multisig, call_hash, until });
+			Ok(())
+		}
+
+		/// Lift the pause of a multisig operation.
+		///
+		/// The dispatch origin for this call must be `GuardianOrigin`.
+		///
+		/// - `multisig`: The multisig account of the operation.
+		/// - `call_hash`: The hash of the call of the operation.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::unpause_operation())]
+		pub fn unpause_operation(
+			origin: OriginFor<T>,
+			multisig: T::AccountId,
+			call_hash: [u8; 32],
+		) -> DispatchResult {
+			T::GuardianOrigin::ensure_origin(origin)?;
+
+			<Multisigs<T>>::try_mutate(&multisig, call_hash, |maybe_m| -> DispatchResult {
+				let m = maybe_m.as_mut().ok_or(Error::<T>::NotFound)?;
+				ensure!(m.paused_until.take().is_some(), Error::<T>::NotPaused);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::MultisigUnpaused { multisig, call_hash });
+			Ok(())
+		}
+
+		/// Approve a dispatch to be made from a deterministic composite account and, once it is
+		/// approved by a total of `threshold` signatories, announce it for execution after
+		/// `delay` blocks.
+		///
+		/// With a `threshold` of `1` the operation is stored and announced right away, instead of
+		/// being dispatched as with `as_multi_threshold_1`. Otherwise the operation must already
+		/// be underway, and this is its final approval.
+		///
+		/// Payment: `DepositBase` plus `threshold` times `DepositFactor` is held if this is
+		/// the first approval. It is returned once the announced call is executed or the
+		/// announcement is cancelled.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `threshold`: The total number of approvals required before the announcement.
+		/// - `other_signatories`: The accounts (other than the sender) who can approve this
+		/// dispatch. May not be empty.
+		/// - `maybe_timepoint`: If this is the first approval, then this must be `None`. If it is
+		/// not the first approval, then it must be `Some`, with the timepoint (block number and
+		/// transaction index) of the first approval transaction.
+		/// - `call_hash`: The hash of the call to be executed.
+		/// - `delay`: The number of blocks after which the announced call may be executed.
+		///
+		/// Fails with `OperationPaused` while the operation is paused by the guardian.
+		///
+		/// ## Complexity
+		/// Same as `approve_as_multi`.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::as_multi_announce(other_signatories.len() as u32))]
+		pub fn as_multi_announced(
+			origin: OriginFor<T>,
+			threshold: u16,
+			other_signatories: Vec<T::AccountId>,
+			maybe_timepoint: Option<Timepoint<BlockNumberFor<T>>>,
+			call_hash: [u8; 32],
+			delay: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(threshold >= 1, Error::<T>::MinimumThreshold);
+			let max_sigs = T::MaxSignatories::get() as usize;
+			ensure!(!other_signatories.is_empty(), Error::<T>::TooFewSignatories);
+			ensure!(other_signatories.len() < max_sigs, Error::<T>::TooManySignatories);
+			let signatories = Self::ensure_sorted_and_insert(other_signatories, who.clone())?;
+
+			let id = Self::multi_account_id(&signatories, threshold);
+			let now = <system::Pallet<T>>::block_number();
+			let execute_after = now.saturating_add(delay);
+
+			let m = if let Some(mut m) = <Multisigs<T>>::get(&id, call_hash) {
+				let timepoint = maybe_timepoint.ok_or(Error::<T>::NoTimepoint)?;
+				ensure!(m.when == timepoint, Error::<T>::WrongTimepoint);
+				ensure!(m.execute_after.is_none(), Error::<T>::OperationAnnounced);
+				if m.paused_until.is_some() {
+					ensure!(!m.is_paused(&now), Error::<T>::OperationPaused);
+					m.paused_until = None;
+					Self::deposit_event(Event::MultisigPauseExpired {
+						multisig: id.clone(),
+						call_hash,
+					});
+				}
+				if let Err(pos) = m.approval_position(&who) {
+					m.approvals
+						.try_insert(pos, (who.clone(), now, None))
+						.map_err(|_| Error::<T>::TooManySignatories)?;
+				}
+				ensure!(m.approvals.len() >= threshold as usize, Error::<T>::ApprovalsMissing);
+				m.execute_after = Some(execute_after);
+				m
+			} else {
+				ensure!(maybe_timepoint.is_none(), Error::<T>::UnexpectedTimepoint);
+				// Only a single approval is needed to announce a new operation.
+				ensure!(threshold == 1, Error::<T>::ApprovalsMissing);
+
+				let deposit = Self::deposit(threshold, None);
+				T::Currency::hold(&HoldReason::MultisigDeposit.into(), &who, deposit)?;
+
+				Multisig {
+					when: Self::timepoint(),
+					deposit,
+					depositor: who.clone(),
+					approvals: vec![(who.clone(), now, None)]
+						.try_into()
+						.map_err(|_| Error::<T>::TooManySignatories)?,
+					metadata: None,
+					paused_until: None,
+					execute_after: Some(execute_after),
+					deposit_held: true,
+					max_signatories_override: None,
+				}
+			};
+			<Multisigs<T>>::insert(&id, call_hash, m);
+
+			Self::deposit_event(Event::MultisigAnnounced {
+				approving: who,
 				multisig: id,
 				call_hash,
+				execute_after,
 			});
 			Ok(())
 		}
+
+		/// Dispatch an announced multisig operation whose delay has passed.
+		///
+		/// The operation is removed from storage and the deposit returned to its depositor before
+		/// the call is dispatched from the multisig account.
+		///
+		/// The dispatch origin for this call must be _Signed_, by any account.
+		///
+		/// - `multisig`: The multisig account of the operation.
+		/// - `call_hash`: The hash of the call of the operation.
+		/// - `call`: The call to be executed, which must match `call_hash`.
+		///
+		/// Fails with `AnnouncementNotDue` before the block given by the announcement and with
+		/// `OperationPaused` while the operation is paused by the guardian. On success, the
+		/// result of the call may be found in the deposited `MultisigAnnouncementExecuted` event.
+		///
+		/// ## Complexity
+		/// O(Z + C) where Z is the length of the call and C its execution weight.
+		#[pallet::call_index(8)]
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(
+				T::WeightInfo::execute_announced(call.using_encoded(|c| c.len() as u32))
+					.saturating_add(T::WeightInfo::check_call_filter())
+					.saturating_add(dispatch_info.weight),
+				dispatch_info.class,
+			)
+		})]
+		pub fn execute_announced(
+			origin: OriginFor<T>,
+			multisig: T::AccountId,
+			call_hash: [u8; 32],
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let m = <Multisigs<T>>::get(&multisig, call_hash).ok_or(Error::<T>::NotFound)?;
+			let execute_after = m.execute_after.ok_or(Error::<T>::NotAnnounced)?;
+			let now = <system::Pallet<T>>::block_number();
+			ensure!(now >= execute_after, Error::<T>::AnnouncementNotDue);
+			ensure!(!m.is_paused(&now), Error::<T>::OperationPaused);
+			let (hash, call_len) = call.using_encoded(|d| (blake2_256(d), d.len()));
+			ensure!(hash == call_hash, Error::<T>::CallHashMismatch);
+			ensure!(Self::call_allowed(&multisig, &call), Error::<T>::CallFiltered);
+
+			// Clean up storage before executing call to avoid an possibility of reentrancy attack.
+			<Multisigs<T>>::remove(&multisig, call_hash);
+			Self::release_deposit(&m);
+
+			let call_info = call.get_dispatch_info();
+			let result = call.dispatch(RawOrigin::Signed(multisig.clone()).into());
+			let call_weight = extract_actual_weight(&result, &call_info);
+			Self::deposit_event(Event::MultisigAnnouncementExecuted {
+				multisig,
+				call_hash,
+				result: result.map(|_| ()).map_err(|e| e.error),
+				weight: call_weight,
+			});
+
+			let final_weight = T::WeightInfo::execute_announced(call_len as u32)
+				.saturating_add(T::WeightInfo::check_call_filter())
+				.saturating_add(call_weight);
+			Ok(Some(final_weight).into())
+		}
+
+		/// Cancel an announced multisig operation before its delay has passed. Any deposit
+		/// held for this operation will be released on success.
+		///
+		/// The dispatch origin for this call must be _Signed_, by any of the signatories.
+		///
+		/// - `threshold`: The total number of approvals required before the announcement.
+		/// - `other_signatories`: The accounts (other than the sender) who can approve this
+		/// dispatch. May not be empty.
+		/// - `call_hash`: The hash of the call of the operation.
+		///
+		/// Fails with `AnnouncementDue` from the block given by the announcement on.
+		///
+		/// ## Complexity
+		/// Same as `cancel_as_multi`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::cancel_announcement(other_signatories.len() as u32))]
+		pub fn cancel_announcement(
+			origin: OriginFor<T>,
+			threshold: u16,
+			other_signatories: Vec<T::AccountId>,
+			call_hash: [u8; 32],
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(threshold >= 1, Error::<T>::MinimumThreshold);
+			let max_sigs = T::MaxSignatories::get() as usize;
+			ensure!(!other_signatories.is_empty(), Error::<T>::TooFewSignatories);
+			ensure!(other_signatories.len() < max_sigs, Error::<T>::TooManySignatories);
+			let signatories = Self::ensure_sorted_and_insert(other_signatories, who.clone())?;
+
+			let id = Self::multi_account_id(&signatories, threshold);
+
+			let m = <Multisigs<T>>::get(&id, call_hash).ok_or(Error::<T>::NotFound)?;
+			let execute_after = m.execute_after.ok_or(Error::<T>::NotAnnounced)?;
+			let now = <system::Pallet<T>>::block_number();
+			ensure!(now < execute_after, Error::<T>::AnnouncementDue);
+
+			Self::release_deposit(&m);
+			<Multisigs<T>>::remove(&id, &call_hash);
+
+			Self::deposit_event(Event::MultisigAnnouncementCancelled {
+				cancelling: who,
+				multisig: id,
+				call_hash,
+			});
+			Ok(())
+		}
+
+		/// Turn the deposit of an operation opened before deposits were held, and which is
+		/// therefore still reserved through `OldCurrency`, into a hold.
+		///
+		/// The dispatch origin for this call must be _Signed_, by any account. The transaction
+		/// fee is refunded on success.
+		///
+		/// - `multisig`: The multisig account of the operation.
+		/// - `call_hash`: The hash of the call of the operation.
+		///
+		/// Fails with `DepositAlreadyHeld` if the deposit is already held.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::migrate_deposit())]
+		pub fn migrate_deposit(
+			origin: OriginFor<T>,
+			multisig: T::AccountId,
+			call_hash: [u8; 32],
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let (depositor, deposit) = <Multisigs<T>>::try_mutate(
+				&multisig,
+				call_hash,
+				|maybe_m| -> Result<_, DispatchError> {
+					let m = maybe_m.as_mut().ok_or(Error::<T>::NotFound)?;
+					ensure!(!m.deposit_held, Error::<T>::DepositAlreadyHeld);
+					let err_amount = T::OldCurrency::unreserve(&m.depositor, m.deposit);
+					debug_assert!(err_amount.is_zero());
+					// Only hold what was actually reserved, so it can be released in full.
+					m.deposit = m.deposit.saturating_sub(err_amount);
+					T::Currency::hold(
+						&HoldReason::MultisigDeposit.into(),
+						&m.depositor,
+						m.deposit,
+					)?;
+					m.deposit_held = true;
+					Ok((m.depositor.clone(), m.deposit))
+				},
+			)?;
+
+			Self::deposit_event(Event::DepositMigrated { multisig, call_hash, depositor, deposit });
+			Ok(Pays::No.into())
+		}
+
+		/// Open a multisig operation like `approve_as_multi`, capping the number of its
+		/// signatories below `MaxSignatories`.
+		///
+		/// The cap is stored with the operation, so approvals keep being rejected with
+		/// `TooManySignatories` for a multisig with more than `max_signatories` signatories,
+		/// even if `MaxSignatories` is raised later on.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `threshold`: The total number of approvals for this dispatch before it is executed.
+		/// - `other_signatories`: The accounts (other than the sender) who can approve this
+		/// dispatch. May not be empty.
+		/// - `call_hash`: The hash of the call to be executed.
+		/// - `metadata`: An opaque reference stored with the operation.
+		/// - `max_signatories`: The maximum number of signatories, including the sender. May not
+		/// exceed `MaxSignatories`.
+		///
+		/// Fails with `UnexpectedTimepoint` if the operation is already underway.
+		///
+		/// ## Complexity
+		/// Same as `approve_as_multi` when opening an operation.
+		#[pallet::call_index(11)]
+		#[pallet::weight({
+			let s = other_signatories.len() as u32;
+
+			T::WeightInfo::approve_as_multi_create(s).saturating_add(*max_weight)
+		})]
+		pub fn approve_as_multi_capped(
+			origin: OriginFor<T>,
+			threshold: u16,
+			other_signatories: Vec<T::AccountId>,
+			call_hash: [u8; 32],
+			max_weight: Weight,
+			metadata: Option<Metadata>,
+			max_signatories: u16,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::operate(
+				who,
+				threshold,
+				other_signatories,
+				None,
+				CallOrHash::Hash(call_hash),
+				max_weight,
+				metadata,
+				None,
+				Some(max_signatories),
+			)
+		}
+
+		/// Restrict the calls the origin can dispatch as a multisig account to `filter`,
+		/// replacing any previous filter.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the multisig account itself,
+		/// i.e. this call has to be dispatched through a multisig operation.
+		///
+		/// Dispatching an operation whose call is not allowed fails with `CallFiltered`, leaving
+		/// the operation open so that it can be cancelled. Only the outermost call is inspected:
+		/// allowing e.g. `Utility::batch` allows any call within the batch. Calls managing the
+		/// filter itself are always allowed, so an empty `filter` only allows these.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::set_call_filter())]
+		pub fn set_call_filter(origin: OriginFor<T>, filter: CallFilter) -> DispatchResult {
+			let multisig = ensure_signed(origin)?;
+			AccountCallFilter::<T>::insert(&multisig, &filter);
+			Self::deposit_event(Event::CallFilterSet { multisig, filter });
+			Ok(())
+		}
+
+		/// Remove the call filter of the origin, see `set_call_filter`.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the multisig account itself.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::clear_call_filter())]
+		pub fn clear_call_filter(origin: OriginFor<T>) -> DispatchResult {
+			let multisig = ensure_signed(origin)?;
+			if AccountCallFilter::<T>::take(&multisig).is_some() {
+				Self::deposit_event(Event::CallFilterCleared { multisig });
+			}
+			Ok(())
+		}
 	}
 }
 
@@ -507,6 +1291,65 @@ impl<T: Config> Pallet<T> {
 			.expect("infinite length input; no invalid inputs for type; qed")
 	}
 
+	/// Derive the multi-account ID `who` would dispatch through with `other_signatories` and
+	/// `threshold`, validating them exactly like the dispatchables do.
+	///
+	/// Unlike [`Pallet::multi_account_id`], this fails with the error the dispatchables would
+	/// return for signatories they reject, e.g. [`Error::SignatoriesOutOfOrderAt`] for unsorted
+	/// `other_signatories` unless [`Config::LenientSignatoryOrder`] is set, or
+	/// [`Error::SenderInSignatories`] if they include `who`. A `threshold` of 1 derives the
+	/// account of [`Pallet::as_multi_threshold_1`].
+	pub fn compute_multi_account(
+		who: T::AccountId,
+		other_signatories: Vec<T::AccountId>,
+		threshold: u16,
+	) -> Result<T::AccountId, DispatchError> {
+		ensure!(threshold >= 1, Error::<T>::MinimumThreshold);
+		let max_sigs = T::MaxSignatories::get() as usize;
+		ensure!(!other_signatories.is_empty(), Error::<T>::TooFewSignatories);
+		ensure!(other_signatories.len() < max_sigs, Error::<T>::TooManySignatories);
+		let signatories = Self::ensure_sorted_and_insert(other_signatories, who)?;
+		Ok(Self::multi_account_id(&signatories, threshold))
+	}
+
+	/// The `DepositBase`, `DepositFactor` and `MaxSignatories` constants, which determine the
+	/// deposit of a new operation and the size of a multisig.
+	pub fn constants() -> (BalanceOf<T>, BalanceOf<T>, u32) {
+		(T::DepositBase::get(), T::DepositFactor::get(), T::MaxSignatories::get())
+	}
+
+	/// The details of the operation with `call_hash` that is underway for `multisig`, including
+	/// the block and note of each of its approvals.
+	pub fn operation_details(
+		multisig: T::AccountId,
+		call_hash: [u8; 32],
+	) -> Option<OperationDetails<BlockNumberFor<T>, BalanceOf<T>, T::AccountId>> {
+		<Multisigs<T>>::get(&multisig, call_hash).map(|m| OperationDetails {
+			when: m.when,
+			depositor: m.depositor,
+			deposit: m.deposit,
+			approvals: m.approvals.into_inner(),
+			metadata: m.metadata,
+			paused_until: m.paused_until,
+			execute_after: m.execute_after,
+		})
+	}
+
+	/// The timepoint of the operation with `call_hash` that is underway for the multisig of
+	/// `who`, `other_signatories` and `threshold`.
+	fn pending_timepoint(
+		who: &T::AccountId,
+		threshold: u16,
+		other_signatories: &[T::AccountId],
+		call_hash: &[u8; 32],
+	) -> Result<Timepoint<BlockNumberFor<T>>, DispatchError> {
+		let signatories = Self::ensure_sorted_and_insert(other_signatories.to_vec(), who.clone())?;
+		let id = Self::multi_account_id(&signatories, threshold);
+		<Multisigs<T>>::get(&id, call_hash)
+			.map(|m| m.when)
+			.ok_or_else(|| Error::<T>::NotFound.into())
+	}
+
 	fn operate(
 		who: T::AccountId,
 		threshold: u16,
@@ -514,6 +1357,9 @@ impl<T: Config> Pallet<T> {
 		maybe_timepoint: Option<Timepoint<BlockNumberFor<T>>>,
 		call_or_hash: CallOrHash<T>,
 		max_weight: Weight,
+		metadata: Option<Metadata>,
+		note: Option<H256>,
+		max_signatories_override: Option<u16>,
 	) -> DispatchResultWithPostInfo {
 		ensure!(threshold >= 2, Error::<T>::MinimumThreshold);
 		let max_sigs = T::MaxSignatories::get() as usize;
@@ -538,11 +1384,30 @@ impl<T: Config> Pallet<T> {
 			// Yes; ensure that the timepoint exists and agrees.
 			let timepoint = maybe_timepoint.ok_or(Error::<T>::NoTimepoint)?;
 			ensure!(m.when == timepoint, Error::<T>::WrongTimepoint);
+			// Metadata is only checked if the approver supplied it.
+			if let Some(metadata) = metadata {
+				ensure!(m.metadata.as_ref() == Some(&metadata), Error::<T>::MetadataMismatch);
+			}
+			// An announced operation is only executed through `execute_announced`.
+			ensure!(m.execute_after.is_none(), Error::<T>::OperationAnnounced);
+			if let Some(max) = m.max_signatories_override {
+				ensure!(signatories.len() <= max as usize, Error::<T>::TooManySignatories);
+			}
+			// A paused operation can neither be approved nor executed until the pause expires.
+			if m.paused_until.is_some() {
+				let now = <system::Pallet<T>>::block_number();
+				ensure!(!m.is_paused(&now), Error::<T>::OperationPaused);
+				m.paused_until = None;
+				Self::deposit_event(Event::MultisigPauseExpired {
+					multisig: id.clone(),
+					call_hash,
+				});
+			}
 
 			// Ensure that either we have not yet signed or that it is at threshold.
 			let mut approvals = m.approvals.len() as u16;
 			// We only bother with the approval if we're below threshold.
-			let maybe_pos = m.approvals.binary_search(&who).err().filter(|_| approvals < threshold);
+			let maybe_pos = m.approval_position(&who).err().filter(|_| approvals < threshold);
 			// Bump approvals if not yet voted and the vote is needed.
 			if maybe_pos.is_some() {
 				approvals += 1;
@@ -551,41 +1416,50 @@ impl<T: Config> Pallet<T> {
 			// We only bother fetching/decoding call if we know that we're ready to execute.
 			if let Some(call) = maybe_call.filter(|_| approvals >= threshold) {
 				// verify weight
-				ensure!(
-					call.get_dispatch_info().weight.all_lte(max_weight),
-					Error::<T>::MaxWeightTooLow
-				);
+				let call_info = call.get_dispatch_info();
+				ensure!(call_info.weight.all_lte(max_weight), Error::<T>::MaxWeightTooLow);
+				// The operation stays open, so that the signatories can cancel it.
+				ensure!(Self::call_allowed(&id, &call), Error::<T>::CallFiltered);
 
 				// Clean up storage before executing call to avoid an possibility of reentrancy
 				// attack.
 				<Multisigs<T>>::remove(&id, call_hash);
-				T::Currency::unreserve(&m.depositor, m.deposit);
+				Self::release_deposit(&m);
 
 				let result = call.dispatch(RawOrigin::Signed(id.clone()).into());
+				// Charge for what the call actually used rather than for the `max_weight` given by
+				// the caller, regardless of whether the call succeeded.
+				let call_weight = extract_actual_weight(&result, &call_info);
+				let result = result.map(|_| ()).map_err(|e| e.error);
 				Self::deposit_event(Event::MultisigExecuted {
 					approving: who,
 					timepoint,
-					multisig: id,
+					multisig: id.clone(),
 					call_hash,
-					result: result.map(|_| ()).map_err(|e| e.error),
+					result,
+					weight: call_weight,
 				});
-				Ok(get_result_weight(result)
-					.map(|actual_weight| {
-						T::WeightInfo::as_multi_complete(
-							other_signatories_len as u32,
-							call_len as u32,
-						)
-						.saturating_add(actual_weight)
-					})
-					.into())
+				if let Err(error) = result {
+					Self::deposit_event(Event::MultisigExecutionFailed {
+						multisig: id,
+						call_hash,
+						error,
+					});
+				}
+				let final_weight =
+					T::WeightInfo::as_multi_complete(other_signatories_len as u32, call_len as u32)
+						.saturating_add(T::WeightInfo::check_call_filter())
+						.saturating_add(call_weight);
+				Ok(Some(final_weight).into())
 			} else {
 				// We cannot dispatch the call now; either it isn't available, or it is, but we
 				// don't have threshold approvals even with our signature.
 
 				if let Some(pos) = maybe_pos {
 					// Record approval.
+					let now = <system::Pallet<T>>::block_number();
 					m.approvals
-						.try_insert(pos, who.clone())
+						.try_insert(pos, (who.clone(), now, note))
 						.map_err(|_| Error::<T>::TooManySignatories)?;
 					<Multisigs<T>>::insert(&id, call_hash, m);
 					Self::deposit_event(Event::MultisigApproval {
@@ -608,34 +1482,168 @@ impl<T: Config> Pallet<T> {
 		} else {
 			// Not yet started; there should be no timepoint given.
 			ensure!(maybe_timepoint.is_none(), Error::<T>::UnexpectedTimepoint);
+			if let Some(max) = max_signatories_override {
+				ensure!(max as usize <= max_sigs, Error::<T>::TooManySignatories);
+				ensure!(signatories.len() <= max as usize, Error::<T>::TooManySignatories);
+			}
 
 			// Just start the operation by recording it in storage.
-			let deposit = T::DepositBase::get() + T::DepositFactor::get() * threshold.into();
+			let exempt = maybe_call.as_ref().map_or(false, |call| T::DepositExempt::contains(call));
+			let deposit =
+				if exempt { Zero::zero() } else { Self::deposit(threshold, metadata.as_ref()) };
 
-			T::Currency::reserve(&who, deposit)?;
+			if !exempt {
+				T::Currency::hold(&HoldReason::MultisigDeposit.into(), &who, deposit)?;
+			}
 
-			let initial_approvals =
-				vec![who.clone()].try_into().map_err(|_| Error::<T>::TooManySignatories)?;
+			let when = Self::timepoint();
+			let initial_approvals = vec![(who.clone(), when.height, note)]
+				.try_into()
+				.map_err(|_| Error::<T>::TooManySignatories)?;
 
 			<Multisigs<T>>::insert(
 				&id,
 				call_hash,
 				Multisig {
-					when: Self::timepoint(),
+					when,
 					deposit,
 					depositor: who.clone(),
 					approvals: initial_approvals,
+					metadata: metadata.clone(),
+					paused_until: None,
+					execute_after: None,
+					deposit_held: true,
+					max_signatories_override,
 				},
 			);
-			Self::deposit_event(Event::NewMultisig { approving: who, multisig: id, call_hash });
+			Self::deposit_event(Event::NewMultisig {
+				approving: who,
+				multisig: id.clone(),
+				call_hash,
+				metadata_hash: metadata.as_ref().map(|m| blake2_256(m)),
+			});
+			if let Some(metadata) = metadata {
+				Self::deposit_event(Event::MultisigMetadata { multisig: id, call_hash, metadata });
+			}
 
-			let final_weight =
-				T::WeightInfo::as_multi_create(other_signatories_len as u32, call_len as u32);
+			let final_weight = if exempt {
+				T::WeightInfo::as_multi_create_exempt(other_signatories_len as u32, call_len as u32)
+			} else {
+				T::WeightInfo::as_multi_create(other_signatories_len as u32, call_len as u32)
+			};
 			// Call is not made, so the actual weight does not include call
 			Ok(Some(final_weight).into())
 		}
 	}
 
+	/// Whether `call` may be dispatched from `multisig` according to its call filter, see
+	/// [`Pallet::set_call_filter`].
+	pub fn call_allowed(multisig: &T::AccountId, call: &<T as Config>::RuntimeCall) -> bool {
+		let Some(filter) = AccountCallFilter::<T>::get(multisig) else { return true };
+		if matches!(
+			call.is_sub_type(),
+			Some(Call::set_call_filter { .. } | Call::clear_call_filter { .. })
+		) {
+			return true
+		}
+		let (pallet, index) = call.using_encoded(|c| (c.first().copied(), c.get(1).copied()));
+		filter
+			.iter()
+			.any(|(p, c)| Some(*p) == pallet && c.map_or(true, |c| Some(c) == index))
+	}
+
+	/// Return the deposit of `m` to its depositor, either releasing the hold or unreserving it.
+	fn release_deposit(
+		m: &Multisig<BlockNumberFor<T>, BalanceOf<T>, T::AccountId, T::MaxSignatories>,
+	) {
+		// Operations exempt from the deposit have nothing to return.
+		if m.deposit.is_zero() {
+			return
+		}
+		if m.deposit_held {
+			let released = T::Currency::release(
+				&HoldReason::MultisigDeposit.into(),
+				&m.depositor,
+				m.deposit,
+				Precision::BestEffort,
+			);
+			debug_assert!(released == Ok(m.deposit));
+		} else {
+			let err_amount = T::OldCurrency::unreserve(&m.depositor, m.deposit);
+			debug_assert!(err_amount.is_zero());
+		}
+	}
+
+	/// Return the deposit of `m` to its depositor except for `penalty`, which is slashed and
+	/// handed to `CancelSlashHandler`. Returns the amount actually slashed.
+	fn release_deposit_with_penalty(
+		m: &Multisig<BlockNumberFor<T>, BalanceOf<T>, T::AccountId, T::MaxSignatories>,
+		penalty: BalanceOf<T>,
+	) -> BalanceOf<T> {
+		if penalty.is_zero() {
+			Self::release_deposit(m);
+			return Zero::zero()
+		}
+		let penalty = penalty.min(m.deposit);
+		let credit = if m.deposit_held {
+			let reason = HoldReason::MultisigDeposit.into();
+			let (credit, _) =
+				<T::Currency as BalancedHold<_>>::slash(&reason, &m.depositor, penalty);
+			let _ = T::Currency::release(
+				&reason,
+				&m.depositor,
+				m.deposit.saturating_sub(credit.peek()),
+				Precision::BestEffort,
+			);
+			credit
+		} else {
+			let err_amount = T::OldCurrency::unreserve(&m.depositor, m.deposit);
+			debug_assert!(err_amount.is_zero());
+			<T::Currency as Balanced<_>>::withdraw(
+				&m.depositor,
+				penalty,
+				Precision::BestEffort,
+				Preservation::Expendable,
+				Fortitude::Force,
+			)
+			.unwrap_or_default()
+		};
+		let slashed = credit.peek();
+		T::CancelSlashHandler::on_unbalanced(credit);
+		slashed
+	}
+
+	/// Turn the held deposit of an operation into one reserved through `OldCurrency`, as taken
+	/// by a former version of the pallet.
+	#[cfg(any(test, feature = "runtime-benchmarks"))]
+	pub(crate) fn unmigrate_deposit(
+		multisig: &T::AccountId,
+		call_hash: [u8; 32],
+	) -> DispatchResult {
+		<Multisigs<T>>::try_mutate(multisig, call_hash, |maybe_m| -> DispatchResult {
+			let m = maybe_m.as_mut().ok_or(Error::<T>::NotFound)?;
+			T::Currency::release(
+				&HoldReason::MultisigDeposit.into(),
+				&m.depositor,
+				m.deposit,
+				Precision::Exact,
+			)?;
+			T::OldCurrency::reserve(&m.depositor, m.deposit)?;
+			m.deposit_held = false;
+			Ok(())
+		})
+	}
+
+	/// The deposit held for a multisig operation with the given `threshold` and `metadata`.
+	///
+	/// The metadata is charged one `DepositFactor` for every started 32 bytes of its length.
+	pub fn deposit(threshold: u16, metadata: Option<&Metadata>) -> BalanceOf<T> {
+		let metadata_words = metadata.map_or(0, |m| m.len().div_ceil(32)) as u32;
+		T::DepositBase::get() +
+			T::DepositFactor::get() * threshold.into() +
+			T::DepositFactor::get() * metadata_words.into()
+	}
+
 	/// The current `Timepoint`.
 	pub fn timepoint() -> Timepoint<BlockNumberFor<T>> {
 		Timepoint {
@@ -645,34 +1653,31 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Check that signatories is sorted and doesn't contain sender, then insert sender.
+	///
+	/// Unsorted signatories are sorted instead if [`Config::LenientSignatoryOrder`] is set, as
+	/// long as they contain no duplicates.
 	fn ensure_sorted_and_insert(
 		other_signatories: Vec<T::AccountId>,
 		who: T::AccountId,
 	) -> Result<Vec<T::AccountId>, DispatchError> {
 		let mut signatories = other_signatories;
-		let mut maybe_last = None;
+		if let Some(position) = signatories.windows(2).position(|pair| pair[0] >= pair[1]) {
+			let index: u16 = (position + 1).saturated_into();
+			ensure!(T::LenientSignatoryOrder::get(), Error::<T>::SignatoriesOutOfOrderAt(index));
+			signatories.sort();
+			ensure!(
+				signatories.windows(2).all(|pair| pair[0] < pair[1]),
+				Error::<T>::SignatoriesOutOfOrderAt(index)
+			);
+		}
 		let mut index = 0;
 		for item in signatories.iter() {
-			if let Some(last) = maybe_last {
-				ensure!(last < item, Error::<T>::SignatoriesOutOfOrder);
-			}
 			if item <= &who {
 				ensure!(item != &who, Error::<T>::SenderInSignatories);
 				index += 1;
 			}
-			maybe_last = Some(item);
 		}
 		signatories.insert(index, who);
 		Ok(signatories)
 	}
 }
-
-/// Return the weight of a dispatch call result as an `Option`.
-///
-/// Will return the weight regardless of what the state of the result is.
-fn get_result_weight(result: DispatchResultWithPostInfo) -> Option<Weight> {
-	match result {
-		Ok(post_info) => post_info.actual_weight,
-		Err(err) => err.post_info.actual_weight,
-	}
-}
@@ -19,13 +19,22 @@
 
 use super::*;
 use frame_support::{
-	traits::{GetStorageVersion, OnRuntimeUpgrade, WrapperKeepOpaque},
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion, WrapperKeepOpaque},
 	Identity,
 };
 
 #[cfg(feature = "try-runtime")]
 use frame_support::ensure;
 
+/// Records each of the `approvals` of an operation opened at `height` as given at that block,
+/// without a note, as their actual blocks were not stored before.
+fn approvals_at<AccountId, BlockNumber: Copy, MaxApprovals: Get<u32>>(
+	approvals: BoundedVec<AccountId, MaxApprovals>,
+	height: BlockNumber,
+) -> BoundedVec<Approval<AccountId, BlockNumber>, MaxApprovals> {
+	BoundedVec::truncate_from(approvals.into_iter().map(|who| (who, height, None)).collect())
+}
+
 pub mod v1 {
 	use super::*;
 
@@ -51,7 +60,6 @@ pub mod v1 {
 		fn on_runtime_upgrade() -> Weight {
 			use sp_runtime::Saturating;
 
-			let current = Pallet::<T>::in_code_storage_version();
 			let onchain = Pallet::<T>::on_chain_storage_version();
 
 			if onchain > 0 {
@@ -61,11 +69,11 @@ pub mod v1 {
 
 			let mut call_count = 0u64;
 			Calls::<T>::drain().for_each(|(_call_hash, (_data, caller, deposit))| {
-				T::Currency::unreserve(&caller, deposit);
+				T::OldCurrency::unreserve(&caller, deposit);
 				call_count.saturating_inc();
 			});
 
-			current.put::<Pallet<T>>();
+			StorageVersion::new(1).put::<Pallet<T>>();
 
 			T::DbWeight::get().reads_writes(
 				// Reads: Get Calls + Get Version
@@ -85,3 +93,621 @@ pub mod v1 {
 		}
 	}
 }
+
+pub mod v2 {
+	use super::*;
+	use frame_support::{
+		migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade, Blake2_128Concat,
+		Twox64Concat,
+	};
+
+	/// The `Multisig` struct as stored before the `metadata` field was added.
+	#[derive(Encode, Decode)]
+	struct OldMultisig<BlockNumber, Balance, AccountId, MaxApprovals>
+	where
+		MaxApprovals: Get<u32>,
+	{
+		when: Timepoint<BlockNumber>,
+		deposit: Balance,
+		depositor: AccountId,
+		approvals: BoundedVec<AccountId, MaxApprovals>,
+	}
+
+	type OldMultisigOf<T> = OldMultisig<
+		BlockNumberFor<T>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::AccountId,
+		<T as Config>::MaxSignatories,
+	>;
+
+	/// The open multisig operations in the layout written by this migration.
+	#[frame_support::storage_alias]
+	type Multisigs<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Twox64Concat,
+		<T as frame_system::Config>::AccountId,
+		Blake2_128Concat,
+		[u8; 32],
+		v3::OldMultisigOf<T>,
+	>;
+
+	/// Adds the empty `metadata` field to all open multisig operations.
+	pub struct UncheckedMigrateToV2<T>(core::marker::PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrateToV2<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			let count = Multisigs::<T>::iter_keys().count() as u32;
+			log!(info, "Number of multisigs to migrate: {}", count);
+
+			Ok(count.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			use sp_runtime::Saturating;
+
+			let mut count = 0u64;
+			Multisigs::<T>::translate::<OldMultisigOf<T>, _>(|_, _, old| {
+				count.saturating_inc();
+				Some(v3::OldMultisig {
+					when: old.when,
+					deposit: old.deposit,
+					depositor: old.depositor,
+					approvals: old.approvals,
+					metadata: None,
+				})
+			});
+
+			// Reads: Get Multisigs, Writes: Multisigs
+			T::DbWeight::get().reads_writes(count, count)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+			ensure!(
+				Multisigs::<T>::iter_values().count() as u32 == count,
+				"some multisigs were not migrated"
+			);
+			ensure!(
+				Multisigs::<T>::iter_values().all(|m| m.metadata.is_none()),
+				"migrated multisigs should not have metadata"
+			);
+			Ok(())
+		}
+	}
+
+	/// [`UncheckedMigrateToV2`], run only if the on-chain storage version is 1.
+	pub type MigrateToV2<T> = VersionedMigration<
+		1,
+		2,
+		UncheckedMigrateToV2<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v3 {
+	use super::*;
+	use frame_support::{
+		migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade, Blake2_128Concat,
+		Twox64Concat,
+	};
+
+	/// The `Multisig` struct as stored before the `paused_until` field was added.
+	#[derive(Encode, Decode)]
+	pub(crate) struct OldMultisig<BlockNumber, Balance, AccountId, MaxApprovals>
+	where
+		MaxApprovals: Get<u32>,
+	{
+		pub(crate) when: Timepoint<BlockNumber>,
+		pub(crate) deposit: Balance,
+		pub(crate) depositor: AccountId,
+		pub(crate) approvals: BoundedVec<AccountId, MaxApprovals>,
+		pub(crate) metadata: Option<Metadata>,
+	}
+
+	pub(crate) type OldMultisigOf<T> = OldMultisig<
+		BlockNumberFor<T>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::AccountId,
+		<T as Config>::MaxSignatories,
+	>;
+
+	/// The open multisig operations in the layout written by this migration.
+	#[frame_support::storage_alias]
+	type Multisigs<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Twox64Concat,
+		<T as frame_system::Config>::AccountId,
+		Blake2_128Concat,
+		[u8; 32],
+		v4::OldMultisigOf<T>,
+	>;
+
+	/// Marks all open multisig operations as not paused.
+	pub struct UncheckedMigrateToV3<T>(core::marker::PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrateToV3<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			let count = Multisigs::<T>::iter_keys().count() as u32;
+			log!(info, "Number of multisigs to migrate: {}", count);
+
+			Ok(count.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			use sp_runtime::Saturating;
+
+			let mut count = 0u64;
+			Multisigs::<T>::translate::<OldMultisigOf<T>, _>(|_, _, old| {
+				count.saturating_inc();
+				Some(v4::OldMultisig {
+					when: old.when,
+					deposit: old.deposit,
+					depositor: old.depositor,
+					approvals: old.approvals,
+					metadata: old.metadata,
+					paused_until: None,
+				})
+			});
+
+			// Reads: Get Multisigs, Writes: Multisigs
+			T::DbWeight::get().reads_writes(count, count)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+			ensure!(
+				Multisigs::<T>::iter_values().count() as u32 == count,
+				"some multisigs were not migrated"
+			);
+			ensure!(
+				Multisigs::<T>::iter_values().all(|m| m.paused_until.is_none()),
+				"migrated multisigs should not be paused"
+			);
+			Ok(())
+		}
+	}
+
+	/// [`UncheckedMigrateToV3`], run only if the on-chain storage version is 2.
+	pub type MigrateToV3<T> = VersionedMigration<
+		2,
+		3,
+		UncheckedMigrateToV3<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v4 {
+	use super::*;
+	use frame_support::{
+		migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade, Blake2_128Concat,
+		Twox64Concat,
+	};
+
+	/// The `Multisig` struct as stored before the `execute_after` field was added.
+	#[derive(Encode, Decode)]
+	pub(crate) struct OldMultisig<BlockNumber, Balance, AccountId, MaxApprovals>
+	where
+		MaxApprovals: Get<u32>,
+	{
+		pub(crate) when: Timepoint<BlockNumber>,
+		pub(crate) deposit: Balance,
+		pub(crate) depositor: AccountId,
+		pub(crate) approvals: BoundedVec<AccountId, MaxApprovals>,
+		pub(crate) metadata: Option<Metadata>,
+		pub(crate) paused_until: Option<BlockNumber>,
+	}
+
+	pub(crate) type OldMultisigOf<T> = OldMultisig<
+		BlockNumberFor<T>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::AccountId,
+		<T as Config>::MaxSignatories,
+	>;
+
+	/// The open multisig operations in the layout written by this migration.
+	#[frame_support::storage_alias]
+	type Multisigs<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Twox64Concat,
+		<T as frame_system::Config>::AccountId,
+		Blake2_128Concat,
+		[u8; 32],
+		v5::OldMultisigOf<T>,
+	>;
+
+	/// Marks all open multisig operations as not announced.
+	pub struct UncheckedMigrateToV4<T>(core::marker::PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrateToV4<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			let count = Multisigs::<T>::iter_keys().count() as u32;
+			log!(info, "Number of multisigs to migrate: {}", count);
+
+			Ok(count.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			use sp_runtime::Saturating;
+
+			let mut count = 0u64;
+			Multisigs::<T>::translate::<OldMultisigOf<T>, _>(|_, _, old| {
+				count.saturating_inc();
+				Some(v5::OldMultisig {
+					when: old.when,
+					deposit: old.deposit,
+					depositor: old.depositor,
+					approvals: old.approvals,
+					metadata: old.metadata,
+					paused_until: old.paused_until,
+					execute_after: None,
+				})
+			});
+
+			// Reads: Get Multisigs, Writes: Multisigs
+			T::DbWeight::get().reads_writes(count, count)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+			ensure!(
+				Multisigs::<T>::iter_values().count() as u32 == count,
+				"some multisigs were not migrated"
+			);
+			ensure!(
+				Multisigs::<T>::iter_values().all(|m| m.execute_after.is_none()),
+				"migrated multisigs should not be announced"
+			);
+			Ok(())
+		}
+	}
+
+	/// [`UncheckedMigrateToV4`], run only if the on-chain storage version is 3.
+	pub type MigrateToV4<T> = VersionedMigration<
+		3,
+		4,
+		UncheckedMigrateToV4<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v5 {
+	use super::*;
+	use frame_support::{
+		migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade, Blake2_128Concat,
+		Twox64Concat,
+	};
+
+	/// The `Multisig` struct as stored before the `deposit_held` field was added.
+	#[derive(Encode, Decode)]
+	pub(crate) struct OldMultisig<BlockNumber, Balance, AccountId, MaxApprovals>
+	where
+		MaxApprovals: Get<u32>,
+	{
+		pub(crate) when: Timepoint<BlockNumber>,
+		pub(crate) deposit: Balance,
+		pub(crate) depositor: AccountId,
+		pub(crate) approvals: BoundedVec<AccountId, MaxApprovals>,
+		pub(crate) metadata: Option<Metadata>,
+		pub(crate) paused_until: Option<BlockNumber>,
+		pub(crate) execute_after: Option<BlockNumber>,
+	}
+
+	pub(crate) type OldMultisigOf<T> = OldMultisig<
+		BlockNumberFor<T>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::AccountId,
+		<T as Config>::MaxSignatories,
+	>;
+
+	/// The open multisig operations in the layout written by this migration.
+	#[frame_support::storage_alias]
+	type Multisigs<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Twox64Concat,
+		<T as frame_system::Config>::AccountId,
+		Blake2_128Concat,
+		[u8; 32],
+		v6::OldMultisigOf<T>,
+	>;
+
+	/// Marks the deposits of all open multisig operations as reserved.
+	///
+	/// The deposits themselves are not touched. They are turned into holds lazily, through
+	/// [`Pallet::migrate_deposit`], or unreserved once the operation ends.
+	pub struct UncheckedMigrateToV5<T>(core::marker::PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrateToV5<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			let count = Multisigs::<T>::iter_keys().count() as u32;
+			log!(info, "Number of multisigs to migrate: {}", count);
+
+			Ok(count.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			use sp_runtime::Saturating;
+
+			let mut count = 0u64;
+			Multisigs::<T>::translate::<OldMultisigOf<T>, _>(|_, _, old| {
+				count.saturating_inc();
+				Some(v6::OldMultisig {
+					when: old.when,
+					deposit: old.deposit,
+					depositor: old.depositor,
+					approvals: old.approvals,
+					metadata: old.metadata,
+					paused_until: old.paused_until,
+					execute_after: old.execute_after,
+					deposit_held: false,
+				})
+			});
+
+			// Reads: Get Multisigs, Writes: Multisigs
+			T::DbWeight::get().reads_writes(count, count)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+			ensure!(
+				Multisigs::<T>::iter_values().count() as u32 == count,
+				"some multisigs were not migrated"
+			);
+			ensure!(
+				Multisigs::<T>::iter_values().all(|m| !m.deposit_held),
+				"migrated multisigs should have their deposit reserved"
+			);
+			Ok(())
+		}
+	}
+
+	/// [`UncheckedMigrateToV5`], run only if the on-chain storage version is 4.
+	pub type MigrateToV5<T> = VersionedMigration<
+		4,
+		5,
+		UncheckedMigrateToV5<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v6 {
+	use super::*;
+	use frame_support::{
+		migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade, Blake2_128Concat,
+		Twox64Concat,
+	};
+
+	/// The `Multisig` struct as stored before the `max_signatories_override` field was added.
+	#[derive(Encode, Decode)]
+	pub(crate) struct OldMultisig<BlockNumber, Balance, AccountId, MaxApprovals>
+	where
+		MaxApprovals: Get<u32>,
+	{
+		pub(crate) when: Timepoint<BlockNumber>,
+		pub(crate) deposit: Balance,
+		pub(crate) depositor: AccountId,
+		pub(crate) approvals: BoundedVec<AccountId, MaxApprovals>,
+		pub(crate) metadata: Option<Metadata>,
+		pub(crate) paused_until: Option<BlockNumber>,
+		pub(crate) execute_after: Option<BlockNumber>,
+		pub(crate) deposit_held: bool,
+	}
+
+	pub(crate) type OldMultisigOf<T> = OldMultisig<
+		BlockNumberFor<T>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::AccountId,
+		<T as Config>::MaxSignatories,
+	>;
+
+	/// The open multisig operations in the layout written by this migration, before approvals
+	/// recorded their block and note.
+	#[frame_support::storage_alias]
+	type Multisigs<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Twox64Concat,
+		<T as frame_system::Config>::AccountId,
+		Blake2_128Concat,
+		[u8; 32],
+		v7::OldMultisigOf<T>,
+	>;
+
+	/// [`OldMultisig`] decoded regardless of `MaxSignatories`, to catch operations whose approvals
+	/// no longer fit.
+	type UnboundedOldMultisigOf<T> = OldMultisig<
+		BlockNumberFor<T>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::AccountId,
+		ConstU32<{ u32::MAX }>,
+	>;
+
+	/// Re-encodes all open multisig operations under the current `MaxSignatories`, without a
+	/// signatory cap.
+	///
+	/// Must be run with the runtime upgrade which changes `MaxSignatories`, if any. Operations
+	/// with more approvals than the new bound are removed and their deposit is returned to the
+	/// depositor.
+	pub struct UncheckedMigrateToV6<T>(core::marker::PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrateToV6<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			use frame_support::storage::unhashed;
+
+			let max = T::MaxSignatories::get() as usize;
+			let oversized = Multisigs::<T>::iter_keys()
+				.filter(|(multi, hash)| {
+					unhashed::get::<UnboundedOldMultisigOf<T>>(&Multisigs::<T>::hashed_key_for(
+						multi, hash,
+					))
+					.map_or(false, |m| m.approvals.len() > max)
+				})
+				.count() as u32;
+			let count = (Multisigs::<T>::iter_keys().count() as u32).saturating_sub(oversized);
+			log!(info, "Number of multisigs to migrate: {}, to refund: {}", count, oversized);
+
+			Ok(count.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			use sp_runtime::Saturating;
+
+			let max = T::MaxSignatories::get() as usize;
+			let mut count = 0u64;
+			let mut refunded = 0u64;
+			Multisigs::<T>::translate::<UnboundedOldMultisigOf<T>, _>(|_, _, old| {
+				count.saturating_inc();
+				if old.approvals.len() > max {
+					refunded.saturating_inc();
+					if old.deposit_held {
+						let _ = T::Currency::release(
+							&HoldReason::MultisigDeposit.into(),
+							&old.depositor,
+							old.deposit,
+							Precision::BestEffort,
+						);
+					} else {
+						T::OldCurrency::unreserve(&old.depositor, old.deposit);
+					}
+					return None
+				}
+				Some(v7::OldMultisig {
+					when: old.when,
+					deposit: old.deposit,
+					depositor: old.depositor,
+					approvals: BoundedVec::truncate_from(old.approvals.into_inner()),
+					metadata: old.metadata,
+					paused_until: old.paused_until,
+					execute_after: old.execute_after,
+					deposit_held: old.deposit_held,
+					max_signatories_override: None,
+				})
+			});
+
+			// Reads: Get Multisigs, Writes: Multisigs + Refunds
+			T::DbWeight::get().reads_writes(count, count.saturating_add(refunded))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+			ensure!(
+				Multisigs::<T>::iter_values().count() as u32 == count,
+				"some multisigs were not migrated"
+			);
+			ensure!(
+				Multisigs::<T>::iter_values().all(|m| m.max_signatories_override.is_none()),
+				"migrated multisigs should not have a signatory cap"
+			);
+			Ok(())
+		}
+	}
+
+	/// [`UncheckedMigrateToV6`], run only if the on-chain storage version is 5.
+	pub type MigrateToV6<T> = VersionedMigration<
+		5,
+		6,
+		UncheckedMigrateToV6<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+pub mod v7 {
+	use super::*;
+	use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+	/// The `Multisig` struct as stored before approvals recorded their block and note.
+	#[derive(Encode, Decode)]
+	pub(crate) struct OldMultisig<BlockNumber, Balance, AccountId, MaxApprovals>
+	where
+		MaxApprovals: Get<u32>,
+	{
+		pub(crate) when: Timepoint<BlockNumber>,
+		pub(crate) deposit: Balance,
+		pub(crate) depositor: AccountId,
+		pub(crate) approvals: BoundedVec<AccountId, MaxApprovals>,
+		pub(crate) metadata: Option<Metadata>,
+		pub(crate) paused_until: Option<BlockNumber>,
+		pub(crate) execute_after: Option<BlockNumber>,
+		pub(crate) deposit_held: bool,
+		pub(crate) max_signatories_override: Option<u16>,
+	}
+
+	pub(crate) type OldMultisigOf<T> = OldMultisig<
+		BlockNumberFor<T>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::AccountId,
+		<T as Config>::MaxSignatories,
+	>;
+
+	/// Re-encodes the approvals of all open multisig operations with their block and note.
+	///
+	/// The block of an approval was not stored before, so the height at which the operation was
+	/// opened is recorded for all of its approvals as a best-effort approximation.
+	pub struct UncheckedMigrateToV7<T>(core::marker::PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for UncheckedMigrateToV7<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			let count = Multisigs::<T>::iter_keys().count() as u32;
+			log!(info, "Number of multisigs to migrate: {}", count);
+
+			Ok(count.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			use sp_runtime::Saturating;
+
+			let mut count = 0u64;
+			Multisigs::<T>::translate::<OldMultisigOf<T>, _>(|_, _, old| {
+				count.saturating_inc();
+				Some(Multisig {
+					when: old.when,
+					deposit: old.deposit,
+					depositor: old.depositor,
+					approvals: approvals_at(old.approvals, old.when.height),
+					metadata: old.metadata,
+					paused_until: old.paused_until,
+					execute_after: old.execute_after,
+					deposit_held: old.deposit_held,
+					max_signatories_override: old.max_signatories_override,
+				})
+			});
+
+			// Reads: Get Multisigs, Writes: Multisigs
+			T::DbWeight::get().reads_writes(count, count)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "invalid pre-upgrade state")?;
+			ensure!(
+				Multisigs::<T>::iter_values().count() as u32 == count,
+				"some multisigs were not migrated"
+			);
+			ensure!(
+				Multisigs::<T>::iter_values().all(|m| m
+					.approvals
+					.iter()
+					.all(|(_, at, note)| *at == m.when.height && note.is_none())),
+				"migrated approvals should be given at the opening block without a note"
+			);
+			Ok(())
+		}
+	}
+
+	/// [`UncheckedMigrateToV7`], run only if the on-chain storage version is 6.
+	pub type MigrateToV7<T> = VersionedMigration<
+		6,
+		7,
+		UncheckedMigrateToV7<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
@@ -23,9 +23,10 @@ use super::*;
 
 use crate as pallet_multisig;
 use frame_support::{
-	assert_noop, assert_ok, derive_impl,
-	traits::{ConstU32, ConstU64, Contains},
+	assert_noop, assert_ok, derive_impl, ord_parameter_types, parameter_types,
+	traits::{fungible::InspectHold, tokens::imbalance::ResolveTo, ConstU32, ConstU64, Contains},
 };
+use frame_system::EnsureSignedBy;
 use sp_runtime::{BuildStorage, TokenError};
 
 type Block = frame_system::mocking::MockBlockU32<Test>;
@@ -35,6 +36,7 @@ frame_support::construct_runtime!(
 		System: frame_system,
 		Balances: pallet_balances,
 		Multisig: pallet_multisig,
+		Utility: pallet_utility,
 	}
 );
 
@@ -50,6 +52,7 @@ impl frame_system::Config for Test {
 impl pallet_balances::Config for Test {
 	type ReserveIdentifier = [u8; 8];
 	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
 }
 
 pub struct TestBaseCallFilter;
@@ -57,28 +60,67 @@ impl Contains<RuntimeCall> for TestBaseCallFilter {
 	fn contains(c: &RuntimeCall) -> bool {
 		match *c {
 			RuntimeCall::Balances(_) => true,
+			// Needed for nested multisigs
+			RuntimeCall::Multisig(_) => true,
+			// Needed for call filters of batches
+			RuntimeCall::Utility(_) => true,
 			// Needed for benchmarking
 			RuntimeCall::System(frame_system::Call::remark { .. }) => true,
 			_ => false,
 		}
 	}
 }
+impl pallet_utility::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
+	type WeightInfo = ();
+}
+
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
 	type Currency = Balances;
+	type OldCurrency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type DepositBase = ConstU64<1>;
 	type DepositFactor = ConstU64<1>;
 	type MaxSignatories = ConstU32<3>;
+	type GuardianOrigin = EnsureSignedBy<Guardian, u64>;
+	type MaxPauseDuration = ConstU32<10>;
+	type CancelPenalty = CancelPenalty;
+	type CancelSlashHandler = ResolveTo<Treasury, Balances>;
+	type DepositExempt = ExemptRemarks;
+	type LenientSignatoryOrder = LenientSignatoryOrder;
 	type WeightInfo = ();
 }
 
+ord_parameter_types! {
+	pub const Guardian: u64 = 42;
+}
+
+parameter_types! {
+	pub static CancelPenalty: Perbill = Perbill::zero();
+	pub const Treasury: u64 = 7;
+	pub static RemarksExempt: bool = false;
+	pub static LenientSignatoryOrder: bool = false;
+}
+
+/// Exempts `frame_system::remark` from the deposit, while `RemarksExempt` is set.
+pub struct ExemptRemarks;
+impl Contains<RuntimeCall> for ExemptRemarks {
+	fn contains(c: &RuntimeCall) -> bool {
+		RemarksExempt::get() && matches!(c, RuntimeCall::System(frame_system::Call::remark { .. }))
+	}
+}
+
 use pallet_balances::Call as BalancesCall;
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
 	pallet_balances::GenesisConfig::<Test> {
 		balances: vec![(1, 10), (2, 10), (3, 10), (4, 10), (5, 2)],
+		..Default::default()
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
@@ -111,7 +153,9 @@ fn multisig_deposit_is_taken_and_returned() {
 			vec![2, 3],
 			None,
 			call.clone(),
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_eq!(Balances::free_balance(1), 2);
 		assert_eq!(Balances::reserved_balance(1), 3);
@@ -122,7 +166,9 @@ fn multisig_deposit_is_taken_and_returned() {
 			vec![1, 3],
 			Some(now()),
 			call,
-			call_weight
+			call_weight,
+			None,
+			None
 		));
 		assert_eq!(Balances::free_balance(1), 5);
 		assert_eq!(Balances::reserved_balance(1), 0);
@@ -140,7 +186,9 @@ fn cancel_multisig_returns_deposit() {
 			vec![2, 3],
 			None,
 			hash,
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_ok!(Multisig::approve_as_multi(
 			RuntimeOrigin::signed(2),
@@ -148,7 +196,9 @@ fn cancel_multisig_returns_deposit() {
 			vec![1, 3],
 			Some(now()),
 			hash,
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_eq!(Balances::free_balance(1), 6);
 		assert_eq!(Balances::reserved_balance(1), 4);
@@ -176,7 +226,9 @@ fn timepoint_checking_works() {
 				vec![1, 3],
 				Some(now()),
 				hash,
-				Weight::zero()
+				Weight::zero(),
+				None,
+				None
 			),
 			Error::<Test>::UnexpectedTimepoint,
 		);
@@ -187,7 +239,9 @@ fn timepoint_checking_works() {
 			vec![2, 3],
 			None,
 			hash,
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 
 		assert_noop!(
@@ -197,7 +251,9 @@ fn timepoint_checking_works() {
 				vec![1, 3],
 				None,
 				call.clone(),
-				Weight::zero()
+				Weight::zero(),
+				None,
+				None
 			),
 			Error::<Test>::NoTimepoint,
 		);
@@ -209,7 +265,9 @@ fn timepoint_checking_works() {
 				vec![1, 3],
 				Some(later),
 				call,
-				Weight::zero()
+				Weight::zero(),
+				None,
+				None
 			),
 			Error::<Test>::WrongTimepoint,
 		);
@@ -233,7 +291,9 @@ fn multisig_2_of_3_works() {
 			vec![2, 3],
 			None,
 			hash,
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_eq!(Balances::free_balance(6), 0);
 
@@ -243,9 +303,24 @@ fn multisig_2_of_3_works() {
 			vec![1, 3],
 			Some(now()),
 			call,
-			call_weight
+			call_weight,
+			None,
+			None
 		));
 		assert_eq!(Balances::free_balance(6), 15);
+
+		// The event follows the events of the dispatched call.
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigExecuted {
+				approving: 2,
+				timepoint: now(),
+				multisig: multi,
+				call_hash: hash,
+				result: Ok(()),
+				weight: call_weight,
+			}
+			.into(),
+		);
 	});
 }
 
@@ -266,7 +341,9 @@ fn multisig_3_of_3_works() {
 			vec![2, 3],
 			None,
 			hash,
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_ok!(Multisig::approve_as_multi(
 			RuntimeOrigin::signed(2),
@@ -274,7 +351,9 @@ fn multisig_3_of_3_works() {
 			vec![1, 3],
 			Some(now()),
 			hash,
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_eq!(Balances::free_balance(6), 0);
 
@@ -284,7 +363,9 @@ fn multisig_3_of_3_works() {
 			vec![1, 2],
 			Some(now()),
 			call,
-			call_weight
+			call_weight,
+			None,
+			None
 		));
 		assert_eq!(Balances::free_balance(6), 15);
 	});
@@ -301,7 +382,9 @@ fn cancel_multisig_works() {
 			vec![2, 3],
 			None,
 			hash,
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_ok!(Multisig::approve_as_multi(
 			RuntimeOrigin::signed(2),
@@ -309,7 +392,9 @@ fn cancel_multisig_works() {
 			vec![1, 3],
 			Some(now()),
 			hash,
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_noop!(
 			Multisig::cancel_as_multi(RuntimeOrigin::signed(2), 3, vec![1, 3], now(), hash),
@@ -319,6 +404,112 @@ fn cancel_multisig_works() {
 	});
 }
 
+#[test]
+fn approve_as_multi_latest_works_after_cancel_and_recreate() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 3);
+		let call = call_transfer(6, 15).encode();
+		let hash = blake2_256(&call);
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			3,
+			vec![2, 3],
+			None,
+			hash,
+			Weight::zero(),
+			None,
+			None
+		));
+		let stale = now();
+		assert_ok!(Multisig::cancel_as_multi(RuntimeOrigin::signed(1), 3, vec![2, 3], stale, hash));
+
+		System::set_block_number(2);
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			3,
+			vec![2, 3],
+			None,
+			hash,
+			Weight::zero(),
+			None,
+			None
+		));
+		let fresh = now();
+
+		// The timepoint cached before the operation was recreated no longer works...
+		assert_noop!(
+			Multisig::approve_as_multi(
+				RuntimeOrigin::signed(2),
+				3,
+				vec![1, 3],
+				Some(stale),
+				hash,
+				Weight::zero(),
+				None,
+				None
+			),
+			Error::<Test>::WrongTimepoint,
+		);
+		// ...but approving without a timepoint picks up the recreated operation.
+		assert_ok!(Multisig::approve_as_multi_latest(
+			RuntimeOrigin::signed(2),
+			3,
+			vec![1, 3],
+			hash,
+			Weight::zero()
+		));
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigApproval {
+				approving: 2,
+				timepoint: fresh,
+				multisig: multi,
+				call_hash: hash,
+			}
+			.into(),
+		);
+		let approvals = Multisigs::<Test>::get(multi, hash).unwrap().approvals;
+		assert_eq!(approvals.iter().map(|(who, ..)| *who).collect::<Vec<_>>(), vec![1, 2]);
+
+		assert_noop!(
+			Multisig::approve_as_multi_latest(
+				RuntimeOrigin::signed(2),
+				3,
+				vec![1, 3],
+				hash,
+				Weight::zero()
+			),
+			Error::<Test>::AlreadyApproved,
+		);
+	});
+}
+
+#[test]
+fn approve_as_multi_latest_requires_operation_underway() {
+	new_test_ext().execute_with(|| {
+		let hash = blake2_256(&call_transfer(6, 15).encode());
+		assert_noop!(
+			Multisig::approve_as_multi_latest(
+				RuntimeOrigin::signed(1),
+				2,
+				vec![2, 3],
+				hash,
+				Weight::zero()
+			),
+			Error::<Test>::NotFound,
+		);
+		assert_noop!(
+			Multisig::approve_as_multi_latest(
+				RuntimeOrigin::signed(1),
+				2,
+				vec![3, 2],
+				hash,
+				Weight::zero()
+			),
+			Error::<Test>::SignatoriesOutOfOrderAt(1),
+		);
+	});
+}
+
 #[test]
 fn multisig_2_of_3_as_multi_works() {
 	new_test_ext().execute_with(|| {
@@ -335,7 +526,9 @@ fn multisig_2_of_3_as_multi_works() {
 			vec![2, 3],
 			None,
 			call.clone(),
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_eq!(Balances::free_balance(6), 0);
 
@@ -345,7 +538,9 @@ fn multisig_2_of_3_as_multi_works() {
 			vec![1, 3],
 			Some(now()),
 			call,
-			call_weight
+			call_weight,
+			None,
+			None
 		));
 		assert_eq!(Balances::free_balance(6), 15);
 	});
@@ -370,7 +565,9 @@ fn multisig_2_of_3_as_multi_with_many_calls_works() {
 			vec![2, 3],
 			None,
 			call1.clone(),
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_ok!(Multisig::as_multi(
 			RuntimeOrigin::signed(2),
@@ -378,7 +575,9 @@ fn multisig_2_of_3_as_multi_with_many_calls_works() {
 			vec![1, 3],
 			None,
 			call2.clone(),
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_ok!(Multisig::as_multi(
 			RuntimeOrigin::signed(3),
@@ -386,7 +585,9 @@ fn multisig_2_of_3_as_multi_with_many_calls_works() {
 			vec![1, 2],
 			Some(now()),
 			call1,
-			call1_weight
+			call1_weight,
+			None,
+			None
 		));
 		assert_ok!(Multisig::as_multi(
 			RuntimeOrigin::signed(3),
@@ -394,7 +595,9 @@ fn multisig_2_of_3_as_multi_with_many_calls_works() {
 			vec![1, 2],
 			Some(now()),
 			call2,
-			call2_weight
+			call2_weight,
+			None,
+			None
 		));
 
 		assert_eq!(Balances::free_balance(6), 10);
@@ -419,7 +622,9 @@ fn multisig_2_of_3_cannot_reissue_same_call() {
 			vec![2, 3],
 			None,
 			call.clone(),
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_ok!(Multisig::as_multi(
 			RuntimeOrigin::signed(2),
@@ -427,7 +632,9 @@ fn multisig_2_of_3_cannot_reissue_same_call() {
 			vec![1, 3],
 			Some(now()),
 			call.clone(),
-			call_weight
+			call_weight,
+			None,
+			None
 		));
 		assert_eq!(Balances::free_balance(multi), 5);
 
@@ -437,7 +644,9 @@ fn multisig_2_of_3_cannot_reissue_same_call() {
 			vec![2, 3],
 			None,
 			call.clone(),
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_ok!(Multisig::as_multi(
 			RuntimeOrigin::signed(3),
@@ -445,19 +654,92 @@ fn multisig_2_of_3_cannot_reissue_same_call() {
 			vec![1, 2],
 			Some(now()),
 			call.clone(),
-			call_weight
+			call_weight,
+			None,
+			None
 		));
 
-		System::assert_last_event(
+		System::assert_has_event(
 			pallet_multisig::Event::MultisigExecuted {
 				approving: 3,
 				timepoint: now(),
 				multisig: multi,
 				call_hash: hash,
 				result: Err(TokenError::FundsUnavailable.into()),
+				weight: call_weight,
+			}
+			.into(),
+		);
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigExecutionFailed {
+				multisig: multi,
+				call_hash: hash,
+				error: TokenError::FundsUnavailable.into(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn multisig_execution_failure_carries_module_error() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		// Rejected by `TestBaseCallFilter`, which fails the dispatch with a module error.
+		let call: Box<RuntimeCall> =
+			Box::new(frame_system::Call::remark_with_event { remark: vec![1] }.into());
+		let call_weight = call.get_dispatch_info().weight;
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			None,
+			call.clone(),
+			Weight::zero(),
+			None,
+			None
+		));
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			2,
+			vec![1, 3],
+			Some(now()),
+			call,
+			call_weight,
+			None,
+			None
+		));
+
+		let error = DispatchError::from(frame_system::Error::<Test>::CallFiltered);
+		assert!(matches!(error, DispatchError::Module(_)));
+		System::assert_has_event(
+			pallet_multisig::Event::MultisigExecuted {
+				approving: 2,
+				timepoint: now(),
+				multisig: multi,
+				call_hash: hash,
+				result: Err(error),
+				weight: call_weight,
+			}
+			.into(),
+		);
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigExecutionFailed {
+				multisig: multi,
+				call_hash: hash,
+				error,
 			}
 			.into(),
 		);
+
+		// The error survives the encoding of the event.
+		let event = System::events().pop().unwrap().event;
+		match RuntimeEvent::decode(&mut &event.encode()[..]).unwrap() {
+			RuntimeEvent::Multisig(Event::MultisigExecutionFailed { error: decoded, .. }) =>
+				assert_eq!(decoded, error),
+			other => panic!("unexpected event: {:?}", other),
+		}
 	});
 }
 
@@ -472,7 +754,9 @@ fn minimum_threshold_check_works() {
 				vec![2],
 				None,
 				call.clone(),
-				Weight::zero()
+				Weight::zero(),
+				None,
+				None
 			),
 			Error::<Test>::MinimumThreshold,
 		);
@@ -483,7 +767,9 @@ fn minimum_threshold_check_works() {
 				vec![2],
 				None,
 				call.clone(),
-				Weight::zero()
+				Weight::zero(),
+				None,
+				None
 			),
 			Error::<Test>::MinimumThreshold,
 		);
@@ -501,7 +787,9 @@ fn too_many_signatories_fails() {
 				vec![2, 3, 4],
 				None,
 				call.clone(),
-				Weight::zero()
+				Weight::zero(),
+				None,
+				None
 			),
 			Error::<Test>::TooManySignatories,
 		);
@@ -519,7 +807,9 @@ fn duplicate_approvals_are_ignored() {
 			vec![2, 3],
 			None,
 			hash,
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_noop!(
 			Multisig::approve_as_multi(
@@ -528,7 +818,9 @@ fn duplicate_approvals_are_ignored() {
 				vec![2, 3],
 				Some(now()),
 				hash,
-				Weight::zero()
+				Weight::zero(),
+				None,
+				None
 			),
 			Error::<Test>::AlreadyApproved,
 		);
@@ -538,7 +830,9 @@ fn duplicate_approvals_are_ignored() {
 			vec![1, 3],
 			Some(now()),
 			hash,
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_noop!(
 			Multisig::approve_as_multi(
@@ -547,7 +841,9 @@ fn duplicate_approvals_are_ignored() {
 				vec![1, 2],
 				Some(now()),
 				hash,
-				Weight::zero()
+				Weight::zero(),
+				None,
+				None
 			),
 			Error::<Test>::AlreadyApproved,
 		);
@@ -571,7 +867,9 @@ fn multisig_1_of_3_works() {
 				vec![2, 3],
 				None,
 				hash,
-				Weight::zero()
+				Weight::zero(),
+				None,
+				None
 			),
 			Error::<Test>::MinimumThreshold,
 		);
@@ -582,7 +880,9 @@ fn multisig_1_of_3_works() {
 				vec![2, 3],
 				None,
 				call.clone(),
-				Weight::zero()
+				Weight::zero(),
+				None,
+				None
 			),
 			Error::<Test>::MinimumThreshold,
 		);
@@ -622,7 +922,9 @@ fn weight_check_works() {
 			vec![2, 3],
 			None,
 			call.clone(),
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_eq!(Balances::free_balance(6), 0);
 
@@ -633,7 +935,9 @@ fn weight_check_works() {
 				vec![1, 3],
 				Some(now()),
 				call,
-				Weight::zero()
+				Weight::zero(),
+				None,
+				None
 			),
 			Error::<Test>::MaxWeightTooLow,
 		);
@@ -660,7 +964,9 @@ fn multisig_handles_no_preimage_after_all_approve() {
 			vec![2, 3],
 			None,
 			hash,
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_ok!(Multisig::approve_as_multi(
 			RuntimeOrigin::signed(2),
@@ -668,7 +974,9 @@ fn multisig_handles_no_preimage_after_all_approve() {
 			vec![1, 3],
 			Some(now()),
 			hash,
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_ok!(Multisig::approve_as_multi(
 			RuntimeOrigin::signed(3),
@@ -676,7 +984,9 @@ fn multisig_handles_no_preimage_after_all_approve() {
 			vec![1, 2],
 			Some(now()),
 			hash,
-			Weight::zero()
+			Weight::zero(),
+			None,
+			None
 		));
 		assert_eq!(Balances::free_balance(6), 0);
 
@@ -686,8 +996,1940 @@ fn multisig_handles_no_preimage_after_all_approve() {
 			vec![1, 2],
 			Some(now()),
 			call,
-			call_weight
+			call_weight,
+			None,
+			None
 		));
 		assert_eq!(Balances::free_balance(6), 15);
 	});
 }
+
+#[test]
+fn as_multi_refunds_unused_max_weight() {
+	new_test_ext().execute_with(|| {
+		let call: Box<RuntimeCall> =
+			Box::new(frame_system::Call::remark { remark: vec![1, 2, 3] }.into());
+		let call_weight = call.get_dispatch_info().weight;
+		let call_len = call.encoded_size() as u32;
+		let max_weight = Weight::from_parts(u64::MAX / 2, u64::MAX / 2);
+
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			None,
+			call.clone(),
+			Weight::zero(),
+			None,
+			None
+		));
+		let post_info = Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			2,
+			vec![1, 3],
+			Some(now()),
+			call,
+			max_weight,
+			None,
+			None,
+		)
+		.unwrap();
+
+		// Only the weight actually used by the call is charged, not `max_weight`.
+		let expected = <() as WeightInfo>::as_multi_complete(2, call_len) + call_weight;
+		assert_eq!(post_info.actual_weight, Some(expected));
+		assert!(expected.all_lt(max_weight));
+	});
+}
+
+#[test]
+fn as_multi_refunds_unused_max_weight_when_call_fails() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		// The multisig account has no funds, so the transfer fails.
+		let call = call_transfer(6, 15);
+		let call_weight = call.get_dispatch_info().weight;
+		let call_len = call.encoded_size() as u32;
+		let hash = blake2_256(&call.encode());
+		let max_weight = Weight::from_parts(u64::MAX / 2, u64::MAX / 2);
+
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			None,
+			call.clone(),
+			Weight::zero(),
+			None,
+			None
+		));
+		let post_info = Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			2,
+			vec![1, 3],
+			Some(now()),
+			call,
+			max_weight,
+			None,
+			None,
+		)
+		.unwrap();
+
+		System::assert_has_event(
+			pallet_multisig::Event::MultisigExecuted {
+				approving: 2,
+				timepoint: now(),
+				multisig: multi,
+				call_hash: hash,
+				result: Err(TokenError::FundsUnavailable.into()),
+				weight: call_weight,
+			}
+			.into(),
+		);
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigExecutionFailed {
+				multisig: multi,
+				call_hash: hash,
+				error: TokenError::FundsUnavailable.into(),
+			}
+			.into(),
+		);
+		let expected = <() as WeightInfo>::as_multi_complete(2, call_len) + call_weight;
+		assert_eq!(post_info.actual_weight, Some(expected));
+	});
+}
+
+fn metadata(len: usize) -> Metadata {
+	vec![7; len].try_into().unwrap()
+}
+
+#[test]
+fn metadata_mismatch_is_rejected() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 3);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), multi, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multi, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 5));
+
+		let call = call_transfer(6, 15);
+		let call_weight = call.get_dispatch_info().weight;
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			3,
+			vec![2, 3],
+			None,
+			hash,
+			Weight::zero(),
+			Some(metadata(4)),
+			None,
+		));
+
+		assert_noop!(
+			Multisig::approve_as_multi(
+				RuntimeOrigin::signed(2),
+				3,
+				vec![1, 3],
+				Some(now()),
+				hash,
+				Weight::zero(),
+				Some(metadata(5)),
+				None,
+			),
+			Error::<Test>::MetadataMismatch,
+		);
+		// Not supplying the metadata skips the check.
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(2),
+			3,
+			vec![1, 3],
+			Some(now()),
+			hash,
+			Weight::zero(),
+			None,
+			None,
+		));
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(3),
+			3,
+			vec![1, 2],
+			Some(now()),
+			call,
+			call_weight,
+			Some(metadata(4)),
+			None,
+		));
+		assert_eq!(Balances::free_balance(6), 15);
+	});
+}
+
+#[test]
+fn metadata_is_required_to_match_operation_without_metadata() {
+	new_test_ext().execute_with(|| {
+		let hash = blake2_256(&call_transfer(6, 15).encode());
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			3,
+			vec![2, 3],
+			None,
+			hash,
+			Weight::zero(),
+			None,
+			None,
+		));
+		assert_noop!(
+			Multisig::approve_as_multi(
+				RuntimeOrigin::signed(2),
+				3,
+				vec![1, 3],
+				Some(now()),
+				hash,
+				Weight::zero(),
+				Some(metadata(1)),
+				None,
+			),
+			Error::<Test>::MetadataMismatch,
+		);
+	});
+}
+
+#[test]
+fn new_multisig_event_contains_metadata_hash() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		let hash = blake2_256(&call_transfer(6, 15).encode());
+		let metadata = metadata(10);
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			None,
+			hash,
+			Weight::zero(),
+			Some(metadata.clone()),
+			None,
+		));
+
+		System::assert_has_event(
+			pallet_multisig::Event::NewMultisig {
+				approving: 1,
+				multisig: multi,
+				call_hash: hash,
+				metadata_hash: Some(blake2_256(&metadata)),
+			}
+			.into(),
+		);
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigMetadata { multisig: multi, call_hash: hash, metadata }
+				.into(),
+		);
+
+		// Without metadata, there is neither a hash nor a metadata event.
+		let hash = blake2_256(&call_transfer(6, 10).encode());
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			None,
+			hash,
+			Weight::zero(),
+			None,
+			None,
+		));
+		System::assert_last_event(
+			pallet_multisig::Event::NewMultisig {
+				approving: 1,
+				multisig: multi,
+				call_hash: hash,
+				metadata_hash: None,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn deposit_scales_with_metadata_length() {
+	new_test_ext().execute_with(|| {
+		// `DepositBase + threshold * DepositFactor`, plus one `DepositFactor` per started 32
+		// bytes of metadata.
+		for (len, deposit) in [(None, 3), (Some(0), 3), (Some(1), 4), (Some(32), 4), (Some(33), 5)]
+		{
+			let hash = blake2_256(&call_transfer(6, len.unwrap_or(100) as u64).encode());
+			assert_ok!(Multisig::approve_as_multi(
+				RuntimeOrigin::signed(1),
+				2,
+				vec![2, 3],
+				None,
+				hash,
+				Weight::zero(),
+				len.map(metadata),
+				None,
+			));
+			assert_eq!(Balances::reserved_balance(1), deposit);
+			assert_ok!(Multisig::cancel_as_multi(
+				RuntimeOrigin::signed(1),
+				2,
+				vec![2, 3],
+				now(),
+				hash
+			));
+			assert_eq!(Balances::reserved_balance(1), 0);
+		}
+		assert_eq!(Multisig::deposit(2, Some(&metadata(MAX_METADATA_LEN as usize))), 5);
+	});
+}
+
+fn pause(multi: u64, hash: [u8; 32]) -> DispatchResult {
+	Multisig::pause_operation(RuntimeOrigin::signed(42), multi, hash)
+}
+
+#[test]
+fn paused_operation_cannot_be_approved_or_executed() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 3);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), multi, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multi, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 5));
+
+		let call = call_transfer(6, 15);
+		let call_weight = call.get_dispatch_info().weight;
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(1),
+			3,
+			vec![2, 3],
+			None,
+			call.clone(),
+			Weight::zero(),
+			None,
+			None
+		));
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(2),
+			3,
+			vec![1, 3],
+			Some(now()),
+			hash,
+			Weight::zero(),
+			None,
+			None
+		));
+
+		assert_ok!(pause(multi, hash));
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigPaused { multisig: multi, call_hash: hash, until: 11 }
+				.into(),
+		);
+		assert_noop!(pause(multi, hash), Error::<Test>::AlreadyPaused);
+
+		assert_noop!(
+			Multisig::as_multi(
+				RuntimeOrigin::signed(3),
+				3,
+				vec![1, 2],
+				Some(now()),
+				call.clone(),
+				call_weight,
+				None,
+				None
+			),
+			Error::<Test>::OperationPaused,
+		);
+		assert_noop!(
+			Multisig::approve_as_multi(
+				RuntimeOrigin::signed(3),
+				3,
+				vec![1, 2],
+				Some(now()),
+				hash,
+				Weight::zero(),
+				None,
+				None
+			),
+			Error::<Test>::OperationPaused,
+		);
+
+		assert_ok!(Multisig::unpause_operation(RuntimeOrigin::signed(42), multi, hash));
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigUnpaused { multisig: multi, call_hash: hash }.into(),
+		);
+		assert_noop!(
+			Multisig::unpause_operation(RuntimeOrigin::signed(42), multi, hash),
+			Error::<Test>::NotPaused,
+		);
+
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(3),
+			3,
+			vec![1, 2],
+			Some(now()),
+			call,
+			call_weight,
+			None,
+			None
+		));
+		assert_eq!(Balances::free_balance(6), 15);
+	});
+}
+
+#[test]
+fn only_guardian_can_pause_existing_operations() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		let hash = blake2_256(&call_transfer(6, 15).encode());
+
+		assert_noop!(pause(multi, hash), Error::<Test>::NotFound);
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			None,
+			hash,
+			Weight::zero(),
+			None,
+			None
+		));
+
+		// Neither the signatories nor root are the guardian.
+		for origin in [RuntimeOrigin::signed(1), RuntimeOrigin::root()] {
+			assert_noop!(
+				Multisig::pause_operation(origin.clone(), multi, hash),
+				DispatchError::BadOrigin,
+			);
+			assert_noop!(
+				Multisig::unpause_operation(origin, multi, hash),
+				DispatchError::BadOrigin,
+			);
+		}
+		assert_noop!(
+			Multisig::unpause_operation(RuntimeOrigin::signed(42), multi, hash),
+			Error::<Test>::NotPaused,
+		);
+	});
+}
+
+#[test]
+fn paused_operation_can_be_cancelled() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		let hash = blake2_256(&call_transfer(6, 15).encode());
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			None,
+			hash,
+			Weight::zero(),
+			None,
+			None
+		));
+		assert_eq!(Balances::reserved_balance(1), 3);
+		assert_ok!(pause(multi, hash));
+
+		assert_ok!(Multisig::cancel_as_multi(RuntimeOrigin::signed(1), 2, vec![2, 3], now(), hash));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(!Multisigs::<Test>::contains_key(multi, hash));
+		assert_noop!(
+			Multisig::unpause_operation(RuntimeOrigin::signed(42), multi, hash),
+			Error::<Test>::NotFound,
+		);
+	});
+}
+
+#[test]
+fn pause_expires_after_max_pause_duration() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 3);
+		let hash = blake2_256(&call_transfer(6, 15).encode());
+		let timepoint = now();
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			3,
+			vec![2, 3],
+			None,
+			hash,
+			Weight::zero(),
+			None,
+			None
+		));
+		assert_ok!(pause(multi, hash));
+
+		System::set_block_number(10);
+		assert_noop!(
+			Multisig::approve_as_multi(
+				RuntimeOrigin::signed(2),
+				3,
+				vec![1, 3],
+				Some(timepoint),
+				hash,
+				Weight::zero(),
+				None,
+				None
+			),
+			Error::<Test>::OperationPaused,
+		);
+
+		// `MaxPauseDuration` blocks after the pause it is lifted on the next approval.
+		System::set_block_number(11);
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(2),
+			3,
+			vec![1, 3],
+			Some(timepoint),
+			hash,
+			Weight::zero(),
+			None,
+			None
+		));
+		System::assert_has_event(
+			pallet_multisig::Event::MultisigPauseExpired { multisig: multi, call_hash: hash }
+				.into(),
+		);
+		let m = Multisigs::<Test>::get(multi, hash).unwrap();
+		assert_eq!(m.paused_until, None);
+		assert_eq!(m.approvals.len(), 2);
+
+		// The guardian may pause it again.
+		assert_ok!(pause(multi, hash));
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigPaused { multisig: multi, call_hash: hash, until: 21 }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn nested_multisig_deposits_are_taken_and_returned() {
+	new_test_ext().execute_with(|| {
+		// A 2-of-2 multisig of account 4 and a 2-of-3 multisig of accounts 1, 2 and 3.
+		let inner = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		let mut outer_signatories = vec![inner, 4];
+		outer_signatories.sort();
+		let outer = Multisig::multi_account_id(&outer_signatories, 2);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), outer, 5));
+
+		let call = call_transfer(6, 5);
+		let call_weight = call.get_dispatch_info().weight;
+		let call_hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(4),
+			2,
+			vec![inner],
+			None,
+			call.clone(),
+			Weight::zero(),
+			None,
+			None
+		));
+		assert_eq!(Balances::free_balance(4), 7);
+		assert_eq!(Balances::reserved_balance(4), 3);
+
+		// The inner multisig approves the outer operation with the final approval.
+		let approve: Box<RuntimeCall> = Box::new(
+			pallet_multisig::Call::as_multi {
+				threshold: 2,
+				other_signatories: vec![4],
+				maybe_timepoint: Some(now()),
+				call: call.clone(),
+				max_weight: call_weight,
+				metadata: None,
+				note: None,
+			}
+			.into(),
+		);
+		let approve_weight = approve.get_dispatch_info().weight;
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			None,
+			approve.clone(),
+			Weight::zero(),
+			None,
+			None
+		));
+		assert_eq!(Balances::free_balance(1), 7);
+		assert_eq!(Balances::reserved_balance(1), 3);
+
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			2,
+			vec![1, 3],
+			Some(now()),
+			approve,
+			approve_weight,
+			None,
+			None
+		));
+		System::assert_has_event(
+			pallet_multisig::Event::MultisigExecuted {
+				approving: inner,
+				timepoint: now(),
+				multisig: outer,
+				call_hash,
+				result: Ok(()),
+				weight: call_weight,
+			}
+			.into(),
+		);
+
+		// The transfer was made and the deposits of both levels were returned.
+		assert_eq!(Balances::free_balance(6), 5);
+		assert_eq!(Balances::free_balance(outer), 0);
+		assert_eq!(Balances::free_balance(inner), 0);
+		assert_eq!(Balances::free_balance(1), 10);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(4), 10);
+		assert_eq!(Balances::reserved_balance(4), 0);
+		assert_eq!(Multisigs::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn announced_operation_executes_from_boundary_block() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 1);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), multi, 5));
+
+		let call = call_transfer(6, 5);
+		let call_weight = call.get_dispatch_info().weight;
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::as_multi_announced(
+			RuntimeOrigin::signed(1),
+			1,
+			vec![2, 3],
+			None,
+			hash,
+			5
+		));
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigAnnounced {
+				approving: 1,
+				multisig: multi,
+				call_hash: hash,
+				execute_after: 6,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(1), 3);
+		assert_eq!(Balances::reserved_balance(1), 2);
+
+		System::set_block_number(5);
+		assert_noop!(
+			Multisig::execute_announced(RuntimeOrigin::signed(4), multi, hash, call.clone()),
+			Error::<Test>::AnnouncementNotDue,
+		);
+
+		System::set_block_number(6);
+		assert_noop!(
+			Multisig::execute_announced(RuntimeOrigin::signed(4), multi, hash, call_transfer(6, 4)),
+			Error::<Test>::CallHashMismatch,
+		);
+		assert_ok!(Multisig::execute_announced(RuntimeOrigin::signed(4), multi, hash, call));
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigAnnouncementExecuted {
+				multisig: multi,
+				call_hash: hash,
+				result: Ok(()),
+				weight: call_weight,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(6), 5);
+		assert_eq!(Balances::free_balance(1), 5);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(!Multisigs::<Test>::contains_key(multi, hash));
+	});
+}
+
+#[test]
+fn announcement_can_be_cancelled_by_any_signatory_before_delay() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 1);
+		let call = call_transfer(6, 5);
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::as_multi_announced(
+			RuntimeOrigin::signed(1),
+			1,
+			vec![2, 3],
+			None,
+			hash,
+			5
+		));
+
+		// Accounts outside of the multisig cannot find the announcement.
+		assert_noop!(
+			Multisig::cancel_announcement(RuntimeOrigin::signed(4), 1, vec![1, 2], hash),
+			Error::<Test>::NotFound,
+		);
+
+		System::set_block_number(5);
+		assert_ok!(Multisig::cancel_announcement(RuntimeOrigin::signed(3), 1, vec![1, 2], hash));
+		System::assert_last_event(
+			pallet_multisig::Event::MultisigAnnouncementCancelled {
+				cancelling: 3,
+				multisig: multi,
+				call_hash: hash,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		System::set_block_number(6);
+		assert_noop!(
+			Multisig::execute_announced(RuntimeOrigin::signed(4), multi, hash, call),
+			Error::<Test>::NotFound,
+		);
+	});
+}
+
+#[test]
+fn announcement_cannot_be_cancelled_once_due() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 1);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), multi, 5));
+		let call = call_transfer(6, 5);
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::as_multi_announced(
+			RuntimeOrigin::signed(1),
+			1,
+			vec![2, 3],
+			None,
+			hash,
+			5
+		));
+
+		System::set_block_number(6);
+		assert_noop!(
+			Multisig::cancel_announcement(RuntimeOrigin::signed(2), 1, vec![1, 3], hash),
+			Error::<Test>::AnnouncementDue,
+		);
+		assert_ok!(Multisig::execute_announced(
+			RuntimeOrigin::signed(2),
+			multi,
+			hash,
+			call.clone()
+		));
+
+		// The operation is gone once executed.
+		assert_noop!(
+			Multisig::execute_announced(RuntimeOrigin::signed(2), multi, hash, call),
+			Error::<Test>::NotFound,
+		);
+		assert_noop!(
+			Multisig::cancel_announcement(RuntimeOrigin::signed(2), 1, vec![1, 3], hash),
+			Error::<Test>::NotFound,
+		);
+	});
+}
+
+#[test]
+fn announcement_needs_threshold_approvals() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), multi, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multi, 5));
+		let call = call_transfer(6, 10);
+		let hash = blake2_256(&call.encode());
+
+		assert_noop!(
+			Multisig::as_multi_announced(RuntimeOrigin::signed(1), 2, vec![2, 3], None, hash, 3),
+			Error::<Test>::ApprovalsMissing,
+		);
+
+		let timepoint = now();
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			None,
+			hash,
+			Weight::zero(),
+			None,
+			None
+		));
+		assert_noop!(
+			Multisig::execute_announced(RuntimeOrigin::signed(4), multi, hash, call.clone()),
+			Error::<Test>::NotAnnounced,
+		);
+		assert_ok!(Multisig::as_multi_announced(
+			RuntimeOrigin::signed(2),
+			2,
+			vec![1, 3],
+			Some(timepoint),
+			hash,
+			3
+		));
+		assert_eq!(Multisigs::<Test>::get(multi, hash).unwrap().approvals.len(), 2);
+
+		// An announced operation is no longer subject to regular approvals and cancellation.
+		assert_noop!(
+			Multisig::as_multi(
+				RuntimeOrigin::signed(3),
+				2,
+				vec![1, 2],
+				Some(timepoint),
+				call.clone(),
+				Weight::MAX,
+				None,
+				None
+			),
+			Error::<Test>::OperationAnnounced,
+		);
+		assert_noop!(
+			Multisig::as_multi_announced(
+				RuntimeOrigin::signed(3),
+				2,
+				vec![1, 2],
+				Some(timepoint),
+				hash,
+				0
+			),
+			Error::<Test>::OperationAnnounced,
+		);
+		assert_noop!(
+			Multisig::cancel_as_multi(RuntimeOrigin::signed(1), 2, vec![2, 3], timepoint, hash),
+			Error::<Test>::OperationAnnounced,
+		);
+
+		System::set_block_number(4);
+		assert_ok!(Multisig::execute_announced(RuntimeOrigin::signed(4), multi, hash, call));
+		assert_eq!(Balances::free_balance(6), 10);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+fn held_deposit(who: u64) -> u64 {
+	Balances::balance_on_hold(&HoldReason::MultisigDeposit.into(), &who)
+}
+
+#[test]
+fn mixed_deposits_are_returned_on_cancel() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		let hash = blake2_256(&call_transfer(6, 15).encode());
+		let legacy_hash = blake2_256(&call_transfer(6, 10).encode());
+		for hash in [hash, legacy_hash] {
+			assert_ok!(Multisig::approve_as_multi(
+				RuntimeOrigin::signed(1),
+				2,
+				vec![2, 3],
+				None,
+				hash,
+				Weight::zero(),
+				None,
+				None
+			));
+		}
+		assert_ok!(Multisig::unmigrate_deposit(&multi, legacy_hash));
+
+		// Both deposits count as reserved, but only one of them is held.
+		assert_eq!(Balances::reserved_balance(1), 6);
+		assert_eq!(held_deposit(1), 3);
+		assert_eq!(Balances::free_balance(1), 4);
+
+		assert_ok!(Multisig::cancel_as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			now(),
+			legacy_hash
+		));
+		assert_eq!(Balances::reserved_balance(1), 3);
+		assert_eq!(held_deposit(1), 3);
+		assert_eq!(Balances::free_balance(1), 7);
+
+		assert_ok!(Multisig::cancel_as_multi(RuntimeOrigin::signed(1), 2, vec![2, 3], now(), hash));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(held_deposit(1), 0);
+		assert_eq!(Balances::free_balance(1), 10);
+	});
+}
+
+#[test]
+fn migrate_deposit_turns_reserve_into_hold() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		let hash = blake2_256(&call_transfer(6, 15).encode());
+		let legacy_hash = blake2_256(&call_transfer(6, 10).encode());
+		for hash in [hash, legacy_hash] {
+			assert_ok!(Multisig::approve_as_multi(
+				RuntimeOrigin::signed(1),
+				2,
+				vec![2, 3],
+				None,
+				hash,
+				Weight::zero(),
+				None,
+				None
+			));
+		}
+		assert_ok!(Multisig::unmigrate_deposit(&multi, legacy_hash));
+		assert_noop!(
+			Multisig::migrate_deposit(RuntimeOrigin::signed(5), multi, hash),
+			Error::<Test>::DepositAlreadyHeld,
+		);
+		assert_noop!(
+			Multisig::migrate_deposit(RuntimeOrigin::signed(5), multi, [0; 32]),
+			Error::<Test>::NotFound,
+		);
+
+		// Anyone may migrate the deposit, free of charge.
+		let info = Multisig::migrate_deposit(RuntimeOrigin::signed(5), multi, legacy_hash).unwrap();
+		assert_eq!(info.pays_fee, Pays::No);
+		System::assert_last_event(
+			Event::DepositMigrated {
+				multisig: multi,
+				call_hash: legacy_hash,
+				depositor: 1,
+				deposit: 3,
+			}
+			.into(),
+		);
+		assert!(Multisigs::<Test>::get(multi, legacy_hash).unwrap().deposit_held);
+		assert_eq!(Balances::reserved_balance(1), 6);
+		assert_eq!(held_deposit(1), 6);
+		assert_noop!(
+			Multisig::migrate_deposit(RuntimeOrigin::signed(5), multi, legacy_hash),
+			Error::<Test>::DepositAlreadyHeld,
+		);
+
+		// The migrated deposit is released once the operation ends.
+		assert_ok!(Multisig::cancel_as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			now(),
+			legacy_hash
+		));
+		assert_eq!(held_deposit(1), 3);
+		assert_eq!(Balances::free_balance(1), 7);
+	});
+}
+
+fn approve_3_of_3(who: u64, hash: [u8; 32], timepoint: Option<Timepoint<u32>>) {
+	let others = [1, 2, 3].into_iter().filter(|s| *s != who).collect();
+	assert_ok!(Multisig::approve_as_multi(
+		RuntimeOrigin::signed(who),
+		3,
+		others,
+		timepoint,
+		hash,
+		Weight::zero(),
+		None,
+		None
+	));
+}
+
+#[test]
+fn cancel_penalty_only_applies_after_half_of_approvals() {
+	new_test_ext().execute_with(|| {
+		CancelPenalty::set(Perbill::from_percent(50));
+		let hash = blake2_256(&call_transfer(6, 15).encode());
+
+		// One approval out of three required: no penalty.
+		approve_3_of_3(1, hash, None);
+		assert_eq!(held_deposit(1), 4);
+		assert_ok!(Multisig::cancel_as_multi(RuntimeOrigin::signed(1), 3, vec![2, 3], now(), hash));
+		assert_eq!(Balances::free_balance(1), 10);
+		assert_eq!(Balances::free_balance(7), 0);
+		assert!(!System::events().iter().any(|r| matches!(
+			r.event,
+			RuntimeEvent::Multisig(Event::MultisigCancelledWithPenalty { .. })
+		)));
+	});
+}
+
+#[test]
+fn cancel_penalty_at_zero_returns_full_deposit() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 3);
+		let hash = blake2_256(&call_transfer(6, 15).encode());
+		approve_3_of_3(1, hash, None);
+		approve_3_of_3(2, hash, Some(now()));
+
+		assert_ok!(Multisig::cancel_as_multi(RuntimeOrigin::signed(1), 3, vec![2, 3], now(), hash));
+		System::assert_last_event(
+			Event::MultisigCancelled {
+				cancelling: 1,
+				timepoint: now(),
+				multisig: multi,
+				call_hash: hash,
+			}
+			.into(),
+		);
+		assert_eq!(held_deposit(1), 0);
+		assert_eq!(Balances::free_balance(1), 10);
+		assert_eq!(Balances::free_balance(7), 0);
+	});
+}
+
+#[test]
+fn cancel_penalty_is_slashed_to_handler() {
+	for (percent, penalty) in [(50, 2), (100, 4)] {
+		new_test_ext().execute_with(|| {
+			CancelPenalty::set(Perbill::from_percent(percent));
+			let multi = Multisig::multi_account_id(&[1, 2, 3][..], 3);
+			let hash = blake2_256(&call_transfer(6, 15).encode());
+			approve_3_of_3(1, hash, None);
+			approve_3_of_3(2, hash, Some(now()));
+			assert_eq!(held_deposit(1), 4);
+			let issuance = Balances::total_issuance();
+
+			assert_ok!(Multisig::cancel_as_multi(
+				RuntimeOrigin::signed(1),
+				3,
+				vec![2, 3],
+				now(),
+				hash
+			));
+			System::assert_last_event(
+				Event::MultisigCancelledWithPenalty {
+					multisig: multi,
+					call_hash: hash,
+					depositor: 1,
+					penalty,
+				}
+				.into(),
+			);
+			assert_eq!(held_deposit(1), 0);
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::free_balance(1), 10 - penalty);
+			assert_eq!(Balances::free_balance(7), penalty);
+			assert_eq!(Balances::total_issuance(), issuance);
+		});
+	}
+}
+
+#[test]
+fn cancel_penalty_applies_to_reserved_deposits() {
+	new_test_ext().execute_with(|| {
+		CancelPenalty::set(Perbill::from_percent(50));
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 3);
+		let hash = blake2_256(&call_transfer(6, 15).encode());
+		let legacy_hash = blake2_256(&call_transfer(6, 10).encode());
+		for hash in [hash, legacy_hash] {
+			approve_3_of_3(1, hash, None);
+			approve_3_of_3(2, hash, Some(now()));
+		}
+		assert_ok!(Multisig::unmigrate_deposit(&multi, legacy_hash));
+		assert_eq!(Balances::reserved_balance(1), 8);
+		assert_eq!(held_deposit(1), 4);
+
+		// Only the legacy deposit is slashed, the held one stays untouched.
+		assert_ok!(Multisig::cancel_as_multi(
+			RuntimeOrigin::signed(1),
+			3,
+			vec![2, 3],
+			now(),
+			legacy_hash
+		));
+		assert_eq!(Balances::reserved_balance(1), 4);
+		assert_eq!(held_deposit(1), 4);
+		assert_eq!(Balances::free_balance(1), 4);
+		assert_eq!(Balances::free_balance(7), 2);
+
+		// The held deposit is slashed from the hold.
+		assert_ok!(Multisig::cancel_as_multi(RuntimeOrigin::signed(1), 3, vec![2, 3], now(), hash));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 6);
+		assert_eq!(Balances::free_balance(7), 4);
+	});
+}
+
+#[test]
+fn approve_as_multi_capped_records_signatory_cap() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), multi, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multi, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 5));
+
+		let call = call_transfer(6, 15);
+		let call_weight = call.get_dispatch_info().weight;
+		let hash = blake2_256(&call.encode());
+
+		// The cap may neither be below the number of signatories nor above `MaxSignatories`.
+		assert_noop!(
+			Multisig::approve_as_multi_capped(
+				RuntimeOrigin::signed(1),
+				2,
+				vec![2, 3],
+				hash,
+				Weight::zero(),
+				None,
+				2
+			),
+			Error::<Test>::TooManySignatories,
+		);
+		assert_noop!(
+			Multisig::approve_as_multi_capped(
+				RuntimeOrigin::signed(1),
+				2,
+				vec![2, 3],
+				hash,
+				Weight::zero(),
+				None,
+				4
+			),
+			Error::<Test>::TooManySignatories,
+		);
+
+		assert_ok!(Multisig::approve_as_multi_capped(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			hash,
+			Weight::zero(),
+			None,
+			3
+		));
+		assert_eq!(Multisigs::<Test>::get(multi, hash).unwrap().max_signatories_override, Some(3));
+
+		// The operation is already underway.
+		assert_noop!(
+			Multisig::approve_as_multi_capped(
+				RuntimeOrigin::signed(2),
+				2,
+				vec![1, 3],
+				hash,
+				Weight::zero(),
+				None,
+				3
+			),
+			Error::<Test>::UnexpectedTimepoint,
+		);
+
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			2,
+			vec![1, 3],
+			Some(now()),
+			call,
+			call_weight,
+			None,
+			None
+		));
+		assert_eq!(Balances::free_balance(6), 15);
+	});
+}
+
+#[test]
+fn migrations_from_v1_upgrade_to_the_latest_layout() {
+	use frame_support::{
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 3);
+		let hash = blake2_256(&call_transfer(6, 15).encode());
+		let key = Multisigs::<Test>::hashed_key_for(multi, hash);
+
+		// The layout before the `metadata` field was added.
+		let when = Timepoint { height: 4u32, index: 1 };
+		unhashed::put_raw(&key, &(when, 3u64, 1u64, vec![1u64, 2]).encode());
+		StorageVersion::new(1).put::<Multisig>();
+
+		// Each step writes its own layout and version, so that the next step applies.
+		migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(Multisig::on_chain_storage_version(), 2);
+		migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+		assert_eq!(Multisig::on_chain_storage_version(), 3);
+		migrations::v4::MigrateToV4::<Test>::on_runtime_upgrade();
+		assert_eq!(Multisig::on_chain_storage_version(), 4);
+		migrations::v5::MigrateToV5::<Test>::on_runtime_upgrade();
+		assert_eq!(Multisig::on_chain_storage_version(), 5);
+		migrations::v6::MigrateToV6::<Test>::on_runtime_upgrade();
+		assert_eq!(Multisig::on_chain_storage_version(), 6);
+		migrations::v7::MigrateToV7::<Test>::on_runtime_upgrade();
+		assert_eq!(Multisig::on_chain_storage_version(), 7);
+
+		let details = Multisig::operation_details(multi, hash).unwrap();
+		assert_eq!(details.when, when);
+		assert_eq!((details.depositor, details.deposit), (1, 3));
+		assert_eq!(details.approvals, vec![(1, 4, None), (2, 4, None)]);
+		let m = Multisigs::<Test>::get(multi, hash).unwrap();
+		assert_eq!((m.metadata, m.paused_until, m.execute_after), (None, None, None));
+		assert!(!m.deposit_held);
+		assert_eq!(m.max_signatories_override, None);
+	});
+}
+
+#[test]
+fn migration_to_v6_reencodes_multisigs() {
+	use frame_support::{
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		let hash = blake2_256(&call_transfer(6, 15).encode());
+		let key = Multisigs::<Test>::hashed_key_for(multi, hash);
+
+		let old = migrations::v6::OldMultisig::<u32, u64, u64, ConstU32<3>> {
+			when: Timepoint { height: 1, index: 2 },
+			deposit: 3,
+			depositor: 1,
+			approvals: vec![1].try_into().unwrap(),
+			metadata: None,
+			paused_until: Some(5),
+			execute_after: None,
+			deposit_held: true,
+		};
+		#[rustfmt::skip]
+		let encoded = vec![
+			1, 0, 0, 0, 2, 0, 0, 0, // when
+			3, 0, 0, 0, 0, 0, 0, 0, // deposit
+			1, 0, 0, 0, 0, 0, 0, 0, // depositor
+			4, 1, 0, 0, 0, 0, 0, 0, 0, // approvals
+			0, // metadata
+			1, 5, 0, 0, 0, // paused_until
+			0, // execute_after
+			1, // deposit_held
+		];
+		assert_eq!(old.encode(), encoded);
+		unhashed::put_raw(&key, &encoded);
+		StorageVersion::new(5).put::<Multisig>();
+
+		migrations::v6::MigrateToV6::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Multisig::on_chain_storage_version(), 6);
+		assert_eq!(unhashed::get_raw(&key), Some([&encoded[..], &[0]].concat()));
+		let m = migrations::v7::OldMultisig::<u32, u64, u64, ConstU32<3>>::decode(
+			&mut &unhashed::get_raw(&key).unwrap()[..],
+		)
+		.unwrap();
+		assert_eq!(m.when, Timepoint { height: 1, index: 2 });
+		assert_eq!((m.deposit, m.depositor, m.approvals.into_inner()), (3, 1, vec![1]));
+		assert_eq!((m.paused_until, m.execute_after), (Some(5), None));
+		assert!(m.deposit_held);
+		assert_eq!(m.max_signatories_override, None);
+
+		// The migration only runs once.
+		unhashed::put_raw(&key, &encoded);
+		migrations::v6::MigrateToV6::<Test>::on_runtime_upgrade();
+		assert_eq!(unhashed::get_raw(&key), Some(encoded));
+	});
+}
+
+#[test]
+fn migration_to_v6_refunds_multisigs_over_the_signatory_bound() {
+	use frame_support::{
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, ReservableCurrency, StorageVersion},
+	};
+
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3, 4][..], 4);
+		let hash = blake2_256(&call_transfer(6, 15).encode());
+		let key = Multisigs::<Test>::hashed_key_for(multi, hash);
+
+		// Opened while `MaxSignatories` was larger than the current bound of 3.
+		let old = migrations::v6::OldMultisig::<u32, u64, u64, ConstU32<4>> {
+			when: Timepoint { height: 1, index: 0 },
+			deposit: 3,
+			depositor: 1,
+			approvals: vec![1, 2, 3, 4].try_into().unwrap(),
+			metadata: None,
+			paused_until: None,
+			execute_after: None,
+			deposit_held: false,
+		};
+		assert_ok!(Balances::reserve(&1, 3));
+		unhashed::put_raw(&key, &old.encode());
+		StorageVersion::new(5).put::<Multisig>();
+
+		migrations::v6::MigrateToV6::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Multisig::on_chain_storage_version(), 6);
+		assert!(unhashed::get_raw(&key).is_none());
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn migration_to_v7_records_approvals_at_opening_block() {
+	use frame_support::{
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 3);
+		let hash = blake2_256(&call_transfer(6, 15).encode());
+		let key = Multisigs::<Test>::hashed_key_for(multi, hash);
+
+		let old = migrations::v7::OldMultisig::<u32, u64, u64, ConstU32<3>> {
+			when: Timepoint { height: 4, index: 1 },
+			deposit: 4,
+			depositor: 1,
+			approvals: vec![1, 3].try_into().unwrap(),
+			metadata: None,
+			paused_until: None,
+			execute_after: None,
+			deposit_held: true,
+			max_signatories_override: Some(3),
+		};
+		unhashed::put_raw(&key, &old.encode());
+		StorageVersion::new(6).put::<Multisig>();
+
+		migrations::v7::MigrateToV7::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Multisig::on_chain_storage_version(), 7);
+		let details = Multisig::operation_details(multi, hash).unwrap();
+		assert_eq!(details.when, Timepoint { height: 4, index: 1 });
+		assert_eq!((details.depositor, details.deposit), (1, 4));
+		assert_eq!(details.approvals, vec![(1, 4, None), (3, 4, None)]);
+		assert_eq!(Multisigs::<Test>::get(multi, hash).unwrap().max_signatories_override, Some(3));
+
+		// The migration only runs once.
+		let migrated = unhashed::get_raw(&key);
+		migrations::v7::MigrateToV7::<Test>::on_runtime_upgrade();
+		assert_eq!(unhashed::get_raw(&key), migrated);
+	});
+}
+
+#[test]
+fn approvals_record_their_block_and_note() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 3);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), multi, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multi, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 5));
+
+		let call = call_transfer(6, 15);
+		let call_weight = call.get_dispatch_info().weight;
+		let hash = blake2_256(&call.encode());
+		let timepoint = now();
+		let note = H256::repeat_byte(1);
+		assert_eq!(Multisig::operation_details(multi, hash), None);
+
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(2),
+			3,
+			vec![1, 3],
+			None,
+			hash,
+			Weight::zero(),
+			None,
+			Some(note)
+		));
+
+		System::set_block_number(4);
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			3,
+			vec![2, 3],
+			Some(timepoint),
+			hash,
+			Weight::zero(),
+			None,
+			None
+		));
+		let details = Multisig::operation_details(multi, hash).unwrap();
+		assert_eq!(details.when, timepoint);
+		assert_eq!((details.depositor, details.deposit), (2, 4));
+		// Approvals are sorted by account, not by the block they were given at.
+		assert_eq!(details.approvals, vec![(1, 4, None), (2, 1, Some(note))]);
+
+		// A repeated approval does not overwrite the recorded one.
+		System::set_block_number(5);
+		assert_noop!(
+			Multisig::approve_as_multi(
+				RuntimeOrigin::signed(1),
+				3,
+				vec![2, 3],
+				Some(timepoint),
+				hash,
+				Weight::zero(),
+				None,
+				Some(note)
+			),
+			Error::<Test>::AlreadyApproved,
+		);
+
+		System::set_block_number(6);
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(3),
+			3,
+			vec![1, 2],
+			Some(timepoint),
+			call,
+			call_weight,
+			None,
+			Some(note)
+		));
+		assert_eq!(Balances::free_balance(6), 15);
+		assert_eq!(Multisig::operation_details(multi, hash), None);
+	});
+}
+
+#[test]
+fn operation_details_include_metadata_and_pause() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 3);
+		let hash = blake2_256(&call_transfer(6, 15).encode());
+
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(1),
+			3,
+			vec![2, 3],
+			None,
+			hash,
+			Weight::zero(),
+			Some(metadata(3)),
+			None
+		));
+		let details = Multisig::operation_details(multi, hash).unwrap();
+		assert_eq!(details.metadata, Some(metadata(3)));
+		assert_eq!(details.paused_until, None);
+
+		assert_ok!(pause(multi, hash));
+		let details = Multisig::operation_details(multi, hash).unwrap();
+		assert_eq!(details.paused_until, Some(11));
+	});
+}
+
+#[test]
+fn exempt_call_is_opened_and_executed_without_deposit() {
+	new_test_ext().execute_with(|| {
+		RemarksExempt::set(true);
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		let call: Box<RuntimeCall> =
+			Box::new(frame_system::Call::remark { remark: vec![1, 2, 3] }.into());
+		let call_len = call.encoded_size() as u32;
+		let hash = blake2_256(&call.encode());
+
+		// Threshold and timepoint are enforced as usual.
+		assert_noop!(
+			Multisig::as_multi(
+				RuntimeOrigin::signed(1),
+				2,
+				vec![2, 3],
+				Some(now()),
+				call.clone(),
+				Weight::zero(),
+				None,
+				None
+			),
+			Error::<Test>::UnexpectedTimepoint,
+		);
+		let post_info = Multisig::as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			None,
+			call.clone(),
+			Weight::zero(),
+			None,
+			None,
+		)
+		.unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<() as WeightInfo>::as_multi_create_exempt(2, call_len))
+		);
+		assert_eq!(Multisigs::<Test>::get(multi, hash).unwrap().deposit, 0);
+		assert_eq!(held_deposit(1), 0);
+		assert_eq!(Balances::free_balance(1), 10);
+
+		assert_noop!(
+			Multisig::as_multi(
+				RuntimeOrigin::signed(2),
+				2,
+				vec![1, 3],
+				None,
+				call.clone(),
+				Weight::zero(),
+				None,
+				None
+			),
+			Error::<Test>::NoTimepoint,
+		);
+		let call_weight = call.get_dispatch_info().weight;
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			2,
+			vec![1, 3],
+			Some(now()),
+			call,
+			call_weight,
+			None,
+			None
+		));
+		assert!(!Multisigs::<Test>::contains_key(multi, hash));
+		assert_eq!(Balances::free_balance(1), 10);
+	});
+}
+
+#[test]
+fn only_exempt_calls_skip_the_deposit() {
+	new_test_ext().execute_with(|| {
+		RemarksExempt::set(true);
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+
+		// Calls not matched by `DepositExempt` take a deposit.
+		let call: Box<RuntimeCall> =
+			Box::new(frame_system::Call::remark_with_event { remark: vec![1] }.into());
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![2, 3],
+			None,
+			call,
+			Weight::zero(),
+			None,
+			None
+		));
+		assert_eq!(held_deposit(1), 3);
+
+		// Only the hash of the call is known to `approve_as_multi`, so it takes a deposit.
+		let remark = frame_system::Call::<Test>::remark { remark: vec![1] };
+		let hash = blake2_256(&RuntimeCall::from(remark).encode());
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(2),
+			2,
+			vec![1, 3],
+			None,
+			hash,
+			Weight::zero(),
+			None,
+			None
+		));
+		assert_eq!(held_deposit(2), 3);
+		assert_eq!(Multisigs::<Test>::get(multi, hash).unwrap().deposit, 3);
+	});
+}
+
+#[test]
+fn any_signatory_may_cancel_exempt_operation() {
+	new_test_ext().execute_with(|| {
+		RemarksExempt::set(true);
+		CancelPenalty::set(Perbill::from_percent(50));
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 3);
+		let call: Box<RuntimeCall> =
+			Box::new(frame_system::Call::remark { remark: vec![1] }.into());
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(1),
+			3,
+			vec![2, 3],
+			None,
+			call,
+			Weight::zero(),
+			None,
+			None
+		));
+		assert_ok!(Multisig::approve_as_multi(
+			RuntimeOrigin::signed(2),
+			3,
+			vec![1, 3],
+			Some(now()),
+			hash,
+			Weight::zero(),
+			None,
+			None
+		));
+
+		// There is no deposit to migrate either.
+		assert_noop!(
+			Multisig::migrate_deposit(RuntimeOrigin::signed(5), multi, hash),
+			Error::<Test>::DepositAlreadyHeld,
+		);
+
+		assert_ok!(Multisig::cancel_as_multi(RuntimeOrigin::signed(3), 3, vec![1, 2], now(), hash));
+		assert!(!Multisigs::<Test>::contains_key(multi, hash));
+		// Nothing is refunded or forfeited.
+		System::assert_last_event(
+			Event::MultisigCancelled {
+				cancelling: 3,
+				timepoint: now(),
+				multisig: multi,
+				call_hash: hash,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(7), 0);
+		assert_eq!((Balances::free_balance(1), held_deposit(1)), (10, 0));
+	});
+}
+
+#[test]
+fn compute_multi_account_matches_dispatched_multisig() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::compute_multi_account(2, vec![1, 3], 2).unwrap();
+
+		let call = call_transfer(6, 15);
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			2,
+			vec![1, 3],
+			None,
+			call,
+			Weight::zero(),
+			None,
+			None
+		));
+		System::assert_has_event(
+			Event::NewMultisig {
+				approving: 2,
+				multisig: multi,
+				call_hash: hash,
+				metadata_hash: None,
+			}
+			.into(),
+		);
+		assert!(Multisigs::<Test>::contains_key(multi, hash));
+	});
+}
+
+#[test]
+fn compute_multi_account_validates_like_dispatchables() {
+	new_test_ext().execute_with(|| {
+		let as_multi = |who: u64, other_signatories: Vec<u64>| {
+			Multisig::as_multi(
+				RuntimeOrigin::signed(who),
+				2,
+				other_signatories,
+				None,
+				call_transfer(6, 15),
+				Weight::zero(),
+				None,
+				None,
+			)
+			.map_err(|e| e.error)
+		};
+		let cases: Vec<(u64, Vec<u64>, DispatchError)> = vec![
+			(1, vec![], Error::<Test>::TooFewSignatories.into()),
+			(1, vec![2, 3, 4], Error::<Test>::TooManySignatories.into()),
+			(1, vec![3, 2], Error::<Test>::SignatoriesOutOfOrderAt(1).into()),
+			(1, vec![2, 2], Error::<Test>::SignatoriesOutOfOrderAt(1).into()),
+			(2, vec![1, 2], Error::<Test>::SenderInSignatories.into()),
+		];
+		for (who, other_signatories, error) in cases {
+			assert_eq!(
+				Multisig::compute_multi_account(who, other_signatories.clone(), 2),
+				Err(error)
+			);
+			assert_noop!(as_multi(who, other_signatories), error);
+		}
+		assert_eq!(
+			Multisig::compute_multi_account(1, vec![2, 3], 0),
+			Err(Error::<Test>::MinimumThreshold.into())
+		);
+
+		assert_eq!(Multisig::constants(), (1, 1, 3));
+	});
+}
+
+#[test]
+fn compute_multi_account_only_derives_accounts_the_pallet_accepts() {
+	new_test_ext().execute_with(|| {
+		let call = call_transfer(6, 15);
+		let hash = blake2_256(&call.encode());
+		let as_multi = |call: RuntimeCall| {
+			Multisig::as_multi(
+				RuntimeOrigin::signed(1),
+				2,
+				vec![3, 2],
+				None,
+				call,
+				Weight::zero(),
+				None,
+				None,
+			)
+		};
+
+		// A strict pallet rejects unsorted signatories, so no account is derived for them.
+		assert_eq!(
+			Multisig::compute_multi_account(1, vec![3, 2], 2),
+			Err(Error::<Test>::SignatoriesOutOfOrderAt(1).into())
+		);
+		assert_noop!(as_multi(call.clone()), Error::<Test>::SignatoriesOutOfOrderAt(1));
+
+		// A lenient pallet sorts them, and so does the derivation.
+		LenientSignatoryOrder::set(true);
+		let multi = Multisig::compute_multi_account(1, vec![3, 2], 2).unwrap();
+		assert_ok!(as_multi(call));
+		assert!(Multisigs::<Test>::contains_key(multi, hash));
+		LenientSignatoryOrder::set(false);
+	});
+}
+
+/// The pallet and call index of `call`, as matched by call filters.
+fn call_filter_entry(call: &RuntimeCall) -> (PalletIndex, Option<CallIndex>) {
+	let encoded = call.encode();
+	(encoded[0], Some(encoded[1]))
+}
+
+fn call_filter(entries: Vec<(PalletIndex, Option<CallIndex>)>) -> CallFilter {
+	entries.try_into().unwrap()
+}
+
+/// Open an operation of the 2 of 3 multisig of 1, 2 and 3 for `call`, approved by 1.
+fn open_operation(call: &RuntimeCall) -> [u8; 32] {
+	let hash = blake2_256(&call.encode());
+	assert_ok!(Multisig::approve_as_multi(
+		RuntimeOrigin::signed(1),
+		2,
+		vec![2, 3],
+		None,
+		hash,
+		Weight::zero(),
+		None,
+		None
+	));
+	hash
+}
+
+/// Approve the operation for `call` opened by [`open_operation`] by 2, dispatching it.
+fn complete_operation(call: Box<RuntimeCall>) -> DispatchResultWithPostInfo {
+	let call_weight = call.get_dispatch_info().weight;
+	Multisig::as_multi(
+		RuntimeOrigin::signed(2),
+		2,
+		vec![1, 3],
+		Some(now()),
+		call,
+		call_weight,
+		None,
+		None,
+	)
+}
+
+#[test]
+fn call_filter_restricts_dispatched_calls() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 10));
+
+		let allowed = Box::new(RuntimeCall::Balances(BalancesCall::transfer_keep_alive {
+			dest: 6,
+			value: 5,
+		}));
+		let filter = call_filter(vec![call_filter_entry(&allowed)]);
+		assert_ok!(Multisig::set_call_filter(RuntimeOrigin::signed(multi), filter.clone()));
+		System::assert_last_event(
+			pallet_multisig::Event::CallFilterSet { multisig: multi, filter }.into(),
+		);
+
+		// Another call of the same pallet is rejected, leaving the operation open.
+		let forbidden = call_transfer(6, 5);
+		let hash = open_operation(&forbidden);
+		assert_noop!(complete_operation(forbidden), Error::<Test>::CallFiltered);
+		assert!(Multisigs::<Test>::contains_key(multi, hash));
+		assert_ok!(Multisig::cancel_as_multi(RuntimeOrigin::signed(1), 2, vec![2, 3], now(), hash));
+		assert_eq!(Balances::free_balance(1), 10);
+
+		open_operation(&allowed);
+		assert_ok!(complete_operation(allowed));
+		assert_eq!(Balances::free_balance(6), 5);
+
+		// Other multisig accounts are not restricted.
+		let other = Multisig::multi_account_id(&[1, 2, 3][..], 1);
+		assert!(Multisig::call_allowed(&other, &call_transfer(6, 5)));
+	});
+}
+
+#[test]
+fn call_filter_applies_to_every_dispatch_path() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 1);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 10));
+		let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let filter = call_filter(vec![call_filter_entry(&remark)]);
+		assert_ok!(Multisig::set_call_filter(RuntimeOrigin::signed(multi), filter));
+
+		assert_noop!(
+			Multisig::as_multi_threshold_1(
+				RuntimeOrigin::signed(1),
+				vec![2, 3],
+				call_transfer(6, 5)
+			),
+			Error::<Test>::CallFiltered,
+		);
+
+		let call = call_transfer(6, 5);
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::as_multi_announced(
+			RuntimeOrigin::signed(1),
+			1,
+			vec![2, 3],
+			None,
+			hash,
+			0
+		));
+		assert_noop!(
+			Multisig::execute_announced(RuntimeOrigin::signed(4), multi, hash, call),
+			Error::<Test>::CallFiltered,
+		);
+
+		assert_ok!(Multisig::as_multi_threshold_1(
+			RuntimeOrigin::signed(1),
+			vec![2, 3],
+			Box::new(remark)
+		));
+	});
+}
+
+#[test]
+fn call_filter_only_inspects_the_outermost_call() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 10));
+		let (balances, _) = call_filter_entry(&call_transfer(6, 5));
+		let filter = call_filter(vec![(balances, None)]);
+		assert_ok!(Multisig::set_call_filter(RuntimeOrigin::signed(multi), filter));
+
+		// A batch is rejected even though all of its calls are allowed.
+		let batch = |calls: Vec<RuntimeCall>| {
+			Box::new(RuntimeCall::Utility(pallet_utility::Call::batch { calls }))
+		};
+		let allowed_batch = batch(vec![*call_transfer(6, 5)]);
+		open_operation(&allowed_batch);
+		assert_noop!(complete_operation(allowed_batch), Error::<Test>::CallFiltered);
+
+		// Allowing the batch allows any call within it.
+		let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let forbidden_batch = batch(vec![remark.clone()]);
+		let filter = call_filter(vec![(balances, None), call_filter_entry(&forbidden_batch)]);
+		assert_ok!(Multisig::set_call_filter(RuntimeOrigin::signed(multi), filter));
+		assert!(!Multisig::call_allowed(&multi, &remark));
+		open_operation(&forbidden_batch);
+		assert_ok!(complete_operation(forbidden_batch));
+		System::assert_has_event(pallet_utility::Event::BatchCompleted.into());
+	});
+}
+
+#[test]
+fn call_filter_can_always_be_cleared() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		let filter = call_filter(vec![]);
+		assert_ok!(Multisig::set_call_filter(RuntimeOrigin::signed(multi), filter));
+		assert!(!Multisig::call_allowed(&multi, &call_transfer(6, 5)));
+
+		let clear = Box::new(RuntimeCall::Multisig(Call::clear_call_filter {}));
+		open_operation(&clear);
+		assert_ok!(complete_operation(clear));
+		assert!(!AccountCallFilter::<Test>::contains_key(multi));
+		System::assert_has_event(
+			pallet_multisig::Event::CallFilterCleared { multisig: multi }.into(),
+		);
+		assert!(Multisig::call_allowed(&multi, &call_transfer(6, 5)));
+	});
+}
+
+#[test]
+fn unsorted_signatories_are_rejected_by_default() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Multisig::as_multi(
+				RuntimeOrigin::signed(1),
+				2,
+				vec![3, 2],
+				None,
+				call_transfer(6, 15),
+				Weight::zero(),
+				None,
+				None
+			),
+			Error::<Test>::SignatoriesOutOfOrderAt(1),
+		);
+		assert_noop!(
+			Multisig::approve_as_multi(
+				RuntimeOrigin::signed(1),
+				2,
+				vec![3, 2],
+				None,
+				[0; 32],
+				Weight::zero(),
+				None,
+				None
+			),
+			Error::<Test>::SignatoriesOutOfOrderAt(1),
+		);
+	});
+}
+
+#[test]
+fn unsorted_signatories_are_sorted_if_lenient() {
+	new_test_ext().execute_with(|| {
+		LenientSignatoryOrder::set(true);
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), multi, 15));
+
+		let call = call_transfer(6, 15);
+		let call_weight = call.get_dispatch_info().weight;
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(1),
+			2,
+			vec![3, 2],
+			None,
+			call.clone(),
+			Weight::zero(),
+			None,
+			None
+		));
+		// Sorted and unsorted signatories refer to the same multisig.
+		assert!(Multisigs::<Test>::contains_key(multi, blake2_256(&call.encode())));
+
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			2,
+			vec![1, 3],
+			Some(now()),
+			call,
+			call_weight,
+			None,
+			None
+		));
+		assert_eq!(Balances::free_balance(6), 15);
+
+		// Duplicates are rejected nevertheless.
+		assert_noop!(
+			Multisig::as_multi(
+				RuntimeOrigin::signed(1),
+				2,
+				vec![3, 3],
+				None,
+				call_transfer(6, 15),
+				Weight::zero(),
+				None,
+				None
+			),
+			Error::<Test>::SignatoriesOutOfOrderAt(1),
+		);
+	});
+}
+
+#[test]
+fn signatories_out_of_order_report_the_first_offending_index() {
+	new_test_ext().execute_with(|| {
+		let out_of_order_at =
+			|index| Err(DispatchError::from(Error::<Test>::SignatoriesOutOfOrderAt(index)));
+		for lenient in [false, true] {
+			LenientSignatoryOrder::set(lenient);
+			assert_eq!(Multisig::ensure_sorted_and_insert(vec![2, 4, 4, 5], 1), out_of_order_at(2));
+			assert_eq!(Multisig::ensure_sorted_and_insert(vec![2, 4, 5, 2], 1), out_of_order_at(3));
+		}
+
+		LenientSignatoryOrder::set(false);
+		assert_eq!(Multisig::ensure_sorted_and_insert(vec![2, 5, 4, 6], 1), out_of_order_at(2));
+		assert_eq!(Multisig::ensure_sorted_and_insert(vec![5, 2], 1), out_of_order_at(1));
+
+		LenientSignatoryOrder::set(true);
+		assert_eq!(
+			Multisig::ensure_sorted_and_insert(vec![2, 5, 4, 6], 3),
+			Ok(vec![2, 3, 4, 5, 6])
+		);
+		assert_eq!(
+			Multisig::ensure_sorted_and_insert(vec![5, 2], 2),
+			Err(Error::<Test>::SenderInSignatories.into())
+		);
+	});
+}
@@ -3673,7 +3673,11 @@ mod withdraw_unbonded {
 				);
 				assert_eq!(
 					balances_events_since_last_call(),
-					vec![BEvent::Burned { who: default_bonded_account(), amount: 300 }]
+					vec![BEvent::Burned {
+						who: default_bonded_account(),
+						amount: 300,
+						context: None
+					}]
 				);
 
 				// When
@@ -3789,7 +3793,11 @@ mod withdraw_unbonded {
 				);
 				assert_eq!(
 					balances_events_since_last_call(),
-					vec![BEvent::Burned { who: default_bonded_account(), amount: 300 },]
+					vec![BEvent::Burned {
+						who: default_bonded_account(),
+						amount: 300,
+						context: None
+					},]
 				);
 
 				CurrentEra::set(StakingMock::bonding_duration());
@@ -39,6 +39,7 @@ impl frame_system::Config for Runtime {
 impl pallet_balances::Config for Runtime {
 	type ReserveIdentifier = [u8; 8];
 	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
 }
 
 #[docify::export]
@@ -41,6 +41,7 @@ impl frame_system::Config for Runtime {
 impl pallet_balances::Config for Runtime {
 	type ReserveIdentifier = [u8; 8];
 	type AccountStore = System;
+	type AuthoritativeStore = pallet_balances::SystemStore;
 }
 
 #[dynamic_params(RuntimeParametersRenamed, pallet_parameters::Parameters::<Runtime>)]
@@ -118,6 +118,20 @@ impl<T: Config> Pallet<T> {
 			}
 		})
 	}
+
+	/// Check the trie nodes of a membership proof of `key` against the session data `root`.
+	///
+	/// This allows to check proofs of sessions which have been pruned from
+	/// [`HistoricalSessions`] against roots which have been kept elsewhere.
+	pub fn check_proof_with_root<D: AsRef<[u8]>>(
+		root: T::Hash,
+		key: (KeyTypeId, D),
+		trie_nodes: &[Vec<u8>],
+	) -> Option<IdentificationTuple<T>> {
+		let (id, data) = key;
+		let trie = ProvingTrie::<T>::from_nodes(root, trie_nodes);
+		trie.query(id, data.as_ref())
+	}
 }
 
 impl<T: Config> ValidatorSet<T::AccountId> for Pallet<T> {
@@ -364,8 +378,7 @@ impl<T: Config, D: AsRef<[u8]>> KeyOwnerProofSystem<(KeyTypeId, D)> for Pallet<T
 				return None
 			}
 
-			let trie = ProvingTrie::<T>::from_nodes(root, &proof.trie_nodes);
-			trie.query(id, data.as_ref())
+			Self::check_proof_with_root(root, (id, data), &proof.trie_nodes)
 		}
 	}
 }
@@ -1143,6 +1143,7 @@ mod mock {
 	impl pallet_balances::Config for Test {
 		type ReserveIdentifier = [u8; 8];
 		type AccountStore = System;
+		type AuthoritativeStore = pallet_balances::SystemStore;
 	}
 
 	/// Test only Weights for state migration.
@@ -1255,9 +1256,12 @@ mod mock {
 			frame_system::GenesisConfig::<Test>::default()
 				.assimilate_storage(&mut custom_storage)
 				.unwrap();
-			pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 1000)] }
-				.assimilate_storage(&mut custom_storage)
-				.unwrap();
+			pallet_balances::GenesisConfig::<Test> {
+				balances: vec![(1, 1000)],
+				..Default::default()
+			}
+			.assimilate_storage(&mut custom_storage)
+			.unwrap();
 		}
 
 		sp_tracing::try_init_simple();
@@ -452,3 +452,19 @@ pub trait Balanced<AccountId>: super::Balanced<AccountId> + Unbalanced<AccountId
 
 	fn done_slash(_reason: &Self::Reason, _who: &AccountId, _amount: Self::Balance) {}
 }
+
+/// Handler for funds on hold which were slashed with [`Balanced::slash`].
+///
+/// Lets other pallets observe the slashes of an implementation of [`Balanced`] which is
+/// configured with it.
+pub trait DoneSlash<Reason, AccountId, Balance> {
+	/// Called after `amount` of the funds held for `reason` by `who` were slashed.
+	fn done_slash(reason: &Reason, who: &AccountId, amount: Balance);
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<Reason, AccountId, Balance: Copy> DoneSlash<Reason, AccountId, Balance> for Tuple {
+	fn done_slash(reason: &Reason, who: &AccountId, amount: Balance) {
+		for_tuples!( #( Tuple::done_slash(reason, who, amount); )* );
+	}
+}
@@ -166,7 +166,7 @@ use super::{
 };
 pub use freeze::{Inspect as InspectFreeze, Mutate as MutateFreeze};
 pub use hold::{
-	Balanced as BalancedHold, Inspect as InspectHold, Mutate as MutateHold,
+	Balanced as BalancedHold, DoneSlash, Inspect as InspectHold, Mutate as MutateHold,
 	Unbalanced as UnbalancedHold,
 };
 pub use imbalance::{Credit, Debt, HandleImbalanceDrop, Imbalance};
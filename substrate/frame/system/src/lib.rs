@@ -114,7 +114,7 @@ use sp_runtime::{
 		InvalidTransaction, TransactionLongevity, TransactionSource, TransactionValidity,
 		ValidTransaction,
 	},
-	DispatchError, RuntimeDebug,
+	ArithmeticError, DispatchError, RuntimeDebug,
 };
 #[cfg(any(feature = "std", test))]
 use sp_std::map;
@@ -1687,6 +1687,68 @@ impl<T: Config> Pallet<T> {
 		Self::can_accrue_consumers(who, 1)
 	}
 
+	/// Move `providers` provider and `consumers` consumer references of `from` onto `to`,
+	/// ignoring the `MaxConsumers` limits.
+	///
+	/// The references are added to those `to` already has, creating `to` if it did not exist.
+	/// All other references of `from`, including its sufficient references, stay. `from` is
+	/// reaped if it has no references left. Nonces are not moved.
+	///
+	/// Fails with `NoProviders` if `from` has fewer than `providers` providers or if `to` would
+	/// have consumers but no providers, with `ConsumerRemaining` if `from` would be left with
+	/// consumers but no providers, and with an underflow if `from` has fewer than `consumers`
+	/// consumers.
+	///
+	/// NOTE: This only moves the reference counts, any data the references stand for (including
+	/// the `AccountData` of `from`) must be moved by the caller. Since `from` may be reaped, such
+	/// data should be read beforehand.
+	pub fn transplant_refs(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		providers: RefCount,
+		consumers: RefCount,
+	) -> Result<(), DispatchError> {
+		if from == to || (providers == 0 && consumers == 0) {
+			return Ok(())
+		}
+		let source = Account::<T>::get(from);
+		let dest = Account::<T>::get(to);
+		let remaining_providers =
+			source.providers.checked_sub(providers).ok_or(DispatchError::NoProviders)?;
+		let remaining_consumers =
+			source.consumers.checked_sub(consumers).ok_or(ArithmeticError::Underflow)?;
+		if remaining_providers == 0 && remaining_consumers > 0 {
+			return Err(DispatchError::ConsumerRemaining)
+		}
+		if dest.providers.saturating_add(providers) == 0 && consumers > 0 {
+			return Err(DispatchError::NoProviders)
+		}
+
+		Account::<T>::mutate(to, |a| {
+			let created = a.providers == 0 && a.sufficients == 0;
+			a.providers = a.providers.saturating_add(providers);
+			a.consumers = a.consumers.saturating_add(consumers);
+			if created {
+				Self::on_created_account(to.clone(), a);
+			}
+		});
+
+		let reaped = Account::<T>::mutate_exists(from, |maybe_account| {
+			let Some(account) = maybe_account else { return false };
+			account.providers = remaining_providers;
+			account.consumers = remaining_consumers;
+			if account.providers == 0 && account.sufficients == 0 {
+				*maybe_account = None;
+				return true
+			}
+			false
+		});
+		if reaped {
+			Self::on_killed_account(from.clone());
+		}
+		Ok(())
+	}
+
 	/// Deposits an event into this block's event record.
 	///
 	/// NOTE: Events not registered at the genesis block and quietly omitted.
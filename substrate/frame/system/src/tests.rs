@@ -219,6 +219,45 @@ fn provider_required_to_support_consumer() {
 	});
 }
 
+#[test]
+fn transplant_refs_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(System::transplant_refs(&0, &1, 1, 0), DispatchError::NoProviders);
+
+		assert_eq!(System::inc_providers(&0), IncRefStatus::Created);
+		assert_eq!(System::inc_providers(&0), IncRefStatus::Existed);
+		assert_ok!(System::inc_consumers(&0));
+		assert_ok!(System::inc_consumers(&0));
+		System::inc_account_nonce(&0);
+
+		// the consumers may not outlive the providers on either side.
+		assert_noop!(System::transplant_refs(&0, &1, 2, 1), DispatchError::ConsumerRemaining);
+		assert_noop!(System::transplant_refs(&0, &1, 0, 1), DispatchError::NoProviders);
+		assert_noop!(System::transplant_refs(&0, &1, 1, 3), ArithmeticError::Underflow);
+
+		// only the given references are moved, `0` keeps the others.
+		assert_ok!(System::transplant_refs(&0, &1, 1, 1));
+		assert!(System::account_exists(&0));
+		assert_eq!((System::providers(&0), System::consumers(&0)), (1, 1));
+		assert_eq!((System::providers(&1), System::consumers(&1)), (1, 1));
+		assert_eq!(System::account_nonce(&0), 1u64.into());
+		assert_eq!(System::account_nonce(&1), 0u64.into());
+
+		// `0` is reaped once all of its references are moved.
+		assert_ok!(System::transplant_refs(&0, &1, 1, 1));
+		assert!(!System::account_exists(&0));
+		assert_eq!((System::providers(&1), System::consumers(&1)), (2, 2));
+
+		// sufficient references stay and keep the source alive.
+		assert_eq!(System::inc_sufficients(&1), IncRefStatus::Existed);
+		assert_ok!(System::transplant_refs(&1, &2, 2, 2));
+		assert!(System::account_exists(&1));
+		assert_eq!((System::providers(&1), System::consumers(&1)), (0, 0));
+		assert_eq!(System::sufficients(&1), 1);
+		assert_eq!((System::providers(&2), System::consumers(&2)), (2, 2));
+	});
+}
+
 #[test]
 fn deposit_event_should_work() {
 	new_test_ext().execute_with(|| {
@@ -273,6 +273,9 @@ impl<AuthorityId> ValidatorSet<AuthorityId> {
 /// The index of an authority.
 pub type AuthorityIndex = u32;
 
+/// The index of a session, matching `sp_staking::SessionIndex`.
+pub type SessionIndex = u32;
+
 /// The Hashing used within MMR.
 pub type MmrHashing = Keccak256;
 /// The type used to represent an MMR root hash.
@@ -290,6 +293,13 @@ pub enum ConsensusLog<AuthorityId: Codec> {
 	/// MMR root hash.
 	#[codec(index = 3)]
 	MmrRoot(MmrRootHash),
+	/// BEEFY consensus was (re)started to begin at `genesis_block`, while the validator set
+	/// `validator_set_id` was active.
+	///
+	/// Voters discard their current rounds and wait for the new genesis block. The block number
+	/// is saturated into a `u64`, so that the digest does not depend on the block number type.
+	#[codec(index = 4)]
+	GenesisReset { genesis_block: u64, validator_set_id: ValidatorSetId },
 }
 
 /// BEEFY vote message.
@@ -398,6 +408,35 @@ impl<AuthorityId> OnNewValidatorSet<AuthorityId> for () {
 	fn on_new_validator_set(_: &ValidatorSet<AuthorityId>, _: &ValidatorSet<AuthorityId>) {}
 }
 
+/// New BEEFY validator set notification hook, also told when the new set becomes active.
+///
+/// Implemented for every [OnNewValidatorSet], which ignores the additional data.
+pub trait OnNewValidatorSetExt<AuthorityId, BlockNumber> {
+	/// Function called by the pallet when BEEFY validator set changes.
+	///
+	/// `validator_set` is active from block `activation_block` on, which is part of the session
+	/// `session_index`.
+	fn on_new_validator_set_ext(
+		validator_set: &ValidatorSet<AuthorityId>,
+		next_validator_set: &ValidatorSet<AuthorityId>,
+		session_index: SessionIndex,
+		activation_block: BlockNumber,
+	);
+}
+
+impl<AuthorityId, BlockNumber, T: OnNewValidatorSet<AuthorityId>>
+	OnNewValidatorSetExt<AuthorityId, BlockNumber> for T
+{
+	fn on_new_validator_set_ext(
+		validator_set: &ValidatorSet<AuthorityId>,
+		next_validator_set: &ValidatorSet<AuthorityId>,
+		_session_index: SessionIndex,
+		_activation_block: BlockNumber,
+	) {
+		T::on_new_validator_set(validator_set, next_validator_set)
+	}
+}
+
 /// An opaque type used to represent the key ownership proof at the runtime API
 /// boundary. The inner value is an encoded representation of the actual key
 /// ownership proof which will be parameterized when defining the runtime. At
@@ -51,5 +51,14 @@ sp_api::decl_runtime_apis! {
 			tx: <Block as BlockT>::Extrinsic,
 			block_hash: Block::Hash,
 		) -> TransactionValidity;
+
+		/// Returns the hash identifying the given transaction.
+		///
+		/// The transaction pool hashes transactions with the hashing of the block for runtimes
+		/// implementing an older version of this api. Since the hashing may change with a
+		/// runtime upgrade, the pool asks the runtime at the block against which a transaction
+		/// is considered.
+		#[api_version(4)]
+		fn transaction_hash(tx: <Block as BlockT>::Extrinsic) -> Block::Hash;
 	}
 }
@@ -81,6 +81,7 @@ pub struct ChainState {
 	pub block_by_hash: HashMap<Hash, Block>,
 	pub nonces: HashMap<AccountId, u64>,
 	pub invalid_hashes: HashSet<Hash>,
+	pub invalid_hashes_at: HashMap<Hash, HashSet<Hash>>,
 	pub priorities: HashMap<Hash, u64>,
 }
 
@@ -89,6 +90,7 @@ pub struct TestApi {
 	valid_modifier: RwLock<Box<dyn Fn(&mut ValidTransaction) + Send + Sync>>,
 	chain: RwLock<ChainState>,
 	validation_requests: RwLock<Vec<Extrinsic>>,
+	hashing_upgrade_at: RwLock<Option<BlockNumber>>,
 }
 
 impl TestApi {
@@ -107,6 +109,7 @@ impl TestApi {
 			valid_modifier: RwLock::new(Box::new(|_| {})),
 			chain: Default::default(),
 			validation_requests: RwLock::new(Default::default()),
+			hashing_upgrade_at: RwLock::new(None),
 		};
 
 		// Push genesis block
@@ -207,6 +210,24 @@ impl TestApi {
 		(BlakeTwo256::hash(&encoded), encoded.len())
 	}
 
+	/// Simulate a runtime upgrade changing the hashing of transactions, enacted in the blocks
+	/// from `number` onwards.
+	///
+	/// Blocks before `number` report spec version 1 and hash transactions like
+	/// [`ChainApi::hash_and_length`], later blocks report spec version 2 and hash them
+	/// differently.
+	pub fn upgrade_hashing_at(&self, number: BlockNumber) {
+		*self.hashing_upgrade_at.write() = Some(number);
+	}
+
+	/// Returns whether the block `at` uses the hashing set by [`Self::upgrade_hashing_at`], or
+	/// `None` if no upgrade was set.
+	fn is_hashing_upgraded(&self, at: Hash) -> Option<bool> {
+		let upgrade_at = (*self.hashing_upgrade_at.read())?;
+		let number = self.chain.read().block_by_hash.get(&at).map(|b| *b.header.number())?;
+		Some(number >= upgrade_at)
+	}
+
 	/// Mark some transaction is invalid.
 	///
 	/// Next time transaction pool will try to validate this
@@ -215,6 +236,18 @@ impl TestApi {
 		self.chain.write().invalid_hashes.insert(Self::hash_and_length_inner(xts).0);
 	}
 
+	/// Mark some transaction as invalid when validated at the given block.
+	///
+	/// Validating this extrinsic at any other block is not affected.
+	pub fn add_invalid_at(&self, at: Hash, xts: &Extrinsic) {
+		self.chain
+			.write()
+			.invalid_hashes_at
+			.entry(at)
+			.or_default()
+			.insert(Self::hash_and_length_inner(xts).0);
+	}
+
 	/// Remove a transaction that was previously declared as invalid via `[Self::add_invalid]`.
 	///
 	/// Next time transaction pool will try to validate this
@@ -317,6 +350,16 @@ impl ChainApi for TestApi {
 			return ready(Ok(Err(TransactionValidityError::Invalid(InvalidTransaction::Custom(0)))))
 		}
 
+		if self
+			.chain
+			.read()
+			.invalid_hashes_at
+			.get(&at)
+			.map_or(false, |hashes| hashes.contains(&self.hash_and_length(&uxt).0))
+		{
+			return ready(Ok(Err(TransactionValidityError::Invalid(InvalidTransaction::Custom(0)))))
+		}
+
 		let priority = self.chain.read().priorities.get(&self.hash_and_length(&uxt).0).cloned();
 		let mut validity = ValidTransaction {
 			priority: priority.unwrap_or(1),
@@ -359,6 +402,25 @@ impl ChainApi for TestApi {
 		Self::hash_and_length_inner(ex)
 	}
 
+	fn hash_and_length_at(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		ex: &<Self::Block as BlockT>::Extrinsic,
+	) -> (Hash, usize) {
+		let (hash, length) = Self::hash_and_length_inner(ex);
+		match self.is_hashing_upgraded(at) {
+			Some(true) => (BlakeTwo256::hash(&(b"upgraded", ex).encode()), length),
+			_ => (hash, length),
+		}
+	}
+
+	fn runtime_spec_version(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+	) -> Result<Option<u32>, Error> {
+		Ok(self.is_hashing_upgraded(at).map(|upgraded| if upgraded { 2 } else { 1 }))
+	}
+
 	fn block_body(&self, hash: <Self::Block as BlockT>::Hash) -> Self::BodyFuture {
 		futures::future::ready(Ok(self
 			.chain
@@ -49,7 +49,7 @@ fn testnet_genesis() -> Value {
 		.map(|a| (a.to_account_id(), endowment))
 		.collect::<Vec<_>>();
 	json!({
-		"balances": BalancesConfig { balances },
+		"balances": BalancesConfig { balances, ..Default::default() },
 		"sudo": SudoConfig { key: Some(AccountKeyring::Alice.to_account_id()) },
 	})
 }